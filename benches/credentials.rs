@@ -0,0 +1,41 @@
+//! Credential resolution: environment lookup and shell config parsing
+
+use ccstatus::core::network::CredentialManager;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+const SHELL_CONFIG: &str = r#"
+# ~/.zshrc
+alias ll='ls -la'
+export PATH="$HOME/.cargo/bin:$PATH"
+export EDITOR=vim
+
+cc-env() {
+  local env_vars=(
+    "ANTHROPIC_BASE_URL=https://relay.example.com"
+    "ANTHROPIC_AUTH_TOKEN=sk-test-0123456789"
+  )
+  env "${env_vars[@]}" claude "$@"
+}
+
+export ANTHROPIC_BASE_URL="https://api.anthropic.com"
+export ANTHROPIC_AUTH_TOKEN="sk-ant-test-0123456789"
+"#;
+
+fn bench_credentials(c: &mut Criterion) {
+    std::env::set_var("ANTHROPIC_BASE_URL", "https://api.anthropic.com");
+    std::env::set_var("ANTHROPIC_AUTH_TOKEN", "sk-ant-bench");
+    let manager = CredentialManager::new().unwrap();
+
+    c.bench_function("credentials/environment", |b| {
+        b.iter(|| manager.get_from_environment().unwrap())
+    });
+    c.bench_function("credentials/shell_exports", |b| {
+        b.iter(|| manager.parse_export_statements(SHELL_CONFIG).unwrap())
+    });
+    c.bench_function("credentials/shell_functions", |b| {
+        b.iter(|| manager.parse_function_variables(SHELL_CONFIG).unwrap())
+    });
+}
+
+criterion_group!(benches, bench_credentials);
+criterion_main!(benches);
@@ -0,0 +1,47 @@
+//! Monitoring state load/serialize: the JSON round trip every invocation pays
+
+use ccstatus::core::network::types::{MonitoringSnapshot, NetworkStatus};
+use ccstatus::core::network::HttpMonitor;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn snapshot() -> MonitoringSnapshot {
+    let mut state = MonitoringSnapshot {
+        status: NetworkStatus::Healthy,
+        monitoring_enabled: true,
+        timestamp: "2025-01-25T10:30:00-08:00".to_string(),
+        ..Default::default()
+    };
+    state.network.latency_ms = 1234;
+    state.network.breakdown = "DNS:5ms|TCP:10ms|TLS:15ms|TTFB:1204ms|Total:1234ms".to_string();
+    state.network.last_http_status = 200;
+    state.network.p95_latency_ms = 1900;
+    state.network.rolling_totals = (0..12).map(|i| 1000 + i * 75).collect();
+    state
+}
+
+fn bench_state(c: &mut Criterion) {
+    let state = snapshot();
+    let json = serde_json::to_string_pretty(&state).unwrap();
+
+    c.bench_function("state/serialize", |b| {
+        b.iter(|| serde_json::to_string_pretty(&state).unwrap())
+    });
+    c.bench_function("state/deserialize", |b| {
+        b.iter(|| serde_json::from_str::<MonitoringSnapshot>(&json).unwrap())
+    });
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("ccstatus-monitoring.json");
+    std::fs::write(&path, &json).unwrap();
+    let monitor = HttpMonitor::new(Some(path)).unwrap();
+    c.bench_function("state/load", |b| {
+        b.iter(|| runtime.block_on(monitor.load_state()).unwrap())
+    });
+}
+
+criterion_group!(benches, bench_state);
+criterion_main!(benches);
@@ -0,0 +1,58 @@
+//! Statusline generation: segment rendering and joining for the default theme
+
+use ccstatus::config::{Config, SegmentConfig, SegmentId};
+use ccstatus::core::segments::SegmentData;
+use ccstatus::core::StatusLineGenerator;
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use std::collections::HashMap;
+
+fn segments(config: &Config) -> Vec<(SegmentConfig, SegmentData)> {
+    config
+        .segments
+        .iter()
+        .map(|segment_config| {
+            let primary = match segment_config.id {
+                SegmentId::Model => "Sonnet 4",
+                SegmentId::Directory => "CCstatus",
+                SegmentId::Git => "main ✓",
+                SegmentId::Usage => "42.1% · 84.2k tokens",
+                _ => "🟢 P95:1200ms",
+            };
+            (
+                segment_config.clone(),
+                SegmentData {
+                    primary: primary.to_string(),
+                    secondary: String::new(),
+                    metadata: HashMap::new(),
+                },
+            )
+        })
+        .collect()
+}
+
+fn bench_generate(c: &mut Criterion) {
+    let config = Config::default();
+    let data = segments(&config);
+    let generator = StatusLineGenerator::new(config.clone());
+    c.bench_function("statusline/generate", |b| {
+        b.iter_batched(
+            || data.clone(),
+            |data| generator.generate(data),
+            BatchSize::SmallInput,
+        )
+    });
+
+    let mut templated = config;
+    templated.template = Some("{model} | {directory} {git} | {usage}\n{network}".to_string());
+    let generator = StatusLineGenerator::new(templated);
+    c.bench_function("statusline/generate_template", |b| {
+        b.iter_batched(
+            || data.clone(),
+            |data| generator.generate(data),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group!(benches, bench_generate);
+criterion_main!(benches);
@@ -0,0 +1,441 @@
+//! Fake Anthropic API for end-to-end tests
+//!
+//! A small HTTP/1.1 server on `127.0.0.1` that answers the endpoints the
+//! CCstatus probe stack talks to, so the real client code (isahc or curl,
+//! cookie jar, error classification, proxy health) can be exercised without
+//! network access or tokens:
+//!
+//! - `POST /v1/messages`: a canned message, or the failure set by [`MessagesBehavior`]
+//! - `GET /v1/models`: model list with an API key, `401` without
+//! - `GET /health`: proxy health JSON per [`HealthBehavior`]
+//!
+//! An optional request budget emulates rate limiting: once spent, messages
+//! get `429` with `retry-after` until [`FakeApi::reset_rate_limit`].
+//!
+//! ```no_run
+//! use ccstatus_fake_api::{FakeApi, MessagesBehavior};
+//!
+//! let api = FakeApi::start().unwrap();
+//! api.set_messages(MessagesBehavior::CloudflareChallenge { status: 403 });
+//! // point ANTHROPIC_BASE_URL / ApiCredentials at api.base_url()
+//! ```
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// Seconds advertised in `retry-after` for rate limited requests
+pub const RETRY_AFTER_SECS: u32 = 30;
+
+/// Upper bound on a request body; anything larger is answered with `413`
+const MAX_BODY_LEN: usize = 1024 * 1024;
+
+/// How `POST /v1/messages` answers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MessagesBehavior {
+    /// `200` with a minimal message
+    Ok,
+    /// `200`, sent after the delay
+    Slow(Duration),
+    /// `401 authentication_error`
+    Unauthorized,
+    /// `429 rate_limit_error` with `retry-after`, without Cloudflare markers
+    RateLimited,
+    /// `529 overloaded_error`
+    Overloaded,
+    /// `500 api_error`
+    ServerError,
+    /// Cloudflare managed challenge (`403`, `429` or `503`): CF headers, `__cf_bm` cookie, HTML body
+    CloudflareChallenge { status: u16 },
+    /// Close the connection without answering
+    Hangup,
+}
+
+/// How `GET /health` answers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthBehavior {
+    /// `{"status":"healthy"}`
+    Healthy,
+    /// `{"status":"degraded"}`
+    Degraded,
+    /// `404`, like the official API
+    Missing,
+}
+
+/// A request the server received
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    /// Header names lowercased
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl RecordedRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .get(&name.to_ascii_lowercase())
+            .map(String::as_str)
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    messages: MessagesBehavior,
+    health: HealthBehavior,
+    /// Messages allowed before `429`; `None` = unlimited
+    rate_limit: Option<u32>,
+    served: u32,
+    requests: Vec<RecordedRequest>,
+}
+
+/// Running fake API; stops when dropped
+pub struct FakeApi {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    shutdown: Arc<AtomicBool>,
+    acceptor: Option<JoinHandle<()>>,
+}
+
+impl FakeApi {
+    /// Start on an ephemeral port of `127.0.0.1`
+    pub fn start() -> io::Result<Self> {
+        Self::bind("127.0.0.1:0")
+    }
+
+    /// Start on `addr`; healthy messages and `/health` until changed
+    pub fn bind(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(State {
+            messages: MessagesBehavior::Ok,
+            health: HealthBehavior::Healthy,
+            rate_limit: None,
+            served: 0,
+            requests: Vec::new(),
+        }));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let acceptor = {
+            let state = state.clone();
+            let shutdown = shutdown.clone();
+            std::thread::Builder::new()
+                .name("fake-api-accept".to_string())
+                .spawn(move || accept_loop(listener, state, shutdown))?
+        };
+
+        Ok(Self {
+            addr,
+            state,
+            shutdown,
+            acceptor: Some(acceptor),
+        })
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Base URL to use as `ANTHROPIC_BASE_URL`, e.g. `http://127.0.0.1:41235`
+    pub fn base_url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    pub fn set_messages(&self, behavior: MessagesBehavior) {
+        lock(&self.state).messages = behavior;
+    }
+
+    pub fn set_health(&self, behavior: HealthBehavior) {
+        lock(&self.state).health = behavior;
+    }
+
+    /// Answer `429` once `limit` messages have been served; `None` lifts the limit
+    pub fn set_rate_limit(&self, limit: Option<u32>) {
+        lock(&self.state).rate_limit = limit;
+    }
+
+    /// Start a fresh rate limit window
+    pub fn reset_rate_limit(&self) {
+        lock(&self.state).served = 0;
+    }
+
+    /// Every request received so far, oldest first
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        lock(&self.state).requests.clone()
+    }
+
+    /// Forget the recorded requests, e.g. between rounds of a long run
+    pub fn clear_requests(&self) {
+        lock(&self.state).requests.clear();
+    }
+
+    /// Requests received for `path`
+    pub fn requests_to(&self, path: &str) -> Vec<RecordedRequest> {
+        lock(&self.state)
+            .requests
+            .iter()
+            .filter(|request| request.path == path)
+            .cloned()
+            .collect()
+    }
+
+    /// Block until the server stops (used by the `ccstatus-fake-api` binary)
+    pub fn wait(mut self) {
+        if let Some(acceptor) = self.acceptor.take() {
+            let _ = acceptor.join();
+        }
+    }
+}
+
+impl Drop for FakeApi {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Wake the blocking accept so the loop sees the flag
+        let _ = TcpStream::connect(self.addr);
+        if let Some(acceptor) = self.acceptor.take() {
+            let _ = acceptor.join();
+        }
+    }
+}
+
+fn lock(state: &Mutex<State>) -> MutexGuard<'_, State> {
+    state
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn accept_loop(listener: TcpListener, state: Arc<Mutex<State>>, shutdown: Arc<AtomicBool>) {
+    for stream in listener.incoming() {
+        if shutdown.load(Ordering::SeqCst) {
+            break;
+        }
+        let Ok(stream) = stream else {
+            continue;
+        };
+        let state = state.clone();
+        std::thread::spawn(move || {
+            let _ = handle_connection(stream, &state);
+        });
+    }
+}
+
+/// Serve one request, then close the connection
+fn handle_connection(stream: TcpStream, state: &Mutex<State>) -> io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(10)))?;
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Ok(());
+    };
+    let method = method.to_string();
+    let path = target.split('?').next().unwrap_or(target).to_string();
+
+    let mut headers = HashMap::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+        }
+    }
+
+    let content_length: usize = headers
+        .get("content-length")
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(0);
+    if content_length > MAX_BODY_LEN {
+        return Response::json(413, r#"{"type":"error","error":{"type":"request_too_large","message":"Request too large"}}"#)
+            .write_to(&mut writer);
+    }
+    if headers
+        .get("expect")
+        .is_some_and(|value| value.eq_ignore_ascii_case("100-continue"))
+    {
+        writer.write_all(b"HTTP/1.1 100 Continue\r\n\r\n")?;
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    let request = RecordedRequest {
+        method,
+        path,
+        headers,
+        body,
+    };
+    let response = respond(state, &request);
+    lock(state).requests.push(request);
+
+    match response {
+        Some(response) => response.write_to(&mut writer),
+        None => Ok(()),
+    }
+}
+
+/// Response for `request`; `None` hangs up
+fn respond(state: &Mutex<State>, request: &RecordedRequest) -> Option<Response> {
+    let has_key = request.header("x-api-key").is_some()
+        || request
+            .header("authorization")
+            .is_some_and(|value| value.starts_with("Bearer "));
+
+    match (request.method.as_str(), request.path.as_str()) {
+        ("POST", "/v1/messages") => {
+            if !has_key {
+                return Some(error(
+                    401,
+                    "authentication_error",
+                    "x-api-key header is required",
+                ));
+            }
+            let behavior = {
+                let mut state = lock(state);
+                if state.rate_limit.is_some_and(|limit| state.served >= limit) {
+                    return Some(
+                        error(
+                            429,
+                            "rate_limit_error",
+                            "Number of requests has exceeded your rate limit",
+                        )
+                        .header("retry-after", RETRY_AFTER_SECS.to_string())
+                        .header("anthropic-ratelimit-requests-remaining", "0"),
+                    );
+                }
+                state.served += 1;
+                state.messages.clone()
+            };
+            messages_response(behavior)
+        }
+        ("GET", "/v1/models") if has_key => Some(Response::json(
+            200,
+            r#"{"data":[{"type":"model","id":"claude-3-5-haiku-20241022","display_name":"Claude Haiku 3.5"}],"has_more":false}"#,
+        )),
+        ("GET", "/v1/models") => Some(error(
+            401,
+            "authentication_error",
+            "x-api-key header is required",
+        )),
+        ("GET", "/health") => Some(match lock(state).health {
+            HealthBehavior::Healthy => Response::json(200, r#"{"status":"healthy"}"#),
+            HealthBehavior::Degraded => Response::json(200, r#"{"status":"degraded"}"#),
+            HealthBehavior::Missing => error(404, "not_found_error", "Not found"),
+        }),
+        _ => Some(error(404, "not_found_error", "Not found")),
+    }
+}
+
+fn messages_response(behavior: MessagesBehavior) -> Option<Response> {
+    let message = r#"{"id":"msg_fake","type":"message","role":"assistant","model":"claude-3-5-haiku-20241022","content":[{"type":"text","text":"ok"}],"stop_reason":"end_turn","usage":{"input_tokens":8,"output_tokens":1}}"#;
+    match behavior {
+        MessagesBehavior::Ok => Some(Response::json(200, message)),
+        MessagesBehavior::Slow(delay) => {
+            std::thread::sleep(delay);
+            Some(Response::json(200, message))
+        }
+        MessagesBehavior::Unauthorized => {
+            Some(error(401, "authentication_error", "invalid x-api-key"))
+        }
+        MessagesBehavior::RateLimited => Some(
+            error(
+                429,
+                "rate_limit_error",
+                "Number of requests has exceeded your rate limit",
+            )
+            .header("retry-after", RETRY_AFTER_SECS.to_string()),
+        ),
+        MessagesBehavior::Overloaded => Some(error(529, "overloaded_error", "Overloaded")),
+        MessagesBehavior::ServerError => Some(error(500, "api_error", "Internal server error")),
+        MessagesBehavior::CloudflareChallenge { status } => Some(
+            Response::new(
+                status,
+                "text/html; charset=UTF-8",
+                "<!DOCTYPE html><html><head><title>Just a moment...</title></head>\
+                 <body>Enable JavaScript and cookies to continue</body></html>",
+            )
+            .header("server", "cloudflare")
+            .header("cf-ray", "8f1e2d3c4b5a6978-SJC")
+            .header("cf-mitigated", "challenge")
+            .header("set-cookie", "__cf_bm=fake-clearance; Path=/; HttpOnly"),
+        ),
+        MessagesBehavior::Hangup => None,
+    }
+}
+
+/// Anthropic-style error body
+fn error(status: u16, error_type: &str, message: &str) -> Response {
+    Response::json(
+        status,
+        &format!(
+            r#"{{"type":"error","error":{{"type":"{}","message":"{}"}}}}"#,
+            error_type, message
+        ),
+    )
+}
+
+struct Response {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl Response {
+    fn new(status: u16, content_type: &str, body: &str) -> Self {
+        Self {
+            status,
+            headers: vec![("content-type".to_string(), content_type.to_string())],
+            body: body.to_string(),
+        }
+    }
+
+    fn json(status: u16, body: &str) -> Self {
+        Self::new(status, "application/json", body).header("request-id", "req_fake")
+    }
+
+    fn header(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.headers.push((name.to_string(), value.into()));
+        self
+    }
+
+    fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        let mut head = format!("HTTP/1.1 {} {}\r\n", self.status, reason(self.status));
+        for (name, value) in &self.headers {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        head.push_str(&format!(
+            "content-length: {}\r\nconnection: close\r\n\r\n",
+            self.body.len()
+        ));
+        writer.write_all(head.as_bytes())?;
+        writer.write_all(self.body.as_bytes())?;
+        writer.flush()
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        413 => "Payload Too Large",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        529 => "Overloaded",
+        _ => "Unknown",
+    }
+}
@@ -0,0 +1,114 @@
+//! `ccstatus-fake-api`: serve the fake Anthropic API until interrupted
+//!
+//! ```sh
+//! cargo run -p ccstatus-fake-api -- --port 8787 --messages cf-challenge
+//! ANTHROPIC_BASE_URL=http://127.0.0.1:8787 ANTHROPIC_API_KEY=fake ccstatus < input.json
+//! ```
+
+use ccstatus_fake_api::{FakeApi, HealthBehavior, MessagesBehavior};
+use std::time::Duration;
+
+const USAGE: &str = "\
+usage: ccstatus-fake-api [--port N] [--messages BEHAVIOR] [--health BEHAVIOR] [--rate-limit N]
+
+  --port N             listen on 127.0.0.1:N (default: any free port)
+  --messages BEHAVIOR  ok | slow | unauthorized | rate-limited | overloaded |
+                       server-error | cf-challenge | hangup (default: ok)
+  --health BEHAVIOR    healthy | degraded | missing (default: healthy)
+  --rate-limit N       answer 429 after N messages";
+
+struct Options {
+    port: u16,
+    messages: MessagesBehavior,
+    health: HealthBehavior,
+    rate_limit: Option<u32>,
+}
+
+fn main() {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    let api = match FakeApi::bind(("127.0.0.1", options.port)) {
+        Ok(api) => api,
+        Err(e) => {
+            eprintln!(
+                "ccstatus-fake-api: cannot listen on port {}: {}",
+                options.port, e
+            );
+            std::process::exit(1);
+        }
+    };
+    api.set_messages(options.messages);
+    api.set_health(options.health);
+    api.set_rate_limit(options.rate_limit);
+
+    println!("ccstatus-fake-api listening on {}", api.base_url());
+    api.wait();
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        port: 0,
+        messages: MessagesBehavior::Ok,
+        health: HealthBehavior::Healthy,
+        rate_limit: None,
+    };
+
+    while let Some(flag) = args.next() {
+        if flag == "-h" || flag == "--help" {
+            println!("{}", USAGE);
+            std::process::exit(0);
+        }
+        if !matches!(
+            flag.as_str(),
+            "--port" | "--messages" | "--health" | "--rate-limit"
+        ) {
+            return Err(format!("unknown option {}", flag));
+        }
+        let value = args
+            .next()
+            .ok_or_else(|| format!("missing value for {}", flag))?;
+        match flag.as_str() {
+            "--port" => options.port = value.parse().map_err(|_| invalid(&flag, &value))?,
+            "--messages" => {
+                options.messages = parse_messages(&value).ok_or_else(|| invalid(&flag, &value))?
+            }
+            "--health" => {
+                options.health = match value.as_str() {
+                    "healthy" => HealthBehavior::Healthy,
+                    "degraded" => HealthBehavior::Degraded,
+                    "missing" => HealthBehavior::Missing,
+                    _ => return Err(invalid(&flag, &value)),
+                }
+            }
+            "--rate-limit" => {
+                options.rate_limit = Some(value.parse().map_err(|_| invalid(&flag, &value))?)
+            }
+            _ => unreachable!("checked above"),
+        }
+    }
+    Ok(options)
+}
+
+fn parse_messages(value: &str) -> Option<MessagesBehavior> {
+    Some(match value {
+        "ok" => MessagesBehavior::Ok,
+        "slow" => MessagesBehavior::Slow(Duration::from_secs(3)),
+        "unauthorized" => MessagesBehavior::Unauthorized,
+        "rate-limited" => MessagesBehavior::RateLimited,
+        "overloaded" => MessagesBehavior::Overloaded,
+        "server-error" => MessagesBehavior::ServerError,
+        "cf-challenge" => MessagesBehavior::CloudflareChallenge { status: 403 },
+        "hangup" => MessagesBehavior::Hangup,
+        _ => return None,
+    })
+}
+
+fn invalid(flag: &str, value: &str) -> String {
+    format!("invalid value '{}' for {}", value, flag)
+}
@@ -0,0 +1,163 @@
+//! Python bindings for ccstatus
+//!
+//! Exposes the same state parsing, probe and rendering paths as the binary, so
+//! latency history can be analysed from notebooks without re-implementing the
+//! file formats:
+//!
+//! ```python
+//! import ccstatus
+//!
+//! snapshot = ccstatus.read_snapshot()      # ccstatus-monitoring.json as a dict
+//! history = ccstatus.probe_history()       # one dict per executed probe
+//! record = ccstatus.probe("green")         # run a probe (needs `ccstatus net enable`)
+//! line = ccstatus.render_statusline(payload_json)
+//! ```
+//!
+//! Build and install into the active virtualenv with `maturin develop` from
+//! this directory. Blocking work releases the GIL.
+
+use ccstatus::config::{Config, ConfigLoader, FlagSet, InputData, ProxyPolicy};
+use ccstatus::core::color;
+use ccstatus::core::network::probe_history::ProbeRecord;
+use ccstatus::core::network::{
+    CredentialManager, HttpMonitor, NetworkError, ProbeMode, StatuslineInput,
+};
+use ccstatus::core::segments::NetworkSegmentWrapper;
+use ccstatus::core::state_location::StateLocation;
+use ccstatus::core::statusline::responsive::available_width;
+use ccstatus::core::{collect_all_segments, StatusLineGenerator};
+use ccstatus::runtime::block_on;
+use pyo3::exceptions::{PyPermissionError, PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Monitoring snapshot as a dict (schema of `ccstatus-monitoring.json`)
+///
+/// `state_path` defaults to `~/.claude/ccstatus/ccstatus-monitoring.json` (under
+/// `[network] local_state_dir` when set); before the first probe the default snapshot with status `Unknown` is returned.
+#[pyfunction]
+#[pyo3(signature = (state_path=None))]
+pub fn read_snapshot(py: Python<'_>, state_path: Option<PathBuf>) -> PyResult<PyObject> {
+    let state = py
+        .allow_threads(|| {
+            block_on(async {
+                HttpMonitor::new(configured_state_path(state_path))?
+                    .load_state()
+                    .await
+            })
+        })
+        .map_err(network_error)?;
+    to_python(py, &state)
+}
+
+/// Executed probes recorded next to the state file, oldest first
+#[pyfunction]
+#[pyo3(signature = (state_path=None))]
+pub fn probe_history(py: Python<'_>, state_path: Option<PathBuf>) -> PyResult<PyObject> {
+    let records = py
+        .allow_threads(|| {
+            HttpMonitor::new(configured_state_path(state_path)).map(|m| m.probe_history().load())
+        })
+        .map_err(network_error)?;
+    to_python(py, &records)
+}
+
+/// Run one probe (`"cold"`, `"green"` or `"red"`) and return its history record
+///
+/// Honours `network.probe_consent` and sends the probe with the statusline's
+/// `[network]`, `[proxy]` and `[network.tls]` settings from config.toml (allowlist,
+/// proxy, trust store, address family, probe request); the result is persisted
+/// to the state file.
+#[pyfunction]
+#[pyo3(signature = (mode="green", state_path=None))]
+pub fn probe(py: Python<'_>, mode: &str, state_path: Option<PathBuf>) -> PyResult<PyObject> {
+    let mode = parse_mode(mode)?;
+    let config = Config::load()
+        .map_err(|e| PyRuntimeError::new_err(format!("cannot load config.toml: {}", e)))?;
+    if !config.network.probe_consent {
+        return Err(PyPermissionError::new_err(
+            "network probes are disabled; run `ccstatus net enable` first",
+        ));
+    }
+
+    let outcome = py.allow_threads(|| {
+        block_on(async {
+            let creds = CredentialManager::new()?
+                .get_credentials()
+                .await?
+                .ok_or_else(|| NetworkError::CredentialError("no API credentials found".into()))?;
+            NetworkSegmentWrapper::new()?
+                .with_config(&config.network)
+                .with_proxy(ProxyPolicy::from_config(&config))
+                .with_flags(FlagSet::load(&config.flags))
+                .monitor(state_path)?
+                .probe(mode, creds, None)
+                .await
+        })
+    });
+    to_python(
+        py,
+        &ProbeRecord::from_outcome(&outcome.map_err(network_error)?),
+    )
+}
+
+/// Render the statusline for `input_json`, Claude Code's statusline stdin payload
+///
+/// Matches `ccstatus --format plain` with the user's config.toml, ANSI styling included.
+#[pyfunction]
+pub fn render_statusline(py: Python<'_>, input_json: &str) -> PyResult<String> {
+    let full_input: StatuslineInput = serde_json::from_str(input_json)
+        .map_err(|e| PyValueError::new_err(format!("invalid statusline input: {}", e)))?;
+
+    Ok(py.allow_threads(|| {
+        let input = InputData::from(&full_input);
+        let config = ConfigLoader::load();
+        let segments_data = block_on(collect_all_segments(&config, &input, Some(&full_input)));
+        StatusLineGenerator::new(config)
+            .with_max_width(available_width(input.terminal_width))
+            .with_color_level(color::detect())
+            .generate(segments_data)
+    }))
+}
+
+#[pymodule]
+#[pyo3(name = "ccstatus")]
+fn ccstatus_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("__version__", env!("CARGO_PKG_VERSION"))?;
+    m.add_function(wrap_pyfunction!(read_snapshot, m)?)?;
+    m.add_function(wrap_pyfunction!(probe_history, m)?)?;
+    m.add_function(wrap_pyfunction!(probe, m)?)?;
+    m.add_function(wrap_pyfunction!(render_statusline, m)?)?;
+    Ok(())
+}
+
+/// `state_path`, else the monitoring state file of the `[network]` section in config.toml
+fn configured_state_path(state_path: Option<PathBuf>) -> Option<PathBuf> {
+    state_path.or_else(|| {
+        StateLocation::from_config(&ConfigLoader::load().network)
+            .map(|location| location.monitoring_path())
+    })
+}
+
+fn parse_mode(mode: &str) -> PyResult<ProbeMode> {
+    match mode.trim().to_ascii_lowercase().as_str() {
+        "cold" => Ok(ProbeMode::Cold),
+        "green" => Ok(ProbeMode::Green),
+        "red" => Ok(ProbeMode::Red),
+        other => Err(PyValueError::new_err(format!(
+            "unknown probe mode '{}' (expected cold, green or red)",
+            other
+        ))),
+    }
+}
+
+/// Hand a serde value to Python through `json.loads`, so dicts match the files on disk
+fn to_python<T: Serialize>(py: Python<'_>, value: &T) -> PyResult<PyObject> {
+    let json = serde_json::to_string(value).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+    Ok(py.import("json")?.call_method1("loads", (json,))?.unbind())
+}
+
+fn network_error(e: NetworkError) -> PyErr {
+    PyRuntimeError::new_err(e.to_string())
+}
@@ -0,0 +1,84 @@
+use ccstatus::config::{Config, NetworkConfig, ProxyConfig};
+use ccstatus_fake_api::FakeApi;
+use ccstatus_py::{probe, probe_history, read_snapshot, render_statusline};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use tempfile::TempDir;
+
+fn with_python<F: for<'py> FnOnce(Python<'py>)>(f: F) {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(f);
+}
+
+#[test]
+fn test_read_snapshot_defaults_without_state_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("monitoring.json");
+
+    with_python(|py| {
+        let snapshot = read_snapshot(py, Some(path)).unwrap();
+        let snapshot = snapshot.bind(py).downcast::<PyDict>().unwrap().clone();
+        let status: String = snapshot
+            .get_item("status")
+            .unwrap()
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(status, "Unknown");
+    });
+}
+
+#[test]
+fn test_probe_history_empty_without_records() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("monitoring.json");
+
+    with_python(|py| {
+        let history = probe_history(py, Some(path)).unwrap();
+        assert!(history.bind(py).downcast::<PyList>().unwrap().is_empty());
+    });
+}
+
+#[test]
+fn test_invalid_arguments_raise_value_error() {
+    with_python(|py| {
+        let err = probe(py, "purple", None).unwrap_err();
+        assert!(err.is_instance_of::<PyValueError>(py));
+
+        let err = render_statusline(py, "{not json").unwrap_err();
+        assert!(err.is_instance_of::<PyValueError>(py));
+    });
+}
+
+#[test]
+fn test_probe_honours_configured_proxy() {
+    let home = TempDir::new().unwrap();
+    let proxy = FakeApi::start().unwrap();
+    std::env::set_var("HOME", home.path());
+    std::env::set_var("ANTHROPIC_BASE_URL", "http://api.example.invalid");
+    std::env::set_var("ANTHROPIC_AUTH_TOKEN", "sk-ant-fake");
+    Config {
+        network: NetworkConfig {
+            probe_consent: true,
+            ..Default::default()
+        },
+        proxy: Some(ProxyConfig {
+            url: Some(proxy.base_url()),
+            no_proxy: Some(Vec::new()),
+        }),
+        ..Config::default()
+    }
+    .save()
+    .unwrap();
+
+    with_python(|py| {
+        probe(py, "green", Some(home.path().join("monitoring.json"))).unwrap();
+    });
+
+    // Only a proxy is sent the absolute URL; a direct probe cannot resolve the host
+    assert!(proxy
+        .requests()
+        .iter()
+        .any(|request| request.path == "http://api.example.invalid/v1/messages"));
+}
@@ -0,0 +1,80 @@
+//! Invariants checked between iterations: memory, file descriptors and state files
+
+use ccstatus::core::network::probe_history::{ProbeHistory, COMPACT_AT_BYTES};
+use ccstatus::core::network::{MonitoringSnapshot, ROLLING_WINDOW_LEN};
+use std::fs;
+use std::path::Path;
+
+/// Slack over `COMPACT_AT_BYTES`: the history is compacted on the append after it passes the limit
+const HISTORY_SLACK_BYTES: u64 = 64 * 1024;
+
+/// Resident set size in bytes; `None` where `/proc` is unavailable
+pub fn resident_bytes() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let kilobytes = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(kilobytes * 1024)
+}
+
+/// Open file descriptors of this process; `None` where neither `/proc` nor `/dev/fd` lists them
+pub fn open_fds() -> Option<usize> {
+    ["/proc/self/fd", "/dev/fd"]
+        .into_iter()
+        .find_map(|dir| fs::read_dir(dir).ok())
+        .map(|entries| entries.count())
+}
+
+/// Check the monitoring state and the files around it
+///
+/// The state must parse without recovery, keep its rolling window bounded and
+/// leave no quarantined or half-written files behind; the probe history must
+/// stay near its compaction size.
+pub fn check_state_files(state_path: &Path) -> Result<(), String> {
+    if state_path.exists() {
+        let content = fs::read(state_path)
+            .map_err(|e| format!("cannot read {}: {}", state_path.display(), e))?;
+        let state: MonitoringSnapshot = serde_json::from_slice(&content)
+            .map_err(|e| format!("{} does not parse: {}", state_path.display(), e))?;
+        if state.network.rolling_totals.len() > ROLLING_WINDOW_LEN {
+            return Err(format!(
+                "rolling window holds {} samples (max {})",
+                state.network.rolling_totals.len(),
+                ROLLING_WINDOW_LEN
+            ));
+        }
+    }
+
+    let history = ProbeHistory::for_state_path(state_path);
+    if let Ok(metadata) = fs::metadata(history.path()) {
+        if metadata.len() > COMPACT_AT_BYTES + HISTORY_SLACK_BYTES {
+            return Err(format!(
+                "probe history is {} bytes (compacts at {})",
+                metadata.len(),
+                COMPACT_AT_BYTES
+            ));
+        }
+    }
+
+    let Some(dir) = state_path.parent() else {
+        return Ok(());
+    };
+    let leftovers: Vec<String> = fs::read_dir(dir)
+        .map_err(|e| format!("cannot list {}: {}", dir.display(), e))?
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| name.contains(".corrupt-") || name.ends_with(".tmp"))
+        .collect();
+    if !leftovers.is_empty() {
+        return Err(format!(
+            "quarantined or half-written files: {}",
+            leftovers.join(", ")
+        ));
+    }
+    Ok(())
+}
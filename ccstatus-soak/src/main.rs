@@ -0,0 +1,473 @@
+//! `ccstatus-soak`: run the statusline pipeline for hours to catch slow leaks
+//!
+//! Each iteration renders a statusline from randomized input, the way Claude
+//! Code invokes ccstatus, and sends one more probe of a random mode, all
+//! against the bundled fake API whose behavior changes at random. Every
+//! `--check-every` iterations the process is checked for a memory ceiling,
+//! file descriptor growth and state-file integrity (see `checks.rs`); the
+//! first violation stops the run with exit code 1.
+//!
+//! Everything runs in a temporary `HOME`, so real configuration, state and
+//! credentials are never touched.
+//!
+//! ```sh
+//! cargo run --release -p ccstatus-soak -- --duration 4h --seed 42
+//! ```
+
+mod checks;
+
+use ccstatus::config::{Config, InputData, SegmentId};
+use ccstatus::core::network::{
+    ApiCredentials, CredentialSource, HttpMonitor, ProbeMode, StatuslineInput,
+};
+use ccstatus::core::{collect_all_segments, StatusLineGenerator};
+use ccstatus::runtime;
+use ccstatus_fake_api::{FakeApi, HealthBehavior, MessagesBehavior};
+use serde_json::json;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+const USAGE: &str = "\
+usage: ccstatus-soak [--duration D] [--iterations N] [--seed N] [--max-rss-mb N]
+                     [--fd-slack N] [--check-every N] [--keep]
+
+  --duration D     stop after D: seconds, or with an s/m/h suffix (default: 1h)
+  --iterations N   stop after N iterations, if sooner
+  --seed N         seed for the randomized inputs (default: from the clock)
+  --max-rss-mb N   fail when resident memory exceeds N MiB (default: 256)
+  --fd-slack N     fail when open fds exceed the first check's count by N (default: 16)
+  --check-every N  iterations between checks (default: 200)
+  --keep           keep the temporary HOME for inspection";
+
+/// Segments rendered each iteration besides the defaults; none of them reach the network
+const EXTRA_SEGMENTS: [SegmentId; 8] = [
+    SegmentId::Cost,
+    SegmentId::BurnRate,
+    SegmentId::Block,
+    SegmentId::Stopwatch,
+    SegmentId::Quota,
+    SegmentId::Project,
+    SegmentId::DiffStat,
+    SegmentId::Mode,
+];
+
+/// Transcripts are rewritten from scratch past this size
+const MAX_TRANSCRIPT_BYTES: u64 = 256 * 1024;
+
+const TRANSCRIPTS: usize = 8;
+const SESSIONS: usize = 16;
+const MODELS: [&str; 4] = [
+    "claude-sonnet-4",
+    "claude-opus-4-1",
+    "claude-3-5-haiku-20241022",
+    "unknown-model",
+];
+
+struct Options {
+    duration: Duration,
+    iterations: Option<u64>,
+    seed: u64,
+    max_rss_bytes: u64,
+    fd_slack: usize,
+    check_every: u64,
+    keep: bool,
+}
+
+struct Soak {
+    rng: fastrand::Rng,
+    api: FakeApi,
+    home: PathBuf,
+    state_path: PathBuf,
+    transcripts: Vec<PathBuf>,
+    workspaces: Vec<PathBuf>,
+    /// Requests the fake API has served, counted before its log is cleared
+    served: usize,
+}
+
+fn main() {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    let home = match tempfile::Builder::new().prefix("ccstatus-soak-").tempdir() {
+        Ok(home) => home,
+        Err(e) => {
+            eprintln!("ccstatus-soak: cannot create a temporary HOME: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let api = match FakeApi::start() {
+        Ok(api) => api,
+        Err(e) => {
+            eprintln!("ccstatus-soak: cannot start the fake API: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    // Before the first library call: paths and credentials are read from the environment
+    isolate_environment(home.path(), &api.base_url());
+
+    let mut soak = match Soak::new(home.path(), api, options.seed) {
+        Ok(soak) => soak,
+        Err(e) => {
+            eprintln!("ccstatus-soak: setup failed: {}", e);
+            std::process::exit(1);
+        }
+    };
+    println!(
+        "ccstatus-soak: seed {}, HOME {}, API {}",
+        options.seed,
+        home.path().display(),
+        soak.api.base_url()
+    );
+
+    let result = soak.run(&options);
+    if options.keep {
+        println!("Kept {}", home.keep().display());
+    }
+    if let Err(message) = result {
+        eprintln!("ccstatus-soak: FAILED: {}", message);
+        std::process::exit(1);
+    }
+}
+
+/// Point HOME at `home` and the credentials at the fake API, dropping any real ones
+fn isolate_environment(home: &Path, base_url: &str) {
+    std::env::set_var("HOME", home);
+    std::env::set_var("USERPROFILE", home);
+    for name in [
+        "ANTHROPIC_AUTH_TOKEN",
+        "ANTHROPIC_BEDROCK_BASE_URL",
+        "ANTHROPIC_VERTEX_BASE_URL",
+        "CCSTATUS_DRYRUN",
+        "CCSTATUS_PROBE_PROFILE",
+    ] {
+        std::env::remove_var(name);
+    }
+    std::env::set_var("ANTHROPIC_BASE_URL", base_url);
+    std::env::set_var("ANTHROPIC_API_KEY", "sk-ant-soak");
+}
+
+impl Soak {
+    fn new(home: &Path, api: FakeApi, seed: u64) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut config = Config::default();
+        config.network.probe_consent = true;
+        let template = config.segments[0].clone();
+        for id in EXTRA_SEGMENTS {
+            let mut segment = template.clone();
+            segment.id = id;
+            config.segments.push(segment);
+        }
+        for segment in &mut config.segments {
+            segment.enabled = true;
+        }
+        config.save()?;
+
+        let data = home.join("soak");
+        let transcripts = (0..TRANSCRIPTS)
+            .map(|i| data.join(format!("transcript-{}.jsonl", i)))
+            .collect();
+        let workspaces: Vec<PathBuf> = (0..3)
+            .map(|i| data.join(format!("project-{}", i)))
+            .collect();
+        for workspace in &workspaces {
+            fs::create_dir_all(workspace)?;
+        }
+
+        Ok(Self {
+            rng: fastrand::Rng::with_seed(seed),
+            api,
+            home: home.to_path_buf(),
+            state_path: home
+                .join(".claude")
+                .join("ccstatus")
+                .join("ccstatus-monitoring.json"),
+            transcripts,
+            workspaces,
+            served: 0,
+        })
+    }
+
+    fn run(&mut self, options: &Options) -> Result<(), String> {
+        let started = Instant::now();
+        let mut fd_baseline = None;
+        let mut peak_rss = 0;
+        let mut iteration = 0;
+
+        while started.elapsed() < options.duration
+            && options.iterations.is_none_or(|limit| iteration < limit)
+        {
+            iteration += 1;
+            self.iterate()
+                .map_err(|e| format!("iteration {}: {}", iteration, e))?;
+
+            if iteration % options.check_every != 0 {
+                continue;
+            }
+            let rss = checks::resident_bytes();
+            if let Some(rss) = rss {
+                peak_rss = peak_rss.max(rss);
+                if rss > options.max_rss_bytes {
+                    return Err(format!(
+                        "iteration {}: resident memory {} exceeds the ceiling {}",
+                        iteration,
+                        mib(rss),
+                        mib(options.max_rss_bytes)
+                    ));
+                }
+            }
+            let fds = checks::open_fds();
+            if let Some(fds) = fds {
+                let baseline = *fd_baseline.get_or_insert(fds);
+                if fds > baseline + options.fd_slack {
+                    return Err(format!(
+                        "iteration {}: {} open fds, {} at the first check",
+                        iteration, fds, baseline
+                    ));
+                }
+            }
+            checks::check_state_files(&self.state_path)
+                .map_err(|e| format!("iteration {}: {}", iteration, e))?;
+
+            println!(
+                "[{}] iteration {} rss {} fds {}",
+                format_elapsed(started.elapsed()),
+                iteration,
+                rss.map_or("n/a".to_string(), mib),
+                fds.map_or("n/a".to_string(), |fds| fds.to_string())
+            );
+        }
+
+        checks::check_state_files(&self.state_path)?;
+        println!(
+            "ccstatus-soak: OK, {} iterations in {}, peak rss {}, {} requests served",
+            iteration,
+            format_elapsed(started.elapsed()),
+            mib(peak_rss),
+            self.served
+        );
+        Ok(())
+    }
+
+    /// One statusline render plus one direct probe, with randomized API behavior and input
+    fn iterate(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        if self.rng.u8(..10) == 0 {
+            let behavior = self.random_behavior();
+            self.api.set_messages(behavior);
+            self.api.set_health(match self.rng.u8(..3) {
+                0 => HealthBehavior::Healthy,
+                1 => HealthBehavior::Degraded,
+                _ => HealthBehavior::Missing,
+            });
+        }
+        // The recorded requests would otherwise grow with the run
+        self.served += self.api.requests().len();
+        self.api.clear_requests();
+
+        let transcript = self.transcripts[self.rng.usize(..TRANSCRIPTS)].clone();
+        self.append_transcript(&transcript)?;
+        let full_input: StatuslineInput = serde_json::from_value(self.random_input(&transcript))?;
+        let input = InputData::from(&full_input);
+
+        let config = Config::load()?;
+        let segments = runtime::block_on(collect_all_segments(&config, &input, Some(&full_input)));
+        let rendered = StatusLineGenerator::new(config)
+            .with_max_width(input.terminal_width.map(usize::from))
+            .generate(segments);
+        if rendered.is_empty() {
+            return Err("empty statusline".into());
+        }
+
+        let mode = match self.rng.u8(..3) {
+            0 => ProbeMode::Cold,
+            1 => ProbeMode::Red,
+            _ => ProbeMode::Green,
+        };
+        let creds = ApiCredentials::builder(
+            self.api.base_url(),
+            "sk-ant-soak",
+            CredentialSource::Environment,
+        )
+        .build()?;
+        let mut monitor = HttpMonitor::new(Some(self.state_path.clone()))?;
+        runtime::block_on(monitor.probe(mode, creds, None))?;
+        Ok(())
+    }
+
+    fn random_behavior(&mut self) -> MessagesBehavior {
+        match self.rng.u8(..20) {
+            0..=11 => MessagesBehavior::Ok,
+            12 | 13 => MessagesBehavior::Slow(Duration::from_millis(self.rng.u64(50..1500))),
+            14 => MessagesBehavior::RateLimited,
+            15 => MessagesBehavior::Overloaded,
+            16 => MessagesBehavior::ServerError,
+            17 => MessagesBehavior::Unauthorized,
+            18 => MessagesBehavior::CloudflareChallenge {
+                status: [403, 429, 503][self.rng.usize(..3)],
+            },
+            _ => MessagesBehavior::Hangup,
+        }
+    }
+
+    /// Append a few lines: priced messages, sometimes an API error or a torn line
+    fn append_transcript(&mut self, path: &Path) -> std::io::Result<()> {
+        if fs::metadata(path).is_ok_and(|metadata| metadata.len() > MAX_TRANSCRIPT_BYTES) {
+            fs::remove_file(path)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        let now = unix_now();
+        for _ in 0..self.rng.usize(1..4) {
+            let timestamp = iso_timestamp(now - self.rng.i64(0..3600));
+            let line = match self.rng.u8(..20) {
+                0 => json!({
+                    "type": "assistant",
+                    "timestamp": timestamp,
+                    "isApiErrorMessage": true,
+                    "message": {"content": [{"type": "text", "text": "API Error: 529 Overloaded"}]},
+                })
+                .to_string(),
+                // A line cut off mid-write, as a crash would leave it
+                1 => "{\"type\":\"assistant\",\"message\":{\"usa".to_string(),
+                _ => json!({
+                    "type": "assistant",
+                    "timestamp": timestamp,
+                    "message": {
+                        "id": format!("msg_{}", self.rng.u64(..)),
+                        "model": MODELS[self.rng.usize(..MODELS.len())],
+                        "usage": {
+                            "input_tokens": self.rng.u32(1..5000),
+                            "output_tokens": self.rng.u32(1..2000),
+                            "cache_read_input_tokens": self.rng.u32(..20000),
+                        },
+                    },
+                })
+                .to_string(),
+            };
+            writeln!(file, "{}", line)?;
+        }
+        Ok(())
+    }
+
+    fn random_input(&mut self, transcript: &Path) -> serde_json::Value {
+        let workspace = &self.workspaces[self.rng.usize(..self.workspaces.len())];
+        let model = MODELS[self.rng.usize(..MODELS.len())];
+        let mut input = json!({
+            "session_id": format!("soak-session-{}", self.rng.usize(..SESSIONS)),
+            "transcript_path": transcript,
+            "cwd": workspace,
+            "model": {"id": model, "display_name": model},
+            "workspace": {"current_dir": workspace, "project_dir": self.home},
+            "version": "1.0.93",
+            "output_style": {"name": "default"},
+            "cost": {
+                "total_cost_usd": self.rng.f64() * 20.0,
+                "total_duration_ms": self.rng.u64(..7_200_000),
+                "total_api_duration_ms": self.rng.u64(..3_600_000),
+                "total_lines_added": self.rng.u32(..5000),
+                "total_lines_removed": self.rng.u32(..5000),
+            },
+            "exceeds_200k_tokens": self.rng.bool(),
+        });
+        if self.rng.bool() {
+            input["terminal_width"] = json!(self.rng.u16(20..240));
+        }
+        if self.rng.bool() {
+            input["permission_mode"] =
+                json!(["default", "plan", "acceptEdits"][self.rng.usize(..3)]);
+        }
+        input
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        duration: Duration::from_secs(3600),
+        iterations: None,
+        seed: unix_now() as u64,
+        max_rss_bytes: 256 * 1024 * 1024,
+        fd_slack: 16,
+        check_every: 200,
+        keep: false,
+    };
+
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                std::process::exit(0);
+            }
+            "--keep" => {
+                options.keep = true;
+                continue;
+            }
+            "--duration" | "--iterations" | "--seed" | "--max-rss-mb" | "--fd-slack"
+            | "--check-every" => {}
+            _ => return Err(format!("unknown option {}", flag)),
+        }
+        let value = args
+            .next()
+            .ok_or_else(|| format!("missing value for {}", flag))?;
+        let number = || value.parse::<u64>().map_err(|_| invalid(&flag, &value));
+        match flag.as_str() {
+            "--duration" => {
+                options.duration = parse_duration(&value).ok_or_else(|| invalid(&flag, &value))?
+            }
+            "--iterations" => options.iterations = Some(number()?),
+            "--seed" => options.seed = number()?,
+            "--max-rss-mb" => options.max_rss_bytes = number()? * 1024 * 1024,
+            "--fd-slack" => options.fd_slack = number()? as usize,
+            "--check-every" => options.check_every = number()?.max(1),
+            _ => unreachable!("checked above"),
+        }
+    }
+    Ok(options)
+}
+
+/// `90`, `90s`, `15m` or `4h`
+fn parse_duration(value: &str) -> Option<Duration> {
+    let (number, unit) = match value.find(|c: char| !c.is_ascii_digit()) {
+        Some(split) => value.split_at(split),
+        None => (value, "s"),
+    };
+    let multiplier = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        _ => return None,
+    };
+    Some(Duration::from_secs(
+        number.parse::<u64>().ok()? * multiplier,
+    ))
+}
+
+fn invalid(flag: &str, value: &str) -> String {
+    format!("invalid value '{}' for {}", value, flag)
+}
+
+fn mib(bytes: u64) -> String {
+    format!("{:.1}MiB", bytes as f64 / (1024.0 * 1024.0))
+}
+
+fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+fn iso_timestamp(secs: i64) -> String {
+    chrono::DateTime::from_timestamp(secs, 0)
+        .unwrap_or_default()
+        .to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+}
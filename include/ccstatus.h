@@ -0,0 +1,41 @@
+/*
+ * ccstatus C ABI (build with the `ffi` feature, see src/ffi.rs)
+ *
+ * Returned strings are NUL-terminated UTF-8 owned by the caller; release them
+ * with ccstatus_string_free(). NULL signals an error.
+ */
+
+#ifndef CCSTATUS_H
+#define CCSTATUS_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define CCSTATUS_FFI_ABI_VERSION 1
+
+/* ABI version of the loaded library; compare with CCSTATUS_FFI_ABI_VERSION */
+uint32_t ccstatus_abi_version(void);
+
+/*
+ * Current monitoring snapshot as JSON (schema of ccstatus-monitoring.json).
+ * Before the first probe this is the default snapshot with status "Unknown".
+ */
+char *ccstatus_read_snapshot_json(void);
+
+/*
+ * Render the statusline for input_json (Claude Code's statusline stdin payload),
+ * matching `ccstatus --format plain` with the user's config.toml.
+ */
+char *ccstatus_render_statusline(const char *input_json);
+
+/* Release a string returned by this library; NULL is ignored */
+void ccstatus_string_free(char *s);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* CCSTATUS_H */
@@ -1,4 +1,10 @@
-use clap::Parser;
+use crate::core::output::OutputFormat;
+use clap::{Arg, CommandFactory, Parser, Subcommand};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+
+/// Bumped when the `--help-json` layout changes incompatibly
+pub const HELP_JSON_SCHEMA: u32 = 1;
 
 #[derive(Parser, Debug)]
 #[command(name = "High-performance Claude Code StatusLine with Network Probe")]
@@ -12,10 +18,193 @@ pub struct Cli {
     /// Check for updates and exit
     #[arg(long = "check-update")]
     pub check_update: bool,
+
+    /// Output format: plain statusline, or JSON with a tooltip detail block
+    #[arg(long = "format", value_enum, default_value_t = OutputFormat::Plain)]
+    pub format: OutputFormat,
+
+    /// Read the statusline JSON from a file, or pass it inline; `-` = stdin (default)
+    #[arg(long = "input", value_name = "PATH|JSON|-")]
+    pub input: Option<String>,
+
+    /// Re-render each time the `--input` file changes, one statusline per line
+    #[arg(long = "watch", requires = "input")]
+    pub watch: bool,
+
+    /// Print every command and flag as JSON (for GUI wrappers) and exit
+    #[arg(long = "help-json")]
+    pub help_json: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Network monitoring controls
+    Net {
+        #[command(subcommand)]
+        action: NetAction,
+    },
+    /// Show per-segment health (panics and timeouts caught while rendering)
+    Doctor,
+    /// Show version, build features and effective kill-switch flags
+    About,
+    /// List the CCSTATUS_* environment variables with their effective values
+    Env,
+    /// Show sessions, cost and last activity per project
+    Stats,
+    /// Print all segment data as ANSI-free JSON for hook scripts
+    Snapshot {
+        /// Emit a single line (for Claude Code hooks)
+        #[arg(long)]
+        for_hook: bool,
+    },
+    /// Print the monitoring state for other tools
+    Export {
+        /// Prometheus text exposition format (latency, P95, status, error counters)
+        #[arg(long, required = true)]
+        prometheus: bool,
+        /// Serve the metrics at http://ADDR/metrics instead of printing them once
+        /// (`prometheus-listener` feature); ADDR defaults to 127.0.0.1:9464
+        #[arg(
+            long,
+            value_name = "ADDR",
+            num_args = 0..=1,
+            default_missing_value = "127.0.0.1:9464"
+        )]
+        listen: Option<String>,
+    },
+    /// Render the statusline under the current config and a candidate one, segment by segment
+    Preview {
+        /// Candidate config.toml to compare against the current config
+        #[arg(long, value_name = "PATH")]
+        diff: PathBuf,
+        /// Strip ANSI colors and styling from the output
+        #[arg(long)]
+        plain: bool,
+    },
+    /// Probe on a timer without Claude Code; the statusline only reads the results meanwhile
+    Daemon {
+        /// Probe profile setting the GREEN/RED cadence (default: CCSTATUS_PROBE_PROFILE, else standard)
+        #[arg(long, value_name = "NAME")]
+        profile: Option<String>,
+    },
+    /// Summarize the per-probe history: status mix and long-term latency percentiles
+    History {
+        /// Window to summarize, e.g. 6h, 7d or 30d
+        #[arg(long, value_name = "RANGE", default_value = "7d")]
+        since: String,
+        /// Only this target (`primary` is the default probe)
+        #[arg(long, value_name = "NAME")]
+        target: Option<String>,
+        /// Also list the newest N probes of the window
+        #[arg(long, value_name = "N", default_value_t = 0)]
+        recent: usize,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum NetAction {
+    /// Allow live API probes (each probe is a real, token-consuming request)
+    Enable,
+    /// Revoke probe consent and fall back to transcript-only monitoring
+    Disable,
+    /// Show the last probe result, including the edge's response headers on error
+    Status,
+    /// Probe once now and exit 0 healthy, 1 degraded, 2 error, 3 unknown (for CI gates)
+    Check,
+    /// Sync monitoring history with other machines now (`[network.sync]`)
+    Sync,
+    /// Summarize recorded probes for the running version
+    Report {
+        /// Compare latency and classification across ccstatus versions
+        #[arg(long)]
+        compare_versions: bool,
+    },
+    /// Compare latency of the alternate regions in `[network.standby]`
+    Regions {
+        /// Measure every region now instead of only showing recorded results
+        #[arg(long)]
+        measure: bool,
+    },
+    /// Clear rolling statistics, window IDs and error history for a clean baseline
+    Reset {
+        /// Delete the whole monitoring state and probe history instead
+        #[arg(long)]
+        all: bool,
+        /// Skip the confirmation prompt of --all
+        #[arg(long, short)]
+        yes: bool,
+    },
+    /// Monthly compliance with the `[[network.sla]]` targets, for sending to your vendor
+    Sla {
+        /// Month to report (`YYYY-MM`, UTC); default = the current month
+        #[arg(long)]
+        month: Option<String>,
+    },
 }
 
 impl Cli {
     pub fn parse_args() -> Self {
         Self::parse()
     }
+
+    /// Commands, flags, descriptions and defaults for `--help-json`
+    ///
+    /// Built from the clap definitions, so wrappers never parse the human help text.
+    pub fn help_json() -> Value {
+        let mut root = describe_command(&Self::command());
+        root["name"] = json!("ccstatus");
+        root["version"] = json!(env!("CARGO_PKG_VERSION"));
+        root["schema"] = json!(HELP_JSON_SCHEMA);
+        root
+    }
+}
+
+fn describe_command(command: &clap::Command) -> Value {
+    json!({
+        "name": command.get_name(),
+        "about": command.get_about().map(|about| about.to_string()),
+        "args": command
+            .get_arguments()
+            .filter(|arg| !arg.is_hide_set())
+            .map(describe_arg)
+            .collect::<Vec<_>>(),
+        "subcommands": command
+            .get_subcommands()
+            .filter(|subcommand| !subcommand.is_hide_set())
+            .map(describe_command)
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn describe_arg(arg: &Arg) -> Value {
+    let takes_value = arg.get_action().takes_values();
+    // Boolean flags would otherwise list "true"/"false"
+    let possible_values: Vec<String> = if takes_value {
+        arg.get_possible_values()
+            .iter()
+            .filter(|value| !value.is_hide_set())
+            .map(|value| value.get_name().to_string())
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    json!({
+        "id": arg.get_id().as_str(),
+        "long": arg.get_long(),
+        "short": arg.get_short().map(String::from),
+        "help": arg.get_help().map(|help| help.to_string()),
+        "positional": arg.is_positional(),
+        "required": arg.is_required_set(),
+        "takes_value": takes_value,
+        "default": arg
+            .get_default_values()
+            .iter()
+            .map(|value| value.to_string_lossy().into_owned())
+            .collect::<Vec<_>>(),
+        "possible_values": possible_values,
+    })
 }
@@ -0,0 +1,318 @@
+//! Alert routes (`alerts = [{ sink = "slack", on = ["outage>5m", "update"] }]`)
+//!
+//! Each route names a sink and the triggers it fires on. A trigger is an event
+//! (`outage`, `degraded`, `recovered`, `update`) or a severity (`critical`,
+//! `warning`, `info`) that matches events of that severity and above. Either
+//! may require the condition to last a while first: `outage>5m`, `warning>90s`.
+//! Delivery, debounce and dedup live in `core::network::alerts`.
+//!
+//! `[notifications]` is a shorthand for a desktop route with one switch per
+//! event (outage, recovery, update).
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Where a route delivers its alerts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertSinkKind {
+    /// POST the alert as JSON to `url`
+    Webhook,
+    /// POST `{"text": ...}` to a Slack incoming webhook `url`
+    Slack,
+    /// POST `{"content": ...}` to a Discord webhook `url`
+    Discord,
+    /// Desktop notification (`notify-send`, `osascript`)
+    Desktop,
+    /// Shell `command` with the alert in `CCSTATUS_ALERT_*` variables
+    Command,
+}
+
+impl AlertSinkKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AlertSinkKind::Webhook => "webhook",
+            AlertSinkKind::Slack => "slack",
+            AlertSinkKind::Discord => "discord",
+            AlertSinkKind::Desktop => "desktop",
+            AlertSinkKind::Command => "command",
+        }
+    }
+}
+
+/// How urgent an alert is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertSeverity {
+    Info,
+    Warning,
+    Critical,
+}
+
+impl AlertSeverity {
+    pub const ALL: [AlertSeverity; 3] = [
+        AlertSeverity::Info,
+        AlertSeverity::Warning,
+        AlertSeverity::Critical,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AlertSeverity::Info => "info",
+            AlertSeverity::Warning => "warning",
+            AlertSeverity::Critical => "critical",
+        }
+    }
+}
+
+/// Conditions an alert can report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertEvent {
+    /// Probes are failing (network status `error`)
+    Outage,
+    /// Elevated latency or rate limiting (network status `degraded`)
+    Degraded,
+    /// Back to healthy after an outage or degradation
+    Recovered,
+    /// A newer ccstatus release is available
+    Update,
+}
+
+impl AlertEvent {
+    pub const ALL: [AlertEvent; 4] = [
+        AlertEvent::Outage,
+        AlertEvent::Degraded,
+        AlertEvent::Recovered,
+        AlertEvent::Update,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AlertEvent::Outage => "outage",
+            AlertEvent::Degraded => "degraded",
+            AlertEvent::Recovered => "recovered",
+            AlertEvent::Update => "update",
+        }
+    }
+
+    pub fn severity(self) -> AlertSeverity {
+        match self {
+            AlertEvent::Outage => AlertSeverity::Critical,
+            AlertEvent::Degraded => AlertSeverity::Warning,
+            AlertEvent::Recovered | AlertEvent::Update => AlertSeverity::Info,
+        }
+    }
+}
+
+/// What a trigger selects
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriggerTarget {
+    Event(AlertEvent),
+    /// Any event of this severity or above
+    Severity(AlertSeverity),
+}
+
+/// One entry of a route's `on` list, e.g. `outage>5m`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct AlertTrigger {
+    pub target: TriggerTarget,
+    /// How long the condition must have lasted, in seconds
+    pub min_secs: u64,
+}
+
+impl AlertTrigger {
+    /// Whether an `event` that has lasted `duration_secs` fires this trigger
+    pub fn matches(&self, event: AlertEvent, duration_secs: u64) -> bool {
+        let selected = match self.target {
+            TriggerTarget::Event(target) => target == event,
+            TriggerTarget::Severity(severity) => event.severity() >= severity,
+        };
+        selected && duration_secs >= self.min_secs
+    }
+}
+
+impl FromStr for AlertTrigger {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, duration) = match s.split_once('>') {
+            Some((name, duration)) => (name.trim(), Some(duration.trim())),
+            None => (s.trim(), None),
+        };
+        let name = name.to_ascii_lowercase();
+
+        let target = AlertEvent::ALL
+            .into_iter()
+            .find(|event| event.as_str() == name)
+            .map(TriggerTarget::Event)
+            .or_else(|| {
+                AlertSeverity::ALL
+                    .into_iter()
+                    .find(|severity| severity.as_str() == name)
+                    .map(TriggerTarget::Severity)
+            })
+            .ok_or_else(|| format!("unknown alert trigger '{}'", s.trim()))?;
+        let min_secs = match duration {
+            Some(duration) => parse_duration_secs(duration)
+                .ok_or_else(|| format!("invalid duration in alert trigger '{}'", s.trim()))?,
+            None => 0,
+        };
+
+        Ok(Self { target, min_secs })
+    }
+}
+
+impl TryFrom<String> for AlertTrigger {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<AlertTrigger> for String {
+    fn from(trigger: AlertTrigger) -> Self {
+        trigger.to_string()
+    }
+}
+
+impl fmt::Display for AlertTrigger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.target {
+            TriggerTarget::Event(event) => f.write_str(event.as_str())?,
+            TriggerTarget::Severity(severity) => f.write_str(severity.as_str())?,
+        }
+        match self.min_secs {
+            0 => Ok(()),
+            secs if secs.is_multiple_of(3600) => write!(f, ">{}h", secs / 3600),
+            secs if secs.is_multiple_of(60) => write!(f, ">{}m", secs / 60),
+            secs => write!(f, ">{}s", secs),
+        }
+    }
+}
+
+/// Parse `90`, `90s`, `5m` or `2h` into seconds
+pub fn parse_duration_secs(value: &str) -> Option<u64> {
+    let value = value.trim();
+    let (number, unit) = match value.find(|c: char| !c.is_ascii_digit()) {
+        Some(index) => value.split_at(index),
+        None => (value, "s"),
+    };
+    let number: u64 = number.parse().ok()?;
+    match unit {
+        "s" => Some(number),
+        "m" => number.checked_mul(60),
+        "h" => number.checked_mul(3600),
+        _ => None,
+    }
+}
+
+/// One alert route (an `alerts` entry)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlertConfig {
+    pub sink: AlertSinkKind,
+    /// Triggers; the route fires when any of them matches
+    pub on: Vec<AlertTrigger>,
+    /// Endpoint for `webhook`, `slack` and `discord`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Shell command for `command` (`sh -c`, `cmd /C` on Windows)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// Minimum seconds between two alerts of the same event on this route
+    #[serde(default = "default_alert_debounce_secs")]
+    pub debounce_secs: u64,
+}
+
+impl AlertConfig {
+    /// Check that the route can deliver anything at all
+    pub fn validate(&self) -> Result<(), String> {
+        if self.on.is_empty() {
+            return Err(format!("{} alert has no triggers", self.sink.as_str()));
+        }
+        let missing = match self.sink {
+            AlertSinkKind::Webhook | AlertSinkKind::Slack | AlertSinkKind::Discord => self
+                .url
+                .as_deref()
+                .is_none_or(|url| url.trim().is_empty())
+                .then_some("url"),
+            AlertSinkKind::Command => self
+                .command
+                .as_deref()
+                .is_none_or(|command| command.trim().is_empty())
+                .then_some("command"),
+            AlertSinkKind::Desktop => None,
+        };
+        match missing {
+            Some(field) => Err(format!("{} alert needs a {}", self.sink.as_str(), field)),
+            None => Ok(()),
+        }
+    }
+}
+
+fn default_alert_debounce_secs() -> u64 {
+    300
+}
+
+/// Desktop notifications (`[notifications]`); present = enabled
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NotificationsConfig {
+    /// Notify when probes start failing
+    #[serde(default = "default_true")]
+    pub outage: bool,
+    /// Notify when the API is healthy again
+    #[serde(default = "default_true")]
+    pub recovered: bool,
+    /// Notify when a new ccstatus version is ready
+    #[serde(default = "default_true")]
+    pub update: bool,
+    /// Minimum seconds between two notifications of the same event
+    #[serde(default = "default_alert_debounce_secs")]
+    pub debounce_secs: u64,
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            outage: true,
+            recovered: true,
+            update: true,
+            debounce_secs: default_alert_debounce_secs(),
+        }
+    }
+}
+
+impl NotificationsConfig {
+    /// The equivalent `desktop` alert route; None with every event switched off
+    pub fn alert_route(&self) -> Option<AlertConfig> {
+        let on: Vec<AlertTrigger> = [
+            (AlertEvent::Outage, self.outage),
+            (AlertEvent::Recovered, self.recovered),
+            (AlertEvent::Update, self.update),
+        ]
+        .into_iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(event, _)| AlertTrigger {
+            target: TriggerTarget::Event(event),
+            min_secs: 0,
+        })
+        .collect();
+        if on.is_empty() {
+            return None;
+        }
+        Some(AlertConfig {
+            sink: AlertSinkKind::Desktop,
+            on,
+            url: None,
+            command: None,
+            debounce_secs: self.debounce_secs,
+        })
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
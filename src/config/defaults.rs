@@ -15,9 +15,11 @@ impl Default for Config {
         #[cfg(not(feature = "tui"))]
         {
             use crate::config::{
-                AnsiColor, ColorConfig, IconConfig, SegmentConfig, SegmentId, StyleConfig,
-                StyleMode, TextStyleConfig,
+                AnsiColor, ColorConfig, FeatureFlags, IconConfig, NetworkConfig, SegmentConfig,
+                SegmentId, StyleConfig, StyleMode, TextStyleConfig,
             };
+            use crate::core::attention::AttentionStyle;
+            use crate::core::format::NumberFormat;
 
             let mut segments = vec![
                 SegmentConfig {
@@ -105,8 +107,21 @@ impl Default for Config {
                 style: StyleConfig {
                     mode: StyleMode::Plain,
                     separator: " | ".to_string(),
+                    icons: None,
                 },
                 segments,
+                locale: None,
+                template: None,
+                colors: std::collections::HashMap::new(),
+                formatting: NumberFormat::default(),
+                attention: AttentionStyle::default(),
+                network: NetworkConfig::default(),
+                flags: FeatureFlags::default(),
+                custom_segments: Vec::new(),
+                alerts: Vec::new(),
+                notifications: None,
+                heartbeat: None,
+                proxy: None,
             }
         }
     }
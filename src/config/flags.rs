@@ -0,0 +1,176 @@
+//! Subsystem kill switches
+//!
+//! Flags can be set remotely through the update manifest's `flags` section
+//! (e.g. to stop RED probing during a probe storm) and locally through the
+//! `[flags]` table in config.toml. Local values always win; unset flags are on.
+
+use crate::core::recovery;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Subsystems that can be switched off
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flag {
+    ColdProbing,
+    GreenProbing,
+    RedProbing,
+    ProxyHealth,
+}
+
+impl Flag {
+    pub const ALL: [Flag; 4] = [
+        Flag::ColdProbing,
+        Flag::GreenProbing,
+        Flag::RedProbing,
+        Flag::ProxyHealth,
+    ];
+
+    /// Key used in the manifest and in `[flags]`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Flag::ColdProbing => "cold_probing",
+            Flag::GreenProbing => "green_probing",
+            Flag::RedProbing => "red_probing",
+            Flag::ProxyHealth => "proxy_health",
+        }
+    }
+}
+
+/// Flag name → enabled; unknown names are kept so newer manifests round-trip
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct FeatureFlags(pub BTreeMap<String, bool>);
+
+impl FeatureFlags {
+    pub fn get(&self, flag: Flag) -> Option<bool> {
+        self.0.get(flag.name()).copied()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Flags from the last fetched update manifest (`manifest-flags.json`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RemoteFlags {
+    pub flags: FeatureFlags,
+    /// Manifest version the flags came from
+    pub manifest_version: Option<String>,
+    /// When the manifest was fetched (RFC 3339)
+    pub fetched_at: Option<String>,
+}
+
+impl RemoteFlags {
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| {
+            home.join(".claude")
+                .join("ccstatus")
+                .join("manifest-flags.json")
+        })
+    }
+
+    /// Load remote flags, treating a missing or corrupt file as empty
+    pub fn load() -> Self {
+        Self::default_path()
+            .map(|path| Self::load_from(&path))
+            .unwrap_or_default()
+    }
+
+    pub fn load_from(path: &Path) -> Self {
+        recovery::load_json(path).unwrap_or_default()
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        match Self::default_path() {
+            Some(path) => self.save_to(&path),
+            None => Ok(()),
+        }
+    }
+
+    pub fn save_to(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        fs::write(path, content)
+    }
+}
+
+/// Where a resolved flag value came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlagSource {
+    Default,
+    Manifest,
+    Local,
+}
+
+impl std::fmt::Display for FlagSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FlagSource::Default => write!(f, "default"),
+            FlagSource::Manifest => write!(f, "manifest"),
+            FlagSource::Local => write!(f, "config.toml"),
+        }
+    }
+}
+
+/// Effective flags: local `[flags]` > manifest > on
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FlagSet {
+    local: FeatureFlags,
+    remote: RemoteFlags,
+}
+
+impl FlagSet {
+    pub fn new(local: FeatureFlags, remote: RemoteFlags) -> Self {
+        Self { local, remote }
+    }
+
+    /// Combine `[flags]` from config.toml with the persisted manifest flags
+    pub fn load(local: &FeatureFlags) -> Self {
+        Self::new(local.clone(), RemoteFlags::load())
+    }
+
+    pub fn resolve(&self, flag: Flag) -> (bool, FlagSource) {
+        if let Some(enabled) = self.local.get(flag) {
+            (enabled, FlagSource::Local)
+        } else if let Some(enabled) = self.remote.flags.get(flag) {
+            (enabled, FlagSource::Manifest)
+        } else {
+            (true, FlagSource::Default)
+        }
+    }
+
+    pub fn is_enabled(&self, flag: Flag) -> bool {
+        self.resolve(flag).0
+    }
+
+    /// One line per flag with its value and source, for `ccstatus about`
+    pub fn render_report(&self) -> String {
+        let mut lines: Vec<String> = Flag::ALL
+            .iter()
+            .map(|flag| {
+                let (enabled, source) = self.resolve(*flag);
+                format!(
+                    "  {:<14} {:<3} ({})",
+                    flag.name(),
+                    if enabled { "on" } else { "off" },
+                    source
+                )
+            })
+            .collect();
+
+        if let Some(version) = &self.remote.manifest_version {
+            lines.push(format!(
+                "  manifest v{} fetched {}",
+                version,
+                self.remote.fetched_at.as_deref().unwrap_or("unknown")
+            ));
+        }
+        lines.join("\n")
+    }
+}
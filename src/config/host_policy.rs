@@ -0,0 +1,93 @@
+//! Outbound host allowlist (`network.allowed_hosts`)
+//!
+//! Every outbound request (API probe, proxy health check, update manifest,
+//! geo detection) is checked against this policy first. An empty allowlist
+//! means no restriction. Patterns are case-insensitive host globs where `*`
+//! matches any run of characters and `?` matches a single character, e.g.
+//! `api.anthropic.com`, `*.anthropic.com`, `gateway-?.corp.example`.
+
+use super::types::NetworkConfig;
+use url::Url;
+
+/// Host allowlist built from `network.allowed_hosts`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HostPolicy {
+    patterns: Vec<String>,
+}
+
+impl HostPolicy {
+    /// Build a policy from host glob patterns (empty = allow everything)
+    pub fn new(patterns: Vec<String>) -> Self {
+        let patterns = patterns
+            .into_iter()
+            .map(|p| p.trim().to_ascii_lowercase())
+            .filter(|p| !p.is_empty())
+            .collect();
+        Self { patterns }
+    }
+
+    /// Build a policy from the `[network]` config section
+    pub fn from_config(config: &NetworkConfig) -> Self {
+        Self::new(config.allowed_hosts.clone())
+    }
+
+    /// Whether an allowlist is configured at all
+    pub fn is_restricted(&self) -> bool {
+        !self.patterns.is_empty()
+    }
+
+    /// Check whether a bare host name is allowed
+    pub fn is_host_allowed(&self, host: &str) -> bool {
+        if !self.is_restricted() {
+            return true;
+        }
+
+        let host = host.trim_end_matches('.').to_ascii_lowercase();
+        self.patterns
+            .iter()
+            .any(|pattern| glob_matches(pattern.as_bytes(), host.as_bytes()))
+    }
+
+    /// Check whether a URL's host is allowed
+    ///
+    /// URLs that cannot be parsed or carry no host are rejected whenever an
+    /// allowlist is configured.
+    pub fn is_url_allowed(&self, url: &str) -> bool {
+        if !self.is_restricted() {
+            return true;
+        }
+
+        match Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_string))
+        {
+            Some(host) => self.is_host_allowed(&host),
+            None => false,
+        }
+    }
+}
+
+/// Case-normalized glob match supporting `*` and `?`
+fn glob_matches(pattern: &[u8], text: &[u8]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == b'?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == b'*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = backtrack {
+            // Let the last `*` absorb one more character and retry
+            p = star_p + 1;
+            t = star_t + 1;
+            backtrack = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == b'*')
+}
@@ -1,4 +1,6 @@
+use super::alerts::{AlertConfig, NotificationsConfig};
 use super::types::Config;
+use crate::core::compat;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -28,6 +30,8 @@ impl ConfigLoader {
             "minimal",
             "gruvbox",
             "nord",
+            "solarized",
+            "monochrome",
             "powerline-dark",
             "powerline-light",
             "powerline-rose-pine",
@@ -89,6 +93,8 @@ impl ConfigLoader {
             "minimal",
             "gruvbox",
             "nord",
+            "solarized",
+            "monochrome",
             "powerline-dark",
             "powerline-light",
             "powerline-rose-pine",
@@ -122,6 +128,9 @@ impl Config {
         }
 
         let content = fs::read_to_string(config_path)?;
+        if let Ok(raw) = toml::from_str::<toml::Table>(&content) {
+            compat::record_all(compat::check_config(&raw));
+        }
         let config: Config = toml::from_str(&content)?;
         Ok(config)
     }
@@ -174,6 +183,17 @@ impl Config {
         Ok(())
     }
 
+    /// `alerts` plus the desktop route of `[notifications]`
+    pub fn alert_routes(&self) -> Vec<AlertConfig> {
+        let mut routes = self.alerts.clone();
+        routes.extend(
+            self.notifications
+                .as_ref()
+                .and_then(NotificationsConfig::alert_route),
+        );
+        routes
+    }
+
     /// Validate configuration
     pub fn check(&self) -> Result<(), Box<dyn std::error::Error>> {
         // Basic validation
@@ -189,6 +209,28 @@ impl Config {
             }
         }
 
+        let mut seen_custom = std::collections::HashSet::new();
+        for custom in &self.custom_segments {
+            if custom.name.trim().is_empty() || custom.command.trim().is_empty() {
+                return Err("Custom segments need a name and a command".into());
+            }
+            if !seen_custom.insert(custom.name.as_str()) {
+                return Err(format!("Duplicate custom segment name: {}", custom.name).into());
+            }
+        }
+
+        for alert in &self.alerts {
+            alert.validate()?;
+        }
+
+        let mut seen_sla = std::collections::HashSet::new();
+        for sla in &self.network.sla {
+            sla.validate()?;
+            if !seen_sla.insert(sla.endpoint.as_str()) {
+                return Err(format!("Duplicate SLA for endpoint: {}", sla.endpoint).into());
+            }
+        }
+
         Ok(())
     }
 
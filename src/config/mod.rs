@@ -1,6 +1,18 @@
+pub mod alerts;
 pub mod defaults;
+pub mod flags;
+pub mod host_policy;
 pub mod loader;
+pub mod proxy;
+pub mod tls;
 pub mod types;
 
+pub use alerts::{
+    AlertConfig, AlertEvent, AlertSeverity, AlertSinkKind, AlertTrigger, NotificationsConfig,
+};
+pub use flags::{FeatureFlags, Flag, FlagSet, FlagSource, RemoteFlags};
+pub use host_policy::HostPolicy;
 pub use loader::ConfigLoader;
+pub use proxy::ProxyPolicy;
+pub use tls::TlsPolicy;
 pub use types::*;
@@ -0,0 +1,253 @@
+//! TLS verification for probes and proxy health checks (`[network.tls]`)
+//!
+//! Self-hosted gateways behind a private PKI need their CA trusted: `ca_file`
+//! replaces the system trust store with a PEM bundle for probes and proxy
+//! health checks (update checks keep the system store). `pinned_spki` pins the
+//! gateway's public key (`sha256//<base64>` of the SubjectPublicKeyInfo, as
+//! curl's `--pinnedpubkey` takes it); any key listed is accepted. Gateways that
+//! require mutual TLS get `client_cert` (with `client_key` when the key is in
+//! its own file), its passphrase read from `client_key_password_env`.
+//!
+//! Only libcurl can check pins, so they need the `timings-curl` transport: an
+//! isahc request is refused rather than sent unpinned, including the fallback
+//! after a failed curl probe. `insecure_skip_verify` accepts any certificate
+//! and host name; it is logged, flagged in `ccstatus doctor`,
+//! `ccstatus net status` and the network segment, and marks recorded
+//! certificate chains untrusted.
+//!
+//! An invalid `[network.tls]` (a missing file, a malformed pin) does not fall
+//! back to default verification: probes and health checks are refused until
+//! it is fixed, and the reason is shown wherever the policy is reported.
+
+use super::types::{Config, NetworkConfig, TlsConfig};
+use std::path::{Path, PathBuf};
+
+/// Trust store, public key pins and verification switch for probe transfers
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TlsPolicy {
+    ca_file: Option<PathBuf>,
+    pinned_spki: Vec<String>,
+    insecure_skip_verify: bool,
+    client_identity: Option<ClientIdentity>,
+    /// Why `[network.tls]` could not be applied; every transfer is refused
+    invalid: Option<String>,
+}
+
+/// PEM client certificate and key presented for mutual TLS
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientIdentity {
+    pub cert: PathBuf,
+    /// The certificate file itself when `client_key` is unset
+    pub key: PathBuf,
+    pub key_password: Option<String>,
+}
+
+impl TlsPolicy {
+    /// Policy from `[network.tls]`, expanding a leading `~/` against `home`
+    ///
+    /// Errors on a file that does not exist, a pin that is not a SHA-256 hash or
+    /// an unset client key passphrase variable.
+    pub fn from_config(config: Option<&TlsConfig>, home: Option<&Path>) -> Result<Self, String> {
+        Self::from_config_with(config, home, |name| std::env::var(name).ok())
+    }
+
+    /// [`Self::from_config`], reading the client key passphrase via `get` (for testing)
+    pub fn from_config_with<F>(
+        config: Option<&TlsConfig>,
+        home: Option<&Path>,
+        get: F,
+    ) -> Result<Self, String>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let Some(config) = config else {
+            return Ok(Self::default());
+        };
+
+        let ca_file = existing_file("ca_file", config.ca_file.as_deref(), home)?;
+        let pinned_spki = config
+            .pinned_spki
+            .iter()
+            .map(|pin| normalize_pin(pin))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let client_cert = existing_file("client_cert", config.client_cert.as_deref(), home)?;
+        let client_key = existing_file("client_key", config.client_key.as_deref(), home)?;
+        let client_identity = match (client_cert, client_key) {
+            (Some(cert), key) => {
+                let key_password = match config.client_key_password_env.as_deref() {
+                    Some(name) => Some(get(name).ok_or_else(|| format!("{} is not set", name))?),
+                    None => None,
+                };
+                Some(ClientIdentity {
+                    key: key.unwrap_or_else(|| cert.clone()),
+                    cert,
+                    key_password,
+                })
+            }
+            (None, Some(_)) => return Err("client_key needs client_cert".to_string()),
+            (None, None) => None,
+        };
+
+        Ok(Self {
+            ca_file,
+            pinned_spki,
+            insecure_skip_verify: config.insecure_skip_verify,
+            client_identity,
+            invalid: None,
+        })
+    }
+
+    /// Policy for the `[network.tls]` of a loaded config
+    ///
+    /// An invalid section yields a policy refusing every transfer (see
+    /// [`Self::invalid`]) rather than one with default verification.
+    pub fn from_network(config: &NetworkConfig) -> Self {
+        let policy = Self::from_config(config.tls.as_ref(), dirs::home_dir().as_deref())
+            .unwrap_or_else(Self::invalid);
+        #[cfg(feature = "network-monitoring")]
+        {
+            let logger = crate::core::network::debug_logger::get_debug_logger();
+            if let Some(reason) = policy.error() {
+                logger.warn_sync(
+                    "TlsPolicy",
+                    "config",
+                    &format!("[network.tls] invalid, transfers refused: {}", reason),
+                );
+            } else if policy.skips_verification() {
+                logger.warn_sync(
+                    "TlsPolicy",
+                    "insecure",
+                    "TLS verification disabled by [network.tls] insecure_skip_verify",
+                );
+            }
+        }
+        policy
+    }
+
+    /// Load the policy from the user's config.toml (default verification if
+    /// config.toml cannot be read)
+    pub fn load() -> Self {
+        Self::from_network(&Config::load().unwrap_or_default().network)
+    }
+
+    /// Policy that refuses every transfer because `[network.tls]` is invalid
+    pub fn invalid(reason: String) -> Self {
+        Self {
+            invalid: Some(reason),
+            ..Self::default()
+        }
+    }
+
+    /// Why transfers are refused, when `[network.tls]` is invalid
+    pub fn error(&self) -> Option<&str> {
+        self.invalid.as_deref()
+    }
+
+    /// CA bundle replacing the system trust store
+    pub fn ca_file(&self) -> Option<&Path> {
+        self.ca_file.as_deref()
+    }
+
+    /// Whether transfers must present one of the pinned public keys
+    pub fn is_pinned(&self) -> bool {
+        !self.pinned_spki.is_empty()
+    }
+
+    /// Pins in libcurl's `CURLOPT_PINNEDPUBLICKEY` form (`sha256//a;sha256//b`)
+    pub fn pinned_public_key(&self) -> Option<String> {
+        self.is_pinned().then(|| self.pinned_spki.join(";"))
+    }
+
+    /// Client certificate for gateways that require mutual TLS
+    pub fn client_identity(&self) -> Option<&ClientIdentity> {
+        self.client_identity.as_ref()
+    }
+
+    /// Whether certificate and host name verification is off
+    pub fn skips_verification(&self) -> bool {
+        self.insecure_skip_verify
+    }
+
+    /// `ccstatus doctor` section: trust store, pins and verification
+    pub fn render_report(&self) -> String {
+        if let Some(ref reason) = self.invalid {
+            return format!("ERROR: {}: {}", INVALID_LABEL, reason);
+        }
+        let mut lines = vec![match self.ca_file {
+            Some(ref path) => format!("CA:     {}", path.display()),
+            None => "CA:     system trust store".to_string(),
+        }];
+        if self.is_pinned() {
+            let transport = if cfg!(feature = "timings-curl") {
+                ""
+            } else {
+                " (needs timings-curl; probes are refused)"
+            };
+            lines.push(format!(
+                "pinned: {}{}",
+                self.pinned_spki.join(", "),
+                transport
+            ));
+        }
+        if let Some(ref identity) = self.client_identity {
+            let key = if identity.key == identity.cert {
+                String::new()
+            } else {
+                format!(" (key {})", identity.key.display())
+            };
+            lines.push(format!("client: {}{}", identity.cert.display(), key));
+        }
+        if self.insecure_skip_verify {
+            lines.push(format!("WARNING: {}", INSECURE_LABEL));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Label for `insecure_skip_verify` wherever ccstatus reports on TLS
+pub const INSECURE_LABEL: &str =
+    "TLS verification DISABLED ([network.tls] insecure_skip_verify): any certificate is accepted";
+
+/// Label for an invalid `[network.tls]` wherever ccstatus reports on TLS
+pub const INVALID_LABEL: &str =
+    "[network.tls] is invalid; probes and health checks are refused until it is fixed";
+
+/// `field`'s path with a leading `~/` expanded; None when unset, an error when missing
+fn existing_file(
+    field: &str,
+    path: Option<&str>,
+    home: Option<&Path>,
+) -> Result<Option<PathBuf>, String> {
+    let path = match path.map(str::trim) {
+        Some(path) if !path.is_empty() => path,
+        _ => return Ok(None),
+    };
+    let path = match (path.strip_prefix("~/"), home) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    };
+    if !path.is_file() {
+        return Err(format!("{} '{}' not found", field, path.display()));
+    }
+    Ok(Some(path))
+}
+
+/// `sha256//<base64>` from a pin with or without the prefix
+fn normalize_pin(pin: &str) -> Result<String, String> {
+    let hash = pin.trim();
+    let hash = hash.strip_prefix("sha256//").unwrap_or(hash);
+    // 32 bytes of standard base64: 43 characters and one `=` of padding
+    let valid = hash.len() == 44
+        && hash.ends_with('=')
+        && hash[..43]
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'+' || b == b'/');
+    if !valid {
+        return Err(format!(
+            "pinned_spki '{}' is not a base64 SHA-256 hash (sha256//...)",
+            pin.trim()
+        ));
+    }
+    Ok(format!("sha256//{}", hash))
+}
@@ -1,3 +1,10 @@
+use super::alerts::{AlertConfig, NotificationsConfig};
+use super::flags::FeatureFlags;
+use crate::core::attention::AttentionStyle;
+use crate::core::format::NumberFormat;
+use crate::core::i18n::Locale;
+use crate::core::icons::IconSet;
+use crate::core::segments::cost::{UsageCache, UsageRecord};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -10,14 +17,576 @@ pub struct Config {
     pub style: StyleConfig,
     pub segments: Vec<SegmentConfig>,
     pub theme: String,
+    /// Language of statusline words (`en`, `zh`); unset = `LC_ALL`/`LC_MESSAGES`/`LANG`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<Locale>,
+    /// Layout template, e.g. `"{model} | {directory} {git}\n{network}"` (see `core::layout`);
+    /// unset = fixed segment order with network on its own row
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    /// Per-segment color overrides (`[colors.<segment>]`), applied on top of the
+    /// color scheme named by `theme` (see `core::statusline::themes`)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub colors: HashMap<SegmentId, ColorConfig>,
+    /// Units, separators, decimals and currency symbol for numbers (`[formatting]`)
+    #[serde(default, skip_serializing_if = "NumberFormat::is_default")]
+    pub formatting: NumberFormat,
+    /// How the update notice and network errors stand out (`blink`, `bold`, `inverse`, `none`)
+    #[serde(default, skip_serializing_if = "AttentionStyle::is_default")]
+    pub attention: AttentionStyle,
+    #[serde(default)]
+    pub network: NetworkConfig,
+    /// Local kill-switch overrides (`[flags]`), taking precedence over manifest flags
+    #[serde(default, skip_serializing_if = "FeatureFlags::is_empty")]
+    pub flags: FeatureFlags,
+    /// External command segments (`[[custom_segments]]`), run alongside the built-in ones
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom_segments: Vec<CustomSegmentConfig>,
+    /// Alert routes (`alerts = [{ sink = "slack", on = ["outage>5m"] }]`, see `config::alerts`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub alerts: Vec<AlertConfig>,
+    /// Desktop notifications on outage, recovery and update (`[notifications]`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notifications: Option<NotificationsConfig>,
+    /// Heartbeat file for external watchdogs (`[heartbeat]`), rewritten on every render
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heartbeat: Option<HeartbeatConfig>,
+    /// HTTP proxy for probes and update checks (`[proxy]`), overriding `HTTPS_PROXY`/`HTTP_PROXY`/`NO_PROXY`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<ProxyConfig>,
+}
+
+/// Heartbeat file for external watchdogs (`[heartbeat]`); present = enabled
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HeartbeatConfig {
+    /// Heartbeat file (`~/` expands to the home directory);
+    /// unset = `~/.claude/ccstatus/heartbeat.json`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+/// HTTP proxy override (`[proxy]`); unset fields fall back to the environment
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProxyConfig {
+    /// Proxy for `https://` and `http://` requests, e.g. `http://proxy.corp:3128`;
+    /// `none` connects directly even when `HTTPS_PROXY` is set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Hosts reached without the proxy, replacing `NO_PROXY`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub no_proxy: Option<Vec<String>>,
 }
 
 // Default implementation moved to ui/themes/presets.rs
 
+/// Network monitoring settings (`[network]` table in config.toml)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NetworkConfig {
+    /// Explicit opt-in for live API probes; each probe is a real, token-consuming request.
+    /// Until set (via `ccstatus net enable`) the network segment stays transcript-only.
+    #[serde(default)]
+    pub probe_consent: bool,
+    /// Host globs ccstatus may contact (probes, health checks, updates); empty = any host
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_hosts: Vec<String>,
+    /// Persist probe cookies (e.g. Cloudflare clearance) across invocations, scoped to the API host
+    #[serde(default)]
+    pub persist_cookies: bool,
+    /// Cache the probe host's addresses for a few minutes so lookups stay out of probe latency
+    #[serde(default)]
+    pub dns_cache: bool,
+    /// Also check GET /v1/models on each probe and keep a composite per-endpoint health score
+    #[serde(default)]
+    pub aux_checks: bool,
+    /// Drop GREEN latency samples older than this many seconds, so P80/P95 track
+    /// recent conditions after long idle periods; unset = count-based window only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sample_horizon_secs: Option<u64>,
+    /// Days before expiry a custom gateway's TLS certificate is warned about in the proxy
+    /// health detail (curl probes only); 0 = only untrusted chains, unset = 14
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cert_warn_days: Option<u32>,
+    /// Address family of probes: `auto` (the resolver's choice), `v4`/`v6` to force one,
+    /// or `compare` to also check both families each probe and flag a slow or failing one
+    #[serde(default, skip_serializing_if = "IpFamilyMode::is_auto")]
+    pub ip_family: IpFamilyMode,
+    /// Expression overriding the built-in status classification,
+    /// e.g. `error if consecutive_failures > 2 else default`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_expression: Option<String>,
+    /// Named probe targets (`[[network.targets]]`); when set, the segment renders a strip like `A✓ R✗`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub targets: Vec<NetworkTarget>,
+    /// Alternative base URLs (e.g. a proxy next to the official API) probed in parallel
+    /// with the active one; `ccstatus net status` compares them and the segment names a faster one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub compare_endpoints: Vec<String>,
+    /// Per-endpoint service-level targets (`[[network.sla]]`), reported by `ccstatus net sla`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sla: Vec<SlaTarget>,
+    /// Opt-in encrypted sync of latency baselines between machines (`[network.sync]`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sync: Option<SyncConfig>,
+    /// Hourly unauthenticated checks of alternate regional endpoints (`[network.standby]`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub standby: Option<StandbyConfig>,
+    /// Advisory file written while the API stays in error (`[network.pause_advisory]`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pause_advisory: Option<PauseAdvisoryConfig>,
+    /// `false` never probes with OAuth (subscription) credentials; unset = probe
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oauth_probe: Option<bool>,
+    /// Model, token budget and path of x-api-key probes (`[network.probe]`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub probe: Option<ProbeConfig>,
+    /// Local directory for the monitoring state when `~/.claude` is on NFS/SMB (`~/` expands);
+    /// copied back to `~/.claude/ccstatus` every few minutes. Unset = state stays in the home directory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local_state_dir: Option<String>,
+    /// Retention of the per-probe history behind `ccstatus history` (`[network.history]`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history: Option<HistoryConfig>,
+    /// Rolling window length, status cutoffs and extra percentiles (`[network.window]`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window: Option<WindowConfig>,
+    /// Probes in a row needed before the shown status changes (`[network.hysteresis]`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hysteresis: Option<HysteresisConfig>,
+    /// Allowed share of failed probes and burn-rate warning (`[network.error_budget]`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_budget: Option<ErrorBudgetConfig>,
+    /// SOCKS5 proxy for probes and proxy health checks (`[network.socks5]`), e.g. an SSH tunnel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub socks5: Option<Socks5Config>,
+    /// CA bundle, public key pins and verification for probes and proxy health checks (`[network.tls]`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls: Option<TlsConfig>,
+}
+
+/// SOCKS5 transport for probes and proxy health checks (`[network.socks5]`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Socks5Config {
+    /// Proxy `host:port`, e.g. `127.0.0.1:1080` for `ssh -D 1080`
+    pub address: String,
+    /// User name, for proxies that require authentication
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    /// Environment variable holding the password for `username`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password_env: Option<String>,
+    /// Let the proxy resolve host names (`socks5h`), so lookups also go through the tunnel
+    #[serde(default = "default_remote_dns")]
+    pub remote_dns: bool,
+}
+
+fn default_remote_dns() -> bool {
+    true
+}
+
+/// TLS settings for self-hosted gateways with a private PKI (`[network.tls]`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TlsConfig {
+    /// PEM CA bundle trusted instead of the system store (`~/` expands)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_file: Option<String>,
+    /// Accepted public keys, `sha256//<base64>` of the SubjectPublicKeyInfo (curl transport only)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pinned_spki: Vec<String>,
+    /// Accept any certificate and host name; for testing only, flagged wherever TLS is reported
+    #[serde(default)]
+    pub insecure_skip_verify: bool,
+    /// PEM client certificate for gateways that require mutual TLS (`~/` expands)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_cert: Option<String>,
+    /// PEM private key for `client_cert`; unset = the key is in the certificate file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_key: Option<String>,
+    /// Environment variable holding the passphrase of an encrypted client key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_key_password_env: Option<String>,
+}
+
+impl NetworkConfig {
+    /// Whether OAuth credentials may be probed (`oauth_probe` unset or true)
+    pub fn oauth_probe_enabled(&self) -> bool {
+        self.oauth_probe != Some(false)
+    }
+}
+
+/// Probe request overrides (`[network.probe]`) for gateways without `/v1/messages`
+/// or the default model; unset fields keep the defaults
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProbeConfig {
+    /// Model id, e.g. `claude-3-haiku-20240307`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Output tokens requested, 1-16
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    /// Path appended to the base URL, e.g. `/api/v1/messages`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+/// Rolling latency window overrides (`[network.window]`); unset fields keep the defaults
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WindowConfig {
+    /// GREEN samples kept, 1-120 (default 12)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub samples: Option<usize>,
+    /// Latency at or below this percentile of the window is healthy (default 80)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub healthy_percentile: Option<f64>,
+    /// Latency up to this percentile is degraded, above it error (default 95)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub degraded_percentile: Option<f64>,
+    /// Further percentiles to compute and report, e.g. `[50, 99]`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub percentiles: Vec<f64>,
+}
+
+/// Pause advisory for hook scripts (`[network.pause_advisory]`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PauseAdvisoryConfig {
+    /// Seconds the status must stay error before the advisory is written
+    #[serde(default = "default_pause_after_secs")]
+    pub after_secs: u64,
+    /// Advisory file (`~/` expands to the home directory);
+    /// unset = `~/.claude/ccstatus/pause-advisory.json`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+impl Default for PauseAdvisoryConfig {
+    fn default() -> Self {
+        Self {
+            after_secs: default_pause_after_secs(),
+            path: None,
+        }
+    }
+}
+
+fn default_pause_after_secs() -> u64 {
+    300
+}
+
+/// Probe history retention (`[network.history]`); whichever limit is hit first applies
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HistoryConfig {
+    /// Days a probe record is kept
+    #[serde(default = "default_history_retention_days")]
+    pub retention_days: u32,
+    /// Newest records kept per state partition
+    #[serde(default = "default_history_max_records")]
+    pub max_records: usize,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            retention_days: default_history_retention_days(),
+            max_records: default_history_max_records(),
+        }
+    }
+}
+
+/// Error budget over the probe history (`[network.error_budget]`)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ErrorBudgetConfig {
+    /// Failed probes allowed, in percent of the probes in the window (default 1)
+    #[serde(default = "default_budget_allowed_percent")]
+    pub allowed_failure_percent: f64,
+    /// Hours the budget covers, 1-720 (default 24)
+    #[serde(default = "default_budget_window_hours")]
+    pub window_hours: u32,
+    /// Recent span the burn rate is measured over, in minutes (default 60)
+    #[serde(default = "default_budget_burn_window_minutes")]
+    pub burn_window_minutes: u32,
+    /// Burn rate (multiple of the sustainable failure rate) from which the segment warns (default 10)
+    #[serde(default = "default_budget_burn_alert")]
+    pub burn_alert: f64,
+}
+
+impl Default for ErrorBudgetConfig {
+    fn default() -> Self {
+        Self {
+            allowed_failure_percent: default_budget_allowed_percent(),
+            window_hours: default_budget_window_hours(),
+            burn_window_minutes: default_budget_burn_window_minutes(),
+            burn_alert: default_budget_burn_alert(),
+        }
+    }
+}
+
+fn default_budget_allowed_percent() -> f64 {
+    1.0
+}
+
+fn default_budget_window_hours() -> u32 {
+    24
+}
+
+fn default_budget_burn_window_minutes() -> u32 {
+    60
+}
+
+fn default_budget_burn_alert() -> f64 {
+    10.0
+}
+
+/// Debounced status transitions (`[network.hysteresis]`); 1 flips on every probe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HysteresisConfig {
+    /// GREEN/COLD probes in a row classified worse than the shown status before it degrades
+    #[serde(default = "default_hysteresis_probes")]
+    pub degrade_after: u32,
+    /// Probes in a row classified better than the shown status before it recovers
+    #[serde(default = "default_hysteresis_probes")]
+    pub recover_after: u32,
+}
+
+impl Default for HysteresisConfig {
+    fn default() -> Self {
+        Self {
+            degrade_after: default_hysteresis_probes(),
+            recover_after: default_hysteresis_probes(),
+        }
+    }
+}
+
+fn default_hysteresis_probes() -> u32 {
+    1
+}
+
+fn default_history_retention_days() -> u32 {
+    30
+}
+
+fn default_history_max_records() -> usize {
+    20_000
+}
+
+/// Warm-standby region measurement (`[network.standby]`), shown by `ccstatus net regions`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StandbyConfig {
+    /// Minimum seconds between measurement rounds
+    #[serde(default = "default_standby_interval_secs")]
+    pub interval_secs: u64,
+    /// Endpoints measured with a HEAD request each round (`[[network.standby.regions]]`)
+    #[serde(default)]
+    pub regions: Vec<StandbyRegion>,
+}
+
+/// An alternate endpoint, e.g. a regional proxy the user might switch to
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StandbyRegion {
+    pub name: String,
+    /// Base URL, e.g. `https://eu.proxy.example.com`
+    pub url: String,
+}
+
+fn default_standby_interval_secs() -> u64 {
+    3600
+}
+
+/// Address family of probes and proxy health checks (`network.ip_family`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IpFamilyMode {
+    /// Whatever the resolver and libcurl's happy eyeballs pick
+    #[default]
+    Auto,
+    /// IPv4 only
+    #[serde(alias = "ipv4")]
+    V4,
+    /// IPv6 only
+    #[serde(alias = "ipv6")]
+    V6,
+    /// Auto, plus an unauthenticated check over each family per probe
+    Compare,
+}
+
+impl IpFamilyMode {
+    pub fn is_auto(&self) -> bool {
+        *self == Self::Auto
+    }
+}
+
+/// Remote storage flavour for state sync
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncBackendKind {
+    /// S3-compatible object storage (path-style, SigV4)
+    #[default]
+    S3,
+    /// WebDAV server (PUT/GET with basic auth)
+    Webdav,
+}
+
+/// Encrypted state sync settings (`[network.sync]`, requires the `state-sync` feature)
+///
+/// Secrets are never stored in config.toml: each `*_env` names the environment
+/// variable that holds the value.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SyncConfig {
+    pub backend: SyncBackendKind,
+    /// S3: endpoint plus bucket (`https://s3.example.com/bucket`); WebDAV: collection URL
+    pub url: String,
+    /// Object / file name inside `url`
+    #[serde(default = "default_sync_object")]
+    pub object: String,
+    /// S3 signing region
+    #[serde(default = "default_sync_region")]
+    pub region: String,
+    /// S3 access key id, or WebDAV username
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_key_env: Option<String>,
+    /// S3 secret access key, or WebDAV password
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret_key_env: Option<String>,
+    /// Passphrase for client-side encryption (required)
+    pub passphrase_env: String,
+    /// Minimum seconds between automatic syncs
+    #[serde(default = "default_sync_interval_secs")]
+    pub interval_secs: u64,
+}
+
+fn default_sync_object() -> String {
+    "ccstatus-sync.bin".to_string()
+}
+
+fn default_sync_region() -> String {
+    "us-east-1".to_string()
+}
+
+fn default_sync_interval_secs() -> u64 {
+    900
+}
+
+/// A named provider monitored side by side with others (`[[network.targets]]`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NetworkTarget {
+    /// Target name; also partitions its state file (letters, digits, `-` and `_` only)
+    pub name: String,
+    /// Short label in the strip (default: first letter of `name`, uppercased)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// API base URL; unset = the regular credential chain (env > OAuth > shell > Claude config)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// Environment variable holding the token for `base_url`
+    /// (default: `ANTHROPIC_AUTH_TOKEN`, then `ANTHROPIC_API_KEY`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_env: Option<String>,
+}
+
+impl NetworkTarget {
+    /// Whether `name` is safe to use as a state file partition
+    pub fn has_valid_name(&self) -> bool {
+        !self.name.is_empty()
+            && self
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    }
+
+    /// Label shown in the multi-target strip
+    pub fn display_label(&self) -> String {
+        match self.label {
+            Some(ref label) if !label.trim().is_empty() => label.trim().to_string(),
+            _ => self
+                .name
+                .chars()
+                .next()
+                .map(|c| c.to_uppercase().collect())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Service-level target for one endpoint (`[[network.sla]]`)
+///
+/// Compliance is tracked per UTC calendar month from the probes themselves.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SlaTarget {
+    /// `primary` for the default endpoint, otherwise a `[[network.targets]]` name
+    #[serde(default = "default_sla_endpoint")]
+    pub endpoint: String,
+    /// P95 latency of successful probes must stay below this many milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p95_ms: Option<u32>,
+    /// Minimum share of successful probes, in percent (e.g. `99.0`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub availability: Option<f64>,
+}
+
+impl Default for SlaTarget {
+    fn default() -> Self {
+        Self {
+            endpoint: default_sla_endpoint(),
+            p95_ms: None,
+            availability: None,
+        }
+    }
+}
+
+impl SlaTarget {
+    /// Endpoint name of the default (non-target) probe
+    pub const PRIMARY: &'static str = "primary";
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.p95_ms.is_none() && self.availability.is_none() {
+            return Err(format!(
+                "SLA for {} needs p95_ms or availability",
+                self.endpoint
+            ));
+        }
+        match self.availability {
+            Some(percent) if !(percent > 0.0 && percent <= 100.0) => Err(format!(
+                "SLA availability for {} must be a percentage in (0, 100]",
+                self.endpoint
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn default_sla_endpoint() -> String {
+    SlaTarget::PRIMARY.to_string()
+}
+
+/// A segment whose text is the first line of a shell command's stdout (`[[custom_segments]]`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomSegmentConfig {
+    /// Identifies the segment in health records and failure placeholders
+    pub name: String,
+    /// Run with `sh -c` (`cmd /C` on Windows) in the workspace directory
+    pub command: String,
+    /// Budget before the command is killed and a placeholder shown
+    #[serde(default = "default_custom_timeout_ms")]
+    pub timeout_ms: u64,
+    /// Insert before the `[[segments]]` entry at this index; unset = after all of them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<usize>,
+    #[serde(default = "default_custom_icon")]
+    pub icon: String,
+}
+
+fn default_custom_timeout_ms() -> u64 {
+    500
+}
+
+fn default_custom_icon() -> String {
+    "⚙".to_string()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StyleConfig {
     pub mode: StyleMode,
     pub separator: String,
+    /// Status glyph set (`nerd_font`, `unicode`, `ascii`); unset = derived from `mode`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icons: Option<IconSet>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -72,6 +641,31 @@ pub enum SegmentId {
     Git,
     Usage,
     Update,
+    Stopwatch,
+    /// Session cost from transcript usage
+    Cost,
+    /// Spend or tokens per hour over a rolling window
+    BurnRate,
+    /// Time left in the 5-hour usage block
+    Block,
+    /// Week-to-date spend of all sessions against a weekly budget
+    Quota,
+    /// Sessions and cost of the current workspace across sessions
+    Project,
+    /// Lines added and removed by Edit/Write tool calls this session
+    DiffStat,
+    /// Permission mode and output style badge, hidden when both are default
+    Mode,
+    /// `user@host` over SSH or inside a container
+    Host,
+    /// Active Python virtualenv or conda env and the workspace's Node version
+    Env,
+    /// Claude Code and ccstatus versions
+    Version,
+    /// Context left before Claude Code auto-compacts
+    Compact,
+    /// Any `[[custom_segments]]` entry
+    Custom,
     #[cfg(feature = "network-monitoring")]
     Network,
 }
@@ -86,21 +680,50 @@ pub struct SegmentsConfig {
 }
 
 // Data structures compatible with existing main.rs
-#[derive(Deserialize)]
+#[derive(Clone, Deserialize)]
 pub struct Model {
+    /// Model id (`claude-sonnet-4-20250514`), when the host reports it
+    #[serde(default)]
+    pub id: String,
     pub display_name: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Clone, Deserialize)]
 pub struct Workspace {
     pub current_dir: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Clone, Deserialize)]
+pub struct OutputStyle {
+    /// Style name (`default`, `Explanatory`, `Learning` or a custom style)
+    #[serde(default)]
+    pub name: String,
+}
+
+#[derive(Clone, Deserialize)]
 pub struct InputData {
     pub model: Model,
     pub workspace: Workspace,
     pub transcript_path: String,
+    /// Columns available to the statusline, when the host reports them
+    #[serde(default)]
+    pub terminal_width: Option<u16>,
+    /// Active output style, when the host reports it
+    #[serde(default)]
+    pub output_style: Option<OutputStyle>,
+    /// Permission mode (`default`, `plan`, `acceptEdits`, `bypassPermissions`), when reported
+    #[serde(default)]
+    pub permission_mode: Option<String>,
+    /// Transcript usage records, shared by the segments of one render
+    #[serde(skip)]
+    pub usage: UsageCache,
+}
+
+impl InputData {
+    /// Usage records of `transcript_path`, parsed once for all clones of this input
+    pub fn usage_records(&self) -> &[UsageRecord] {
+        self.usage.records(&self.transcript_path)
+    }
 }
 
 // InputData conversion from StatuslineInput for network monitoring integration
@@ -109,6 +732,12 @@ impl From<&StatuslineInput> for InputData {
     fn from(input: &StatuslineInput) -> Self {
         Self {
             model: Model {
+                id: input
+                    .model
+                    .get("id")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .to_string(),
                 display_name: input
                     .model
                     .get("display_name")
@@ -120,6 +749,16 @@ impl From<&StatuslineInput> for InputData {
                 current_dir: input.cwd.clone(),
             },
             transcript_path: input.transcript_path.clone(),
+            terminal_width: input.terminal_width,
+            output_style: input
+                .output_style
+                .get("name")
+                .and_then(|v| v.as_str())
+                .map(|name| OutputStyle {
+                    name: name.to_string(),
+                }),
+            permission_mode: input.permission_mode.clone(),
+            usage: UsageCache::default(),
         }
     }
 }
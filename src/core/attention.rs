@@ -0,0 +1,83 @@
+//! How notices that need the user's attention stand out
+//!
+//! The update notification and the network segment's error state both go
+//! through one `AttentionStyle` (`attention` in config.toml) instead of each
+//! hard-coding a blink escape. Each style closes with its own SGR "off" code
+//! rather than a full reset, so the segment's color around it survives.
+//!
+//! The style is dropped when the terminal gets no escapes at all
+//! (`NO_COLOR`, `TERM=dumb`, `CCSTATUS_COLOR=none`) or when the legacy
+//! `CCSTATUS_FLASH=0` is set.
+
+use crate::core::color;
+use crate::core::env;
+use serde::{Deserialize, Serialize};
+
+/// Emphasis applied to attention-worthy text
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttentionStyle {
+    /// Blinking text (`\x1b[5m`), the historical behaviour
+    #[default]
+    Blink,
+    /// Bold text (`\x1b[1m`) for terminals or users that dislike blinking
+    Bold,
+    /// Swapped foreground and background (`\x1b[7m`)
+    Inverse,
+    /// Plain text
+    None,
+}
+
+impl AttentionStyle {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Parse a config or environment value (`blink`, `bold`, `inverse`, `none`/`off`)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "blink" => Some(AttentionStyle::Blink),
+            "bold" => Some(AttentionStyle::Bold),
+            "inverse" | "reverse" => Some(AttentionStyle::Inverse),
+            "none" | "off" => Some(AttentionStyle::None),
+            _ => None,
+        }
+    }
+
+    /// `configured` style, unless the environment rules escapes out
+    pub fn for_config(configured: AttentionStyle) -> Self {
+        resolve_from_env(configured, |name| std::env::var(name).ok())
+    }
+
+    /// SGR codes switching the style on and off
+    fn codes(self) -> Option<(&'static str, &'static str)> {
+        match self {
+            AttentionStyle::Blink => Some(("5", "25")),
+            AttentionStyle::Bold => Some(("1", "22")),
+            AttentionStyle::Inverse => Some(("7", "27")),
+            AttentionStyle::None => None,
+        }
+    }
+
+    /// `text` wrapped in the style's on/off sequences
+    pub fn apply(self, text: &str) -> String {
+        match self.codes() {
+            Some((on, off)) => format!("\x1b[{}m{}\x1b[{}m", on, text, off),
+            None => text.to_string(),
+        }
+    }
+}
+
+/// Resolution with injectable environment lookup (for testing)
+pub fn resolve_from_env<F>(configured: AttentionStyle, get: F) -> AttentionStyle
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let flash_disabled =
+        get(env::FLASH.name).and_then(|value| env::parse_bool(&value)) == Some(false);
+    if flash_disabled || !color::detect_from_env(&get).has_color() {
+        AttentionStyle::None
+    } else {
+        configured
+    }
+}
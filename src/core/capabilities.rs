@@ -0,0 +1,282 @@
+//! Runtime capability detection, computed once and cached
+//!
+//! What this machine and terminal can do (keychain, libcurl build, color
+//! depth, OSC 8 links, emoji, desktop notifications) is detected here instead
+//! of by each consumer. The renderer, the credential manager and `ccstatus
+//! doctor` all read [`Capabilities::current`].
+//!
+//! Results are cached in `~/.claude/ccstatus/capabilities.json` with a
+//! fingerprint of the environment variables they depend on: a different
+//! terminal, or a changed override, re-detects at once; otherwise command
+//! lookups are redone at most daily so an installed `notify-send` is noticed.
+
+use crate::core::color::{self, ColorLevel};
+use crate::core::env;
+use crate::core::hyperlink;
+use crate::core::recovery;
+use crate::core::scheduler::{stable_hash, Schedule};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Job key of the capability cache in [`Schedule`] terms
+pub const CAPABILITIES_JOB: &str = "capabilities";
+
+/// How long cached capabilities are trusted when the environment is unchanged
+pub const CAPABILITIES_SCHEDULE: Schedule = Schedule::every(24 * 60 * 60);
+
+/// Environment variables detection depends on
+const FINGERPRINT_VARS: [&str; 12] = [
+    "TERM",
+    "TERM_PROGRAM",
+    "COLORTERM",
+    "WT_SESSION",
+    "VTE_VERSION",
+    "KITTY_WINDOW_ID",
+    "NO_COLOR",
+    "CI",
+    env::COLOR.name,
+    env::HYPERLINKS.name,
+    env::EMOJI.name,
+    "PATH",
+];
+
+/// Terminals that draw emoji as boxes or not at all (`TERM` values)
+const NO_EMOJI_TERMS: [&str; 5] = ["linux", "dumb", "vt100", "vt220", "cons25"];
+
+/// Features of the libcurl ccstatus is linked against
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CurlCapabilities {
+    pub version: String,
+    /// TLS backend, e.g. `OpenSSL/3.0.13`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssl: Option<String>,
+    pub http2: bool,
+    pub http3: bool,
+}
+
+impl CurlCapabilities {
+    /// The linked libcurl; None when built without `timings-curl`
+    pub fn detect() -> Option<Self> {
+        #[cfg(feature = "timings-curl")]
+        {
+            let version = curl::Version::get();
+            Some(Self {
+                version: version.version().to_string(),
+                ssl: version.ssl_version().map(str::to_string),
+                http2: version.feature_http2(),
+                http3: version.feature_http3(),
+            })
+        }
+        #[cfg(not(feature = "timings-curl"))]
+        None
+    }
+}
+
+/// What the platform and terminal support
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Capabilities {
+    /// `std::env::consts::OS` of the build
+    pub platform: String,
+    /// macOS Keychain reachable through `security`
+    pub keychain: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub curl: Option<CurlCapabilities>,
+    pub color: ColorLevel,
+    pub hyperlinks: bool,
+    pub emoji: bool,
+    /// Command desktop alerts go through (`osascript`, `notify-send`), `native` with the
+    /// `notifications` feature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notifier: Option<String>,
+    /// Hash of the environment the capabilities were detected in
+    pub fingerprint: String,
+    /// Unix seconds of detection
+    pub detected_at: i64,
+}
+
+impl Capabilities {
+    /// Capabilities of this process, detected or read from the cache on first use
+    pub fn current() -> &'static Capabilities {
+        static CURRENT: OnceLock<Capabilities> = OnceLock::new();
+        CURRENT.get_or_init(|| {
+            Self::load_or_detect(
+                Self::default_path().as_deref(),
+                |name| std::env::var(name).ok(),
+                unix_now(),
+            )
+        })
+    }
+
+    /// Detect from scratch with injectable environment and command lookups (for testing)
+    pub fn detect_with<F, C>(get: F, has_command: C, now: i64) -> Self
+    where
+        F: Fn(&str) -> Option<String>,
+        C: Fn(&str) -> bool,
+    {
+        let keychain = cfg!(target_os = "macos") && has_command("security");
+        // `notifications` builds notify natively and need no command
+        let notifier = if cfg!(feature = "notifications") {
+            Some("native".to_string())
+        } else {
+            if cfg!(target_os = "macos") {
+                Some("osascript")
+            } else if cfg!(unix) {
+                Some("notify-send")
+            } else {
+                None
+            }
+            .filter(|command| has_command(command))
+            .map(str::to_string)
+        };
+
+        Self {
+            platform: std::env::consts::OS.to_string(),
+            keychain,
+            curl: CurlCapabilities::detect(),
+            color: color::detect_from_env(&get),
+            hyperlinks: hyperlink::supports_hyperlinks_from_env(&get),
+            emoji: supports_emoji_from_env(&get),
+            notifier,
+            fingerprint: fingerprint_from_env(&get),
+            detected_at: now,
+        }
+    }
+
+    /// Cached capabilities when still valid for this environment, else detect and cache
+    pub fn load_or_detect<F>(path: Option<&Path>, get: F, now: i64) -> Self
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let fingerprint = fingerprint_from_env(&get);
+        let cached = path.and_then(Self::load).filter(|cached| {
+            cached.fingerprint == fingerprint
+                && !CAPABILITIES_SCHEDULE.is_due(CAPABILITIES_JOB, Some(cached.detected_at), now)
+        });
+        if let Some(cached) = cached {
+            return cached;
+        }
+
+        let path_var = get("PATH");
+        let detected = Self::detect_with(
+            &get,
+            |command| command_on_path(command, path_var.as_deref()),
+            now,
+        );
+        if let Some(path) = path {
+            let _ = detected.save(path);
+        }
+        detected
+    }
+
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| {
+            home.join(".claude")
+                .join("ccstatus")
+                .join("capabilities.json")
+        })
+    }
+
+    /// Load the cache; a missing file is none, a corrupt one is quarantined
+    pub fn load(path: &Path) -> Option<Self> {
+        recovery::load_json(path)
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, content)?;
+        fs::rename(&tmp, path)
+    }
+
+    /// Human-readable listing for `ccstatus doctor`
+    pub fn render_report(&self) -> String {
+        let yes_no = |supported: bool| if supported { "yes" } else { "no" };
+        let color = match self.color {
+            ColorLevel::None => "none",
+            ColorLevel::Ansi16 => "16 colors",
+            ColorLevel::Ansi256 => "256 colors",
+            ColorLevel::TrueColor => "truecolor",
+        };
+        let curl = match &self.curl {
+            Some(curl) => format!(
+                "{}{} (HTTP/2 {}, HTTP/3 {})",
+                curl.version,
+                curl.ssl
+                    .as_deref()
+                    .map(|ssl| format!(", {}", ssl))
+                    .unwrap_or_default(),
+                yes_no(curl.http2),
+                yes_no(curl.http3)
+            ),
+            None => "not linked (timings-curl disabled)".to_string(),
+        };
+
+        [
+            format!("Platform:      {}", self.platform),
+            format!("Keychain:      {}", yes_no(self.keychain)),
+            format!("libcurl:       {}", curl),
+            format!("Color:         {}", color),
+            format!("Hyperlinks:    {}", yes_no(self.hyperlinks)),
+            format!("Emoji:         {}", yes_no(self.emoji)),
+            format!(
+                "Notifications: {}",
+                self.notifier.as_deref().unwrap_or("unavailable")
+            ),
+        ]
+        .join("\n")
+    }
+}
+
+/// Whether the terminal draws emoji; `CCSTATUS_EMOJI=always|never` overrides detection
+pub fn supports_emoji_from_env<F>(get: F) -> bool
+where
+    F: Fn(&str) -> Option<String>,
+{
+    if let Some(forced) = get(env::EMOJI.name).and_then(|value| env::parse_override(&value)) {
+        return forced;
+    }
+
+    let term = get("TERM").unwrap_or_default();
+    if NO_EMOJI_TERMS.contains(&term.as_str()) {
+        return false;
+    }
+    // The legacy Windows console has no emoji font; Windows Terminal does
+    !(cfg!(windows) && term.is_empty() && get("WT_SESSION").is_none())
+}
+
+/// Hash of the detection-relevant variables, hex-encoded
+pub fn fingerprint_from_env<F>(get: F) -> String
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let hash = stable_hash(FINGERPRINT_VARS.iter().flat_map(|name| {
+        let value = get(name).unwrap_or_default();
+        format!("{}={}\0", name, value).into_bytes()
+    }));
+    format!("{:016x}", hash)
+}
+
+/// Whether an executable named `command` is in one of `path`'s directories
+fn command_on_path(command: &str, path: Option<&str>) -> bool {
+    let Some(path) = path else {
+        return false;
+    };
+    std::env::split_paths(path).any(|dir| {
+        let candidate = dir.join(command);
+        candidate.is_file() || (cfg!(windows) && candidate.with_extension("exe").is_file())
+    })
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() as i64)
+        .unwrap_or(0)
+}
@@ -0,0 +1,247 @@
+//! Terminal color capability detection with graceful downgrade
+//!
+//! Claude Code pipes the statusline, so like hyperlinks the color depth is
+//! inferred from the environment: truecolor → 256 → 16 → none. Colors a
+//! terminal can't show are mapped to the nearest one it can, so SSH sessions
+//! and CI logs get readable output instead of raw 24-bit escape sequences.
+//!
+//! `CCSTATUS_COLOR=truecolor|256|16|none` overrides detection; `NO_COLOR`
+//! disables color when no override is set.
+
+use crate::config::AnsiColor;
+use crate::core::env;
+use serde::{Deserialize, Serialize};
+
+/// Terminals known to render 24-bit color (`TERM_PROGRAM` values)
+const TRUECOLOR_TERM_PROGRAMS: [&str; 6] = [
+    "iTerm.app",
+    "WezTerm",
+    "vscode",
+    "ghostty",
+    "Hyper",
+    "Tabby",
+];
+
+/// The 16 standard colors, xterm defaults
+const ANSI16_PALETTE: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// Standard colors at 0°, 60°, … 300° hue: red, yellow, green, cyan, blue, magenta
+const HUES: [u8; 6] = [1, 3, 2, 6, 4, 5];
+
+/// Black, gray, silver, white
+const GRAYS: [u8; 4] = [0, 8, 7, 15];
+
+/// Channel spread below which a color counts as gray for the 16-color palette
+const GRAY_SATURATION: u8 = 48;
+
+/// Brightest channel from which the bright variant of a hue is used
+const BRIGHT_THRESHOLD: u8 = 224;
+
+/// Channel values of the 6×6×6 cube in the 256-color palette
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Color depth the terminal can display, lowest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorLevel {
+    /// No escape sequences at all
+    None,
+    /// The 16 standard colors
+    Ansi16,
+    /// The xterm 256-color palette
+    Ansi256,
+    /// 24-bit RGB
+    #[default]
+    TrueColor,
+}
+
+impl ColorLevel {
+    /// Parse an override value (`truecolor`/`24bit`, `256`, `16`, `none`/`never`/`0`)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "truecolor" | "24bit" | "24-bit" | "always" => Some(Self::TrueColor),
+            "256" | "ansi256" => Some(Self::Ansi256),
+            "16" | "ansi16" | "basic" => Some(Self::Ansi16),
+            "none" | "never" | "off" | "0" => Some(Self::None),
+            _ => None,
+        }
+    }
+
+    /// `color` as this level can show it; `None` when color is off
+    pub fn downgrade(self, color: &AnsiColor) -> Option<AnsiColor> {
+        match (self, color) {
+            (ColorLevel::None, _) => None,
+            (ColorLevel::TrueColor, _) | (_, AnsiColor::Color16 { .. }) => Some(color.clone()),
+            (ColorLevel::Ansi256, AnsiColor::Color256 { .. }) => Some(color.clone()),
+            (ColorLevel::Ansi256, AnsiColor::Rgb { r, g, b }) => Some(AnsiColor::Color256 {
+                c256: rgb_to_256(*r, *g, *b),
+            }),
+            (ColorLevel::Ansi16, _) => {
+                let (r, g, b) = to_rgb(color);
+                Some(AnsiColor::Color16 {
+                    c16: rgb_to_16(r, g, b),
+                })
+            }
+        }
+    }
+
+    /// Whether any escape sequence (color, bold, blink) may be emitted
+    pub fn has_color(self) -> bool {
+        self != ColorLevel::None
+    }
+}
+
+/// Color depth of the current terminal
+pub fn detect() -> ColorLevel {
+    detect_from_env(|name| std::env::var(name).ok())
+}
+
+/// Detection with injectable environment lookup (for testing)
+pub fn detect_from_env<F>(get: F) -> ColorLevel
+where
+    F: Fn(&str) -> Option<String>,
+{
+    if let Some(level) = get(env::COLOR.name).and_then(|value| ColorLevel::parse(&value)) {
+        return level;
+    }
+    if get("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+        return ColorLevel::None;
+    }
+
+    let term = get("TERM").unwrap_or_default();
+    if term == "dumb" {
+        return ColorLevel::None;
+    }
+
+    let colorterm = get("COLORTERM").unwrap_or_default().to_ascii_lowercase();
+    if colorterm == "truecolor" || colorterm == "24bit" {
+        return ColorLevel::TrueColor;
+    }
+    if get("TERM_PROGRAM")
+        .is_some_and(|program| TRUECOLOR_TERM_PROGRAMS.contains(&program.as_str()))
+        || get("WT_SESSION").is_some()
+        || term.contains("direct")
+    {
+        return ColorLevel::TrueColor;
+    }
+    if term.contains("256color") {
+        return ColorLevel::Ansi256;
+    }
+
+    // CI log viewers reliably render only the basic palette
+    if get("CI").is_some() {
+        return ColorLevel::Ansi16;
+    }
+    // Windows 10+ consoles render 24-bit color without setting TERM
+    if term.is_empty() && cfg!(windows) {
+        return ColorLevel::TrueColor;
+    }
+    ColorLevel::Ansi16
+}
+
+/// Nearest 256-color index: the 6×6×6 cube or the 24-step gray ramp
+pub fn rgb_to_256(r: u8, g: u8, b: u8) -> u8 {
+    let level = |c: u8| {
+        CUBE_LEVELS
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, &level)| (level as i32 - c as i32).abs())
+            .map(|(index, _)| index)
+            .unwrap_or(0)
+    };
+    let (ri, gi, bi) = (level(r), level(g), level(b));
+    let cube = (CUBE_LEVELS[ri], CUBE_LEVELS[gi], CUBE_LEVELS[bi]);
+
+    let average = (r as u32 + g as u32 + b as u32) / 3;
+    let gray_index = (average.saturating_sub(3) / 10).min(23) as u8;
+    let gray_value = 8 + 10 * gray_index;
+
+    if distance((r, g, b), (gray_value, gray_value, gray_value)) < distance((r, g, b), cube) {
+        232 + gray_index
+    } else {
+        16 + 36 * ri as u8 + 6 * gi as u8 + bi as u8
+    }
+}
+
+/// Closest of the 16 standard colors, keeping the hue of saturated colors
+///
+/// Plain RGB distance would send muted theme colors to gray; instead grays map
+/// by lightness and everything else to the nearest of the six hues, bright
+/// when the color is light.
+pub fn rgb_to_16(r: u8, g: u8, b: u8) -> u8 {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    if max - min < GRAY_SATURATION {
+        return GRAYS[nearest(&GRAYS.map(|c| ANSI16_PALETTE[c as usize]), (r, g, b))];
+    }
+
+    let (r, g, b, delta) = (r as f32, g as f32, b as f32, (max - min) as f32);
+    let hue = if max as f32 == r {
+        60.0 * ((g - b) / delta)
+    } else if max as f32 == g {
+        60.0 * (2.0 + (b - r) / delta)
+    } else {
+        60.0 * (4.0 + (r - g) / delta)
+    };
+    let sector = ((hue.rem_euclid(360.0) + 30.0) / 60.0) as usize % 6;
+    let base = HUES[sector];
+    if max >= BRIGHT_THRESHOLD {
+        base + 8
+    } else {
+        base
+    }
+}
+
+/// RGB value of any color, using xterm defaults for the palettes
+fn to_rgb(color: &AnsiColor) -> (u8, u8, u8) {
+    match *color {
+        AnsiColor::Rgb { r, g, b } => (r, g, b),
+        AnsiColor::Color16 { c16 } => ANSI16_PALETTE[(c16 % 16) as usize],
+        AnsiColor::Color256 { c256 } => match c256 {
+            0..=15 => ANSI16_PALETTE[c256 as usize],
+            16..=231 => {
+                let index = c256 - 16;
+                (
+                    CUBE_LEVELS[(index / 36) as usize],
+                    CUBE_LEVELS[(index / 6 % 6) as usize],
+                    CUBE_LEVELS[(index % 6) as usize],
+                )
+            }
+            _ => {
+                let value = 8 + 10 * (c256 - 232);
+                (value, value, value)
+            }
+        },
+    }
+}
+
+fn nearest(palette: &[(u8, u8, u8)], rgb: (u8, u8, u8)) -> usize {
+    palette
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, &candidate)| distance(rgb, candidate))
+        .map(|(index, _)| index)
+        .unwrap_or(0)
+}
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let channel = |x: u8, y: u8| (x as i32 - y as i32).pow(2) as u32;
+    channel(a.0, b.0) + channel(a.1, b.1) + channel(a.2, b.2)
+}
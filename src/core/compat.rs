@@ -0,0 +1,215 @@
+//! Compatibility warnings for obsolete settings
+//!
+//! Config keys, environment variables and state fields change names or lose
+//! their meaning over time. Old values keep working where they can, but they
+//! should not be accepted silently: each notice is recorded here once per
+//! process, written to the debug log when first seen, and listed by
+//! `ccstatus doctor`.
+//!
+//! Sources:
+//!
+//! - legacy environment names (`ccstatus_TIMEOUT_MS`), when read (see [`crate::core::env`])
+//! - `CCSTATUS_FLASH`, superseded by `attention` in config.toml
+//! - top-level config.toml keys ccstatus does not know, which are ignored
+//! - state fields migrated on load, such as `last_prompted_version` in the update state
+
+use crate::config::Config;
+use crate::core::env;
+use serde::de::{self, Deserializer, Visitor};
+use serde::Deserialize;
+use std::fmt;
+use std::sync::{Mutex, OnceLock};
+
+/// What a compatibility warning is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CompatKind {
+    EnvVar,
+    ConfigKey,
+    StateField,
+}
+
+impl CompatKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CompatKind::EnvVar => "env",
+            CompatKind::ConfigKey => "config",
+            CompatKind::StateField => "state",
+        }
+    }
+}
+
+/// One non-fatal compatibility notice
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompatWarning {
+    pub kind: CompatKind,
+    /// Variable, key or field the notice is about
+    pub subject: String,
+    pub message: String,
+}
+
+impl CompatWarning {
+    pub fn new(kind: CompatKind, subject: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            subject: subject.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for CompatWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {}: {}",
+            self.kind.as_str(),
+            self.subject,
+            self.message
+        )
+    }
+}
+
+/// Warnings recorded in this process, in the order first seen
+static WARNINGS: Mutex<Vec<CompatWarning>> = Mutex::new(Vec::new());
+
+/// Record a warning, logging it the first time its kind and subject are seen
+pub fn record(warning: CompatWarning) {
+    let mut warnings = WARNINGS.lock().unwrap_or_else(|e| e.into_inner());
+    if warnings
+        .iter()
+        .any(|seen| seen.kind == warning.kind && seen.subject == warning.subject)
+    {
+        return;
+    }
+    warnings.push(warning.clone());
+    drop(warnings);
+    #[cfg(feature = "network-monitoring")]
+    crate::core::network::debug_logger::get_debug_logger().warn_sync(
+        "Compat",
+        "compat_warning",
+        &warning.to_string(),
+    );
+}
+
+/// Record several warnings (see [`record`])
+pub fn record_all(warnings: impl IntoIterator<Item = CompatWarning>) {
+    warnings.into_iter().for_each(record);
+}
+
+/// Warnings recorded so far
+pub fn warnings() -> Vec<CompatWarning> {
+    WARNINGS.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Top-level config.toml keys, the fields of `Config` as serde declares them
+pub fn config_keys() -> &'static [&'static str] {
+    static KEYS: OnceLock<&'static [&'static str]> = OnceLock::new();
+    KEYS.get_or_init(|| {
+        let mut fields: &'static [&'static str] = &[];
+        let _ = Config::deserialize(StructFields(&mut fields));
+        fields
+    })
+}
+
+/// Unknown top-level keys of a parsed config.toml
+pub fn check_config(raw: &toml::Table) -> Vec<CompatWarning> {
+    let known = config_keys();
+    raw.keys()
+        .filter(|key| !known.contains(&key.as_str()))
+        .map(|key| {
+            CompatWarning::new(
+                CompatKind::ConfigKey,
+                key.as_str(),
+                "not a ccstatus setting, ignored",
+            )
+        })
+        .collect()
+}
+
+/// Legacy and superseded environment variables that are set, via `get` (for testing)
+pub fn check_env<F>(get: F) -> Vec<CompatWarning>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut warnings: Vec<CompatWarning> = env::VARIABLES
+        .iter()
+        .filter_map(|var| {
+            let found = var.lookup_with(&get)?;
+            found
+                .deprecated
+                .then(|| legacy_env_warning(found.name, var.name))
+        })
+        .collect();
+    if get(env::FLASH.name).is_some() {
+        warnings.push(CompatWarning::new(
+            CompatKind::EnvVar,
+            env::FLASH.name,
+            "superseded by `attention` in config.toml (`attention = \"none\"` for no blinking)",
+        ));
+    }
+    warnings
+}
+
+/// Warning for a variable read under its legacy name
+pub fn legacy_env_warning(legacy: &str, current: &str) -> CompatWarning {
+    CompatWarning::new(
+        CompatKind::EnvVar,
+        legacy,
+        format!("deprecated, use {}", current),
+    )
+}
+
+/// `ccstatus doctor` section listing `warnings`
+pub fn render_report(warnings: &[CompatWarning]) -> String {
+    if warnings.is_empty() {
+        return "No compatibility warnings".to_string();
+    }
+
+    let mut sorted = warnings.to_vec();
+    sorted.sort_by(|a, b| (a.kind, &a.subject).cmp(&(b.kind, &b.subject)));
+    let subject_width = sorted
+        .iter()
+        .map(|warning| warning.subject.len())
+        .max()
+        .unwrap_or(0);
+    sorted
+        .iter()
+        .map(|warning| {
+            format!(
+                "{:<6} {:<width$}  {}",
+                warning.kind.as_str(),
+                warning.subject,
+                warning.message,
+                width = subject_width
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Deserializer that only notes the field names of the struct asked for
+struct StructFields<'a>(&'a mut &'static [&'static str]);
+
+impl<'de> Deserializer<'de> for StructFields<'_> {
+    type Error = serde::de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("not a struct"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        *self.0 = fields;
+        Err(de::Error::custom("fields noted"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
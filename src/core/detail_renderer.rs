@@ -0,0 +1,145 @@
+//! Grouped multi-line detail block for tooltip-capable consumers
+//!
+//! Structured output modes (`--format json|waybar`) carry this block next to
+//! the statusline text, so every format shares one rendering of the probe
+//! breakdown, last error, proxy health and update status.
+
+#[cfg(feature = "network-monitoring")]
+use crate::core::network::proxy_health::config::ProxyHealthLevel;
+#[cfg(feature = "network-monitoring")]
+use crate::core::network::types::{MonitoringSnapshot, NetworkStatus};
+
+/// Renders the detail block shown in GUI tooltips
+#[derive(Default)]
+pub struct DetailRenderer {
+    /// Persisted network monitoring state
+    #[cfg(feature = "network-monitoring")]
+    snapshot: Option<MonitoringSnapshot>,
+    /// Version awaiting update, if any
+    update_version: Option<String>,
+}
+
+impl DetailRenderer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Include the network group from the persisted monitoring state
+    #[cfg(feature = "network-monitoring")]
+    pub fn with_snapshot(mut self, snapshot: MonitoringSnapshot) -> Self {
+        self.snapshot = Some(snapshot);
+        self
+    }
+
+    /// Include the update group (`None` = up to date)
+    pub fn with_update(mut self, version: Option<String>) -> Self {
+        self.update_version = version;
+        self
+    }
+
+    /// Status keyword for consumers that style by class (waybar `class`)
+    pub fn status_class(&self) -> &'static str {
+        #[cfg(feature = "network-monitoring")]
+        if let Some(ref snapshot) = self.snapshot {
+            return snapshot.status.as_str();
+        }
+        "unknown"
+    }
+
+    /// Render the groups as plain text, one `Heading` line followed by indented items
+    pub fn render(&self) -> String {
+        let mut groups: Vec<(&str, Vec<String>)> = Vec::new();
+
+        #[cfg(feature = "network-monitoring")]
+        if let Some(ref snapshot) = self.snapshot {
+            groups.push(("Network", Self::network_lines(snapshot)));
+        }
+
+        groups.push((
+            "Update",
+            vec![match self.update_version {
+                Some(ref version) => format!("v{} available", version.trim_start_matches('v')),
+                None => format!("v{} (up to date)", env!("CARGO_PKG_VERSION")),
+            }],
+        ));
+
+        groups
+            .into_iter()
+            .map(|(heading, lines)| {
+                std::iter::once(heading.to_string())
+                    .chain(lines.into_iter().map(|line| format!("  {}", line)))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[cfg(feature = "network-monitoring")]
+    fn network_lines(snapshot: &MonitoringSnapshot) -> Vec<String> {
+        let network = &snapshot.network;
+        let status = match snapshot.status {
+            NetworkStatus::Healthy => "healthy",
+            NetworkStatus::Degraded => "degraded",
+            NetworkStatus::Error => "error",
+            NetworkStatus::Unknown => "unknown",
+            NetworkStatus::BlockedByPolicy => "blocked by policy",
+        };
+
+        let mut lines = vec![if network.last_http_status == 0 {
+            format!("Status: {}", status)
+        } else {
+            format!(
+                "Status: {} (HTTP {}, {}ms)",
+                status, network.last_http_status, network.latency_ms
+            )
+        }];
+
+        if network.p95_latency_ms > 0 {
+            lines.push(format!(
+                "P95: {}ms ({} samples)",
+                network.p95_latency_ms,
+                network.rolling_totals.len()
+            ));
+        }
+        if !network.breakdown.is_empty() {
+            lines.push(format!("Breakdown: {}", network.breakdown));
+        }
+
+        let proxy = match network.get_proxy_health_level() {
+            Some(ProxyHealthLevel::Healthy) => Some("healthy"),
+            Some(ProxyHealthLevel::Degraded) => Some("degraded"),
+            Some(ProxyHealthLevel::Bad) => Some("bad"),
+            Some(ProxyHealthLevel::Unknown) => Some("unknown"),
+            None => None,
+        };
+        if let Some(proxy) = proxy {
+            match network
+                .proxy_health_detail
+                .as_ref()
+                .and_then(|detail| detail.reason.as_deref())
+            {
+                Some(reason) => lines.push(format!("Proxy: {} ({})", proxy, reason)),
+                None => lines.push(format!("Proxy: {}", proxy)),
+            }
+        }
+
+        if let Some(ref error) = snapshot.last_probe_error {
+            lines.push(format!(
+                "Last error: HTTP {} {} at {}",
+                error.http_status, error.error_type, error.timestamp
+            ));
+        }
+        if let Some(ref error) = snapshot.last_jsonl_error_event {
+            lines.push(format!(
+                "Transcript error: {} {} at {}",
+                error.code, error.message, error.timestamp
+            ));
+        }
+        if !snapshot.timestamp.is_empty() {
+            lines.push(format!("Updated: {}", snapshot.timestamp));
+        }
+
+        lines
+    }
+}
@@ -0,0 +1,407 @@
+//! Typed access to the `CCSTATUS_*` environment variables
+//!
+//! Every variable ccstatus reads is declared here with its parsing rule and
+//! default, and `ccstatus env` lists their effective values. Booleans accept
+//! `1`, `true`, `yes` or `on` and `0`, `false`, `no` or `off` in any case;
+//! other values count as unset. Numbers that do not parse count as unset too.
+//!
+//! Lowercase-prefixed legacy names (`ccstatus_TIMEOUT_MS`) are still read when
+//! the current name is unset, with a compatibility warning (see
+//! [`crate::core::compat`]).
+
+use crate::core::compat;
+use std::str::FromStr;
+
+/// How a variable's value is parsed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnvKind {
+    /// `1`/`true`/`yes`/`on` or `0`/`false`/`no`/`off`
+    Bool,
+    /// Unsigned integer
+    Number,
+    /// Free text, a path or one of a few keywords
+    Text,
+}
+
+impl EnvKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EnvKind::Bool => "bool",
+            EnvKind::Number => "number",
+            EnvKind::Text => "text",
+        }
+    }
+}
+
+/// One `CCSTATUS_*` variable
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnvVar {
+    pub name: &'static str,
+    /// Deprecated spelling still read when `name` is unset
+    pub legacy: Option<&'static str>,
+    pub kind: EnvKind,
+    /// Effective behaviour when unset, for `ccstatus env`
+    pub default: &'static str,
+    pub description: &'static str,
+}
+
+/// Where a variable's value came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvValue {
+    /// Name the value was found under (the legacy one if deprecated)
+    pub name: &'static str,
+    pub value: String,
+    pub deprecated: bool,
+}
+
+pub const DEBUG: EnvVar = EnvVar {
+    name: "CCSTATUS_DEBUG",
+    legacy: None,
+    kind: EnvKind::Bool,
+    default: "false",
+    description: "Write the debug log to ~/.claude/ccstatus/ccstatus-debug.log",
+};
+
+pub const JSONL_FILE: EnvVar = EnvVar {
+    name: "CCSTATUS_JSONL_FILE",
+    legacy: None,
+    kind: EnvKind::Text,
+    default: "~/.claude/ccstatus/ccstatus-jsonl-error.json",
+    description: "Path of the JSONL operational log",
+};
+
+pub const TIMEOUT_MS: EnvVar = EnvVar {
+    name: "CCSTATUS_TIMEOUT_MS",
+    legacy: Some("ccstatus_TIMEOUT_MS"),
+    kind: EnvKind::Number,
+    default: "adaptive (P95 + 500ms within the probe profile bounds)",
+    description: "Probe timeout in milliseconds, capped at 6000",
+};
+
+pub const COLD_WINDOW_MS: EnvVar = EnvVar {
+    name: "CCSTATUS_COLD_WINDOW_MS",
+    legacy: Some("ccstatus_COLD_WINDOW_MS"),
+    kind: EnvKind::Number,
+    default: "5000",
+    description: "State older than this triggers a COLD probe at session start",
+};
+
+pub const JSONL_TAIL_KB: EnvVar = EnvVar {
+    name: "CCSTATUS_JSONL_TAIL_KB",
+    legacy: None,
+    kind: EnvKind::Number,
+    default: "64",
+    description: "Transcript tail scanned for API errors, 1-10240 KB",
+};
+
+pub const RED_WATCHDOG_SECS: EnvVar = EnvVar {
+    name: "CCSTATUS_RED_WATCHDOG_SECS",
+    legacy: None,
+    kind: EnvKind::Number,
+    default: "1800",
+    description: "RED probing on the same transcript error longer than this falls back to GREEN",
+};
+
+pub const IDLE_AFTER_SECS: EnvVar = EnvVar {
+    name: "CCSTATUS_IDLE_AFTER_SECS",
+    legacy: None,
+    kind: EnvKind::Number,
+    default: "600",
+    description: "Transcript untouched this long marks the session idle",
+};
+
+pub const IDLE_GREEN_SECS: EnvVar = EnvVar {
+    name: "CCSTATUS_IDLE_GREEN_SECS",
+    legacy: None,
+    kind: EnvKind::Number,
+    default: "900",
+    description: "GREEN probe interval of idle sessions",
+};
+
+pub const DRYRUN: EnvVar = EnvVar {
+    name: "CCSTATUS_DRYRUN",
+    legacy: None,
+    kind: EnvKind::Bool,
+    default: "false",
+    description: "Log probe decisions without probing or writing state",
+};
+
+pub const PROBE_PROFILE: EnvVar = EnvVar {
+    name: "CCSTATUS_PROBE_PROFILE",
+    legacy: None,
+    kind: EnvKind::Text,
+    default: "standard",
+    description:
+        "Probe profile pinned to the session (standard, conservative, aggressive, minimal)",
+};
+
+pub const FLASH: EnvVar = EnvVar {
+    name: "CCSTATUS_FLASH",
+    legacy: None,
+    kind: EnvKind::Bool,
+    default: "true",
+    description: "false turns off attention styling (superseded by [style] attention)",
+};
+
+pub const COLOR: EnvVar = EnvVar {
+    name: "CCSTATUS_COLOR",
+    legacy: None,
+    kind: EnvKind::Text,
+    default: "detected",
+    description: "Color depth: truecolor, 256, 16 or none",
+};
+
+pub const HYPERLINKS: EnvVar = EnvVar {
+    name: "CCSTATUS_HYPERLINKS",
+    legacy: None,
+    kind: EnvKind::Text,
+    default: "detected",
+    description: "OSC 8 hyperlinks: always or never",
+};
+
+pub const EMOJI: EnvVar = EnvVar {
+    name: "CCSTATUS_EMOJI",
+    legacy: None,
+    kind: EnvKind::Text,
+    default: "detected",
+    description: "Emoji glyphs: always or never",
+};
+
+pub const NO_CREDENTIALS: EnvVar = EnvVar {
+    name: "CCSTATUS_NO_CREDENTIALS",
+    legacy: None,
+    kind: EnvKind::Bool,
+    default: "false",
+    description: "Testing: behave as if no credentials were found",
+};
+
+pub const TEST_OAUTH_PRESENT: EnvVar = EnvVar {
+    name: "CCSTATUS_TEST_OAUTH_PRESENT",
+    legacy: None,
+    kind: EnvKind::Bool,
+    default: "false",
+    description: "Testing: simulate OAuth credentials in the Keychain",
+};
+
+pub const TEST_CHINA_GEO: EnvVar = EnvVar {
+    name: "CCSTATUS_TEST_CHINA_GEO",
+    legacy: None,
+    kind: EnvKind::Bool,
+    default: "false",
+    description: "Testing: region reported by the update geo check in CI",
+};
+
+pub const TEST_OAUTH_EXPIRES_AT: EnvVar = EnvVar {
+    name: "CCSTATUS_TEST_OAUTH_EXPIRES_AT",
+    legacy: None,
+    kind: EnvKind::Number,
+    default: "unset",
+    description: "Testing: expiry (Unix milliseconds) of simulated OAuth credentials",
+};
+
+pub const TEST_HEADERS_FILE: EnvVar = EnvVar {
+    name: "CCSTATUS_TEST_HEADERS_FILE",
+    legacy: None,
+    kind: EnvKind::Text,
+    default: "unset",
+    description: "Testing: file of `Name: value` lines added to OAuth probe headers",
+};
+
+pub const USER_AGENT: EnvVar = EnvVar {
+    name: "CCSTATUS_USER_AGENT",
+    legacy: None,
+    kind: EnvKind::Text,
+    default: "built-in Claude CLI profile",
+    description: "User-Agent of OAuth probes",
+};
+
+pub const ANTHROPIC_BETA: EnvVar = EnvVar {
+    name: "CCSTATUS_ANTHROPIC_BETA",
+    legacy: None,
+    kind: EnvKind::Text,
+    default: "built-in Claude CLI profile",
+    description: "anthropic-beta header of OAuth probes",
+};
+
+pub const STAINLESS_PACKAGE_VERSION: EnvVar = EnvVar {
+    name: "CCSTATUS_STAINLESS_PACKAGE_VERSION",
+    legacy: None,
+    kind: EnvKind::Text,
+    default: "built-in Claude CLI profile",
+    description: "X-Stainless-Package-Version header of OAuth probes",
+};
+
+pub const STAINLESS_OS: EnvVar = EnvVar {
+    name: "CCSTATUS_STAINLESS_OS",
+    legacy: None,
+    kind: EnvKind::Text,
+    default: "built-in Claude CLI profile",
+    description: "X-Stainless-OS header of OAuth probes",
+};
+
+pub const STAINLESS_ARCH: EnvVar = EnvVar {
+    name: "CCSTATUS_STAINLESS_ARCH",
+    legacy: None,
+    kind: EnvKind::Text,
+    default: "built-in Claude CLI profile",
+    description: "X-Stainless-Arch header of OAuth probes",
+};
+
+pub const STAINLESS_RUNTIME: EnvVar = EnvVar {
+    name: "CCSTATUS_STAINLESS_RUNTIME",
+    legacy: None,
+    kind: EnvKind::Text,
+    default: "built-in Claude CLI profile",
+    description: "X-Stainless-Runtime header of OAuth probes",
+};
+
+pub const STAINLESS_RUNTIME_VERSION: EnvVar = EnvVar {
+    name: "CCSTATUS_STAINLESS_RUNTIME_VERSION",
+    legacy: None,
+    kind: EnvKind::Text,
+    default: "built-in Claude CLI profile",
+    description: "X-Stainless-Runtime-Version header of OAuth probes",
+};
+
+/// Every variable, in the order `ccstatus env` lists them
+pub const VARIABLES: &[EnvVar] = &[
+    DEBUG,
+    JSONL_FILE,
+    TIMEOUT_MS,
+    COLD_WINDOW_MS,
+    JSONL_TAIL_KB,
+    RED_WATCHDOG_SECS,
+    IDLE_AFTER_SECS,
+    IDLE_GREEN_SECS,
+    DRYRUN,
+    PROBE_PROFILE,
+    FLASH,
+    COLOR,
+    HYPERLINKS,
+    EMOJI,
+    USER_AGENT,
+    ANTHROPIC_BETA,
+    STAINLESS_PACKAGE_VERSION,
+    STAINLESS_OS,
+    STAINLESS_ARCH,
+    STAINLESS_RUNTIME,
+    STAINLESS_RUNTIME_VERSION,
+    NO_CREDENTIALS,
+    TEST_OAUTH_PRESENT,
+    TEST_OAUTH_EXPIRES_AT,
+    TEST_CHINA_GEO,
+    TEST_HEADERS_FILE,
+];
+
+/// `1`/`true`/`yes`/`on` → true, `0`/`false`/`no`/`off` → false, else None
+pub fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+/// `always`/`never` or a boolean, for variables that force a detected capability
+pub fn parse_override(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "always" => Some(true),
+        "never" => Some(false),
+        other => parse_bool(other),
+    }
+}
+
+impl EnvVar {
+    /// Value under the current name, else the legacy one, via `get` (for testing)
+    pub fn lookup_with<F>(&self, get: F) -> Option<EnvValue>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        if let Some(value) = get(self.name) {
+            return Some(EnvValue {
+                name: self.name,
+                value,
+                deprecated: false,
+            });
+        }
+        let legacy = self.legacy?;
+        get(legacy).map(|value| EnvValue {
+            name: legacy,
+            value,
+            deprecated: true,
+        })
+    }
+
+    /// Value from the process environment, warning once about a legacy name
+    pub fn lookup(&self) -> Option<EnvValue> {
+        let found = self.lookup_with(|name| std::env::var(name).ok())?;
+        if found.deprecated {
+            self.warn_deprecated(found.name);
+        }
+        Some(found)
+    }
+
+    /// Raw value, if set
+    pub fn raw(&self) -> Option<String> {
+        self.lookup().map(|found| found.value)
+    }
+
+    /// Boolean value; None when unset or not a recognized boolean
+    pub fn bool(&self) -> Option<bool> {
+        self.raw().as_deref().and_then(parse_bool)
+    }
+
+    /// Whether the variable is set to a true value
+    pub fn is_enabled(&self) -> bool {
+        self.bool().unwrap_or(false)
+    }
+
+    /// Parsed number; None when unset or unparseable
+    pub fn number<T: FromStr>(&self) -> Option<T> {
+        self.raw().and_then(|value| value.trim().parse().ok())
+    }
+
+    fn warn_deprecated(&self, legacy: &'static str) {
+        compat::record(compat::legacy_env_warning(legacy, self.name));
+    }
+}
+
+/// `ccstatus env`: effective value of every variable, via `get` (for testing)
+pub fn render_report<F>(get: F) -> String
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let width = VARIABLES
+        .iter()
+        .map(|var| var.name.len())
+        .max()
+        .unwrap_or(0);
+    let mut lines = Vec::new();
+    for var in VARIABLES {
+        let value = match var.lookup_with(&get) {
+            Some(found) => {
+                let mut value = format!("{:?}", found.value);
+                if var.kind == EnvKind::Bool && parse_bool(&found.value).is_none() {
+                    value.push_str(" (not a boolean, ignored)");
+                }
+                if var.kind == EnvKind::Number && found.value.trim().parse::<u64>().is_err() {
+                    value.push_str(" (not a number, ignored)");
+                }
+                if found.deprecated {
+                    value.push_str(&format!(" (from deprecated {})", found.name));
+                }
+                value
+            }
+            None => format!("unset (default: {})", var.default),
+        };
+        lines.push(format!("{:width$}  {}", var.name, value, width = width));
+        lines.push(format!(
+            "{:width$}  {} · {}",
+            "",
+            var.kind.as_str(),
+            var.description,
+            width = width
+        ));
+    }
+    lines.join("\n")
+}
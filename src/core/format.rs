@@ -0,0 +1,126 @@
+//! Number formatting for latency and cost
+//!
+//! Segments format numbers through a `NumberFormat` (the `[formatting]` table
+//! in config.toml) instead of `format!("{}ms")`, so units, thousands
+//! separators, decimal places and the currency symbol are chosen in one place.
+//! The defaults reproduce the historical output (`1200ms`, `$0.42`).
+//!
+//! Network timing breakdowns (`DNS:20ms|…|Total:2650ms`) are diagnostics and
+//! always stay in milliseconds.
+
+use serde::{Deserialize, Serialize};
+
+/// Decimal places for seconds when `decimals` is unset
+const DEFAULT_SECONDS_DECIMALS: u8 = 1;
+
+/// Decimal places for amounts of money when `decimals` is unset
+const DEFAULT_COST_DECIMALS: u8 = 2;
+
+/// Most decimal places honoured
+const MAX_DECIMALS: u8 = 6;
+
+/// Unit latencies are shown in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LatencyUnit {
+    /// `1200ms`
+    #[default]
+    Ms,
+    /// `1.2s`
+    S,
+    /// Milliseconds below one second, seconds from there on
+    Auto,
+}
+
+/// Number display options (`[formatting]` table)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NumberFormat {
+    pub latency_unit: LatencyUnit,
+    /// Inserted between groups of three digits, e.g. `","` for `1,200ms`; empty = none
+    pub thousands_separator: String,
+    /// Decimal places for seconds and money; unset = 1 for seconds, 2 for money
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decimals: Option<u8>,
+    pub currency_symbol: String,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            latency_unit: LatencyUnit::Ms,
+            thousands_separator: String::new(),
+            decimals: None,
+            currency_symbol: "$".to_string(),
+        }
+    }
+}
+
+impl NumberFormat {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// `1,200ms`, `1.2s`, … per `latency_unit`
+    pub fn latency_ms(&self, ms: u64) -> String {
+        let seconds = match self.latency_unit {
+            LatencyUnit::Ms => false,
+            LatencyUnit::S => true,
+            LatencyUnit::Auto => ms >= 1000,
+        };
+        if seconds {
+            format!(
+                "{}s",
+                self.decimal(
+                    ms as f64 / 1000.0,
+                    self.decimals.unwrap_or(DEFAULT_SECONDS_DECIMALS)
+                )
+            )
+        } else {
+            format!("{}ms", self.integer(ms))
+        }
+    }
+
+    /// `$1,234.50`: currency symbol, grouped integer part, `decimals` places
+    pub fn cost(&self, amount: f64) -> String {
+        let formatted = self.decimal(amount.abs(), self.decimals.unwrap_or(DEFAULT_COST_DECIMALS));
+        let sign = if amount < 0.0 && formatted.chars().any(|c| ('1'..='9').contains(&c)) {
+            "-"
+        } else {
+            ""
+        };
+        format!("{}{}{}", sign, self.currency_symbol, formatted)
+    }
+
+    /// `n` with `thousands_separator` between digit groups
+    pub fn integer(&self, n: u64) -> String {
+        group_digits(&n.to_string(), &self.thousands_separator)
+    }
+
+    /// Non-negative `value` with `places` decimals and a grouped integer part
+    fn decimal(&self, value: f64, places: u8) -> String {
+        let formatted = format!("{:.*}", places.min(MAX_DECIMALS) as usize, value.max(0.0));
+        match formatted.split_once('.') {
+            Some((whole, fraction)) => format!(
+                "{}.{}",
+                group_digits(whole, &self.thousands_separator),
+                fraction
+            ),
+            None => group_digits(&formatted, &self.thousands_separator),
+        }
+    }
+}
+
+fn group_digits(digits: &str, separator: &str) -> String {
+    if separator.is_empty() || digits.len() <= 3 {
+        return digits.to_string();
+    }
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3 * separator.len());
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            grouped.push_str(separator);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
@@ -0,0 +1,98 @@
+//! Heartbeat file for external watchdogs (`[heartbeat]`)
+//!
+//! When the statusline integration breaks (settings.json edited, the binary
+//! moved, a Claude Code update changing the statusline contract) the line just
+//! disappears, which is easy to miss. With `[heartbeat]` configured, every
+//! successful render rewrites a small JSON file with the render time, process
+//! id and ccstatus version; a systemd timer or launchd job can alert once its
+//! `unix` timestamp (or the file's mtime) stops advancing.
+
+use crate::config::HeartbeatConfig;
+use crate::core::recovery;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Contents of the heartbeat file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Heartbeat {
+    /// Render time (local ISO-8601)
+    pub timestamp: String,
+    /// Render time (Unix seconds), for watchdogs comparing against `date +%s`
+    pub unix: i64,
+    pub pid: u32,
+    pub version: String,
+}
+
+impl Heartbeat {
+    /// Heartbeat of this process, now
+    pub fn now() -> Self {
+        let now = chrono::Local::now();
+        Self {
+            timestamp: now.to_rfc3339_opts(chrono::SecondsFormat::Secs, false),
+            unix: now.timestamp(),
+            pid: std::process::id(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+
+    /// Seconds since the heartbeat at `now` (Unix seconds); 0 for one from the future
+    pub fn age_secs(&self, now: i64) -> u64 {
+        now.saturating_sub(self.unix).max(0) as u64
+    }
+}
+
+/// Writes the heartbeat file after each render
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeartbeatWriter {
+    path: PathBuf,
+}
+
+impl HeartbeatWriter {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Default location: `~/.claude/ccstatus/heartbeat.json`
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| home.join(".claude").join("ccstatus").join("heartbeat.json"))
+    }
+
+    /// Build from `[heartbeat]`, expanding a leading `~/`; None without a home directory
+    pub fn from_config(config: &HeartbeatConfig) -> Option<Self> {
+        let path = match config.path.as_deref().map(str::trim) {
+            Some(path) if !path.is_empty() => match path.strip_prefix("~/") {
+                Some(rest) => dirs::home_dir()?.join(rest),
+                None => PathBuf::from(path),
+            },
+            _ => Self::default_path()?,
+        };
+        Some(Self::new(path))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Replace the heartbeat file atomically
+    ///
+    /// Concurrent sessions render at the same time, so each writes its own
+    /// temp file (suffixed with the pid) before renaming it into place.
+    pub fn write(&self, heartbeat: &Heartbeat) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string(heartbeat)?;
+        let temp_path = self.path.with_extension(format!("{}.tmp", heartbeat.pid));
+        fs::write(&temp_path, content)?;
+        fs::rename(&temp_path, &self.path).inspect_err(|_| {
+            let _ = fs::remove_file(&temp_path);
+        })
+    }
+
+    /// Last heartbeat written; None when missing or unreadable (a corrupt file is quarantined)
+    pub fn load(&self) -> Option<Heartbeat> {
+        recovery::load_json(&self.path)
+    }
+}
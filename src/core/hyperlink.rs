@@ -0,0 +1,81 @@
+//! OSC 8 terminal hyperlinks with plain-text fallback
+//!
+//! Claude Code pipes the statusline, so support can't be probed with isatty;
+//! it is inferred from the terminal's environment instead.
+//! `CCSTATUS_HYPERLINKS=always|never` overrides detection.
+
+use crate::core::env;
+
+/// Release notes for a published version
+pub const RELEASES_URL: &str = "https://github.com/MaurUppi/CCstatus/releases/tag";
+
+/// Anthropic service status page
+pub const STATUS_PAGE_URL: &str = "https://status.anthropic.com";
+
+/// README section documenting each network `error_type`
+pub const ERROR_DOCS_URL: &str = "https://github.com/MaurUppi/CCstatus/blob/main/README_EN.md";
+
+/// Terminals known to render OSC 8 links (`TERM_PROGRAM` values)
+const SUPPORTED_TERM_PROGRAMS: [&str; 6] = [
+    "iTerm.app",
+    "WezTerm",
+    "vscode",
+    "ghostty",
+    "Hyper",
+    "Tabby",
+];
+
+/// Whether the current terminal renders OSC 8 hyperlinks
+pub fn supports_hyperlinks() -> bool {
+    supports_hyperlinks_from_env(|name| std::env::var(name).ok())
+}
+
+/// Detection with injectable environment lookup (for testing)
+pub fn supports_hyperlinks_from_env<F>(get: F) -> bool
+where
+    F: Fn(&str) -> Option<String>,
+{
+    if let Some(forced) = get(env::HYPERLINKS.name).and_then(|value| env::parse_override(&value)) {
+        return forced;
+    }
+
+    let term = get("TERM").unwrap_or_default();
+    if term == "dumb" {
+        return false;
+    }
+
+    get("TERM_PROGRAM").is_some_and(|program| SUPPORTED_TERM_PROGRAMS.contains(&program.as_str()))
+        || get("WT_SESSION").is_some()
+        || get("KITTY_WINDOW_ID").is_some()
+        || term.contains("kitty")
+        || term.contains("foot")
+        // VTE (GNOME Terminal, Tilix, ...) supports OSC 8 since 0.50
+        || get("VTE_VERSION")
+            .and_then(|version| version.parse::<u32>().ok())
+            .is_some_and(|version| version >= 5000)
+}
+
+/// Wrap `text` in an OSC 8 link to `url`, or return it unchanged when disabled
+pub fn link(text: &str, url: &str, enabled: bool) -> String {
+    if enabled {
+        format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, text)
+    } else {
+        text.to_string()
+    }
+}
+
+/// Release notes for `version` (with or without a leading `v`)
+pub fn release_notes_url(version: &str) -> String {
+    format!("{}/v{}", RELEASES_URL, version.trim_start_matches('v'))
+}
+
+/// Where to send the user for an error: the status page for server-side
+/// outages, otherwise the docs anchor for the specific `error_type`
+pub fn error_help_url(error_type: &str) -> String {
+    match error_type {
+        "api_error" | "overloaded_error" | "server_error" | "socket_hang_up" => {
+            STATUS_PAGE_URL.to_string()
+        }
+        _ => format!("{}#{}", ERROR_DOCS_URL, error_type),
+    }
+}
@@ -0,0 +1,161 @@
+//! Localized status words and labels
+//!
+//! Segments and `StatusRenderer` look words up in a `Locale` instead of
+//! embedding them, the same way glyphs come from an `IconSet`. `locale` in
+//! config.toml picks the language; when unset it follows `LC_ALL`,
+//! `LC_MESSAGES` then `LANG` (`zh_CN.UTF-8` → Chinese, anything else → English).
+//!
+//! Numbers, units, HTTP codes and `P95:`/`Total:` labels are not translated, so
+//! scripts parsing the statusline work in every language.
+
+use serde::{Deserialize, Serialize};
+
+/// Language of statusline words
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Locale {
+    #[default]
+    En,
+    Zh,
+}
+
+/// A translatable word or phrase, resolved per `Locale`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Text {
+    /// `Update v2.3.0!`
+    UpdateAvailable,
+    /// Self-update in progress
+    Installing,
+    /// `Updated v2.3.0!`
+    Updated,
+    /// Network status could not be determined
+    Unknown,
+    /// No API credentials in the environment
+    EnvVarsNotFound,
+    /// Live probes not consented to
+    ProbesOff,
+    /// `API error 529`
+    ApiError,
+    /// Probe deferred after 429 / bot challenge
+    Cooldown,
+    /// Countdown to the rate limit reset after a 429
+    ResetsIn,
+    /// Error budget burn rate badge
+    Budget,
+    /// Faster endpoint among `network.compare_endpoints`
+    Alternative,
+    /// Cloudflare (or similar) challenge page
+    BotChallenge,
+    /// OAuth login without probe details
+    OAuthMode,
+    /// OAuth credentials found but `network.oauth_probe = false` (or not built)
+    OAuthOff,
+    /// Probe host name did not resolve
+    DnsFailed,
+    /// TCP connection refused or unreachable
+    ConnectRefused,
+    /// TLS handshake or certificate failure
+    TlsFailed,
+    /// No response within the probe timeout
+    TimedOut,
+    /// `[network.tls] insecure_skip_verify` is on
+    TlsInsecure,
+    /// Countdown to the end of the usage block, `2h10m left`
+    BlockLeft,
+    /// Context past the auto-compaction threshold
+    CompactDue,
+    /// Context left before auto-compaction, `25k to compact`
+    ToCompact,
+}
+
+impl Locale {
+    /// Parse a config value or POSIX locale (`zh`, `zh_CN.UTF-8`, `zh-Hans`, `en_US`)
+    pub fn parse(value: &str) -> Option<Self> {
+        let language = value
+            .trim()
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match language.as_str() {
+            "zh" => Some(Locale::Zh),
+            "en" | "c" | "posix" => Some(Locale::En),
+            _ => None,
+        }
+    }
+
+    /// Locale configured in `locale`, else detected from the environment
+    pub fn for_config(configured: Option<Locale>) -> Self {
+        configured.unwrap_or_else(detect)
+    }
+
+    pub fn text(self, text: Text) -> &'static str {
+        match (self, text) {
+            (Locale::Zh, Text::UpdateAvailable) => "更新",
+            (Locale::Zh, Text::Installing) => "安装中...",
+            (Locale::Zh, Text::Updated) => "已更新",
+            (Locale::Zh, Text::Unknown) => "未知",
+            (Locale::Zh, Text::EnvVarsNotFound) => "未找到环境变量",
+            (Locale::Zh, Text::ProbesOff) => "探测已关闭",
+            (Locale::Zh, Text::ApiError) => "API 错误",
+            (Locale::Zh, Text::Cooldown) => "冷却",
+            (Locale::Zh, Text::ResetsIn) => "限额重置",
+            (Locale::Zh, Text::Budget) => "错误预算",
+            (Locale::Zh, Text::Alternative) => "备选",
+            (Locale::Zh, Text::BotChallenge) => "人机验证",
+            (Locale::Zh, Text::OAuthMode) => "OAuth 模式",
+            (Locale::Zh, Text::OAuthOff) => "OAuth 探测已关闭",
+            (Locale::Zh, Text::DnsFailed) => "DNS 解析失败",
+            (Locale::Zh, Text::ConnectRefused) => "连接被拒绝",
+            (Locale::Zh, Text::TlsFailed) => "TLS 失败",
+            (Locale::Zh, Text::TimedOut) => "超时",
+            (Locale::Zh, Text::TlsInsecure) => "TLS 未验证",
+            (Locale::Zh, Text::BlockLeft) => "后结束",
+            (Locale::Zh, Text::CompactDue) => "即将压缩",
+            (Locale::Zh, Text::ToCompact) => "后压缩",
+
+            (Locale::En, Text::UpdateAvailable) => "Update",
+            (Locale::En, Text::Installing) => "Installing...",
+            (Locale::En, Text::Updated) => "Updated",
+            (Locale::En, Text::Unknown) => "Unknown",
+            (Locale::En, Text::EnvVarsNotFound) => "Env vars NOT Found",
+            (Locale::En, Text::ProbesOff) => "probes off",
+            (Locale::En, Text::ApiError) => "API error",
+            (Locale::En, Text::Cooldown) => "cooldown",
+            (Locale::En, Text::ResetsIn) => "resets in",
+            (Locale::En, Text::Budget) => "budget",
+            (Locale::En, Text::Alternative) => "alt",
+            (Locale::En, Text::BotChallenge) => "Bot challenge",
+            (Locale::En, Text::OAuthMode) => "OAuth mode",
+            (Locale::En, Text::OAuthOff) => "oauth off",
+            (Locale::En, Text::DnsFailed) => "DNS failed",
+            (Locale::En, Text::ConnectRefused) => "refused",
+            (Locale::En, Text::TlsFailed) => "TLS failed",
+            (Locale::En, Text::TimedOut) => "timeout",
+            (Locale::En, Text::TlsInsecure) => "TLS unverified",
+            (Locale::En, Text::BlockLeft) => "left",
+            (Locale::En, Text::CompactDue) => "compact due",
+            (Locale::En, Text::ToCompact) => "to compact",
+        }
+    }
+}
+
+/// Locale of the current environment
+pub fn detect() -> Locale {
+    detect_from_env(|name| std::env::var(name).ok())
+}
+
+/// Detection with injectable environment lookup (for testing)
+///
+/// The first non-empty of `LC_ALL`, `LC_MESSAGES`, `LANG` decides, as in POSIX.
+pub fn detect_from_env<F>(get: F) -> Locale
+where
+    F: Fn(&str) -> Option<String>,
+{
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(&get)
+        .find(|value| !value.trim().is_empty())
+        .and_then(|value| Locale::parse(&value))
+        .unwrap_or_default()
+}
@@ -0,0 +1,101 @@
+//! Icon sets for status glyphs
+//!
+//! Segments and `StatusRenderer` look glyphs up in an `IconSet` instead of
+//! embedding them, so terminals without Nerd Fonts (or without emoji) still
+//! get clean output. `style.icons` in config.toml picks the set; when unset it
+//! follows `style.mode` (`plain` → Unicode, `nerd_font`/`powerline` → Nerd Font),
+//! or is ASCII on terminals that can't draw emoji (the Linux console).
+
+use crate::config::{StyleConfig, StyleMode};
+use serde::{Deserialize, Serialize};
+
+/// Glyph family used for status indicators
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IconSet {
+    /// Nerd Font private-use glyphs (update status), emoji status lights
+    NerdFont,
+    /// Emoji and standard Unicode symbols
+    #[default]
+    Unicode,
+    /// Printable ASCII only
+    Ascii,
+}
+
+/// A status glyph, resolved per `IconSet`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Icon {
+    Healthy,
+    Degraded,
+    Error,
+    Unknown,
+    Blocked,
+    BotChallenge,
+    Check,
+    Cross,
+    Warning,
+    GitDirty,
+    Ahead,
+    Behind,
+    UpdateAvailable,
+    Downloading,
+    Updated,
+}
+
+impl IconSet {
+    /// Set configured for `style`: explicit `icons`, else derived from `mode`
+    pub fn for_style(style: &StyleConfig) -> Self {
+        style.icons.unwrap_or(match style.mode {
+            StyleMode::Plain => IconSet::Unicode,
+            StyleMode::NerdFont | StyleMode::Powerline => IconSet::NerdFont,
+        })
+    }
+
+    /// `for_style`, but a derived set falls back to ASCII where emoji can't be drawn
+    pub fn for_terminal(style: &StyleConfig, emoji: bool) -> Self {
+        match style.icons {
+            None if !emoji => IconSet::Ascii,
+            _ => Self::for_style(style),
+        }
+    }
+
+    pub fn glyph(self, icon: Icon) -> &'static str {
+        match (self, icon) {
+            (IconSet::Ascii, Icon::Healthy) => "[ok]",
+            (IconSet::Ascii, Icon::Degraded) => "[~]",
+            (IconSet::Ascii, Icon::Error) => "[x]",
+            (IconSet::Ascii, Icon::Unknown) => "[?]",
+            (IconSet::Ascii, Icon::Blocked) => "[-]",
+            (IconSet::Ascii, Icon::BotChallenge) => "[!]",
+            (IconSet::Ascii, Icon::Check) => "+",
+            (IconSet::Ascii, Icon::Cross) => "x",
+            (IconSet::Ascii, Icon::Warning) => "!",
+            (IconSet::Ascii, Icon::GitDirty) => "*",
+            (IconSet::Ascii, Icon::Ahead) => "^",
+            (IconSet::Ascii, Icon::Behind) => "v",
+            (IconSet::Ascii, Icon::UpdateAvailable) => "^",
+            (IconSet::Ascii, Icon::Downloading) => "v",
+            (IconSet::Ascii, Icon::Updated) => "+",
+
+            (IconSet::NerdFont, Icon::UpdateAvailable) => "\u{f06b0}",
+            (IconSet::NerdFont, Icon::Downloading) => "\u{f01da}",
+            (IconSet::NerdFont, Icon::Updated) => "\u{f058}",
+
+            (_, Icon::Healthy) => "🟢",
+            (_, Icon::Degraded) => "🟡",
+            (_, Icon::Error) => "🔴",
+            (_, Icon::Unknown) => "⚪",
+            (_, Icon::Blocked) => "⛔",
+            (_, Icon::BotChallenge) => "🛡️",
+            (_, Icon::Check) => "✓",
+            (_, Icon::Cross) => "✗",
+            (_, Icon::Warning) => "⚠",
+            (_, Icon::GitDirty) => "●",
+            (_, Icon::Ahead) => "↑",
+            (_, Icon::Behind) => "↓",
+            (_, Icon::UpdateAvailable) => "⬆",
+            (_, Icon::Downloading) => "⇣",
+            (_, Icon::Updated) => "✔",
+        }
+    }
+}
@@ -0,0 +1,83 @@
+//! Where the statusline input comes from (`--input`)
+//!
+//! Claude Code pipes its JSON on stdin. Scripts, tests and other editors can
+//! pass `--input <path>` instead, or the JSON itself as the argument; `-`
+//! keeps stdin. With `--watch` a file input is re-rendered each time it
+//! changes, one statusline per line, so an editor only has to rewrite the file.
+
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// How often `--watch` checks the input file
+pub const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Source of the statusline JSON
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    /// JSON passed directly as the `--input` argument
+    Inline(String),
+    File(PathBuf),
+}
+
+impl InputSource {
+    /// Interpret the `--input` argument: absent or `-` is stdin, a value
+    /// starting with `{` is inline JSON, anything else a path
+    pub fn from_arg(arg: Option<&str>) -> Self {
+        match arg.map(str::trim) {
+            None | Some("-") => Self::Stdin,
+            Some(value) if value.starts_with('{') => Self::Inline(value.to_string()),
+            Some(path) => Self::File(PathBuf::from(path)),
+        }
+    }
+
+    /// File to watch, for sources that can change
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Self::File(path) => Some(path),
+            _ => None,
+        }
+    }
+
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            Self::Stdin => {
+                let mut content = String::new();
+                io::stdin().lock().read_to_string(&mut content)?;
+                Ok(content)
+            }
+            Self::Inline(json) => Ok(json.clone()),
+            Self::File(path) => fs::read_to_string(path),
+        }
+    }
+}
+
+/// Detects changes to a file by modification time and size
+pub struct FileWatch {
+    path: PathBuf,
+    seen: Option<(Option<SystemTime>, u64)>,
+}
+
+impl FileWatch {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            seen: None,
+        }
+    }
+
+    /// Whether the file changed since the last poll
+    ///
+    /// The first poll of an existing file counts as a change. A missing file
+    /// never does, but once it reappears it is reported again.
+    pub fn poll(&mut self) -> bool {
+        let current = fs::metadata(&self.path)
+            .ok()
+            .map(|metadata| (metadata.modified().ok(), metadata.len()));
+        let changed = current.is_some() && current != self.seen;
+        self.seen = current;
+        changed
+    }
+}
@@ -0,0 +1,203 @@
+//! Template-based statusline layout (`template` in config.toml)
+//!
+//! A template such as `"{model} | {directory} {git}\n{network}"` replaces the
+//! fixed segment ordering: placeholders name segments by id, everything else is
+//! literal text, and `\n` starts a new statusline row. `{{` and `}}` produce
+//! literal braces; unknown placeholders are kept verbatim so typos stay visible.
+//!
+//! Literal text between two placeholders acts as a separator and is dropped
+//! along with a placeholder whose segment rendered nothing, so `"{a} | {b} | {c}"`
+//! with an empty `b` renders as `a | c`. Rows whose placeholders all rendered
+//! nothing are omitted.
+
+use crate::config::SegmentId;
+use crate::core::segments::health::segment_name;
+use std::collections::HashMap;
+
+/// One piece of a template row
+#[derive(Debug, Clone, PartialEq)]
+pub enum LayoutItem {
+    Literal(String),
+    Segment(SegmentId),
+}
+
+/// Parsed layout template
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutTemplate {
+    rows: Vec<Vec<LayoutItem>>,
+}
+
+impl LayoutTemplate {
+    /// Parse `template`, resolving placeholders against every known segment id
+    pub fn parse(template: &str) -> Self {
+        let ids: HashMap<String, SegmentId> = all_segment_ids()
+            .into_iter()
+            .map(|id| (segment_name(id), id))
+            .collect();
+
+        let rows = template
+            .split('\n')
+            .map(|row| parse_row(row, &ids))
+            .collect();
+        Self { rows }
+    }
+
+    /// Segments referenced anywhere in the template
+    pub fn segment_ids(&self) -> Vec<SegmentId> {
+        self.rows
+            .iter()
+            .flatten()
+            .filter_map(|item| match item {
+                LayoutItem::Segment(id) => Some(*id),
+                LayoutItem::Literal(_) => None,
+            })
+            .collect()
+    }
+
+    /// Render every row; `render` returns the styled segment (empty = not shown)
+    pub fn render<F>(&self, mut render: F) -> String
+    where
+        F: FnMut(SegmentId) -> String,
+    {
+        self.rows
+            .iter()
+            .filter_map(|row| render_row(row, &mut render))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn all_segment_ids() -> Vec<SegmentId> {
+    vec![
+        SegmentId::Model,
+        SegmentId::Directory,
+        SegmentId::Git,
+        SegmentId::Usage,
+        SegmentId::Update,
+        SegmentId::Stopwatch,
+        SegmentId::Cost,
+        SegmentId::BurnRate,
+        SegmentId::Block,
+        SegmentId::Quota,
+        SegmentId::Project,
+        SegmentId::DiffStat,
+        SegmentId::Mode,
+        SegmentId::Host,
+        SegmentId::Env,
+        SegmentId::Version,
+        SegmentId::Compact,
+        SegmentId::Custom,
+        #[cfg(feature = "network-monitoring")]
+        SegmentId::Network,
+    ]
+}
+
+fn parse_row(row: &str, ids: &HashMap<String, SegmentId>) -> Vec<LayoutItem> {
+    let mut items = Vec::new();
+    let mut literal = String::new();
+    let mut chars = row.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                let mut closed = false;
+                for c in chars.by_ref() {
+                    if c == '}' {
+                        closed = true;
+                        break;
+                    }
+                    name.push(c);
+                }
+
+                match ids.get(name.trim()) {
+                    Some(id) if closed => {
+                        if !literal.is_empty() {
+                            items.push(LayoutItem::Literal(std::mem::take(&mut literal)));
+                        }
+                        items.push(LayoutItem::Segment(*id));
+                    }
+                    _ => {
+                        literal.push('{');
+                        literal.push_str(&name);
+                        if closed {
+                            literal.push('}');
+                        }
+                    }
+                }
+            }
+            _ => literal.push(ch),
+        }
+    }
+
+    if !literal.is_empty() {
+        items.push(LayoutItem::Literal(literal));
+    }
+    items
+}
+
+fn render_row<F>(row: &[LayoutItem], render: &mut F) -> Option<String>
+where
+    F: FnMut(SegmentId) -> String,
+{
+    let first = row
+        .iter()
+        .position(|item| matches!(item, LayoutItem::Segment(_)));
+    let last = row
+        .iter()
+        .rposition(|item| matches!(item, LayoutItem::Segment(_)));
+    let (Some(first), Some(last)) = (first, last) else {
+        // Literal-only row
+        return Some(
+            row.iter()
+                .map(|item| match item {
+                    LayoutItem::Literal(text) => text.as_str(),
+                    LayoutItem::Segment(_) => "",
+                })
+                .collect(),
+        );
+    };
+
+    let mut output = String::new();
+    let mut separator = String::new();
+    let mut replace_separator = false;
+    let mut rendered_any = false;
+
+    for (index, item) in row.iter().enumerate() {
+        match item {
+            // Leading and trailing text frames the row
+            LayoutItem::Literal(text) if index < first || index > last => output.push_str(text),
+            LayoutItem::Literal(text) => {
+                if replace_separator {
+                    separator.clear();
+                    replace_separator = false;
+                }
+                separator.push_str(text);
+            }
+            LayoutItem::Segment(id) => {
+                let rendered = render(*id);
+                if rendered.is_empty() {
+                    replace_separator = true;
+                    continue;
+                }
+                if rendered_any {
+                    output.push_str(&separator);
+                }
+                output.push_str(&rendered);
+                separator.clear();
+                replace_separator = false;
+                rendered_any = true;
+            }
+        }
+    }
+
+    rendered_any.then_some(output)
+}
@@ -1,6 +1,29 @@
+pub mod attention;
+pub mod capabilities;
+pub mod color;
+pub mod compat;
+pub mod detail_renderer;
+pub mod env;
+pub mod format;
+pub mod heartbeat;
+pub mod hyperlink;
+pub mod i18n;
+pub mod icons;
+pub mod input;
+pub mod layout;
 #[cfg(feature = "network-monitoring")]
 pub mod network;
+pub mod output;
+pub mod perf;
+pub mod preview;
+pub mod recovery;
+pub mod scheduler;
 pub mod segments;
+#[cfg(any(feature = "runtime-tokio", feature = "runtime-smol"))]
+pub mod shutdown;
+pub mod snapshot;
+pub mod state_location;
+pub mod stats;
 pub mod statusline;
 
 pub use statusline::{collect_all_segments, StatusLineGenerator};
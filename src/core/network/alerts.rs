@@ -0,0 +1,567 @@
+//! Alert sinks and their dispatcher (`alerts` in config.toml)
+//!
+//! Sinks only deliver: a webhook, a Slack or Discord webhook, a desktop
+//! notification or a shell command, all behind [`AlertSink`]. What reaches them
+//! is decided by [`AlertDispatcher`], a status hook that runs after every probe.
+//! It derives alerts from the status (outage, degraded, recovered) and from a
+//! pending ccstatus update, and hands each to the routes whose triggers match
+//! (see `config::alerts`):
+//!
+//! - dedup: every alert has a key (the outage's start, the update's version)
+//!   and a route delivers each key once
+//! - debounce: a route sends one event kind at most once per `debounce_secs`,
+//!   so a flapping API does not notify on every transition
+//!
+//! Delivered keys are kept in `~/.claude/ccstatus/alerts.json` for a week. A
+//! failing sink is logged and retried on the next probe.
+
+use crate::config::{
+    AlertConfig, AlertEvent, AlertSeverity, AlertSinkKind, AlertTrigger, HostPolicy,
+};
+use crate::core::network::cookie_jar::unix_now_secs;
+use crate::core::network::debug_logger::get_debug_logger;
+use crate::core::network::status_hooks::{StatusChange, StatusHook};
+use crate::core::network::types::{NetworkError, NetworkStatus};
+use crate::core::recovery;
+use crate::core::segments::custom::shell_command;
+use crate::core::segments::stopwatch::StopwatchSegment;
+use crate::runtime;
+use isahc::config::Configurable;
+use isahc::{HttpClient, Request};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Budget for one webhook delivery
+const WEBHOOK_TIMEOUT_MS: u64 = 3000;
+
+/// How long delivered keys are remembered
+const KEY_RETENTION_SECS: i64 = 7 * 24 * 3600;
+
+/// One alert, as handed to sinks
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Alert {
+    pub event: AlertEvent,
+    pub severity: AlertSeverity,
+    /// Identifies the occurrence for dedup, e.g. `outage:2025-01-25T10:30:00-08:00`
+    pub key: String,
+    /// Seconds the condition has lasted
+    pub duration_secs: u64,
+    pub title: String,
+    pub message: String,
+}
+
+impl Alert {
+    pub fn new(event: AlertEvent, key: String, duration_secs: u64, message: String) -> Self {
+        let title = match event {
+            AlertEvent::Outage => "Claude API outage",
+            AlertEvent::Degraded => "Claude API degraded",
+            AlertEvent::Recovered => "Claude API recovered",
+            AlertEvent::Update => "ccstatus update available",
+        };
+        Self {
+            event,
+            severity: event.severity(),
+            key,
+            duration_secs,
+            title: title.to_string(),
+            message,
+        }
+    }
+}
+
+/// Delivery channel for alerts
+#[async_trait::async_trait]
+pub trait AlertSink: Send + Sync {
+    /// Short name for debug logs and the delivery log
+    fn name(&self) -> &'static str;
+
+    async fn send(&self, alert: &Alert) -> Result<(), NetworkError>;
+}
+
+/// POSTs alerts as JSON: the [`Alert`] itself, Slack's `{"text": ...}` or
+/// Discord's `{"content": ...}`
+pub struct WebhookSink {
+    url: String,
+    /// `Webhook`, `Slack` or `Discord`
+    kind: AlertSinkKind,
+    host_policy: HostPolicy,
+}
+
+impl WebhookSink {
+    pub fn new(url: String, kind: AlertSinkKind, host_policy: HostPolicy) -> Self {
+        Self {
+            url,
+            kind,
+            host_policy,
+        }
+    }
+
+    /// Request body for `alert`
+    pub fn body(&self, alert: &Alert) -> Result<String, NetworkError> {
+        let body = match self.kind {
+            AlertSinkKind::Slack => serde_json::json!({
+                "text": format!("*{}* ({})\n{}", alert.title, alert.severity.as_str(), alert.message)
+            }),
+            AlertSinkKind::Discord => serde_json::json!({
+                "content": format!("**{}** ({})\n{}", alert.title, alert.severity.as_str(), alert.message)
+            }),
+            _ => serde_json::to_value(alert).map_err(|e| {
+                NetworkError::ValidationError(format!("Failed to serialize alert: {}", e))
+            })?,
+        };
+        Ok(body.to_string())
+    }
+}
+
+#[async_trait::async_trait]
+impl AlertSink for WebhookSink {
+    fn name(&self) -> &'static str {
+        self.kind.as_str()
+    }
+
+    async fn send(&self, alert: &Alert) -> Result<(), NetworkError> {
+        if !self.host_policy.is_url_allowed(&self.url) {
+            return Err(NetworkError::BlockedByPolicy(self.url.clone()));
+        }
+
+        let client = HttpClient::builder()
+            .timeout(Duration::from_millis(WEBHOOK_TIMEOUT_MS))
+            .build()
+            .map_err(|e| {
+                NetworkError::HttpError(format!("Failed to create alert client: {}", e))
+            })?;
+        let request = Request::post(&self.url)
+            .header("Content-Type", "application/json")
+            .header(
+                "User-Agent",
+                format!("CCstatus/{}", env!("CARGO_PKG_VERSION")),
+            )
+            .body(self.body(alert)?)
+            .map_err(|e| {
+                NetworkError::HttpError(format!("Alert request creation failed: {}", e))
+            })?;
+
+        let response = client
+            .send_async(request)
+            .await
+            .map_err(|e| NetworkError::HttpError(format!("Alert request failed: {}", e)))?;
+        let status = response.status().as_u16();
+        if !(200..300).contains(&status) {
+            return Err(NetworkError::HttpError(format!(
+                "Alert webhook returned HTTP {}",
+                status
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Desktop notification through `osascript` (macOS) or `notify-send` (Linux, BSD),
+/// or natively with the `notifications` feature (Windows too)
+pub struct DesktopSink;
+
+#[async_trait::async_trait]
+impl AlertSink for DesktopSink {
+    fn name(&self) -> &'static str {
+        "desktop"
+    }
+
+    #[cfg(feature = "notifications")]
+    async fn send(&self, alert: &Alert) -> Result<(), NetworkError> {
+        let alert = alert.clone();
+        // Showing a notification can block on the notification server
+        runtime::spawn_blocking(move || {
+            let mut notification = notify_rust::Notification::new();
+            notification
+                .appname("ccstatus")
+                .summary(&alert.title)
+                .body(&alert.message);
+            #[cfg(all(unix, not(target_os = "macos")))]
+            notification.urgency(match alert.severity {
+                AlertSeverity::Critical => notify_rust::Urgency::Critical,
+                AlertSeverity::Warning | AlertSeverity::Info => notify_rust::Urgency::Normal,
+            });
+            notification.show().map(|_| ())
+        })
+        .await
+        .map_err(|e| NetworkError::ValidationError(format!("Desktop alert failed: {}", e)))?
+        .map_err(|e| NetworkError::ValidationError(format!("Desktop alert failed: {}", e)))
+    }
+
+    #[cfg(not(feature = "notifications"))]
+    async fn send(&self, alert: &Alert) -> Result<(), NetworkError> {
+        let mut command = if cfg!(target_os = "macos") {
+            let mut command = std::process::Command::new("osascript");
+            command.args([
+                "-e",
+                &format!(
+                    "display notification {} with title {}",
+                    applescript_string(&alert.message),
+                    applescript_string(&alert.title)
+                ),
+            ]);
+            command
+        } else if cfg!(unix) {
+            let urgency = match alert.severity {
+                AlertSeverity::Critical => "critical",
+                AlertSeverity::Warning | AlertSeverity::Info => "normal",
+            };
+            let mut command = std::process::Command::new("notify-send");
+            command.args([
+                "-u",
+                urgency,
+                "-a",
+                "ccstatus",
+                &alert.title,
+                &alert.message,
+            ]);
+            command
+        } else {
+            return Err(NetworkError::ValidationError(
+                "Desktop alerts are not supported on this platform".to_string(),
+            ));
+        };
+
+        // Notifiers return at once; the process is not waited on
+        command
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn()
+            .map(|_| ())
+            .map_err(|e| NetworkError::ValidationError(format!("Desktop alert failed: {}", e)))
+    }
+}
+
+#[cfg(not(feature = "notifications"))]
+fn applescript_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Runs a shell command with the alert in `CCSTATUS_ALERT_*` environment variables
+pub struct CommandSink {
+    command: String,
+}
+
+impl CommandSink {
+    pub fn new(command: String) -> Self {
+        Self { command }
+    }
+}
+
+#[async_trait::async_trait]
+impl AlertSink for CommandSink {
+    fn name(&self) -> &'static str {
+        "command"
+    }
+
+    async fn send(&self, alert: &Alert) -> Result<(), NetworkError> {
+        // Like the desktop notifier, the command is started and left to finish on its own
+        shell_command(&self.command)
+            .env("CCSTATUS_ALERT_EVENT", alert.event.as_str())
+            .env("CCSTATUS_ALERT_SEVERITY", alert.severity.as_str())
+            .env("CCSTATUS_ALERT_KEY", &alert.key)
+            .env("CCSTATUS_ALERT_TITLE", &alert.title)
+            .env("CCSTATUS_ALERT_MESSAGE", &alert.message)
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn()
+            .map(|_| ())
+            .map_err(|e| NetworkError::ValidationError(format!("Alert command failed: {}", e)))
+    }
+}
+
+/// Sink for one configured route
+pub fn sink_for(
+    config: &AlertConfig,
+    host_policy: &HostPolicy,
+) -> Result<Box<dyn AlertSink>, NetworkError> {
+    config.validate().map_err(NetworkError::ValidationError)?;
+    Ok(match config.sink {
+        AlertSinkKind::Webhook | AlertSinkKind::Slack | AlertSinkKind::Discord => {
+            Box::new(WebhookSink::new(
+                config.url.clone().unwrap_or_default().trim().to_string(),
+                config.sink,
+                host_policy.clone(),
+            ))
+        }
+        AlertSinkKind::Desktop => Box::new(DesktopSink),
+        AlertSinkKind::Command => {
+            Box::new(CommandSink::new(config.command.clone().unwrap_or_default()))
+        }
+    })
+}
+
+/// A sink with the triggers routed to it
+pub struct AlertRoute {
+    sink: Box<dyn AlertSink>,
+    triggers: Vec<AlertTrigger>,
+    debounce_secs: u64,
+}
+
+impl AlertRoute {
+    pub fn new(sink: Box<dyn AlertSink>, triggers: Vec<AlertTrigger>, debounce_secs: u64) -> Self {
+        Self {
+            sink,
+            triggers,
+            debounce_secs,
+        }
+    }
+
+    pub fn matches(&self, alert: &Alert) -> bool {
+        self.triggers
+            .iter()
+            .any(|trigger| trigger.matches(alert.event, alert.duration_secs))
+    }
+}
+
+/// Deliveries of one route
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RouteLog {
+    /// Event → last delivery (Unix seconds), for debounce
+    #[serde(default)]
+    pub last_sent: BTreeMap<String, i64>,
+    /// Alert key → delivery (Unix seconds), for dedup
+    #[serde(default)]
+    pub keys: BTreeMap<String, i64>,
+}
+
+/// Persisted deliveries by route (`<index>:<sink>`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AlertLog {
+    #[serde(default)]
+    pub routes: BTreeMap<String, RouteLog>,
+}
+
+impl AlertLog {
+    /// Default location: `~/.claude/ccstatus/alerts.json`
+    pub fn default_path() -> Result<PathBuf, NetworkError> {
+        let home = dirs::home_dir().ok_or(NetworkError::HomeDirNotFound)?;
+        Ok(home.join(".claude").join("ccstatus").join("alerts.json"))
+    }
+
+    /// Load the log; a missing or unreadable file yields an empty log
+    pub async fn load(path: &Path) -> Self {
+        runtime::fs::read(path)
+            .await
+            .ok()
+            .and_then(|content| recovery::parse_json(path, &content))
+            .unwrap_or_default()
+    }
+
+    /// Write the log atomically
+    pub async fn save(&self, path: &Path) -> Result<(), NetworkError> {
+        if let Some(parent) = path.parent() {
+            runtime::fs::create_dir_all(parent).await.map_err(|e| {
+                NetworkError::StateFileError(format!("Failed to create alert directory: {}", e))
+            })?;
+        }
+        let content = serde_json::to_string_pretty(self).map_err(|e| {
+            NetworkError::StateFileError(format!("Failed to serialize alert log: {}", e))
+        })?;
+        let temp_path = path.with_extension("tmp");
+        runtime::fs::write(&temp_path, content).await.map_err(|e| {
+            NetworkError::StateFileError(format!("Failed to write alert log: {}", e))
+        })?;
+        runtime::fs::rename(&temp_path, path)
+            .await
+            .map_err(|e| NetworkError::StateFileError(format!("Failed to rename alert log: {}", e)))
+    }
+}
+
+/// Routes alerts to sinks with debounce and dedup
+pub struct AlertDispatcher {
+    routes: Vec<AlertRoute>,
+    log_path: PathBuf,
+    /// ccstatus version the updater is currently announcing
+    pending_update: Option<String>,
+}
+
+impl AlertDispatcher {
+    pub fn new(routes: Vec<AlertRoute>, log_path: PathBuf) -> Self {
+        Self {
+            routes,
+            log_path,
+            pending_update: None,
+        }
+    }
+
+    /// Build from `alerts`; routes that cannot deliver are skipped and logged
+    pub async fn from_config(
+        alerts: &[AlertConfig],
+        host_policy: &HostPolicy,
+    ) -> Result<Self, NetworkError> {
+        let mut routes = Vec::new();
+        for config in alerts {
+            match sink_for(config, host_policy) {
+                Ok(sink) => routes.push(AlertRoute::new(
+                    sink,
+                    config.on.clone(),
+                    config.debounce_secs,
+                )),
+                Err(e) => {
+                    get_debug_logger()
+                        .warn("Alerts", &format!("Alert route disabled: {}", e))
+                        .await
+                }
+            }
+        }
+        Ok(Self::new(routes, AlertLog::default_path()?))
+    }
+
+    /// Announce `version` as an `update` alert (see `UpdateSegment::pending_version`)
+    pub fn with_pending_update(mut self, version: Option<String>) -> Self {
+        self.pending_update = version;
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+
+    /// Alerts describing the state after a probe
+    pub fn alerts_for(&self, change: &StatusChange<'_>) -> Vec<Alert> {
+        let state = change.state;
+        let since = state
+            .monitoring_state
+            .status_since
+            .clone()
+            .unwrap_or_else(|| state.timestamp.clone());
+        let lasted = StopwatchSegment::format_elapsed(change.status_secs as i64);
+
+        let mut alerts = Vec::new();
+        match state.status {
+            NetworkStatus::Error => {
+                let mut message = format!("Claude API has been failing for {}", lasted);
+                if state.network.last_http_status != 0 {
+                    message.push_str(&format!(" (HTTP {}", state.network.last_http_status));
+                    if let Some(error_type) = &state.network.error_type {
+                        message.push_str(&format!(" {}", error_type));
+                    }
+                    message.push(')');
+                }
+                alerts.push(Alert::new(
+                    AlertEvent::Outage,
+                    format!("outage:{}", since),
+                    change.status_secs,
+                    message,
+                ));
+            }
+            NetworkStatus::Degraded => alerts.push(Alert::new(
+                AlertEvent::Degraded,
+                format!("degraded:{}", since),
+                change.status_secs,
+                format!("Claude API has been slow or rate limited for {}", lasted),
+            )),
+            NetworkStatus::Healthy
+                if matches!(
+                    change.previous,
+                    NetworkStatus::Error | NetworkStatus::Degraded
+                ) =>
+            {
+                alerts.push(Alert::new(
+                    AlertEvent::Recovered,
+                    format!("recovered:{}", since),
+                    0,
+                    format!(
+                        "Claude API is healthy again after {}",
+                        change.previous.as_str()
+                    ),
+                ))
+            }
+            _ => {}
+        }
+
+        if let Some(version) = &self.pending_update {
+            alerts.push(Alert::new(
+                AlertEvent::Update,
+                format!("update:{}", version),
+                0,
+                format!(
+                    "ccstatus v{} is available (running v{})",
+                    version,
+                    env!("CARGO_PKG_VERSION")
+                ),
+            ));
+        }
+        alerts
+    }
+
+    /// Deliver `alerts` to every matching route; returns how many deliveries succeeded
+    pub async fn dispatch(&self, alerts: &[Alert], now_secs: i64) -> usize {
+        if self.routes.is_empty() || alerts.is_empty() {
+            return 0;
+        }
+
+        let mut log = AlertLog::load(&self.log_path).await;
+        let mut delivered = 0;
+        for (index, route) in self.routes.iter().enumerate() {
+            let entry = log
+                .routes
+                .entry(format!("{}:{}", index, route.sink.name()))
+                .or_default();
+            entry
+                .keys
+                .retain(|_, sent_at| now_secs - *sent_at < KEY_RETENTION_SECS);
+
+            for alert in alerts.iter().filter(|alert| route.matches(alert)) {
+                if entry.keys.contains_key(&alert.key) {
+                    continue;
+                }
+                let debounced = entry
+                    .last_sent
+                    .get(alert.event.as_str())
+                    .is_some_and(|sent_at| now_secs - sent_at < route.debounce_secs as i64);
+                if debounced {
+                    continue;
+                }
+
+                match route.sink.send(alert).await {
+                    Ok(()) => {
+                        entry.keys.insert(alert.key.clone(), now_secs);
+                        entry
+                            .last_sent
+                            .insert(alert.event.as_str().to_string(), now_secs);
+                        delivered += 1;
+                    }
+                    Err(e) => {
+                        get_debug_logger()
+                            .debug(
+                                "Alerts",
+                                &format!(
+                                    "{} sink failed for {}: {}",
+                                    route.sink.name(),
+                                    alert.key,
+                                    e
+                                ),
+                            )
+                            .await
+                    }
+                }
+            }
+        }
+
+        if let Err(e) = log.save(&self.log_path).await {
+            get_debug_logger()
+                .debug("Alerts", &format!("Failed to save alert log: {}", e))
+                .await;
+        }
+        delivered
+    }
+}
+
+#[async_trait::async_trait]
+impl StatusHook for AlertDispatcher {
+    fn name(&self) -> &'static str {
+        "alerts"
+    }
+
+    async fn on_probe(&self, change: &StatusChange<'_>) -> Result<(), NetworkError> {
+        let alerts = self.alerts_for(change);
+        self.dispatch(&alerts, unix_now_secs()).await;
+        Ok(())
+    }
+}
@@ -0,0 +1,137 @@
+//! Server certificates seen by curl probes
+//!
+//! With the `timings-curl` transport, probes ask libcurl for the server's
+//! certificate chain (`CURLOPT_CERTINFO`) and keep the subject, issuer,
+//! subject alternative names and expiry of each certificate with the
+//! connection details in the monitoring state. A chain that fails
+//! verification still fails the probe, but its certificates are kept and the
+//! chain is marked untrusted.
+//!
+//! For custom gateways (any base URL other than the official API) the proxy
+//! health detail carries a warning when the chain is untrusted or a
+//! certificate expires within `network.cert_warn_days` (14 by default).
+//! `ccstatus net status` shows the chain either way.
+
+use chrono::{DateTime, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+/// Days before expiry a custom gateway's certificate is warned about, unless configured
+pub const DEFAULT_WARN_DAYS: u32 = 14;
+
+/// One certificate of the chain, as libcurl describes it
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CertificateInfo {
+    pub subject: String,
+    pub issuer: String,
+    /// Subject alternative names, such as `DNS:gateway.example.com`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sans: Vec<String>,
+    /// Unix seconds of notAfter; None when the date could not be read
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not_after: Option<i64>,
+}
+
+impl CertificateInfo {
+    /// Certificate from libcurl's `Name:value` entries
+    pub fn from_certinfo(entries: &[String]) -> Self {
+        let mut cert = Self::default();
+        for entry in entries {
+            let Some((name, value)) = entry.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            match name.trim() {
+                "Subject" => cert.subject = value.to_string(),
+                "Issuer" => cert.issuer = value.to_string(),
+                "Expire date" => cert.not_after = parse_expire_date(value),
+                "X509v3 Subject Alternative Name" => {
+                    cert.sans = value
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|name| !name.is_empty())
+                        .map(str::to_string)
+                        .collect();
+                }
+                _ => {}
+            }
+        }
+        cert
+    }
+}
+
+/// Certificate chain of the last probe, server certificate first
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CertificateChain {
+    pub certs: Vec<CertificateInfo>,
+    /// Whether the chain passed verification
+    pub trusted: bool,
+}
+
+impl CertificateChain {
+    /// Chain from libcurl's per-certificate entries; None when there are none
+    pub fn from_certinfo(certs: &[Vec<String>], trusted: bool) -> Option<Self> {
+        if certs.is_empty() {
+            return None;
+        }
+        Some(Self {
+            certs: certs
+                .iter()
+                .map(|entries| CertificateInfo::from_certinfo(entries))
+                .collect(),
+            trusted,
+        })
+    }
+
+    /// The server's own certificate
+    pub fn leaf(&self) -> Option<&CertificateInfo> {
+        self.certs.first()
+    }
+
+    /// Earliest notAfter in the chain, since any expired link breaks it
+    pub fn not_after(&self) -> Option<i64> {
+        self.certs.iter().filter_map(|cert| cert.not_after).min()
+    }
+
+    /// Warning for a custom gateway as of `now`: an untrusted chain, or expiry
+    /// within `warn_days` (0 = no expiry warning)
+    pub fn warning(&self, now: i64, warn_days: u32) -> Option<String> {
+        if !self.trusted {
+            return Some("untrusted certificate chain".to_string());
+        }
+        let not_after = self.not_after()?;
+        if not_after <= now {
+            return Some(format!("certificate expired on {}", format_date(not_after)));
+        }
+        let days = (not_after - now) / 86_400;
+        if warn_days == 0 || days >= warn_days as i64 {
+            return None;
+        }
+        Some(match days {
+            0 => format!("certificate expires today ({})", format_date(not_after)),
+            1 => format!("certificate expires in 1 day ({})", format_date(not_after)),
+            days => format!(
+                "certificate expires in {} days ({})",
+                days,
+                format_date(not_after)
+            ),
+        })
+    }
+}
+
+/// `YYYY-MM-DD` (UTC) of Unix seconds
+pub fn format_date(secs: i64) -> String {
+    DateTime::from_timestamp(secs, 0)
+        .map(|time| time.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+/// libcurl's expire date: `Jan  1 00:00:00 2027 GMT` (OpenSSL) or
+/// `2027-01-01 00:00:00 GMT` (other TLS backends)
+fn parse_expire_date(value: &str) -> Option<i64> {
+    let value = value.split_whitespace().collect::<Vec<_>>().join(" ");
+    let value = value.strip_suffix(" GMT").unwrap_or(&value);
+    ["%b %d %H:%M:%S %Y", "%Y-%m-%d %H:%M:%S"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .map(|time| time.and_utc().timestamp())
+}
@@ -0,0 +1,266 @@
+//! Persisted cookie jar for bot-fight continuity (`network.persist_cookies`)
+//!
+//! Cloudflare clearance cookies (`cf_clearance`, `__cf_bm`) are normally lost
+//! between statusline invocations because both probe engines only keep cookies
+//! in memory. When enabled, HttpMonitor loads this jar before a probe, sends its
+//! cookies as a `Cookie` header on either engine (isahc or curl), and stores any
+//! `Set-Cookie` responses afterwards.
+//!
+//! The jar is scoped to a single API host: loading it for a different host
+//! starts empty, and cookies whose `Domain` does not match the host are ignored.
+//! The file (`~/.claude/ccstatus/cookies.json`) is written atomically with
+//! permission 0600 on Unix.
+
+use crate::core::network::types::NetworkError;
+use crate::core::recovery;
+use crate::runtime;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// A cookie stored for the API host
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredCookie {
+    pub name: String,
+    pub value: String,
+    /// Expiry as Unix seconds (None = session cookie, kept until replaced)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<i64>,
+}
+
+/// On-disk jar layout
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CookieJarFile {
+    host: String,
+    cookies: Vec<StoredCookie>,
+}
+
+/// Cookie jar persisted across invocations for one API host
+#[derive(Debug, Clone)]
+pub struct PersistentCookieJar {
+    path: PathBuf,
+    host: String,
+    cookies: Vec<StoredCookie>,
+    dirty: bool,
+}
+
+impl PersistentCookieJar {
+    /// Default jar location: `~/.claude/ccstatus/cookies.json`
+    pub fn default_path() -> Result<PathBuf, NetworkError> {
+        let home = dirs::home_dir().ok_or(NetworkError::HomeDirNotFound)?;
+        Ok(home.join(".claude").join("ccstatus").join("cookies.json"))
+    }
+
+    /// Load the jar for `host`, dropping expired cookies
+    ///
+    /// A missing or unreadable file, or one recorded for another host, yields an empty jar.
+    pub async fn load(path: &Path, host: &str, now: i64) -> Self {
+        let host = host.to_ascii_lowercase();
+        let stored = runtime::fs::read(path)
+            .await
+            .ok()
+            .and_then(|content| recovery::parse_json::<CookieJarFile>(path, &content))
+            .filter(|file| file.host == host)
+            .unwrap_or_default();
+
+        let total = stored.cookies.len();
+        let cookies: Vec<StoredCookie> = stored
+            .cookies
+            .into_iter()
+            .filter(|cookie| !is_expired(cookie, now))
+            .collect();
+
+        Self {
+            path: path.to_path_buf(),
+            dirty: cookies.len() != total,
+            host,
+            cookies,
+        }
+    }
+
+    /// Host this jar is scoped to
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// Cookies currently held
+    pub fn cookies(&self) -> &[StoredCookie] {
+        &self.cookies
+    }
+
+    /// `Cookie` request header value, or None when the jar is empty
+    pub fn cookie_header(&self) -> Option<String> {
+        if self.cookies.is_empty() {
+            return None;
+        }
+
+        Some(
+            self.cookies
+                .iter()
+                .map(|cookie| format!("{}={}", cookie.name, cookie.value))
+                .collect::<Vec<_>>()
+                .join("; "),
+        )
+    }
+
+    /// Store every cookie from newline-joined `Set-Cookie` header values
+    pub fn store_set_cookie_headers(&mut self, headers: &str, now: i64) {
+        for line in headers.lines() {
+            self.store_set_cookie(line, now);
+        }
+    }
+
+    /// Store one `Set-Cookie` header value
+    ///
+    /// Cookies for a foreign `Domain` are ignored; `Max-Age<=0` or a past `Expires`
+    /// removes the cookie.
+    pub fn store_set_cookie(&mut self, header: &str, now: i64) {
+        let mut parts = header.split(';');
+        let (name, value) = match parts.next().and_then(|pair| pair.split_once('=')) {
+            Some((name, value)) if !name.trim().is_empty() => {
+                (name.trim().to_string(), value.trim().to_string())
+            }
+            _ => return,
+        };
+
+        let mut max_age: Option<i64> = None;
+        let mut expires: Option<i64> = None;
+        for attribute in parts {
+            let (key, val) = match attribute.split_once('=') {
+                Some((key, val)) => (key.trim(), val.trim()),
+                None => (attribute.trim(), ""),
+            };
+            if key.eq_ignore_ascii_case("domain") && !self.domain_matches(val) {
+                return;
+            } else if key.eq_ignore_ascii_case("max-age") {
+                max_age = val.parse().ok();
+            } else if key.eq_ignore_ascii_case("expires") {
+                expires = parse_cookie_date(val);
+            }
+        }
+
+        // Max-Age takes precedence over Expires (RFC 6265 §5.3)
+        let expires_at = max_age.map(|secs| now.saturating_add(secs)).or(expires);
+
+        self.cookies.retain(|cookie| cookie.name != name);
+        let cookie = StoredCookie {
+            name,
+            value,
+            expires_at,
+        };
+        if !is_expired(&cookie, now) {
+            self.cookies.push(cookie);
+        }
+        self.dirty = true;
+    }
+
+    /// Persist the jar if it changed (atomic write, 0600 on Unix)
+    pub async fn save(&mut self) -> Result<(), NetworkError> {
+        if !self.dirty {
+            return Ok(());
+        }
+
+        if let Some(parent) = self.path.parent() {
+            runtime::fs::create_dir_all(parent).await.map_err(|e| {
+                NetworkError::StateFileError(format!(
+                    "Failed to create cookie jar directory: {}",
+                    e
+                ))
+            })?;
+        }
+
+        let file = CookieJarFile {
+            host: self.host.clone(),
+            cookies: self.cookies.clone(),
+        };
+        let content = serde_json::to_string_pretty(&file).map_err(|e| {
+            NetworkError::StateFileError(format!("Failed to serialize cookie jar: {}", e))
+        })?;
+
+        let temp_path = self.path.with_extension("tmp");
+        write_private(&temp_path, content.as_bytes()).await?;
+        runtime::fs::rename(&temp_path, &self.path)
+            .await
+            .map_err(|e| {
+                NetworkError::StateFileError(format!("Failed to rename cookie jar: {}", e))
+            })?;
+
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Domain-match a `Domain` attribute against the jar host (leading dot ignored)
+    fn domain_matches(&self, domain: &str) -> bool {
+        let domain = domain.trim_start_matches('.').to_ascii_lowercase();
+        self.host == domain || self.host.ends_with(&format!(".{}", domain))
+    }
+}
+
+/// Current time as Unix seconds (cookie expiry clock)
+pub fn unix_now_secs() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+/// Parse an `Expires` date: RFC 1123 or the legacy dashed form (`Thu, 15-Oct-26 10:00:00 GMT`)
+fn parse_cookie_date(value: &str) -> Option<i64> {
+    chrono::DateTime::parse_from_rfc2822(value)
+        .map(|dt| dt.timestamp())
+        .or_else(|_| {
+            chrono::NaiveDateTime::parse_from_str(value, "%a, %d-%b-%y %H:%M:%S GMT")
+                .or_else(|_| {
+                    chrono::NaiveDateTime::parse_from_str(value, "%a, %d-%b-%Y %H:%M:%S GMT")
+                })
+                .map(|dt| dt.and_utc().timestamp())
+        })
+        .ok()
+}
+
+fn is_expired(cookie: &StoredCookie, now: i64) -> bool {
+    cookie
+        .expires_at
+        .is_some_and(|expires_at| expires_at <= now)
+}
+
+/// Create (or truncate) a file readable only by the current user
+async fn write_private(path: &Path, content: &[u8]) -> Result<(), NetworkError> {
+    let path = path.to_path_buf();
+    let content = content.to_vec();
+    runtime::spawn_blocking(move || write_private_blocking(&path, &content))
+        .await
+        .map_err(|e| {
+            NetworkError::StateFileError(format!("Failed to join cookie jar write: {}", e))
+        })?
+}
+
+fn write_private_blocking(path: &Path, content: &[u8]) -> Result<(), NetworkError> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options
+        .open(path)
+        .map_err(|e| NetworkError::StateFileError(format!("Failed to open cookie jar: {}", e)))?;
+    file.write_all(content)
+        .map_err(|e| NetworkError::StateFileError(format!("Failed to write cookie jar: {}", e)))?;
+    file.flush()
+        .map_err(|e| NetworkError::StateFileError(format!("Failed to flush cookie jar: {}", e)))?;
+
+    // Tighten permissions on files created before the mode was applied
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600)).map_err(|e| {
+            NetworkError::StateFileError(format!("Failed to set cookie jar permissions: {}", e))
+        })?;
+    }
+
+    Ok(())
+}
@@ -69,9 +69,11 @@ use regex::Regex;
 use serde_json::Value;
 use std::env;
 use std::path::{Path, PathBuf};
-use tokio::fs;
+use std::sync::OnceLock;
 
 use crate::core::network::types::{ApiCredentials, CredentialSource, NetworkError};
+use crate::core::perf::PerfRecorder;
+use crate::runtime::fs;
 
 /// Shell types supported for configuration parsing
 #[derive(Debug, Clone, PartialEq)]
@@ -89,6 +91,10 @@ pub enum ShellType {
 /// Manages credential resolution from multiple sources with shell configuration parsing
 pub struct CredentialManager {
     claude_config_paths: Vec<PathBuf>,
+    /// Fixed base URL for a named target (`[[network.targets]]`); bypasses the source chain
+    target_base_url: Option<String>,
+    /// Environment variable holding the token for `target_base_url`
+    target_token_env: Option<String>,
 }
 
 impl CredentialManager {
@@ -100,8 +106,6 @@ impl CredentialManager {
     const ENV_API_KEY: &'static str = "ANTHROPIC_API_KEY";
 
     // Test control constants
-    const TEST_NO_CREDENTIALS: &'static str = "CCSTATUS_NO_CREDENTIALS";
-    const TEST_OAUTH_PRESENT: &'static str = "CCSTATUS_TEST_OAUTH_PRESENT";
 
     // OAuth constants
     const OAUTH_KEYCHAIN_SERVICE: &'static str = "Claude Code-credentials";
@@ -125,9 +129,20 @@ impl CredentialManager {
 
         Ok(Self {
             claude_config_paths,
+            target_base_url: None,
+            target_token_env: None,
         })
     }
 
+    /// Resolve credentials for a named target: fixed `base_url`, token from `token_env`
+    ///
+    /// Without `token_env` the token comes from `ANTHROPIC_AUTH_TOKEN`, then `ANTHROPIC_API_KEY`.
+    pub fn with_target(mut self, base_url: String, token_env: Option<String>) -> Self {
+        self.target_base_url = Some(base_url);
+        self.target_token_env = token_env;
+        self
+    }
+
     /// Logging helper for credential source start
     async fn log_source_start(
         &self,
@@ -197,7 +212,7 @@ impl CredentialManager {
         &self,
         logger: &crate::core::network::debug_logger::EnhancedDebugLogger,
     ) -> Option<ApiCredentials> {
-        if env::var(Self::TEST_OAUTH_PRESENT).unwrap_or_default() == "1" {
+        if crate::core::env::TEST_OAUTH_PRESENT.is_enabled() {
             logger
                 .debug(
                     "CredentialManager",
@@ -206,16 +221,16 @@ impl CredentialManager {
                 .await;
 
             // Test override with configurable expiry
-            let test_expires_at = env::var("CCSTATUS_TEST_OAUTH_EXPIRES_AT")
-                .ok()
-                .and_then(|s| s.parse::<i64>().ok());
-
-            return Some(ApiCredentials {
-                base_url: Self::OAUTH_FIXED_BASE_URL.to_string(),
-                auth_token: Self::OAUTH_FIXED_TOKEN.to_string(),
-                source: CredentialSource::OAuth,
-                expires_at: test_expires_at,
-            });
+            let test_expires_at = crate::core::env::TEST_OAUTH_EXPIRES_AT.number::<i64>();
+
+            return ApiCredentials::builder(
+                Self::OAUTH_FIXED_BASE_URL,
+                Self::OAUTH_FIXED_TOKEN,
+                CredentialSource::OAuth,
+            )
+            .with_expires_at(test_expires_at)
+            .build()
+            .ok();
         }
         None
     }
@@ -236,16 +251,16 @@ impl CredentialManager {
                     .await;
 
                 // For env token testing, use configurable expiry
-                let test_expires_at = env::var("CCSTATUS_TEST_OAUTH_EXPIRES_AT")
-                    .ok()
-                    .and_then(|s| s.parse::<i64>().ok());
-
-                return Some(ApiCredentials {
-                    base_url: Self::OAUTH_FIXED_BASE_URL.to_string(),
-                    auth_token: Self::OAUTH_FIXED_TOKEN.to_string(),
-                    source: CredentialSource::OAuth,
-                    expires_at: test_expires_at,
-                });
+                let test_expires_at = crate::core::env::TEST_OAUTH_EXPIRES_AT.number::<i64>();
+
+                return ApiCredentials::builder(
+                    Self::OAUTH_FIXED_BASE_URL,
+                    Self::OAUTH_FIXED_TOKEN,
+                    CredentialSource::OAuth,
+                )
+                .with_expires_at(test_expires_at)
+                .build()
+                .ok();
             }
         }
         None
@@ -275,12 +290,21 @@ impl CredentialManager {
     /// ## Test-specific Behavior
     /// - Set CCSTATUS_NO_CREDENTIALS=1 to force return None (for testing unknown scenarios)
     /// - Set CCSTATUS_TEST_OAUTH_PRESENT=1 to simulate OAuth presence on macOS (deterministic testing)
+    ///
+    /// Lookup time is recorded as the `credentials` stage of the invocation's perf report.
     pub async fn get_credentials(&self) -> Result<Option<ApiCredentials>, NetworkError> {
+        let started = std::time::Instant::now();
+        let credentials = self.resolve_credentials().await;
+        PerfRecorder::global().record("credentials", started.elapsed());
+        credentials
+    }
+
+    async fn resolve_credentials(&self) -> Result<Option<ApiCredentials>, NetworkError> {
         use crate::core::network::debug_logger::get_debug_logger;
         let logger = get_debug_logger();
 
         // Test override: force no credentials
-        if env::var(Self::TEST_NO_CREDENTIALS).unwrap_or_default() == "1" {
+        if crate::core::env::NO_CREDENTIALS.is_enabled() {
             logger
                 .debug(
                     "CredentialManager",
@@ -297,6 +321,17 @@ impl CredentialManager {
             )
             .await;
 
+        // Named target: fixed base URL, token from its own environment variable
+        if let Some(ref base_url) = self.target_base_url {
+            self.log_source_start(&logger, "target").await;
+            let creds = self.get_for_target(base_url);
+            match creds {
+                Some(ref creds) => self.log_credentials_found(&logger, "target", creds).await,
+                None => self.log_no_credentials(&logger, "target").await,
+            }
+            return Ok(creds);
+        }
+
         // Priority 1: Environment variables (returns error on failure)
         self.log_source_start(&logger, "environment").await;
         match self.get_from_environment() {
@@ -427,17 +462,30 @@ impl CredentialManager {
 
         // Return credentials when both base URL and token are present and non-empty
         if let (Ok(base_url), Ok(auth_token)) = (base_url, auth_token) {
-            return Ok(Some(ApiCredentials {
-                base_url,
-                auth_token,
-                source: CredentialSource::Environment,
-                expires_at: None,
-            }));
+            return ApiCredentials::builder(base_url, auth_token, CredentialSource::Environment)
+                .build()
+                .map(Some);
         }
 
         Ok(None)
     }
 
+    /// Credentials for a named target (`with_target`), None when its token variable is unset
+    /// or the target URL fails validation
+    fn get_for_target(&self, base_url: &str) -> Option<ApiCredentials> {
+        let get_non_empty_var = |name: &str| env::var(name).ok().filter(|v| !v.trim().is_empty());
+
+        let auth_token = match self.target_token_env {
+            Some(ref token_env) => get_non_empty_var(token_env),
+            None => get_non_empty_var(Self::ENV_AUTH_TOKEN)
+                .or_else(|| get_non_empty_var(Self::ENV_API_KEY)),
+        }?;
+
+        ApiCredentials::builder(base_url, auth_token, CredentialSource::Environment)
+            .build()
+            .ok()
+    }
+
     /// Try to get credentials from macOS OAuth Keychain (macOS only)
     #[cfg(target_os = "macos")]
     async fn get_from_oauth_keychain(&self) -> Result<Option<ApiCredentials>, NetworkError> {
@@ -461,8 +509,15 @@ impl CredentialManager {
             return Ok(Some(env_creds));
         }
 
+        if !crate::core::capabilities::Capabilities::current().keychain {
+            logger
+                .debug("CredentialManager", "Keychain unavailable, skipping")
+                .await;
+            return Ok(None);
+        }
+
         // Check if Claude Code credentials exist in Keychain
-        let output = tokio::task::spawn_blocking(|| {
+        let output = crate::runtime::spawn_blocking(|| {
             std::process::Command::new("security")
                 .arg("find-generic-password")
                 .arg("-s")
@@ -577,32 +632,35 @@ impl CredentialManager {
     ) -> Result<Option<ApiCredentials>, NetworkError> {
         // Phase 1: Look for traditional export statements
         if let Some(creds) = self.parse_export_statements(content)? {
-            return Ok(Some(ApiCredentials {
-                base_url: creds.0,
-                auth_token: creds.1,
-                source: CredentialSource::ShellConfig(source_path.to_path_buf()),
-                expires_at: None,
-            }));
+            return ApiCredentials::builder(
+                creds.0,
+                creds.1,
+                CredentialSource::ShellConfig(source_path.to_path_buf()),
+            )
+            .build()
+            .map(Some);
         }
 
         // Phase 2: Look for function-based variable definitions (like cc-env)
         if let Some(creds) = self.parse_function_variables(content)? {
-            return Ok(Some(ApiCredentials {
-                base_url: creds.0,
-                auth_token: creds.1,
-                source: CredentialSource::ShellConfig(source_path.to_path_buf()),
-                expires_at: None,
-            }));
+            return ApiCredentials::builder(
+                creds.0,
+                creds.1,
+                CredentialSource::ShellConfig(source_path.to_path_buf()),
+            )
+            .build()
+            .map(Some);
         }
 
         // Phase 3: Look for non-exported assignments as fallback (VAR=value without export)
         if let Some(creds) = self.parse_variable_assignments(content)? {
-            return Ok(Some(ApiCredentials {
-                base_url: creds.0,
-                auth_token: creds.1,
-                source: CredentialSource::ShellConfig(source_path.to_path_buf()),
-                expires_at: None,
-            }));
+            return ApiCredentials::builder(
+                creds.0,
+                creds.1,
+                CredentialSource::ShellConfig(source_path.to_path_buf()),
+            )
+            .build()
+            .map(Some);
         }
 
         Ok(None)
@@ -615,11 +673,11 @@ impl CredentialManager {
     ) -> Result<Option<(String, String)>, NetworkError> {
         // Enhanced regex to match export statements with better value extraction
         // Matches: export VAR="value" or export VAR='value' or export VAR=value
-        let export_regex = Regex::new(r#"^\s*export\s+([A-Z_]+)=(.*)"#)
-            .map_err(|e| NetworkError::RegexError(e.to_string()))?;
+        static EXPORT_REGEX: OnceLock<Regex> = OnceLock::new();
+        let export_regex = cached_regex(&EXPORT_REGEX, r#"^\s*export\s+([A-Z_]+)=(.*)"#)?;
 
         // Use the common helper method (skip_export = false for export statements)
-        self.process_shell_variables_with_regex(content, &export_regex, false)
+        self.process_shell_variables_with_regex(content, export_regex, false)
     }
 
     /// Process shell variables using a regex pattern with common logic
@@ -735,18 +793,26 @@ impl CredentialManager {
         content: &str,
     ) -> Result<Option<(String, String)>, NetworkError> {
         // Regex to detect function definitions
-        let function_regex =
-            Regex::new(r#"^\s*(function\s+)?([a-zA-Z_][a-zA-Z0-9_-]*)\s*\(\s*\)\s*\{"#)
-                .map_err(|e| NetworkError::RegexError(e.to_string()))?;
+        static FUNCTION_REGEX: OnceLock<Regex> = OnceLock::new();
+        let function_regex = cached_regex(
+            &FUNCTION_REGEX,
+            r#"^\s*(function\s+)?([a-zA-Z_][a-zA-Z0-9_-]*)\s*\(\s*\)\s*\{"#,
+        )?;
 
         // Regex to detect array assignments within functions
-        let array_start_regex = Regex::new(r#"^\s*local\s+[a-zA-Z_][a-zA-Z0-9_]*\s*=\s*\("#)
-            .map_err(|e| NetworkError::RegexError(e.to_string()))?;
+        static ARRAY_START_REGEX: OnceLock<Regex> = OnceLock::new();
+        let array_start_regex = cached_regex(
+            &ARRAY_START_REGEX,
+            r#"^\s*local\s+[a-zA-Z_][a-zA-Z0-9_]*\s*=\s*\("#,
+        )?;
 
         // Regex to extract ANTHROPIC variables from array elements
         // Matches: "ANTHROPIC_BASE_URL=value" or 'ANTHROPIC_BASE_URL=value' or ANTHROPIC_BASE_URL=value
-        let var_regex = Regex::new(r#"^\s*(["']?)(ANTHROPIC_(?:BASE_URL|BEDROCK_BASE_URL|VERTEX_BASE_URL|AUTH_TOKEN|API_KEY))=([^\n\r]+)"#)
-            .map_err(|e| NetworkError::RegexError(e.to_string()))?;
+        static VAR_REGEX: OnceLock<Regex> = OnceLock::new();
+        let var_regex = cached_regex(
+            &VAR_REGEX,
+            r#"^\s*(["']?)(ANTHROPIC_(?:BASE_URL|BEDROCK_BASE_URL|VERTEX_BASE_URL|AUTH_TOKEN|API_KEY))=([^\n\r]+)"#,
+        )?;
 
         let lines: Vec<&str> = content.lines().collect();
         let mut i = 0;
@@ -837,11 +903,11 @@ impl CredentialManager {
     ) -> Result<Option<(String, String)>, NetworkError> {
         // Regex to match variable assignments without export
         // Matches: VAR="value" or VAR='value' or VAR=value (at start of line, not within export)
-        let assignment_regex = Regex::new(r#"^\s*([A-Z_]+)=(.*)"#)
-            .map_err(|e| NetworkError::RegexError(e.to_string()))?;
+        static ASSIGNMENT_REGEX: OnceLock<Regex> = OnceLock::new();
+        let assignment_regex = cached_regex(&ASSIGNMENT_REGEX, r#"^\s*([A-Z_]+)=(.*)"#)?;
 
         // Use the common helper method (skip_export = true to avoid processing export statements)
-        self.process_shell_variables_with_regex(content, &assignment_regex, true)
+        self.process_shell_variables_with_regex(content, assignment_regex, true)
     }
 
     /// Parse PowerShell config file for environment variables
@@ -852,10 +918,13 @@ impl CredentialManager {
     ) -> Result<Option<ApiCredentials>, NetworkError> {
         // Regex for PowerShell environment variable setting
         // Matches: $env:VAR = "value" or [Environment]::SetEnvironmentVariable("VAR", "value", ...)
-        let env_regex = Regex::new(r#"\$env:([A-Z_]+)\s*=\s*["']([^"']+)["']"#)
-            .map_err(|e| NetworkError::RegexError(e.to_string()))?;
-        let setenv_regex = Regex::new(r#"\[.*Environment.*]::SetEnvironmentVariable\s*\(\s*["']([A-Z_]+)["']\s*,\s*["']([^"']+)["']"#)
-            .map_err(|e| NetworkError::RegexError(e.to_string()))?;
+        static ENV_REGEX: OnceLock<Regex> = OnceLock::new();
+        let env_regex = cached_regex(&ENV_REGEX, r#"\$env:([A-Z_]+)\s*=\s*["']([^"']+)["']"#)?;
+        static SETENV_REGEX: OnceLock<Regex> = OnceLock::new();
+        let setenv_regex = cached_regex(
+            &SETENV_REGEX,
+            r#"\[.*Environment.*]::SetEnvironmentVariable\s*\(\s*["']([A-Z_]+)["']\s*,\s*["']([^"']+)["']"#,
+        )?;
 
         let mut base_url: Option<String> = None;
         let mut auth_token: Option<String> = None;
@@ -867,20 +936,21 @@ impl CredentialManager {
             }
 
             // Check $env: syntax
-            process_powershell_regex_captures(&env_regex, line, &mut base_url, &mut auth_token);
+            process_powershell_regex_captures(env_regex, line, &mut base_url, &mut auth_token);
 
             // Check SetEnvironmentVariable syntax
-            process_powershell_regex_captures(&setenv_regex, line, &mut base_url, &mut auth_token);
+            process_powershell_regex_captures(setenv_regex, line, &mut base_url, &mut auth_token);
         }
 
         // Check if we have complete credentials
         if let (Some(url), Some(token)) = (base_url, auth_token) {
-            return Ok(Some(ApiCredentials {
-                base_url: url,
-                auth_token: token,
-                source: CredentialSource::ShellConfig(source_path.to_path_buf()),
-                expires_at: None,
-            }));
+            return ApiCredentials::builder(
+                url,
+                token,
+                CredentialSource::ShellConfig(source_path.to_path_buf()),
+            )
+            .build()
+            .map(Some);
         }
 
         Ok(None)
@@ -895,7 +965,7 @@ impl CredentialManager {
             return Ok(None);
         }
 
-        let content = tokio::fs::read_to_string(config_path).await?;
+        let content = fs::read_to_string(config_path).await?;
 
         let config: Value = serde_json::from_str(&content)?;
 
@@ -905,12 +975,13 @@ impl CredentialManager {
             config.get("api_base_url").and_then(|v| v.as_str()),
             config.get("auth_token").and_then(|v| v.as_str()),
         ) {
-            return Ok(Some(ApiCredentials {
-                base_url: base_url.to_string(),
-                auth_token: auth_token.to_string(),
-                source: CredentialSource::ClaudeConfig(config_path.clone()),
-                expires_at: None,
-            }));
+            return ApiCredentials::builder(
+                base_url,
+                auth_token,
+                CredentialSource::ClaudeConfig(config_path.clone()),
+            )
+            .build()
+            .map(Some);
         }
 
         // Alternative config format - try different field names
@@ -918,12 +989,13 @@ impl CredentialManager {
             config.get("base_url").and_then(|v| v.as_str()),
             config.get("auth_token").and_then(|v| v.as_str()),
         ) {
-            return Ok(Some(ApiCredentials {
-                base_url: base_url.to_string(),
-                auth_token: auth_token.to_string(),
-                source: CredentialSource::ClaudeConfig(config_path.clone()),
-                expires_at: None,
-            }));
+            return ApiCredentials::builder(
+                base_url,
+                auth_token,
+                CredentialSource::ClaudeConfig(config_path.clone()),
+            )
+            .build()
+            .map(Some);
         }
 
         Ok(None)
@@ -936,7 +1008,7 @@ impl CredentialManager {
         logger: &crate::core::network::debug_logger::EnhancedDebugLogger,
     ) -> Result<Option<ApiCredentials>, NetworkError> {
         // Get the actual credentials from keychain with -w flag
-        let output = tokio::task::spawn_blocking(|| {
+        let output = crate::runtime::spawn_blocking(|| {
             std::process::Command::new("security")
                 .arg("find-generic-password")
                 .arg("-s")
@@ -966,10 +1038,9 @@ impl CredentialManager {
                 }
 
                 // Parse JSON from keychain
-                let keychain_json: Value = serde_json::from_str(&keychain_data)
-                    .map_err(|e| {
-                        NetworkError::CredentialError(format!("Invalid JSON in keychain: {}", e))
-                    })?;
+                let keychain_json: Value = serde_json::from_str(&keychain_data).map_err(|e| {
+                    NetworkError::CredentialError(format!("Invalid JSON in keychain: {}", e))
+                })?;
 
                 // Extract OAuth credentials
                 let access_token = keychain_json
@@ -999,12 +1070,14 @@ impl CredentialManager {
                     )
                     .await;
 
-                Ok(Some(ApiCredentials {
-                    base_url: Self::OAUTH_FIXED_BASE_URL.to_string(),
-                    auth_token: access_token.to_string(),
-                    source: CredentialSource::OAuth,
-                    expires_at,
-                }))
+                ApiCredentials::builder(
+                    Self::OAUTH_FIXED_BASE_URL,
+                    access_token,
+                    CredentialSource::OAuth,
+                )
+                .with_expires_at(expires_at)
+                .build()
+                .map(Some)
             }
             Ok(Ok(_)) => {
                 logger
@@ -1039,6 +1112,18 @@ impl CredentialManager {
 
 // Private helper functions
 
+/// Compile `pattern` once per process; shell configs are parsed on every invocation
+fn cached_regex(
+    cell: &'static OnceLock<Regex>,
+    pattern: &str,
+) -> Result<&'static Regex, NetworkError> {
+    if let Some(regex) = cell.get() {
+        return Ok(regex);
+    }
+    let regex = Regex::new(pattern).map_err(|e| NetworkError::RegexError(e.to_string()))?;
+    Ok(cell.get_or_init(|| regex))
+}
+
 /// Detect the current shell type based on environment and platform
 pub fn detect_shell() -> ShellType {
     // Check SHELL environment variable first
@@ -0,0 +1,128 @@
+//! Standalone probing (`ccstatus daemon`)
+//!
+//! The statusline only probes while Claude Code invokes it, so an idle machine
+//! has no fresh status and the first render after a break shows stale data.
+//! `ccstatus daemon` runs the same GREEN/RED cadence on its own timer: a COLD
+//! probe at start, RED probes while the last one failed, GREEN probes
+//! otherwise, with the periods of the probe profile. Results go to the same
+//! `ccstatus-monitoring.json` (and per-target files), through the same status
+//! hooks.
+//!
+//! While it runs, the daemon rewrites `ccstatus-daemon.json` (in the state
+//! directory, so under `[network] local_state_dir` when set) every few seconds.
+//! A statusline that finds a fresh lease does not probe at all and only renders
+//! the state; a lease older than `STALE_AFTER_SECS` (daemon killed or hung) is
+//! ignored and the statusline goes back to probing on its own. A lock file
+//! keeps a second daemon from starting.
+
+use crate::core::network::probe_profile::ProbeProfile;
+use crate::core::network::types::{NetworkStatus, ProbeMode};
+use crate::core::recovery;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Seconds between two scheduling passes (and lease refreshes)
+pub const TICK_SECS: u64 = 5;
+
+/// Age after which a lease no longer counts as a running daemon
+///
+/// Well above `TICK_SECS`, since one pass can wait on several probe timeouts.
+pub const STALE_AFTER_SECS: i64 = 60;
+
+/// File name of the lease inside the state directory
+pub const LEASE_FILE: &str = "ccstatus-daemon.json";
+
+/// Contents of `ccstatus-daemon.json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaemonLease {
+    pub pid: u32,
+    /// Unix seconds the daemon started
+    pub started_at: i64,
+    /// Unix seconds of the last scheduling pass
+    pub heartbeat_at: i64,
+    pub profile: ProbeProfile,
+}
+
+impl DaemonLease {
+    pub fn new(profile: ProbeProfile, now: i64) -> Self {
+        Self {
+            pid: std::process::id(),
+            started_at: now,
+            heartbeat_at: now,
+            profile,
+        }
+    }
+
+    /// Lease in `state_dir` (see `StateLocation::state_dir`)
+    pub fn path_in(state_dir: &Path) -> PathBuf {
+        state_dir.join(LEASE_FILE)
+    }
+
+    /// Lock file held for the daemon's lifetime, next to the lease
+    pub fn lock_path(path: &Path) -> PathBuf {
+        path.with_extension("lock")
+    }
+
+    /// Whether the daemon refreshed the lease recently enough as of `now`
+    pub fn is_fresh(&self, now: i64) -> bool {
+        now - self.heartbeat_at <= STALE_AFTER_SECS
+    }
+
+    /// Lease at `path`; None when missing or unreadable (a corrupt lease is quarantined)
+    pub fn load(path: &Path) -> Option<Self> {
+        recovery::load_json(path)
+    }
+
+    /// Replace the lease file atomically
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&temp_path, serde_json::to_string(self)?)?;
+        fs::rename(&temp_path, path).inspect_err(|_| {
+            let _ = fs::remove_file(&temp_path);
+        })
+    }
+
+    /// Whether a daemon holds a fresh lease at `path` as of `now`
+    pub fn running_at(path: &Path, now: i64) -> bool {
+        Self::load(path).is_some_and(|lease| lease.is_fresh(now))
+    }
+}
+
+/// When the daemon probes an endpoint, from the probe profile's periods
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DaemonSchedule {
+    green_secs: u64,
+    /// None = the profile does no RED probing
+    red_secs: Option<u64>,
+}
+
+impl DaemonSchedule {
+    pub fn new(profile: ProbeProfile) -> Self {
+        Self {
+            green_secs: profile.green_interval_secs(),
+            red_secs: profile.red_interval_secs(),
+        }
+    }
+
+    /// Probe due at `now` after the last one (Unix seconds and resulting status)
+    ///
+    /// COLD when nothing was probed yet, RED once its period passed after a
+    /// failed probe, GREEN once its period passed otherwise.
+    pub fn due(&self, last: Option<(i64, &NetworkStatus)>, now: i64) -> Option<ProbeMode> {
+        let Some((at, status)) = last else {
+            return Some(ProbeMode::Cold);
+        };
+        let elapsed = now.saturating_sub(at).max(0) as u64;
+        match self.red_secs {
+            Some(red_secs) if *status == NetworkStatus::Error => {
+                (elapsed >= red_secs).then_some(ProbeMode::Red)
+            }
+            _ => (elapsed >= self.green_secs).then_some(ProbeMode::Green),
+        }
+    }
+}
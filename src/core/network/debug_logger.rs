@@ -1,22 +1,37 @@
+//! Debug and operational logging behind [`get_debug_logger`]
+//!
+//! Every module resolves its logger through [`get_debug_logger`]. Library
+//! consumers and long-running modes call [`init_logging`] once with an explicit
+//! [`LoggerConfig`] (paths, level, rotation); until then, or after
+//! [`reset_logging`], each call builds a logger from `CCSTATUS_DEBUG` and
+//! `CCSTATUS_JSONL_FILE` as before. The global slot is only locked to swap or
+//! clone the shared logger, never while writing, so logging code may itself
+//! call `get_debug_logger` or `init_logging`.
+
 use std::collections::HashMap;
 use std::fs::{File, OpenOptions};
 use std::io::{BufReader, Write};
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicU32, Ordering};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, RwLock};
 
+use crate::core::env;
+use crate::core::perf::{PerfReport, PERF_EVENT};
+use crate::core::shutdown::{FileLock, ShutdownCoordinator};
 use chrono::Local;
 use flate2::{write::GzEncoder, Compression};
-use fs2::FileExt;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-// Hardcoded configuration - no environment variables needed
+// Rotation defaults (see `LogRotation`)
 const LOG_ROTATION_SIZE_MB: u64 = 8;
 const MAX_ARCHIVES: u32 = 5;
 const ROTATION_CHECK_INTERVAL: u32 = 200;
 
+/// Logger installed by [`init_logging`]; None = build from the environment per call
+static LOGGER: RwLock<Option<EnhancedDebugLogger>> = RwLock::new(None);
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 struct LogEntry {
     timestamp: String,                          // ISO-8601 with timezone
@@ -30,11 +45,12 @@ struct LogEntry {
 
 struct RotatingLogger {
     log_path: PathBuf,
+    rotation: LogRotation,
     write_count: AtomicU32,
 }
 
 impl RotatingLogger {
-    pub fn new(log_path: PathBuf) -> Self {
+    pub fn new(log_path: PathBuf, rotation: LogRotation) -> Self {
         // Ensure parent directory exists
         if let Some(parent) = log_path.parent() {
             let _ = std::fs::create_dir_all(parent);
@@ -42,6 +58,7 @@ impl RotatingLogger {
 
         Self {
             log_path,
+            rotation,
             write_count: AtomicU32::new(0),
         }
     }
@@ -67,24 +84,17 @@ impl RotatingLogger {
             return Ok(());
         }
 
-        // File locking to prevent concurrent rotation
+        // File locking to prevent concurrent rotation; released on drop or shutdown
         let lock_path = self.log_path.with_extension("lock");
-        let lock_file = OpenOptions::new()
-            .create(true)
-            .write(true)
-            .truncate(true)
-            .open(&lock_path)?;
-
-        match lock_file.try_lock_exclusive() {
-            Ok(()) => {
+        match FileLock::try_acquire(ShutdownCoordinator::global(), lock_path)? {
+            Some(_lock) => {
                 // Double-check if rotation is still needed after acquiring lock
                 if self.needs_rotation()? {
                     self.perform_rotation()?;
                 }
-                let _ = std::fs::remove_file(&lock_path);
                 Ok(())
             }
-            Err(_) => {
+            None => {
                 // Another process is rotating, skip this time
                 Ok(())
             }
@@ -97,7 +107,7 @@ impl RotatingLogger {
         }
 
         let metadata = std::fs::metadata(&self.log_path)?;
-        Ok(metadata.len() >= LOG_ROTATION_SIZE_MB * 1024 * 1024)
+        Ok(metadata.len() >= self.rotation.max_size_mb * 1024 * 1024)
     }
 
     fn perform_rotation(&self) -> Result<(), std::io::Error> {
@@ -120,7 +130,7 @@ impl RotatingLogger {
         // Remove temporary file
         std::fs::remove_file(&temp_path)?;
 
-        // Cleanup old archives (keep last MAX_ARCHIVES)
+        // Cleanup old archives (keep the last `max_archives`)
         let _ = self.cleanup_old_archives(); // Don't let cleanup errors stop rotation
 
         Ok(())
@@ -140,10 +150,11 @@ impl RotatingLogger {
             }
         }
 
-        // Keep only the most recent MAX_ARCHIVES
+        // Keep only the most recent `max_archives`
+        let max_archives = self.rotation.max_archives as usize;
         archives.sort_by_key(|(_, modified)| *modified);
-        if archives.len() > MAX_ARCHIVES as usize {
-            let to_remove = archives.len() - MAX_ARCHIVES as usize;
+        if archives.len() > max_archives {
+            let to_remove = archives.len() - max_archives;
             for (path, _) in archives.iter().take(to_remove) {
                 let _ = std::fs::remove_file(path); // Ignore individual cleanup errors
             }
@@ -153,6 +164,109 @@ impl RotatingLogger {
     }
 }
 
+/// Lowest severity written to the flat-text debug log
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    /// Everything, including probe, credential and performance events
+    #[default]
+    Debug,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    /// Level of an entry tag such as `DEBUG`, `PERF` or `ERROR`
+    fn of_tag(tag: &str) -> Self {
+        match tag {
+            "ERROR" => Self::Error,
+            "WARN" => Self::Warn,
+            _ => Self::Debug,
+        }
+    }
+}
+
+/// Size-based rotation of a log file into gzip archives
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogRotation {
+    /// Size at which the file is rotated
+    pub max_size_mb: u64,
+    /// Archives kept per log file
+    pub max_archives: u32,
+}
+
+impl Default for LogRotation {
+    fn default() -> Self {
+        Self {
+            max_size_mb: LOG_ROTATION_SIZE_MB,
+            max_archives: MAX_ARCHIVES,
+        }
+    }
+}
+
+/// Explicit logger setup for [`init_logging`]
+#[derive(Clone, Debug)]
+pub struct LoggerConfig {
+    /// Flat-text debug log; None disables it
+    pub debug_log_path: Option<PathBuf>,
+    /// Always-on NDJSON operational log
+    pub jsonl_path: PathBuf,
+    pub level: LogLevel,
+    pub rotation: LogRotation,
+}
+
+impl LoggerConfig {
+    /// The setup `CCSTATUS_DEBUG` and `CCSTATUS_JSONL_FILE` describe
+    pub fn from_env() -> Self {
+        JsonlLoggerConfig::default().into()
+    }
+
+    /// Write the debug log to `path` (enables it)
+    pub fn with_debug_log(mut self, path: PathBuf) -> Self {
+        self.debug_log_path = Some(path);
+        self
+    }
+
+    pub fn with_jsonl_path(mut self, path: PathBuf) -> Self {
+        self.jsonl_path = path;
+        self
+    }
+
+    pub fn with_level(mut self, level: LogLevel) -> Self {
+        self.level = level;
+        self
+    }
+
+    pub fn with_rotation(mut self, rotation: LogRotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+}
+
+impl From<JsonlLoggerConfig> for LoggerConfig {
+    fn from(config: JsonlLoggerConfig) -> Self {
+        Self {
+            debug_log_path: config.debug_enabled.then_some(config.debug_log_path),
+            jsonl_path: config.jsonl_path,
+            level: LogLevel::default(),
+            rotation: LogRotation::default(),
+        }
+    }
+}
+
+/// Install the logger every module resolves through [`get_debug_logger`]
+///
+/// Calling it again replaces the logger; loggers already handed out keep
+/// writing to their old files.
+pub fn init_logging(config: LoggerConfig) {
+    let logger = EnhancedDebugLogger::from_logger_config(config);
+    *LOGGER.write().unwrap_or_else(|e| e.into_inner()) = Some(logger);
+}
+
+/// Drop the logger installed by [`init_logging`], falling back to the environment
+pub fn reset_logging() {
+    *LOGGER.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
 /// Configuration for JsonL logger and debug settings
 /// Replaces direct environment variable access for better testability
 #[derive(Clone, Debug)]
@@ -184,7 +298,7 @@ impl JsonlLoggerConfig {
     /// Get default JSONL log path, checking environment variable first
     fn get_default_jsonl_path() -> PathBuf {
         // Check if environment variable is set (primarily for testing)
-        if let Ok(jsonl_path) = std::env::var("CCSTATUS_JSONL_FILE") {
+        if let Some(jsonl_path) = env::JSONL_FILE.raw() {
             return PathBuf::from(jsonl_path);
         }
 
@@ -207,15 +321,14 @@ impl JsonlLoggerConfig {
 
     /// Parse debug enabled flag from environment
     fn parse_debug_enabled() -> bool {
-        match std::env::var("CCSTATUS_DEBUG").as_deref() {
-            Ok("true") | Ok("1") | Ok("yes") | Ok("on") => true,
-            _ => false,
-        }
+        env::DEBUG.is_enabled()
     }
 }
 
+#[derive(Clone)]
 pub struct EnhancedDebugLogger {
     enabled: bool,
+    level: LogLevel,
     debug_logger: Option<Arc<Mutex<RotatingLogger>>>, // Flat text debug log (CCSTATUS_DEBUG gated)
     jsonl_logger: Arc<Mutex<RotatingLogger>>,         // NDJSON operational log (always-on)
     session_id: String,                               // Correlation ID for this session
@@ -224,55 +337,40 @@ pub struct EnhancedDebugLogger {
 
 impl EnhancedDebugLogger {
     pub fn new() -> Self {
-        let enabled = Self::parse_debug_enabled();
-        let session_id = Uuid::new_v4().to_string()[..8].to_string();
-
-        // Debug logger - only created when CCSTATUS_DEBUG=true
-        let debug_logger = if enabled {
-            let debug_path = Self::get_debug_log_path();
-            Some(Arc::new(Mutex::new(RotatingLogger::new(debug_path))))
-        } else {
-            None
-        };
-
-        // JSONL logger - always created (always-on operational logging)
-        let jsonl_path = Self::get_jsonl_log_path();
-        let jsonl_logger = Arc::new(Mutex::new(RotatingLogger::new(jsonl_path)));
-
-        // Compile redaction patterns once at startup
-        let redaction_patterns = Self::compile_redaction_patterns();
-
-        Self {
-            enabled,
-            debug_logger,
-            jsonl_logger,
-            session_id,
-            redaction_patterns,
-        }
+        Self::from_config(JsonlLoggerConfig {
+            jsonl_path: Self::get_jsonl_log_path(),
+            debug_log_path: Self::get_debug_log_path(),
+            debug_enabled: Self::parse_debug_enabled(),
+        })
     }
 
     /// Create EnhancedDebugLogger from configuration
     /// This replaces environment variable dependency for better testability
     pub fn from_config(config: JsonlLoggerConfig) -> Self {
+        Self::from_logger_config(config.into())
+    }
+
+    /// Create EnhancedDebugLogger with an explicit level and rotation
+    pub fn from_logger_config(config: LoggerConfig) -> Self {
         let session_id = Uuid::new_v4().to_string()[..8].to_string();
 
-        // Debug logger - only created when debug is enabled
-        let debug_logger = if config.debug_enabled {
-            Some(Arc::new(Mutex::new(RotatingLogger::new(
-                config.debug_log_path,
-            ))))
-        } else {
-            None
-        };
+        // Debug logger - only created when a debug log path is configured
+        let debug_logger = config
+            .debug_log_path
+            .map(|path| Arc::new(Mutex::new(RotatingLogger::new(path, config.rotation))));
 
         // JSONL logger - always created (always-on operational logging)
-        let jsonl_logger = Arc::new(Mutex::new(RotatingLogger::new(config.jsonl_path)));
+        let jsonl_logger = Arc::new(Mutex::new(RotatingLogger::new(
+            config.jsonl_path,
+            config.rotation,
+        )));
 
         // Compile redaction patterns once at startup
         let redaction_patterns = Self::compile_redaction_patterns();
 
         Self {
-            enabled: config.debug_enabled,
+            enabled: debug_logger.is_some(),
+            level: config.level,
             debug_logger,
             jsonl_logger,
             session_id,
@@ -281,9 +379,8 @@ impl EnhancedDebugLogger {
     }
 
     /// Parse debug enabled status from CCSTATUS_DEBUG environment variable only
-    /// Supports: true/false (case insensitive)
     fn parse_debug_enabled() -> bool {
-        crate::core::network::types::parse_env_bool("CCSTATUS_DEBUG")
+        env::DEBUG.is_enabled()
     }
 
     fn get_debug_log_path() -> PathBuf {
@@ -296,7 +393,7 @@ impl EnhancedDebugLogger {
 
     pub fn get_jsonl_log_path() -> PathBuf {
         // Check if environment variable is set (primarily for testing)
-        if let Ok(jsonl_path) = std::env::var("CCSTATUS_JSONL_FILE") {
+        if let Some(jsonl_path) = env::JSONL_FILE.raw() {
             return PathBuf::from(jsonl_path);
         }
 
@@ -349,14 +446,14 @@ impl EnhancedDebugLogger {
     /// Core synchronous logging method with flat-text format
     fn log_sync(
         &self,
-        _level: &str,
+        level: &str,
         component: &str,
         event: &str,
         message: &str,
         correlation_id: Option<String>,
         fields: HashMap<String, serde_json::Value>,
     ) {
-        if !self.enabled {
+        if !self.enabled || LogLevel::of_tag(level) < self.level {
             return;
         }
 
@@ -542,6 +639,23 @@ impl EnhancedDebugLogger {
         );
     }
 
+    /// Append this invocation's stage timings (see `core::perf`)
+    pub fn perf_report_sync(&self, report: &PerfReport) {
+        let mut fields = HashMap::new();
+        fields.insert(
+            "total_ms".to_string(),
+            serde_json::Value::Number((report.total.as_millis() as u64).into()),
+        );
+        if let Some(slowest) = report.slowest() {
+            fields.insert(
+                "slowest".to_string(),
+                serde_json::Value::String(slowest.stage.clone()),
+            );
+        }
+
+        self.log_sync("PERF", "Perf", PERF_EVENT, &report.summary(), None, fields);
+    }
+
     pub fn render_summary(&self, emoji: &str, status: &str) {
         let mut fields = HashMap::new();
         fields.insert(
@@ -601,8 +715,8 @@ impl Default for EnhancedDebugLogger {
     }
 }
 
-// Factory function for backward compatibility
-// Uses default configuration to reduce environment variable dependency
+/// The logger installed by [`init_logging`], or one configured from the environment
 pub fn get_debug_logger() -> EnhancedDebugLogger {
-    EnhancedDebugLogger::from_config(JsonlLoggerConfig::default())
+    let installed = LOGGER.read().unwrap_or_else(|e| e.into_inner()).clone();
+    installed.unwrap_or_else(|| EnhancedDebugLogger::from_config(JsonlLoggerConfig::default()))
 }
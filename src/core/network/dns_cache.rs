@@ -0,0 +1,244 @@
+//! Persisted DNS cache for the probe host (`network.dns_cache`)
+//!
+//! Every statusline run is a new process, so each probe re-resolves the API
+//! host and the lookup lands in the measured latency as noise. With the cache
+//! on, HttpMonitor resolves the host itself, keeps the answer for the record's
+//! TTL in `~/.claude/ccstatus/dns-cache.json`, and pins the probe connection
+//! (isahc or curl) to the cached address, so probe latency no longer includes
+//! DNS. The lookup time of a miss is recorded separately.
+//!
+//! Lookups go through the system resolver, like Claude Code's own, so the hosts
+//! file, nsswitch and scoped or VPN resolvers apply and the pinned address is
+//! the one Claude Code would connect to. It reports no TTL, so answers live for
+//! `TTL_SECS`. A miss may take at most what is left of the probe's timeout;
+//! past that the client resolves on its own.
+//!
+//! RED probes bypass the cache and drop the host's entry, so a stale address
+//! can never hide an outage; a transport failure on a cached address drops it
+//! too. Every probe records `hit`, `miss` or `bypass` in the monitoring state.
+
+use crate::core::network::types::NetworkError;
+use crate::core::recovery;
+use crate::runtime;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::net::{IpAddr, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Lifetime of a cached answer; the system resolver reports no TTL
+pub const TTL_SECS: u32 = 300;
+
+/// Longest an answer is kept, whatever its TTL
+pub const MAX_TTL_SECS: u32 = 3600;
+
+/// What the cache did for one probe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DnsCacheStatus {
+    /// Connected to a cached address; no lookup
+    Hit,
+    /// Looked the host up and cached the answer
+    Miss,
+    /// RED probe: the client resolved on its own
+    Bypass,
+}
+
+impl DnsCacheStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DnsCacheStatus::Hit => "hit",
+            DnsCacheStatus::Miss => "miss",
+            DnsCacheStatus::Bypass => "bypass",
+        }
+    }
+}
+
+/// Address a probe connection is pinned to instead of resolving `host`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DnsPin {
+    pub host: String,
+    pub port: u16,
+    pub addr: IpAddr,
+}
+
+impl DnsPin {
+    /// `host:port:addr`, the libcurl `CURLOPT_RESOLVE` entry
+    pub fn resolve_entry(&self) -> String {
+        format!("{}:{}:{}", self.host, self.port, self.addr)
+    }
+}
+
+/// DNS cache decision for one probe
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DnsLookup {
+    pub status: DnsCacheStatus,
+    /// Address to connect to; None lets the client resolve
+    pub pin: Option<DnsPin>,
+    /// Time spent resolving on a miss
+    pub lookup_ms: Option<u32>,
+}
+
+/// Cached answer for one host
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedAddrs {
+    pub addrs: Vec<IpAddr>,
+    /// Unix seconds after which the answer is stale
+    pub expires_at: i64,
+}
+
+/// On-disk cache layout
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DnsCacheFile {
+    entries: BTreeMap<String, CachedAddrs>,
+}
+
+/// Host → addresses cache persisted across invocations
+#[derive(Debug, Clone)]
+pub struct DnsCache {
+    path: PathBuf,
+    entries: BTreeMap<String, CachedAddrs>,
+    dirty: bool,
+}
+
+impl DnsCache {
+    /// Default cache location: `~/.claude/ccstatus/dns-cache.json`
+    pub fn default_path() -> Result<PathBuf, NetworkError> {
+        let home = dirs::home_dir().ok_or(NetworkError::HomeDirNotFound)?;
+        Ok(home.join(".claude").join("ccstatus").join("dns-cache.json"))
+    }
+
+    /// Load the cache, dropping stale entries; a missing or unreadable file yields an empty cache
+    pub async fn load(path: &Path, now: i64) -> Self {
+        let stored = runtime::fs::read(path)
+            .await
+            .ok()
+            .and_then(|content| recovery::parse_json::<DnsCacheFile>(path, &content))
+            .unwrap_or_default();
+
+        let total = stored.entries.len();
+        let entries: BTreeMap<String, CachedAddrs> = stored
+            .entries
+            .into_iter()
+            .filter(|(_, cached)| cached.expires_at > now)
+            .collect();
+
+        Self {
+            path: path.to_path_buf(),
+            dirty: entries.len() != total,
+            entries,
+        }
+    }
+
+    /// Fresh addresses for `host`
+    pub fn get(&self, host: &str, now: i64) -> Option<&[IpAddr]> {
+        self.entries
+            .get(&host.to_ascii_lowercase())
+            .filter(|cached| cached.expires_at > now && !cached.addrs.is_empty())
+            .map(|cached| cached.addrs.as_slice())
+    }
+
+    /// Cache `addrs` for `ttl_secs` (capped at `MAX_TTL_SECS`); a zero TTL or no addresses removes the host
+    pub fn insert(&mut self, host: &str, addrs: Vec<IpAddr>, ttl_secs: u32, now: i64) {
+        if ttl_secs == 0 || addrs.is_empty() {
+            self.remove(host);
+            return;
+        }
+        self.entries.insert(
+            host.to_ascii_lowercase(),
+            CachedAddrs {
+                addrs,
+                expires_at: now + ttl_secs.min(MAX_TTL_SECS) as i64,
+            },
+        );
+        self.dirty = true;
+    }
+
+    /// Forget `host`
+    pub fn remove(&mut self, host: &str) {
+        if self.entries.remove(&host.to_ascii_lowercase()).is_some() {
+            self.dirty = true;
+        }
+    }
+
+    /// Write the cache atomically if it changed
+    pub async fn save(&mut self) -> Result<(), NetworkError> {
+        if !self.dirty {
+            return Ok(());
+        }
+
+        if let Some(parent) = self.path.parent() {
+            runtime::fs::create_dir_all(parent).await.map_err(|e| {
+                NetworkError::StateFileError(format!("Failed to create DNS cache directory: {}", e))
+            })?;
+        }
+
+        let file = DnsCacheFile {
+            entries: self.entries.clone(),
+        };
+        let content = serde_json::to_string_pretty(&file).map_err(|e| {
+            NetworkError::StateFileError(format!("Failed to serialize DNS cache: {}", e))
+        })?;
+
+        let temp_path = self.path.with_extension("tmp");
+        runtime::fs::write(&temp_path, content).await.map_err(|e| {
+            NetworkError::StateFileError(format!("Failed to write DNS cache: {}", e))
+        })?;
+        runtime::fs::rename(&temp_path, &self.path)
+            .await
+            .map_err(|e| {
+                NetworkError::StateFileError(format!("Failed to rename DNS cache: {}", e))
+            })?;
+
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+/// Addresses of a lookup and how long they may be cached
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resolved {
+    pub addrs: Vec<IpAddr>,
+    pub ttl_secs: u32,
+}
+
+/// Resolve `host` through the system resolver, giving up after `budget`
+///
+/// The lookup runs on a thread of its own that is left behind on timeout, since
+/// `getaddrinfo` cannot be cancelled.
+pub async fn resolve(host: &str, budget: Duration) -> Result<Resolved, String> {
+    let (sender, receiver) = futures::channel::oneshot::channel();
+    let lookup_host = host.to_string();
+    std::thread::Builder::new()
+        .name("ccstatus-dns".to_string())
+        .spawn(move || {
+            let _ = sender.send(lookup(&lookup_host));
+        })
+        .map_err(|e| format!("DNS lookup failed: {}", e))?;
+
+    match runtime::timeout(budget, receiver).await {
+        Ok(Ok(addrs)) => Ok(Resolved {
+            addrs: addrs?,
+            ttl_secs: TTL_SECS,
+        }),
+        Ok(Err(_)) => Err("DNS lookup failed: resolver thread exited".to_string()),
+        Err(_) => Err(format!(
+            "DNS lookup timed out after {}ms",
+            budget.as_millis()
+        )),
+    }
+}
+
+/// Distinct addresses of `host` in the resolver's order (blocking)
+fn lookup(host: &str) -> Result<Vec<IpAddr>, String> {
+    let mut addrs: Vec<IpAddr> = Vec::new();
+    for addr in (host, 443)
+        .to_socket_addrs()
+        .map_err(|e| format!("DNS lookup failed: {}", e))?
+    {
+        if !addrs.contains(&addr.ip()) {
+            addrs.push(addr.ip());
+        }
+    }
+    Ok(addrs)
+}
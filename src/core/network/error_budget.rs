@@ -0,0 +1,187 @@
+//! Error budget over the probe history (`[network.error_budget]`)
+//!
+//! A budget such as "1% failed probes per day" turns scattered failures into a
+//! single question: at the current pace, will the day end within the budget?
+//! After each probe the history of the last `window_hours` is tallied into an
+//! `ErrorBudget` kept in the monitoring state. The burn rate compares the
+//! failure rate of the last `burn_window_minutes` with the allowed one: 1x
+//! spends the budget exactly over the window, 10x spends a day's budget in
+//! under two and a half hours. At `burn_alert` the segment switches to its
+//! warning colors and shows `| budget 12.5x`.
+//!
+//! Probes are counted as in the SLA ledger: healthy and degraded are up, error
+//! is a failure, unknown and policy-blocked probes are not counted.
+
+use crate::config::ErrorBudgetConfig;
+use crate::core::network::probe_history::ProbeRecord;
+use crate::core::network::types::{NetworkError, NetworkStatus};
+use serde::{Deserialize, Serialize};
+
+/// Longest budget window in hours (30 days, the default history retention)
+pub const MAX_WINDOW_HOURS: u32 = 720;
+
+/// Probes the burn window needs before the burn rate can warn, so the first
+/// probe after a quiet spell cannot trip it on its own
+pub const MIN_BURN_PROBES: u64 = 3;
+
+/// Error budget of one state partition as of its last probe
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ErrorBudget {
+    pub allowed_failure_percent: f64,
+    pub window_hours: u32,
+    /// Counted probes and failures within the window
+    pub probes: u64,
+    pub failed: u64,
+    pub burn_window_minutes: u32,
+    /// Counted probes and failures within the burn window
+    pub recent_probes: u64,
+    pub recent_failed: u64,
+    /// Failure rate of the burn window as a multiple of the allowed rate
+    pub burn_rate: f64,
+    pub burn_alert: f64,
+    /// Whether the burn rate reached `burn_alert`
+    pub burning: bool,
+    /// Unix seconds of the evaluation
+    pub evaluated_at: i64,
+}
+
+impl ErrorBudget {
+    /// Failures the budget allows for the probes counted so far
+    pub fn allowed_failures(&self) -> f64 {
+        self.probes as f64 * self.allowed_failure_percent / 100.0
+    }
+
+    /// Share of the budget left in percent, 0 once it is spent
+    pub fn remaining_percent(&self) -> f64 {
+        let allowed = self.allowed_failures();
+        if allowed <= 0.0 {
+            return if self.failed == 0 { 100.0 } else { 0.0 };
+        }
+        (100.0 - self.failed as f64 * 100.0 / allowed).clamp(0.0, 100.0)
+    }
+}
+
+/// Validated `[network.error_budget]`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ErrorBudgetPolicy {
+    allowed_failure_percent: f64,
+    window_hours: u32,
+    burn_window_minutes: u32,
+    burn_alert: f64,
+}
+
+impl Default for ErrorBudgetPolicy {
+    fn default() -> Self {
+        let config = ErrorBudgetConfig::default();
+        Self {
+            allowed_failure_percent: config.allowed_failure_percent,
+            window_hours: config.window_hours,
+            burn_window_minutes: config.burn_window_minutes,
+            burn_alert: config.burn_alert,
+        }
+    }
+}
+
+impl ErrorBudgetPolicy {
+    /// # Errors
+    ///
+    /// Returns `NetworkError::ValidationError` for an allowed share outside
+    /// `(0, 100]`, a window outside 1..=`MAX_WINDOW_HOURS` hours, a burn
+    /// window of 0 or longer than the window, or a burn alert that is not positive.
+    pub fn from_config(config: &ErrorBudgetConfig) -> Result<Self, NetworkError> {
+        let invalid = |message: String| Err(NetworkError::ValidationError(message));
+
+        if !(config.allowed_failure_percent > 0.0 && config.allowed_failure_percent <= 100.0) {
+            return invalid(format!(
+                "error_budget allowed_failure_percent must be in (0, 100], got {}",
+                config.allowed_failure_percent
+            ));
+        }
+        if !(1..=MAX_WINDOW_HOURS).contains(&config.window_hours) {
+            return invalid(format!(
+                "error_budget window_hours must be 1-{}, got {}",
+                MAX_WINDOW_HOURS, config.window_hours
+            ));
+        }
+        if config.burn_window_minutes == 0
+            || config.burn_window_minutes as u64 > config.window_hours as u64 * 60
+        {
+            return invalid(format!(
+                "error_budget burn_window_minutes must be 1-{}, got {}",
+                config.window_hours as u64 * 60,
+                config.burn_window_minutes
+            ));
+        }
+        if !(config.burn_alert > 0.0 && config.burn_alert.is_finite()) {
+            return invalid(format!(
+                "error_budget burn_alert must be positive, got {}",
+                config.burn_alert
+            ));
+        }
+
+        Ok(Self {
+            allowed_failure_percent: config.allowed_failure_percent,
+            window_hours: config.window_hours,
+            burn_window_minutes: config.burn_window_minutes,
+            burn_alert: config.burn_alert,
+        })
+    }
+
+    /// Tally `(Unix seconds, status)` probes (any order) as of `now`
+    pub fn evaluate<'a, I>(&self, probes: I, now: i64) -> ErrorBudget
+    where
+        I: IntoIterator<Item = (i64, &'a NetworkStatus)>,
+    {
+        let window_from = now - self.window_hours as i64 * 3600;
+        let burn_from = now - self.burn_window_minutes as i64 * 60;
+        let mut budget = ErrorBudget {
+            allowed_failure_percent: self.allowed_failure_percent,
+            window_hours: self.window_hours,
+            burn_window_minutes: self.burn_window_minutes,
+            burn_alert: self.burn_alert,
+            evaluated_at: now,
+            ..Default::default()
+        };
+
+        for (at, status) in probes {
+            if !(window_from..=now).contains(&at) {
+                continue;
+            }
+            let failed = match status {
+                NetworkStatus::Healthy | NetworkStatus::Degraded => false,
+                NetworkStatus::Error => true,
+                NetworkStatus::Unknown | NetworkStatus::BlockedByPolicy => continue,
+            };
+            budget.probes += 1;
+            budget.failed += failed as u64;
+            if at >= burn_from {
+                budget.recent_probes += 1;
+                budget.recent_failed += failed as u64;
+            }
+        }
+
+        if budget.recent_probes > 0 {
+            let failure_percent = budget.recent_failed as f64 * 100.0 / budget.recent_probes as f64;
+            budget.burn_rate = failure_percent / self.allowed_failure_percent;
+        }
+        budget.burning =
+            budget.recent_probes >= MIN_BURN_PROBES && budget.burn_rate >= self.burn_alert;
+        budget
+    }
+
+    /// Tally the probe history plus the probe just classified as `current`
+    ///
+    /// The history is appended after the state is written, so the current
+    /// probe is not in `records` yet.
+    pub fn evaluate_history(
+        &self,
+        records: &[ProbeRecord],
+        current: &NetworkStatus,
+        now: i64,
+    ) -> ErrorBudget {
+        let history = records.iter().filter_map(|record| {
+            Some((record.timestamp_millis()?.div_euclid(1000), &record.status))
+        });
+        self.evaluate(history.chain(std::iter::once((now, current))), now)
+    }
+}
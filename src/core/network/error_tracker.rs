@@ -1,6 +1,7 @@
 // Error tracking and classification for network monitoring
 
 use crate::core::network::types::{JsonlError, NetworkStatus};
+use crate::core::stats;
 use std::collections::VecDeque;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
@@ -166,6 +167,56 @@ impl ErrorTracker {
         }
     }
 
+    /// Classify a probe that got no HTTP response from its client error message
+    ///
+    /// Reads the libcurl code curl errors carry (`[6] Couldn't resolve host name`)
+    /// and the isahc error kind wording (`failed to resolve host name`). Returns
+    /// `dns_error`, `tls_error`, `timeout` or `connect_refused`, falling back to
+    /// `connection_error` when the message does not say which phase failed.
+    pub fn classify_transport_error(error_message: &str) -> String {
+        if let Some(error_type) = curl_error_code(error_message).and_then(classify_curl_code) {
+            return error_type.to_string();
+        }
+
+        let message_lower = error_message.to_lowercase();
+        let error_type = if [
+            "resolve host",
+            "resolve proxy",
+            "name resolution",
+            "dns",
+            "name or service not known",
+            "no such host",
+        ]
+        .iter()
+        .any(|pattern| message_lower.contains(pattern))
+        {
+            "dns_error"
+        } else if ["ssl", "tls", "certificate", "secure socket"]
+            .iter()
+            .any(|pattern| message_lower.contains(pattern))
+        {
+            "tls_error"
+        } else if ["timed out", "timeout", "took longer than"]
+            .iter()
+            .any(|pattern| message_lower.contains(pattern))
+        {
+            "timeout"
+        } else if [
+            "connection refused",
+            "couldn't connect",
+            "could not connect",
+            "failed to connect",
+        ]
+        .iter()
+        .any(|pattern| message_lower.contains(pattern))
+        {
+            "connect_refused"
+        } else {
+            "connection_error"
+        };
+        error_type.to_string()
+    }
+
     /// Determine network status based on HTTP response
     ///
     /// **DEPRECATED**: This method duplicates logic that should be centralized in HttpMonitor/StatusRenderer.
@@ -202,30 +253,10 @@ impl ErrorTracker {
 
     /// Calculate percentiles from rolling totals using nearest-rank method
     ///
-    /// Uses the nearest-rank method: idx = max(0, ceil(p * n) - 1)
-    /// This avoids off-by-one issues and provides better estimates for small samples.
-    ///
-    /// **DEPRECATED**: This method duplicates percentile calculation logic that should be
-    /// centralized in HttpMonitor. Use shared utilities to avoid architectural drift.
-    #[deprecated(
-        note = "Use HttpMonitor utilities for percentile calculations to maintain single source of truth"
-    )]
+    /// **DEPRECATED**: Kept for compatibility; returns `(p80, p95)` from `core::stats`.
+    #[deprecated(note = "Use core::stats::percentiles")]
     pub fn calculate_percentiles(&self, rolling_totals: &[u32]) -> (u32, u32) {
-        if rolling_totals.is_empty() {
-            return (0, 0);
-        }
-
-        let mut sorted = rolling_totals.to_vec();
-        sorted.sort_unstable();
-        let n = sorted.len();
-
-        // Use nearest-rank method: idx = max(0, ceil(p * n) - 1)
-        let p80_index = (((n as f64) * 0.80).ceil() as usize).saturating_sub(1);
-        let p95_index = (((n as f64) * 0.95).ceil() as usize).saturating_sub(1);
-
-        let p80 = sorted.get(p80_index).copied().unwrap_or(0);
-        let p95 = sorted.get(p95_index).copied().unwrap_or(0);
-
+        let [p80, p95] = stats::percentiles(rolling_totals, [0.80, 0.95]);
         (p80, p95)
     }
 
@@ -336,3 +367,24 @@ impl Default for ErrorTracker {
         Self::new()
     }
 }
+
+/// libcurl error code from a message containing `curl::Error`'s `[N] …` form
+fn curl_error_code(error_message: &str) -> Option<u32> {
+    error_message.split('[').skip(1).find_map(|rest| {
+        let (code, _) = rest.split_once(']')?;
+        code.parse().ok()
+    })
+}
+
+/// Transport error type for a libcurl error code (`CURLE_*`), if it names a phase
+fn classify_curl_code(code: u32) -> Option<&'static str> {
+    match code {
+        5 | 6 => Some("dns_error"),   // COULDNT_RESOLVE_PROXY / _HOST
+        7 => Some("connect_refused"), // COULDNT_CONNECT
+        28 => Some("timeout"),        // OPERATION_TIMEDOUT
+        35 | 51 | 53 | 54 | 58 | 59 | 60 | 64 | 66 | 77 | 80 | 82 | 83 | 90 | 91 | 98 => {
+            Some("tls_error") // SSL_* / PEER_FAILED_VERIFICATION
+        }
+        _ => None,
+    }
+}
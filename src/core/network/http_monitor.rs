@@ -23,18 +23,38 @@ endpoints and maintains atomic state persistence with comprehensive timing metri
 ## Dependencies
 
 - `isahc`: HTTP client with timing metrics and configurability
-- `tokio`: Async runtime for non-blocking probe execution
+- `runtime`: Async runtime shim (tokio or smol) for non-blocking probe execution
 - `serde_json`: State serialization/deserialization
 - `chrono`: Local timezone timestamp generation
 */
 
+use crate::config::{
+    HistoryConfig, HostPolicy, HysteresisConfig, IpFamilyMode, NetworkConfig, ProxyPolicy,
+    TlsPolicy,
+};
+use crate::core::env;
+#[cfg(feature = "timings-curl")]
+use crate::core::network::certificate::CertificateChain;
+use crate::core::network::certificate::DEFAULT_WARN_DAYS;
+use crate::core::network::cookie_jar::{unix_now_secs, PersistentCookieJar};
 use crate::core::network::debug_logger::get_debug_logger;
+use crate::core::network::dns_cache::{DnsCache, DnsCacheStatus, DnsLookup, DnsPin};
+use crate::core::network::error_budget::ErrorBudgetPolicy;
+use crate::core::network::error_tracker::ErrorTracker;
+use crate::core::network::ip_family::{self, IpFamily};
+#[cfg(feature = "oauth-probe")]
 use crate::core::network::oauth_masquerade::{
     run_probe as oauth_run_probe, OauthMasqueradeOptions,
 };
+use crate::core::network::probe_payload::{
+    build_probe_payload, encode_probe_payload, payload_checksum, ProbePayloadKind, ProbeRequest,
+};
+use crate::core::network::probe_profile::{PayloadStrategy, ProbeProfile, SessionProfile};
 use crate::core::network::proxy_health::{
-    assess_proxy_health, build_messages_endpoint, HealthCheckClient, ProxyHealthOptions,
+    assess_proxy_health, build_messages_endpoint, build_models_endpoint, is_official_base_url,
+    normalize_base_url, HealthCheckClient, ProxyHealthOptions, ProxyHealthOutcome,
 };
+use crate::core::network::status_hooks::{StatusChange, StatusHook};
 use serde_json;
 
 #[cfg(all(feature = "network-monitoring", feature = "timings-curl"))]
@@ -43,14 +63,22 @@ use crate::core::network::proxy_health::CurlHealthCheckClient;
 #[cfg(all(feature = "network-monitoring", not(feature = "timings-curl")))]
 use crate::core::network::proxy_health::IsahcHealthCheckClient;
 
+use crate::core::network::probe_history::{ProbeHistory, ProbeRecord};
 #[cfg(not(feature = "network-monitoring"))]
 use crate::core::network::proxy_health::MockHealthCheckClient;
+use crate::core::network::status_expression::{StatusExpression, StatusMetrics};
 use crate::core::network::types::*;
+use crate::core::perf::PerfRecorder;
+use crate::core::recovery;
+use crate::core::shutdown::ShutdownCoordinator;
+use crate::core::state_location::StateLocation;
+use crate::core::stats;
+use crate::runtime;
 use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
 #[cfg(feature = "network-monitoring")]
-use isahc::config::Configurable;
+use isahc::config::{Configurable, ResolveMap};
 #[cfg(feature = "network-monitoring")]
 use isahc::{HttpClient, Request};
 
@@ -71,6 +99,38 @@ pub struct PhaseTimings {
     pub ttfb_ms: u32,       // ServerTTFB (isolated server processing time)
     pub total_ttfb_ms: u32, // TotalTTFB (end-to-end first byte time)
     pub total_ms: u32,
+    pub set_cookies: Vec<String>, // Raw Set-Cookie values (for the persisted cookie jar)
+    pub headers: std::collections::HashMap<String, String>, // Final response headers, lowercase names
+    pub connection: ConnectionInfo, // Serving IP, connections opened, redirects
+}
+
+#[cfg(feature = "timings-curl")]
+impl PhaseTimings {
+    /// Timing breakdown `DNS:..|TCP:..|TLS:..|TTFB:..|Total:..`; with `split_ttfb`
+    /// the TTFB field becomes `ServerTTFB:..ms/TotalTTFB:..ms`
+    ///
+    /// Written into one buffer sized for the widest form, so formatting never reallocates.
+    pub fn breakdown(&self, split_ttfb: bool) -> String {
+        use std::fmt::Write;
+
+        let mut breakdown = String::with_capacity(96);
+        let _ = write!(
+            breakdown,
+            "DNS:{}ms|TCP:{}ms|TLS:{}ms|",
+            self.dns_ms, self.tcp_ms, self.tls_ms
+        );
+        let _ = if split_ttfb {
+            write!(
+                breakdown,
+                "ServerTTFB:{}ms/TotalTTFB:{}ms",
+                self.ttfb_ms, self.total_ttfb_ms
+            )
+        } else {
+            write!(breakdown, "TTFB:{}ms", self.ttfb_ms)
+        };
+        let _ = write!(breakdown, "|Total:{}ms", self.total_ms);
+        breakdown
+    }
 }
 
 #[cfg(feature = "timings-curl")]
@@ -84,6 +144,22 @@ pub trait CurlProbeRunner: Send + Sync {
         body: &[u8],
         timeout_ms: u32,
     ) -> Result<PhaseTimings, NetworkError>;
+
+    /// `run` connecting to `pin.addr` for the URL host (`network.dns_cache`);
+    /// runners that cannot pin addresses resolve as usual
+    async fn run_pinned(
+        &self,
+        url: &str,
+        headers: &[(&str, String)],
+        body: &[u8],
+        timeout_ms: u32,
+        _pin: Option<&DnsPin>,
+    ) -> Result<PhaseTimings, NetworkError> {
+        self.run(url, headers, body, timeout_ms).await
+    }
+
+    /// Send later transfers as `transport` says; runners without a network ignore it
+    fn set_transport(&mut self, _transport: &TransportPolicy) {}
 }
 
 /// HTTP client abstraction for dependency injection and testing
@@ -107,10 +183,49 @@ pub trait HttpClientTrait: Send + Sync {
         ),
         String,
     >;
+
+    /// `execute_request` connecting to `pin.addr` for the URL host (`network.dns_cache`);
+    /// clients that cannot pin addresses resolve as usual
+    async fn execute_request_pinned(
+        &self,
+        url: String,
+        headers: std::collections::HashMap<String, String>,
+        body: Vec<u8>,
+        timeout_ms: u32,
+        _pin: Option<DnsPin>,
+    ) -> Result<
+        (
+            u16,
+            Duration,
+            String,
+            std::collections::HashMap<String, String>,
+            Option<String>,
+        ),
+        String,
+    > {
+        self.execute_request(url, headers, body, timeout_ms).await
+    }
+
+    /// Send later requests as `transport` says; clients without a network ignore it
+    fn set_transport(&mut self, _transport: &TransportPolicy) {}
 }
 
 // HealthCheckClient and HealthResponse are now imported from proxy_health module
 
+/// How probe and health check transfers reach the network, from the loaded config
+///
+/// Injected through [`HttpMonitor::with_transport`] so every client of one
+/// monitor follows the same settings.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TransportPolicy {
+    /// `[proxy]`, `[network.socks5]` and the proxy environment
+    pub proxy: ProxyPolicy,
+    /// `[network.tls]`: trust store, pins, client certificate and verification
+    pub tls: TlsPolicy,
+    /// Address family `network.ip_family` forces, if any
+    pub ip_family: Option<IpFamily>,
+}
+
 /// Clock abstraction for dependency injection and testing  
 pub trait ClockTrait: Send + Sync {
     /// Get current system time
@@ -123,6 +238,7 @@ pub trait ClockTrait: Send + Sync {
 #[cfg(feature = "network-monitoring")]
 pub struct IsahcHttpClient {
     client: HttpClient,
+    transport: TransportPolicy,
 }
 
 #[cfg(feature = "network-monitoring")]
@@ -144,12 +260,55 @@ impl HttpClientTrait for IsahcHttpClient {
         ),
         String,
     > {
+        self.execute_request_pinned(url, headers, body, timeout_ms, None)
+            .await
+    }
+
+    async fn execute_request_pinned(
+        &self,
+        url: String,
+        headers: std::collections::HashMap<String, String>,
+        body: Vec<u8>,
+        timeout_ms: u32,
+        pin: Option<DnsPin>,
+    ) -> Result<
+        (
+            u16,
+            Duration,
+            String,
+            std::collections::HashMap<String, String>,
+            Option<String>,
+        ),
+        String,
+    > {
+        // Resolve overrides are per client, so a pinned probe gets its own
+        let pinned_client;
+        let client = match pin {
+            Some(pin) => {
+                pinned_client = HttpClient::builder()
+                    .cookies()
+                    .dns_resolve(ResolveMap::new().add(&pin.host, pin.port, pin.addr))
+                    .build()
+                    .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+                &pinned_client
+            }
+            None => &self.client,
+        };
+
         let start = Instant::now();
 
-        let mut request = Request::post(&url)
-            .timeout(Duration::from_millis(timeout_ms as u64))
-            .body(body)
-            .map_err(|e| format!("Request creation failed: {}", e))?;
+        let mut request = isahc_tls(
+            isahc_ip_family(
+                Request::post(&url)
+                    .timeout(Duration::from_millis(timeout_ms as u64))
+                    .proxy(isahc_proxy(&self.transport.proxy, &url)?),
+                self.transport.ip_family,
+            ),
+            &self.transport.tls,
+            &url,
+        )?
+        .body(body)
+        .map_err(|e| format!("Request creation failed: {}", e))?;
 
         // Add headers
         for (key, value) in headers {
@@ -162,8 +321,7 @@ impl HttpClientTrait for IsahcHttpClient {
             request.headers_mut().insert(header_name, header_value);
         }
 
-        let response = self
-            .client
+        let response = client
             .send_async(request)
             .await
             .map_err(|e| format!("Request failed: {}", e))?;
@@ -182,10 +340,20 @@ impl HttpClientTrait for IsahcHttpClient {
         };
 
         // Capture response headers for Cloudflare bot challenge detection
-        let mut response_headers = std::collections::HashMap::new();
+        // (repeated Set-Cookie values are newline-joined so none are lost)
+        let mut response_headers: std::collections::HashMap<String, String> =
+            std::collections::HashMap::new();
         for (name, value) in response.headers() {
             if let Ok(value_str) = value.to_str() {
-                response_headers.insert(name.to_string(), value_str.to_string());
+                match response_headers.get_mut(name.as_str()) {
+                    Some(existing) if name == isahc::http::header::SET_COOKIE => {
+                        existing.push('\n');
+                        existing.push_str(value_str);
+                    }
+                    _ => {
+                        response_headers.insert(name.to_string(), value_str.to_string());
+                    }
+                }
             }
         }
 
@@ -209,6 +377,10 @@ impl HttpClientTrait for IsahcHttpClient {
             http_version,
         ))
     }
+
+    fn set_transport(&mut self, transport: &TransportPolicy) {
+        self.transport = transport.clone();
+    }
 }
 
 #[cfg(feature = "network-monitoring")]
@@ -218,7 +390,84 @@ impl IsahcHttpClient {
             .cookies() // Enable in-memory cookie store for session continuity
             .build()
             .map_err(|e| NetworkError::HttpError(format!("Failed to create HTTP client: {}", e)))?;
-        Ok(Self { client })
+        Ok(Self {
+            client,
+            transport: TransportPolicy::default(),
+        })
+    }
+}
+
+/// Proxy for an isahc request to `url` from `policy`; None = direct
+///
+/// Always set on the request, so isahc's own environment handling never applies.
+#[cfg(feature = "network-monitoring")]
+pub(crate) fn isahc_proxy(
+    policy: &ProxyPolicy,
+    url: &str,
+) -> Result<Option<isahc::http::Uri>, String> {
+    policy
+        .probe_proxy_for(url)
+        .map(|proxy| {
+            proxy
+                .parse::<isahc::http::Uri>()
+                .map_err(|e| format!("Invalid proxy URL '{}': {}", proxy, e))
+        })
+        .transpose()
+}
+
+/// Apply `policy` to an isahc request to `url`: CA bundle, client certificate
+/// and verification
+///
+/// isahc cannot check public key pins, so a pinned `https://` request is refused
+/// rather than sent unpinned, as is any request under an invalid policy.
+#[cfg(feature = "network-monitoring")]
+pub(crate) fn isahc_tls(
+    request: isahc::http::request::Builder,
+    policy: &TlsPolicy,
+    url: &str,
+) -> Result<isahc::http::request::Builder, String> {
+    use isahc::config::{CaCertificate, ClientCertificate, PrivateKey, SslOption};
+
+    if let Some(reason) = policy.error() {
+        return Err(format!(
+            "[network.tls] invalid ({}); request not sent",
+            reason
+        ));
+    }
+    if policy.is_pinned() && url.starts_with("https://") {
+        return Err(
+            "[network.tls] pinned_spki needs the curl transport (timings-curl); request not sent"
+                .to_string(),
+        );
+    }
+    let mut request = request;
+    if let Some(ca_file) = policy.ca_file() {
+        request = request.ssl_ca_certificate(CaCertificate::file(ca_file));
+    }
+    if let Some(identity) = policy.client_identity() {
+        let key = PrivateKey::pem_file(&identity.key, identity.key_password.clone());
+        request = request.ssl_client_certificate(ClientCertificate::pem_file(&identity.cert, key));
+    }
+    if policy.skips_verification() {
+        request = request.ssl_options(
+            SslOption::DANGER_ACCEPT_INVALID_CERTS | SslOption::DANGER_ACCEPT_INVALID_HOSTS,
+        );
+    }
+    Ok(request)
+}
+
+/// Restrict an isahc request to `family`, if any
+#[cfg(feature = "network-monitoring")]
+pub(crate) fn isahc_ip_family(
+    request: isahc::http::request::Builder,
+    family: Option<IpFamily>,
+) -> isahc::http::request::Builder {
+    use isahc::config::IpVersion;
+
+    match family {
+        Some(IpFamily::V4) => request.ip_version(IpVersion::V4),
+        Some(IpFamily::V6) => request.ip_version(IpVersion::V6),
+        None => request,
     }
 }
 
@@ -240,7 +489,10 @@ impl ClockTrait for SystemClock {
 
 /// Production curl runner implementation
 #[cfg(feature = "timings-curl")]
-pub struct RealCurlRunner;
+#[derive(Default)]
+pub struct RealCurlRunner {
+    transport: TransportPolicy,
+}
 
 #[cfg(feature = "timings-curl")]
 #[async_trait::async_trait]
@@ -252,14 +504,27 @@ impl CurlProbeRunner for RealCurlRunner {
         body: &[u8],
         timeout_ms: u32,
     ) -> Result<PhaseTimings, NetworkError> {
+        self.run_pinned(url, headers, body, timeout_ms, None).await
+    }
+
+    async fn run_pinned(
+        &self,
+        url: &str,
+        headers: &[(&str, String)],
+        body: &[u8],
+        timeout_ms: u32,
+        pin: Option<&DnsPin>,
+    ) -> Result<PhaseTimings, NetworkError> {
+        let resolve_entry = pin.map(DnsPin::resolve_entry);
         let url = url.to_string();
         let headers = headers
             .iter()
             .map(|(k, v)| (k.to_string(), v.clone()))
             .collect::<Vec<_>>();
         let body = body.to_vec();
+        let transport = self.transport.clone();
 
-        let result = tokio::task::spawn_blocking(move || -> Result<PhaseTimings, String> {
+        let result = runtime::spawn_blocking(move || -> Result<PhaseTimings, String> {
             let mut handle = Easy::new();
 
             // Configure request
@@ -275,8 +540,24 @@ impl CurlProbeRunner for RealCurlRunner {
             handle
                 .timeout(std::time::Duration::from_millis(timeout_ms as u64))
                 .map_err(|e| format!("Timeout set failed: {}", e))?;
+            set_curl_proxy(&mut handle, &transport.proxy, &url)?;
+            set_curl_tls(&mut handle, &transport.tls)?;
+            set_curl_ip_family(&mut handle, transport.ip_family)?;
+            if let Some(entry) = resolve_entry {
+                let mut resolve = curl::easy::List::new();
+                resolve
+                    .append(&entry)
+                    .map_err(|e| format!("Resolve entry failed: {}", e))?;
+                handle
+                    .resolve(resolve)
+                    .map_err(|e| format!("Resolve set failed: {}", e))?;
+            }
 
             // Bot-fight protocol enhancements
+            #[cfg(feature = "tls-parity")]
+            crate::core::network::tls_profile::apply_claude_code_tls_profile(&mut handle)
+                .map_err(|e| format!("TLS profile failed: {}", e))?;
+            #[cfg(not(feature = "tls-parity"))]
             handle
                 .http_version(curl::easy::HttpVersion::V2TLS)
                 .map_err(|e| format!("HTTP/2 version failed: {}", e))?;
@@ -291,6 +572,47 @@ impl CurlProbeRunner for RealCurlRunner {
             handle
                 .cookie_file("")
                 .map_err(|e| format!("Cookie engine failed: {}", e))?;
+            handle
+                .certinfo(true)
+                .map_err(|e| format!("Certificate info failed: {}", e))?;
+
+            // Collect Set-Cookie values for the persisted cookie jar, and the
+            // final response's headers (rate limits, bot challenge detection)
+            let set_cookies = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+            let set_cookies_sink = set_cookies.clone();
+            let response_headers =
+                std::sync::Arc::new(std::sync::Mutex::new(std::collections::HashMap::new()));
+            let response_headers_sink = response_headers.clone();
+            handle
+                .header_function(move |line| {
+                    let Ok(line) = std::str::from_utf8(line) else {
+                        return true;
+                    };
+                    let Ok(mut headers) = response_headers_sink.lock() else {
+                        return true;
+                    };
+                    // A status line starts the headers of the next response (redirect, 100)
+                    if line.starts_with("HTTP/") {
+                        headers.clear();
+                    } else if let Some((name, value)) = line.split_once(':') {
+                        let (name, value) = (name.trim().to_ascii_lowercase(), value.trim());
+                        if name == "set-cookie" {
+                            if let Ok(mut cookies) = set_cookies_sink.lock() {
+                                cookies.push(value.to_string());
+                            }
+                        } else {
+                            headers
+                                .entry(name)
+                                .and_modify(|existing: &mut String| {
+                                    existing.push_str(", ");
+                                    existing.push_str(value);
+                                })
+                                .or_insert_with(|| value.to_string());
+                        }
+                    }
+                    true
+                })
+                .map_err(|e| format!("Header function failed: {}", e))?;
 
             // Set headers
             let mut header_list = curl::easy::List::new();
@@ -311,10 +633,24 @@ impl CurlProbeRunner for RealCurlRunner {
                 })
                 .map_err(|e| format!("Write function failed: {}", e))?;
 
-            // Execute request and capture timings
-            handle
-                .perform()
-                .map_err(|e| format!("Request perform failed: {}", e))?;
+            // Execute request and capture timings. A chain that fails verification
+            // or its pins fails the probe as a connection error, keeping its
+            // certificates; an unverified chain is never recorded as trusted.
+            let performed = handle.perform();
+            let untrusted = performed.as_ref().err().is_some_and(|e| {
+                e.is_peer_failed_verification()
+                    || e.is_ssl_cacert()
+                    || e.code() == curl_sys::CURLE_SSL_PINNEDPUBKEYNOTMATCH
+            });
+            let certificate = CertificateChain::from_certinfo(
+                &curl_certinfo(&handle),
+                !untrusted && !transport.tls.skips_verification(),
+            );
+            if let Err(e) = performed {
+                if !untrusted || certificate.is_none() {
+                    return Err(format!("Request perform failed: {}", e));
+                }
+            }
 
             // Extract phase timings from libcurl (in seconds, convert to ms)
             let dns_time = handle
@@ -360,6 +696,15 @@ impl CurlProbeRunner for RealCurlRunner {
                 ttfb_ms,
                 total_ttfb_ms,
                 total_ms,
+                set_cookies: set_cookies.lock().map(|c| c.clone()).unwrap_or_default(),
+                headers: response_headers
+                    .lock()
+                    .map(|h| h.clone())
+                    .unwrap_or_default(),
+                connection: ConnectionInfo {
+                    certificate,
+                    ..curl_connection_info(&mut handle)
+                },
             })
         })
         .await
@@ -368,8 +713,172 @@ impl CurlProbeRunner for RealCurlRunner {
 
         Ok(result)
     }
+
+    fn set_transport(&mut self, transport: &TransportPolicy) {
+        self.transport = transport.clone();
+    }
+}
+
+/// Serving address, connections and redirects of a finished transfer
+///
+/// Diagnostics only: a value libcurl cannot report is left empty or 0.
+#[cfg(feature = "timings-curl")]
+pub(crate) fn curl_connection_info(handle: &mut Easy) -> ConnectionInfo {
+    ConnectionInfo {
+        primary_ip: handle.primary_ip().ok().flatten().map(str::to_string),
+        num_connects: curl_num_connects(handle),
+        redirect_count: handle.redirect_count().unwrap_or(0),
+        redirect_ms: handle
+            .redirect_time()
+            .map(|time| (time.as_secs_f64() * 1000.0) as u32)
+            .unwrap_or(0),
+        certificate: None,
+    }
+}
+
+/// Send a curl transfer to `url` through the proxy `policy` picks, or directly
+///
+/// An empty proxy keeps libcurl from reading the environment itself.
+#[cfg(feature = "timings-curl")]
+pub(crate) fn set_curl_proxy(
+    handle: &mut Easy,
+    policy: &ProxyPolicy,
+    url: &str,
+) -> Result<(), String> {
+    handle
+        .proxy(policy.probe_proxy_for(url).unwrap_or(""))
+        .map_err(|e| format!("Proxy set failed: {}", e))
+}
+
+/// Apply `policy` to a curl transfer: CA bundle, public key pins, client
+/// certificate and verification
+///
+/// Under an invalid policy the transfer is refused before it is sent.
+#[cfg(feature = "timings-curl")]
+pub(crate) fn set_curl_tls(handle: &mut Easy, policy: &TlsPolicy) -> Result<(), String> {
+    if let Some(reason) = policy.error() {
+        return Err(format!(
+            "[network.tls] invalid ({}); request not sent",
+            reason
+        ));
+    }
+    if let Some(ca_file) = policy.ca_file() {
+        handle
+            .cainfo(ca_file)
+            .map_err(|e| format!("CA file set failed: {}", e))?;
+    }
+    if let Some(pins) = policy.pinned_public_key() {
+        handle
+            .pinned_public_key(&pins)
+            .map_err(|e| format!("Pinned public key set failed: {}", e))?;
+    }
+    if let Some(identity) = policy.client_identity() {
+        handle
+            .ssl_cert(&identity.cert)
+            .map_err(|e| format!("Client certificate set failed: {}", e))?;
+        handle
+            .ssl_key(&identity.key)
+            .map_err(|e| format!("Client key set failed: {}", e))?;
+        if let Some(ref password) = identity.key_password {
+            handle
+                .key_password(password)
+                .map_err(|e| format!("Client key password set failed: {}", e))?;
+        }
+    }
+    if policy.skips_verification() {
+        handle
+            .ssl_verify_peer(false)
+            .map_err(|e| format!("Peer verification set failed: {}", e))?;
+        handle
+            .ssl_verify_host(false)
+            .map_err(|e| format!("Host verification set failed: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Restrict a curl transfer to `family`, if any
+#[cfg(feature = "timings-curl")]
+pub(crate) fn set_curl_ip_family(
+    handle: &mut Easy,
+    family: Option<IpFamily>,
+) -> Result<(), String> {
+    use curl::easy::IpResolve;
+
+    let resolve = match family {
+        Some(IpFamily::V4) => IpResolve::V4,
+        Some(IpFamily::V6) => IpResolve::V6,
+        None => IpResolve::Any,
+    };
+    handle
+        .ip_resolve(resolve)
+        .map_err(|e| format!("IP family set failed: {}", e))
+}
+
+/// Certificate chain entries (`CURLINFO_CERTINFO`), which the curl crate does not wrap
+///
+/// One `Name:value` list per certificate, server certificate first; empty unless
+/// `certinfo` was enabled on the handle and the TLS backend supports it.
+#[cfg(feature = "timings-curl")]
+fn curl_certinfo(handle: &Easy) -> Vec<Vec<String>> {
+    let mut info: *mut curl_sys::curl_certinfo = std::ptr::null_mut();
+    // SAFETY: the handle is live for the borrow and CERTINFO writes one pointer
+    let code = unsafe {
+        curl_sys::curl_easy_getinfo(
+            handle.raw(),
+            curl_sys::CURLINFO_CERTINFO,
+            &mut info as *mut *mut curl_sys::curl_certinfo,
+        )
+    };
+    if code != curl_sys::CURLE_OK || info.is_null() {
+        return Vec::new();
+    }
+
+    // SAFETY: libcurl owns the chain until the handle is reused or dropped; every
+    // list and string is copied out before returning
+    unsafe {
+        let info = &*info;
+        (0..info.num_of_certs.max(0) as usize)
+            .map(|index| {
+                let mut entries = Vec::new();
+                let mut node = *info.certinfo.add(index);
+                while !node.is_null() {
+                    if !(*node).data.is_null() {
+                        entries.push(
+                            std::ffi::CStr::from_ptr((*node).data)
+                                .to_string_lossy()
+                                .into_owned(),
+                        );
+                    }
+                    node = (*node).next;
+                }
+                entries
+            })
+            .collect()
+    }
+}
+
+/// `CURLINFO_NUM_CONNECTS`, which the curl crate does not wrap
+#[cfg(feature = "timings-curl")]
+fn curl_num_connects(handle: &Easy) -> u32 {
+    let mut num_connects: std::os::raw::c_long = 0;
+    // SAFETY: the handle is live for the borrow and NUM_CONNECTS writes one long
+    let code = unsafe {
+        curl_sys::curl_easy_getinfo(
+            handle.raw(),
+            curl_sys::CURLINFO_NUM_CONNECTS,
+            &mut num_connects as *mut std::os::raw::c_long,
+        )
+    };
+    if code == curl_sys::CURLE_OK {
+        num_connects.max(0) as u32
+    } else {
+        0
+    }
 }
 
+/// Upper bound for one cycle's proxy health check, including fallback URLs
+const PROXY_HEALTH_TIMEOUT_MS: u64 = 3000;
+
 /// HTTP monitoring component - single writer for network state
 ///
 /// HttpMonitor executes lightweight HTTP probes and maintains authoritative
@@ -389,6 +898,44 @@ pub struct HttpMonitor {
     timeout_override_ms: Option<u32>,
     /// Current session ID for COLD probe deduplication
     current_session_id: Option<String>,
+    /// Outbound host allowlist checked before any probe or health request
+    host_policy: HostPolicy,
+    /// Persisted cookie jar location (None = in-memory cookies only)
+    cookie_jar_path: Option<PathBuf>,
+    /// Run auxiliary endpoint checks (GET /v1/models) for composite health
+    aux_checks: bool,
+    /// Persisted DNS cache for the probe host (`network.dns_cache`)
+    dns_cache_path: Option<PathBuf>,
+    /// Age-out horizon for rolling samples in seconds (`network.sample_horizon_secs`)
+    sample_horizon_secs: Option<u64>,
+    /// Retention of the per-probe history (`[network.history]`)
+    history_retention: HistoryConfig,
+    /// Window length, status cutoffs and extra percentiles (`[network.window]`)
+    window_policy: WindowPolicy,
+    /// Probes in a row needed before the shown status changes (`[network.hysteresis]`)
+    hysteresis: HysteresisConfig,
+    /// Failed-probe budget evaluated after each probe (`[network.error_budget]`)
+    error_budget: Option<ErrorBudgetPolicy>,
+    /// Run the proxy health check alongside probes (`proxy_health` kill switch)
+    proxy_health: bool,
+    /// Days before expiry a custom gateway's certificate is warned about (`network.cert_warn_days`)
+    cert_warn_days: u32,
+    /// Forced address family, or `compare` for per-family checks (`network.ip_family`)
+    ip_family: IpFamilyMode,
+    /// Proxy, TLS and address family settings handed to every client (see [`TransportPolicy`])
+    transport: TransportPolicy,
+    /// User-defined classification overriding the built-in one (`network.status_expression`)
+    status_expression: Option<StatusExpression>,
+    /// Hooks run after each probe's state is written (e.g. the pause advisory)
+    status_hooks: Vec<Box<dyn StatusHook>>,
+    /// Whether OAuth credentials may be probed (`network.oauth_probe`)
+    oauth_probe: bool,
+    /// Model, token budget and path of x-api-key probes (`[network.probe]`)
+    probe_request: ProbeRequest,
+    /// Alternative base URLs probed alongside the active one (`network.compare_endpoints`)
+    compare_endpoints: Vec<String>,
+    /// Timeouts and extra checks of the active probe profile (`CCSTATUS_PROBE_PROFILE`)
+    probe_profile: ProbeProfile,
     /// Optional curl probe runner for phase timing measurement
     #[cfg(feature = "timings-curl")]
     curl_runner: Option<Box<dyn CurlProbeRunner>>,
@@ -397,7 +944,8 @@ pub struct HttpMonitor {
 impl HttpMonitor {
     /// Create new HttpMonitor with default configuration
     ///
-    /// Uses default state path: `~/.claude/ccstatus/ccstatus-monitoring.json`
+    /// Uses default state path: `~/.claude/ccstatus/ccstatus-monitoring.json`; callers
+    /// honouring `[network] local_state_dir` pass [`StateLocation::monitoring_path`]
     ///
     /// When `timings-curl` feature is enabled, automatically wires `RealCurlRunner`
     /// for detailed phase timings (disabled in test builds for safety).
@@ -410,12 +958,9 @@ impl HttpMonitor {
     pub fn new(state_path: Option<PathBuf>) -> Result<Self, NetworkError> {
         let state_path = match state_path {
             Some(path) => path,
-            None => {
-                let home = dirs::home_dir().ok_or(NetworkError::HomeDirNotFound)?;
-                home.join(".claude")
-                    .join("ccstatus")
-                    .join("ccstatus-monitoring.json")
-            }
+            None => StateLocation::from_config(&NetworkConfig::default())
+                .ok_or(NetworkError::HomeDirNotFound)?
+                .monitoring_path(),
         };
 
         #[cfg(feature = "network-monitoring")]
@@ -438,19 +983,40 @@ impl HttpMonitor {
             clock: Box::new(SystemClock),
             timeout_override_ms: None,
             current_session_id: None,
+            host_policy: HostPolicy::default(),
+            cookie_jar_path: None,
+            dns_cache_path: None,
+            aux_checks: false,
+            sample_horizon_secs: None,
+            history_retention: HistoryConfig::default(),
+            window_policy: WindowPolicy::default(),
+            hysteresis: HysteresisConfig::default(),
+            error_budget: None,
+            proxy_health: true,
+            cert_warn_days: DEFAULT_WARN_DAYS,
+            ip_family: IpFamilyMode::Auto,
+            transport: TransportPolicy::default(),
+            status_expression: None,
+            status_hooks: Vec::new(),
+            oauth_probe: true,
+            probe_request: ProbeRequest::default(),
+            compare_endpoints: Vec::new(),
+            probe_profile: ProbeProfile::default(),
             #[cfg(feature = "timings-curl")]
-            curl_runner: Some(Box::new(RealCurlRunner)),
+            curl_runner: Some(Box::new(RealCurlRunner::default())),
         })
     }
 
     /// Configure HttpMonitor with custom HTTP client (for testing)
-    pub fn with_http_client(mut self, client: Box<dyn HttpClientTrait>) -> Self {
+    pub fn with_http_client(mut self, mut client: Box<dyn HttpClientTrait>) -> Self {
+        client.set_transport(&self.transport);
         self.http_client = client;
         self
     }
 
     /// Configure HttpMonitor with custom health check client (for testing)
-    pub fn with_health_client(mut self, client: Box<dyn HealthCheckClient>) -> Self {
+    pub fn with_health_client(mut self, mut client: Box<dyn HealthCheckClient>) -> Self {
+        client.set_transport(&self.transport);
         self.health_client = client;
         self
     }
@@ -463,7 +1029,8 @@ impl HttpMonitor {
 
     /// Configure HttpMonitor with custom curl runner (for testing with timings-curl feature)
     #[cfg(feature = "timings-curl")]
-    pub fn with_curl_runner(mut self, runner: Box<dyn CurlProbeRunner>) -> Self {
+    pub fn with_curl_runner(mut self, mut runner: Box<dyn CurlProbeRunner>) -> Self {
+        runner.set_transport(&self.transport);
         self.curl_runner = Some(runner);
         self
     }
@@ -484,6 +1051,155 @@ impl HttpMonitor {
         self
     }
 
+    /// Restrict probes and health checks to hosts allowed by `network.allowed_hosts`
+    pub fn with_host_policy(mut self, policy: HostPolicy) -> Self {
+        self.host_policy = policy;
+        self
+    }
+
+    /// Hosts outbound requests of this monitor may reach
+    pub fn host_policy(&self) -> &HostPolicy {
+        &self.host_policy
+    }
+
+    /// Send probes and health checks through the proxy, TLS and address family settings of
+    /// `transport`, on every client
+    pub fn with_transport(mut self, transport: TransportPolicy) -> Self {
+        self.transport = transport;
+        self.apply_transport();
+        self
+    }
+
+    /// Hand the current [`TransportPolicy`] to every client
+    fn apply_transport(&mut self) {
+        self.http_client.set_transport(&self.transport);
+        self.health_client.set_transport(&self.transport);
+        #[cfg(feature = "timings-curl")]
+        if let Some(runner) = self.curl_runner.as_mut() {
+            runner.set_transport(&self.transport);
+        }
+    }
+
+    /// Persist probe cookies at `path` across invocations (`network.persist_cookies`)
+    pub fn with_cookie_jar(mut self, path: PathBuf) -> Self {
+        self.cookie_jar_path = Some(path);
+        self
+    }
+
+    /// Cache probe host lookups at `path` for their TTL and pin probes to them (`network.dns_cache`)
+    pub fn with_dns_cache(mut self, path: PathBuf) -> Self {
+        self.dns_cache_path = Some(path);
+        self
+    }
+
+    /// Enable auxiliary endpoint checks folded into a composite health score (`network.aux_checks`)
+    pub fn with_aux_checks(mut self, enabled: bool) -> Self {
+        self.aux_checks = enabled;
+        self
+    }
+
+    /// Evict rolling samples older than `secs` before computing percentiles (`network.sample_horizon_secs`)
+    pub fn with_sample_horizon(mut self, secs: Option<u64>) -> Self {
+        self.sample_horizon_secs = secs;
+        self
+    }
+
+    /// Keep the per-probe history within `retention` (`[network.history]`)
+    pub fn with_history_retention(mut self, retention: HistoryConfig) -> Self {
+        self.history_retention = retention;
+        self
+    }
+
+    /// Window length and percentiles used to classify GREEN/COLD probes (`[network.window]`)
+    pub fn with_window_policy(mut self, policy: WindowPolicy) -> Self {
+        self.window_policy = policy;
+        self
+    }
+
+    /// Probes in a row needed before the shown status degrades or recovers (`[network.hysteresis]`)
+    pub fn with_hysteresis(mut self, hysteresis: HysteresisConfig) -> Self {
+        self.hysteresis = hysteresis;
+        self
+    }
+
+    /// Track the failed-probe budget over the probe history (`[network.error_budget]`)
+    pub fn with_error_budget(mut self, policy: Option<ErrorBudgetPolicy>) -> Self {
+        self.error_budget = policy;
+        self
+    }
+
+    /// Enable or disable the proxy health check (`proxy_health` flag)
+    pub fn with_proxy_health(mut self, enabled: bool) -> Self {
+        self.proxy_health = enabled;
+        self
+    }
+
+    /// Warn about a custom gateway's certificate `days` before it expires; 0 = only
+    /// untrusted chains (`network.cert_warn_days`)
+    pub fn with_cert_warn_days(mut self, days: u32) -> Self {
+        self.cert_warn_days = days;
+        self
+    }
+
+    /// Force a family on every transfer and DNS cache pin, or check both
+    /// families after each probe (`network.ip_family`)
+    pub fn with_ip_family(mut self, mode: IpFamilyMode) -> Self {
+        self.ip_family = mode;
+        self.transport.ip_family = ip_family::forced_family(mode);
+        self.apply_transport();
+        self
+    }
+
+    /// Classify probes with a user-defined expression instead of the built-in thresholds
+    pub fn with_status_expression(mut self, expression: Option<StatusExpression>) -> Self {
+        self.status_expression = expression;
+        self
+    }
+
+    /// Run `hook` after every probe's state is written
+    pub fn with_status_hook(mut self, hook: Box<dyn StatusHook>) -> Self {
+        self.status_hooks.push(hook);
+        self
+    }
+
+    /// Allow or refuse probes with OAuth credentials (`network.oauth_probe`)
+    pub fn with_oauth_probe(mut self, enabled: bool) -> Self {
+        self.oauth_probe = enabled;
+        self
+    }
+
+    /// Probe model, token budget and path for x-api-key credentials (`[network.probe]`)
+    pub fn with_probe_request(mut self, request: ProbeRequest) -> Self {
+        self.probe_request = request;
+        self
+    }
+
+    /// Probe these base URLs in parallel with the active one on GREEN and COLD probes
+    /// (`network.compare_endpoints`); each costs one more API request
+    pub fn with_compare_endpoints(mut self, base_urls: Vec<String>) -> Self {
+        self.compare_endpoints = base_urls
+            .iter()
+            .map(|url| normalize_base_url(url))
+            .filter(|url| !url.is_empty())
+            .collect();
+        self.compare_endpoints.dedup();
+        self
+    }
+
+    /// URL probed for `creds`: the configured path, or `/v1/messages` for the OAuth masquerade
+    fn probe_endpoint(&self, creds: &ApiCredentials) -> String {
+        if creds.source == CredentialSource::OAuth {
+            build_messages_endpoint(&creds.base_url)
+        } else {
+            self.probe_request.endpoint(&creds.base_url)
+        }
+    }
+
+    /// Whether an OAuth probe would run: enabled in config and built with `oauth-probe`
+    pub fn oauth_probe_enabled(&self) -> bool {
+        self.oauth_probe && cfg!(feature = "oauth-probe")
+    }
+
     /// Set session ID for COLD probe deduplication
     ///
     /// This method allows NetworkSegment to provide the actual session_id for proper
@@ -502,6 +1218,35 @@ impl HttpMonitor {
         self.current_session_id = Some(session_id);
     }
 
+    /// Use `profile`'s timeouts and payload strategy for the following probes
+    pub fn set_probe_profile(&mut self, profile: ProbeProfile) {
+        self.probe_profile = profile;
+    }
+
+    pub fn probe_profile(&self) -> ProbeProfile {
+        self.probe_profile
+    }
+
+    /// Pin `profile` to `session_id` so later stdin events of the session keep it
+    ///
+    /// # Errors
+    ///
+    /// Returns `NetworkError::StateFileError` if the state cannot be persisted.
+    pub async fn pin_probe_profile(
+        &self,
+        session_id: &str,
+        profile: ProbeProfile,
+    ) -> Result<(), NetworkError> {
+        let mut state = self.load_state().await?;
+        state.monitoring_state.probe_profile = Some(SessionProfile {
+            profile,
+            session_id: session_id.to_string(),
+            since: self.clock.local_timestamp(),
+        });
+        state.timestamp = self.clock.local_timestamp();
+        self.write_state_atomic(&state).await
+    }
+
     /// Set last GREEN window ID for per-window deduplication
     ///
     /// Updates the persisted state with the GREEN window ID to prevent redundant
@@ -629,6 +1374,18 @@ impl HttpMonitor {
     ) -> Result<ProbeOutcome, NetworkError> {
         let debug_logger = get_debug_logger();
         let probe_start = self.clock.now();
+        let started = Instant::now();
+
+        // Host allowlist: never contact a host outside `network.allowed_hosts`
+        if !self.host_policy.is_url_allowed(&creds.base_url) {
+            debug_logger
+                .debug(
+                    "HttpMonitor",
+                    &format!("Probe blocked by host policy: base_url={}", creds.base_url),
+                )
+                .await;
+            return self.write_blocked_by_policy(mode, creds).await;
+        }
 
         // Calculate timeout based on mode and existing state
         let timeout_ms = self.calculate_timeout(mode).await?;
@@ -641,20 +1398,52 @@ impl HttpMonitor {
             probe_id.clone(),
         );
 
-        // Execute HTTP probe
-        let probe_result = self
-            .execute_http_probe(&creds, timeout_ms, probe_start)
-            .await;
+        // Cached address for the probe host, so DNS stays out of the measured latency;
+        // a miss may spend at most what is left of the probe's timeout
+        let budget = Duration::from_millis(timeout_ms as u64).saturating_sub(started.elapsed());
+        let dns = self.lookup_probe_host(&creds, mode, budget).await;
+        let dns_pin = dns.as_ref().and_then(|dns| dns.pin.as_ref());
+
+        // Execute HTTP probe, proxy health check and endpoint comparison concurrently;
+        // each has its own timeout
+        let (probe_result, proxy_health, compared) = futures::join!(
+            self.execute_http_probe(&creds, timeout_ms, probe_start, dns_pin),
+            self.check_proxy_health(&creds),
+            self.probe_alternatives(&creds, mode, timeout_ms)
+        );
 
-        let (status_code, latency_ms, breakdown, error_type, http_version) = match probe_result {
-            Ok((status, duration, breakdown, response_headers, http_version)) => {
+        // Serving IP, connection count and redirects (curl transport only)
+        let connection = probe_result
+            .as_ref()
+            .ok()
+            .and_then(|(.., connection)| connection.clone());
+        let (
+            status_code,
+            latency_ms,
+            breakdown,
+            error_type,
+            http_version,
+            error_headers,
+            rate_limit,
+        ) = match probe_result {
+            Ok((status, duration, breakdown, response_headers, http_version, _)) => {
                 let error_type = self.classify_http_error(status, &response_headers);
+                // Keep what the edge returned, but only for failed probes
+                let error_headers = if error_type.is_some() {
+                    snapshot_error_headers(&response_headers)
+                } else {
+                    Default::default()
+                };
+                let rate_limit =
+                    RateLimitInfo::from_headers(&response_headers, self.now_unix_secs());
                 (
                     status,
                     duration.as_millis() as u32,
                     breakdown,
                     error_type,
                     http_version,
+                    error_headers,
+                    rate_limit,
                 )
             }
             Err(NetworkError::SkipProbe(skip_reason)) => {
@@ -664,22 +1453,25 @@ impl HttpMonitor {
                     .await;
 
                 let state = self.load_state().await?;
-                let outcome = ProbeOutcome {
-                    status: state.status,
-                    metrics: ProbeMetrics {
+                // No state is written since we skipped
+                let outcome = ProbeOutcome::builder(mode, state.status)
+                    .with_metrics(ProbeMetrics {
                         latency_ms: state.network.latency_ms,
                         breakdown: state.network.breakdown,
                         last_http_status: state.network.last_http_status,
                         error_type: state.network.error_type,
                         http_version: state.network.http_version,
-                    },
-                    p95_latency_ms: state.network.p95_latency_ms,
-                    rolling_len: state.network.rolling_totals.len(),
-                    api_config: state.api_config.unwrap_or_default(),
-                    mode,
-                    state_written: false, // No state was written since we skipped
-                    timestamp_local: state.timestamp,
-                };
+                        error_headers: Default::default(),
+                        connection: state.network.connection,
+                        rate_limit: state.network.rate_limit,
+                    })
+                    .with_p95(
+                        state.network.p95_latency_ms,
+                        state.network.rolling_totals.len(),
+                    )
+                    .with_api_config(state.api_config.unwrap_or_default())
+                    .with_timestamp(state.timestamp)
+                    .build()?;
 
                 debug_logger.network_probe_end(
                     &format!("{:?}", mode),
@@ -711,8 +1503,11 @@ impl HttpMonitor {
                     0,
                     elapsed_ms as u32,
                     breakdown,
-                    Some("connection_error".to_string()),
+                    // Which phase failed: dns_error / connect_refused / tls_error / timeout
+                    Some(ErrorTracker::classify_transport_error(&err.to_string())),
                     None, // No HTTP version available for connection errors
+                    Default::default(),
+                    None,
                 )
             }
         };
@@ -724,12 +1519,45 @@ impl HttpMonitor {
             last_http_status: status_code,
             error_type: error_type.clone(),
             http_version: http_version.clone(),
+            error_headers,
+            connection,
+            rate_limit,
         };
 
-        // Process probe results and update state
+        // A cached address that no longer answers is dropped for the next probe
+        if status_code == 0 && dns.as_ref().map(|dns| dns.status) == Some(DnsCacheStatus::Hit) {
+            self.forget_probe_host(&creds).await;
+        }
+
+        // Process probe results and update state
+        let active_base_url = normalize_base_url(&creds.base_url);
         let outcome = self
-            .process_probe_results(mode, creds, metrics, last_jsonl_error_event)
+            .process_probe_results(
+                mode,
+                creds,
+                metrics,
+                proxy_health,
+                last_jsonl_error_event,
+                dns,
+            )
             .await?;
+        if !compared.is_empty() {
+            self.record_comparison(active_base_url, status_code, latency_ms, compared)
+                .await?;
+        }
+
+        // Version-tagged history for `ccstatus net report --compare-versions`
+        if let Err(e) = self
+            .probe_history()
+            .append(&ProbeRecord::from_outcome(&outcome))
+        {
+            debug_logger
+                .debug(
+                    "HttpMonitor",
+                    &format!("Probe history append failed: {}", e),
+                )
+                .await;
+        }
 
         debug_logger.network_probe_end(
             &format!("{:?}", mode),
@@ -790,6 +1618,7 @@ impl HttpMonitor {
         state.monitoring_enabled = monitoring_enabled;
         state.api_config = None; // Clear API config when no credentials
         state.timestamp = self.clock.local_timestamp();
+        state.uptime.record(&state.status, self.now_unix_secs());
 
         // Write state atomically
         self.write_state_atomic(&state).await?;
@@ -803,6 +1632,269 @@ impl HttpMonitor {
         Ok(())
     }
 
+    /// Record a probe that was refused by the host allowlist
+    ///
+    /// No request is sent. The state is marked `BlockedByPolicy` with
+    /// `error_type = "blocked_by_policy"` while rolling statistics are preserved,
+    /// so a policy violation is never mistaken for a network failure.
+    async fn write_blocked_by_policy(
+        &mut self,
+        mode: ProbeMode,
+        creds: ApiCredentials,
+    ) -> Result<ProbeOutcome, NetworkError> {
+        let mut state = self.load_state_internal().await.unwrap_or_default();
+
+        state.status = NetworkStatus::BlockedByPolicy;
+        state.monitoring_state.state = NetworkStatus::BlockedByPolicy;
+        state.monitoring_enabled = true;
+        state.api_config = Some(ApiConfig {
+            endpoint: self.probe_endpoint(&creds),
+            source: creds.source.to_string(),
+        });
+        state.network.latency_ms = 0;
+        state.network.breakdown = String::new();
+        state.network.last_http_status = 0;
+        state.network.error_type = Some("blocked_by_policy".to_string());
+        state.network.http_version = None;
+        state.network.set_proxy_health(None, None);
+        state.timestamp = self.clock.local_timestamp();
+        state.uptime.record(&state.status, self.now_unix_secs());
+
+        if mode == ProbeMode::Cold {
+            if let Some(ref session_id) = self.current_session_id {
+                state.monitoring_state.last_cold_session_id = Some(session_id.clone());
+                state.monitoring_state.last_cold_probe_at = Some(state.timestamp.clone());
+            }
+        }
+
+        self.write_state_atomic(&state).await?;
+
+        ProbeOutcome::builder(mode, NetworkStatus::BlockedByPolicy)
+            .with_metrics(ProbeMetrics {
+                error_type: state.network.error_type.clone(),
+                ..ProbeMetrics::default()
+            })
+            .with_p95(
+                state.network.p95_latency_ms,
+                state.network.rolling_totals.len(),
+            )
+            .with_api_config(state.api_config.unwrap_or_default())
+            .with_state_written(true)
+            .with_timestamp(state.timestamp)
+            .build()
+    }
+
+    /// Whether the RED watchdog should demote RED probing for `error_at`
+    ///
+    /// True when the current RED streak was started by the same transcript error
+    /// (`error_at`, raw timestamp) and has lasted at least `ceiling_secs`.
+    pub fn red_watchdog_expired(
+        &self,
+        state: &MonitoringSnapshot,
+        error_at: &str,
+        ceiling_secs: u64,
+    ) -> bool {
+        let monitoring_state = &state.monitoring_state;
+        if monitoring_state.red_error_at.as_deref() != Some(error_at) {
+            return false;
+        }
+
+        let parse = |ts: &str| chrono::DateTime::parse_from_rfc3339(ts).ok();
+        match (
+            monitoring_state.red_since.as_deref().and_then(parse),
+            parse(&self.clock.local_timestamp()),
+        ) {
+            (Some(since), Some(now)) => (now - since).num_seconds() >= ceiling_secs as i64,
+            _ => false,
+        }
+    }
+
+    /// Replace the latency baseline shared by other machines (state sync)
+    pub async fn set_shared_baseline(
+        &self,
+        shared_baseline: Option<SharedBaseline>,
+    ) -> Result<(), NetworkError> {
+        let mut state = self.load_state().await?;
+        state.shared_baseline = shared_baseline;
+        self.write_state_atomic(&state).await
+    }
+
+    /// Record that the RED watchdog demoted a stuck RED streak
+    pub async fn set_red_watchdog_triggered(&self) -> Result<(), NetworkError> {
+        let mut state = self.load_state().await?;
+        if state.monitoring_state.red_watchdog_triggered {
+            return Ok(());
+        }
+
+        state.monitoring_state.red_watchdog_triggered = true;
+        state.timestamp = self.clock.local_timestamp();
+        self.write_state_atomic(&state).await
+    }
+
+    /// Start a clean baseline (`ccstatus net reset`), backing the state up first
+    ///
+    /// The state file is kept as `<file>.backup-<YYYYmmddTHHMMSS>`; `All` also
+    /// moves the probe history aside. Returns the backup path, or `None` when
+    /// there was no state to reset.
+    pub async fn reset_state(&self, scope: ResetScope) -> Result<Option<PathBuf>, NetworkError> {
+        if !self.state_path.exists() {
+            return Ok(None);
+        }
+
+        let stamp = chrono::DateTime::parse_from_rfc3339(&self.clock.local_timestamp())
+            .map(|now| now.format("%Y%m%dT%H%M%S").to_string())
+            .unwrap_or_else(|_| chrono::Local::now().format("%Y%m%dT%H%M%S").to_string());
+        let backup_of = |path: &std::path::Path| {
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            path.with_file_name(format!("{}.backup-{}", name, stamp))
+        };
+        let backup = backup_of(&self.state_path);
+        let backup_error = |e: std::io::Error| {
+            NetworkError::StateFileError(format!("Failed to back up state: {}", e))
+        };
+
+        match scope {
+            ResetScope::All => {
+                let history = self.probe_history();
+                if history.path().exists() {
+                    runtime::fs::rename(history.path(), backup_of(history.path()))
+                        .await
+                        .map_err(backup_error)?;
+                }
+                runtime::fs::rename(&self.state_path, &backup)
+                    .await
+                    .map_err(backup_error)?;
+            }
+            ResetScope::Statistics => {
+                let content = runtime::fs::read(&self.state_path)
+                    .await
+                    .map_err(backup_error)?;
+                runtime::fs::write(&backup, content)
+                    .await
+                    .map_err(backup_error)?;
+
+                let previous = self.load_state_internal().await?;
+                let mut state = self.empty_state();
+                state.monitoring_enabled = previous.monitoring_enabled;
+                state.api_config = previous.api_config;
+                state.monitoring_state.ledger = previous.monitoring_state.ledger;
+                state.monitoring_state.activity = previous.monitoring_state.activity;
+                state.monitoring_state.activity_changed_at =
+                    previous.monitoring_state.activity_changed_at;
+                state.monitoring_state.probe_profile = previous.monitoring_state.probe_profile;
+                // The server asked us to back off; a local reset must not cut that short
+                state.monitoring_state.penalty = previous.monitoring_state.penalty;
+                state.network.rate_limit = previous.network.rate_limit;
+                self.write_state_atomic(&state).await?;
+            }
+        }
+        Ok(Some(backup))
+    }
+
+    /// Probe each `compare_endpoints` URL with the active credentials, in parallel
+    ///
+    /// Returns `(base_url, http_status, latency_ms)` per URL, status 0 on a
+    /// connection error. Skipped for RED probes, OAuth credentials (the masquerade
+    /// only targets the official API) and URLs outside `network.allowed_hosts`.
+    async fn probe_alternatives(
+        &self,
+        creds: &ApiCredentials,
+        mode: ProbeMode,
+        timeout_ms: u32,
+    ) -> Vec<(String, u16, u32)> {
+        if mode == ProbeMode::Red || creds.source == CredentialSource::OAuth {
+            return Vec::new();
+        }
+
+        let active = normalize_base_url(&creds.base_url);
+        let probes = self
+            .compare_endpoints
+            .iter()
+            .filter(|url| **url != active && self.host_policy.is_url_allowed(url))
+            .map(|url| async move {
+                let alternative = ApiCredentials {
+                    base_url: url.clone(),
+                    ..creds.clone()
+                };
+                let started = Instant::now();
+                match self
+                    .execute_http_probe(&alternative, timeout_ms, started, None)
+                    .await
+                {
+                    Ok((status, duration, ..)) => {
+                        (url.clone(), status, duration.as_millis() as u32)
+                    }
+                    Err(_) => (url.clone(), 0, started.elapsed().as_millis() as u32),
+                }
+            });
+        futures::future::join_all(probes).await
+    }
+
+    /// Fold the active probe and its alternatives into `state.comparison`
+    ///
+    /// URLs no longer configured are dropped.
+    async fn record_comparison(
+        &self,
+        active: String,
+        http_status: u16,
+        latency_ms: u32,
+        compared: Vec<(String, u16, u32)>,
+    ) -> Result<(), NetworkError> {
+        let mut state = self.load_state_internal().await?;
+        let mut comparison = state.comparison.take().unwrap_or_default();
+        comparison
+            .endpoints
+            .retain(|url, _| *url == active || self.compare_endpoints.contains(url));
+        comparison.active = active.clone();
+
+        let checked_at = self.clock.local_timestamp();
+        for (url, http_status, latency_ms) in
+            std::iter::once((active, http_status, latency_ms)).chain(compared)
+        {
+            comparison.endpoints.entry(url).or_default().record(
+                http_status,
+                latency_ms,
+                &checked_at,
+            );
+        }
+        state.comparison = Some(comparison);
+        self.write_state_atomic(&state).await
+    }
+
+    /// Version-tagged probe history kept next to the state file
+    pub fn probe_history(&self) -> ProbeHistory {
+        ProbeHistory::for_state_path(&self.state_path).with_retention(self.history_retention)
+    }
+
+    /// Record the session activity class; persists only when it changes
+    ///
+    /// Returns `true` when the class changed (active ↔ idle).
+    pub async fn set_activity(&self, activity: ActivityClass) -> Result<bool, NetworkError> {
+        let mut state = self.load_state().await?;
+        if state.monitoring_state.activity == activity {
+            return Ok(false);
+        }
+
+        state.monitoring_state.activity = activity;
+        state.monitoring_state.activity_changed_at = Some(self.clock.local_timestamp());
+        state.timestamp = self.clock.local_timestamp();
+        self.write_state_atomic(&state).await?;
+        Ok(true)
+    }
+
+    /// Seconds left in the 429 / bot challenge penalty cooldown, if still active
+    ///
+    /// Probes of every mode are deferred while this returns `Some`.
+    pub fn penalty_remaining_secs(&self, state: &MonitoringSnapshot) -> Option<u64> {
+        state
+            .monitoring_state
+            .penalty
+            .remaining_secs(&self.clock.local_timestamp())
+    }
+
     /// Load current monitoring state for read-only access
     ///
     /// This method provides read-only access to the current monitoring state
@@ -820,24 +1912,20 @@ impl HttpMonitor {
     ///
     /// Returns `NetworkError::StateFileError` if state file cannot be read or parsed.
     /// If the state file doesn't exist, returns a default state rather than an error.
+    ///
+    /// Load time is recorded as the `state_load` stage of the invocation's perf report.
     pub async fn load_state(&self) -> Result<MonitoringSnapshot, NetworkError> {
-        self.load_state_internal().await
+        let started = Instant::now();
+        let state = self.load_state_internal().await;
+        PerfRecorder::global().record("state_load", started.elapsed());
+        state
     }
 
     // Private helper methods
 
-    /// Get timeout from environment variables (supports both naming conventions)
+    /// Timeout override from `CCSTATUS_TIMEOUT_MS` (legacy `ccstatus_TIMEOUT_MS`)
     fn get_timeout_env_var() -> Option<u32> {
-        // Try both uppercase (current) and lowercase (spec) variants for compatibility
-        let env_names = ["CCSTATUS_TIMEOUT_MS", "ccstatus_TIMEOUT_MS"];
-        for name in &env_names {
-            if let Ok(env_timeout) = std::env::var(name) {
-                if let Ok(env_val) = env_timeout.parse::<u32>() {
-                    return Some(env_val);
-                }
-            }
-        }
-        None
+        env::TIMEOUT_MS.number()
     }
 
     /// Convert UTC timestamp to local timezone ISO-8601 format
@@ -855,9 +1943,9 @@ impl HttpMonitor {
         Ok(local_dt.to_rfc3339())
     }
 
-    /// Calculate appropriate timeout for probe mode
-    async fn calculate_timeout(&self, mode: ProbeMode) -> Result<u32, NetworkError> {
-        // Check for environment override first (supports both naming conventions)
+    /// Calculate appropriate timeout for probe mode (also reported by dry runs)
+    pub async fn calculate_timeout(&self, mode: ProbeMode) -> Result<u32, NetworkError> {
+        // Check for environment override first
         if let Some(env_val) = Self::get_timeout_env_var() {
             return Ok(std::cmp::min(env_val, 6000));
         }
@@ -868,17 +1956,18 @@ impl HttpMonitor {
         }
 
         match mode {
-            ProbeMode::Red => Ok(2000), // Fixed 2000ms for RED mode
+            ProbeMode::Red => Ok(self.probe_profile.red_timeout_ms()), // Fixed per profile
             ProbeMode::Green | ProbeMode::Cold => {
                 // GREEN/COLD use adaptive timeout based on P95
                 let state = self.load_state_internal().await.unwrap_or_default();
+                let (initial, min, max) = self.probe_profile.adaptive_timeout_ms();
 
                 if state.network.rolling_totals.len() < 4 {
-                    Ok(3500) // Default when insufficient samples
+                    Ok(initial) // Default when insufficient samples
                 } else {
                     let p95 = state.network.p95_latency_ms;
                     let adaptive_timeout = p95 + 500;
-                    Ok(adaptive_timeout.clamp(2500, 4000))
+                    Ok(adaptive_timeout.clamp(min, max))
                 }
             }
         }
@@ -895,6 +1984,7 @@ impl HttpMonitor {
         creds: &ApiCredentials,
         timeout_ms: u32,
         _probe_start: Instant,
+        dns_pin: Option<&DnsPin>,
     ) -> Result<
         (
             u16,
@@ -902,101 +1992,47 @@ impl HttpMonitor {
             String,
             std::collections::HashMap<String, String>,
             Option<String>,
+            Option<ConnectionInfo>,
         ),
         NetworkError,
     > {
         // Path selection: OAuth masquerade vs x-api-key flow
         if creds.source == CredentialSource::OAuth {
-            // Check if token is expired (hard gate)
-            if let Some(expires_at) = creds.expires_at {
-                let now_ms = std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap_or_default()
-                    .as_millis() as i64;
-
-                if expires_at <= now_ms {
-                    // Token expired - return error immediately without probe
-                    let debug_logger = get_debug_logger();
-                    let _ = debug_logger
-                        .debug(
-                            "HttpMonitor",
-                            &format!(
-                                "OAuth token expired: now={} expires_at={}",
-                                now_ms, expires_at
-                            ),
-                        )
-                        .await;
-
-                    return Err(NetworkError::SkipProbe("OAuth token expired".to_string()));
-                }
+            if !self.oauth_probe {
+                return Err(NetworkError::SkipProbe(
+                    "OAuth probe disabled (network.oauth_probe)".to_string(),
+                ));
             }
+            return self.execute_oauth_probe(creds).await;
+        }
 
-            // OAuth masquerade path
-            let oauth_opts = OauthMasqueradeOptions {
-                base_url: creds.base_url.clone(),
-                access_token: creds.auth_token.clone(),
-                expires_at: creds.expires_at,
-                stream: false, // No streaming for probe requests
-            };
-
-            #[cfg(feature = "timings-curl")]
-            let result = oauth_run_probe(
-                &oauth_opts,
-                self.http_client.as_ref(),
-                self.curl_runner.as_ref().map(|r| r.as_ref()),
+        // x-api-key flow (existing implementation)
+        // Sanitized probe body shared by the curl and isahc transports
+        let body = encode_probe_payload(
+            &build_probe_payload(ProbePayloadKind::ApiKey, &self.probe_request),
+            &self.probe_request,
+        )?;
+        get_debug_logger()
+            .debug(
+                "HttpMonitor",
+                &format!(
+                    "probe_payload sha256={} size={}",
+                    payload_checksum(&body),
+                    body.len()
+                ),
             )
             .await;
 
-            #[cfg(not(feature = "timings-curl"))]
-            let result = oauth_run_probe(&oauth_opts, self.http_client.as_ref()).await;
-
-            return match result {
-                Ok(result) => {
-                    let duration = Duration::from_millis(result.duration_ms as u64);
-                    Ok((
-                        result.status,
-                        duration,
-                        result.breakdown,
-                        result.response_headers,
-                        result.http_version,
-                    ))
-                }
-                Err(e) => {
-                    let debug_logger = get_debug_logger();
-                    let _ = debug_logger
-                        .error(
-                            "HttpMonitor",
-                            &format!("OAuth masquerade probe failed: {}", e),
-                        )
-                        .await;
-
-                    // For now, return the error rather than falling back to x-api-key
-                    // (OAuth-only environments should not have x-api-key as fallback)
-                    Err(e)
-                }
-            };
-        }
+        // Opt-in persisted cookie jar shared by the curl and isahc transports
+        let mut cookie_jar = self.load_cookie_jar(&creds.base_url).await;
+        let cookie_header = cookie_jar.as_ref().and_then(|jar| jar.cookie_header());
 
-        // x-api-key flow (existing implementation)
         // Check if curl runner is available for detailed timing measurements
         #[cfg(feature = "timings-curl")]
         if let Some(ref curl_runner) = self.curl_runner {
-            let endpoint = build_messages_endpoint(&creds.base_url);
-
-            // Minimal Claude API payload for probing
-            let payload = serde_json::json!({
-                "model": "claude-3-5-haiku-20241022",
-                "max_tokens": 1,
-                "messages": [
-                    {"role": "user", "content": "Hi"}
-                ]
-            });
-
-            let body = serde_json::to_vec(&payload).map_err(|e| {
-                NetworkError::HttpError(format!("Payload serialization failed: {}", e))
-            })?;
+            let endpoint = self.probe_request.endpoint(&creds.base_url);
 
-            let headers = vec![
+            let mut headers = vec![
                 ("Content-Type", "application/json".to_string()),
                 ("x-api-key", creds.auth_token.clone()),
                 (
@@ -1008,55 +2044,47 @@ impl HttpMonitor {
                 ("Accept", "application/json".to_string()),
                 ("Accept-Encoding", "gzip, deflate, br".to_string()),
             ];
+            if let Some(ref cookie) = cookie_header {
+                headers.push(("Cookie", cookie.clone()));
+            }
 
             // Try curl first, fallback to isahc on failure for resiliency
             match curl_runner
-                .run(&endpoint, &headers, &body, timeout_ms)
+                .run_pinned(&endpoint, &headers, &body, timeout_ms, dns_pin)
                 .await
             {
                 Ok(phase_timings) => {
+                    self.persist_cookies(
+                        cookie_jar.as_mut(),
+                        &phase_timings.set_cookies.join("\n"),
+                    )
+                    .await;
                     let duration = Duration::from_millis(phase_timings.ttfb_ms as u64);
 
-                    // Load current state to get P80 threshold for network performance check
+                    // Load current state to get the healthy cutoff for network performance check
                     let temp_state = self.load_state_internal().await.unwrap_or_default();
-                    let p80 = self.calculate_p80(&temp_state.network.rolling_totals);
+                    let healthy_cutoff = stats::percentile(
+                        &temp_state.network.rolling_totals,
+                        self.window_policy.healthy_percentile / 100.0,
+                    );
 
                     // Check both HTTP errors AND network performance degradation
                     let is_degraded_or_error = phase_timings.status >= 400
                         || phase_timings.status == 0
-                        || phase_timings.ttfb_ms > p80;
-
-                    let breakdown = if is_degraded_or_error {
-                        format!(
-                            "DNS:{}ms|TCP:{}ms|TLS:{}ms|ServerTTFB:{}ms/TotalTTFB:{}ms|Total:{}ms",
-                            phase_timings.dns_ms,
-                            phase_timings.tcp_ms,
-                            phase_timings.tls_ms,
-                            phase_timings.ttfb_ms,
-                            phase_timings.total_ttfb_ms,
-                            phase_timings.total_ms
-                        )
-                    } else {
-                        format!(
-                            "DNS:{}ms|TCP:{}ms|TLS:{}ms|TTFB:{}ms|Total:{}ms",
-                            phase_timings.dns_ms,
-                            phase_timings.tcp_ms,
-                            phase_timings.tls_ms,
-                            phase_timings.ttfb_ms,
-                            phase_timings.total_ms
-                        )
-                    };
+                        || phase_timings.ttfb_ms > healthy_cutoff;
+
+                    let breakdown = phase_timings.breakdown(is_degraded_or_error);
 
-                    // Note: curl branch doesn't capture response headers or HTTP version in current implementation
+                    // Note: curl branch doesn't capture the HTTP version in current implementation
                     // Setting http_version=None to avoid misleading diagnostics about version negotiation
-                    let empty_headers = std::collections::HashMap::new();
                     let http_version = None; // Unknown version - curl implementation doesn't capture this
                     return Ok((
                         phase_timings.status,
                         duration,
                         breakdown,
-                        empty_headers,
+                        phase_timings.headers.clone(),
                         http_version,
+                        Some(phase_timings.connection.clone()),
                     ));
                 }
                 Err(curl_error) => {
@@ -1074,19 +2102,7 @@ impl HttpMonitor {
         }
 
         // Fallback to isahc-based probe with heuristic timing breakdown
-        let endpoint = build_messages_endpoint(&creds.base_url);
-
-        // Minimal Claude API payload for probing
-        let payload = serde_json::json!({
-            "model": "claude-3-5-haiku-20241022",
-            "max_tokens": 1,
-            "messages": [
-                {"role": "user", "content": "Hi"}
-            ]
-        });
-
-        let body = serde_json::to_vec(&payload)
-            .map_err(|e| NetworkError::HttpError(format!("Payload serialization failed: {}", e)))?;
+        let endpoint = self.probe_request.endpoint(&creds.base_url);
 
         let mut headers = std::collections::HashMap::new();
         headers.insert("Content-Type".to_string(), "application/json".to_string());
@@ -1102,19 +2118,31 @@ impl HttpMonitor {
             "Accept-Encoding".to_string(),
             "gzip, deflate, br".to_string(),
         );
+        if let Some(cookie) = cookie_header {
+            headers.insert("Cookie".to_string(), cookie);
+        }
 
         let (status_code, duration, breakdown, response_headers, http_version) = self
             .http_client
-            .execute_request(endpoint, headers, body, timeout_ms)
+            .execute_request_pinned(endpoint, headers, body, timeout_ms, dns_pin.cloned())
             .await
             .map_err(NetworkError::HttpError)?;
 
+        if let Some(set_cookie) = response_headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("set-cookie"))
+            .map(|(_, value)| value.clone())
+        {
+            self.persist_cookies(cookie_jar.as_mut(), &set_cookie).await;
+        }
+
         Ok((
             status_code,
             duration,
             breakdown,
             response_headers,
             http_version,
+            None,
         ))
     }
 
@@ -1166,15 +2194,82 @@ impl HttpMonitor {
         }
     }
 
+    /// Proxy health for `creds`, run alongside the main probe
+    ///
+    /// Returns `None` when the check is skipped (OAuth mode, the `minimal` profile or
+    /// `proxy_health` switched off), fails, or exceeds `PROXY_HEALTH_TIMEOUT_MS` overall.
+    async fn check_proxy_health(&self, creds: &ApiCredentials) -> Option<ProxyHealthOutcome> {
+        // Skip proxy health check in OAuth mode per development plan
+        let probe_only = self.probe_profile.payload_strategy() == PayloadStrategy::ProbeOnly;
+        if creds.source == CredentialSource::OAuth || !self.proxy_health || probe_only {
+            return None;
+        }
+
+        // Enhanced mode: root-based URLs first, safe same-host redirect following
+        let proxy_health_options = ProxyHealthOptions::enhanced();
+
+        // Per-request timeouts bound each attempt; this bounds the whole fallback chain
+        match runtime::timeout(
+            Duration::from_millis(PROXY_HEALTH_TIMEOUT_MS),
+            assess_proxy_health(&creds.base_url, &proxy_health_options, &*self.health_client),
+        )
+        .await
+        {
+            Ok(Ok(outcome)) => Some(outcome),
+            // Health check errors: no proxy detected or internal error
+            Ok(Err(_)) => None,
+            Err(_) => {
+                get_debug_logger()
+                    .debug(
+                        "HttpMonitor",
+                        &format!(
+                            "Proxy health check timed out after {}ms",
+                            PROXY_HEALTH_TIMEOUT_MS
+                        ),
+                    )
+                    .await;
+                None
+            }
+        }
+    }
+
     /// Process probe results and update persistent state
+    ///
+    /// `proxy_health` is the outcome of `check_proxy_health`, run concurrently
+    /// with the probe; `None` clears the proxy health fields.
     async fn process_probe_results(
         &mut self,
         mode: ProbeMode,
         creds: ApiCredentials,
         metrics: ProbeMetrics,
+        proxy_health: Option<ProxyHealthOutcome>,
         last_jsonl_error_event: Option<JsonlError>,
+        dns: Option<DnsLookup>,
     ) -> Result<ProbeOutcome, NetworkError> {
         let mut state = self.load_state_internal().await.unwrap_or_default();
+        let now_secs = self.now_unix_secs();
+        let previous_status = state.status.clone();
+
+        // Time-based eviction: after a long outage or idle stretch the window may
+        // still hold samples from hours ago, so drop them before any percentile use
+        if let Some(horizon) = self.sample_horizon_secs {
+            let cutoff = now_secs.saturating_sub(horizon as i64);
+            let evicted = state.network.evict_samples_before(cutoff);
+            if evicted > 0 {
+                self.refresh_percentiles(&mut state);
+                get_debug_logger()
+                    .debug(
+                        "HttpMonitor",
+                        &format!(
+                            "sample_horizon: evicted={} kept={} horizon={}s",
+                            evicted,
+                            state.network.rolling_totals.len(),
+                            horizon
+                        ),
+                    )
+                    .await;
+            }
+        }
 
         // Connection reuse calculation (only for heuristic path)
         let _p95 = state.network.p95_latency_ms;
@@ -1232,53 +2327,125 @@ impl HttpMonitor {
                 false
             };
 
-            state.network.connection_reused = Some(dns_reused);
+            // libcurl's connection count is exact; the DNS heuristic covers the isahc fallback
+            state.network.connection_reused = Some(
+                metrics
+                    .connection
+                    .as_ref()
+                    .map_or(dns_reused, ConnectionInfo::is_reused),
+            );
             state.network.breakdown_source = Some(breakdown_source.to_string());
         }
+        state.network.dns_cache = dns.as_ref().map(|dns| dns.status);
+        state.network.dns_lookup_ms = dns.as_ref().and_then(|dns| dns.lookup_ms);
+        state.network.connection = metrics.connection.clone();
+        state.network.rate_limit = metrics.rate_limit.clone();
         state.timestamp = self.clock.local_timestamp();
 
         // Update API config
         state.api_config = Some(ApiConfig {
-            endpoint: build_messages_endpoint(&creds.base_url),
+            endpoint: self.probe_endpoint(&creds),
             source: creds.source.to_string(),
         });
         state.monitoring_enabled = true;
 
-        // Proxy health check using new proxy_health module
-        // Skip proxy health check in OAuth mode per development plan
-        if creds.source == CredentialSource::OAuth {
-            // OAuth mode: skip proxy health check and set fields to None
-            state.network.set_proxy_health(None, None);
+        // Use centralized mapping function to set both legacy and new fields
+        match proxy_health {
+            Some(outcome) => state
+                .network
+                .set_proxy_health(outcome.level, outcome.detail),
+            None => state.network.set_proxy_health(None, None),
+        }
+
+        // Certificate of a custom gateway (curl transport only)
+        let certificate_warning = metrics
+            .connection
+            .as_ref()
+            .and_then(|connection| connection.certificate.as_ref())
+            .filter(|_| !is_official_base_url(&creds.base_url))
+            .and_then(|chain| chain.warning(now_secs, self.cert_warn_days));
+        if let Some(ref warning) = certificate_warning {
+            get_debug_logger()
+                .warn("HttpMonitor", &format!("{}: {}", creds.base_url, warning))
+                .await;
+        }
+        if let Some(detail) = state.network.proxy_health_detail.as_mut() {
+            detail.certificate_warning = certificate_warning;
+        }
+
+        // Composite health: messages probe + auxiliary GET /v1/models
+        let aux_checks = match self.probe_profile.payload_strategy() {
+            PayloadStrategy::Configured => self.aux_checks,
+            PayloadStrategy::Full => true,
+            PayloadStrategy::ProbeOnly => false,
+        };
+        if aux_checks {
+            let messages = EndpointHealth::new(
+                metrics.last_http_status,
+                metrics.latency_ms,
+                &state.timestamp,
+            );
+            let models = self
+                .check_models_endpoint(&creds.base_url, &state.timestamp)
+                .await;
+            state.endpoints = [
+                ("messages".to_string(), messages),
+                ("models".to_string(), models),
+            ]
+            .into_iter()
+            .collect();
+            state.health_score = composite_health_score(&state.endpoints);
         } else {
-            // Non-OAuth mode: perform proxy health check as usual
-            let proxy_health_options = ProxyHealthOptions {
-                use_root_urls: true, // Enhanced mode: try root-based URLs first
-                try_fallback: true,
-                follow_redirect_once: true, // Enable safe same-host redirect following
-                timeout_ms: 1500,
-            };
+            state.endpoints.clear();
+            state.health_score = None;
+        }
 
-            let proxy_health_outcome =
-                assess_proxy_health(&creds.base_url, &proxy_health_options, &*self.health_client)
+        // IPv4 vs IPv6: the same unauthenticated check once over each family
+        if self.ip_family != IpFamilyMode::Compare {
+            state.ip_families = None;
+        } else if self.probe_profile.payload_strategy() != PayloadStrategy::ProbeOnly {
+            let mut comparison = state.ip_families.take().unwrap_or_default();
+            for family in [IpFamily::V4, IpFamily::V6] {
+                let result = self.check_models_over(&creds.base_url, family).await;
+                comparison.record(family, result);
+            }
+            comparison.checked_at = state.timestamp.clone();
+            if let Some(verdict) = comparison.verdict() {
+                get_debug_logger()
+                    .warn("HttpMonitor", &format!("{}: {}", creds.base_url, verdict))
                     .await;
-
-            // Use centralized mapping function to set both legacy and new fields
-            match proxy_health_outcome {
-                Ok(outcome) => {
-                    state
-                        .network
-                        .set_proxy_health(outcome.level, outcome.detail);
-                }
-                Err(_) => {
-                    // Health check errors: no proxy detected or internal error
-                    state.network.set_proxy_health(None, None);
-                }
             }
+            state.ip_families = Some(comparison);
+        }
+
+        // Comparison results of endpoints no longer configured are dropped
+        if self.compare_endpoints.is_empty() {
+            state.comparison = None;
+        }
+
+        // Grow-only probe ledger (synced between machines)
+        state.monitoring_state.ledger.probes += 1;
+        if metrics.last_http_status != 200 {
+            state.monitoring_state.ledger.errors += 1;
+            state.monitoring_state.consecutive_failures += 1;
+        } else {
+            state.monitoring_state.consecutive_failures = 0;
         }
 
         // Mode-specific processing
         let (final_status, p95_updated, rolling_len) = match mode {
             ProbeMode::Red => {
+                // RED watchdog bookkeeping: a different transcript error starts a new streak
+                if let Some(ref error_event) = last_jsonl_error_event {
+                    if state.monitoring_state.red_error_at.as_deref()
+                        != Some(error_event.timestamp.as_str())
+                    {
+                        state.monitoring_state.red_since = Some(state.timestamp.clone());
+                        state.monitoring_state.red_error_at = Some(error_event.timestamp.clone());
+                        state.monitoring_state.red_watchdog_triggered = false;
+                    }
+                }
+
                 // RED mode: Set status=error, update error event, don't touch rolling stats
                 if let Some(mut error_event) = last_jsonl_error_event {
                     // Convert UTC timestamp to local time for consistent persistence
@@ -1298,47 +2465,49 @@ impl HttpMonitor {
             }
             ProbeMode::Green | ProbeMode::Cold => {
                 // GREEN/COLD: Update rolling stats ONLY if HTTP 200 AND no bot challenge
-                let is_bot_blocked =
-                    metrics.error_type.as_ref() == Some(&"bot_challenge".to_string());
-
-                let (status, p95, rolling_len) =
-                    if metrics.last_http_status == 200 && !is_bot_blocked {
-                        // Safe to add to rolling statistics - HTTP 200 with no bot challenge
-                        state.network.rolling_totals.push(metrics.latency_ms);
-                        if state.network.rolling_totals.len() > 12 {
-                            state.network.rolling_totals.remove(0);
-                        }
-
-                        let new_p95 = self.calculate_p95(&state.network.rolling_totals);
-                        state.network.p95_latency_ms = new_p95;
+                let is_bot_blocked = metrics.error_type.as_deref() == Some("bot_challenge");
 
-                        // Determine status based on P80/P95 thresholds
-                        let p80 = self.calculate_p80(&state.network.rolling_totals);
-                        let status = if metrics.latency_ms <= p80 {
-                            NetworkStatus::Healthy
-                        } else if metrics.latency_ms <= new_p95 {
-                            NetworkStatus::Degraded
-                        } else {
-                            NetworkStatus::Error
-                        };
-
-                        (status, new_p95, state.network.rolling_totals.len())
-                    } else if metrics.last_http_status == 429 && !is_bot_blocked {
-                        // Rate limited but not bot blocked - degraded status
-                        (
-                            NetworkStatus::Degraded,
-                            state.network.p95_latency_ms,
-                            state.network.rolling_totals.len(),
-                        )
+                let (status, p95, rolling_len) = if metrics.last_http_status == 200
+                    && !is_bot_blocked
+                {
+                    // Safe to add to rolling statistics - HTTP 200 with no bot challenge
+                    state.network.push_sample_within(
+                        metrics.latency_ms,
+                        now_secs,
+                        self.window_policy.samples,
+                    );
+
+                    // Determine status based on the healthy/degraded cutoffs (P80/P95 by default)
+                    let (healthy_cutoff, degraded_cutoff) = self.refresh_percentiles(&mut state);
+                    let status = if metrics.latency_ms <= healthy_cutoff {
+                        NetworkStatus::Healthy
+                    } else if metrics.latency_ms <= degraded_cutoff {
+                        NetworkStatus::Degraded
                     } else {
-                        // Bot blocked or error - don't contaminate stats
-                        (
-                            NetworkStatus::Error,
-                            state.network.p95_latency_ms,
-                            state.network.rolling_totals.len(),
-                        )
+                        NetworkStatus::Error
                     };
 
+                    (
+                        status,
+                        state.network.p95_latency_ms,
+                        state.network.rolling_totals.len(),
+                    )
+                } else if metrics.last_http_status == 429 && !is_bot_blocked {
+                    // Rate limited but not bot blocked - degraded status
+                    (
+                        NetworkStatus::Degraded,
+                        state.network.p95_latency_ms,
+                        state.network.rolling_totals.len(),
+                    )
+                } else {
+                    // Bot blocked or error - don't contaminate stats
+                    (
+                        NetworkStatus::Error,
+                        state.network.p95_latency_ms,
+                        state.network.rolling_totals.len(),
+                    )
+                };
+
                 state.status = status.clone();
                 state.monitoring_state.state = status.clone();
 
@@ -1355,86 +2524,530 @@ impl HttpMonitor {
             }
         };
 
+        // Last error details: classified error plus the sanitized header snapshot
+        if let Some(ref error_type) = metrics.error_type {
+            state.last_probe_error = Some(ProbeErrorDetails {
+                timestamp: state.timestamp.clone(),
+                http_status: metrics.last_http_status,
+                error_type: error_type.clone(),
+                headers: metrics.error_headers.clone(),
+            });
+        }
+
+        // Penalty backoff: 429 / bot challenge escalates, a clean 200 clears
+        match Self::penalty_reason(&metrics) {
+            Some(reason) => {
+                state
+                    .monitoring_state
+                    .penalty
+                    .escalate(reason, &state.timestamp);
+                // The server knows best when to try again
+                if let Some(retry_in) = metrics
+                    .rate_limit
+                    .as_ref()
+                    .and_then(|rate_limit| rate_limit.retry_in_secs(now_secs))
+                {
+                    state
+                        .monitoring_state
+                        .penalty
+                        .hold_for(retry_in, &state.timestamp);
+                }
+                get_debug_logger()
+                    .debug(
+                        "HttpMonitor",
+                        &format!(
+                            "penalty_backoff: reason={} level={} until={:?}",
+                            reason,
+                            state.monitoring_state.penalty.level,
+                            state.monitoring_state.penalty.until
+                        ),
+                    )
+                    .await;
+            }
+            None if metrics.last_http_status == 200 => state.monitoring_state.penalty.clear(),
+            None => {}
+        }
+
+        // User-defined expression has the final say over the built-in classification
+        let final_status = match self.status_expression {
+            Some(ref expression) => {
+                let status =
+                    expression.evaluate(&StatusMetrics::from_snapshot(&state), &final_status);
+                state.status = status.clone();
+                state.monitoring_state.state = status.clone();
+                status
+            }
+            None => final_status,
+        };
+
+        // Hysteresis: single outliers do not flip the shown status; RED is a confirmed error
+        let final_status = if mode == ProbeMode::Red {
+            state.monitoring_state.clear_pending_status();
+            final_status
+        } else {
+            let shown = state.monitoring_state.debounce_status(
+                &previous_status,
+                final_status.clone(),
+                &self.hysteresis,
+            );
+            if shown != final_status {
+                get_debug_logger()
+                    .debug(
+                        "HttpMonitor",
+                        &format!(
+                            "hysteresis: holding {:?}, classified {:?} ({} in a row)",
+                            shown, final_status, state.monitoring_state.pending_count
+                        ),
+                    )
+                    .await;
+            }
+            state.status = shown.clone();
+            state.monitoring_state.state = shown.clone();
+            shown
+        };
+
+        if state.status != previous_status || state.monitoring_state.status_since.is_none() {
+            state.monitoring_state.status_since = Some(state.timestamp.clone());
+        }
+        state.uptime.record(&state.status, now_secs);
+        state.error_budget = self.error_budget.map(|policy| {
+            policy.evaluate_history(&self.probe_history().load(), &state.status, now_secs)
+        });
+
         // Write state atomically
         self.write_state_atomic(&state).await?;
+        self.run_status_hooks(&previous_status, &state, now_secs)
+            .await;
+
+        ProbeOutcome::builder(mode, final_status)
+            .with_metrics(metrics)
+            .with_p95(p95_updated, rolling_len)
+            .with_api_config(state.api_config.unwrap_or_default())
+            .with_state_written(true)
+            .with_timestamp(state.timestamp)
+            .build()
+    }
 
-        // Build outcome
-        let outcome = ProbeOutcome {
-            status: final_status,
-            metrics,
-            p95_latency_ms: p95_updated,
-            rolling_len,
-            api_config: state.api_config.unwrap_or_default(),
-            mode,
-            state_written: true,
-            timestamp_local: state.timestamp,
+    /// Hand the written state to every status hook; hook failures are logged only
+    async fn run_status_hooks(
+        &self,
+        previous: &NetworkStatus,
+        state: &MonitoringSnapshot,
+        now_secs: i64,
+    ) {
+        let status_secs = state
+            .monitoring_state
+            .status_since
+            .as_deref()
+            .and_then(|since| chrono::DateTime::parse_from_rfc3339(since).ok())
+            .map(|since| now_secs.saturating_sub(since.timestamp()).max(0) as u64)
+            .unwrap_or(0);
+        let change = StatusChange {
+            previous,
+            state,
+            status_secs,
         };
 
-        Ok(outcome)
+        for hook in &self.status_hooks {
+            if let Err(e) = hook.on_probe(&change).await {
+                get_debug_logger()
+                    .debug(
+                        "HttpMonitor",
+                        &format!("Status hook {} failed: {}", hook.name(), e),
+                    )
+                    .await;
+            }
+        }
     }
 
-    /// Calculate 95th percentile from rolling samples using nearest-rank method
-    fn calculate_p95(&self, samples: &[u32]) -> u32 {
-        if samples.is_empty() {
-            return 0;
+    /// First-party-shaped probe for OAuth credentials (see `oauth_masquerade`)
+    #[cfg(feature = "oauth-probe")]
+    async fn execute_oauth_probe(
+        &self,
+        creds: &ApiCredentials,
+    ) -> Result<
+        (
+            u16,
+            Duration,
+            String,
+            std::collections::HashMap<String, String>,
+            Option<String>,
+            Option<ConnectionInfo>,
+        ),
+        NetworkError,
+    > {
+        // Check if token is expired (hard gate)
+        if let Some(expires_at) = creds.expires_at {
+            let now_ms = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as i64;
+
+            if expires_at <= now_ms {
+                // Token expired - return error immediately without probe
+                let debug_logger = get_debug_logger();
+                let _ = debug_logger
+                    .debug(
+                        "HttpMonitor",
+                        &format!(
+                            "OAuth token expired: now={} expires_at={}",
+                            now_ms, expires_at
+                        ),
+                    )
+                    .await;
+
+                return Err(NetworkError::SkipProbe("OAuth token expired".to_string()));
+            }
         }
 
-        let mut sorted = samples.to_vec();
-        sorted.sort_unstable();
-        let n = sorted.len();
+        // OAuth masquerade path
+        let oauth_opts = OauthMasqueradeOptions {
+            base_url: creds.base_url.clone(),
+            access_token: creds.auth_token.clone(),
+            expires_at: creds.expires_at,
+            stream: false, // No streaming for probe requests
+        };
+
+        #[cfg(feature = "timings-curl")]
+        let result = oauth_run_probe(
+            &oauth_opts,
+            self.http_client.as_ref(),
+            self.curl_runner.as_ref().map(|r| r.as_ref()),
+        )
+        .await;
+
+        #[cfg(not(feature = "timings-curl"))]
+        let result = oauth_run_probe(&oauth_opts, self.http_client.as_ref()).await;
+
+        match result {
+            Ok(result) => {
+                let duration = Duration::from_millis(result.duration_ms as u64);
+                Ok((
+                    result.status,
+                    duration,
+                    result.breakdown,
+                    result.response_headers,
+                    result.http_version,
+                    result.connection,
+                ))
+            }
+            Err(e) => {
+                let debug_logger = get_debug_logger();
+                let _ = debug_logger
+                    .error(
+                        "HttpMonitor",
+                        &format!("OAuth masquerade probe failed: {}", e),
+                    )
+                    .await;
 
-        // Use nearest-rank (inclusive) method: ceil(p * n) - 1
-        let rank = ((0.95 * n as f64).ceil() as usize).max(1); // 1-based rank
-        sorted[rank - 1] // Convert to 0-based index
+                // For now, return the error rather than falling back to x-api-key
+                // (OAuth-only environments should not have x-api-key as fallback)
+                Err(e)
+            }
+        }
     }
 
-    /// Calculate 80th percentile from rolling samples using nearest-rank method
-    fn calculate_p80(&self, samples: &[u32]) -> u32 {
-        if samples.is_empty() {
-            return 0;
+    /// Built without `oauth-probe`: OAuth credentials are never probed
+    #[cfg(not(feature = "oauth-probe"))]
+    async fn execute_oauth_probe(
+        &self,
+        _creds: &ApiCredentials,
+    ) -> Result<
+        (
+            u16,
+            Duration,
+            String,
+            std::collections::HashMap<String, String>,
+            Option<String>,
+            Option<ConnectionInfo>,
+        ),
+        NetworkError,
+    > {
+        Err(NetworkError::SkipProbe(
+            "OAuth probe not built (oauth-probe feature)".to_string(),
+        ))
+    }
+
+    /// Unauthenticated GET /v1/models: a 401 still proves the API is answering,
+    /// and no credentials or tokens are spent on the auxiliary check
+    async fn check_models_endpoint(&self, base_url: &str, checked_at: &str) -> EndpointHealth {
+        let url = build_models_endpoint(base_url);
+        match self.health_client.get_health(url, 1500).await {
+            Ok(response) => EndpointHealth::new(
+                response.status_code,
+                response.duration.as_millis() as u32,
+                checked_at,
+            ),
+            Err(e) => {
+                get_debug_logger()
+                    .debug(
+                        "HttpMonitor",
+                        &format!("Models endpoint check failed: {}", e),
+                    )
+                    .await;
+                EndpointHealth::new(0, 0, checked_at)
+            }
+        }
+    }
+
+    /// GET /v1/models over one address family: its latency, or why it could not connect
+    async fn check_models_over(&self, base_url: &str, family: IpFamily) -> Result<u32, String> {
+        let url = build_models_endpoint(base_url);
+        self.health_client
+            .get_health_over(url, family, 1500)
+            .await
+            .map(|response| response.duration.as_millis() as u32)
+    }
+
+    /// Load the persisted cookie jar for the probe host (None when persistence is off)
+    async fn load_cookie_jar(&self, base_url: &str) -> Option<PersistentCookieJar> {
+        let path = self.cookie_jar_path.as_ref()?;
+        let url = url::Url::parse(base_url).ok()?;
+        let host = url.host_str()?;
+        Some(PersistentCookieJar::load(path, host, unix_now_secs()).await)
+    }
+
+    /// Store `Set-Cookie` values (newline-joined) into the jar and persist it
+    async fn persist_cookies(&self, jar: Option<&mut PersistentCookieJar>, set_cookie: &str) {
+        let Some(jar) = jar else {
+            return;
+        };
+
+        jar.store_set_cookie_headers(set_cookie, unix_now_secs());
+        if let Err(e) = jar.save().await {
+            get_debug_logger()
+                .debug("HttpMonitor", &format!("Cookie jar save failed: {}", e))
+                .await;
+        }
+    }
+
+    /// DNS cache decision for this probe; None when `network.dns_cache` is off
+    ///
+    /// OAuth probes and IP-literal hosts are left alone. RED probes bypass the
+    /// cache and drop the host's entry; otherwise a fresh entry is a hit and
+    /// anything else is looked up (timed, within `budget`) and cached.
+    async fn lookup_probe_host(
+        &self,
+        creds: &ApiCredentials,
+        mode: ProbeMode,
+        budget: Duration,
+    ) -> Option<DnsLookup> {
+        let path = self.dns_cache_path.as_ref()?;
+        if creds.source == CredentialSource::OAuth {
+            return None;
+        }
+        let url = url::Url::parse(&creds.base_url).ok()?;
+        let Some(url::Host::Domain(host)) = url.host() else {
+            return None;
+        };
+        let host = host.to_ascii_lowercase();
+        let port = url.port_or_known_default()?;
+        let now = unix_now_secs();
+        let mut cache = DnsCache::load(path, now).await;
+        let debug_logger = get_debug_logger();
+
+        if mode == ProbeMode::Red {
+            cache.remove(&host);
+            self.save_dns_cache(&mut cache).await;
+            debug_logger
+                .debug("HttpMonitor", &format!("dns_cache: bypass host={}", host))
+                .await;
+            return Some(DnsLookup {
+                status: DnsCacheStatus::Bypass,
+                pin: None,
+                lookup_ms: None,
+            });
+        }
+
+        if let Some(addr) = cache
+            .get(&host, now)
+            .and_then(|addrs| self.pin_address(addrs))
+        {
+            debug_logger
+                .debug(
+                    "HttpMonitor",
+                    &format!("dns_cache: hit host={} addr={}", host, addr),
+                )
+                .await;
+            return Some(DnsLookup {
+                status: DnsCacheStatus::Hit,
+                pin: Some(DnsPin { host, port, addr }),
+                lookup_ms: None,
+            });
         }
 
-        let mut sorted = samples.to_vec();
-        sorted.sort_unstable();
-        let n = sorted.len();
+        let started = Instant::now();
+        let resolved = crate::core::network::dns_cache::resolve(&host, budget).await;
+        let lookup_ms = started.elapsed().as_millis() as u32;
 
-        // Use nearest-rank (inclusive) method: ceil(p * n) - 1
-        let rank = ((0.80 * n as f64).ceil() as usize).max(1); // 1-based rank
-        sorted[rank - 1] // Convert to 0-based index
+        // A failed lookup leaves resolution to the client, which reports it as dns_error
+        let pin = match resolved {
+            Ok(resolved) => {
+                let addr = self.pin_address(&resolved.addrs);
+                debug_logger
+                    .debug(
+                        "HttpMonitor",
+                        &format!(
+                            "dns_cache: miss host={} addrs={} ttl={}s lookup={}ms",
+                            host,
+                            resolved.addrs.len(),
+                            resolved.ttl_secs,
+                            lookup_ms
+                        ),
+                    )
+                    .await;
+                cache.insert(&host, resolved.addrs, resolved.ttl_secs, now);
+                self.save_dns_cache(&mut cache).await;
+                addr.map(|addr| DnsPin { host, port, addr })
+            }
+            Err(e) => {
+                debug_logger
+                    .debug("HttpMonitor", &format!("dns_cache: lookup failed: {}", e))
+                    .await;
+                None
+            }
+        };
+        Some(DnsLookup {
+            status: DnsCacheStatus::Miss,
+            pin,
+            lookup_ms: Some(lookup_ms),
+        })
+    }
+
+    /// First address of the family `network.ip_family` forces, or the first address
+    fn pin_address(&self, addrs: &[std::net::IpAddr]) -> Option<std::net::IpAddr> {
+        let forced = ip_family::forced_family(self.ip_family);
+        addrs
+            .iter()
+            .copied()
+            .find(|addr| forced.is_none_or(|family| IpFamily::of(*addr) == family))
+    }
+
+    /// Drop the probe host from the DNS cache
+    async fn forget_probe_host(&self, creds: &ApiCredentials) {
+        let Some(path) = self.dns_cache_path.as_ref() else {
+            return;
+        };
+        let Some(host) = url::Url::parse(&creds.base_url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+        else {
+            return;
+        };
+        let mut cache = DnsCache::load(path, unix_now_secs()).await;
+        cache.remove(&host);
+        self.save_dns_cache(&mut cache).await;
+    }
+
+    async fn save_dns_cache(&self, cache: &mut DnsCache) {
+        if let Err(e) = cache.save().await {
+            get_debug_logger()
+                .debug("HttpMonitor", &format!("DNS cache save failed: {}", e))
+                .await;
+        }
+    }
+
+    /// Classify a probe result that should trigger penalty backoff
+    ///
+    /// Returns "bot_challenge" when the probe hit a bot challenge, "rate_limit" for a plain 429.
+    fn penalty_reason(metrics: &ProbeMetrics) -> Option<&'static str> {
+        if metrics.error_type.as_deref() == Some("bot_challenge") {
+            Some("bot_challenge")
+        } else if metrics.last_http_status == 429 {
+            Some("rate_limit")
+        } else {
+            None
+        }
+    }
+
+    /// Percentiles (`0.0..=1.0`) over the rolling window, including samples
+    /// shared by other machines (state sync)
+    fn baseline_percentiles(&self, state: &MonitoringSnapshot, ps: &[f64]) -> Vec<u32> {
+        let mut baseline = state.network.rolling_totals.to_vec();
+        if let Some(ref shared) = state.shared_baseline {
+            baseline.extend_from_slice(&shared.totals);
+        }
+        baseline.sort_unstable();
+        ps.iter()
+            .map(|&p| stats::percentile_sorted(&baseline, p))
+            .collect()
+    }
+
+    /// Recompute P95 and the `[network.window]` percentiles after the window changed
+    ///
+    /// Returns the healthy and degraded cutoffs (P80 and P95 by default).
+    fn refresh_percentiles(&self, state: &mut MonitoringSnapshot) -> (u32, u32) {
+        let policy = &self.window_policy;
+        let ps: Vec<f64> = [95.0, policy.healthy_percentile, policy.degraded_percentile]
+            .iter()
+            .chain(&policy.percentiles)
+            .map(|percent| percent / 100.0)
+            .collect();
+        let values = self.baseline_percentiles(state, &ps);
+
+        state.network.p95_latency_ms = values[0];
+        state.network.window_percentiles = policy
+            .percentiles
+            .iter()
+            .zip(&values[3..])
+            .map(|(&percent, &value)| (percentile_key(percent), value))
+            .collect();
+        (values[1], values[2])
+    }
+
+    /// Current Unix time from the injected clock, so tests can pin sample ages
+    fn now_unix_secs(&self) -> i64 {
+        chrono::DateTime::parse_from_rfc3339(&self.clock.local_timestamp())
+            .map(|t| t.timestamp())
+            .unwrap_or_else(|_| unix_now_secs())
+    }
+
+    /// State used before the first write (or after a corrupt file was quarantined)
+    fn empty_state(&self) -> MonitoringSnapshot {
+        MonitoringSnapshot {
+            status: NetworkStatus::Unknown,
+            monitoring_enabled: false,
+            api_config: None,
+            network: NetworkMetrics::default(),
+            monitoring_state: MonitoringState::default(),
+            last_jsonl_error_event: None,
+            last_probe_error: None,
+            endpoints: Default::default(),
+            health_score: None,
+            shared_baseline: None,
+            comparison: None,
+            uptime: Default::default(),
+            error_budget: None,
+            ip_families: None,
+            timestamp: self.clock.local_timestamp(),
+        }
     }
 
     /// Load monitoring state from file (internal)
     async fn load_state_internal(&self) -> Result<MonitoringSnapshot, NetworkError> {
         if !self.state_path.exists() {
-            return Ok(MonitoringSnapshot {
-                status: NetworkStatus::Unknown,
-                monitoring_enabled: false,
-                api_config: None,
-                network: NetworkMetrics::default(),
-                monitoring_state: MonitoringState::default(),
-                last_jsonl_error_event: None,
-                timestamp: self.clock.local_timestamp(),
-            });
+            return Ok(self.empty_state());
         }
 
-        let content = tokio::fs::read_to_string(&self.state_path)
-            .await
-            .map_err(|e| {
-                NetworkError::StateFileError(format!("Failed to read state file: {}", e))
-            })?;
-
-        let state: MonitoringSnapshot = serde_json::from_str(&content).map_err(|e| {
-            NetworkError::StateFileError(format!("Failed to parse state file: {}", e))
+        let content = runtime::fs::read(&self.state_path).await.map_err(|e| {
+            NetworkError::StateFileError(format!("Failed to read state file: {}", e))
         })?;
 
-        Ok(state)
+        // A corrupt state file is quarantined rather than failing every render
+        match recovery::parse_json(&self.state_path, &content) {
+            Some(state) => Ok(state),
+            None => Ok(self.empty_state()),
+        }
     }
 
     /// Write state atomically using temp file + rename
     async fn write_state_atomic(&self, state: &MonitoringSnapshot) -> Result<(), NetworkError> {
+        // Shutdown waits for the rename so the state file is never left half-written
+        let _write = ShutdownCoordinator::global().begin_write();
+
         // Ensure directory exists
         if let Some(parent) = self.state_path.parent() {
-            tokio::fs::create_dir_all(parent).await.map_err(|e| {
+            runtime::fs::create_dir_all(parent).await.map_err(|e| {
                 NetworkError::StateFileError(format!("Failed to create directory: {}", e))
             })?;
         }
@@ -1445,12 +3058,12 @@ impl HttpMonitor {
             NetworkError::StateFileError(format!("Failed to serialize state: {}", e))
         })?;
 
-        tokio::fs::write(&temp_path, content).await.map_err(|e| {
+        runtime::fs::write(&temp_path, content).await.map_err(|e| {
             NetworkError::StateFileError(format!("Failed to write temp file: {}", e))
         })?;
 
         // Atomic rename
-        tokio::fs::rename(&temp_path, &self.state_path)
+        runtime::fs::rename(&temp_path, &self.state_path)
             .await
             .map_err(|e| {
                 NetworkError::StateFileError(format!("Failed to rename temp file: {}", e))
@@ -0,0 +1,206 @@
+//! IPv4/IPv6 dual-stack probing (`network.ip_family`)
+//!
+//! By default probes connect over whichever family the resolver and libcurl's
+//! happy eyeballs pick; `ccstatus net status` shows which one served the last
+//! curl probe. `v4` or `v6` forces a family on probes and proxy health checks
+//! (and on DNS cache pins), for networks where one of them is broken.
+//!
+//! `compare` keeps the automatic choice for the probe itself and afterwards
+//! sends the unauthenticated GET /v1/models of the auxiliary checks once over
+//! each family. The last `FAMILY_WINDOW` results per family are kept in the
+//! monitoring state, and a family is flagged when it fails
+//! `FAILING_STREAK` checks in a row while the other connects, or when its
+//! median latency is at least `SLOWER_RATIO` times (and `SLOWER_MIN_MS` more
+//! than) the other's over `MIN_SAMPLES` checks or more. Happy eyeballs hides
+//! both cases from the probe, but they still cost a fallback on every new
+//! connection.
+
+use crate::config::IpFamilyMode;
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+
+/// Checks kept per family
+pub const FAMILY_WINDOW: usize = 10;
+/// Successful checks each family needs before latencies are compared
+pub const MIN_SAMPLES: usize = 5;
+/// Median latency ratio at which a family counts as consistently slower
+pub const SLOWER_RATIO: f64 = 1.5;
+/// Smallest median difference worth flagging, so 10ms vs 16ms is not
+pub const SLOWER_MIN_MS: u32 = 50;
+/// Failed checks in a row at which a family counts as failing
+pub const FAILING_STREAK: u32 = 3;
+
+/// One address family
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IpFamily {
+    V4,
+    V6,
+}
+
+impl IpFamily {
+    /// Family of an address; an IPv4-mapped IPv6 address counts as IPv4
+    pub fn of(addr: IpAddr) -> Self {
+        match addr {
+            IpAddr::V4(_) => Self::V4,
+            IpAddr::V6(v6) if v6.to_ipv4_mapped().is_some() => Self::V4,
+            IpAddr::V6(_) => Self::V6,
+        }
+    }
+
+    /// Family of an address as libcurl reports it; None when it does not parse
+    pub fn of_str(addr: &str) -> Option<Self> {
+        addr.trim_matches(|c| c == '[' || c == ']')
+            .parse::<IpAddr>()
+            .ok()
+            .map(Self::of)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::V4 => "IPv4",
+            Self::V6 => "IPv6",
+        }
+    }
+
+    fn other(&self) -> Self {
+        match self {
+            Self::V4 => Self::V6,
+            Self::V6 => Self::V4,
+        }
+    }
+}
+
+impl std::fmt::Display for IpFamily {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// Family `mode` forces, if any
+pub fn forced_family(mode: IpFamilyMode) -> Option<IpFamily> {
+    match mode {
+        IpFamilyMode::V4 => Some(IpFamily::V4),
+        IpFamilyMode::V6 => Some(IpFamily::V6),
+        IpFamilyMode::Auto | IpFamilyMode::Compare => None,
+    }
+}
+
+/// Recent checks over one family
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FamilyStats {
+    /// Latency of the last successful checks, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub samples_ms: Vec<u32>,
+    /// Checks in the window, successful or not
+    pub checks: u32,
+    pub consecutive_failures: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+impl FamilyStats {
+    /// Record one check: its latency, or why it could not connect
+    pub fn record(&mut self, result: Result<u32, String>) {
+        match result {
+            Ok(latency_ms) => {
+                self.samples_ms.push(latency_ms);
+                if self.samples_ms.len() > FAMILY_WINDOW {
+                    self.samples_ms.remove(0);
+                }
+                self.consecutive_failures = 0;
+                self.last_error = None;
+            }
+            Err(e) => {
+                self.consecutive_failures += 1;
+                self.last_error = Some(e);
+            }
+        }
+        self.checks = (self.checks + 1).min(FAMILY_WINDOW as u32);
+    }
+
+    /// Median of the kept latencies
+    pub fn median_ms(&self) -> Option<u32> {
+        if self.samples_ms.is_empty() {
+            return None;
+        }
+        let mut sorted = self.samples_ms.clone();
+        sorted.sort_unstable();
+        let mid = sorted.len() / 2;
+        Some(if sorted.len().is_multiple_of(2) {
+            (sorted[mid - 1] + sorted[mid]) / 2
+        } else {
+            sorted[mid]
+        })
+    }
+
+    fn is_failing(&self) -> bool {
+        self.consecutive_failures >= FAILING_STREAK
+    }
+}
+
+/// IPv4 and IPv6 checks of `compare` mode, kept in the monitoring state
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IpFamilyComparison {
+    pub v4: FamilyStats,
+    pub v6: FamilyStats,
+    /// Local timestamp of the last pair of checks
+    pub checked_at: String,
+}
+
+impl IpFamilyComparison {
+    pub fn stats(&self, family: IpFamily) -> &FamilyStats {
+        match family {
+            IpFamily::V4 => &self.v4,
+            IpFamily::V6 => &self.v6,
+        }
+    }
+
+    /// Record a check over `family`
+    pub fn record(&mut self, family: IpFamily, result: Result<u32, String>) {
+        match family {
+            IpFamily::V4 => self.v4.record(result),
+            IpFamily::V6 => self.v6.record(result),
+        }
+    }
+
+    /// Why one family should be avoided, if it is failing or consistently slower
+    pub fn verdict(&self) -> Option<String> {
+        for family in [IpFamily::V6, IpFamily::V4] {
+            let (stats, other) = (self.stats(family), self.stats(family.other()));
+            if stats.is_failing() && other.consecutive_failures == 0 && other.checks > 0 {
+                return Some(format!(
+                    "{} failing ({} checks in a row{}) while {} connects",
+                    family,
+                    stats.consecutive_failures,
+                    stats
+                        .last_error
+                        .as_deref()
+                        .map(|e| format!(": {}", e))
+                        .unwrap_or_default(),
+                    family.other()
+                ));
+            }
+        }
+
+        if self.v4.samples_ms.len() < MIN_SAMPLES || self.v6.samples_ms.len() < MIN_SAMPLES {
+            return None;
+        }
+        let (v4, v6) = (self.v4.median_ms()?, self.v6.median_ms()?);
+        let (slower, slow_ms, fast_ms) = if v6 > v4 {
+            (IpFamily::V6, v6, v4)
+        } else {
+            (IpFamily::V4, v4, v6)
+        };
+        let consistently_slower = slow_ms as f64 >= fast_ms as f64 * SLOWER_RATIO
+            && slow_ms.saturating_sub(fast_ms) >= SLOWER_MIN_MS;
+        consistently_slower.then(|| {
+            format!(
+                "{} consistently slower: median {}ms vs {}ms over {}",
+                slower,
+                slow_ms,
+                fast_ms,
+                slower.other()
+            )
+        })
+    }
+}
@@ -6,11 +6,10 @@ use crate::core::network::types::{JsonlError, NetworkError};
 use serde_json::Value;
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
-use std::io::SeekFrom;
+use std::io::{Read, Seek, SeekFrom};
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
-use tokio::io::{AsyncReadExt, AsyncSeekExt};
 
 /// Monitor for scanning JSONL transcript files and detecting API errors
 ///
@@ -116,56 +115,18 @@ impl JsonlMonitor {
     /// Configurable via CCSTATUS_JSONL_TAIL_KB environment variable (default: 64KB)
     async fn read_tail_content<P: AsRef<Path>>(&self, path: P) -> Result<String, NetworkError> {
         // Get configurable tail size with security bounds (default 64KB, max 10MB)
-        let tail_kb = std::env::var("CCSTATUS_JSONL_TAIL_KB")
-            .unwrap_or_else(|_| "64".to_string())
-            .parse::<u64>()
+        let tail_kb = crate::core::env::JSONL_TAIL_KB
+            .number::<u64>()
             .unwrap_or(64)
             .clamp(1, 10240); // Phase 2: Bound between 1KB and 10MB for security
         let tail_bytes = tail_kb * 1024;
 
-        // Open file and get metadata
-        let mut file = tokio::fs::File::open(path).await.map_err(|e| {
-            NetworkError::ConfigReadError(format!("Failed to open transcript: {}", e))
-        })?;
-
-        let file_len = file
-            .metadata()
+        let path = path.as_ref().to_path_buf();
+        crate::runtime::spawn_blocking(move || read_tail(&path, tail_bytes))
             .await
             .map_err(|e| {
-                NetworkError::ConfigReadError(format!("Failed to get file metadata: {}", e))
+                NetworkError::ConfigReadError(format!("Failed to join tail read: {}", e))
             })?
-            .len();
-
-        // If file is smaller than tail size, read entire file
-        if file_len <= tail_bytes {
-            let mut content = String::new();
-            file.read_to_string(&mut content).await.map_err(|e| {
-                NetworkError::ConfigReadError(format!("Failed to read small file: {}", e))
-            })?;
-            return Ok(content);
-        }
-
-        // Seek to tail position
-        let seek_pos = file_len - tail_bytes;
-        file.seek(SeekFrom::Start(seek_pos))
-            .await
-            .map_err(|e| NetworkError::ConfigReadError(format!("Failed to seek to tail: {}", e)))?;
-
-        // Read from seek position to find first complete line boundary
-        let mut buffer = Vec::new();
-        file.read_to_end(&mut buffer)
-            .await
-            .map_err(|e| NetworkError::ConfigReadError(format!("Failed to read tail: {}", e)))?;
-
-        // Convert to string and find first newline to avoid partial lines
-        let content = String::from_utf8_lossy(&buffer);
-        if let Some(first_newline) = content.find('\n') {
-            // Start from after the first newline to ensure complete lines
-            Ok(content[first_newline + 1..].to_string())
-        } else {
-            // If no newline found, use entire tail content
-            Ok(content.to_string())
-        }
     }
 
     /// Normalize error timestamp to a trustworthy RFC3339 value
@@ -609,6 +570,46 @@ impl JsonlMonitor {
     }
 }
 
+/// Blocking tail read of `path`, starting at the first complete line within `tail_bytes`
+fn read_tail(path: &Path, tail_bytes: u64) -> Result<String, NetworkError> {
+    let mut file = std::fs::File::open(path)
+        .map_err(|e| NetworkError::ConfigReadError(format!("Failed to open transcript: {}", e)))?;
+
+    let file_len = file
+        .metadata()
+        .map_err(|e| NetworkError::ConfigReadError(format!("Failed to get file metadata: {}", e)))?
+        .len();
+
+    // If file is smaller than tail size, read entire file
+    if file_len <= tail_bytes {
+        let mut content = String::new();
+        file.read_to_string(&mut content).map_err(|e| {
+            NetworkError::ConfigReadError(format!("Failed to read small file: {}", e))
+        })?;
+        return Ok(content);
+    }
+
+    // Seek to tail position
+    let seek_pos = file_len - tail_bytes;
+    file.seek(SeekFrom::Start(seek_pos))
+        .map_err(|e| NetworkError::ConfigReadError(format!("Failed to seek to tail: {}", e)))?;
+
+    // Read from seek position to find first complete line boundary
+    let mut buffer = Vec::new();
+    file.read_to_end(&mut buffer)
+        .map_err(|e| NetworkError::ConfigReadError(format!("Failed to read tail: {}", e)))?;
+
+    // Convert to string and find first newline to avoid partial lines
+    let content = String::from_utf8_lossy(&buffer);
+    if let Some(first_newline) = content.find('\n') {
+        // Start from after the first newline to ensure complete lines
+        Ok(content[first_newline + 1..].to_string())
+    } else {
+        // If no newline found, use entire tail content
+        Ok(content.to_string())
+    }
+}
+
 /// Internal struct for parsing transcript entries
 #[derive(Debug)]
 #[allow(dead_code)] // Fields are used for parsing but clippy can't detect due to Debug derive
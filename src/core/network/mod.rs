@@ -1,20 +1,42 @@
+pub mod alerts;
+pub mod certificate;
+pub mod cookie_jar;
 pub mod credential;
+pub mod daemon;
 pub mod debug_logger;
+pub mod dns_cache;
+pub mod error_budget;
 pub mod error_tracker;
 pub mod http_monitor;
+pub mod ip_family;
 pub mod jsonl_monitor;
 pub mod network_segment;
+#[cfg(feature = "oauth-probe")]
 pub mod oauth_masquerade;
+pub mod probe_history;
+pub mod probe_payload;
+pub mod probe_profile;
+pub mod prometheus;
 pub mod proxy_health;
+pub mod sla;
+pub mod standby;
+#[cfg(feature = "state-sync")]
+pub mod state_sync;
+pub mod status_expression;
+pub mod status_hooks;
 pub mod status_renderer;
+pub mod timeseries;
+#[cfg(feature = "tls-parity")]
+pub mod tls_profile;
 pub mod types;
 
 // Re-export commonly used items
 pub use credential::CredentialManager;
 pub use debug_logger::{get_debug_logger, EnhancedDebugLogger, JsonlLoggerConfig};
-pub use http_monitor::{ClockTrait, HttpClientTrait, HttpMonitor};
+pub use http_monitor::{ClockTrait, HttpClientTrait, HttpMonitor, TransportPolicy};
 pub use jsonl_monitor::JsonlMonitor;
-pub use network_segment::{CostInfo, NetworkSegment, StatuslineInput, WindowDecision};
+pub use network_segment::{CostInfo, NetworkSegment, ProbeCheck, StatuslineInput, WindowDecision};
+#[cfg(feature = "oauth-probe")]
 pub use oauth_masquerade::{run_probe, OauthMasqueradeOptions, OauthMasqueradeResult};
 pub use status_renderer::StatusRenderer;
 pub use types::*;
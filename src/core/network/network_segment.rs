@@ -31,32 +31,65 @@
 //! 3. **GREEN** (lowest priority): Regular health monitoring
 //!    - Trigger: `(total_duration_ms % 300_000) < 3_000`  
 //!    - Frequency: Every 300 seconds (first 3 seconds of window)
+//!    - Idle sessions (transcript untouched for `CCSTATUS_IDLE_AFTER_SECS`, default 600s)
+//!      stretch this to `CCSTATUS_IDLE_GREEN_SECS` (default 900s) until new activity
 //!    - Purpose: Baseline monitoring and P95 calculation
 //!
+//! The RED and GREEN periods above (and probe timeouts) are those of the `standard`
+//! probe profile; `CCSTATUS_PROBE_PROFILE` switches to another bundle for the
+//! session (see `probe_profile`).
+//!
 //! ## Integration Contract
 //!
 //! NetworkSegment follows the exact call sequence specified in the requirements:
 //!
 //! 1. Parse stdin → extract `total_duration_ms`, `transcript_path`, `session_id`
+//!    - No probe consent (`network.probe_consent`) → transcript-only passive mode → exit
+//!    - `ccstatus daemon` running (fresh lease, see `daemon`) → render its state → exit
 //! 2. `CredentialManager::get_credentials()` → `Option<ApiCredentials>`
 //! 3. No credentials → `HttpMonitor::write_unknown(false)` → render → exit
+//!    - OAuth credentials with `network.oauth_probe = false` → no probe, `oauth off` hint → exit
+//!    - Resolve the probe profile (`CCSTATUS_PROBE_PROFILE`, else the one pinned to the session)
 //! 4. Has credentials → `JsonlMonitor::scan_tail(transcript_path)` → error detection
 //! 5. Window calculation with priority: COLD > RED > GREEN
+//!    - Active 429 / bot challenge penalty cooldown → probe deferred
 //! 6. At most one `HttpMonitor::probe()` call per stdin event
 //! 7. `StatusRenderer::render_status()` → stdout
+//!
+//! ## Dry Run
+//!
+//! `CCSTATUS_DRYRUN=1` runs steps 2-5 unchanged but stops short of any network IO
+//! or state write: instead of probing, the would-be decision (mode, timeout, window
+//! IDs, skip reason) is appended to the JSONL operational log as a `dry_run` record.
 
+use crate::config::{
+    Flag, FlagSet, HistoryConfig, HostPolicy, HysteresisConfig, IpFamilyMode, NetworkTarget,
+};
+use crate::core::attention::AttentionStyle;
+use crate::core::env;
+use crate::core::format::NumberFormat;
+use crate::core::i18n::Locale;
+use crate::core::icons::IconSet;
 use crate::core::network::credential::CredentialManager;
+use crate::core::network::daemon::DaemonLease;
 use crate::core::network::debug_logger::get_debug_logger;
-use crate::core::network::http_monitor::HttpMonitor;
+use crate::core::network::error_budget::ErrorBudgetPolicy;
+use crate::core::network::http_monitor::{HttpMonitor, TransportPolicy};
 use crate::core::network::jsonl_monitor::JsonlMonitor;
+use crate::core::network::probe_payload::ProbeRequest;
+use crate::core::network::probe_profile::{ProbeProfile, PROBE_PROFILE_ENV};
+use crate::core::network::status_expression::StatusExpression;
+use crate::core::network::status_hooks::StatusHook;
 use crate::core::network::status_renderer::StatusRenderer;
-use crate::core::network::types::{NetworkError, ProbeMode};
+use crate::core::network::types::{
+    ActivityClass, CredentialSource, JsonlError, NetworkError, NetworkStatus, ProbeMode,
+    WindowPolicy,
+};
+use crate::core::scheduler::Cadence;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::env;
 use std::io::{self, Read};
 use std::path::PathBuf;
-use tokio::task;
 
 /// Stdin input structure from Claude Code statusline
 ///
@@ -83,6 +116,12 @@ pub struct StatuslineInput {
     pub cost: CostInfo,
     /// Whether session exceeds token limits
     pub exceeds_200k_tokens: bool,
+    /// Columns available to the statusline, when Claude Code reports them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub terminal_width: Option<u16>,
+    /// Permission mode (`plan`, `acceptEdits`, ...), when Claude Code reports it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permission_mode: Option<String>,
 }
 
 /// Cost and timing information from Claude Code
@@ -111,10 +150,61 @@ pub struct WindowDecision {
     pub is_green_window: bool,
     /// Selected probe mode based on priority and conditions
     pub probe_mode: Option<ProbeMode>,
-    /// GREEN window ID for deduplication (total_duration_ms / 300_000)
+    /// GREEN window ID for deduplication (total_duration_ms / GREEN period, 300_000 by default)
     pub green_window_id: Option<u64>,
-    /// RED window ID for deduplication (total_duration_ms / 10_000)
+    /// RED window ID for deduplication (total_duration_ms / RED period, 10_000 by default)
     pub red_window_id: Option<u64>,
+    /// Session activity class; idle sessions only probe every Nth GREEN window
+    pub activity: ActivityClass,
+}
+
+/// Result of [`NetworkSegment::check_once`] and [`NetworkSegment::probe_scheduled`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbeCheck {
+    pub status: NetworkStatus,
+    /// Where the probe's credentials came from; None when no probe was sent
+    pub credential_source: Option<String>,
+    pub latency_ms: Option<u32>,
+    /// Why no probe was sent
+    pub skip_reason: Option<String>,
+}
+
+impl ProbeCheck {
+    fn skipped(reason: &str) -> Self {
+        Self {
+            status: NetworkStatus::Unknown,
+            credential_source: None,
+            latency_ms: None,
+            skip_reason: Some(reason.to_string()),
+        }
+    }
+}
+
+/// Decision a probe would have acted on, recorded instead of probing in dry-run mode
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DryRunRecord {
+    pub session_id: String,
+    pub total_duration_ms: u64,
+    /// Credential source that would be used (`None` = no credentials, unknown status)
+    pub credential_source: Option<String>,
+    /// Transcript error scan result (`None` = skipped on the COLD path)
+    pub error_detected: Option<bool>,
+    pub is_cold_window: bool,
+    pub is_red_window: bool,
+    pub is_green_window: bool,
+    pub green_window_id: Option<u64>,
+    pub red_window_id: Option<u64>,
+    pub activity: ActivityClass,
+    /// Probe profile the decision was made under
+    pub probe_profile: ProbeProfile,
+    /// RED watchdog would have demoted the probe to GREEN cadence
+    pub red_watchdog_demoted: bool,
+    /// Probe mode that would run: `cold` | `green` | `red`
+    pub would_probe: Option<String>,
+    /// Timeout the probe would use
+    pub timeout_ms: Option<u32>,
+    /// Why no probe would run
+    pub skip_reason: Option<String>,
 }
 
 /// NetworkSegment - primary orchestration component for network monitoring
@@ -126,6 +216,18 @@ pub struct NetworkSegment {
     jsonl_monitor: JsonlMonitor,
     http_monitor: HttpMonitor,
     status_renderer: StatusRenderer,
+    /// Whether the user opted in to live probes (`network.probe_consent`)
+    probe_consent: bool,
+    /// Subsystem kill switches (manifest `flags`, overridden by `[flags]`)
+    flags: FlagSet,
+    /// Log decisions instead of probing or writing state (`CCSTATUS_DRYRUN`)
+    dry_run: bool,
+    /// Record of the last dry-run orchestration
+    last_dry_run: Option<DryRunRecord>,
+    /// Last run found OAuth credentials with the OAuth probe off (see `oauth_off()`)
+    oauth_off: bool,
+    /// Lease of a running `ccstatus daemon`, which takes over probing while fresh
+    daemon_lease: Option<PathBuf>,
 }
 
 impl NetworkSegment {
@@ -139,6 +241,12 @@ impl NetworkSegment {
             jsonl_monitor: JsonlMonitor::new(),
             http_monitor: HttpMonitor::new(None)?,
             status_renderer: StatusRenderer::new(),
+            probe_consent: false,
+            flags: FlagSet::default(),
+            dry_run: Self::get_dry_run(),
+            last_dry_run: None,
+            oauth_off: false,
+            daemon_lease: None,
         })
     }
 
@@ -149,6 +257,323 @@ impl NetworkSegment {
             jsonl_monitor: JsonlMonitor::new(),
             http_monitor: HttpMonitor::new(Some(state_path))?,
             status_renderer: StatusRenderer::new(),
+            probe_consent: false,
+            flags: FlagSet::default(),
+            dry_run: Self::get_dry_run(),
+            last_dry_run: None,
+            oauth_off: false,
+            daemon_lease: None,
+        })
+    }
+
+    /// Create NetworkSegment for a named target (`[[network.targets]]`)
+    ///
+    /// The target gets its own state partition at `state_path` (see
+    /// [`crate::core::state_location::StateLocation::target_path`]) and, when it sets
+    /// `base_url`, its own credentials instead of the regular resolution chain.
+    pub fn for_target(target: &NetworkTarget, state_path: PathBuf) -> Result<Self, NetworkError> {
+        let mut credential_manager = CredentialManager::new()?;
+        if let Some(ref base_url) = target.base_url {
+            credential_manager =
+                credential_manager.with_target(base_url.clone(), target.token_env.clone());
+        }
+
+        Ok(Self {
+            credential_manager,
+            jsonl_monitor: JsonlMonitor::new(),
+            http_monitor: HttpMonitor::new(Some(state_path))?,
+            status_renderer: StatusRenderer::new(),
+            probe_consent: false,
+            flags: FlagSet::default(),
+            dry_run: Self::get_dry_run(),
+            last_dry_run: None,
+            oauth_off: false,
+            daemon_lease: None,
+        })
+    }
+
+    /// Record whether the user consented to live API probes
+    ///
+    /// Without consent the segment never resolves credentials or probes; it only
+    /// scans the transcript (see `run_passive()`).
+    pub fn with_probe_consent(mut self, consent: bool) -> Self {
+        self.probe_consent = consent;
+        self
+    }
+
+    /// Apply `configure` to the HttpMonitor behind this segment
+    pub fn map_monitor(mut self, configure: impl FnOnce(HttpMonitor) -> HttpMonitor) -> Self {
+        self.http_monitor = configure(self.http_monitor);
+        self
+    }
+
+    /// Restrict probes and health checks to hosts allowed by `network.allowed_hosts`
+    pub fn with_host_policy(mut self, policy: HostPolicy) -> Self {
+        self.http_monitor = self.http_monitor.with_host_policy(policy);
+        self
+    }
+
+    /// Send probes and health checks through the proxy, TLS and address family
+    /// settings of `transport`
+    pub fn with_transport(mut self, transport: TransportPolicy) -> Self {
+        self.http_monitor = self.http_monitor.with_transport(transport);
+        self
+    }
+
+    /// Persist probe cookies at `path` across invocations (`network.persist_cookies`)
+    pub fn with_cookie_jar(mut self, path: PathBuf) -> Self {
+        self.http_monitor = self.http_monitor.with_cookie_jar(path);
+        self
+    }
+
+    /// Cache probe host lookups at `path` and pin probes to them (`network.dns_cache`)
+    pub fn with_dns_cache(mut self, path: PathBuf) -> Self {
+        self.http_monitor = self.http_monitor.with_dns_cache(path);
+        self
+    }
+
+    /// Add auxiliary endpoint checks to each probe (`network.aux_checks`)
+    pub fn with_aux_checks(mut self, enabled: bool) -> Self {
+        self.http_monitor = self.http_monitor.with_aux_checks(enabled);
+        self
+    }
+
+    /// Warn about a custom gateway's certificate `days` before expiry (`network.cert_warn_days`)
+    pub fn with_cert_warn_days(mut self, days: u32) -> Self {
+        self.http_monitor = self.http_monitor.with_cert_warn_days(days);
+        self
+    }
+
+    /// Force an address family on probes or compare both (`network.ip_family`)
+    pub fn with_ip_family(mut self, mode: IpFamilyMode) -> Self {
+        self.http_monitor = self.http_monitor.with_ip_family(mode);
+        self
+    }
+
+    /// Age out latency samples older than `secs` (`network.sample_horizon_secs`)
+    pub fn with_sample_horizon(mut self, secs: Option<u64>) -> Self {
+        self.http_monitor = self.http_monitor.with_sample_horizon(secs);
+        self
+    }
+
+    /// Keep the per-probe history within `retention` (`[network.history]`)
+    pub fn with_history_retention(mut self, retention: HistoryConfig) -> Self {
+        self.http_monitor = self.http_monitor.with_history_retention(retention);
+        self
+    }
+
+    /// Rolling window length and status cutoffs (`[network.window]`)
+    pub fn with_window_policy(mut self, policy: WindowPolicy) -> Self {
+        self.http_monitor = self.http_monitor.with_window_policy(policy);
+        self
+    }
+
+    /// Probes in a row needed before the shown status changes (`[network.hysteresis]`)
+    pub fn with_hysteresis(mut self, hysteresis: HysteresisConfig) -> Self {
+        self.http_monitor = self.http_monitor.with_hysteresis(hysteresis);
+        self
+    }
+
+    /// Track the failed-probe budget over the probe history (`[network.error_budget]`)
+    pub fn with_error_budget(mut self, policy: Option<ErrorBudgetPolicy>) -> Self {
+        self.http_monitor = self.http_monitor.with_error_budget(policy);
+        self
+    }
+
+    /// Classify probes with `network.status_expression` instead of the built-in thresholds
+    pub fn with_status_expression(mut self, expression: Option<StatusExpression>) -> Self {
+        self.http_monitor = self.http_monitor.with_status_expression(expression);
+        self
+    }
+
+    /// Allow or refuse probes with OAuth credentials (`network.oauth_probe`)
+    pub fn with_oauth_probe(mut self, enabled: bool) -> Self {
+        self.http_monitor = self.http_monitor.with_oauth_probe(enabled);
+        self
+    }
+
+    /// Probe model, token budget and path for x-api-key credentials (`[network.probe]`)
+    pub fn with_probe_request(mut self, request: ProbeRequest) -> Self {
+        self.http_monitor = self.http_monitor.with_probe_request(request);
+        self
+    }
+
+    /// Probe these base URLs alongside the active one (`network.compare_endpoints`)
+    pub fn with_compare_endpoints(mut self, base_urls: Vec<String>) -> Self {
+        self.http_monitor = self.http_monitor.with_compare_endpoints(base_urls);
+        self
+    }
+
+    /// Use `profile` for window decisions made outside `run()` (for testing)
+    ///
+    /// `run()` resolves the session's profile itself (see `resolve_probe_profile()`).
+    pub fn with_probe_profile(mut self, profile: ProbeProfile) -> Self {
+        self.http_monitor.set_probe_profile(profile);
+        self
+    }
+
+    /// Run `hook` after every probe (`network.pause_advisory`)
+    pub fn with_status_hook(mut self, hook: Box<dyn StatusHook>) -> Self {
+        self.http_monitor = self.http_monitor.with_status_hook(hook);
+        self
+    }
+
+    /// Apply kill switches: disabled probe modes are skipped, `proxy_health` gates the proxy check
+    pub fn with_flags(mut self, flags: FlagSet) -> Self {
+        self.http_monitor = self
+            .http_monitor
+            .with_proxy_health(flags.is_enabled(Flag::ProxyHealth));
+        self.flags = flags;
+        self
+    }
+
+    /// Render error hints as OSC 8 links (status page / `error_type` docs)
+    pub fn with_hyperlinks(mut self, enabled: bool) -> Self {
+        self.status_renderer = self.status_renderer.with_hyperlinks(enabled);
+        self
+    }
+
+    /// Render status lights and marks from `icons`
+    pub fn with_icons(mut self, icons: IconSet) -> Self {
+        self.status_renderer = self.status_renderer.with_icons(icons);
+        self
+    }
+
+    /// Render status words in `locale`
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.status_renderer = self.status_renderer.with_locale(locale);
+        self
+    }
+
+    /// Render latencies per `[formatting]`
+    pub fn with_number_format(mut self, format: NumberFormat) -> Self {
+        self.status_renderer = self.status_renderer.with_number_format(format);
+        self
+    }
+
+    /// Make the error state stand out per `attention`
+    pub fn with_attention(mut self, attention: AttentionStyle) -> Self {
+        self.status_renderer = self.status_renderer.with_attention(attention);
+        self
+    }
+
+    /// Log the probe decision instead of probing or writing state (overrides `CCSTATUS_DRYRUN`)
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Decision recorded by the last dry-run orchestration
+    pub fn last_dry_run(&self) -> Option<&DryRunRecord> {
+        self.last_dry_run.as_ref()
+    }
+
+    /// Leave probing to a `ccstatus daemon` holding a fresh lease at `path`
+    pub fn with_daemon_lease(mut self, path: PathBuf) -> Self {
+        self.daemon_lease = Some(path);
+        self
+    }
+
+    /// Whether live API probes are allowed
+    pub fn probe_consent(&self) -> bool {
+        self.probe_consent
+    }
+
+    /// One full cycle right now, for `ccstatus net check`
+    ///
+    /// Resolves credentials and runs a COLD probe (proxy health and
+    /// classification included), regardless of windows and probe consent:
+    /// invoking the command is the consent. Unknown when there are no
+    /// credentials, OAuth probing is off or the `cold_probing` kill switch is set;
+    /// the state file is left alone in those cases.
+    pub async fn check_once(&mut self) -> Result<ProbeCheck, NetworkError> {
+        self.probe_now(ProbeMode::Cold).await
+    }
+
+    /// One probe on `ccstatus daemon`'s schedule
+    ///
+    /// Like `check_once` with the daemon's mode, except that an active 429 /
+    /// bot challenge penalty defers it.
+    pub async fn probe_scheduled(&mut self, mode: ProbeMode) -> Result<ProbeCheck, NetworkError> {
+        let state = self.http_monitor.load_state().await.unwrap_or_default();
+        if let Some(remaining) = self.http_monitor.penalty_remaining_secs(&state) {
+            return Ok(ProbeCheck::skipped(&format!(
+                "penalty cooldown, {}s left",
+                remaining
+            )));
+        }
+        self.probe_now(mode).await
+    }
+
+    async fn probe_now(&mut self, mode: ProbeMode) -> Result<ProbeCheck, NetworkError> {
+        let Some(creds) = self.credential_manager.get_credentials().await? else {
+            return Ok(ProbeCheck::skipped("no credentials found"));
+        };
+        if creds.source == CredentialSource::OAuth && !self.http_monitor.oauth_probe_enabled() {
+            return Ok(ProbeCheck::skipped(
+                "OAuth credentials with network.oauth_probe off",
+            ));
+        }
+        let flag = Self::probe_flag(mode);
+        let (enabled, source) = self.flags.resolve(flag);
+        if !enabled {
+            return Ok(ProbeCheck::skipped(&format!(
+                "{} disabled by {}",
+                flag.name(),
+                source
+            )));
+        }
+
+        let credential_source = creds.source.to_string();
+        let outcome = self.http_monitor.probe(mode, creds, None).await?;
+        Ok(ProbeCheck {
+            status: outcome.status,
+            credential_source: Some(credential_source),
+            latency_ms: Some(outcome.metrics.latency_ms),
+            skip_reason: None,
+        })
+    }
+
+    /// Kill switch governing probes of `mode`
+    fn probe_flag(mode: ProbeMode) -> Flag {
+        match mode {
+            ProbeMode::Cold => Flag::ColdProbing,
+            ProbeMode::Green => Flag::GreenProbing,
+            ProbeMode::Red => Flag::RedProbing,
+        }
+    }
+
+    /// Passive (transcript-only) monitoring used until probe consent is granted
+    ///
+    /// Scans the transcript tail for API errors without touching credentials,
+    /// the network or the monitoring state file, and returns the rendered status.
+    pub async fn run_passive(&self, input: &StatuslineInput) -> Result<String, NetworkError> {
+        let last_error = self.last_transcript_error(input).await?;
+        Ok(self.status_renderer.render_probes_off(last_error.as_ref()))
+    }
+
+    /// Whether the last run skipped probing because the credentials are OAuth and
+    /// `network.oauth_probe` is off (or the build lacks the `oauth-probe` feature)
+    pub fn oauth_off(&self) -> bool {
+        self.oauth_off
+    }
+
+    /// Transcript-only status rendered instead of the probe result when `oauth_off()`
+    pub async fn render_oauth_off(&self, input: &StatuslineInput) -> Result<String, NetworkError> {
+        let last_error = self.last_transcript_error(input).await?;
+        Ok(self.status_renderer.render_oauth_off(last_error.as_ref()))
+    }
+
+    async fn last_transcript_error(
+        &self,
+        input: &StatuslineInput,
+    ) -> Result<Option<JsonlError>, NetworkError> {
+        let (error_detected, last_error_event) =
+            self.jsonl_monitor.scan_tail(&input.transcript_path).await?;
+        Ok(if error_detected {
+            last_error_event
+        } else {
+            None
         })
     }
 
@@ -225,6 +650,31 @@ impl NetworkSegment {
     async fn orchestrate(&mut self, input: StatuslineInput) -> Result<(), NetworkError> {
         let debug_logger = get_debug_logger();
 
+        // Step 1a: Probe consent gate - probes are real, token-consuming requests
+        if !self.probe_consent {
+            debug_logger
+                .debug(
+                    "NetworkSegment",
+                    "Probe consent not granted - skipping probes (run `ccstatus net enable`)",
+                )
+                .await;
+            return Ok(());
+        }
+
+        // Step 1b: A running `ccstatus daemon` owns probing; only render its state
+        if let Some(ref lease_path) = self.daemon_lease {
+            if DaemonLease::running_at(lease_path, chrono::Utc::now().timestamp()) {
+                debug_logger
+                    .debug(
+                        "NetworkSegment",
+                        "ccstatus daemon is running - reading its state without probing",
+                    )
+                    .await;
+                self.render_and_output().await?;
+                return Ok(());
+            }
+        }
+
         // Step 2: Resolve credentials (env > shell > config priority)
         debug_logger
             .debug("NetworkSegment", "Resolving credentials...")
@@ -232,6 +682,29 @@ impl NetworkSegment {
         let credentials = self.credential_manager.get_credentials().await?;
 
         if credentials.is_none() {
+            if self.dry_run {
+                self.record_dry_run(DryRunRecord {
+                    session_id: input.session_id.clone(),
+                    total_duration_ms: input.cost.total_duration_ms,
+                    credential_source: None,
+                    error_detected: None,
+                    is_cold_window: false,
+                    is_red_window: false,
+                    is_green_window: false,
+                    green_window_id: None,
+                    red_window_id: None,
+                    activity: ActivityClass::default(),
+                    probe_profile: self.http_monitor.probe_profile(),
+                    red_watchdog_demoted: false,
+                    would_probe: None,
+                    timeout_ms: None,
+                    skip_reason: Some("no_credentials".to_string()),
+                })
+                .await;
+                self.render_and_output().await?;
+                return Ok(());
+            }
+
             debug_logger
                 .debug(
                     "NetworkSegment",
@@ -253,6 +726,22 @@ impl NetworkSegment {
             )
             .await;
 
+        // Step 2b: OAuth probe gate - leave the state untouched and let the caller hint
+        self.oauth_off =
+            creds.source == CredentialSource::OAuth && !self.http_monitor.oauth_probe_enabled();
+        if self.oauth_off {
+            debug_logger
+                .debug(
+                    "NetworkSegment",
+                    "OAuth credentials with network.oauth_probe off - skipping probes",
+                )
+                .await;
+            return Ok(());
+        }
+
+        // Step 2c: Probe profile for this stdin event (cadence, timeouts, payload strategy)
+        self.resolve_probe_profile(&input.session_id).await;
+
         // Step 3: Scan transcript for error detection (non-COLD path only)
         // This eliminates duplicate JSONL scans by performing it once before window calculation
         let cold_window_ms = Self::get_cold_window_threshold();
@@ -288,7 +777,7 @@ impl NetworkSegment {
         };
 
         // Step 4: Calculate window decisions
-        let window_decision = self
+        let mut window_decision = self
             .calculate_window_decision(&input, error_detected)
             .await?;
         debug_logger
@@ -304,6 +793,113 @@ impl NetworkSegment {
             )
             .await;
 
+        let mut red_watchdog_demoted = false;
+        let mut skip_reason = None;
+
+        // Step 4a: RED watchdog - a stale transcript error must not pin RED cadence forever
+        if window_decision.is_red_window {
+            if let Some(ref error_event) = last_error_event {
+                let state = self.http_monitor.load_state().await.unwrap_or_default();
+                let ceiling_secs = Self::get_red_watchdog_ceiling();
+                if self.http_monitor.red_watchdog_expired(
+                    &state,
+                    &error_event.timestamp,
+                    ceiling_secs,
+                ) {
+                    debug_logger
+                        .debug(
+                            "NetworkSegment",
+                            &format!(
+                                "RED watchdog: no new transcript error for {}s - demoting to GREEN cadence",
+                                ceiling_secs
+                            ),
+                        )
+                        .await;
+                    if !self.dry_run {
+                        self.http_monitor.set_red_watchdog_triggered().await?;
+                    }
+                    red_watchdog_demoted = true;
+                    window_decision = self
+                        .green_window_decision(
+                            input.cost.total_duration_ms,
+                            window_decision.activity,
+                        )
+                        .await?;
+                }
+            }
+        }
+
+        // Step 4b: Penalty backoff after 429 / bot challenge defers every probe mode
+        if window_decision.probe_mode.is_some() {
+            let state = self.http_monitor.load_state().await.unwrap_or_default();
+            if let Some(remaining) = self.http_monitor.penalty_remaining_secs(&state) {
+                debug_logger
+                    .debug(
+                        "NetworkSegment",
+                        &format!(
+                            "Penalty cooldown active ({}s left, level {}) - deferring probe",
+                            remaining, state.monitoring_state.penalty.level
+                        ),
+                    )
+                    .await;
+                window_decision.probe_mode = None;
+                skip_reason = Some("penalty_cooldown".to_string());
+            }
+        }
+
+        // Step 4c: Kill switches (remote manifest flags or local `[flags]`)
+        if let Some(probe_mode) = window_decision.probe_mode {
+            let flag = Self::probe_flag(probe_mode);
+            let (enabled, source) = self.flags.resolve(flag);
+            if !enabled {
+                debug_logger
+                    .debug(
+                        "NetworkSegment",
+                        &format!(
+                            "{:?} probe skipped: {} disabled by {}",
+                            probe_mode,
+                            flag.name(),
+                            source
+                        ),
+                    )
+                    .await;
+                window_decision.probe_mode = None;
+                skip_reason = Some(format!("{}_disabled", flag.name()));
+            }
+        }
+
+        if self.dry_run {
+            let timeout_ms = match window_decision.probe_mode {
+                Some(probe_mode) => Some(self.http_monitor.calculate_timeout(probe_mode).await?),
+                None => None,
+            };
+            self.record_dry_run(DryRunRecord {
+                session_id: input.session_id.clone(),
+                total_duration_ms: input.cost.total_duration_ms,
+                credential_source: Some(creds.source.to_string()),
+                error_detected,
+                is_cold_window: window_decision.is_cold_window,
+                is_red_window: window_decision.is_red_window,
+                is_green_window: window_decision.is_green_window,
+                green_window_id: window_decision.green_window_id,
+                red_window_id: window_decision.red_window_id,
+                activity: window_decision.activity,
+                probe_profile: self.http_monitor.probe_profile(),
+                red_watchdog_demoted,
+                would_probe: window_decision
+                    .probe_mode
+                    .map(|mode| format!("{:?}", mode).to_lowercase()),
+                timeout_ms,
+                skip_reason: match window_decision.probe_mode {
+                    Some(_) => None,
+                    None => skip_reason.or_else(|| Some("no_active_window".to_string())),
+                },
+            })
+            .await;
+            self.render_and_output().await?;
+            return Ok(());
+        }
+
         // Step 5: Execute probe if window is active
         if let Some(probe_mode) = window_decision.probe_mode {
             self.http_monitor.set_session_id(input.session_id.clone());
@@ -365,9 +961,10 @@ impl NetworkSegment {
                                 #[cfg(feature = "self-update")]
                                 {
                                     let mut update_state = crate::updater::UpdateStateFile::load();
-                                    if let Err(e) =
-                                        update_state.tick_from_green(&green_id.to_string())
-                                    {
+                                    if let Err(e) = update_state.tick_from_green(
+                                        &green_id.to_string(),
+                                        self.http_monitor.host_policy(),
+                                    ) {
                                         debug_logger
                                             .debug(
                                                 "NetworkSegment",
@@ -414,7 +1011,9 @@ impl NetworkSegment {
                         #[cfg(feature = "self-update")]
                         {
                             let mut update_state = crate::updater::UpdateStateFile::load();
-                            if let Err(e) = update_state.tick_from_cold() {
+                            if let Err(e) =
+                                update_state.tick_from_cold(self.http_monitor.host_policy())
+                            {
                                 debug_logger
                                     .debug(
                                         "NetworkSegment",
@@ -443,7 +1042,7 @@ impl NetworkSegment {
     /// Reads complete stdin content and parses it as StatuslineInput JSON.
     /// Validates that required fields are present and have valid values.
     async fn parse_stdin_input(&self) -> Result<StatuslineInput, NetworkError> {
-        let buffer = task::spawn_blocking(|| {
+        let buffer = crate::runtime::spawn_blocking(|| {
             let mut stdin = io::stdin();
             let mut buffer = Vec::new();
             stdin.read_to_end(&mut buffer).map(|_| buffer)
@@ -483,7 +1082,10 @@ impl NetworkSegment {
     ///
     /// - **COLD**: `total_duration_ms < COLD_WINDOW_MS` with session deduplication
     /// - **RED**: `(total_duration_ms % 10_000) < 1_000` AND error detected AND window deduplication
-    /// - **GREEN**: `(total_duration_ms % 300_000) < 3_000` AND window deduplication
+    /// - **GREEN**: `(total_duration_ms % 300_000) < 3_000` AND window deduplication;
+    ///   idle sessions only use every Nth GREEN window (see `classify_activity`)
+    ///
+    /// Periods are the active probe profile's (shown: `standard`); `minimal` has no RED window.
     ///
     /// # Priority Rules
     ///
@@ -497,6 +1099,23 @@ impl NetworkSegment {
     ) -> Result<WindowDecision, NetworkError> {
         let total_duration_ms = input.cost.total_duration_ms;
 
+        // Session activity (state-tracked so transitions are visible in `net status`)
+        let activity = Self::classify_activity(
+            &input.transcript_path,
+            std::time::SystemTime::now(),
+            Self::get_idle_threshold(),
+        );
+        if self.dry_run {
+            // Activity transitions are state writes; dry runs only report the class
+        } else if let Ok(true) = self.http_monitor.set_activity(activity).await {
+            get_debug_logger()
+                .debug(
+                    "NetworkSegment",
+                    &format!("Session activity changed to {}", activity),
+                )
+                .await;
+        }
+
         // COLD window check (highest priority): Based on timing only (original design)
         let cold_window_ms = Self::get_cold_window_threshold();
         let is_cold_window = total_duration_ms < cold_window_ms;
@@ -512,6 +1131,7 @@ impl NetworkSegment {
                     probe_mode: None, // Skip due to deduplication
                     green_window_id: None,
                     red_window_id: None,
+                    activity,
                 });
             }
 
@@ -522,14 +1142,20 @@ impl NetworkSegment {
                 probe_mode: Some(ProbeMode::Cold),
                 green_window_id: None,
                 red_window_id: None,
+                activity,
             });
         }
 
         // RED window check (medium priority) - requires error detection
-        let red_timing_condition = (total_duration_ms % 10_000) < 1_000;
-        let red_window_id = total_duration_ms / 10_000;
+        let red_period_ms = self
+            .http_monitor
+            .probe_profile()
+            .red_interval_secs()
+            .map(|secs| secs * 1000);
 
-        if red_timing_condition {
+        if let Some(red_window_id) = red_period_ms
+            .and_then(|period| Cadence::new(period, 1_000).open_window(total_duration_ms))
+        {
             let error_detected = if let Some(detected) = error_detected {
                 // Use pre-computed error detection result
                 detected
@@ -551,6 +1177,7 @@ impl NetworkSegment {
                         probe_mode: None, // Skip due to window deduplication
                         green_window_id: None,
                         red_window_id: Some(red_window_id),
+                        activity,
                     });
                 }
 
@@ -561,16 +1188,32 @@ impl NetworkSegment {
                     probe_mode: Some(ProbeMode::Red),
                     green_window_id: None,
                     red_window_id: Some(red_window_id),
+                    activity,
                 });
             }
         }
 
         // GREEN window check (lowest priority)
+        self.green_window_decision(total_duration_ms, activity)
+            .await
+    }
+
+    /// GREEN window decision for `total_duration_ms` (shared with the RED watchdog)
+    async fn green_window_decision(
+        &self,
+        total_duration_ms: u64,
+        activity: ActivityClass,
+    ) -> Result<WindowDecision, NetworkError> {
         // Width widened from 3_000ms to 10_000ms for better capture
-        let is_green_window = (total_duration_ms % 300_000) < 10_000;
-        let green_window_id = total_duration_ms / 300_000;
+        let green_secs = self.http_monitor.probe_profile().green_interval_secs();
+        // Idle sessions skip windows in between, so the stretched cadence keeps the same IDs
+        let stride = match activity {
+            ActivityClass::Active => 1,
+            ActivityClass::Idle => Self::get_idle_green_stride(green_secs),
+        };
+        let cadence = Cadence::new(green_secs * 1000, 10_000).with_stride(stride);
 
-        if is_green_window {
+        if let Some(green_window_id) = cadence.open_window(total_duration_ms) {
             // Check GREEN window deduplication
             let state = self.http_monitor.load_state().await.unwrap_or_default();
             if state.monitoring_state.last_green_window_id == Some(green_window_id) {
@@ -582,6 +1225,7 @@ impl NetworkSegment {
                     probe_mode: None, // Skip due to window deduplication
                     green_window_id: Some(green_window_id),
                     red_window_id: None,
+                    activity,
                 });
             }
 
@@ -592,6 +1236,7 @@ impl NetworkSegment {
                 probe_mode: Some(ProbeMode::Green),
                 green_window_id: Some(green_window_id),
                 red_window_id: None,
+                activity,
             });
         }
 
@@ -603,6 +1248,7 @@ impl NetworkSegment {
             probe_mode: None,
             green_window_id: None,
             red_window_id: None,
+            activity,
         })
     }
 
@@ -635,31 +1281,151 @@ impl NetworkSegment {
         Ok(state.monitoring_state.last_cold_session_id.as_deref() == Some(session_id))
     }
 
+    /// Keep `record` and append it to the JSONL operational log as a `dry_run` entry
+    async fn record_dry_run(&mut self, record: DryRunRecord) {
+        let debug_logger = get_debug_logger();
+        debug_logger
+            .debug(
+                "NetworkSegment",
+                &format!(
+                    "Dry run: would_probe={:?} timeout_ms={:?} skip_reason={:?}",
+                    record.would_probe, record.timeout_ms, record.skip_reason
+                ),
+            )
+            .await;
+
+        if let Ok(mut entry) = serde_json::to_value(&record) {
+            entry["type"] = Value::String("dry_run".to_string());
+            entry["logged_at"] = Value::String(chrono::Local::now().to_rfc3339());
+            let _ = debug_logger.jsonl_sync(entry);
+        }
+        self.last_dry_run = Some(record);
+    }
+
     /// Render current status and output to stdout
     ///
     /// Loads current monitoring state and renders it using StatusRenderer.
     /// Output goes to stdout for Claude Code statusline display.
     async fn render_and_output(&self) -> Result<(), NetworkError> {
         let state = self.http_monitor.load_state().await.unwrap_or_default();
-        let _status_text = self.status_renderer.render_status(
+        let status_text = self.status_renderer.render_status(
             &state.status,
             &state.network,
             state.api_config.as_ref(),
         );
+        let status_text = self
+            .status_renderer
+            .render_with_endpoints(status_text, &state);
+        let status_text = self
+            .status_renderer
+            .render_with_alternative(status_text, &state);
+        let _status_text = self.status_renderer.render_with_cooldown(
+            status_text,
+            self.http_monitor.penalty_remaining_secs(&state),
+        );
 
         // Note: status_text output is handled by the segment wrapper for statusline integration
         Ok(())
     }
 
-    /// Get COLD window threshold in milliseconds from environment variables
-    ///
-    /// Checks both `CCSTATUS_COLD_WINDOW_MS` and `ccstatus_COLD_WINDOW_MS`
-    /// with fallback to 5000ms default
+    /// Dry-run switch from `CCSTATUS_DRYRUN`
+    fn get_dry_run() -> bool {
+        env::DRYRUN.is_enabled()
+    }
+
+    /// COLD window threshold in milliseconds from `CCSTATUS_COLD_WINDOW_MS`
+    /// (legacy `ccstatus_COLD_WINDOW_MS`), 5000ms by default
     fn get_cold_window_threshold() -> u64 {
-        env::var("CCSTATUS_COLD_WINDOW_MS")
-            .or_else(|_| env::var("ccstatus_COLD_WINDOW_MS"))
-            .map(|s| s.parse::<u64>().unwrap_or(5000))
-            .unwrap_or(5000)
+        env::COLD_WINDOW_MS.number().unwrap_or(5000)
+    }
+
+    /// Get RED watchdog ceiling in seconds from `CCSTATUS_RED_WATCHDOG_SECS`
+    ///
+    /// RED probing driven by the same transcript error for longer than this is
+    /// demoted to GREEN cadence. Defaults to 1800s (30 minutes).
+    fn get_red_watchdog_ceiling() -> u64 {
+        env::RED_WATCHDOG_SECS.number().unwrap_or(1800)
+    }
+
+    /// Classify the session from the transcript's last write
+    ///
+    /// Idle when the transcript hasn't been written for `idle_after_secs`; a missing
+    /// transcript or unreadable mtime counts as active so cadence is never stretched by mistake.
+    pub fn classify_activity(
+        transcript_path: &str,
+        now: std::time::SystemTime,
+        idle_after_secs: u64,
+    ) -> ActivityClass {
+        let modified = std::fs::metadata(transcript_path).and_then(|meta| meta.modified());
+        match modified.map(|modified| now.duration_since(modified)) {
+            Ok(Ok(age)) if age.as_secs() >= idle_after_secs => ActivityClass::Idle,
+            _ => ActivityClass::Active,
+        }
+    }
+
+    /// Get idle threshold from environment variable or use default
+    ///
+    /// Sessions whose transcript hasn't changed for this long are idle. Defaults to 600s.
+    fn get_idle_threshold() -> u64 {
+        env::IDLE_AFTER_SECS.number().unwrap_or(600)
+    }
+
+    /// GREEN window stride for idle sessions
+    ///
+    /// `CCSTATUS_IDLE_GREEN_SECS` (default 900s = every 3rd 300s window), rounded down
+    /// to whole GREEN windows of `green_secs`.
+    fn get_idle_green_stride(green_secs: u64) -> u64 {
+        let secs: u64 = env::IDLE_GREEN_SECS.number().unwrap_or(900);
+        (secs / green_secs).max(1)
+    }
+
+    /// Resolve the probe profile for `session_id` and hand it to HttpMonitor
+    ///
+    /// A valid `CCSTATUS_PROBE_PROFILE` is pinned to the session (not in dry runs);
+    /// without one the session keeps its pinned profile, other sessions get `standard`.
+    async fn resolve_probe_profile(&mut self, session_id: &str) {
+        let debug_logger = get_debug_logger();
+        let env_value = env::PROBE_PROFILE.raw();
+        if let Some(Err(e)) = env_value.as_deref().map(str::parse::<ProbeProfile>) {
+            debug_logger
+                .debug(
+                    "NetworkSegment",
+                    &format!("{} ignored: {}", PROBE_PROFILE_ENV, e),
+                )
+                .await;
+        }
+
+        let state = self.http_monitor.load_state().await.unwrap_or_default();
+        let (profile, pin) = ProbeProfile::resolve(
+            env_value.as_deref(),
+            state.monitoring_state.probe_profile.as_ref(),
+            session_id,
+        );
+        if pin && !self.dry_run {
+            match self
+                .http_monitor
+                .pin_probe_profile(session_id, profile)
+                .await
+            {
+                Ok(()) => {
+                    debug_logger
+                        .debug(
+                            "NetworkSegment",
+                            &format!("Probe profile {} pinned to session", profile),
+                        )
+                        .await
+                }
+                Err(e) => {
+                    debug_logger
+                        .debug(
+                            "NetworkSegment",
+                            &format!("Failed to pin probe profile: {}", e),
+                        )
+                        .await
+                }
+            }
+        }
+        self.http_monitor.set_probe_profile(profile);
     }
 
     // No GREEN width env override by design.
@@ -4,9 +4,12 @@
 // POST request to https://api.anthropic.com/v1/messages when OAuth credentials are
 // present and unexpired. It maintains the existing x-api-key flow unchanged.
 
-use crate::core::network::types::NetworkError;
+use crate::core::env;
+use crate::core::network::probe_payload::{
+    build_probe_payload, encode_probe_payload, payload_checksum, ProbePayloadKind, ProbeRequest,
+};
+use crate::core::network::types::{ConnectionInfo, NetworkError};
 use std::collections::HashMap;
-use std::env;
 
 #[cfg(feature = "timings-curl")]
 use crate::core::network::http_monitor::CurlProbeRunner;
@@ -35,6 +38,8 @@ pub struct OauthMasqueradeResult {
     pub response_headers: HashMap<String, String>,
     /// HTTP version used (HTTP/1.1 or HTTP/2.0)
     pub http_version: Option<String>,
+    /// Serving address, connections and redirects (curl transport only)
+    pub connection: Option<ConnectionInfo>,
 }
 
 /// Default header profile constants matching demo defaults
@@ -95,18 +100,11 @@ const DEFAULT_HEADER_PROFILE: HeaderProfile = HeaderProfile {
 };
 
 /// Claude Code system prompt that must always be at system[0]
-pub const CLAUDE_CODE_SYSTEM_PROMPT: &str = "You are Claude Code, Anthropic's official CLI for Claude.";
-
-/// Configuration environment variable names for OAuth masquerade headers
-/// These allow runtime overrides of default header values to prevent drift
-pub const TEST_HEADERS_FILE: &str = "CCSTATUS_TEST_HEADERS_FILE";
-const TEST_UA: &str = "CCSTATUS_USER_AGENT";
-const TEST_BETA_HEADER: &str = "CCSTATUS_ANTHROPIC_BETA";
-const TEST_STAINLESS_PACKAGE_VERSION: &str = "CCSTATUS_STAINLESS_PACKAGE_VERSION";
-const TEST_STAINLESS_OS: &str = "CCSTATUS_STAINLESS_OS";
-const TEST_STAINLESS_ARCH: &str = "CCSTATUS_STAINLESS_ARCH";
-const TEST_STAINLESS_RUNTIME: &str = "CCSTATUS_STAINLESS_RUNTIME";
-const TEST_STAINLESS_RUNTIME_VERSION: &str = "CCSTATUS_STAINLESS_RUNTIME_VERSION";
+pub const CLAUDE_CODE_SYSTEM_PROMPT: &str =
+    "You are Claude Code, Anthropic's official CLI for Claude.";
+
+/// Variable naming a file of extra test headers (see [`crate::core::env::TEST_HEADERS_FILE`])
+pub const TEST_HEADERS_FILE: &str = env::TEST_HEADERS_FILE.name;
 
 /// Build headers for OAuth masquerade request
 pub fn build_headers(opts: &OauthMasqueradeOptions) -> HashMap<String, String> {
@@ -133,8 +131,9 @@ pub fn build_headers(opts: &OauthMasqueradeOptions) -> HashMap<String, String> {
     );
 
     // User-Agent (test override available)
-    let user_agent =
-        env::var(TEST_UA).unwrap_or_else(|_| DEFAULT_HEADER_PROFILE.user_agent.to_string());
+    let user_agent = env::USER_AGENT
+        .raw()
+        .unwrap_or_else(|| DEFAULT_HEADER_PROFILE.user_agent.to_string());
     headers.insert("User-Agent".to_string(), user_agent);
 
     headers.insert(
@@ -161,26 +160,29 @@ pub fn build_headers(opts: &OauthMasqueradeOptions) -> HashMap<String, String> {
         "X-Stainless-Lang".to_string(),
         DEFAULT_HEADER_PROFILE.x_stainless_lang.to_string(),
     );
-    let package_version = env::var(TEST_STAINLESS_PACKAGE_VERSION).unwrap_or_else(|_| {
+    let package_version = env::STAINLESS_PACKAGE_VERSION.raw().unwrap_or_else(|| {
         DEFAULT_HEADER_PROFILE
             .x_stainless_package_version
             .to_string()
     });
     headers.insert("X-Stainless-Package-Version".to_string(), package_version);
 
-    let os = env::var(TEST_STAINLESS_OS)
-        .unwrap_or_else(|_| DEFAULT_HEADER_PROFILE.x_stainless_os.to_string());
+    let os = env::STAINLESS_OS
+        .raw()
+        .unwrap_or_else(|| DEFAULT_HEADER_PROFILE.x_stainless_os.to_string());
     headers.insert("X-Stainless-OS".to_string(), os);
 
-    let arch = env::var(TEST_STAINLESS_ARCH)
-        .unwrap_or_else(|_| DEFAULT_HEADER_PROFILE.x_stainless_arch.to_string());
+    let arch = env::STAINLESS_ARCH
+        .raw()
+        .unwrap_or_else(|| DEFAULT_HEADER_PROFILE.x_stainless_arch.to_string());
     headers.insert("X-Stainless-Arch".to_string(), arch);
 
-    let runtime = env::var(TEST_STAINLESS_RUNTIME)
-        .unwrap_or_else(|_| DEFAULT_HEADER_PROFILE.x_stainless_runtime.to_string());
+    let runtime = env::STAINLESS_RUNTIME
+        .raw()
+        .unwrap_or_else(|| DEFAULT_HEADER_PROFILE.x_stainless_runtime.to_string());
     headers.insert("X-Stainless-Runtime".to_string(), runtime);
 
-    let runtime_version = env::var(TEST_STAINLESS_RUNTIME_VERSION).unwrap_or_else(|_| {
+    let runtime_version = env::STAINLESS_RUNTIME_VERSION.raw().unwrap_or_else(|| {
         DEFAULT_HEADER_PROFILE
             .x_stainless_runtime_version
             .to_string()
@@ -198,8 +200,9 @@ pub fn build_headers(opts: &OauthMasqueradeOptions) -> HashMap<String, String> {
     );
 
     // Beta header (test override available)
-    let beta = env::var(TEST_BETA_HEADER)
-        .unwrap_or_else(|_| DEFAULT_HEADER_PROFILE.anthropic_beta.to_string());
+    let beta = env::ANTHROPIC_BETA
+        .raw()
+        .unwrap_or_else(|| DEFAULT_HEADER_PROFILE.anthropic_beta.to_string());
     headers.insert("anthropic-beta".to_string(), beta);
 
     // Stream helper method header when stream=true
@@ -211,7 +214,7 @@ pub fn build_headers(opts: &OauthMasqueradeOptions) -> HashMap<String, String> {
     }
 
     // Append test-only headers from file (internal testing)
-    if let Ok(headers_file) = env::var(TEST_HEADERS_FILE) {
+    if let Some(headers_file) = env::TEST_HEADERS_FILE.raw() {
         if let Ok(content) = std::fs::read_to_string(&headers_file) {
             for line in content.lines() {
                 if let Some((key, value)) = line.split_once(':') {
@@ -240,48 +243,38 @@ pub fn build_headers(opts: &OauthMasqueradeOptions) -> HashMap<String, String> {
 }
 
 /// Build request body for OAuth masquerade request
+///
+/// The body comes from the shared probe payload builder and must pass its
+/// sanitization contract (fixed model, fixed message, fixed system prompt).
 pub fn build_request_body(opts: &OauthMasqueradeOptions) -> Result<Vec<u8>, NetworkError> {
-    let mut body = serde_json::json!({
-        "model": "claude-3-5-haiku-20241022",
-        "max_tokens": 1,
-        "system": [
-            {
-                "type": "text",
-                "text": CLAUDE_CODE_SYSTEM_PROMPT
-            }
-        ],
-        "messages": [
-            {
-                "role": "user",
-                "content": "Hi"
-            }
-        ]
-    });
-
-    // Add stream parameter when requested
-    if opts.stream {
-        body["stream"] = serde_json::Value::Bool(true);
-    }
+    // Always the default model and path, as Claude Code itself sends
+    let request = ProbeRequest::default();
+    let payload = build_probe_payload(
+        ProbePayloadKind::OauthMasquerade {
+            stream: opts.stream,
+        },
+        &request,
+    );
 
-    serde_json::to_vec(&body).map_err(|e| {
+    encode_probe_payload(&payload, &request).map_err(|e| {
         // Debug logging for serialization errors
-        if is_debug_enabled() {
-            eprintln!("OAuth masquerade body serialization error: {}", e);
-        }
+        crate::core::network::debug_logger::get_debug_logger().error_sync(
+            "OauthMasquerade",
+            "body_serialization",
+            &format!("OAuth masquerade body serialization error: {}", e),
+        );
         NetworkError::HttpError(format!("OAuth masquerade body serialization failed: {}", e))
     })
 }
 
-/// Check if debug mode is enabled via CCSTATUS_DEBUG environment variable
+/// Whether the debug log is on (`CCSTATUS_DEBUG` or `init_logging`)
 fn is_debug_enabled() -> bool {
-    env::var("CCSTATUS_DEBUG")
-        .unwrap_or_default()
-        .to_uppercase()
-        == "TRUE"
+    crate::core::network::debug_logger::get_debug_logger().is_enabled()
 }
 
 /// Redact response headers using allowlist approach for security
 /// Only returns headers that are safe to log and don't contain sensitive information
+/// (plus the `anthropic-ratelimit-*` quota headers)
 pub fn redact_response_headers(headers: &HashMap<String, String>) -> HashMap<String, String> {
     // Allowlist of safe response headers that don't contain sensitive information
     const ALLOWED_HEADERS: &[&str] = &[
@@ -314,6 +307,9 @@ pub fn redact_response_headers(headers: &HashMap<String, String>) -> HashMap<Str
             ALLOWED_HEADERS
                 .iter()
                 .any(|allowed| key.eq_ignore_ascii_case(allowed))
+                || key
+                    .to_ascii_lowercase()
+                    .starts_with(crate::core::network::types::RATE_LIMIT_HEADER_PREFIX)
         })
         .map(|(k, v)| (k.clone(), v.clone()))
         .collect()
@@ -334,9 +330,11 @@ pub fn is_token_expired(expires_at: Option<i64>) -> bool {
 
 /// Check for expired OAuth token and handle debug logging
 /// Returns error if token is expired, otherwise returns Ok(())
-async fn check_token_expiry_with_logging(opts: &OauthMasqueradeOptions) -> Result<(), NetworkError> {
+async fn check_token_expiry_with_logging(
+    opts: &OauthMasqueradeOptions,
+) -> Result<(), NetworkError> {
     if is_token_expired(opts.expires_at) {
-        // Log expiry skip when CCSTATUS_DEBUG=TRUE
+        // Log expiry skip when the debug log is on
         if is_debug_enabled() {
             let now_ms = std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
@@ -348,7 +346,7 @@ async fn check_token_expiry_with_logging(opts: &OauthMasqueradeOptions) -> Resul
                 .map(|exp| format!("{}", exp))
                 .unwrap_or_else(|| "none".to_string());
 
-            tokio::spawn(async move {
+            crate::runtime::spawn(async move {
                 use crate::core::network::debug_logger::get_debug_logger;
                 let logger = get_debug_logger();
                 let _ = logger
@@ -363,7 +361,9 @@ async fn check_token_expiry_with_logging(opts: &OauthMasqueradeOptions) -> Resul
             });
         }
 
-        return Err(NetworkError::CredentialError("OAuth token expired".to_string()));
+        return Err(NetworkError::CredentialError(
+            "OAuth token expired".to_string(),
+        ));
     }
     Ok(())
 }
@@ -373,10 +373,11 @@ async fn log_entry_decision(opts: &OauthMasqueradeOptions) {
     if is_debug_enabled() {
         use crate::core::network::debug_logger::get_debug_logger;
         let logger = get_debug_logger();
-        let user_agent =
-            env::var(TEST_UA).unwrap_or_else(|_| DEFAULT_HEADER_PROFILE.user_agent.to_string());
-        let beta_present =
-            env::var(TEST_BETA_HEADER).is_ok() || !DEFAULT_HEADER_PROFILE.anthropic_beta.is_empty();
+        let user_agent = env::USER_AGENT
+            .raw()
+            .unwrap_or_else(|| DEFAULT_HEADER_PROFILE.user_agent.to_string());
+        let beta_present = env::ANTHROPIC_BETA.raw().is_some()
+            || !DEFAULT_HEADER_PROFILE.anthropic_beta.is_empty();
         let token_len = opts.access_token.len();
         let expires_desc = opts
             .expires_at
@@ -394,21 +395,28 @@ async fn log_entry_decision(opts: &OauthMasqueradeOptions) {
 }
 
 /// Log OAuth masquerade request construction debug information
-async fn log_request_construction(endpoint: &str, headers: &std::collections::HashMap<String, String>, body: &[u8], opts: &OauthMasqueradeOptions) {
+async fn log_request_construction(
+    endpoint: &str,
+    headers: &std::collections::HashMap<String, String>,
+    body: &[u8],
+    opts: &OauthMasqueradeOptions,
+) {
     if is_debug_enabled() {
         use crate::core::network::debug_logger::get_debug_logger;
         let logger = get_debug_logger();
         let header_count = headers.len();
         let body_size = body.len();
-        let has_test_overrides = env::var(TEST_HEADERS_FILE).is_ok()
-            || env::var(TEST_UA).is_ok()
-            || env::var(TEST_BETA_HEADER).is_ok();
+        let has_test_overrides = env::TEST_HEADERS_FILE.raw().is_some()
+            || env::USER_AGENT.raw().is_some()
+            || env::ANTHROPIC_BETA.raw().is_some();
+
+        let body_sha256 = payload_checksum(body);
 
         let _ = logger.debug(
             "OauthMasquerade",
             &format!(
-                "request_construction endpoint={} headers_count={} body_size={} test_overrides={} stream={}",
-                endpoint, header_count, body_size, has_test_overrides, opts.stream
+                "request_construction endpoint={} headers_count={} body_size={} body_sha256={} test_overrides={} stream={}",
+                endpoint, header_count, body_size, body_sha256, has_test_overrides, opts.stream
             )
         ).await;
     }
@@ -465,26 +473,19 @@ pub async fn run_probe(
         // Try curl first for detailed phase timings
         match curl_runner.run(&endpoint, &headers_vec, &body, 10000).await {
             Ok(phase_timings) => {
-                let breakdown = format!(
-                    "DNS:{}ms|TCP:{}ms|TLS:{}ms|TTFB:{}ms|Total:{}ms",
-                    phase_timings.dns_ms,
-                    phase_timings.tcp_ms,
-                    phase_timings.tls_ms,
-                    phase_timings.ttfb_ms,
-                    phase_timings.total_ms
-                );
-
-                // Note: curl branch doesn't capture response headers or HTTP version in current implementation
+                let breakdown = phase_timings.breakdown(false);
+
+                // Note: curl branch doesn't capture the HTTP version in current implementation
                 // Setting http_version=None to avoid misleading diagnostics about version negotiation
-                let empty_headers = std::collections::HashMap::new();
                 let http_version = None; // Unknown version - curl implementation doesn't capture this
 
                 return Ok(OauthMasqueradeResult {
                     status: phase_timings.status,
                     duration_ms: phase_timings.total_ms,
                     breakdown,
-                    response_headers: empty_headers,
+                    response_headers: redact_response_headers(&phase_timings.headers),
                     http_version,
+                    connection: Some(phase_timings.connection.clone()),
                 });
             }
             Err(curl_error) => {
@@ -526,6 +527,7 @@ pub async fn run_probe(
         breakdown,
         response_headers: redacted_response_headers,
         http_version,
+        connection: None,
     })
 }
 
@@ -582,6 +584,7 @@ pub async fn run_probe(
         breakdown,
         response_headers: redacted_response_headers,
         http_version,
+        connection: None,
     })
 }
 
@@ -594,4 +597,3 @@ pub mod testing {
         TEST_HEADERS_FILE,
     };
 }
-
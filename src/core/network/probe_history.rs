@@ -0,0 +1,332 @@
+//! Probe history tagged with the ccstatus version that ran each probe
+//!
+//! Every executed probe appends one JSON line to `<state>.history.jsonl` next to
+//! the monitoring state file. After an update, `ccstatus net report
+//! --compare-versions` groups the records by version to show whether latency or
+//! classification shifted, which catches regressions in the probe path itself.
+//! `ccstatus history` summarizes any window of it, so P95 over weeks is
+//! available long after the rolling window forgot those samples.
+//!
+//! Retention follows `[network.history]`: records older than `retention_days`
+//! and all but the newest `max_records` are dropped when the file is rewritten.
+
+use crate::config::{HistoryConfig, NetworkConfig, SlaTarget};
+use crate::core::network::types::{NetworkError, NetworkStatus, ProbeMode, ProbeOutcome};
+use crate::core::state_location::StateLocation;
+use crate::core::stats::LatencySketch;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// File size below which the history is never compacted for its size
+pub const COMPACT_AT_BYTES: u64 = 1024 * 1024;
+
+/// Rough size of one serialized record, for the size that triggers compaction
+const RECORD_BYTES: u64 = 200;
+
+/// Age past `retention_days` at which the oldest record triggers a rewrite,
+/// so a history at its age limit is not rewritten on every probe
+const RETENTION_SLACK_SECS: i64 = 24 * 60 * 60;
+
+/// One executed probe
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProbeRecord {
+    /// Local ISO-8601 completion time
+    pub timestamp: String,
+    /// ccstatus version that executed the probe
+    pub version: String,
+    pub mode: String,
+    pub http_status: u16,
+    pub latency_ms: u32,
+    /// Resulting classification
+    pub status: NetworkStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_type: Option<String>,
+}
+
+impl ProbeRecord {
+    /// Record for a completed probe, tagged with the running binary's version
+    pub fn from_outcome(outcome: &ProbeOutcome) -> Self {
+        Self {
+            timestamp: outcome.timestamp_local.clone(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            mode: mode_name(outcome.mode).to_string(),
+            http_status: outcome.metrics.last_http_status,
+            latency_ms: outcome.metrics.latency_ms,
+            status: outcome.status.clone(),
+            error_type: outcome.metrics.error_type.clone(),
+        }
+    }
+
+    /// Completion time in Unix milliseconds; None for an unparseable timestamp
+    pub fn timestamp_millis(&self) -> Option<i64> {
+        chrono::DateTime::parse_from_rfc3339(&self.timestamp)
+            .ok()
+            .map(|at| at.timestamp_millis())
+    }
+}
+
+fn mode_name(mode: ProbeMode) -> &'static str {
+    match mode {
+        ProbeMode::Cold => "cold",
+        ProbeMode::Green => "green",
+        ProbeMode::Red => "red",
+    }
+}
+
+/// Append-only probe log
+pub struct ProbeHistory {
+    path: PathBuf,
+    retention: HistoryConfig,
+}
+
+impl ProbeHistory {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            retention: HistoryConfig::default(),
+        }
+    }
+
+    /// History next to `state_path`, e.g. `ccstatus-monitoring.history.jsonl`
+    /// (so every target partition keeps its own history)
+    pub fn for_state_path(state_path: &Path) -> Self {
+        Self::new(state_path.with_extension("history.jsonl"))
+    }
+
+    /// Retention applied when the file is compacted (`[network.history]`)
+    pub fn with_retention(mut self, retention: HistoryConfig) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append a record, then apply the retention once the file outgrows
+    /// `max_records` (at least `COMPACT_AT_BYTES`) or its oldest record expired
+    ///
+    /// Age is measured against the appended record's timestamp.
+    pub fn append(&self, record: &ProbeRecord) -> Result<(), NetworkError> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).map_err(|e| NetworkError::StateFileError(e.to_string()))?;
+        }
+
+        let line = serde_json::to_string(record)
+            .map_err(|e| NetworkError::StateFileError(e.to_string()))?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| NetworkError::StateFileError(e.to_string()))?;
+        writeln!(file, "{}", line).map_err(|e| NetworkError::StateFileError(e.to_string()))?;
+
+        let size = file.metadata().map(|meta| meta.len()).unwrap_or(0);
+        let compact_at = COMPACT_AT_BYTES.max(self.retention.max_records as u64 * RECORD_BYTES);
+        let now_ms = record.timestamp_millis();
+        let expired =
+            now_ms
+                .zip(self.oldest_timestamp_millis())
+                .is_some_and(|(now_ms, oldest_ms)| {
+                    oldest_ms < now_ms - (self.retention_secs() + RETENTION_SLACK_SECS) * 1000
+                });
+        if size > compact_at || expired {
+            let records = self.load();
+            self.rewrite(&self.retained(&records, now_ms))?;
+        }
+        Ok(())
+    }
+
+    fn retention_secs(&self) -> i64 {
+        self.retention.retention_days as i64 * 24 * 60 * 60
+    }
+
+    /// Records within the retention as of `now_ms` (unparseable timestamps are kept)
+    fn retained<'a>(
+        &self,
+        records: &'a [ProbeRecord],
+        now_ms: Option<i64>,
+    ) -> Vec<&'a ProbeRecord> {
+        let cutoff_ms = now_ms.map(|now_ms| now_ms - self.retention_secs() * 1000);
+        let fresh: Vec<&ProbeRecord> = records
+            .iter()
+            .filter(|record| {
+                cutoff_ms
+                    .zip(record.timestamp_millis())
+                    .is_none_or(|(cutoff_ms, at_ms)| at_ms >= cutoff_ms)
+            })
+            .collect();
+        let skip = fresh.len().saturating_sub(self.retention.max_records);
+        fresh[skip..].to_vec()
+    }
+
+    /// Timestamp of the first record, read without loading the file
+    fn oldest_timestamp_millis(&self) -> Option<i64> {
+        let mut line = String::new();
+        BufReader::new(File::open(&self.path).ok()?)
+            .read_line(&mut line)
+            .ok()?;
+        serde_json::from_str::<ProbeRecord>(&line)
+            .ok()?
+            .timestamp_millis()
+    }
+
+    /// All readable records, oldest first (corrupt lines are skipped)
+    pub fn load(&self) -> Vec<ProbeRecord> {
+        fs::read_to_string(&self.path)
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect()
+    }
+
+    fn rewrite(&self, records: &[&ProbeRecord]) -> Result<(), NetworkError> {
+        let content: String = records
+            .iter()
+            .filter_map(|record| serde_json::to_string(record).ok())
+            .map(|line| line + "\n")
+            .collect();
+        let tmp = self.path.with_extension("jsonl.tmp");
+        fs::write(&tmp, content).map_err(|e| NetworkError::StateFileError(e.to_string()))?;
+        fs::rename(&tmp, &self.path).map_err(|e| NetworkError::StateFileError(e.to_string()))
+    }
+}
+
+/// Aggregate probe results for one ccstatus version
+#[derive(Debug, Clone, PartialEq)]
+pub struct VersionSummary {
+    pub version: String,
+    pub probes: usize,
+    /// Median latency of HTTP 200 probes
+    pub p50_latency_ms: u32,
+    /// P95 latency of HTTP 200 probes
+    pub p95_latency_ms: u32,
+    pub healthy: usize,
+    pub degraded: usize,
+    pub error: usize,
+    pub first_seen: String,
+    pub last_seen: String,
+}
+
+impl VersionSummary {
+    /// Share of probes classified as error, in percent
+    pub fn error_rate(&self) -> f64 {
+        if self.probes == 0 {
+            0.0
+        } else {
+            self.error as f64 * 100.0 / self.probes as f64
+        }
+    }
+}
+
+/// Group records by version, in the order each version first appears
+pub fn summarize_by_version(records: &[ProbeRecord]) -> Vec<VersionSummary> {
+    let mut versions: Vec<&str> = Vec::new();
+    for record in records {
+        if !versions.contains(&record.version.as_str()) {
+            versions.push(&record.version);
+        }
+    }
+
+    versions
+        .into_iter()
+        .map(|version| {
+            let group: Vec<&ProbeRecord> =
+                records.iter().filter(|r| r.version == version).collect();
+            let latencies: LatencySketch = group
+                .iter()
+                .filter(|r| r.http_status == 200)
+                .map(|r| r.latency_ms)
+                .collect();
+            let count = |status: NetworkStatus| group.iter().filter(|r| r.status == status).count();
+
+            VersionSummary {
+                version: version.to_string(),
+                probes: group.len(),
+                p50_latency_ms: latencies.percentile(0.50),
+                p95_latency_ms: latencies.percentile(0.95),
+                healthy: count(NetworkStatus::Healthy),
+                degraded: count(NetworkStatus::Degraded),
+                error: count(NetworkStatus::Error),
+                first_seen: group
+                    .first()
+                    .map(|r| r.timestamp.clone())
+                    .unwrap_or_default(),
+                last_seen: group
+                    .last()
+                    .map(|r| r.timestamp.clone())
+                    .unwrap_or_default(),
+            }
+        })
+        .collect()
+}
+
+/// Probe history of the primary partition and every validly named target, in config order
+pub fn load_partition_histories(
+    config: &NetworkConfig,
+) -> Result<Vec<(String, Vec<ProbeRecord>)>, NetworkError> {
+    let location = StateLocation::from_config(config).ok_or(NetworkError::HomeDirNotFound)?;
+    let mut histories = vec![(
+        SlaTarget::PRIMARY.to_string(),
+        ProbeHistory::for_state_path(&location.monitoring_path()).load(),
+    )];
+    for target in &config.targets {
+        if !target.has_valid_name() {
+            continue;
+        }
+        let state_path = location.target_path(&target.name);
+        histories.push((
+            target.name.clone(),
+            ProbeHistory::for_state_path(&state_path).load(),
+        ));
+    }
+    Ok(histories)
+}
+
+/// Records completed within `from_ms..=to_ms`, oldest first
+pub fn records_between(records: &[ProbeRecord], from_ms: i64, to_ms: i64) -> Vec<&ProbeRecord> {
+    let mut within: Vec<(i64, &ProbeRecord)> = records
+        .iter()
+        .filter_map(|record| Some((record.timestamp_millis()?, record)))
+        .filter(|(at_ms, _)| (from_ms..=to_ms).contains(at_ms))
+        .collect();
+    within.sort_by_key(|&(at_ms, _)| at_ms);
+    within.into_iter().map(|(_, record)| record).collect()
+}
+
+/// Aggregate of the probes in a window, for `ccstatus history`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HistorySummary {
+    pub probes: usize,
+    /// Probes answered with HTTP 200, the ones the percentiles cover
+    pub successful: usize,
+    pub p50_latency_ms: u32,
+    pub p95_latency_ms: u32,
+    pub p99_latency_ms: u32,
+    pub healthy: usize,
+    pub degraded: usize,
+    pub error: usize,
+}
+
+/// Summarize `records` (any order)
+pub fn summarize(records: &[&ProbeRecord]) -> HistorySummary {
+    let latencies: LatencySketch = records
+        .iter()
+        .filter(|r| r.http_status == 200)
+        .map(|r| r.latency_ms)
+        .collect();
+    let count = |status: NetworkStatus| records.iter().filter(|r| r.status == status).count();
+
+    HistorySummary {
+        probes: records.len(),
+        successful: latencies.count() as usize,
+        p50_latency_ms: latencies.percentile(0.50),
+        p95_latency_ms: latencies.percentile(0.95),
+        p99_latency_ms: latencies.percentile(0.99),
+        healthy: count(NetworkStatus::Healthy),
+        degraded: count(NetworkStatus::Degraded),
+        error: count(NetworkStatus::Error),
+    }
+}
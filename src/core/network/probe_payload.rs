@@ -0,0 +1,241 @@
+//! Probe payload builder with a sanitization contract
+//!
+//! Every probe is a real API request, so its body must never carry workspace
+//! context (paths, usernames, hostnames, transcript content, env values). This
+//! module is the single place probe bodies are built, and every body passes
+//! through `validate_probe_payload()` before it is serialized:
+//!
+//! - `model` and `max_tokens` are those of the [`ProbeRequest`]: `PROBE_MODEL`
+//!   and 1 unless `[network.probe]` overrides them for the x-api-key flow
+//! - exactly one user message whose content is `PROBE_MESSAGE`
+//! - `system` (OAuth masquerade only, `oauth-probe` builds) holds the fixed Claude Code prompt
+//! - no other top-level keys besides an optional boolean `stream`
+//!
+//! Because every string is compared against a compile-time constant or a
+//! validated config value, nothing derived from the environment can reach the
+//! wire. In debug mode a SHA-256 checksum of the encoded body is logged so the
+//! payload can be verified.
+
+use crate::config::ProbeConfig;
+#[cfg(feature = "oauth-probe")]
+use crate::core::network::oauth_masquerade::CLAUDE_CODE_SYSTEM_PROMPT;
+use crate::core::network::proxy_health::build_messages_endpoint;
+use crate::core::network::types::NetworkError;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+/// Fixed model used for every probe
+pub const PROBE_MODEL: &str = "claude-3-5-haiku-20241022";
+
+/// Fixed user message used for every probe
+pub const PROBE_MESSAGE: &str = "Hi";
+
+/// Path probes are sent to unless `[network.probe]` sets one
+pub const PROBE_PATH: &str = "/v1/messages";
+
+/// Largest `max_tokens` a configured probe may request
+pub const MAX_PROBE_TOKENS: u32 = 16;
+
+/// Longest configured model id or path accepted
+const MAX_PROBE_FIELD_LEN: usize = 128;
+
+/// Top-level keys a probe payload may contain
+const ALLOWED_KEYS: &[&str] = &["model", "max_tokens", "messages", "system", "stream"];
+
+/// Probe payload shapes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProbePayloadKind {
+    /// Minimal payload for the x-api-key flow
+    ApiKey,
+    /// First-party-shaped payload with the Claude Code system prompt
+    #[cfg(feature = "oauth-probe")]
+    OauthMasquerade { stream: bool },
+}
+
+/// Model, token budget and endpoint path of x-api-key probes (`[network.probe]`)
+///
+/// Lets probes reach gateways that don't serve `/v1/messages` or lack the
+/// default model. The OAuth masquerade always keeps the defaults, since it
+/// must look like Claude Code's own request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProbeRequest {
+    pub model: String,
+    pub max_tokens: u32,
+    /// Absolute path appended to the base URL, e.g. `/v1/messages`
+    pub path: String,
+}
+
+impl Default for ProbeRequest {
+    fn default() -> Self {
+        Self {
+            model: PROBE_MODEL.to_string(),
+            max_tokens: 1,
+            path: PROBE_PATH.to_string(),
+        }
+    }
+}
+
+impl ProbeRequest {
+    /// Defaults overlaid with the set fields of `config`
+    ///
+    /// # Errors
+    ///
+    /// Returns `NetworkError::ValidationError` for a model id or path with
+    /// characters outside their usual alphabet, or `max_tokens` outside
+    /// 1..=`MAX_PROBE_TOKENS`.
+    pub fn from_config(config: &ProbeConfig) -> Result<Self, NetworkError> {
+        let invalid = |message: String| Err(NetworkError::ValidationError(message));
+        let mut request = Self::default();
+
+        if let Some(model) = &config.model {
+            let valid = !model.is_empty()
+                && model.len() <= MAX_PROBE_FIELD_LEN
+                && model
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "-._:@/".contains(c));
+            if !valid {
+                return invalid(format!("Invalid probe model '{}'", model));
+            }
+            request.model = model.clone();
+        }
+
+        if let Some(max_tokens) = config.max_tokens {
+            if !(1..=MAX_PROBE_TOKENS).contains(&max_tokens) {
+                return invalid(format!(
+                    "Probe max_tokens must be 1-{}, got {}",
+                    MAX_PROBE_TOKENS, max_tokens
+                ));
+            }
+            request.max_tokens = max_tokens;
+        }
+
+        if let Some(path) = &config.path {
+            let valid = path.starts_with('/')
+                && path.len() <= MAX_PROBE_FIELD_LEN
+                && !path.split('/').any(|segment| segment == "..")
+                && path
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "-._~/".contains(c));
+            if !valid {
+                return invalid(format!("Invalid probe path '{}'", path));
+            }
+            request.path = path.clone();
+        }
+
+        Ok(request)
+    }
+
+    /// Probe URL for `base_url`
+    ///
+    /// The default path keeps `build_messages_endpoint`'s handling of base
+    /// URLs already ending in `/v1`; a configured path is appended as is.
+    pub fn endpoint(&self, base_url: &str) -> String {
+        if self.path == PROBE_PATH {
+            build_messages_endpoint(base_url)
+        } else {
+            format!("{}{}", base_url.trim_end_matches('/'), self.path)
+        }
+    }
+}
+
+/// Build the probe payload for the given flow
+pub fn build_probe_payload(kind: ProbePayloadKind, request: &ProbeRequest) -> Value {
+    let payload = serde_json::json!({
+        "model": request.model,
+        "max_tokens": request.max_tokens,
+        "messages": [
+            {"role": "user", "content": PROBE_MESSAGE}
+        ]
+    });
+
+    match kind {
+        ProbePayloadKind::ApiKey => payload,
+        #[cfg(feature = "oauth-probe")]
+        ProbePayloadKind::OauthMasquerade { stream } => {
+            let mut payload = payload;
+            payload["system"] = serde_json::json!([
+                {"type": "text", "text": CLAUDE_CODE_SYSTEM_PROMPT}
+            ]);
+            if stream {
+                payload["stream"] = Value::Bool(true);
+            }
+            payload
+        }
+    }
+}
+
+/// Check a payload against the probe sanitization contract for `request`
+///
+/// # Errors
+///
+/// Returns `NetworkError::HttpError` naming the first violated rule.
+pub fn validate_probe_payload(payload: &Value, request: &ProbeRequest) -> Result<(), NetworkError> {
+    let violation = |rule: &str| Err(NetworkError::HttpError(format!("Probe payload {}", rule)));
+
+    let object = match payload.as_object() {
+        Some(object) => object,
+        None => return violation("must be a JSON object"),
+    };
+
+    if let Some(key) = object
+        .keys()
+        .find(|key| !ALLOWED_KEYS.contains(&key.as_str()))
+    {
+        return violation(&format!("contains disallowed key '{}'", key));
+    }
+
+    if object.get("model").and_then(Value::as_str) != Some(request.model.as_str()) {
+        return violation("must use the probe model");
+    }
+
+    if object.get("max_tokens").and_then(Value::as_u64) != Some(request.max_tokens as u64) {
+        return violation(&format!("must request max_tokens={}", request.max_tokens));
+    }
+
+    let fixed_message = serde_json::json!([{"role": "user", "content": PROBE_MESSAGE}]);
+    if object.get("messages") != Some(&fixed_message) {
+        return violation("must contain only the fixed probe message");
+    }
+
+    if let Some(system) = object.get("system") {
+        if !is_fixed_system_prompt(system) {
+            return violation("must contain only the fixed system prompt");
+        }
+    }
+
+    if let Some(stream) = object.get("stream") {
+        if !stream.is_boolean() {
+            return violation("stream flag must be a boolean");
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "oauth-probe")]
+fn is_fixed_system_prompt(system: &Value) -> bool {
+    *system == serde_json::json!([{"type": "text", "text": CLAUDE_CODE_SYSTEM_PROMPT}])
+}
+
+/// Without the OAuth probe no payload carries a system prompt
+#[cfg(not(feature = "oauth-probe"))]
+fn is_fixed_system_prompt(_system: &Value) -> bool {
+    false
+}
+
+/// Validate and serialize a probe payload into the request body
+pub fn encode_probe_payload(
+    payload: &Value,
+    request: &ProbeRequest,
+) -> Result<Vec<u8>, NetworkError> {
+    validate_probe_payload(payload, request)?;
+    serde_json::to_vec(payload)
+        .map_err(|e| NetworkError::HttpError(format!("Payload serialization failed: {}", e)))
+}
+
+/// SHA-256 hex digest of an encoded probe body (logged in debug mode for verification)
+pub fn payload_checksum(body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(body);
+    format!("{:x}", hasher.finalize())
+}
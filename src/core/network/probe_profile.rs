@@ -0,0 +1,160 @@
+//! Named probe profiles (`CCSTATUS_PROBE_PROFILE`)
+//!
+//! A profile bundles probe cadence, timeouts and payload strategy so they can be
+//! switched together, e.g. `aggressive` while chasing an incident:
+//!
+//! | profile        | GREEN every | RED every | RED timeout | GREEN/COLD timeout | extra checks          |
+//! |----------------|-------------|-----------|-------------|--------------------|-----------------------|
+//! | `standard`     | 300s        | 10s       | 2000ms      | p95+500, 2500-4000 | as configured         |
+//! | `conservative` | 900s        | 30s       | 3000ms      | p95+500, 3000-5000 | as configured         |
+//! | `aggressive`   | 60s         | 10s       | 4000ms      | p95+500, 3000-6000 | `/v1/models` + proxy  |
+//! | `minimal`      | 1800s       | off       | -           | p95+500, 2500-4000 | none                  |
+//!
+//! The chosen profile is pinned to the Claude Code session in the monitoring
+//! state, so it holds for the rest of that session; the next session starts
+//! back on `standard` unless the variable is still set.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Environment variable selecting the probe profile
+pub const PROBE_PROFILE_ENV: &str = crate::core::env::PROBE_PROFILE.name;
+
+/// Requests that accompany the messages probe
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadStrategy {
+    /// `network.aux_checks` and the `proxy_health` flag decide
+    Configured,
+    /// Always add the `/v1/models` check (the proxy check still obeys its kill switch)
+    Full,
+    /// The messages probe alone
+    ProbeOnly,
+}
+
+/// Named bundle of probe cadence, timeouts and payload strategy
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProbeProfile {
+    /// Built-in cadence and timeouts
+    #[default]
+    Standard,
+    /// Fewer, more patient probes
+    Conservative,
+    /// Frequent probes with generous timeouts and every auxiliary check
+    Aggressive,
+    /// Sparse GREEN probes only, no RED probing
+    Minimal,
+}
+
+impl ProbeProfile {
+    pub const ALL: [ProbeProfile; 4] = [
+        ProbeProfile::Standard,
+        ProbeProfile::Conservative,
+        ProbeProfile::Aggressive,
+        ProbeProfile::Minimal,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ProbeProfile::Standard => "standard",
+            ProbeProfile::Conservative => "conservative",
+            ProbeProfile::Aggressive => "aggressive",
+            ProbeProfile::Minimal => "minimal",
+        }
+    }
+
+    /// Seconds between GREEN windows
+    pub fn green_interval_secs(self) -> u64 {
+        match self {
+            ProbeProfile::Standard => 300,
+            ProbeProfile::Conservative => 900,
+            ProbeProfile::Aggressive => 60,
+            ProbeProfile::Minimal => 1800,
+        }
+    }
+
+    /// Seconds between RED windows (`None` = no RED probing)
+    pub fn red_interval_secs(self) -> Option<u64> {
+        match self {
+            ProbeProfile::Standard | ProbeProfile::Aggressive => Some(10),
+            ProbeProfile::Conservative => Some(30),
+            ProbeProfile::Minimal => None,
+        }
+    }
+
+    /// Fixed RED probe timeout
+    pub fn red_timeout_ms(self) -> u32 {
+        match self {
+            ProbeProfile::Standard | ProbeProfile::Minimal => 2000,
+            ProbeProfile::Conservative => 3000,
+            ProbeProfile::Aggressive => 4000,
+        }
+    }
+
+    /// GREEN/COLD timeout with fewer than 4 samples, then the clamp bounds for p95+500
+    pub fn adaptive_timeout_ms(self) -> (u32, u32, u32) {
+        match self {
+            ProbeProfile::Standard | ProbeProfile::Minimal => (3500, 2500, 4000),
+            ProbeProfile::Conservative => (4000, 3000, 5000),
+            ProbeProfile::Aggressive => (5000, 3000, 6000),
+        }
+    }
+
+    pub fn payload_strategy(self) -> PayloadStrategy {
+        match self {
+            ProbeProfile::Standard | ProbeProfile::Conservative => PayloadStrategy::Configured,
+            ProbeProfile::Aggressive => PayloadStrategy::Full,
+            ProbeProfile::Minimal => PayloadStrategy::ProbeOnly,
+        }
+    }
+
+    /// Profile for this stdin event and whether it needs pinning to the session
+    ///
+    /// A valid `env_value` wins; otherwise a profile pinned to `session_id` is
+    /// kept, and anything else falls back to `standard`.
+    pub fn resolve(
+        env_value: Option<&str>,
+        pinned: Option<&SessionProfile>,
+        session_id: &str,
+    ) -> (ProbeProfile, bool) {
+        let pinned = pinned.filter(|pinned| pinned.session_id == session_id);
+        match env_value.and_then(|value| value.parse::<ProbeProfile>().ok()) {
+            Some(profile) => (
+                profile,
+                pinned.map(|pinned| pinned.profile) != Some(profile),
+            ),
+            None => (
+                pinned.map(|pinned| pinned.profile).unwrap_or_default(),
+                false,
+            ),
+        }
+    }
+}
+
+impl fmt::Display for ProbeProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ProbeProfile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_ascii_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|profile| profile.as_str() == name)
+            .ok_or_else(|| format!("unknown probe profile '{}'", s.trim()))
+    }
+}
+
+/// Profile pinned to one Claude Code session in the monitoring state
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionProfile {
+    pub profile: ProbeProfile,
+    pub session_id: String,
+    /// When the profile was pinned (local ISO-8601)
+    pub since: String,
+}
@@ -0,0 +1,315 @@
+//! Monitoring state as Prometheus metrics (`ccstatus export --prometheus`)
+//!
+//! Every state partition becomes one series per metric, labelled
+//! `target="primary"` for the default probe and with the target name for each
+//! `[[network.targets]]` entry. The numeric status matches the exit codes of
+//! `ccstatus net check` (0 healthy, 1 degraded, 2 error, 3 unknown).
+//!
+//! With the `prometheus-listener` feature, [`serve`] answers `GET /metrics`
+//! from a plain socket, re-reading the state files on every scrape, and
+//! `GET /api/timeseries` for Grafana (see [`crate::core::network::timeseries`]),
+//! until the shared [`crate::core::shutdown::ShutdownCoordinator`] asks it to stop.
+
+use crate::config::{NetworkConfig, SlaTarget};
+use crate::core::network::http_monitor::HttpMonitor;
+use crate::core::network::proxy_health::ProxyHealthLevel;
+#[cfg(feature = "prometheus-listener")]
+use crate::core::network::timeseries::TimeseriesQuery;
+use crate::core::network::types::{MonitoringSnapshot, NetworkError};
+#[cfg(feature = "prometheus-listener")]
+use crate::core::shutdown::ShutdownCoordinator;
+use crate::core::state_location::StateLocation;
+use std::fmt::Write;
+
+/// `Content-Type` of the text exposition format
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Load the primary partition and every validly named target, in config order
+pub async fn load_partitions(
+    config: &NetworkConfig,
+) -> Result<Vec<(String, MonitoringSnapshot)>, NetworkError> {
+    let location = StateLocation::from_config(config).ok_or(NetworkError::HomeDirNotFound)?;
+    let mut partitions = vec![(
+        SlaTarget::PRIMARY.to_string(),
+        HttpMonitor::new(Some(location.monitoring_path()))?
+            .load_state()
+            .await?,
+    )];
+    for target in &config.targets {
+        if !target.has_valid_name() {
+            continue;
+        }
+        let state_path = location.target_path(&target.name);
+        let state = HttpMonitor::new(Some(state_path))?.load_state().await?;
+        partitions.push((target.name.clone(), state));
+    }
+    Ok(partitions)
+}
+
+/// Render partitions in the Prometheus text exposition format
+pub fn render_metrics(partitions: &[(String, MonitoringSnapshot)]) -> String {
+    let mut out = String::new();
+
+    family(
+        &mut out,
+        "ccstatus_status",
+        "gauge",
+        "Network status: 0 healthy, 1 degraded, 2 error, 3 unknown",
+        partitions,
+        |state| Some(state.status.exit_code() as f64),
+    );
+    let _ = writeln!(
+        out,
+        "# HELP ccstatus_status_info Network status as a label (always 1)"
+    );
+    let _ = writeln!(out, "# TYPE ccstatus_status_info gauge");
+    for (target, state) in partitions {
+        let _ = writeln!(
+            out,
+            "ccstatus_status_info{{target=\"{}\",status=\"{}\"}} 1",
+            escape_label(target),
+            state.status.as_str()
+        );
+    }
+    family(
+        &mut out,
+        "ccstatus_latency_ms",
+        "gauge",
+        "Total latency of the last probe in milliseconds",
+        partitions,
+        |state| Some(state.network.latency_ms as f64),
+    );
+    family(
+        &mut out,
+        "ccstatus_p95_latency_ms",
+        "gauge",
+        "P95 latency of the rolling window in milliseconds",
+        partitions,
+        |state| Some(state.network.p95_latency_ms as f64),
+    );
+    family(
+        &mut out,
+        "ccstatus_rolling_samples",
+        "gauge",
+        "Latency samples in the rolling window",
+        partitions,
+        |state| Some(state.network.rolling_totals.len() as f64),
+    );
+    family(
+        &mut out,
+        "ccstatus_last_http_status",
+        "gauge",
+        "HTTP status of the last probe (0 = no response)",
+        partitions,
+        |state| Some(state.network.last_http_status as f64),
+    );
+    family(
+        &mut out,
+        "ccstatus_probes_total",
+        "counter",
+        "Probes sent",
+        partitions,
+        |state| Some(state.monitoring_state.ledger.probes as f64),
+    );
+    family(
+        &mut out,
+        "ccstatus_probe_errors_total",
+        "counter",
+        "Probes that did not return HTTP 200",
+        partitions,
+        |state| Some(state.monitoring_state.ledger.errors as f64),
+    );
+    family(
+        &mut out,
+        "ccstatus_consecutive_failures",
+        "gauge",
+        "Probes in a row that did not return HTTP 200",
+        partitions,
+        |state| Some(state.monitoring_state.consecutive_failures as f64),
+    );
+    family(
+        &mut out,
+        "ccstatus_proxy_health",
+        "gauge",
+        "Proxy health check: 0 healthy, 1 degraded, 2 bad, 3 unknown (absent without a proxy)",
+        partitions,
+        |state| {
+            state
+                .network
+                .get_proxy_health_level()
+                .map(|level| match level {
+                    ProxyHealthLevel::Healthy => 0.0,
+                    ProxyHealthLevel::Degraded => 1.0,
+                    ProxyHealthLevel::Bad => 2.0,
+                    ProxyHealthLevel::Unknown => 3.0,
+                })
+        },
+    );
+    family(
+        &mut out,
+        "ccstatus_health_score",
+        "gauge",
+        "Combined endpoint health score (0-100)",
+        partitions,
+        |state| state.health_score.map(f64::from),
+    );
+    family(
+        &mut out,
+        "ccstatus_updated_timestamp_seconds",
+        "gauge",
+        "When the state was last written (Unix seconds)",
+        partitions,
+        |state| {
+            chrono::DateTime::parse_from_rfc3339(&state.timestamp)
+                .ok()
+                .map(|at| at.timestamp() as f64)
+        },
+    );
+
+    out
+}
+
+/// One metric family; partitions without a value get no sample
+fn family(
+    out: &mut String,
+    name: &str,
+    kind: &str,
+    help: &str,
+    partitions: &[(String, MonitoringSnapshot)],
+    value: impl Fn(&MonitoringSnapshot) -> Option<f64>,
+) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+    for (target, state) in partitions {
+        if let Some(value) = value(state) {
+            let _ = writeln!(
+                out,
+                "{}{{target=\"{}\"}} {}",
+                name,
+                escape_label(target),
+                value
+            );
+        }
+    }
+}
+
+/// Escape a label value: backslash, double quote and newline
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// How often [`serve`] checks for a shutdown request while no client connects
+#[cfg(feature = "prometheus-listener")]
+pub const ACCEPT_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(200);
+
+/// Serve `GET /metrics` and `GET /api/timeseries` on `listener` until shutdown is
+/// requested or accepting fails, one connection at a time
+///
+/// `render` runs per scrape and `timeseries` per valid time series query; their
+/// errors become a `500` with the message as body. A malformed query is a `400`.
+/// The listener is switched to non-blocking so `shutdown` is seen within
+/// [`ACCEPT_POLL_INTERVAL`]; a scrape in progress is answered first.
+#[cfg(feature = "prometheus-listener")]
+pub fn serve(
+    listener: std::net::TcpListener,
+    shutdown: &ShutdownCoordinator,
+    render: impl Fn() -> Result<String, String>,
+    timeseries: impl Fn(&TimeseriesQuery) -> Result<String, String>,
+) -> std::io::Result<()> {
+    use std::io::ErrorKind;
+
+    listener.set_nonblocking(true)?;
+    while !shutdown.is_requested() {
+        let stream = match listener.accept() {
+            Ok((stream, _)) => stream,
+            Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                std::thread::sleep(ACCEPT_POLL_INTERVAL);
+                continue;
+            }
+            // A client that went away before accept is not a reason to stop serving
+            Err(e) if e.kind() == ErrorKind::ConnectionAborted => continue,
+            Err(e) => return Err(e),
+        };
+        answer(stream, &render, &timeseries);
+    }
+    Ok(())
+}
+
+/// Read one request from `stream` and write the response
+#[cfg(feature = "prometheus-listener")]
+fn answer(
+    mut stream: std::net::TcpStream,
+    render: &impl Fn() -> Result<String, String>,
+    timeseries: &impl Fn(&TimeseriesQuery) -> Result<String, String>,
+) {
+    use std::io::{BufRead, BufReader};
+    use std::time::Duration;
+
+    // Accepted sockets may inherit non-blocking mode from the listener
+    let _ = stream.set_nonblocking(false);
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(5)));
+    let _ = stream.set_write_timeout(Some(Duration::from_secs(5)));
+
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+    // Drain the headers so closing the socket does not reset the connection
+    let mut header = String::new();
+    while reader.read_line(&mut header).is_ok_and(|read| read > 2) {
+        header.clear();
+    }
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+    let (path, query) = path.split_once('?').unwrap_or((path, ""));
+
+    let (status, content_type, body) = match (method, path) {
+        ("GET", "/metrics") => match render() {
+            Ok(body) => ("200 OK", PROMETHEUS_CONTENT_TYPE, body),
+            Err(e) => ("500 Internal Server Error", "text/plain", e),
+        },
+        ("GET", "/api/timeseries") => match TimeseriesQuery::parse(query) {
+            Ok(query) => match timeseries(&query) {
+                Ok(body) => ("200 OK", "application/json", body),
+                Err(e) => ("500 Internal Server Error", "text/plain", e),
+            },
+            Err(e) => ("400 Bad Request", "text/plain", e + "\n"),
+        },
+        ("GET", "/") => (
+            "200 OK",
+            "text/plain",
+            "ccstatus exporter: metrics at /metrics, time series at /api/timeseries\n".to_string(),
+        ),
+        ("GET", _) => ("404 Not Found", "text/plain", "not found\n".to_string()),
+        _ => (
+            "405 Method Not Allowed",
+            "text/plain",
+            "method not allowed\n".to_string(),
+        ),
+    };
+    let _ = write_response(&mut stream, status, content_type, &body);
+}
+
+#[cfg(feature = "prometheus-listener")]
+fn write_response(
+    stream: &mut std::net::TcpStream,
+    status: &str,
+    content_type: &str,
+    body: &str,
+) -> std::io::Result<()> {
+    use std::io::Write as _;
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
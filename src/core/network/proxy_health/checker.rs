@@ -9,7 +9,10 @@
 use crate::core::network::proxy_health::{
     client::{HealthCheckClient, HealthResponse},
     config::{ProxyHealthLevel, ProxyHealthOptions},
-    parsing::{detect_cloudflare_challenge, parse_health_response},
+    parsing::{
+        bad_health_guidance, body_excerpt, detect_cloudflare_challenge, parse_health_response,
+        BODY_EXCERPT_CHARS,
+    },
     url::{build_path_health_url, build_root_health_url, extract_host, is_official_base_url},
 };
 use crate::core::network::types::ProxyHealthDetail;
@@ -52,6 +55,16 @@ fn determine_bad_health_reason(response_body: &[u8]) -> String {
     }
 }
 
+/// Keep a body excerpt and guidance for a Bad response so `ccstatus net status` can explain it
+fn annotate_bad_response(detail: &mut ProxyHealthDetail, response: &HealthResponse) {
+    detail.body_excerpt = body_excerpt(&response.body, BODY_EXCERPT_CHARS);
+    detail.guidance = Some(bad_health_guidance(
+        response.status_code,
+        &response.headers,
+        &response.body,
+    ));
+}
+
 /// Build ProxyHealthOutcome with optional response data
 fn build_outcome_with_response(
     level: Option<ProxyHealthLevel>,
@@ -141,6 +154,9 @@ pub async fn assess_proxy_health(
         checked_at,
         response_time_ms: 0,
         reason: None,
+        body_excerpt: None,
+        guidance: None,
+        certificate_warning: None,
     };
 
     let mut had_network_error = false;
@@ -289,7 +305,7 @@ async fn handle_cloudflare_challenge(
     detail.reason = Some("cloudflare_challenge".to_string());
 
     // Wait 300-500ms for any Set-Cookie to process
-    tokio::time::sleep(std::time::Duration::from_millis(400)).await;
+    crate::runtime::sleep(std::time::Duration::from_millis(400)).await;
 
     // Retry once with same URL
     match client.get_health(url.to_string(), options.timeout_ms).await {
@@ -308,6 +324,7 @@ async fn handle_cloudflare_challenge(
                 match &level {
                     Some(ProxyHealthLevel::Bad) => {
                         detail.reason = Some(determine_bad_health_reason(&retry_response.body));
+                        annotate_bad_response(detail, &retry_response);
                     }
                     Some(_) => {
                         // Healthy/Degraded - successful parsing
@@ -373,6 +390,7 @@ async fn handle_response(
             match &level {
                 Some(ProxyHealthLevel::Bad) => {
                     detail.reason = Some(determine_bad_health_reason(&response.body));
+                    annotate_bad_response(detail, &response);
                 }
                 Some(_) => {
                     // Healthy/Degraded - successful parsing
@@ -419,6 +437,7 @@ async fn handle_response(
             // Server errors - proxy exists but unhealthy (Bad level)
             detail.success_method = Some(method.to_string());
             detail.reason = Some("server_error".to_string());
+            annotate_bad_response(detail, &response);
 
             Ok(Some(build_outcome_with_response(
                 Some(ProxyHealthLevel::Bad),
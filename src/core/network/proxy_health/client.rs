@@ -3,10 +3,14 @@
 //! Provides HTTP client abstraction specialized for proxy health check operations
 //! with GET method, response body access, and redirect control.
 
+use crate::core::network::http_monitor::TransportPolicy;
+use crate::core::network::ip_family::IpFamily;
 use crate::core::network::types::NetworkError;
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
+#[cfg(feature = "network-monitoring")]
+use crate::core::network::http_monitor::{isahc_ip_family, isahc_proxy, isahc_tls};
 #[cfg(feature = "network-monitoring")]
 use isahc::config::{Configurable, RedirectPolicy};
 #[cfg(feature = "network-monitoring")]
@@ -49,29 +53,92 @@ pub trait HealthCheckClient: Send + Sync {
     /// * Must not follow redirects (treat 3xx as error)
     /// * Must return complete response body for JSON validation
     async fn get_health(&self, url: String, timeout_ms: u32) -> Result<HealthResponse, String>;
+
+    /// [`Self::get_health`] over one address family (`network.ip_family = "compare"`);
+    /// clients that cannot choose use their default
+    async fn get_health_over(
+        &self,
+        url: String,
+        _family: IpFamily,
+        timeout_ms: u32,
+    ) -> Result<HealthResponse, String> {
+        self.get_health(url, timeout_ms).await
+    }
+
+    /// Send later checks as `transport` says; clients without a network ignore it
+    fn set_transport(&mut self, _transport: &TransportPolicy) {}
 }
 
 /// Production health check client implementation using isahc with GET method
 #[cfg(feature = "network-monitoring")]
 pub struct IsahcHealthCheckClient {
     client: HttpClient,
+    transport: TransportPolicy,
 }
 
 #[cfg(feature = "network-monitoring")]
 #[async_trait::async_trait]
 impl HealthCheckClient for IsahcHealthCheckClient {
     async fn get_health(&self, url: String, timeout_ms: u32) -> Result<HealthResponse, String> {
+        self.get(url, None, timeout_ms).await
+    }
+
+    async fn get_health_over(
+        &self,
+        url: String,
+        family: IpFamily,
+        timeout_ms: u32,
+    ) -> Result<HealthResponse, String> {
+        self.get(url, Some(family), timeout_ms).await
+    }
+
+    fn set_transport(&mut self, transport: &TransportPolicy) {
+        self.transport = transport.clone();
+    }
+}
+
+#[cfg(feature = "network-monitoring")]
+impl IsahcHealthCheckClient {
+    pub fn new() -> Result<Self, NetworkError> {
+        let client = HttpClient::builder()
+            .redirect_policy(RedirectPolicy::None) // Global redirect policy
+            .cookies() // Enable in-memory cookies for session continuity
+            .build()
+            .map_err(|e| {
+                NetworkError::HttpError(format!("Failed to create health check client: {}", e))
+            })?;
+        Ok(Self {
+            client,
+            transport: TransportPolicy::default(),
+        })
+    }
+
+    /// GET `url`, over `family` when given
+    async fn get(
+        &self,
+        url: String,
+        family: Option<IpFamily>,
+        timeout_ms: u32,
+    ) -> Result<HealthResponse, String> {
         let start = Instant::now();
 
-        let request = Request::get(&url)
-            .timeout(Duration::from_millis(timeout_ms as u64))
-            .redirect_policy(RedirectPolicy::None) // Critical: Don't follow redirects
-            .header("User-Agent", "claude-cli/1.0.93 (external, cli)")
-            .header("Accept", "application/json")
-            .header("Accept-Encoding", "gzip, deflate, br") // Bot-fight mitigation
-            .header("Accept-Language", "en-US,en;q=0.9") // Bot-fight mitigation
-            .body(Vec::new()) // Empty body for GET request
-            .map_err(|e| format!("Health check request creation failed: {}", e))?;
+        let request = isahc_tls(
+            isahc_ip_family(
+                Request::get(&url)
+                    .timeout(Duration::from_millis(timeout_ms as u64))
+                    .proxy(isahc_proxy(&self.transport.proxy, &url)?),
+                family.or(self.transport.ip_family),
+            ),
+            &self.transport.tls,
+            &url,
+        )?
+        .redirect_policy(RedirectPolicy::None) // Critical: Don't follow redirects
+        .header("User-Agent", "claude-cli/1.0.93 (external, cli)")
+        .header("Accept", "application/json")
+        .header("Accept-Encoding", "gzip, deflate, br") // Bot-fight mitigation
+        .header("Accept-Language", "en-US,en;q=0.9") // Bot-fight mitigation
+        .body(Vec::new()) // Empty body for GET request
+        .map_err(|e| format!("Health check request creation failed: {}", e))?;
 
         let mut response = self
             .client
@@ -107,14 +174,19 @@ impl HealthCheckClient for IsahcHealthCheckClient {
 }
 
 #[cfg(feature = "timings-curl")]
-use crate::core::network::http_monitor::PhaseTimings;
+use crate::core::network::http_monitor::{
+    curl_connection_info, set_curl_ip_family, set_curl_proxy, set_curl_tls, PhaseTimings,
+};
 
 /// Curl-based GET runner for enhanced proxy health timing
 ///
 /// Provides detailed phase timings (DNS, TCP, TLS, TTFB, Total) using curl
 /// with bot-fight mitigation headers and HTTP/2 support.
 #[cfg(feature = "timings-curl")]
-pub struct CurlGetRunner;
+#[derive(Default)]
+pub struct CurlGetRunner {
+    transport: TransportPolicy,
+}
 
 #[cfg(feature = "timings-curl")]
 impl CurlGetRunner {
@@ -139,7 +211,8 @@ impl CurlGetRunner {
         timeout_ms: u32,
     ) -> Result<(HealthResponse, PhaseTimings), String> {
         let url = url.to_string();
-        let result = tokio::task::spawn_blocking(
+        let transport = self.transport.clone();
+        let result = crate::runtime::spawn_blocking(
             move || -> Result<(HealthResponse, PhaseTimings), String> {
                 let mut handle = curl::easy::Easy::new();
 
@@ -153,6 +226,13 @@ impl CurlGetRunner {
                 handle
                     .timeout(std::time::Duration::from_millis(timeout_ms as u64))
                     .map_err(|e| format!("Timeout set failed: {}", e))?;
+                set_curl_proxy(&mut handle, &transport.proxy, &url)?;
+                set_curl_tls(&mut handle, &transport.tls)?;
+                set_curl_ip_family(&mut handle, transport.ip_family)?;
+                #[cfg(feature = "tls-parity")]
+                crate::core::network::tls_profile::apply_claude_code_tls_profile(&mut handle)
+                    .map_err(|e| format!("TLS profile failed: {}", e))?;
+                #[cfg(not(feature = "tls-parity"))]
                 handle
                     .http_version(curl::easy::HttpVersion::V2TLS)
                     .map_err(|e| format!("HTTP/2 version failed: {}", e))?;
@@ -271,6 +351,9 @@ impl CurlGetRunner {
                     ttfb_ms,
                     total_ms,
                     total_ttfb_ms: dns_ms + tcp_ms + tls_ms + ttfb_ms,
+                    set_cookies: Vec::new(),
+                    headers: health_response.headers.clone(),
+                    connection: curl_connection_info(&mut handle),
                 };
 
                 Ok((health_response, phase_timings))
@@ -305,13 +388,17 @@ impl HealthCheckClient for CurlHealthCheckClient {
 
         Ok(health_response)
     }
+
+    fn set_transport(&mut self, transport: &TransportPolicy) {
+        self.runner.transport = transport.clone();
+    }
 }
 
 #[cfg(all(feature = "network-monitoring", feature = "timings-curl"))]
 impl CurlHealthCheckClient {
     pub fn new() -> Result<Self, NetworkError> {
         Ok(Self {
-            runner: CurlGetRunner,
+            runner: CurlGetRunner::default(),
         })
     }
 
@@ -328,20 +415,6 @@ impl CurlHealthCheckClient {
     }
 }
 
-#[cfg(feature = "network-monitoring")]
-impl IsahcHealthCheckClient {
-    pub fn new() -> Result<Self, NetworkError> {
-        let client = HttpClient::builder()
-            .redirect_policy(RedirectPolicy::None) // Global redirect policy
-            .cookies() // Enable in-memory cookies for session continuity
-            .build()
-            .map_err(|e| {
-                NetworkError::HttpError(format!("Failed to create health check client: {}", e))
-            })?;
-        Ok(Self { client })
-    }
-}
-
 /// Mock health check client implementation when network-monitoring feature is disabled
 #[cfg(not(feature = "network-monitoring"))]
 #[derive(Default)]
@@ -1,7 +1,13 @@
 //! Proxy Health Configuration and Data Types
 
+use crate::core::network::types::NetworkError;
 use serde::{Deserialize, Serialize};
 
+/// Shortest per-request health check timeout accepted by [`ProxyHealthOptionsBuilder`]
+pub const MIN_HEALTH_TIMEOUT_MS: u32 = 100;
+/// Longest per-request health check timeout accepted by [`ProxyHealthOptionsBuilder`]
+pub const MAX_HEALTH_TIMEOUT_MS: u32 = 10_000;
+
 /// Tri-state proxy health levels for enhanced status granularity
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ProxyHealthLevel {
@@ -56,6 +62,13 @@ impl Default for ProxyHealthOptions {
 }
 
 impl ProxyHealthOptions {
+    /// Start a validated builder from the default (compatible) configuration
+    pub fn builder() -> ProxyHealthOptionsBuilder {
+        ProxyHealthOptionsBuilder {
+            options: Self::default(),
+        }
+    }
+
     /// Create default configuration for backward compatibility
     pub fn compatible() -> Self {
         Self::default()
@@ -81,3 +94,43 @@ impl ProxyHealthOptions {
         }
     }
 }
+
+/// Builder for [`ProxyHealthOptions`]
+#[derive(Debug, Clone)]
+pub struct ProxyHealthOptionsBuilder {
+    options: ProxyHealthOptions,
+}
+
+impl ProxyHealthOptionsBuilder {
+    pub fn with_root_urls(mut self, use_root_urls: bool) -> Self {
+        self.options.use_root_urls = use_root_urls;
+        self
+    }
+
+    pub fn with_fallback(mut self, try_fallback: bool) -> Self {
+        self.options.try_fallback = try_fallback;
+        self
+    }
+
+    pub fn with_redirect_once(mut self, follow_redirect_once: bool) -> Self {
+        self.options.follow_redirect_once = follow_redirect_once;
+        self
+    }
+
+    pub fn with_timeout_ms(mut self, timeout_ms: u32) -> Self {
+        self.options.timeout_ms = timeout_ms;
+        self
+    }
+
+    /// Validate and build: `timeout_ms` must lie within
+    /// `MIN_HEALTH_TIMEOUT_MS..=MAX_HEALTH_TIMEOUT_MS`
+    pub fn build(self) -> Result<ProxyHealthOptions, NetworkError> {
+        if !(MIN_HEALTH_TIMEOUT_MS..=MAX_HEALTH_TIMEOUT_MS).contains(&self.options.timeout_ms) {
+            return Err(NetworkError::ValidationError(format!(
+                "timeout_ms {} is outside {}..={}",
+                self.options.timeout_ms, MIN_HEALTH_TIMEOUT_MS, MAX_HEALTH_TIMEOUT_MS
+            )));
+        }
+        Ok(self.options)
+    }
+}
@@ -15,11 +15,11 @@ pub mod url;
 // Re-export public API
 pub use checker::{assess_proxy_health, ProxyHealthError, ProxyHealthOutcome};
 pub use client::{HealthCheckClient, HealthResponse};
-pub use config::{ProxyHealthLevel, ProxyHealthOptions};
+pub use config::{ProxyHealthLevel, ProxyHealthOptions, ProxyHealthOptionsBuilder};
 pub use parsing::{parse_health_response, validate_health_json};
 pub use url::{
-    build_messages_endpoint, build_path_health_url, build_root_health_url, is_official_base_url,
-    normalize_base_url,
+    build_messages_endpoint, build_models_endpoint, build_path_health_url, build_root_health_url,
+    is_official_base_url, normalize_base_url,
 };
 // ProxyHealthDetail is exported from types.rs to avoid conflicts
 pub use crate::core::network::types::ProxyHealthDetail;
@@ -4,8 +4,13 @@
 //! Supports various JSON health schemas commonly used by proxy services.
 
 use crate::core::network::proxy_health::config::ProxyHealthLevel;
+use regex::Regex;
 use serde_json::Value;
 use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Longest response excerpt kept in `ProxyHealthDetail`, in characters
+pub const BODY_EXCERPT_CHARS: usize = 160;
 
 /// Parse health check response body to determine proxy health level
 ///
@@ -246,3 +251,96 @@ pub fn detect_cloudflare_challenge(
 
     false
 }
+
+/// Single-line, sanitized excerpt of a response body for `ProxyHealthDetail`
+///
+/// Scripts, styles and HTML tags are dropped, whitespace collapsed and anything
+/// credential-like redacted before truncating to `max_chars` (marked with `…`).
+///
+/// # Returns
+/// * `Some(excerpt)` - Body had readable text
+/// * `None` - Body was empty or markup only
+pub fn body_excerpt(body: &[u8], max_chars: usize) -> Option<String> {
+    static MARKUP: OnceLock<Regex> = OnceLock::new();
+    static SECRETS: OnceLock<Regex> = OnceLock::new();
+    let markup = MARKUP.get_or_init(|| {
+        Regex::new(r"(?is)<script\b.*?</script>|<style\b.*?</style>|<!--.*?-->|<[^>]*>")
+            .expect("valid markup pattern")
+    });
+    let secrets = SECRETS.get_or_init(|| {
+        Regex::new(
+            r#"(?i)\b(bearer|token|api[_-]?key|secret|password)(["':=\s]+)[^\s"',;&<]+|\bsk-[A-Za-z0-9_-]+|[A-Za-z0-9_-]{32,}"#,
+        )
+        .expect("valid secret pattern")
+    });
+
+    let text = String::from_utf8_lossy(body);
+    let text = markup.replace_all(&text, " ");
+    let text = secrets.replace_all(&text, |caps: &regex::Captures| match caps.get(1) {
+        Some(key) => format!("{}{}[REDACTED]", key.as_str(), &caps[2]),
+        None => "[REDACTED]".to_string(),
+    });
+    let text = text
+        .split_whitespace()
+        .map(|word| word.chars().filter(|c| !c.is_control()).collect::<String>())
+        .collect::<Vec<_>>()
+        .join(" ");
+    if text.is_empty() {
+        return None;
+    }
+
+    if text.chars().count() <= max_chars {
+        return Some(text);
+    }
+    let mut excerpt: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+    excerpt.push('…');
+    Some(excerpt)
+}
+
+/// What a user can do about a Bad health response, from status code, content type and body
+///
+/// Used by `ccstatus net status`, e.g. "proxy returned an HTML login page — check VPN".
+pub fn bad_health_guidance(
+    status_code: u16,
+    headers: &HashMap<String, String>,
+    body: &[u8],
+) -> String {
+    let content_type = headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case("content-type"))
+        .map(|(_, value)| value.to_ascii_lowercase())
+        .unwrap_or_default();
+    let body_lower = String::from_utf8_lossy(body).to_lowercase();
+    let is_html = content_type.contains("html")
+        || body_lower.trim_start().starts_with("<!doctype html")
+        || body_lower.trim_start().starts_with("<html");
+
+    if is_html {
+        let login_markers = [
+            "login", "log in", "sign in", "signin", "password", "captive",
+        ];
+        return if login_markers
+            .iter()
+            .any(|marker| body_lower.contains(marker))
+        {
+            "proxy returned an HTML login page — check VPN or network sign-in".to_string()
+        } else {
+            "proxy returned an HTML page instead of JSON — check the base URL and any gateway in between"
+                .to_string()
+        };
+    }
+
+    match status_code {
+        502 | 504 => {
+            "proxy could not reach its upstream — check the relay's upstream API".to_string()
+        }
+        503 => "proxy is unavailable — it may be restarting or overloaded".to_string(),
+        500..=599 => {
+            "proxy reported a server error — check the relay's logs or status page".to_string()
+        }
+        _ if serde_json::from_slice::<Value>(body).is_err() => {
+            "health endpoint did not return JSON — check the health endpoint path".to_string()
+        }
+        _ => "health endpoint reported an error status — check the relay's status page".to_string(),
+    }
+}
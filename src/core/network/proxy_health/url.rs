@@ -144,3 +144,18 @@ pub fn build_messages_endpoint(base_url: &str) -> String {
         format!("{}/v1/messages", normalized)
     }
 }
+
+/// Build models API endpoint (auxiliary health check) with the same normalization
+///
+/// # Examples
+/// - `https://api.anthropic.com` → `https://api.anthropic.com/v1/models`
+/// - `https://proxy.com/api/v1/` → `https://proxy.com/api/v1/models`
+pub fn build_models_endpoint(base_url: &str) -> String {
+    let normalized = normalize_base_url(base_url);
+
+    if normalized.ends_with("/v1") || normalized.ends_with("/api/v1") {
+        format!("{}/models", normalized)
+    } else {
+        format!("{}/v1/models", normalized)
+    }
+}
@@ -0,0 +1,233 @@
+//! Per-endpoint SLA tracking (`[[network.sla]]`)
+//!
+//! Proxy customers want to hold their vendor to a service level. Every probe
+//! of an endpoint with a declared target is tallied into a monthly ledger at
+//! `~/.claude/ccstatus/sla.json`: probe and success counts plus a
+//! [`LatencySketch`] of successful probes, so a month of probes costs a few
+//! kilobytes and P95 is exact for small months and within
+//! `SKETCH_RELATIVE_ACCURACY` after. The last `MAX_MONTHS` months are kept;
+//! `ccstatus net sla` reports compliance for any of them.
+//!
+//! Healthy and degraded probes count as available, error probes as down.
+//! Unknown (no credentials) and policy-blocked probes never reached the
+//! vendor and are not counted. Months are UTC calendar months.
+
+use crate::config::SlaTarget;
+use crate::core::network::cookie_jar::unix_now_secs;
+use crate::core::network::status_hooks::{StatusChange, StatusHook};
+use crate::core::network::types::{NetworkError, NetworkStatus};
+use crate::core::recovery;
+use crate::core::stats::LatencySketch;
+use crate::runtime;
+use chrono::{DateTime, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Months kept per endpoint (a year plus the current month)
+pub const MAX_MONTHS: usize = 13;
+
+/// UTC calendar month (`YYYY-MM`) of a Unix timestamp
+pub fn month_of(at: i64) -> String {
+    DateTime::from_timestamp(at, 0)
+        .unwrap_or_default()
+        .format("%Y-%m")
+        .to_string()
+}
+
+/// Normalize a `YYYY-MM` month argument; None when it is not a valid month
+pub fn parse_month(text: &str) -> Option<String> {
+    NaiveDate::parse_from_str(&format!("{}-01", text.trim()), "%Y-%m-%d")
+        .ok()
+        .map(|date| date.format("%Y-%m").to_string())
+}
+
+/// One endpoint's probes in one month
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MonthStats {
+    pub probes: u64,
+    /// Probes that succeeded (healthy or degraded)
+    pub up: u64,
+    /// Latency of successful probes
+    #[serde(default)]
+    pub latency: LatencySketch,
+    /// Unix seconds of the first and last probe counted
+    pub first_at: i64,
+    pub last_at: i64,
+}
+
+impl MonthStats {
+    fn record(&mut self, up: bool, latency_ms: u32, at: i64) {
+        if self.probes == 0 {
+            self.first_at = at;
+        }
+        self.probes += 1;
+        self.last_at = at;
+        if up {
+            self.up += 1;
+            self.latency.add(latency_ms);
+        }
+    }
+
+    /// Share of probes that succeeded, in percent
+    pub fn availability_percent(&self) -> Option<f64> {
+        (self.probes > 0).then(|| self.up as f64 * 100.0 / self.probes as f64)
+    }
+
+    /// P95 latency of successful probes
+    pub fn p95_latency_ms(&self) -> Option<u32> {
+        (self.latency.count() > 0).then(|| self.latency.percentile(0.95))
+    }
+}
+
+/// Compliance of one endpoint with its target in one month
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlaCompliance {
+    pub target: SlaTarget,
+    pub month: String,
+    /// None when the endpoint was not probed that month
+    pub stats: Option<MonthStats>,
+}
+
+impl SlaCompliance {
+    pub fn availability_met(&self) -> Option<bool> {
+        let required = self.target.availability?;
+        let actual = self.stats.as_ref()?.availability_percent()?;
+        Some(actual >= required)
+    }
+
+    pub fn p95_met(&self) -> Option<bool> {
+        let limit = self.target.p95_ms?;
+        let actual = self.stats.as_ref()?.p95_latency_ms()?;
+        Some(actual <= limit)
+    }
+
+    /// Whether every declared target was met; None without probes to judge by
+    pub fn is_met(&self) -> Option<bool> {
+        self.stats.as_ref().filter(|stats| stats.probes > 0)?;
+        let availability = self.availability_met().unwrap_or(true);
+        // A declared P95 with no successful probe to measure is a miss
+        let p95 = self.target.p95_ms.is_none() || self.p95_met() == Some(true);
+        Some(availability && p95)
+    }
+}
+
+/// Monthly probe tallies by endpoint, persisted to `~/.claude/ccstatus/sla.json`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SlaLedger {
+    /// Endpoint name → month (`YYYY-MM`) → tallies
+    #[serde(default)]
+    pub endpoints: BTreeMap<String, BTreeMap<String, MonthStats>>,
+}
+
+impl SlaLedger {
+    /// Default location: `~/.claude/ccstatus/sla.json`
+    pub fn default_path() -> Result<PathBuf, NetworkError> {
+        let home = dirs::home_dir().ok_or(NetworkError::HomeDirNotFound)?;
+        Ok(home.join(".claude").join("ccstatus").join("sla.json"))
+    }
+
+    /// Load the ledger; a missing or unreadable file yields an empty ledger
+    pub async fn load(path: &Path) -> Self {
+        runtime::fs::read(path)
+            .await
+            .ok()
+            .and_then(|content| recovery::parse_json(path, &content))
+            .unwrap_or_default()
+    }
+
+    /// Write the ledger atomically
+    pub async fn save(&self, path: &Path) -> Result<(), NetworkError> {
+        if let Some(parent) = path.parent() {
+            runtime::fs::create_dir_all(parent).await.map_err(|e| {
+                NetworkError::StateFileError(format!("Failed to create SLA directory: {}", e))
+            })?;
+        }
+        let content = serde_json::to_string_pretty(self).map_err(|e| {
+            NetworkError::StateFileError(format!("Failed to serialize SLA ledger: {}", e))
+        })?;
+        let temp_path = path.with_extension("tmp");
+        runtime::fs::write(&temp_path, content).await.map_err(|e| {
+            NetworkError::StateFileError(format!("Failed to write SLA ledger: {}", e))
+        })?;
+        runtime::fs::rename(&temp_path, path).await.map_err(|e| {
+            NetworkError::StateFileError(format!("Failed to rename SLA ledger: {}", e))
+        })
+    }
+
+    /// Count a probe of `endpoint`; returns false for statuses that are not counted
+    pub fn record(
+        &mut self,
+        endpoint: &str,
+        status: &NetworkStatus,
+        latency_ms: u32,
+        at: i64,
+    ) -> bool {
+        let up = match status {
+            NetworkStatus::Healthy | NetworkStatus::Degraded => true,
+            NetworkStatus::Error => false,
+            NetworkStatus::Unknown | NetworkStatus::BlockedByPolicy => return false,
+        };
+
+        let months = self.endpoints.entry(endpoint.to_string()).or_default();
+        months
+            .entry(month_of(at))
+            .or_default()
+            .record(up, latency_ms, at);
+        while months.len() > MAX_MONTHS {
+            months.pop_first();
+        }
+        true
+    }
+
+    /// Compliance of every target in `month`, in declaration order
+    pub fn compliance(&self, targets: &[SlaTarget], month: &str) -> Vec<SlaCompliance> {
+        targets
+            .iter()
+            .map(|target| SlaCompliance {
+                target: target.clone(),
+                month: month.to_string(),
+                stats: self
+                    .endpoints
+                    .get(&target.endpoint)
+                    .and_then(|months| months.get(month))
+                    .cloned(),
+            })
+            .collect()
+    }
+}
+
+/// Status hook tallying one endpoint's probes into the SLA ledger
+pub struct SlaTracker {
+    endpoint: String,
+    path: PathBuf,
+}
+
+impl SlaTracker {
+    pub fn new(endpoint: &str, path: PathBuf) -> Self {
+        Self {
+            endpoint: endpoint.to_string(),
+            path,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl StatusHook for SlaTracker {
+    fn name(&self) -> &'static str {
+        "sla"
+    }
+
+    async fn on_probe(&self, change: &StatusChange<'_>) -> Result<(), NetworkError> {
+        let mut ledger = SlaLedger::load(&self.path).await;
+        if ledger.record(
+            &self.endpoint,
+            &change.state.status,
+            change.state.network.latency_ms,
+            unix_now_secs(),
+        ) {
+            ledger.save(&self.path).await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,325 @@
+//! Warm-standby measurement of alternate regional endpoints (`[network.standby]`)
+//!
+//! Users picking a proxy region want numbers for the alternatives without
+//! switching to them. Each configured region gets one unauthenticated HEAD
+//! request per `interval_secs` (hourly by default), so no tokens are spent and
+//! each endpoint sees a single request an hour. Results are kept in
+//! `~/.claude/ccstatus/standby-regions.json`, the last `MAX_REGION_SAMPLES`
+//! per region, and `ccstatus net regions` summarizes them.
+//!
+//! A region is reachable when the server itself answered (see
+//! `EndpointHealth::is_up_status`): a 401 or 404 to an anonymous HEAD still
+//! proves the path works. Latency is the time to the response headers.
+
+use crate::config::{HostPolicy, StandbyConfig, StandbyRegion};
+use crate::core::network::cookie_jar::unix_now_secs;
+use crate::core::network::debug_logger::get_debug_logger;
+use crate::core::network::types::{EndpointHealth, NetworkError};
+use crate::core::recovery;
+use crate::core::scheduler::Schedule;
+use crate::core::stats::LatencySketch;
+use crate::runtime;
+use isahc::config::{Configurable, RedirectPolicy};
+use isahc::{HttpClient, Request};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Samples kept per region (a day at the default hourly cadence)
+pub const MAX_REGION_SAMPLES: usize = 24;
+
+/// Per-request timeout; regions are measured concurrently
+const HEAD_TIMEOUT_MS: u32 = 2000;
+
+/// HEAD request abstraction (mockable for tests)
+#[async_trait::async_trait]
+pub trait HeadClient: Send + Sync {
+    /// Status code and time to response headers
+    async fn head(&self, url: String, timeout_ms: u32) -> Result<(u16, Duration), String>;
+}
+
+/// Production HEAD client using isahc, without credentials or redirects
+pub struct IsahcHeadClient {
+    client: HttpClient,
+}
+
+impl IsahcHeadClient {
+    pub fn new() -> Result<Self, NetworkError> {
+        let client = HttpClient::builder()
+            .redirect_policy(RedirectPolicy::None)
+            .build()
+            .map_err(|e| {
+                NetworkError::HttpError(format!("Failed to create standby client: {}", e))
+            })?;
+        Ok(Self { client })
+    }
+}
+
+#[async_trait::async_trait]
+impl HeadClient for IsahcHeadClient {
+    async fn head(&self, url: String, timeout_ms: u32) -> Result<(u16, Duration), String> {
+        let start = Instant::now();
+        let request = Request::head(&url)
+            .timeout(Duration::from_millis(timeout_ms as u64))
+            .header("User-Agent", "claude-cli/1.0.93 (external, cli)")
+            .body(())
+            .map_err(|e| format!("Standby request creation failed: {}", e))?;
+
+        let response = self
+            .client
+            .send_async(request)
+            .await
+            .map_err(|e| format!("Standby request failed: {}", e))?;
+        Ok((response.status().as_u16(), start.elapsed()))
+    }
+}
+
+/// One measurement of a region
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegionSample {
+    /// Unix seconds
+    pub at: i64,
+    /// HTTP status (0 = connection error)
+    pub http_status: u16,
+    pub latency_ms: u32,
+}
+
+impl RegionSample {
+    pub fn is_reachable(&self) -> bool {
+        EndpointHealth::is_up_status(self.http_status)
+    }
+}
+
+/// Recent measurements of one region
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RegionHistory {
+    /// URL the samples were taken against; a changed URL starts a new history
+    pub url: String,
+    pub samples: Vec<RegionSample>,
+}
+
+impl RegionHistory {
+    fn push(&mut self, sample: RegionSample) {
+        self.samples.push(sample);
+        if self.samples.len() > MAX_REGION_SAMPLES {
+            let excess = self.samples.len() - MAX_REGION_SAMPLES;
+            self.samples.drain(..excess);
+        }
+    }
+}
+
+/// Persisted standby measurements
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StandbyState {
+    /// Start of the last measurement round (Unix seconds)
+    #[serde(default)]
+    pub last_run_at: Option<i64>,
+    #[serde(default)]
+    pub regions: BTreeMap<String, RegionHistory>,
+}
+
+impl StandbyState {
+    /// Default location: `~/.claude/ccstatus/standby-regions.json`
+    pub fn default_path() -> Result<PathBuf, NetworkError> {
+        let home = dirs::home_dir().ok_or(NetworkError::HomeDirNotFound)?;
+        Ok(home
+            .join(".claude")
+            .join("ccstatus")
+            .join("standby-regions.json"))
+    }
+
+    /// Load the state; a missing or unreadable file yields an empty state
+    pub async fn load(path: &Path) -> Self {
+        runtime::fs::read(path)
+            .await
+            .ok()
+            .and_then(|content| recovery::parse_json(path, &content))
+            .unwrap_or_default()
+    }
+
+    /// Write the state atomically
+    pub async fn save(&self, path: &Path) -> Result<(), NetworkError> {
+        if let Some(parent) = path.parent() {
+            runtime::fs::create_dir_all(parent).await.map_err(|e| {
+                NetworkError::StateFileError(format!("Failed to create standby directory: {}", e))
+            })?;
+        }
+        let content = serde_json::to_string_pretty(self).map_err(|e| {
+            NetworkError::StateFileError(format!("Failed to serialize standby state: {}", e))
+        })?;
+        let temp_path = path.with_extension("tmp");
+        runtime::fs::write(&temp_path, content).await.map_err(|e| {
+            NetworkError::StateFileError(format!("Failed to write standby state: {}", e))
+        })?;
+        runtime::fs::rename(&temp_path, path).await.map_err(|e| {
+            NetworkError::StateFileError(format!("Failed to rename standby state: {}", e))
+        })
+    }
+
+    /// Append a sample for `region`
+    pub fn record(&mut self, region: &StandbyRegion, sample: RegionSample) {
+        let history = self.regions.entry(region.name.clone()).or_default();
+        if history.url != region.url {
+            *history = RegionHistory {
+                url: region.url.clone(),
+                samples: Vec::new(),
+            };
+        }
+        history.push(sample);
+    }
+
+    /// Per-region summaries, ordered by name
+    pub fn summarize(&self) -> Vec<RegionSummary> {
+        self.regions
+            .iter()
+            .filter_map(|(name, history)| {
+                let last = history.samples.last()?;
+                let latencies: LatencySketch = history
+                    .samples
+                    .iter()
+                    .filter(|sample| sample.is_reachable())
+                    .map(|sample| sample.latency_ms)
+                    .collect();
+
+                Some(RegionSummary {
+                    name: name.clone(),
+                    url: history.url.clone(),
+                    samples: history.samples.len(),
+                    reachable: latencies.count() as usize,
+                    p50_latency_ms: latencies.percentile(0.50),
+                    p95_latency_ms: latencies.percentile(0.95),
+                    last: last.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// What `ccstatus net regions` shows for one region
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegionSummary {
+    pub name: String,
+    pub url: String,
+    pub samples: usize,
+    /// Samples where the server answered
+    pub reachable: usize,
+    /// Median latency of reachable samples
+    pub p50_latency_ms: u32,
+    /// P95 latency of reachable samples
+    pub p95_latency_ms: u32,
+    pub last: RegionSample,
+}
+
+/// Measures `[[network.standby.regions]]` when a round is due
+pub struct StandbyMonitor {
+    regions: Vec<StandbyRegion>,
+    interval_secs: u64,
+    path: PathBuf,
+    client: Box<dyn HeadClient>,
+    host_policy: HostPolicy,
+}
+
+impl StandbyMonitor {
+    pub fn from_config(config: &StandbyConfig) -> Result<Self, NetworkError> {
+        Ok(Self {
+            regions: config.regions.clone(),
+            interval_secs: config.interval_secs,
+            path: StandbyState::default_path()?,
+            client: Box::new(IsahcHeadClient::new()?),
+            host_policy: HostPolicy::default(),
+        })
+    }
+
+    /// Use a custom HEAD client (for testing)
+    pub fn with_client(mut self, client: Box<dyn HeadClient>) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Use a custom state path (for testing)
+    pub fn with_path(mut self, path: PathBuf) -> Self {
+        self.path = path;
+        self
+    }
+
+    /// Skip regions outside `network.allowed_hosts`
+    pub fn with_host_policy(mut self, policy: HostPolicy) -> Self {
+        self.host_policy = policy;
+        self
+    }
+
+    /// Measure when `interval_secs` elapsed since the last round
+    pub async fn measure_if_due(&self) -> Result<Option<usize>, NetworkError> {
+        if self.regions.is_empty() {
+            return Ok(None);
+        }
+        let state = StandbyState::load(&self.path).await;
+        if !Schedule::every(self.interval_secs).is_due(
+            "standby",
+            state.last_run_at,
+            unix_now_secs(),
+        ) {
+            return Ok(None);
+        }
+        self.measure().await.map(Some)
+    }
+
+    /// Measure every allowed region now (concurrently); returns how many were measured
+    ///
+    /// Regions no longer configured are dropped from the state file.
+    pub async fn measure(&self) -> Result<usize, NetworkError> {
+        let debug_logger = get_debug_logger();
+        let now = unix_now_secs();
+        let mut state = StandbyState::load(&self.path).await;
+        state
+            .regions
+            .retain(|name, _| self.regions.iter().any(|region| &region.name == name));
+
+        let mut allowed = Vec::new();
+        for region in &self.regions {
+            if region.name.is_empty() || !self.host_policy.is_url_allowed(&region.url) {
+                debug_logger
+                    .debug(
+                        "Standby",
+                        &format!("Skipping region {:?} ({})", region.name, region.url),
+                    )
+                    .await;
+                continue;
+            }
+            allowed.push(region);
+        }
+
+        let results = futures::future::join_all(
+            allowed
+                .iter()
+                .map(|region| self.client.head(region.url.clone(), HEAD_TIMEOUT_MS)),
+        )
+        .await;
+
+        for (region, result) in allowed.iter().zip(results) {
+            let sample = match result {
+                Ok((http_status, duration)) => RegionSample {
+                    at: now,
+                    http_status,
+                    latency_ms: duration.as_millis() as u32,
+                },
+                Err(e) => {
+                    debug_logger
+                        .debug("Standby", &format!("Region {} failed: {}", region.name, e))
+                        .await;
+                    RegionSample {
+                        at: now,
+                        http_status: 0,
+                        latency_ms: 0,
+                    }
+                }
+            };
+            state.record(region, sample);
+        }
+
+        state.last_run_at = Some(now);
+        state.save(&self.path).await?;
+        Ok(allowed.len())
+    }
+}
@@ -0,0 +1,677 @@
+//! Encrypted state sync between machines (`[network.sync]`, `state-sync` feature)
+//!
+//! Each machine publishes its GREEN rolling window and probe ledger into a shared
+//! document stored on S3-compatible storage or WebDAV. The document is encrypted
+//! client-side (PBKDF2-HMAC-SHA256 → AES-256-GCM), so the backend only sees an
+//! opaque blob.
+//!
+//! ## Merge rules
+//!
+//! The document is keyed by machine id and every machine only writes its own
+//! entry, so merging is conflict-free:
+//! - Rolling window: last writer wins per machine (`updated_at`)
+//! - Ledger: per-field maximum (counters only grow)
+//!
+//! Both rules are commutative, associative and idempotent. A lost update from two
+//! machines syncing at once heals on the next sync because each machine
+//! re-publishes its own entry every time.
+//!
+//! Fresh windows from other machines become the `shared_baseline` in the local
+//! monitoring state, widening the P80/P95 thresholds used for GREEN probes.
+
+use crate::config::{HostPolicy, SyncBackendKind, SyncConfig};
+use crate::core::network::cookie_jar::unix_now_secs;
+use crate::core::network::http_monitor::HttpMonitor;
+use crate::core::network::types::{get_local_timestamp, NetworkError, ProbeLedger, SharedBaseline};
+use crate::core::recovery;
+use crate::core::scheduler::Schedule;
+use crate::runtime;
+use base64::Engine;
+use isahc::config::Configurable;
+use isahc::{AsyncReadResponseExt, HttpClient, Request};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use ring::{hmac, pbkdf2};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Blob header identifying the encryption format
+const BLOB_MAGIC: &[u8; 4] = b"CCS1";
+const SALT_LEN: usize = 16;
+const PBKDF2_ITERATIONS: u32 = 100_000;
+
+/// Windows older than this are not used as shared baseline
+pub const SHARED_BASELINE_MAX_AGE_SECS: i64 = 24 * 60 * 60;
+/// Machines silent for longer than this are dropped from the document
+pub const MACHINE_RETENTION_SECS: i64 = 30 * 24 * 60 * 60;
+/// Cap on shared samples merged into the local thresholds
+pub const SHARED_BASELINE_MAX_SAMPLES: usize = 48;
+
+const SYNC_TIMEOUT_MS: u64 = 3000;
+
+/// One machine's published monitoring history
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MachineHistory {
+    /// When the machine last published (Unix seconds)
+    pub updated_at: i64,
+    /// GREEN rolling window (successful probe latencies, ms)
+    pub rolling_totals: Vec<u32>,
+    pub p95_latency_ms: u32,
+    pub ledger: ProbeLedger,
+}
+
+impl MachineHistory {
+    /// Last writer wins for the window; ledger counters take the per-field maximum
+    pub fn merge(&self, other: &MachineHistory) -> MachineHistory {
+        let newer = match self.updated_at.cmp(&other.updated_at) {
+            std::cmp::Ordering::Greater => self,
+            std::cmp::Ordering::Less => other,
+            // Same timestamp: pick deterministically so merge stays commutative
+            std::cmp::Ordering::Equal => {
+                if (&self.rolling_totals, self.p95_latency_ms)
+                    >= (&other.rolling_totals, other.p95_latency_ms)
+                {
+                    self
+                } else {
+                    other
+                }
+            }
+        };
+
+        MachineHistory {
+            ledger: self.ledger.merge(&other.ledger),
+            ..newer.clone()
+        }
+    }
+}
+
+/// Shared sync document (plaintext form of the remote blob)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SyncDocument {
+    #[serde(default)]
+    pub machines: BTreeMap<String, MachineHistory>,
+}
+
+impl SyncDocument {
+    /// Merge two documents machine by machine
+    pub fn merge(&self, other: &SyncDocument) -> SyncDocument {
+        let mut machines = self.machines.clone();
+        for (machine_id, history) in &other.machines {
+            let merged = match machines.get(machine_id) {
+                Some(existing) => existing.merge(history),
+                None => history.clone(),
+            };
+            machines.insert(machine_id.clone(), merged);
+        }
+        SyncDocument { machines }
+    }
+
+    /// Drop machines that have not published within `MACHINE_RETENTION_SECS`
+    pub fn prune(&mut self, now: i64) {
+        self.machines
+            .retain(|_, history| now - history.updated_at <= MACHINE_RETENTION_SECS);
+    }
+
+    /// Fresh windows from every machine except `machine_id`, newest first
+    pub fn shared_baseline(&self, machine_id: &str, now: i64) -> Option<(Vec<u32>, u32)> {
+        let mut others: Vec<&MachineHistory> = self
+            .machines
+            .iter()
+            .filter(|(id, history)| {
+                id.as_str() != machine_id
+                    && !history.rolling_totals.is_empty()
+                    && now - history.updated_at <= SHARED_BASELINE_MAX_AGE_SECS
+            })
+            .map(|(_, history)| history)
+            .collect();
+        if others.is_empty() {
+            return None;
+        }
+        others.sort_by_key(|history| std::cmp::Reverse(history.updated_at));
+
+        let totals: Vec<u32> = others
+            .iter()
+            .flat_map(|history| history.rolling_totals.iter().copied())
+            .take(SHARED_BASELINE_MAX_SAMPLES)
+            .collect();
+        Some((totals, others.len() as u32))
+    }
+}
+
+/// Encrypt `plaintext`: `CCS1 | salt | nonce | AES-256-GCM ciphertext+tag`
+pub fn encrypt(passphrase: &str, plaintext: &[u8]) -> Result<Vec<u8>, NetworkError> {
+    let rng = SystemRandom::new();
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rng.fill(&mut salt)
+        .and_then(|_| rng.fill(&mut nonce))
+        .map_err(|_| NetworkError::SyncError("Random number generation failed".to_string()))?;
+
+    let key = derive_key(passphrase, &salt)?;
+    let mut sealed = plaintext.to_vec();
+    key.seal_in_place_append_tag(
+        Nonce::assume_unique_for_key(nonce),
+        Aad::from(BLOB_MAGIC),
+        &mut sealed,
+    )
+    .map_err(|_| NetworkError::SyncError("Encryption failed".to_string()))?;
+
+    let mut blob = Vec::with_capacity(BLOB_MAGIC.len() + SALT_LEN + NONCE_LEN + sealed.len());
+    blob.extend_from_slice(BLOB_MAGIC);
+    blob.extend_from_slice(&salt);
+    blob.extend_from_slice(&nonce);
+    blob.extend_from_slice(&sealed);
+    Ok(blob)
+}
+
+/// Decrypt a blob produced by `encrypt()`; fails on a wrong passphrase or tampering
+pub fn decrypt(passphrase: &str, blob: &[u8]) -> Result<Vec<u8>, NetworkError> {
+    let header_len = BLOB_MAGIC.len() + SALT_LEN + NONCE_LEN;
+    if blob.len() < header_len || &blob[..BLOB_MAGIC.len()] != BLOB_MAGIC {
+        return Err(NetworkError::SyncError(
+            "Remote blob is not a ccstatus sync document".to_string(),
+        ));
+    }
+
+    let salt = &blob[BLOB_MAGIC.len()..BLOB_MAGIC.len() + SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    nonce.copy_from_slice(&blob[BLOB_MAGIC.len() + SALT_LEN..header_len]);
+
+    let key = derive_key(passphrase, salt)?;
+    let mut sealed = blob[header_len..].to_vec();
+    let plaintext = key
+        .open_in_place(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(BLOB_MAGIC),
+            &mut sealed,
+        )
+        .map_err(|_| {
+            NetworkError::SyncError(
+                "Decryption failed (wrong passphrase or corrupted document)".to_string(),
+            )
+        })?;
+    Ok(plaintext.to_vec())
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<LessSafeKey, NetworkError> {
+    let mut key_bytes = [0u8; 32];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        NonZeroU32::new(PBKDF2_ITERATIONS).expect("non-zero iterations"),
+        salt,
+        passphrase.as_bytes(),
+        &mut key_bytes,
+    );
+    let key = UnboundKey::new(&AES_256_GCM, &key_bytes)
+        .map_err(|_| NetworkError::SyncError("Invalid encryption key".to_string()))?;
+    Ok(LessSafeKey::new(key))
+}
+
+/// Remote storage for the encrypted sync document
+#[async_trait::async_trait]
+pub trait SyncBackend: Send + Sync {
+    /// Fetch the current blob; `Ok(None)` when nothing has been stored yet
+    async fn fetch(&self) -> Result<Option<Vec<u8>>, NetworkError>;
+
+    /// Replace the stored blob
+    async fn store(&self, blob: Vec<u8>) -> Result<(), NetworkError>;
+
+    /// Object URL, for host policy checks and diagnostics
+    fn object_url(&self) -> &str;
+}
+
+/// Object URL for `object` inside the configured bucket / collection URL
+pub fn object_url(config: &SyncConfig) -> String {
+    format!("{}/{}", config.url.trim_end_matches('/'), config.object)
+}
+
+/// WebDAV backend: GET/PUT of one file with optional basic auth
+pub struct WebDavBackend {
+    client: HttpClient,
+    url: String,
+    credentials: Option<(String, String)>,
+}
+
+impl WebDavBackend {
+    pub fn new(url: String, credentials: Option<(String, String)>) -> Result<Self, NetworkError> {
+        Ok(Self {
+            client: sync_http_client()?,
+            url,
+            credentials,
+        })
+    }
+
+    fn authorization(&self) -> Option<String> {
+        self.credentials.as_ref().map(|(user, password)| {
+            let token =
+                base64::engine::general_purpose::STANDARD.encode(format!("{}:{}", user, password));
+            format!("Basic {}", token)
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl SyncBackend for WebDavBackend {
+    async fn fetch(&self) -> Result<Option<Vec<u8>>, NetworkError> {
+        let mut builder = Request::get(&self.url);
+        if let Some(authorization) = self.authorization() {
+            builder = builder.header("Authorization", authorization);
+        }
+        send(&self.client, builder, Vec::new(), true).await
+    }
+
+    async fn store(&self, blob: Vec<u8>) -> Result<(), NetworkError> {
+        let mut builder =
+            Request::put(&self.url).header("Content-Type", "application/octet-stream");
+        if let Some(authorization) = self.authorization() {
+            builder = builder.header("Authorization", authorization);
+        }
+        send(&self.client, builder, blob, false).await.map(|_| ())
+    }
+
+    fn object_url(&self) -> &str {
+        &self.url
+    }
+}
+
+/// S3-compatible backend: path-style GET/PUT signed with AWS Signature V4
+pub struct S3Backend {
+    client: HttpClient,
+    url: String,
+    region: String,
+    access_key: String,
+    secret_key: String,
+}
+
+impl S3Backend {
+    pub fn new(
+        url: String,
+        region: String,
+        access_key: String,
+        secret_key: String,
+    ) -> Result<Self, NetworkError> {
+        Ok(Self {
+            client: sync_http_client()?,
+            url,
+            region,
+            access_key,
+            secret_key,
+        })
+    }
+
+    fn signed_headers(
+        &self,
+        method: &str,
+        payload: &[u8],
+    ) -> Result<Vec<(String, String)>, NetworkError> {
+        let amz_date = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+        sigv4_headers(
+            method,
+            &self.url,
+            &self.region,
+            &self.access_key,
+            &self.secret_key,
+            payload,
+            &amz_date,
+        )
+    }
+}
+
+#[async_trait::async_trait]
+impl SyncBackend for S3Backend {
+    async fn fetch(&self) -> Result<Option<Vec<u8>>, NetworkError> {
+        let mut builder = Request::get(&self.url);
+        for (name, value) in self.signed_headers("GET", &[])? {
+            builder = builder.header(name, value);
+        }
+        send(&self.client, builder, Vec::new(), true).await
+    }
+
+    async fn store(&self, blob: Vec<u8>) -> Result<(), NetworkError> {
+        let mut builder =
+            Request::put(&self.url).header("Content-Type", "application/octet-stream");
+        for (name, value) in self.signed_headers("PUT", &blob)? {
+            builder = builder.header(name, value);
+        }
+        send(&self.client, builder, blob, false).await.map(|_| ())
+    }
+
+    fn object_url(&self) -> &str {
+        &self.url
+    }
+}
+
+/// AWS Signature V4 headers for a single-object S3 request without query string
+///
+/// Returns `host`, `x-amz-content-sha256`, `x-amz-date` and `authorization`.
+/// `amz_date` uses the `YYYYMMDDTHHMMSSZ` format.
+pub fn sigv4_headers(
+    method: &str,
+    url: &str,
+    region: &str,
+    access_key: &str,
+    secret_key: &str,
+    payload: &[u8],
+    amz_date: &str,
+) -> Result<Vec<(String, String)>, NetworkError> {
+    let parsed = url::Url::parse(url)
+        .map_err(|e| NetworkError::SyncError(format!("Invalid sync URL: {}", e)))?;
+    let host = match (parsed.host_str(), parsed.port()) {
+        (Some(host), Some(port)) => format!("{}:{}", host, port),
+        (Some(host), None) => host.to_string(),
+        (None, _) => return Err(NetworkError::SyncError("Sync URL has no host".to_string())),
+    };
+
+    let payload_hash = hex(&Sha256::digest(payload));
+    let date = &amz_date[..8.min(amz_date.len())];
+    let scope = format!("{}/{}/s3/aws4_request", date, region);
+    let signed_header_names = "host;x-amz-content-sha256;x-amz-date";
+
+    let canonical_request = format!(
+        "{}\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+        method,
+        parsed.path(),
+        host,
+        payload_hash,
+        amz_date,
+        signed_header_names,
+        payload_hash
+    );
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date,
+        scope,
+        hex(&Sha256::digest(canonical_request.as_bytes()))
+    );
+
+    let sign = |key: &[u8], data: &str| -> Vec<u8> {
+        let key = hmac::Key::new(hmac::HMAC_SHA256, key);
+        hmac::sign(&key, data.as_bytes()).as_ref().to_vec()
+    };
+    let date_key = sign(format!("AWS4{}", secret_key).as_bytes(), date);
+    let region_key = sign(&date_key, region);
+    let service_key = sign(&region_key, "s3");
+    let signing_key = sign(&service_key, "aws4_request");
+    let signature = hex(&sign(&signing_key, &string_to_sign));
+
+    Ok(vec![
+        ("host".to_string(), host),
+        ("x-amz-content-sha256".to_string(), payload_hash),
+        ("x-amz-date".to_string(), amz_date.to_string()),
+        (
+            "authorization".to_string(),
+            format!(
+                "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+                access_key, scope, signed_header_names, signature
+            ),
+        ),
+    ])
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn sync_http_client() -> Result<HttpClient, NetworkError> {
+    HttpClient::builder()
+        .timeout(Duration::from_millis(SYNC_TIMEOUT_MS))
+        .build()
+        .map_err(|e| NetworkError::HttpError(format!("Failed to create sync client: {}", e)))
+}
+
+/// Send a sync request; 404 on GET maps to `Ok(None)`, other non-2xx to an error
+async fn send(
+    client: &HttpClient,
+    builder: isahc::http::request::Builder,
+    body: Vec<u8>,
+    missing_is_empty: bool,
+) -> Result<Option<Vec<u8>>, NetworkError> {
+    let request = builder
+        .body(body)
+        .map_err(|e| NetworkError::SyncError(format!("Request creation failed: {}", e)))?;
+    let mut response = client
+        .send_async(request)
+        .await
+        .map_err(|e| NetworkError::HttpError(format!("Sync request failed: {}", e)))?;
+
+    let status = response.status().as_u16();
+    if status == 404 && missing_is_empty {
+        return Ok(None);
+    }
+    if !(200..300).contains(&status) {
+        return Err(NetworkError::SyncError(format!(
+            "Sync backend returned HTTP {}",
+            status
+        )));
+    }
+
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| NetworkError::HttpError(format!("Failed to read sync response: {}", e)))?;
+    Ok(Some(bytes.to_vec()))
+}
+
+/// Local sync bookkeeping (`~/.claude/ccstatus/sync-meta.json`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncMeta {
+    /// Random id identifying this machine in the shared document
+    pub machine_id: String,
+    /// Last successful sync (Unix seconds)
+    #[serde(default)]
+    pub last_sync_at: Option<i64>,
+}
+
+impl SyncMeta {
+    async fn load_or_create(path: &Path) -> Self {
+        let meta = runtime::fs::read(path)
+            .await
+            .ok()
+            .and_then(|content| recovery::parse_json::<SyncMeta>(path, &content))
+            .filter(|meta| !meta.machine_id.is_empty());
+        meta.unwrap_or_else(|| SyncMeta {
+            machine_id: uuid::Uuid::new_v4().to_string(),
+            last_sync_at: None,
+        })
+    }
+
+    async fn save(&self, path: &Path) -> Result<(), NetworkError> {
+        if let Some(parent) = path.parent() {
+            runtime::fs::create_dir_all(parent).await.map_err(|e| {
+                NetworkError::StateFileError(format!("Failed to create sync directory: {}", e))
+            })?;
+        }
+        let content = serde_json::to_string_pretty(self).map_err(|e| {
+            NetworkError::StateFileError(format!("Failed to serialize sync meta: {}", e))
+        })?;
+        let temp_path = path.with_extension("tmp");
+        runtime::fs::write(&temp_path, content).await.map_err(|e| {
+            NetworkError::StateFileError(format!("Failed to write sync meta: {}", e))
+        })?;
+        runtime::fs::rename(&temp_path, path)
+            .await
+            .map_err(|e| NetworkError::StateFileError(format!("Failed to rename sync meta: {}", e)))
+    }
+}
+
+/// Outcome of one sync round
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncReport {
+    /// Machines present in the merged document (including this one)
+    pub machines: usize,
+    /// Samples now shared into the local thresholds
+    pub shared_samples: usize,
+}
+
+/// Sync runner: fetch → decrypt → merge → encrypt → store → update shared baseline
+pub struct StateSync {
+    backend: Box<dyn SyncBackend>,
+    passphrase: String,
+    interval_secs: u64,
+    meta_path: PathBuf,
+    http_monitor: HttpMonitor,
+    host_policy: HostPolicy,
+}
+
+impl StateSync {
+    /// Build from `[network.sync]`, reading secrets from the configured environment variables
+    pub fn from_config(config: &SyncConfig) -> Result<Self, NetworkError> {
+        let env_secret = |name: &str| -> Option<String> {
+            std::env::var(name)
+                .ok()
+                .filter(|value| !value.trim().is_empty())
+        };
+
+        let passphrase = env_secret(&config.passphrase_env).ok_or_else(|| {
+            NetworkError::SyncError(format!(
+                "Sync passphrase not set (environment variable {})",
+                config.passphrase_env
+            ))
+        })?;
+
+        let url = object_url(config);
+        let access_key = config.access_key_env.as_deref().and_then(env_secret);
+        let secret_key = config.secret_key_env.as_deref().and_then(env_secret);
+        let backend: Box<dyn SyncBackend> = match config.backend {
+            SyncBackendKind::S3 => match (access_key, secret_key) {
+                (Some(access_key), Some(secret_key)) => Box::new(S3Backend::new(
+                    url,
+                    config.region.clone(),
+                    access_key,
+                    secret_key,
+                )?),
+                _ => {
+                    return Err(NetworkError::SyncError(
+                        "S3 sync needs access_key_env and secret_key_env".to_string(),
+                    ))
+                }
+            },
+            SyncBackendKind::Webdav => {
+                Box::new(WebDavBackend::new(url, access_key.zip(secret_key))?)
+            }
+        };
+
+        let home = dirs::home_dir().ok_or(NetworkError::HomeDirNotFound)?;
+        Ok(Self {
+            backend,
+            passphrase,
+            interval_secs: config.interval_secs,
+            meta_path: home.join(".claude").join("ccstatus").join("sync-meta.json"),
+            http_monitor: HttpMonitor::new(None)?,
+            host_policy: HostPolicy::default(),
+        })
+    }
+
+    /// Use a custom backend (for testing)
+    pub fn with_backend(mut self, backend: Box<dyn SyncBackend>) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Use a custom monitor / state path (`[network] local_state_dir`, tests)
+    pub fn with_http_monitor(mut self, http_monitor: HttpMonitor) -> Self {
+        self.http_monitor = http_monitor;
+        self
+    }
+
+    /// Use a custom sync meta path (for testing)
+    pub fn with_meta_path(mut self, meta_path: PathBuf) -> Self {
+        self.meta_path = meta_path;
+        self
+    }
+
+    /// Refuse to contact hosts outside `network.allowed_hosts`
+    pub fn with_host_policy(mut self, policy: HostPolicy) -> Self {
+        self.host_policy = policy;
+        self
+    }
+
+    /// Sync when `interval_secs` elapsed since the last successful sync
+    ///
+    /// Each machine waits up to a tenth of the interval longer, derived from
+    /// its machine ID.
+    pub async fn sync_if_due(&self) -> Result<Option<SyncReport>, NetworkError> {
+        let meta = SyncMeta::load_or_create(&self.meta_path).await;
+        // Jitter per machine so machines sharing a bucket don't sync in lockstep
+        let schedule = Schedule::every(self.interval_secs).with_jitter(self.interval_secs / 10);
+        let job = format!("state-sync:{}", meta.machine_id);
+        if !schedule.is_due(&job, meta.last_sync_at, unix_now_secs()) {
+            return Ok(None);
+        }
+        self.sync().await.map(Some)
+    }
+
+    /// Run one sync round now
+    ///
+    /// A remote document that cannot be decrypted aborts the round without
+    /// overwriting it.
+    pub async fn sync(&self) -> Result<SyncReport, NetworkError> {
+        if !self.host_policy.is_url_allowed(self.backend.object_url()) {
+            return Err(NetworkError::BlockedByPolicy(
+                self.backend.object_url().to_string(),
+            ));
+        }
+
+        let mut meta = SyncMeta::load_or_create(&self.meta_path).await;
+        let now = unix_now_secs();
+
+        let remote = match self.backend.fetch().await? {
+            Some(blob) => {
+                let plaintext = decrypt(&self.passphrase, &blob)?;
+                serde_json::from_slice::<SyncDocument>(&plaintext)
+                    .map_err(|e| NetworkError::SyncError(format!("Invalid sync document: {}", e)))?
+            }
+            None => SyncDocument::default(),
+        };
+
+        let state = self.http_monitor.load_state().await.unwrap_or_default();
+        let mut local = SyncDocument::default();
+        local.machines.insert(
+            meta.machine_id.clone(),
+            MachineHistory {
+                updated_at: now,
+                rolling_totals: state.network.rolling_totals.to_vec(),
+                p95_latency_ms: state.network.p95_latency_ms,
+                ledger: state.monitoring_state.ledger,
+            },
+        );
+
+        let mut merged = remote.merge(&local);
+        merged.prune(now);
+
+        let plaintext = serde_json::to_vec(&merged).map_err(|e| {
+            NetworkError::SyncError(format!("Failed to serialize sync document: {}", e))
+        })?;
+        self.backend
+            .store(encrypt(&self.passphrase, &plaintext)?)
+            .await?;
+
+        let shared_baseline =
+            merged
+                .shared_baseline(&meta.machine_id, now)
+                .map(|(totals, machines)| SharedBaseline {
+                    totals,
+                    machines,
+                    synced_at: get_local_timestamp(),
+                });
+        let shared_samples = shared_baseline
+            .as_ref()
+            .map(|baseline| baseline.totals.len())
+            .unwrap_or(0);
+        self.http_monitor
+            .set_shared_baseline(shared_baseline)
+            .await?;
+
+        meta.last_sync_at = Some(now);
+        meta.save(&self.meta_path).await?;
+
+        Ok(SyncReport {
+            machines: merged.machines.len(),
+            shared_samples,
+        })
+    }
+}
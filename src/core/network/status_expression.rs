@@ -0,0 +1,413 @@
+//! Config-defined status expression (`network.status_expression`)
+//!
+//! Power users can replace the built-in P80/P95 classification with a small
+//! conditional expression over probe metrics, for example:
+//!
+//! ```text
+//! error if consecutive_failures > 2 else degraded if p95 > 2500 else default
+//! ```
+//!
+//! Grammar (keywords are case-insensitive):
+//!
+//! ```text
+//! expr       := status ["if" condition "else" expr]
+//! status     := healthy | degraded | error | unknown | default
+//! condition  := and ("or" and)*
+//! and        := unary ("and" unary)*
+//! unary      := "not" unary | "(" condition ")" | operand cmp operand
+//! cmp        := > | >= | < | <= | == | !=
+//! operand    := metric | integer
+//! ```
+//!
+//! `default` yields the status the built-in classification produced. The
+//! engine has no loops, calls or side effects: evaluation is a bounded walk of
+//! the parsed tree, and unknown metrics are rejected when the config is parsed.
+
+use crate::core::network::types::{MonitoringSnapshot, NetworkError, NetworkStatus};
+
+/// Metrics an expression may reference
+pub const METRICS: &[&str] = &[
+    "latency_ms",
+    "p95",
+    "http_status",
+    "consecutive_failures",
+    "rolling_len",
+    "health_score",
+    "penalty_level",
+];
+
+/// Values an expression is evaluated against
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatusMetrics {
+    /// Latency of the last probe
+    pub latency_ms: u32,
+    /// Rolling P95 latency
+    pub p95: u32,
+    /// HTTP status of the last probe (0 = connection failure)
+    pub http_status: u16,
+    /// Probes in a row that did not return HTTP 200
+    pub consecutive_failures: u32,
+    /// GREEN samples in the rolling window
+    pub rolling_len: usize,
+    /// Composite endpoint health score (0 when aux checks are off)
+    pub health_score: u8,
+    /// Current penalty backoff level
+    pub penalty_level: u32,
+}
+
+impl StatusMetrics {
+    /// Metrics from a freshly updated monitoring snapshot
+    pub fn from_snapshot(state: &MonitoringSnapshot) -> Self {
+        Self {
+            latency_ms: state.network.latency_ms,
+            p95: state.network.p95_latency_ms,
+            http_status: state.network.last_http_status,
+            consecutive_failures: state.monitoring_state.consecutive_failures,
+            rolling_len: state.network.rolling_totals.len(),
+            health_score: state.health_score.unwrap_or(0),
+            penalty_level: state.monitoring_state.penalty.level,
+        }
+    }
+
+    fn get(&self, metric: &str) -> i64 {
+        match metric {
+            "latency_ms" => self.latency_ms as i64,
+            "p95" => self.p95 as i64,
+            "http_status" => self.http_status as i64,
+            "consecutive_failures" => self.consecutive_failures as i64,
+            "rolling_len" => self.rolling_len as i64,
+            "health_score" => self.health_score as i64,
+            "penalty_level" => self.penalty_level as i64,
+            _ => 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Outcome {
+    Status(NetworkStatus),
+    Default,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CmpOp {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Eq,
+    Ne,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Operand {
+    Metric(String),
+    Literal(i64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Condition {
+    Compare(Operand, CmpOp, Operand),
+    Not(Box<Condition>),
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Value(Outcome),
+    IfElse(Outcome, Condition, Box<Expr>),
+}
+
+/// Parsed status expression, ready to evaluate on every probe
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusExpression {
+    source: String,
+    expr: Expr,
+}
+
+impl StatusExpression {
+    /// Parse an expression from config
+    ///
+    /// # Errors
+    ///
+    /// Returns `NetworkError::ConfigParseError` describing the first syntax error
+    /// or unknown metric.
+    pub fn parse(source: &str) -> Result<Self, NetworkError> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let expr = parser.expr()?;
+        if let Some(token) = parser.peek() {
+            return Err(parse_error(format!("unexpected '{}'", token)));
+        }
+        Ok(Self {
+            source: source.trim().to_string(),
+            expr,
+        })
+    }
+
+    /// Expression text as configured
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Evaluate against `metrics`; `default` resolves to `default_status`
+    pub fn evaluate(
+        &self,
+        metrics: &StatusMetrics,
+        default_status: &NetworkStatus,
+    ) -> NetworkStatus {
+        let mut expr = &self.expr;
+        let outcome = loop {
+            match expr {
+                Expr::Value(outcome) => break outcome,
+                Expr::IfElse(outcome, condition, otherwise) => {
+                    if condition.holds(metrics) {
+                        break outcome;
+                    }
+                    expr = otherwise;
+                }
+            }
+        };
+
+        match outcome {
+            Outcome::Default => default_status.clone(),
+            Outcome::Status(status) => status.clone(),
+        }
+    }
+}
+
+impl Condition {
+    fn holds(&self, metrics: &StatusMetrics) -> bool {
+        match self {
+            Condition::Compare(left, op, right) => {
+                let (left, right) = (left.value(metrics), right.value(metrics));
+                match op {
+                    CmpOp::Gt => left > right,
+                    CmpOp::Ge => left >= right,
+                    CmpOp::Lt => left < right,
+                    CmpOp::Le => left <= right,
+                    CmpOp::Eq => left == right,
+                    CmpOp::Ne => left != right,
+                }
+            }
+            Condition::Not(inner) => !inner.holds(metrics),
+            Condition::And(left, right) => left.holds(metrics) && right.holds(metrics),
+            Condition::Or(left, right) => left.holds(metrics) || right.holds(metrics),
+        }
+    }
+}
+
+impl Operand {
+    fn value(&self, metrics: &StatusMetrics) -> i64 {
+        match self {
+            Operand::Metric(name) => metrics.get(name),
+            Operand::Literal(value) => *value,
+        }
+    }
+}
+
+fn parse_error(message: String) -> NetworkError {
+    NetworkError::ConfigParseError(format!("status_expression: {}", message))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Number(i64),
+    Op(CmpOp),
+    Open,
+    Close,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Word(word) => write!(f, "{}", word),
+            Token::Number(value) => write!(f, "{}", value),
+            Token::Op(op) => write!(
+                f,
+                "{}",
+                match op {
+                    CmpOp::Gt => ">",
+                    CmpOp::Ge => ">=",
+                    CmpOp::Lt => "<",
+                    CmpOp::Le => "<=",
+                    CmpOp::Eq => "==",
+                    CmpOp::Ne => "!=",
+                }
+            ),
+            Token::Open => write!(f, "("),
+            Token::Close => write!(f, ")"),
+        }
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, NetworkError> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '(' {
+            tokens.push(Token::Open);
+            i += 1;
+        } else if c == ')' {
+            tokens.push(Token::Close);
+            i += 1;
+        } else if matches!(c, '>' | '<' | '=' | '!') {
+            let followed_by_eq = chars.get(i + 1) == Some(&'=');
+            let op = match (c, followed_by_eq) {
+                ('>', true) => CmpOp::Ge,
+                ('>', false) => CmpOp::Gt,
+                ('<', true) => CmpOp::Le,
+                ('<', false) => CmpOp::Lt,
+                ('=', true) => CmpOp::Eq,
+                ('!', true) => CmpOp::Ne,
+                _ => return Err(parse_error(format!("unexpected '{}'", c))),
+            };
+            tokens.push(Token::Op(op));
+            i += if followed_by_eq { 2 } else { 1 };
+        } else if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && chars[i].is_ascii_digit() {
+                i += 1;
+            }
+            let digits: String = chars[start..i].iter().collect();
+            let value = digits
+                .parse()
+                .map_err(|_| parse_error(format!("number out of range: {}", digits)))?;
+            tokens.push(Token::Number(value));
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            tokens.push(Token::Word(word.to_ascii_lowercase()));
+        } else {
+            return Err(parse_error(format!("unexpected '{}'", c)));
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat_word(&mut self, keyword: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Word(word)) if word == keyword) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr, NetworkError> {
+        let outcome = self.outcome()?;
+        if !self.eat_word("if") {
+            return Ok(Expr::Value(outcome));
+        }
+        let condition = self.condition()?;
+        if !self.eat_word("else") {
+            return Err(parse_error("expected 'else' after condition".to_string()));
+        }
+        let otherwise = self.expr()?;
+        Ok(Expr::IfElse(outcome, condition, Box::new(otherwise)))
+    }
+
+    fn outcome(&mut self) -> Result<Outcome, NetworkError> {
+        let outcome = match self.next() {
+            Some(Token::Word(word)) => match word.as_str() {
+                "healthy" => Outcome::Status(NetworkStatus::Healthy),
+                "degraded" => Outcome::Status(NetworkStatus::Degraded),
+                "error" => Outcome::Status(NetworkStatus::Error),
+                "unknown" => Outcome::Status(NetworkStatus::Unknown),
+                "default" => Outcome::Default,
+                _ => return Err(parse_error(format!("expected a status, found '{}'", word))),
+            },
+            Some(token) => {
+                return Err(parse_error(format!("expected a status, found '{}'", token)))
+            }
+            None => return Err(parse_error("expected a status".to_string())),
+        };
+        Ok(outcome)
+    }
+
+    fn condition(&mut self) -> Result<Condition, NetworkError> {
+        let mut left = self.and()?;
+        while self.eat_word("or") {
+            let right = self.and()?;
+            left = Condition::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self) -> Result<Condition, NetworkError> {
+        let mut left = self.unary()?;
+        while self.eat_word("and") {
+            let right = self.unary()?;
+            left = Condition::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Condition, NetworkError> {
+        if self.eat_word("not") {
+            return Ok(Condition::Not(Box::new(self.unary()?)));
+        }
+        if self.peek() == Some(&Token::Open) {
+            self.pos += 1;
+            let inner = self.condition()?;
+            if self.next() != Some(Token::Close) {
+                return Err(parse_error("expected ')'".to_string()));
+            }
+            return Ok(inner);
+        }
+
+        let left = self.operand()?;
+        let op = match self.next() {
+            Some(Token::Op(op)) => op,
+            _ => return Err(parse_error("expected a comparison operator".to_string())),
+        };
+        let right = self.operand()?;
+        Ok(Condition::Compare(left, op, right))
+    }
+
+    fn operand(&mut self) -> Result<Operand, NetworkError> {
+        match self.next() {
+            Some(Token::Number(value)) => Ok(Operand::Literal(value)),
+            Some(Token::Word(word)) if METRICS.contains(&word.as_str()) => {
+                Ok(Operand::Metric(word))
+            }
+            Some(Token::Word(word)) => Err(parse_error(format!(
+                "unknown metric '{}' (available: {})",
+                word,
+                METRICS.join(", ")
+            ))),
+            Some(token) => Err(parse_error(format!(
+                "expected a metric or number, found '{}'",
+                token
+            ))),
+            None => Err(parse_error("expected a metric or number".to_string())),
+        }
+    }
+}
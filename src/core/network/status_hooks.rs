@@ -0,0 +1,152 @@
+//! Status hooks run after every probe (`HttpMonitor::with_status_hook`)
+//!
+//! Once a probe's state is written, HttpMonitor hands each hook the status
+//! before the probe, the new snapshot and how long the current status has
+//! lasted (`MonitoringState::status_since`). Hooks react to transitions or to
+//! a status persisting; a failing hook is logged and never affects the probe.
+//!
+//! `PauseAdvisory` is the built-in hook: while the status has been `error` for
+//! at least `after_secs` it keeps an advisory file in place, and it removes the
+//! file on recovery. Users' pre-tool-use hooks test for the file to halt
+//! expensive agent loops during outages.
+
+use crate::config::PauseAdvisoryConfig;
+use crate::core::network::types::{MonitoringSnapshot, NetworkError, NetworkStatus};
+use crate::runtime;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// What a hook sees after a probe
+#[derive(Debug, Clone, Copy)]
+pub struct StatusChange<'a> {
+    /// Status before this probe
+    pub previous: &'a NetworkStatus,
+    /// State as just written
+    pub state: &'a MonitoringSnapshot,
+    /// Seconds the current status has lasted (0 right after a transition)
+    pub status_secs: u64,
+}
+
+impl StatusChange<'_> {
+    /// Whether this probe changed the status
+    pub fn is_transition(&self) -> bool {
+        *self.previous != self.state.status
+    }
+}
+
+/// Reaction to probe results
+#[async_trait::async_trait]
+pub trait StatusHook: Send + Sync {
+    /// Short name for debug logs
+    fn name(&self) -> &'static str;
+
+    async fn on_probe(&self, change: &StatusChange<'_>) -> Result<(), NetworkError>;
+}
+
+/// Contents of the pause advisory file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Advisory {
+    /// Always `error` today; kept so scripts need not assume it
+    pub status: String,
+    /// When the error status began (local ISO-8601)
+    pub since: String,
+    pub http_status: u16,
+    pub error_type: Option<String>,
+    /// Probe that last refreshed the advisory (local ISO-8601)
+    pub updated_at: String,
+    pub message: String,
+}
+
+/// Writes an advisory file during sustained errors and removes it on recovery
+pub struct PauseAdvisory {
+    path: PathBuf,
+    after_secs: u64,
+}
+
+impl PauseAdvisory {
+    pub fn new(path: PathBuf, after_secs: u64) -> Self {
+        Self { path, after_secs }
+    }
+
+    /// Default location: `~/.claude/ccstatus/pause-advisory.json`
+    pub fn default_path() -> Result<PathBuf, NetworkError> {
+        let home = dirs::home_dir().ok_or(NetworkError::HomeDirNotFound)?;
+        Ok(home
+            .join(".claude")
+            .join("ccstatus")
+            .join("pause-advisory.json"))
+    }
+
+    /// Build from `[network.pause_advisory]`, expanding a leading `~/`
+    pub fn from_config(config: &PauseAdvisoryConfig) -> Result<Self, NetworkError> {
+        let path = match config.path.as_deref().map(str::trim) {
+            Some(path) if !path.is_empty() => match path.strip_prefix("~/") {
+                Some(rest) => dirs::home_dir()
+                    .ok_or(NetworkError::HomeDirNotFound)?
+                    .join(rest),
+                None => PathBuf::from(path),
+            },
+            _ => Self::default_path()?,
+        };
+        Ok(Self::new(path, config.after_secs))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    async fn write(&self, state: &MonitoringSnapshot) -> Result<(), NetworkError> {
+        let advisory = Advisory {
+            status: state.status.as_str().to_string(),
+            since: state
+                .monitoring_state
+                .status_since
+                .clone()
+                .unwrap_or_else(|| state.timestamp.clone()),
+            http_status: state.network.last_http_status,
+            error_type: state.network.error_type.clone(),
+            updated_at: state.timestamp.clone(),
+            message: "Claude API has been failing; consider pausing agent loops".to_string(),
+        };
+
+        if let Some(parent) = self.path.parent() {
+            runtime::fs::create_dir_all(parent).await.map_err(|e| {
+                NetworkError::StateFileError(format!("Failed to create advisory directory: {}", e))
+            })?;
+        }
+        let content = serde_json::to_string_pretty(&advisory).map_err(|e| {
+            NetworkError::StateFileError(format!("Failed to serialize advisory: {}", e))
+        })?;
+        let temp_path = self.path.with_extension("tmp");
+        runtime::fs::write(&temp_path, content).await.map_err(|e| {
+            NetworkError::StateFileError(format!("Failed to write advisory: {}", e))
+        })?;
+        runtime::fs::rename(&temp_path, &self.path)
+            .await
+            .map_err(|e| NetworkError::StateFileError(format!("Failed to rename advisory: {}", e)))
+    }
+}
+
+#[async_trait::async_trait]
+impl StatusHook for PauseAdvisory {
+    fn name(&self) -> &'static str {
+        "pause_advisory"
+    }
+
+    async fn on_probe(&self, change: &StatusChange<'_>) -> Result<(), NetworkError> {
+        if change.state.status == NetworkStatus::Error {
+            if change.status_secs >= self.after_secs {
+                self.write(change.state).await?;
+            }
+            return Ok(());
+        }
+
+        // Any other status counts as recovery
+        if self.path.exists() {
+            runtime::fs::remove_file(&self.path).await.map_err(|e| {
+                NetworkError::StateFileError(format!("Failed to remove advisory: {}", e))
+            })?;
+        }
+        Ok(())
+    }
+}
@@ -1,27 +1,130 @@
 // Statusline UI rendering for network monitoring
+use crate::core::attention::AttentionStyle;
+use crate::core::format::NumberFormat;
+use crate::core::hyperlink::{self, error_help_url};
+use crate::core::i18n::{Locale, Text};
+use crate::core::icons::{Icon, IconSet};
+use crate::core::network::certificate::{format_date, CertificateChain};
+use crate::core::network::error_tracker::ErrorTracker;
+use crate::core::network::ip_family::{FamilyStats, IpFamily};
+use crate::core::network::probe_history::{HistorySummary, ProbeRecord, VersionSummary};
 use crate::core::network::proxy_health::config::ProxyHealthLevel;
-use crate::core::network::types::{NetworkMetrics, NetworkStatus};
+use crate::core::network::proxy_health::url::extract_host;
+use crate::core::network::sla::SlaCompliance;
+use crate::core::network::standby::RegionSummary;
+use crate::core::network::types::{
+    ActivityClass, ConnectionInfo, JsonlError, MonitoringSnapshot, NetworkMetrics, NetworkStatus,
+    RateLimitInfo, UptimeWindow,
+};
+use crate::core::stats::{self, Histogram};
 
 /// Renders network status for statusline display
-pub struct StatusRenderer;
+pub struct StatusRenderer {
+    /// Emit OSC 8 links on error hints (plain text otherwise)
+    hyperlinks: bool,
+    /// Glyphs for status lights and marks
+    icons: IconSet,
+    /// Language of status words
+    locale: Locale,
+    /// Latency units and separators
+    format: NumberFormat,
+    /// Emphasis on the error light and hint
+    attention: AttentionStyle,
+}
 
 impl StatusRenderer {
     pub fn new() -> Self {
-        Self
+        Self {
+            hyperlinks: false,
+            icons: IconSet::default(),
+            locale: Locale::default(),
+            format: NumberFormat::default(),
+            attention: AttentionStyle::None,
+        }
+    }
+
+    /// Make error hints clickable (status page / docs anchor for the `error_type`)
+    pub fn with_hyperlinks(mut self, enabled: bool) -> Self {
+        self.hyperlinks = enabled;
+        self
+    }
+
+    /// Render status lights and marks from `icons` (e.g. `[ok]` instead of 🟢 for ASCII)
+    pub fn with_icons(mut self, icons: IconSet) -> Self {
+        self.icons = icons;
+        self
+    }
+
+    /// Render status words in `locale` (e.g. `探测已关闭` instead of `probes off`)
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Show latencies per `format` (e.g. `P95:1.2s` with `latency_unit = "s"`)
+    pub fn with_number_format(mut self, format: NumberFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Make the error state stand out per `attention` (e.g. blinking 🔴)
+    pub fn with_attention(mut self, attention: AttentionStyle) -> Self {
+        self.attention = attention;
+        self
+    }
+
+    fn glyph(&self, icon: Icon) -> &'static str {
+        self.icons.glyph(icon)
+    }
+
+    fn text(&self, text: Text) -> &'static str {
+        self.locale.text(text)
+    }
+
+    /// `P95:1200ms`, or `P95:N/A` before the first sample
+    fn p95_display(&self, metrics: &NetworkMetrics) -> String {
+        if metrics.p95_latency_ms == 0 {
+            "P95:N/A".to_string()
+        } else {
+            format!(
+                "P95:{}",
+                self.format.latency_ms(metrics.p95_latency_ms as u64)
+            )
+        }
+    }
+
+    fn link_error(&self, text: &str, error_type: &str) -> String {
+        hyperlink::link(text, &error_help_url(error_type), self.hyperlinks)
     }
 
     /// Render status for statusline display
     /// Emoji: 🟢/🟡/🔴/⚪ map to `healthy/degraded/error/Unknown`
+    /// Policy: ⛔ blocked_by_policy (host) when the probe host is outside `network.allowed_hosts`
     /// Text: 🟢 shows P95; 🟡 shows P95+breakdown; 🔴 shows breakdown; wraps long content to next line
     /// Proxy prefix: 🟢 |/🟡 |/🔴 |/⚪ | prepended when proxy health check is available (tri-state support + Unknown)
     /// Shield: 🛡️ indicators for bot challenges (GET and/or POST)
+    /// Endpoints: `| messages ✗ models ✓` appended via `render_with_endpoints()` on partial outage
+    /// Alternative: `| alt proxy.example.com 410ms` appended via `render_with_alternative()` when a compared endpoint is faster
+    /// Cooldown: `| cooldown Nm` appended via `render_with_cooldown()` while penalty backoff defers probes
+    /// Targets: `A✓ R✗` strip via `render_target_strip()` when `[[network.targets]]` are configured
     /// OAuth mode: Shows green status indicator and timing metrics, omits proxy health checks
+    /// Transport failures: `🔴 DNS failed`, `refused`, `TLS failed` or `timeout` before the breakdown
+    /// Links: with `with_hyperlinks(true)` 🔴 text links to the status page or `error_type` docs
+    /// Glyphs: shown for the default Unicode icon set; `with_icons()` swaps them (e.g. ASCII)
+    /// Words: English by default; `with_locale()` translates them (numbers and `P95:` stay as-is)
+    /// Numbers: P95 and totals follow `with_number_format()`; the phase breakdown stays in ms
+    /// Attention: `with_attention()` blinks/bolds/inverts the 🔴 light and hint (plain by default)
     pub fn render_status(
         &self,
         status: &NetworkStatus,
         metrics: &NetworkMetrics,
         api_config: Option<&crate::core::network::types::ApiConfig>,
     ) -> String {
+        // Host allowlist violation: no request was sent, so no timings to show
+        if *status == NetworkStatus::BlockedByPolicy {
+            return self.render_blocked_by_policy(api_config);
+        }
+
         // OAuth mode: render green status indicator with timing metrics, omits proxy health
         if let Some(config) = api_config {
             if config.source == "oauth" {
@@ -53,59 +156,698 @@ impl StatusRenderer {
         }
         // Determine proxy health prefix based on enhanced tri-state levels with fallback
         let proxy_prefix = match metrics.get_proxy_health_level() {
-            Some(ProxyHealthLevel::Healthy) => Some("🟢 | "), // Healthy proxy
-            Some(ProxyHealthLevel::Degraded) => Some("🟡 | "), // Degraded proxy
-            Some(ProxyHealthLevel::Bad) => Some("🔴 | "),     // Unhealthy proxy
-            Some(ProxyHealthLevel::Unknown) => Some("⚪ | "), // Unknown proxy (Cloudflare challenges, etc.)
+            Some(ProxyHealthLevel::Healthy) => Some(Icon::Healthy), // Healthy proxy
+            Some(ProxyHealthLevel::Degraded) => Some(Icon::Degraded), // Degraded proxy
+            Some(ProxyHealthLevel::Bad) => Some(Icon::Error),       // Unhealthy proxy
+            Some(ProxyHealthLevel::Unknown) => Some(Icon::Unknown), // Unknown proxy (Cloudflare challenges, etc.)
             None => None, // No proxy health check (official endpoint or no health endpoint)
         };
 
         let core = match status {
             NetworkStatus::Healthy => {
                 // healthy: show P95 (N/A if zero)
-                let p95_display = if metrics.p95_latency_ms == 0 {
-                    "P95:N/A".to_string()
-                } else {
-                    format!("P95:{}ms", metrics.p95_latency_ms)
-                };
-                format!("🟢 {}", p95_display)
+                let p95_display = self.p95_display(metrics);
+                format!("{} {}", self.glyph(Icon::Healthy), p95_display)
             }
             NetworkStatus::Degraded => {
                 // degraded: show P95 and breakdown (wrap if long)
-                let p95_display = if metrics.p95_latency_ms == 0 {
-                    "P95:N/A".to_string()
-                } else {
-                    format!("P95:{}ms", metrics.p95_latency_ms)
-                };
-                let base = format!("🟡 {}", p95_display);
+                let p95_display = self.p95_display(metrics);
+                let base = format!("{} {}", self.glyph(Icon::Degraded), p95_display);
                 self.format_with_breakdown(base, &metrics.breakdown)
             }
             NetworkStatus::Error => {
-                // error: show breakdown (wrap if long)
-                self.format_with_breakdown("🔴".to_string(), &metrics.breakdown)
+                // error: name the failed transport phase if known, then breakdown (wrap if long)
+                let base = match metrics.error_type.as_deref().and_then(transport_hint) {
+                    Some(hint) => format!("{} {}", self.glyph(Icon::Error), self.text(hint)),
+                    None => self.glyph(Icon::Error).to_string(),
+                };
+                let text =
+                    self.format_with_breakdown(self.attention.apply(&base), &metrics.breakdown);
+                match metrics.error_type.as_deref() {
+                    Some(error_type) => self.link_error(&text, error_type),
+                    None => text,
+                }
             }
-            NetworkStatus::Unknown => "⚪ Env vars NOT Found".to_string(),
+            NetworkStatus::Unknown => format!(
+                "{} {}",
+                self.glyph(Icon::Unknown),
+                self.text(Text::EnvVarsNotFound)
+            ),
+            NetworkStatus::BlockedByPolicy => self.render_blocked_by_policy(api_config),
         };
 
         // Prepend proxy health prefix if available
         match proxy_prefix {
-            Some(prefix) => format!("{}{}", prefix, core),
+            Some(icon) => format!("{} | {}", self.glyph(icon), core),
             None => core,
         }
     }
 
+    /// Render allowlist violation: ⛔ blocked_by_policy (host)
+    fn render_blocked_by_policy(
+        &self,
+        api_config: Option<&crate::core::network::types::ApiConfig>,
+    ) -> String {
+        let host = api_config
+            .and_then(|config| url::Url::parse(&config.endpoint).ok())
+            .and_then(|url| url.host_str().map(str::to_string));
+
+        match host {
+            Some(host) => format!(
+                "{} {} ({})",
+                self.glyph(Icon::Blocked),
+                self.link_error("blocked_by_policy", "blocked_by_policy"),
+                host
+            ),
+            None => format!(
+                "{} {}",
+                self.glyph(Icon::Blocked),
+                self.link_error("blocked_by_policy", "blocked_by_policy")
+            ),
+        }
+    }
+
+    /// Append a partial-outage hint when endpoints disagree (composite health)
+    /// e.g. `🔴 | messages ✗ models ✓`; unchanged when all agree or no auxiliary checks ran
+    pub fn render_with_endpoints(&self, status_text: String, state: &MonitoringSnapshot) -> String {
+        let any_up = state.endpoints.values().any(|endpoint| endpoint.up);
+        let any_down = state.endpoints.values().any(|endpoint| !endpoint.up);
+        if !(any_up && any_down) {
+            return status_text;
+        }
+
+        let summary = state
+            .endpoints
+            .iter()
+            .map(|(name, endpoint)| {
+                let mark = if endpoint.up {
+                    Icon::Check
+                } else {
+                    Icon::Cross
+                };
+                format!("{} {}", name, self.glyph(mark))
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        format!("{} | {}", status_text, summary)
+    }
+
+    /// Name a compared endpoint that beats the active one (`network.compare_endpoints`)
+    /// e.g. `🟡 P95:1200ms | alt proxy.example.com 410ms`; unchanged when the active endpoint is best
+    pub fn render_with_alternative(
+        &self,
+        status_text: String,
+        state: &MonitoringSnapshot,
+    ) -> String {
+        let Some((url, endpoint)) = state
+            .comparison
+            .as_ref()
+            .and_then(|comparison| comparison.better_alternative())
+        else {
+            return status_text;
+        };
+        format!(
+            "{} | {} {} {}",
+            status_text,
+            self.text(Text::Alternative),
+            extract_host(url).unwrap_or_else(|_| url.to_string()),
+            self.format.latency_ms(endpoint.median_ms() as u64)
+        )
+    }
+
+    /// Render the compact multi-target strip, one `<label><mark>` per target
+    /// Marks: ✓ healthy, ~ degraded, ✗ error, ? unknown, ⛔ blocked_by_policy (e.g. `A✓ R✗`)
+    pub fn render_target_strip(&self, targets: &[(String, NetworkStatus)]) -> String {
+        targets
+            .iter()
+            .map(|(label, status)| {
+                let mark = match status {
+                    NetworkStatus::Healthy => self.glyph(Icon::Check),
+                    NetworkStatus::Degraded => "~",
+                    NetworkStatus::Error => self.glyph(Icon::Cross),
+                    NetworkStatus::Unknown => "?",
+                    NetworkStatus::BlockedByPolicy => self.glyph(Icon::Blocked),
+                };
+                format!("{}{}", label, mark)
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Append the penalty cooldown hint while probes are deferred after 429 / bot challenge
+    /// e.g. `🟡 P95:1200ms | cooldown 4m`; unchanged when no cooldown is active
+    pub fn render_with_cooldown(&self, status_text: String, remaining_secs: Option<u64>) -> String {
+        match remaining_secs {
+            Some(secs) if secs >= 60 => format!(
+                "{} | {} {}m",
+                status_text,
+                self.text(Text::Cooldown),
+                secs.div_ceil(60)
+            ),
+            Some(secs) => format!("{} | {} {}s", status_text, self.text(Text::Cooldown), secs),
+            None => status_text,
+        }
+    }
+
+    /// Append the unverified TLS marker while `[network.tls] insecure_skip_verify` is on
+    /// e.g. `🟢 P95:420ms | ⚠ TLS unverified`; unchanged otherwise
+    pub fn render_with_insecure_tls(&self, status_text: String, insecure: bool) -> String {
+        if insecure {
+            format!(
+                "{} | {} {}",
+                status_text,
+                self.glyph(Icon::Warning),
+                self.text(Text::TlsInsecure)
+            )
+        } else {
+            status_text
+        }
+    }
+
+    /// Append the availability badge over `window` (segment option `uptime`)
+    /// e.g. `🟢 P95:420ms | 99.2%`; unchanged when unset or nothing was observed yet
+    pub fn render_with_uptime(
+        &self,
+        status_text: String,
+        state: &MonitoringSnapshot,
+        window: Option<UptimeWindow>,
+    ) -> String {
+        match window.and_then(|window| state.uptime.availability_for(window)) {
+            Some(percent) => format!("{} | {}", status_text, format_availability(percent)),
+            None => status_text,
+        }
+    }
+
+    /// Append the error budget burn rate while it is at or above `burn_alert`
+    /// (`[network.error_budget]`), e.g. `🟡 P95:... | budget 12.5x`
+    pub fn render_with_error_budget(
+        &self,
+        status_text: String,
+        state: &MonitoringSnapshot,
+    ) -> String {
+        match state.error_budget.as_ref().filter(|budget| budget.burning) {
+            Some(budget) => format!(
+                "{} | {} {:.1}x",
+                status_text,
+                self.text(Text::Budget),
+                budget.burn_rate
+            ),
+            None => status_text,
+        }
+    }
+
+    /// Append the rate limit reset countdown after a 429, from `retry-after` or
+    /// the `anthropic-ratelimit-*-reset` headers, e.g. `🔴 ... | resets in 42s`;
+    /// unchanged for other statuses or once the reset has passed at `now` (Unix seconds)
+    pub fn render_with_rate_limit(
+        &self,
+        status_text: String,
+        state: &MonitoringSnapshot,
+        now: i64,
+    ) -> String {
+        match rate_limit_countdown(state, now) {
+            Some(secs) => format!(
+                "{} | {} {}",
+                status_text,
+                self.text(Text::ResetsIn),
+                format_countdown(secs)
+            ),
+            None => status_text,
+        }
+    }
+
+    /// Render the multi-line `ccstatus net status` report from the persisted state
+    ///
+    /// Includes the last probe error with its response header snapshot so users can see
+    /// exactly what the edge returned (cf-ray, retry-after, server, via).
+    pub fn render_report(&self, state: &MonitoringSnapshot) -> String {
+        let mut lines = vec![format!(
+            "Status: {}",
+            self.render_status(&state.status, &state.network, state.api_config.as_ref())
+        )];
+
+        if let Some(ref api_config) = state.api_config {
+            lines.push(format!(
+                "Endpoint: {} ({})",
+                api_config.endpoint, api_config.source
+            ));
+        }
+        lines.push(format!("Updated: {}", state.timestamp));
+        lines.push(format!(
+            "P95: {}ms ({} samples)",
+            state.network.p95_latency_ms,
+            state.network.rolling_totals.len()
+        ));
+        if !state.network.window_percentiles.is_empty() {
+            let mut percentiles: Vec<(&String, &u32)> =
+                state.network.window_percentiles.iter().collect();
+            percentiles.sort_by(|a, b| {
+                let percent = |key: &str| key[1..].parse::<f64>().unwrap_or_default();
+                percent(a.0).total_cmp(&percent(b.0))
+            });
+            let percentiles: Vec<String> = percentiles
+                .into_iter()
+                .map(|(key, ms)| format!("{} {}ms", key.to_uppercase(), ms))
+                .collect();
+            lines.push(format!("Percentiles: {}", percentiles.join(", ")));
+        }
+        if let Some(trend) = stats::trend(
+            &state.network.rolling_totals,
+            stats::TREND_THRESHOLD_PERCENT,
+        ) {
+            lines.push(format!(
+                "Trend: {} ({:+.0}%)",
+                trend.direction.as_str(),
+                trend.change_percent
+            ));
+        }
+        if !state.network.rolling_totals.is_empty() {
+            lines.push(format!(
+                "Distribution: {}",
+                Histogram::new(&stats::LATENCY_BUCKETS_MS, &state.network.rolling_totals).render()
+            ));
+        }
+        if let Some(dns_cache) = state.network.dns_cache {
+            lines.push(match state.network.dns_lookup_ms {
+                Some(lookup_ms) => {
+                    format!("DNS cache: {} (lookup {}ms)", dns_cache.as_str(), lookup_ms)
+                }
+                None => format!("DNS cache: {}", dns_cache.as_str()),
+            });
+        }
+        if let Some(ref connection) = state.network.connection {
+            lines.push(render_connection(connection));
+            if let Some(ref chain) = connection.certificate {
+                lines.push(render_certificate(chain));
+            }
+        }
+        if let Some(ref rate_limit) = state.network.rate_limit {
+            lines.push(render_rate_limit(rate_limit));
+        }
+
+        if let Some(level) = state.network.get_proxy_health_level() {
+            let level = match level {
+                ProxyHealthLevel::Healthy => "healthy",
+                ProxyHealthLevel::Degraded => "degraded",
+                ProxyHealthLevel::Bad => "bad",
+                ProxyHealthLevel::Unknown => "unknown",
+            };
+            let detail = state.network.proxy_health_detail.as_ref();
+            match detail.and_then(|detail| detail.reason.as_deref()) {
+                Some(reason) => lines.push(format!("Proxy: {} ({})", level, reason)),
+                None => lines.push(format!("Proxy: {}", level)),
+            }
+            if let Some(guidance) = detail.and_then(|detail| detail.guidance.as_deref()) {
+                lines.push(format!("  Hint: {}", guidance));
+            }
+            if let Some(excerpt) = detail.and_then(|detail| detail.body_excerpt.as_deref()) {
+                lines.push(format!("  Response: {}", excerpt));
+            }
+            if let Some(warning) = detail.and_then(|detail| detail.certificate_warning.as_deref()) {
+                lines.push(format!("  Certificate: {}", warning));
+            }
+        }
+
+        if let Some(ref shared) = state.shared_baseline {
+            lines.push(format!(
+                "Shared baseline: {} samples from {} machines (synced {})",
+                shared.totals.len(),
+                shared.machines,
+                shared.synced_at
+            ));
+        }
+
+        let availability: Vec<String> = [UptimeWindow::Day, UptimeWindow::Week]
+            .into_iter()
+            .filter_map(|window| {
+                let percent = state.uptime.availability_for(window)?;
+                Some(format!(
+                    "{} {}",
+                    window.as_str(),
+                    format_availability(percent)
+                ))
+            })
+            .collect();
+        if !availability.is_empty() {
+            lines.push(format!("Availability: {}", availability.join(", ")));
+        }
+
+        if let Some(ref budget) = state.error_budget {
+            lines.push(format!(
+                "Error budget: {}% over {}h, {}/{} failed ({} left); burn {:.1}x over {}m{}",
+                budget.allowed_failure_percent,
+                budget.window_hours,
+                budget.failed,
+                budget.probes,
+                format_availability(budget.remaining_percent()),
+                budget.burn_rate,
+                budget.burn_window_minutes,
+                if budget.burning {
+                    format!(", at or above {}x", budget.burn_alert)
+                } else {
+                    String::new()
+                }
+            ));
+        }
+
+        if let Some(score) = state.health_score {
+            lines.push(format!("Health score: {}%", score));
+            for (name, endpoint) in &state.endpoints {
+                lines.push(format!(
+                    "  {}: HTTP {} {} ({}ms)",
+                    name,
+                    endpoint.http_status,
+                    if endpoint.up { "up" } else { "down" },
+                    endpoint.latency_ms
+                ));
+            }
+        }
+
+        if let Some(ref families) = state.ip_families {
+            lines.push(format!("IP families (checked {}):", families.checked_at));
+            for family in [IpFamily::V4, IpFamily::V6] {
+                lines.push(render_family_stats(family, families.stats(family)));
+            }
+            if let Some(verdict) = families.verdict() {
+                lines.push(format!("  Warning: {}", verdict));
+            }
+        }
+
+        if let Some(ref comparison) = state.comparison {
+            let best = comparison.best_alternative().map(|(url, _)| url);
+            lines.push("Compared endpoints:".to_string());
+            for (url, endpoint) in &comparison.endpoints {
+                let role = if *url == comparison.active {
+                    " (active)"
+                } else if Some(url.as_str()) == best {
+                    " (best alternative)"
+                } else {
+                    ""
+                };
+                lines.push(format!(
+                    "  {}{}: HTTP {} {}ms, median {}ms ({} samples)",
+                    url,
+                    role,
+                    endpoint.http_status,
+                    endpoint.latency_ms,
+                    endpoint.median_ms(),
+                    endpoint.recent_ms.len()
+                ));
+            }
+        }
+
+        if state.monitoring_state.activity == ActivityClass::Idle {
+            lines.push(format!(
+                "Activity: idle since {} (GREEN cadence stretched)",
+                state
+                    .monitoring_state
+                    .activity_changed_at
+                    .as_deref()
+                    .unwrap_or("unknown")
+            ));
+        }
+
+        if let Some(ref pinned) = state.monitoring_state.probe_profile {
+            lines.push(format!(
+                "Probe profile: {} (session {} since {})",
+                pinned.profile, pinned.session_id, pinned.since
+            ));
+        }
+
+        if state.monitoring_state.red_watchdog_triggered {
+            lines.push(format!(
+                "RED watchdog: triggered (stale transcript error since {})",
+                state
+                    .monitoring_state
+                    .red_since
+                    .as_deref()
+                    .unwrap_or("unknown")
+            ));
+        }
+
+        match state.last_probe_error {
+            Some(ref error) => {
+                lines.push(format!(
+                    "Last probe error: HTTP {} {} at {}",
+                    error.http_status, error.error_type, error.timestamp
+                ));
+                for (name, value) in &error.headers {
+                    lines.push(format!("  {}: {}", name, value));
+                }
+            }
+            None => lines.push("Last probe error: none".to_string()),
+        }
+
+        lines.join("\n")
+    }
+
+    /// Render per-version probe statistics for `ccstatus net report --compare-versions`
+    ///
+    /// The last line compares the two most recent versions and flags a likely
+    /// regression when P95 grows by more than 20% or the error rate by more than 5 points.
+    pub fn render_version_comparison(&self, summaries: &[VersionSummary]) -> String {
+        if summaries.is_empty() {
+            return "No probe history recorded yet".to_string();
+        }
+
+        let percent = |count: usize, total: usize| (count * 100).checked_div(total).unwrap_or(0);
+        let mut lines = vec![format!(
+            "{:<10} {:>6} {:>8} {:>8} {:>8} {:>8} {:>6}",
+            "Version", "Probes", "P50", "P95", "Healthy", "Degraded", "Error"
+        )];
+        for summary in summaries {
+            lines.push(format!(
+                "{:<10} {:>6} {:>6}ms {:>6}ms {:>7}% {:>7}% {:>5}%",
+                summary.version,
+                summary.probes,
+                summary.p50_latency_ms,
+                summary.p95_latency_ms,
+                percent(summary.healthy, summary.probes),
+                percent(summary.degraded, summary.probes),
+                percent(summary.error, summary.probes)
+            ));
+        }
+
+        if let [.., previous, current] = summaries {
+            let p95_delta = current.p95_latency_ms as i64 - previous.p95_latency_ms as i64;
+            let p95_change = stats::percent_change(
+                previous.p95_latency_ms as f64,
+                current.p95_latency_ms as f64,
+            );
+            let error_delta = current.error_rate() - previous.error_rate();
+            let mut line = format!(
+                "{} → {}: P50 {:+}ms, P95 {:+}ms ({:+.0}%), error rate {:+.1}pp",
+                previous.version,
+                current.version,
+                current.p50_latency_ms as i64 - previous.p50_latency_ms as i64,
+                p95_delta,
+                p95_change,
+                error_delta
+            );
+            if p95_change > 20.0 || error_delta > 5.0 {
+                line.push(' ');
+                line.push_str(self.glyph(Icon::Warning));
+                line.push_str(" possible regression");
+            }
+            lines.push(line);
+        }
+
+        lines.join("\n")
+    }
+
+    /// Render `ccstatus history`: one row per state partition over `range`,
+    /// then the newest probes of the window when `recent` is not empty
+    pub fn render_history(
+        &self,
+        range: &str,
+        summaries: &[(String, HistorySummary)],
+        recent: &[(&str, &ProbeRecord)],
+    ) -> String {
+        let percent = |count: usize, total: usize| (count * 100).checked_div(total).unwrap_or(0);
+        let mut lines = vec![
+            format!("Probe history, last {}", range),
+            format!(
+                "{:<12} {:>6} {:>8} {:>8} {:>8} {:>8} {:>8} {:>6}",
+                "Target", "Probes", "P50", "P95", "P99", "Healthy", "Degraded", "Error"
+            ),
+        ];
+        for (target, summary) in summaries {
+            if summary.probes == 0 {
+                lines.push(format!("{:<12} {:>6}  no probes in range", target, 0));
+                continue;
+            }
+            let ms = |value: u32| {
+                if summary.successful == 0 {
+                    "-".to_string()
+                } else {
+                    format!("{}ms", value)
+                }
+            };
+            lines.push(format!(
+                "{:<12} {:>6} {:>8} {:>8} {:>8} {:>7}% {:>7}% {:>5}%",
+                target,
+                summary.probes,
+                ms(summary.p50_latency_ms),
+                ms(summary.p95_latency_ms),
+                ms(summary.p99_latency_ms),
+                percent(summary.healthy, summary.probes),
+                percent(summary.degraded, summary.probes),
+                percent(summary.error, summary.probes)
+            ));
+        }
+
+        if !recent.is_empty() {
+            lines.push(String::new());
+            for (target, record) in recent {
+                lines.push(format!(
+                    "{}  {:<12} {:<5} {:>3} {:>6}ms  {}",
+                    record.timestamp,
+                    target,
+                    record.mode,
+                    record.http_status,
+                    record.latency_ms,
+                    record.status.as_str()
+                ));
+            }
+        }
+        lines.join("\n")
+    }
+
+    /// Render warm-standby region measurements for `ccstatus net regions`
+    ///
+    /// Regions are listed fastest median first; unreachable ones go last.
+    pub fn render_region_report(&self, summaries: &[RegionSummary], now: i64) -> String {
+        if summaries.is_empty() {
+            return "No standby regions measured yet".to_string();
+        }
+
+        let mut sorted: Vec<&RegionSummary> = summaries.iter().collect();
+        sorted.sort_by_key(|summary| (summary.reachable == 0, summary.p50_latency_ms));
+
+        let mut lines = vec![format!(
+            "{:<12} {:>8} {:>8} {:>9} {:>14}  {}",
+            "Region", "P50", "P95", "Reachable", "Last", "URL"
+        )];
+        for summary in sorted {
+            let last = if summary.last.is_reachable() {
+                format!(
+                    "{}ms {} ago",
+                    summary.last.latency_ms,
+                    format_age(now - summary.last.at)
+                )
+            } else {
+                format!("down {} ago", format_age(now - summary.last.at))
+            };
+            lines.push(format!(
+                "{:<12} {:>6}ms {:>6}ms {:>5}/{:<3} {:>14}  {}",
+                summary.name,
+                summary.p50_latency_ms,
+                summary.p95_latency_ms,
+                summary.reachable,
+                summary.samples,
+                last,
+                summary.url
+            ));
+        }
+
+        lines.join("\n")
+    }
+
+    /// Render monthly SLA compliance for `ccstatus net sla`
+    ///
+    /// One row per `[[network.sla]]` target; undeclared targets show `-` and
+    /// endpoints without probes that month show `no data`.
+    pub fn render_sla_report(&self, month: &str, compliance: &[SlaCompliance]) -> String {
+        let mut lines = vec![
+            format!("SLA compliance for {} (UTC)", month),
+            format!(
+                "{:<12} {:>8} {:>9} {:>9} {:>8} {:>8}  {}",
+                "Endpoint", "Probes", "Available", "Target", "P95", "Target", "Result"
+            ),
+        ];
+        for row in compliance {
+            let stats = row.stats.as_ref();
+            let available = stats
+                .and_then(|stats| stats.availability_percent())
+                .map_or("-".to_string(), |percent| format!("{:.2}%", percent));
+            let p95 = stats
+                .and_then(|stats| stats.p95_latency_ms())
+                .map_or("-".to_string(), |ms| format!("{}ms", ms));
+            let result = match row.is_met() {
+                Some(true) => "met",
+                Some(false) => "missed",
+                None => "no data",
+            };
+            lines.push(format!(
+                "{:<12} {:>8} {:>9} {:>9} {:>8} {:>8}  {}",
+                row.target.endpoint,
+                stats.map_or(0, |stats| stats.probes),
+                available,
+                row.target
+                    .availability
+                    .map_or("-".to_string(), |percent| format!("≥{:.2}%", percent)),
+                p95,
+                row.target
+                    .p95_ms
+                    .map_or("-".to_string(), |ms| format!("<{}ms", ms)),
+                result
+            ));
+        }
+
+        let judged: Vec<bool> = compliance.iter().filter_map(|row| row.is_met()).collect();
+        lines.push(format!(
+            "\n{} of {} endpoints met their SLA",
+            judged.iter().filter(|met| **met).count(),
+            judged.len()
+        ));
+        lines.join("\n")
+    }
+
+    /// Render passive mode status when live probes have not been consented to
+    /// ⚪ probes off: no transcript errors; 🔴 probes off | API error NNN: last transcript error
+    pub fn render_probes_off(&self, last_error: Option<&JsonlError>) -> String {
+        self.render_off(Text::ProbesOff, last_error)
+    }
+
+    /// Render transcript-only status for OAuth credentials with `network.oauth_probe` off
+    /// ⚪ oauth off: no transcript errors; 🔴 oauth off | API error NNN: last transcript error
+    pub fn render_oauth_off(&self, last_error: Option<&JsonlError>) -> String {
+        self.render_off(Text::OAuthOff, last_error)
+    }
+
+    fn render_off(&self, hint: Text, last_error: Option<&JsonlError>) -> String {
+        match last_error {
+            Some(error) => format!(
+                "{} {} | {}",
+                self.glyph(Icon::Error),
+                self.text(hint),
+                self.link_error(
+                    &format!("{} {}", self.text(Text::ApiError), error.code),
+                    &ErrorTracker::new().classify_http_status(error.code)
+                )
+            ),
+            None => format!("{} {}", self.glyph(Icon::Unknown), self.text(hint)),
+        }
+    }
+
     /// Format status with breakdown, wrapping to next line if too long
     fn format_with_breakdown(&self, base: String, breakdown: &str) -> String {
         if breakdown.is_empty() {
-            base
-        } else {
-            let max_line_length = 80;
-            if base.len() + breakdown.len() + 1 > max_line_length {
-                format!("{}\n{}", base, breakdown)
-            } else {
-                format!("{} {}", base, breakdown)
-            }
+            return base;
         }
+
+        // Append in place: reuses `base`'s buffer instead of formatting a new string
+        let max_line_length = 80;
+        let mut line = base;
+        let separator = if line.len() + breakdown.len() + 1 > max_line_length {
+            '\n'
+        } else {
+            ' '
+        };
+        line.reserve(breakdown.len() + 1);
+        line.push(separator);
+        line.push_str(breakdown);
+        line
     }
 
     /// Render shield indicators for bot challenges
@@ -121,27 +863,36 @@ impl StatusRenderer {
         match (proxy_blocked, post_blocked) {
             (true, true) => {
                 // Both GET and POST blocked
+                let shield = self.glyph(Icon::BotChallenge);
                 format!(
-                    "GET 🛡️ Bot challenge | POST 🛡️ Total: {}ms",
-                    metrics.latency_ms
+                    "GET {} {} | POST {} Total: {}",
+                    shield,
+                    self.text(Text::BotChallenge),
+                    shield,
+                    self.format.latency_ms(metrics.latency_ms as u64)
                 )
             }
             (true, false) => {
                 // Only GET blocked - show proxy challenge with normal P95 info
-                let p95_display = if metrics.p95_latency_ms == 0 {
-                    "P95:N/A".to_string()
-                } else {
-                    format!("P95:{}ms", metrics.p95_latency_ms)
-                };
-                format!("🛡️ Bot challenge | {}", p95_display)
+                let p95_display = self.p95_display(metrics);
+                format!(
+                    "{} {} | {}",
+                    self.glyph(Icon::BotChallenge),
+                    self.text(Text::BotChallenge),
+                    p95_display
+                )
             }
             (false, true) => {
                 // Only POST blocked - show total time suppressed breakdown
-                format!("🛡️ Total: {}ms", metrics.latency_ms)
+                format!(
+                    "{} Total: {}",
+                    self.glyph(Icon::BotChallenge),
+                    self.format.latency_ms(metrics.latency_ms as u64)
+                )
             }
             (false, false) => {
                 // Neither blocked (shouldn't reach here)
-                "🛡️ Bot challenge detected".to_string()
+                format!("{} Bot challenge detected", self.glyph(Icon::BotChallenge))
             }
         }
     }
@@ -152,11 +903,11 @@ impl StatusRenderer {
         let mut parts = Vec::new();
 
         // Add green status indicator for OAuth mode
-        parts.push("🟢".to_string());
+        parts.push(self.glyph(Icon::Healthy).to_string());
 
         // Add P95 if available
         if metrics.p95_latency_ms > 0 {
-            parts.push(format!("P95:{}ms", metrics.p95_latency_ms));
+            parts.push(self.p95_display(metrics));
         }
 
         // Add timing breakdown if available
@@ -172,7 +923,11 @@ impl StatusRenderer {
         // Join parts with space separator, or return minimal info if nothing available
         if parts.len() == 1 {
             // Only emoji present
-            "🟢 OAuth mode".to_string()
+            format!(
+                "{} {}",
+                self.glyph(Icon::Healthy),
+                self.text(Text::OAuthMode)
+            )
         } else {
             parts.join(" ")
         }
@@ -184,3 +939,144 @@ impl Default for StatusRenderer {
         Self::new()
     }
 }
+
+/// Short label for a transport failure `error_type`; `None` for the rest
+fn transport_hint(error_type: &str) -> Option<Text> {
+    match error_type {
+        "dns_error" => Some(Text::DnsFailed),
+        "connect_refused" => Some(Text::ConnectRefused),
+        "tls_error" => Some(Text::TlsFailed),
+        "timeout" => Some(Text::TimedOut),
+        _ => None,
+    }
+}
+
+/// Compact age: `45s`, `12m`, `3h`, `2d`
+fn format_age(secs: i64) -> String {
+    let secs = secs.max(0);
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m", secs / 60),
+        3600..=86399 => format!("{}h", secs / 3600),
+        _ => format!("{}d", secs / 86400),
+    }
+}
+
+/// Seconds until the rate limit lifts while the last probe was a 429
+pub fn rate_limit_countdown(state: &MonitoringSnapshot, now: i64) -> Option<u64> {
+    if state.network.last_http_status != 429 {
+        return None;
+    }
+    state.network.rate_limit.as_ref()?.retry_in_secs(now)
+}
+
+/// `42s`, `4m` (rounded up) or `2h05m`
+fn format_countdown(secs: u64) -> String {
+    match secs {
+        0..60 => format!("{}s", secs),
+        60..3600 => format!("{}m", secs.div_ceil(60)),
+        _ => {
+            let minutes = secs.div_ceil(60);
+            format!("{}h{:02}m", minutes / 60, minutes % 60)
+        }
+    }
+}
+
+/// `Rate limit: requests 0/50 (reset 2025-01-25T18:31:00Z), tokens 39000/40000; retry after 42s`
+fn render_rate_limit(rate_limit: &RateLimitInfo) -> String {
+    let mut quotas: Vec<String> = rate_limit
+        .quotas
+        .iter()
+        .map(|(kind, quota)| {
+            let remaining = quota
+                .remaining
+                .map_or_else(|| "?".to_string(), |n| n.to_string());
+            let mut text = match quota.limit {
+                Some(limit) => format!("{} {}/{}", kind, remaining, limit),
+                None => format!("{} {} left", kind, remaining),
+            };
+            if let Some(ref reset) = quota.reset {
+                text.push_str(&format!(" (reset {})", reset));
+            }
+            text
+        })
+        .collect();
+    if quotas.is_empty() {
+        quotas.push("no quota headers".to_string());
+    }
+    let mut line = format!("Rate limit: {}", quotas.join(", "));
+    if let Some(secs) = rate_limit.retry_after_secs {
+        line.push_str(&format!("; retry after {}s", secs));
+    }
+    line
+}
+
+/// `99.2%`, rounded down so a badge never overstates availability (`100%` only without downtime)
+fn format_availability(percent: f64) -> String {
+    let tenths = (percent * 10.0).floor() / 10.0;
+    if tenths >= 100.0 {
+        "100%".to_string()
+    } else {
+        format!("{:.1}%", tenths)
+    }
+}
+
+/// `  IPv6: median 180ms over 10 checks, last 2 failed (connection refused)`
+fn render_family_stats(family: IpFamily, stats: &FamilyStats) -> String {
+    let mut line = match stats.median_ms() {
+        Some(median) => format!(
+            "  {}: median {}ms over {} checks",
+            family,
+            median,
+            stats.samples_ms.len()
+        ),
+        None => format!("  {}: no successful checks", family),
+    };
+    if stats.consecutive_failures > 0 {
+        line.push_str(&format!(", last {} failed", stats.consecutive_failures));
+        if let Some(ref error) = stats.last_error {
+            line.push_str(&format!(" ({})", error));
+        }
+    }
+    line
+}
+
+/// `Certificate: <subject> (issuer <issuer>, expires <date>)`, flagged when untrusted
+fn render_certificate(chain: &CertificateChain) -> String {
+    let leaf = chain.leaf().cloned().unwrap_or_default();
+    let mut details = vec![format!("issuer {}", leaf.issuer)];
+    if let Some(not_after) = chain.not_after() {
+        details.push(format!("expires {}", format_date(not_after)));
+    }
+    if !chain.trusted {
+        details.push("untrusted".to_string());
+    }
+    format!("Certificate: {} ({})", leaf.subject, details.join(", "))
+}
+
+/// `Served by: 160.79.104.10 (IPv4, new connection, 2 redirects +120ms)`
+fn render_connection(connection: &ConnectionInfo) -> String {
+    let mut details: Vec<String> = connection
+        .ip_family()
+        .map(|family| family.to_string())
+        .into_iter()
+        .collect();
+    details.push(if connection.is_reused() {
+        "reused connection".to_string()
+    } else {
+        "new connection".to_string()
+    });
+    match connection.redirect_count {
+        0 => {}
+        1 => details.push(format!("1 redirect +{}ms", connection.redirect_ms)),
+        count => details.push(format!("{} redirects +{}ms", count, connection.redirect_ms)),
+    }
+    format!(
+        "Served by: {} ({})",
+        connection
+            .primary_ip
+            .as_deref()
+            .unwrap_or("unknown address"),
+        details.join(", ")
+    )
+}
@@ -0,0 +1,118 @@
+//! Probe history as Grafana JSON time series (`GET /api/timeseries`)
+//!
+//! The metrics listener of `ccstatus export --prometheus --listen` also answers
+//! `/api/timeseries?metric=latency&range=6h`, reading the probe history of each
+//! state partition. The payload is the `/query` response of Grafana's
+//! simple-json-datasource: one series per partition, named after it, with
+//! `[value, unix_ms]` datapoints oldest first.
+
+use crate::core::network::probe_history::{records_between, ProbeRecord};
+use serde_json::json;
+
+/// Range when the query has none
+pub const DEFAULT_RANGE_SECS: i64 = 6 * 60 * 60;
+
+/// Per-probe value to chart
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeseriesMetric {
+    /// Total latency in milliseconds
+    Latency,
+    /// 0 healthy, 1 degraded, 2 error, 3 unknown (as `ccstatus net check`)
+    Status,
+    /// HTTP status (0 = no response)
+    HttpStatus,
+}
+
+impl TimeseriesMetric {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "latency" => Some(Self::Latency),
+            "status" => Some(Self::Status),
+            "http_status" => Some(Self::HttpStatus),
+            _ => None,
+        }
+    }
+
+    fn value(self, record: &ProbeRecord) -> f64 {
+        match self {
+            Self::Latency => record.latency_ms as f64,
+            Self::Status => record.status.exit_code() as f64,
+            Self::HttpStatus => record.http_status as f64,
+        }
+    }
+}
+
+/// `metric`, `range` and optional `target` of a `/api/timeseries` request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeseriesQuery {
+    pub metric: TimeseriesMetric,
+    pub range_secs: i64,
+    /// Only this partition; every partition when None
+    pub target: Option<String>,
+}
+
+impl TimeseriesQuery {
+    /// Parse a query string such as `metric=latency&range=6h`
+    pub fn parse(query: &str) -> Result<Self, String> {
+        let mut metric = None;
+        let mut range_secs = DEFAULT_RANGE_SECS;
+        let mut target = None;
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            match key {
+                "metric" => {
+                    metric = Some(TimeseriesMetric::parse(value).ok_or_else(|| {
+                        format!("unknown metric '{}' (latency, status, http_status)", value)
+                    })?)
+                }
+                "range" => {
+                    range_secs = parse_range(value)
+                        .ok_or_else(|| format!("invalid range '{}' (e.g. 30m, 6h, 7d)", value))?
+                }
+                "target" if !value.is_empty() => target = Some(value.to_string()),
+                _ => {}
+            }
+        }
+        Ok(Self {
+            metric: metric.ok_or("missing metric (latency, status, http_status)")?,
+            range_secs,
+            target,
+        })
+    }
+}
+
+/// `30s`, `15m`, `6h` or `7d` in seconds; None for anything else or zero
+pub fn parse_range(range: &str) -> Option<i64> {
+    let unit = match range.chars().last()? {
+        's' => 1,
+        'm' => 60,
+        'h' => 60 * 60,
+        'd' => 24 * 60 * 60,
+        _ => return None,
+    };
+    let count: i64 = range[..range.len() - 1].parse().ok()?;
+    (count > 0).then(|| count.checked_mul(unit)).flatten()
+}
+
+/// simple-json-datasource payload for the records within `range_secs` of `now_ms`
+///
+/// Records with an unparseable timestamp are skipped.
+pub fn render_timeseries(
+    histories: &[(String, Vec<ProbeRecord>)],
+    query: &TimeseriesQuery,
+    now_ms: i64,
+) -> String {
+    let from_ms = now_ms.saturating_sub(query.range_secs.saturating_mul(1000));
+    let series: Vec<_> = histories
+        .iter()
+        .filter(|(name, _)| query.target.as_ref().is_none_or(|target| target == name))
+        .map(|(name, records)| {
+            let datapoints: Vec<(f64, i64)> = records_between(records, from_ms, now_ms)
+                .into_iter()
+                .filter_map(|record| Some((query.metric.value(record), record.timestamp_millis()?)))
+                .collect();
+            json!({ "target": name, "datapoints": datapoints })
+        })
+        .collect();
+    serde_json::Value::Array(series).to_string()
+}
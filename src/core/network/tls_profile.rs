@@ -0,0 +1,45 @@
+//! TLS fingerprint parity with the Claude Code client (`tls-parity` feature)
+//!
+//! Some gateways fingerprint the TLS ClientHello (JA3/JA4) and treat libcurl's
+//! defaults differently from Claude Code, which runs on Node.js. With the
+//! `tls-parity` feature every curl request (API probe and proxy health GET)
+//! is shaped like Node's default client:
+//!
+//! - TLS 1.2 cipher suites in Node's default order (`tls.DEFAULT_CIPHERS`)
+//! - TLS 1.2 minimum, TLS 1.3 maximum
+//! - ALPN `http/1.1` only (Node's `fetch` does not negotiate HTTP/2)
+//!
+//! Parity is best-effort: the curl crate does not expose curve or extension
+//! ordering, so the JA3 hash can still differ on the supported-groups field.
+//! For a byte-exact ClientHello, link against curl-impersonate instead of the
+//! system libcurl. The isahc fallback path keeps its default fingerprint.
+
+use curl::easy::{Easy, HttpVersion, SslVersion};
+
+/// Node.js default TLS 1.2 cipher list, in ClientHello order
+///
+/// TLS 1.3 suites are negotiated by OpenSSL's own defaults, which Node shares.
+pub const NODE_TLS12_CIPHER_LIST: &str = concat!(
+    "ECDHE-RSA-AES128-GCM-SHA256:",
+    "ECDHE-ECDSA-AES128-GCM-SHA256:",
+    "ECDHE-RSA-AES256-GCM-SHA384:",
+    "ECDHE-ECDSA-AES256-GCM-SHA384:",
+    "DHE-RSA-AES128-GCM-SHA256:",
+    "ECDHE-RSA-AES128-SHA256:",
+    "DHE-RSA-AES128-SHA256:",
+    "ECDHE-RSA-AES256-SHA384:",
+    "DHE-RSA-AES256-SHA384:",
+    "ECDHE-RSA-AES256-SHA256:",
+    "DHE-RSA-AES256-SHA256:",
+    "HIGH:!aNULL:!eNULL:!EXPORT:!DES:!RC4:!MD5:!PSK:!SRP:!CAMELLIA"
+);
+
+/// Shape a curl handle's TLS handshake like Claude Code's Node.js client
+///
+/// Replaces the HTTP/2-over-TLS default used when the feature is off.
+pub fn apply_claude_code_tls_profile(handle: &mut Easy) -> Result<(), curl::Error> {
+    handle.http_version(HttpVersion::V11)?;
+    handle.ssl_min_max_version(SslVersion::Tlsv12, SslVersion::Tlsv13)?;
+    handle.ssl_cipher_list(NODE_TLS12_CIPHER_LIST)?;
+    Ok(())
+}
@@ -1,5 +1,13 @@
 // Core types for network monitoring
+use crate::core::network::certificate::CertificateChain;
+use crate::core::network::dns_cache::DnsCacheStatus;
+use crate::core::network::error_budget::ErrorBudget;
+use crate::core::network::ip_family::{IpFamily, IpFamilyComparison};
+use crate::core::network::probe_profile::SessionProfile;
 use crate::core::network::proxy_health::config::ProxyHealthLevel;
+use crate::core::stats;
+use smallvec::SmallVec;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 
 // Re-export credential types from existing module (don't move them)
@@ -17,6 +25,43 @@ pub enum NetworkStatus {
     /// No credentials configured or monitoring disabled
     #[default]
     Unknown,
+    /// Probe target rejected by the `network.allowed_hosts` policy (no request sent)
+    BlockedByPolicy,
+}
+
+impl NetworkStatus {
+    /// Stable snake_case keyword for machine consumers
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NetworkStatus::Healthy => "healthy",
+            NetworkStatus::Degraded => "degraded",
+            NetworkStatus::Error => "error",
+            NetworkStatus::Unknown => "unknown",
+            NetworkStatus::BlockedByPolicy => "blocked_by_policy",
+        }
+    }
+
+    /// Exit code of `ccstatus net check`: 0 healthy, 1 degraded, 2 error, 3 unknown
+    ///
+    /// A probe blocked by `network.allowed_hosts` never reached the API, so it is unknown.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            NetworkStatus::Healthy => 0,
+            NetworkStatus::Degraded => 1,
+            NetworkStatus::Error => 2,
+            NetworkStatus::Unknown | NetworkStatus::BlockedByPolicy => 3,
+        }
+    }
+
+    /// Rank from healthy to error; None for statuses that say nothing about the API
+    fn severity(&self) -> Option<u8> {
+        match self {
+            NetworkStatus::Healthy => Some(0),
+            NetworkStatus::Degraded => Some(1),
+            NetworkStatus::Error => Some(2),
+            NetworkStatus::Unknown | NetworkStatus::BlockedByPolicy => None,
+        }
+    }
 }
 
 /// Detailed information about proxy health check attempt
@@ -38,6 +83,15 @@ pub struct ProxyHealthDetail {
     /// Values: "cloudflare_challenge", "redirect_followed", "no_endpoint_404",
     /// "non_200_no_cf", "invalid_json_200", "unknown_schema_200", "timeout"
     pub reason: Option<String>,
+    /// Sanitized start of the response body (Bad level only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_excerpt: Option<String>,
+    /// What to check, derived from status code and content type (Bad level only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guidance: Option<String>,
+    /// Untrusted chain or upcoming expiry of a custom gateway's certificate
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub certificate_warning: Option<String>,
 }
 
 /// State tracking for monitoring windows and probe deduplication
@@ -58,8 +112,191 @@ pub struct MonitoringState {
     pub last_cold_probe_at: Option<String>,
     /// Current network monitoring status
     pub state: NetworkStatus,
+    /// Penalty backoff after 429 / bot challenge responses (defers all probe modes)
+    #[serde(default, skip_serializing_if = "PenaltyState::is_clear")]
+    pub penalty: PenaltyState,
+    /// When the current RED streak began (local ISO-8601)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub red_since: Option<String>,
+    /// Raw timestamp of the transcript error driving the current RED streak
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub red_error_at: Option<String>,
+    /// When `state` last changed (local ISO-8601); drives status hooks such as the pause advisory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_since: Option<String>,
+    /// Set when the RED watchdog demoted a stuck RED streak to GREEN cadence
+    #[serde(default)]
+    pub red_watchdog_triggered: bool,
+    /// Cumulative probe counters on this machine (synced as a grow-only ledger)
+    #[serde(default)]
+    pub ledger: ProbeLedger,
+    /// Probes in a row that did not return HTTP 200 (reset by a 200)
+    #[serde(default)]
+    pub consecutive_failures: u32,
+    /// Whether the Claude Code session is active or idle (drives GREEN cadence)
+    #[serde(default)]
+    pub activity: ActivityClass,
+    /// When `activity` last changed (local ISO-8601)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub activity_changed_at: Option<String>,
+    /// Probe profile pinned to the session that selected it (`CCSTATUS_PROBE_PROFILE`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub probe_profile: Option<SessionProfile>,
+    /// Latest classification held back by `[network.hysteresis]`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pending_status: Option<NetworkStatus>,
+    /// Probes in a row classified in the direction of `pending_status`
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub pending_count: u32,
+}
+
+fn is_zero(count: &u32) -> bool {
+    *count == 0
+}
+
+impl MonitoringState {
+    /// Status to show after a probe classified as `classified`, given the shown
+    /// status `shown` (`[network.hysteresis]`)
+    ///
+    /// A change toward worse needs `degrade_after` probes in a row classified
+    /// worse than `shown`, a change toward better `recover_after` probes
+    /// classified better; the latest classification is shown once the streak is
+    /// long enough. Changes from or to Unknown / BlockedByPolicy are never held back.
+    pub fn debounce_status(
+        &mut self,
+        shown: &NetworkStatus,
+        classified: NetworkStatus,
+        policy: &crate::config::HysteresisConfig,
+    ) -> NetworkStatus {
+        let (Some(from), Some(to)) = (shown.severity(), classified.severity()) else {
+            self.clear_pending_status();
+            return classified;
+        };
+        if from == to {
+            self.clear_pending_status();
+            return classified;
+        }
+
+        let worse = to > from;
+        let same_direction = self
+            .pending_status
+            .as_ref()
+            .and_then(NetworkStatus::severity)
+            .is_some_and(|pending| (pending > from) == worse);
+        self.pending_count = if same_direction {
+            self.pending_count.saturating_add(1)
+        } else {
+            1
+        };
+        let required = if worse {
+            policy.degrade_after
+        } else {
+            policy.recover_after
+        };
+        if self.pending_count >= required {
+            self.clear_pending_status();
+            classified
+        } else {
+            self.pending_status = Some(classified);
+            shown.clone()
+        }
+    }
+
+    pub fn clear_pending_status(&mut self) {
+        self.pending_status = None;
+        self.pending_count = 0;
+    }
+}
+
+/// Claude Code session activity, classified from transcript recency
+///
+/// Idle sessions stretch GREEN windows to save probes; any new transcript
+/// activity restores the regular cadence on the next stdin event.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityClass {
+    /// Transcript written recently (or recency unknown)
+    #[default]
+    Active,
+    /// No transcript writes for longer than the idle threshold
+    Idle,
+}
+
+impl std::fmt::Display for ActivityClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ActivityClass::Active => write!(f, "active"),
+            ActivityClass::Idle => write!(f, "idle"),
+        }
+    }
+}
+
+/// Cumulative probe counters, only ever incremented
+///
+/// Synced between machines per machine id; merging takes the per-field maximum.
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ProbeLedger {
+    /// Probes executed
+    pub probes: u64,
+    /// Probes that did not return HTTP 200
+    pub errors: u64,
+}
+
+impl ProbeLedger {
+    /// Per-field maximum (commutative, associative, idempotent)
+    pub fn merge(&self, other: &ProbeLedger) -> ProbeLedger {
+        ProbeLedger {
+            probes: self.probes.max(other.probes),
+            errors: self.errors.max(other.errors),
+        }
+    }
 }
 
+/// GREEN latency samples synced from other machines (`[network.sync]`)
+///
+/// Combined with the local rolling window when computing P80/P95 thresholds.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SharedBaseline {
+    /// Recent successful probe latencies (ms) from other machines
+    pub totals: Vec<u32>,
+    /// Number of machines contributing samples
+    pub machines: u32,
+    /// When the baseline was merged (local ISO-8601)
+    pub synced_at: String,
+}
+
+/// Base penalty backoff after the first 429 / bot challenge (seconds)
+pub const PENALTY_BASE_SECS: u64 = 60;
+/// Upper bound for the exponential penalty backoff (seconds)
+pub const PENALTY_MAX_SECS: u64 = 1800;
+
+/// Exponential penalty backoff after rate limiting or bot challenges
+///
+/// Each consecutive penalized probe doubles the cooldown
+/// (`PENALTY_BASE_SECS * 2^(level-1)`, capped at `PENALTY_MAX_SECS`).
+/// A clean HTTP 200 probe clears the penalty.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PenaltyState {
+    /// Consecutive penalized probes (0 = no penalty)
+    pub level: u32,
+    /// Probes are deferred until this local ISO-8601 timestamp
+    pub until: Option<String>,
+    /// What triggered the penalty: "rate_limit" | "bot_challenge"
+    pub reason: Option<String>,
+}
+
+/// GREEN samples kept in the rolling window by default (~60 min at 300s cadence)
+pub const ROLLING_WINDOW_LEN: usize = 12;
+
+/// Largest window `[network.window] samples` may configure
+pub const MAX_ROLLING_WINDOW_LEN: usize = 120;
+
+/// Rolling latency window, stored inline up to `ROLLING_WINDOW_LEN` samples
+pub type RollingWindow = SmallVec<[u32; ROLLING_WINDOW_LEN]>;
+
+/// Unix seconds at which each rolling sample was taken, oldest first
+pub type RollingTimes = SmallVec<[i64; ROLLING_WINDOW_LEN]>;
+
 /// Network metrics and measurements
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct NetworkMetrics {
@@ -67,7 +304,11 @@ pub struct NetworkMetrics {
     pub breakdown: String, // Format: "DNS:20ms|TCP:30ms|TLS:40ms|TTFB:1324ms|Total:2650ms"
     pub last_http_status: u16,
     pub error_type: Option<String>,
-    pub rolling_totals: Vec<u32>, // Capacity: 12 samples (~60 min at 300s cadence)
+    pub rolling_totals: RollingWindow, // Capacity: ROLLING_WINDOW_LEN samples
+    /// Sample times aligned to the newest `rolling_totals` entries; samples
+    /// recorded before times were tracked have no entry and sit at the front
+    #[serde(default, skip_serializing_if = "SmallVec::is_empty")]
+    pub rolling_sampled_at: RollingTimes,
     pub p95_latency_ms: u32,
     #[serde(default)]
     pub connection_reused: Option<bool>, // Connection reuse detection for display purposes
@@ -82,6 +323,124 @@ pub struct NetworkMetrics {
     pub proxy_health_detail: Option<ProxyHealthDetail>, // Detailed health check information
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub http_version: Option<String>, // HTTP version used for request (e.g., "HTTP/1.1", "HTTP/2.0")
+    /// DNS cache outcome of the last probe (`network.dns_cache` only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dns_cache: Option<DnsCacheStatus>,
+    /// Time spent resolving the probe host on a DNS cache miss
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dns_lookup_ms: Option<u32>,
+    /// Serving address, connections and redirects of the last probe (curl transport only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connection: Option<ConnectionInfo>,
+    /// `[network.window] percentiles` of the window, keyed like `p50` or `p99.9`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub window_percentiles: BTreeMap<String, u32>,
+    /// Remaining quota and reset times from the last probe's rate limit headers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<RateLimitInfo>,
+}
+
+/// Rolling window length and the percentiles computed over it (`[network.window]`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowPolicy {
+    /// GREEN samples kept
+    pub samples: usize,
+    /// Latency at or below this percentile (0-100) of the window is healthy
+    pub healthy_percentile: f64,
+    /// Latency above the healthy cutoff and up to this percentile is degraded, above it error
+    pub degraded_percentile: f64,
+    /// Further percentiles stored in `NetworkMetrics::window_percentiles`
+    pub percentiles: Vec<f64>,
+}
+
+impl Default for WindowPolicy {
+    fn default() -> Self {
+        Self {
+            samples: ROLLING_WINDOW_LEN,
+            healthy_percentile: 80.0,
+            degraded_percentile: 95.0,
+            percentiles: Vec::new(),
+        }
+    }
+}
+
+impl WindowPolicy {
+    /// Defaults overlaid with the set fields of `config`
+    ///
+    /// # Errors
+    ///
+    /// Returns `NetworkError::ValidationError` for `samples` outside
+    /// 1..=`MAX_ROLLING_WINDOW_LEN`, a percentile outside `(0, 100]`, or a
+    /// healthy cutoff above the degraded one.
+    pub fn from_config(config: &crate::config::WindowConfig) -> Result<Self, NetworkError> {
+        let invalid = |message: String| Err(NetworkError::ValidationError(message));
+        let mut policy = Self::default();
+
+        if let Some(samples) = config.samples {
+            if !(1..=MAX_ROLLING_WINDOW_LEN).contains(&samples) {
+                return invalid(format!(
+                    "window samples must be 1-{}, got {}",
+                    MAX_ROLLING_WINDOW_LEN, samples
+                ));
+            }
+            policy.samples = samples;
+        }
+        policy.healthy_percentile = config
+            .healthy_percentile
+            .unwrap_or(policy.healthy_percentile);
+        policy.degraded_percentile = config
+            .degraded_percentile
+            .unwrap_or(policy.degraded_percentile);
+        policy.percentiles = config.percentiles.clone();
+
+        let cutoffs = [policy.healthy_percentile, policy.degraded_percentile];
+        if let Some(bad) = cutoffs
+            .iter()
+            .chain(&policy.percentiles)
+            .find(|p| !(**p > 0.0 && **p <= 100.0))
+        {
+            return invalid(format!("percentiles must be in (0, 100], got {}", bad));
+        }
+        if policy.healthy_percentile > policy.degraded_percentile {
+            return invalid(format!(
+                "healthy_percentile {} is above degraded_percentile {}",
+                policy.healthy_percentile, policy.degraded_percentile
+            ));
+        }
+        Ok(policy)
+    }
+}
+
+/// Key of a percentile in `NetworkMetrics::window_percentiles`: 50 → `p50`, 99.9 → `p99.9`
+pub fn percentile_key(percent: f64) -> String {
+    format!("p{}", percent)
+}
+
+/// Connection details libcurl reports for a probe
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ConnectionInfo {
+    /// Address that served the probe
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub primary_ip: Option<String>,
+    /// New connections opened; 0 when an existing connection was reused
+    pub num_connects: u32,
+    pub redirect_count: u32,
+    /// Time spent following redirects before the final request
+    pub redirect_ms: u32,
+    /// Server certificate chain, on probes that asked for it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub certificate: Option<CertificateChain>,
+}
+
+impl ConnectionInfo {
+    pub fn is_reused(&self) -> bool {
+        self.num_connects == 0
+    }
+
+    /// Family of the address that served the probe
+    pub fn ip_family(&self) -> Option<IpFamily> {
+        self.primary_ip.as_deref().and_then(IpFamily::of_str)
+    }
 }
 
 /// Credential source types (aligned with credential.md)
@@ -114,6 +473,73 @@ pub struct ApiCredentials {
     pub expires_at: Option<i64>,
 }
 
+impl ApiCredentials {
+    /// Start a validated builder; see [`ApiCredentialsBuilder::build`] for the checks
+    pub fn builder(
+        base_url: impl Into<String>,
+        auth_token: impl Into<String>,
+        source: CredentialSource,
+    ) -> ApiCredentialsBuilder {
+        ApiCredentialsBuilder {
+            base_url: base_url.into(),
+            auth_token: auth_token.into(),
+            source,
+            expires_at: None,
+        }
+    }
+}
+
+/// Builder for [`ApiCredentials`]
+#[derive(Debug, Clone)]
+pub struct ApiCredentialsBuilder {
+    base_url: String,
+    auth_token: String,
+    source: CredentialSource,
+    expires_at: Option<i64>,
+}
+
+impl ApiCredentialsBuilder {
+    /// Token expiry in milliseconds since epoch (OAuth only)
+    pub fn with_expires_at(mut self, expires_at: Option<i64>) -> Self {
+        self.expires_at = expires_at;
+        self
+    }
+
+    /// Validate and build: `base_url` must be an absolute http(s) URL (which
+    /// implies a host), `auth_token` must be non-empty. Both are trimmed.
+    pub fn build(self) -> Result<ApiCredentials, NetworkError> {
+        let base_url = self.base_url.trim();
+        if base_url.is_empty() {
+            return Err(NetworkError::ValidationError(
+                "base_url must not be empty".to_string(),
+            ));
+        }
+        let parsed = url::Url::parse(base_url).map_err(|e| {
+            NetworkError::ValidationError(format!("base_url '{}' is not a URL: {}", base_url, e))
+        })?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(NetworkError::ValidationError(format!(
+                "base_url scheme must be http or https, got '{}'",
+                parsed.scheme()
+            )));
+        }
+
+        let auth_token = self.auth_token.trim();
+        if auth_token.is_empty() {
+            return Err(NetworkError::ValidationError(
+                "auth_token must not be empty".to_string(),
+            ));
+        }
+
+        Ok(ApiCredentials {
+            base_url: base_url.to_string(),
+            auth_token: auth_token.to_string(),
+            source: self.source,
+            expires_at: self.expires_at,
+        })
+    }
+}
+
 /// Error metadata from JSONL transcript
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct JsonlError {
@@ -157,6 +583,70 @@ pub struct ProbeOutcome {
     pub timestamp_local: String,
 }
 
+impl ProbeOutcome {
+    /// Start a validated builder for the outcome of a `mode` probe
+    pub fn builder(mode: ProbeMode, status: NetworkStatus) -> ProbeOutcomeBuilder {
+        ProbeOutcomeBuilder {
+            outcome: ProbeOutcome {
+                status,
+                metrics: ProbeMetrics::default(),
+                p95_latency_ms: 0,
+                rolling_len: 0,
+                api_config: ApiConfig::default(),
+                mode,
+                state_written: false,
+                timestamp_local: String::new(),
+            },
+        }
+    }
+}
+
+/// Builder for [`ProbeOutcome`]
+#[derive(Debug, Clone)]
+pub struct ProbeOutcomeBuilder {
+    outcome: ProbeOutcome,
+}
+
+impl ProbeOutcomeBuilder {
+    pub fn with_metrics(mut self, metrics: ProbeMetrics) -> Self {
+        self.outcome.metrics = metrics;
+        self
+    }
+
+    /// P95 latency and the number of rolling-window samples it was computed from
+    pub fn with_p95(mut self, p95_latency_ms: u32, rolling_len: usize) -> Self {
+        self.outcome.p95_latency_ms = p95_latency_ms;
+        self.outcome.rolling_len = rolling_len;
+        self
+    }
+
+    pub fn with_api_config(mut self, api_config: ApiConfig) -> Self {
+        self.outcome.api_config = api_config;
+        self
+    }
+
+    pub fn with_state_written(mut self, state_written: bool) -> Self {
+        self.outcome.state_written = state_written;
+        self
+    }
+
+    pub fn with_timestamp(mut self, timestamp_local: impl Into<String>) -> Self {
+        self.outcome.timestamp_local = timestamp_local.into();
+        self
+    }
+
+    /// Validate and build: `rolling_len` cannot exceed `MAX_ROLLING_WINDOW_LEN`
+    pub fn build(self) -> Result<ProbeOutcome, NetworkError> {
+        if self.outcome.rolling_len > MAX_ROLLING_WINDOW_LEN {
+            return Err(NetworkError::ValidationError(format!(
+                "rolling_len {} exceeds the largest {}-sample window",
+                self.outcome.rolling_len, MAX_ROLLING_WINDOW_LEN
+            )));
+        }
+        Ok(self.outcome)
+    }
+}
+
 /// Metrics from a single HTTP probe
 #[derive(Debug, Clone, Default)]
 pub struct ProbeMetrics {
@@ -170,6 +660,199 @@ pub struct ProbeMetrics {
     pub error_type: Option<String>,
     /// HTTP version used for request (e.g., "HTTP/1.1", "HTTP/2.0")
     pub http_version: Option<String>,
+    /// Sanitized response header snapshot, captured only when the probe errored
+    pub error_headers: BTreeMap<String, String>,
+    /// Connection details, when the transport reports them
+    pub connection: Option<ConnectionInfo>,
+    /// Rate limit headers of the response, if it carried any
+    pub rate_limit: Option<RateLimitInfo>,
+}
+
+/// Response headers kept in the probe error snapshot (everything else is dropped)
+pub const ERROR_HEADER_SNAPSHOT_NAMES: &[&str] = &["cf-ray", "retry-after", "server", "via"];
+
+/// Maximum stored length of a snapshot header value
+const ERROR_HEADER_VALUE_MAX_LEN: usize = 200;
+
+/// Details of the most recent failed probe, shown by `ccstatus net status`
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ProbeErrorDetails {
+    /// When the failed probe ran (local ISO-8601)
+    pub timestamp: String,
+    /// HTTP status returned by the edge (0 = connection error)
+    pub http_status: u16,
+    /// Classified error type (e.g. "bot_challenge", "rate_limit_error")
+    pub error_type: String,
+    /// Sanitized subset of response headers (see `ERROR_HEADER_SNAPSHOT_NAMES`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+}
+
+/// Build the sanitized header snapshot for a failed probe
+///
+/// Keeps only `ERROR_HEADER_SNAPSHOT_NAMES` (case-insensitive, stored lowercase),
+/// strips control characters and truncates long values.
+pub fn snapshot_error_headers(headers: &HashMap<String, String>) -> BTreeMap<String, String> {
+    headers
+        .iter()
+        .filter_map(|(name, value)| {
+            let name = name.to_ascii_lowercase();
+            if !ERROR_HEADER_SNAPSHOT_NAMES.contains(&name.as_str()) {
+                return None;
+            }
+            let value: String = value
+                .chars()
+                .filter(|c| !c.is_control())
+                .take(ERROR_HEADER_VALUE_MAX_LEN)
+                .collect();
+            Some((name, value.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Prefix of Anthropic's rate limit headers, e.g. `anthropic-ratelimit-requests-remaining`
+pub const RATE_LIMIT_HEADER_PREFIX: &str = "anthropic-ratelimit-";
+
+/// One quota from `anthropic-ratelimit-<kind>-{limit,remaining,reset}`
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RateLimitQuota {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remaining: Option<u64>,
+    /// When the quota is replenished (RFC 3339, as sent)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reset: Option<String>,
+}
+
+impl RateLimitQuota {
+    /// `reset` as Unix seconds
+    pub fn reset_unix(&self) -> Option<i64> {
+        let reset = self.reset.as_deref()?;
+        chrono::DateTime::parse_from_rfc3339(reset.trim())
+            .ok()
+            .map(|at| at.timestamp())
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining == Some(0)
+    }
+}
+
+/// Rate limit headers of the last probe response
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RateLimitInfo {
+    /// Quotas by kind (`requests`, `tokens`, `input-tokens`, `output-tokens`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub quotas: BTreeMap<String, RateLimitQuota>,
+    /// `retry-after` in seconds from `received_at`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_after_secs: Option<u64>,
+    /// When the response arrived (Unix seconds)
+    pub received_at: i64,
+}
+
+impl RateLimitInfo {
+    /// Parse `anthropic-ratelimit-*` and `retry-after` (delay seconds or HTTP date);
+    /// None when the response carried neither
+    pub fn from_headers(headers: &HashMap<String, String>, received_at: i64) -> Option<Self> {
+        let mut info = Self {
+            received_at,
+            ..Self::default()
+        };
+        for (name, value) in headers {
+            let name = name.to_ascii_lowercase();
+            let value = value.trim();
+            if name == "retry-after" {
+                info.retry_after_secs = value.parse::<u64>().ok().or_else(|| {
+                    chrono::DateTime::parse_from_rfc2822(value)
+                        .ok()
+                        .map(|at| at.timestamp().saturating_sub(received_at).max(0) as u64)
+                });
+                continue;
+            }
+            let Some(rest) = name.strip_prefix(RATE_LIMIT_HEADER_PREFIX) else {
+                continue;
+            };
+            let Some((kind, field)) = rest.rsplit_once('-') else {
+                continue;
+            };
+            let quota = info.quotas.entry(kind.to_string()).or_default();
+            match field {
+                "limit" => quota.limit = value.parse().ok(),
+                "remaining" => quota.remaining = value.parse().ok(),
+                "reset" => quota.reset = Some(value.to_string()),
+                _ => {}
+            }
+        }
+        info.quotas
+            .retain(|_, quota| *quota != RateLimitQuota::default());
+        (!info.quotas.is_empty() || info.retry_after_secs.is_some()).then_some(info)
+    }
+
+    /// When requests may resume (Unix seconds): `retry-after` when sent, else
+    /// the latest reset of an exhausted quota, else the earliest reset
+    pub fn retry_at(&self) -> Option<i64> {
+        if let Some(secs) = self.retry_after_secs {
+            return Some(self.received_at.saturating_add(secs as i64));
+        }
+        let exhausted = self
+            .quotas
+            .values()
+            .filter(|quota| quota.is_exhausted())
+            .filter_map(RateLimitQuota::reset_unix)
+            .max();
+        exhausted.or_else(|| {
+            self.quotas
+                .values()
+                .filter_map(RateLimitQuota::reset_unix)
+                .min()
+        })
+    }
+
+    /// Seconds until `retry_at` from `now`; None once it has passed
+    pub fn retry_in_secs(&self, now: i64) -> Option<u64> {
+        let remaining = self.retry_at()? - now;
+        (remaining > 0).then_some(remaining as u64)
+    }
+}
+
+/// Last observed status of one API endpoint (composite health)
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct EndpointHealth {
+    /// HTTP status (0 = connection error)
+    pub http_status: u16,
+    /// Whether the API answered (see `EndpointHealth::is_up_status`)
+    pub up: bool,
+    pub latency_ms: u32,
+    /// When the endpoint was checked (local ISO-8601)
+    pub checked_at: String,
+}
+
+impl EndpointHealth {
+    pub fn new(http_status: u16, latency_ms: u32, checked_at: &str) -> Self {
+        Self {
+            http_status,
+            up: Self::is_up_status(http_status),
+            latency_ms,
+            checked_at: checked_at.to_string(),
+        }
+    }
+
+    /// An endpoint is up when the API itself answered: any status below 500 except
+    /// 403 (bot challenge). Auth/client errors such as an unauthenticated 401 count as up.
+    pub fn is_up_status(http_status: u16) -> bool {
+        http_status != 0 && http_status < 500 && http_status != 403
+    }
+}
+
+/// Composite health score: percentage of endpoints up (None when nothing was checked)
+pub fn composite_health_score(endpoints: &BTreeMap<String, EndpointHealth>) -> Option<u8> {
+    if endpoints.is_empty() {
+        return None;
+    }
+    let up = endpoints.values().filter(|endpoint| endpoint.up).count();
+    Some(((up * 100) / endpoints.len()) as u8)
 }
 
 /// API configuration metadata
@@ -196,10 +879,286 @@ pub struct MonitoringSnapshot {
     pub monitoring_state: MonitoringState,
     /// Last JSONL error event if any
     pub last_jsonl_error_event: Option<JsonlError>,
+    /// Most recent failed probe with its response header snapshot
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_probe_error: Option<ProbeErrorDetails>,
+    /// Per-endpoint last status ("messages", "models") when auxiliary checks are enabled
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub endpoints: BTreeMap<String, EndpointHealth>,
+    /// Composite health score 0-100 across `endpoints` (share of endpoints up)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health_score: Option<u8>,
+    /// Latency samples from other machines merged by state sync
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shared_baseline: Option<SharedBaseline>,
+    /// Side-by-side results of `network.compare_endpoints`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comparison: Option<EndpointComparisons>,
+    /// Shown status over the last week with rolling 24h / 7d availability
+    #[serde(default, skip_serializing_if = "UptimeStats::is_empty")]
+    pub uptime: UptimeStats,
+    /// Failed-probe budget and burn rate when `[network.error_budget]` is set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_budget: Option<ErrorBudget>,
+    /// IPv4 and IPv6 checks when `network.ip_family = "compare"`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ip_families: Option<IpFamilyComparison>,
     /// Timestamp of last state update
     pub timestamp: String,
 }
 
+/// Longest span of time between two recorded statuses that still counts as
+/// observed; longer gaps (ccstatus not running) count as unknown
+pub const MAX_OBSERVATION_GAP_SECS: i64 = 1800;
+
+/// Most status changes kept, so a week of flapping cannot bloat the state file
+pub const MAX_STATUS_SPANS: usize = 1000;
+
+/// Rolling window an availability figure covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UptimeWindow {
+    Day,
+    Week,
+}
+
+impl UptimeWindow {
+    /// `24h` or `7d`
+    pub fn parse(text: &str) -> Option<Self> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "24h" | "1d" | "day" => Some(UptimeWindow::Day),
+            "7d" | "week" => Some(UptimeWindow::Week),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            UptimeWindow::Day => "24h",
+            UptimeWindow::Week => "7d",
+        }
+    }
+
+    pub fn secs(self) -> i64 {
+        match self {
+            UptimeWindow::Day => 24 * 3600,
+            UptimeWindow::Week => 7 * 24 * 3600,
+        }
+    }
+}
+
+/// A shown status and the Unix second it began; it lasts until the next span
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct StatusSpan {
+    pub status: NetworkStatus,
+    pub since: i64,
+}
+
+/// Seconds spent in each status within a window
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StatusDurations {
+    pub healthy_secs: u64,
+    pub degraded_secs: u64,
+    pub error_secs: u64,
+    /// Unknown, policy-blocked or unobserved time; not counted either way
+    pub unknown_secs: u64,
+}
+
+impl StatusDurations {
+    /// Healthy and degraded time over all observed time, in percent; None
+    /// without observed time
+    pub fn availability_percent(&self) -> Option<f64> {
+        let up = self.healthy_secs + self.degraded_secs;
+        let observed = up + self.error_secs;
+        (observed > 0).then(|| up as f64 * 100.0 / observed as f64)
+    }
+}
+
+/// Status durations over the last week and the availability derived from them
+///
+/// Every state write records the shown status; a span is appended only when
+/// the status changes, and spans that ended more than a week ago are dropped.
+/// The last span lasts until `last_seen`, so time while ccstatus was not
+/// running is never credited to the last known status.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct UptimeStats {
+    /// Status changes, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spans: Vec<StatusSpan>,
+    /// Unix seconds of the latest recorded status
+    #[serde(default)]
+    pub last_seen: i64,
+    /// Availability over the last 24 hours in percent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub availability_24h: Option<f64>,
+    /// Availability over the last 7 days in percent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub availability_7d: Option<f64>,
+}
+
+impl UptimeStats {
+    /// Whether nothing was recorded yet (skipped when serializing)
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// Record `status` as shown at `at` (Unix seconds) and refresh the availability
+    ///
+    /// A gap above `MAX_OBSERVATION_GAP_SECS` since the last record is closed
+    /// with an unknown span. Records older than the last one are ignored.
+    pub fn record(&mut self, status: &NetworkStatus, at: i64) {
+        if !self.spans.is_empty() && at < self.last_seen {
+            return;
+        }
+        if !self.spans.is_empty() && at - self.last_seen > MAX_OBSERVATION_GAP_SECS {
+            self.push_span(NetworkStatus::Unknown, self.last_seen);
+        }
+        self.push_span(status.clone(), at);
+        self.last_seen = at;
+
+        let cutoff = at - UptimeWindow::Week.secs();
+        let expired = self
+            .spans
+            .windows(2)
+            .take_while(|pair| pair[1].since <= cutoff)
+            .count();
+        let excess = self.spans.len().saturating_sub(MAX_STATUS_SPANS);
+        self.spans.drain(..expired.max(excess));
+        self.refresh(at);
+    }
+
+    fn push_span(&mut self, status: NetworkStatus, since: i64) {
+        if self.spans.last().map(|span| &span.status) != Some(&status) {
+            self.spans.push(StatusSpan { status, since });
+        }
+    }
+
+    /// Seconds per status between `from` and `last_seen`
+    pub fn durations(&self, from: i64) -> StatusDurations {
+        let mut durations = StatusDurations::default();
+        for (index, span) in self.spans.iter().enumerate() {
+            let end = self
+                .spans
+                .get(index + 1)
+                .map_or(self.last_seen, |next| next.since);
+            let secs = (end - span.since.max(from)).max(0) as u64;
+            match span.status {
+                NetworkStatus::Healthy => durations.healthy_secs += secs,
+                NetworkStatus::Degraded => durations.degraded_secs += secs,
+                NetworkStatus::Error => durations.error_secs += secs,
+                NetworkStatus::Unknown | NetworkStatus::BlockedByPolicy => {
+                    durations.unknown_secs += secs
+                }
+            }
+        }
+        durations
+    }
+
+    /// Availability over `window` ending at `now`; None without observed time
+    pub fn availability(&self, window: UptimeWindow, now: i64) -> Option<f64> {
+        self.durations(now - window.secs()).availability_percent()
+    }
+
+    /// Stored availability for `window`, as of the latest record
+    pub fn availability_for(&self, window: UptimeWindow) -> Option<f64> {
+        match window {
+            UptimeWindow::Day => self.availability_24h,
+            UptimeWindow::Week => self.availability_7d,
+        }
+    }
+
+    /// Recompute `availability_24h` and `availability_7d` as of `now`
+    pub fn refresh(&mut self, now: i64) {
+        self.availability_24h = self.availability(UptimeWindow::Day, now);
+        self.availability_7d = self.availability(UptimeWindow::Week, now);
+    }
+}
+
+/// Probe results of one base URL compared side by side (`network.compare_endpoints`)
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct EndpointComparison {
+    /// HTTP status of the last probe (0 = connection error)
+    pub http_status: u16,
+    pub latency_ms: u32,
+    /// Latencies of recent HTTP 200 probes, oldest first (at most `ROLLING_WINDOW_LEN`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recent_ms: Vec<u32>,
+    /// When the endpoint was last probed (local ISO-8601)
+    pub checked_at: String,
+}
+
+impl EndpointComparison {
+    /// Whether the last probe returned HTTP 200
+    pub fn is_ok(&self) -> bool {
+        self.http_status == 200
+    }
+
+    /// Median of the recent successful probes, else the last latency
+    pub fn median_ms(&self) -> u32 {
+        if self.recent_ms.is_empty() {
+            self.latency_ms
+        } else {
+            stats::percentile(&self.recent_ms, 0.5)
+        }
+    }
+
+    pub fn record(&mut self, http_status: u16, latency_ms: u32, checked_at: &str) {
+        self.http_status = http_status;
+        self.latency_ms = latency_ms;
+        self.checked_at = checked_at.to_string();
+        if http_status == 200 {
+            if self.recent_ms.len() >= ROLLING_WINDOW_LEN {
+                self.recent_ms.remove(0);
+            }
+            self.recent_ms.push(latency_ms);
+        }
+    }
+}
+
+/// The active base URL and its alternatives, probed in parallel
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct EndpointComparisons {
+    /// Base URL the credentials point at
+    pub active: String,
+    /// Base URL → probe results, the active one included
+    pub endpoints: BTreeMap<String, EndpointComparison>,
+}
+
+impl EndpointComparisons {
+    pub fn active_endpoint(&self) -> Option<&EndpointComparison> {
+        self.endpoints.get(&self.active)
+    }
+
+    /// Alternative with the lowest median whose last probe succeeded
+    pub fn best_alternative(&self) -> Option<(&str, &EndpointComparison)> {
+        self.endpoints
+            .iter()
+            .filter(|(url, endpoint)| **url != self.active && endpoint.is_ok())
+            .min_by_key(|(_, endpoint)| endpoint.median_ms())
+            .map(|(url, endpoint)| (url.as_str(), endpoint))
+    }
+
+    /// Best alternative when it beats the active endpoint: the active one
+    /// failed its last probe, or the alternative's median is lower
+    pub fn better_alternative(&self) -> Option<(&str, &EndpointComparison)> {
+        let (url, best) = self.best_alternative()?;
+        match self.active_endpoint() {
+            Some(active) if active.is_ok() && active.median_ms() <= best.median_ms() => None,
+            _ => Some((url, best)),
+        }
+    }
+}
+
+/// What `ccstatus net reset` clears
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResetScope {
+    /// Rolling statistics, window IDs and error history; the probe counters,
+    /// API config, pinned probe profile and any server-imposed backoff (429 /
+    /// bot challenge penalty, rate limit reset) are kept
+    Statistics,
+    /// The whole state file and the probe history next to it
+    All,
+}
+
 /// Gate types for timing-driven probe execution priority
 ///
 /// Implements COLD > RED > GREEN priority logic where only one gate type
@@ -231,6 +1190,12 @@ pub enum NetworkError {
     CredentialError(String),
     /// Indicates probe should be silently skipped (e.g., expired OAuth token)
     SkipProbe(String),
+    /// Target host is not in the `network.allowed_hosts` allowlist
+    BlockedByPolicy(String),
+    /// State sync failed (backend, encryption or document format)
+    SyncError(String),
+    /// A builder rejected its input (empty or malformed URL, token or bound)
+    ValidationError(String),
 }
 
 impl std::fmt::Display for NetworkError {
@@ -245,6 +1210,9 @@ impl std::fmt::Display for NetworkError {
             NetworkError::StateFileError(msg) => write!(f, "State file error: {}", msg),
             NetworkError::CredentialError(msg) => write!(f, "Credential error: {}", msg),
             NetworkError::SkipProbe(msg) => write!(f, "Skip probe: {}", msg),
+            NetworkError::BlockedByPolicy(msg) => write!(f, "Blocked by policy: {}", msg),
+            NetworkError::SyncError(msg) => write!(f, "Sync error: {}", msg),
+            NetworkError::ValidationError(msg) => write!(f, "Validation error: {}", msg),
         }
     }
 }
@@ -280,6 +1248,18 @@ impl Default for MonitoringState {
             last_cold_session_id: None,
             last_cold_probe_at: None,
             state: NetworkStatus::Unknown,
+            penalty: PenaltyState::default(),
+            red_since: None,
+            red_error_at: None,
+            status_since: None,
+            red_watchdog_triggered: false,
+            ledger: ProbeLedger::default(),
+            consecutive_failures: 0,
+            activity: ActivityClass::Active,
+            activity_changed_at: None,
+            probe_profile: None,
+            pending_status: None,
+            pending_count: 0,
         }
     }
 }
@@ -291,7 +1271,8 @@ impl Default for NetworkMetrics {
             breakdown: String::new(),
             last_http_status: 0,
             error_type: None,
-            rolling_totals: Vec::with_capacity(12), // Max 60 minutes at 300s intervals
+            rolling_totals: RollingWindow::new(), // Max 60 minutes at 300s intervals
+            rolling_sampled_at: RollingTimes::new(),
             p95_latency_ms: 0,
             connection_reused: None,
             breakdown_source: None,
@@ -299,7 +1280,123 @@ impl Default for NetworkMetrics {
             proxy_health_level: None,
             proxy_health_detail: None,
             http_version: None,
+            dns_cache: None,
+            dns_lookup_ms: None,
+            connection: None,
+            window_percentiles: BTreeMap::new(),
+            rate_limit: None,
+        }
+    }
+}
+
+impl NetworkMetrics {
+    /// Append a GREEN sample taken at `sampled_at` (Unix seconds), evicting the
+    /// oldest one first so the default window never spills to the heap
+    pub fn push_sample(&mut self, latency_ms: u32, sampled_at: i64) {
+        self.push_sample_within(latency_ms, sampled_at, ROLLING_WINDOW_LEN);
+    }
+
+    /// [`Self::push_sample`] for a window of `capacity` samples; a window
+    /// shrunk by config loses its oldest samples here
+    pub fn push_sample_within(&mut self, latency_ms: u32, sampled_at: i64, capacity: usize) {
+        self.align_sample_times();
+        while self.rolling_totals.len() >= capacity.max(1) {
+            if self.rolling_sampled_at.len() == self.rolling_totals.len() {
+                self.rolling_sampled_at.remove(0);
+            }
+            self.rolling_totals.remove(0);
+        }
+        self.rolling_totals.push(latency_ms);
+        self.rolling_sampled_at.push(sampled_at);
+    }
+
+    /// Drop samples taken before `cutoff` (Unix seconds), returning how many
+    /// were removed
+    ///
+    /// Untimed legacy samples predate every timed one, so they go as soon as
+    /// the oldest timed sample is stale; with no timed samples they are kept.
+    pub fn evict_samples_before(&mut self, cutoff: i64) -> usize {
+        self.align_sample_times();
+        let stale_timed = self
+            .rolling_sampled_at
+            .iter()
+            .take_while(|&&at| at < cutoff)
+            .count();
+        if stale_timed == 0 {
+            return 0;
+        }
+
+        let untimed = self.rolling_totals.len() - self.rolling_sampled_at.len();
+        let evicted = untimed + stale_timed;
+        self.rolling_totals.drain(..evicted);
+        self.rolling_sampled_at.drain(..stale_timed);
+        evicted
+    }
+
+    /// Trim sample times left over from a hand-edited or truncated state file
+    fn align_sample_times(&mut self) {
+        let excess = self
+            .rolling_sampled_at
+            .len()
+            .saturating_sub(self.rolling_totals.len());
+        self.rolling_sampled_at.drain(..excess);
+    }
+}
+
+impl PenaltyState {
+    /// Whether no penalty is recorded (skipped when serializing)
+    pub fn is_clear(&self) -> bool {
+        self.level == 0 && self.until.is_none()
+    }
+
+    /// Cooldown for a penalty level: base * 2^(level-1), capped at `PENALTY_MAX_SECS`
+    pub fn backoff_secs(level: u32) -> u64 {
+        if level == 0 {
+            return 0;
         }
+        let factor = 1u64 << (level - 1).min(16);
+        PENALTY_BASE_SECS
+            .saturating_mul(factor)
+            .min(PENALTY_MAX_SECS)
+    }
+
+    /// Record another penalized probe at `now` (local ISO-8601) and extend the cooldown
+    pub fn escalate(&mut self, reason: &str, now: &str) {
+        self.level = self.level.saturating_add(1);
+        self.reason = Some(reason.to_string());
+        self.until = chrono::DateTime::parse_from_rfc3339(now).ok().map(|now| {
+            let cooldown = chrono::Duration::seconds(Self::backoff_secs(self.level) as i64);
+            (now + cooldown).to_rfc3339_opts(chrono::SecondsFormat::Secs, false)
+        });
+    }
+
+    /// Defer probes for at least `secs` from `now` (local ISO-8601), e.g. for a
+    /// `retry-after` longer than the backoff
+    pub fn hold_for(&mut self, secs: u64, now: &str) {
+        let Ok(now) = chrono::DateTime::parse_from_rfc3339(now) else {
+            return;
+        };
+        let held = now + chrono::Duration::seconds(secs.min(PENALTY_MAX_SECS) as i64);
+        let current = self
+            .until
+            .as_deref()
+            .and_then(|until| chrono::DateTime::parse_from_rfc3339(until).ok());
+        if current.is_none_or(|until| until < held) {
+            self.until = Some(held.to_rfc3339_opts(chrono::SecondsFormat::Secs, false));
+        }
+    }
+
+    /// Clear the penalty after a clean probe
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Seconds left in the cooldown at `now`, or `None` once it has expired
+    pub fn remaining_secs(&self, now: &str) -> Option<u64> {
+        let until = chrono::DateTime::parse_from_rfc3339(self.until.as_deref()?).ok()?;
+        let now = chrono::DateTime::parse_from_rfc3339(now).ok()?;
+        let remaining = (until - now).num_seconds();
+        (remaining > 0).then_some(remaining as u64)
     }
 }
 
@@ -361,36 +1458,29 @@ impl NetworkMetrics {
 }
 
 // Environment variable utilities
-/// Parse boolean environment variables (strict true/false only)
+/// Parse a boolean environment variable with the rules of [`crate::core::env::parse_bool`]
 ///
-/// Only accepts "true" or "false" (case insensitive). All other values default to false.
+/// `1`, `true`, `yes` and `on` (any case) are true; anything else, or unset, is false.
 ///
 /// # Examples
 ///
 /// ```rust
 /// use ccstatus::core::network::types::parse_env_bool;
 ///
-/// // These return true
-/// std::env::set_var("TEST_VAR", "true");
-/// assert_eq!(parse_env_bool("TEST_VAR"), true);
 /// std::env::set_var("TEST_VAR", "TRUE");
 /// assert_eq!(parse_env_bool("TEST_VAR"), true);
+/// std::env::set_var("TEST_VAR", "1");
+/// assert_eq!(parse_env_bool("TEST_VAR"), true);
 ///
-/// // These all return false  
-/// std::env::set_var("TEST_VAR", "false");
+/// std::env::set_var("TEST_VAR", "off");
 /// assert_eq!(parse_env_bool("TEST_VAR"), false);
-/// std::env::set_var("TEST_VAR", "1");      // Not accepted
-/// std::env::set_var("TEST_VAR", "yes");    // Not accepted  
-/// std::env::remove_var("TEST_VAR");        // Unset
+/// std::env::remove_var("TEST_VAR");
 /// assert_eq!(parse_env_bool("TEST_VAR"), false);
 /// ```
 pub fn parse_env_bool(env_var: &str) -> bool {
     std::env::var(env_var)
-        .map(|v| match v.trim().to_lowercase().as_str() {
-            "true" => true,
-            "false" => false,
-            _ => false,
-        })
+        .ok()
+        .and_then(|value| crate::core::env::parse_bool(&value))
         .unwrap_or(false)
 }
 
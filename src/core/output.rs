@@ -0,0 +1,96 @@
+//! Statusline output formats
+//!
+//! `plain` (default) prints the rendered statusline as Claude Code expects it.
+//! The structured formats wrap it in a JSON object alongside the
+//! `DetailRenderer` block for GUI consumers:
+//!
+//! - `json`: `{"text": ..., "tooltip": ..., "class": ...}` with ANSI styling kept
+//! - `waybar`: the same keys, with `text` reduced to a single unstyled line
+
+use crate::core::detail_renderer::DetailRenderer;
+use serde::Serialize;
+
+/// Output format selected with `--format`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Rendered statusline only
+    #[default]
+    Plain,
+    /// JSON object with the statusline, detail block and status class
+    Json,
+    /// Waybar custom module JSON (`return-type: json`)
+    Waybar,
+}
+
+#[derive(Serialize)]
+struct StructuredOutput<'a> {
+    text: String,
+    tooltip: String,
+    class: &'a str,
+}
+
+impl OutputFormat {
+    /// Whether this format carries the detail block
+    pub fn is_structured(self) -> bool {
+        self != OutputFormat::Plain
+    }
+
+    /// Render `statusline` in this format; `detail` is ignored for `plain`
+    pub fn render(self, statusline: &str, detail: &DetailRenderer) -> String {
+        let text = match self {
+            OutputFormat::Plain => return statusline.to_string(),
+            OutputFormat::Json => statusline.to_string(),
+            OutputFormat::Waybar => strip_ansi(statusline)
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .collect::<Vec<_>>()
+                .join(" "),
+        };
+
+        serde_json::to_string(&StructuredOutput {
+            text,
+            tooltip: detail.render(),
+            class: detail.status_class(),
+        })
+        .unwrap_or_default()
+    }
+}
+
+/// Remove ANSI CSI styling and OSC sequences (e.g. OSC 8 links), keeping link text
+pub fn strip_ansi(text: &str) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if ch != '\x1b' {
+            plain.push(ch);
+            continue;
+        }
+        match chars.next() {
+            // CSI: parameters until a final byte in @..~
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            // OSC: until BEL or ST (ESC \)
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07' {
+                        break;
+                    }
+                    if c == '\x1b' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    plain
+}
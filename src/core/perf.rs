@@ -0,0 +1,146 @@
+//! Per-invocation stage timings
+//!
+//! Every statusline run records how long its stages took (stdin parse,
+//! credential resolution, state load, each segment's collect, render) into the
+//! process-wide [`PerfRecorder`]. At exit `main` appends the finished
+//! [`PerfReport`] to the debug log, one compact line per run:
+//!
+//! ```text
+//! stdin_parse=0.3ms state_load=1.2ms credentials=4.0ms segment.network=38.1ms segment.git=11.6ms render=0.4ms total=52.7ms
+//! ```
+//!
+//! Stages recorded more than once (e.g. several state loads) are summed.
+//! `PerfReport::parse` reads a line back, so `ccstatus analyze` can compare
+//! runs and point at the stage that regressed.
+
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Event name of the report line in the debug log
+pub const PERF_EVENT: &str = "invocation_timings";
+
+/// Time spent in one stage
+#[derive(Debug, Clone, PartialEq)]
+pub struct StageTiming {
+    pub stage: String,
+    pub elapsed: Duration,
+}
+
+/// Collects stage timings for the current invocation
+pub struct PerfRecorder {
+    started: Instant,
+    stages: Mutex<Vec<StageTiming>>,
+}
+
+impl Default for PerfRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PerfRecorder {
+    /// Recorder whose total runs from now
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            stages: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Process-wide recorder; the first call starts the total clock
+    pub fn global() -> &'static PerfRecorder {
+        static GLOBAL: OnceLock<PerfRecorder> = OnceLock::new();
+        GLOBAL.get_or_init(PerfRecorder::new)
+    }
+
+    /// Add `elapsed` to `stage`, keeping first-recorded order
+    pub fn record(&self, stage: &str, elapsed: Duration) {
+        let mut stages = self
+            .stages
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        match stages.iter_mut().find(|timing| timing.stage == stage) {
+            Some(timing) => timing.elapsed += elapsed,
+            None => stages.push(StageTiming {
+                stage: stage.to_string(),
+                elapsed,
+            }),
+        }
+    }
+
+    /// Run `f` and record how long it took as `stage`
+    pub fn time<T>(&self, stage: &str, f: impl FnOnce() -> T) -> T {
+        let started = Instant::now();
+        let result = f();
+        self.record(stage, started.elapsed());
+        result
+    }
+
+    /// Stages recorded so far and the time since the recorder started
+    pub fn report(&self) -> PerfReport {
+        PerfReport {
+            stages: self
+                .stages
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .clone(),
+            total: self.started.elapsed(),
+        }
+    }
+}
+
+/// Stage timings of one invocation
+#[derive(Debug, Clone, PartialEq)]
+pub struct PerfReport {
+    pub stages: Vec<StageTiming>,
+    pub total: Duration,
+}
+
+impl PerfReport {
+    /// `stage=1.2ms … total=52.7ms`, the debug log line
+    pub fn summary(&self) -> String {
+        self.stages
+            .iter()
+            .map(|timing| (timing.stage.as_str(), timing.elapsed))
+            .chain(std::iter::once(("total", self.total)))
+            .map(|(stage, elapsed)| format!("{}={:.1}ms", stage, elapsed.as_secs_f64() * 1000.0))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Parse a `summary()` line back; `None` without a `total`
+    pub fn parse(summary: &str) -> Option<Self> {
+        let mut stages = Vec::new();
+        let mut total = None;
+        for pair in summary.split_whitespace() {
+            let (stage, value) = pair.split_once('=')?;
+            let millis: f64 = value.strip_suffix("ms")?.parse().ok()?;
+            let elapsed = Duration::from_secs_f64(millis.max(0.0) / 1000.0);
+            if stage == "total" {
+                total = Some(elapsed);
+            } else {
+                stages.push(StageTiming {
+                    stage: stage.to_string(),
+                    elapsed,
+                });
+            }
+        }
+        Some(Self {
+            stages,
+            total: total?,
+        })
+    }
+
+    /// Time recorded for `stage`
+    pub fn stage(&self, stage: &str) -> Option<Duration> {
+        self.stages
+            .iter()
+            .find(|timing| timing.stage == stage)
+            .map(|timing| timing.elapsed)
+    }
+
+    /// Stage that took longest
+    pub fn slowest(&self) -> Option<&StageTiming> {
+        self.stages.iter().max_by_key(|timing| timing.elapsed)
+    }
+}
@@ -0,0 +1,219 @@
+//! Side-by-side preview of two configs (`ccstatus preview --diff`)
+//!
+//! Theme and layout edits are easier to judge next to what is in use. Both
+//! configs render the same input; the report shows the two statuslines and,
+//! segment by segment, whether the text changed, only the styling changed, or
+//! the segment was added or removed. Segments are matched by id and, for ids
+//! that repeat (custom segments), by their order.
+//!
+//! Nothing is probed: the network segment needs live statusline input, so it
+//! is left out on both sides.
+
+use crate::config::SegmentId;
+use crate::core::output::strip_ansi;
+use crate::core::segments::health::segment_name;
+use crate::core::statusline::responsive::visible_width;
+
+/// Statusline input used when none is piped or passed with `--input`
+///
+/// The `mock_preview` transcript makes the usage and compact segments show
+/// sample values, as in the TUI preview.
+pub fn sample_input() -> serde_json::Value {
+    serde_json::json!({
+        "model": {
+            "id": "claude-sonnet-4-20250514",
+            "display_name": "Sonnet 4"
+        },
+        "transcript_path": "mock_preview",
+        "output_style": { "name": "default" }
+    })
+}
+
+/// How a segment differs between the current and the candidate config
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SegmentChange {
+    Same,
+    /// Same text, different colors, emphasis or padding
+    Restyled,
+    Changed,
+    Added,
+    Removed,
+}
+
+impl SegmentChange {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SegmentChange::Same => "same",
+            SegmentChange::Restyled => "style",
+            SegmentChange::Changed => "text",
+            SegmentChange::Added => "added",
+            SegmentChange::Removed => "removed",
+        }
+    }
+}
+
+/// One segment under both configs; None where the config does not render it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SegmentDiff {
+    /// Segment name, suffixed `#2`, `#3`, … for repeated ids
+    pub name: String,
+    pub current: Option<String>,
+    pub candidate: Option<String>,
+}
+
+impl SegmentDiff {
+    pub fn change(&self) -> SegmentChange {
+        match (&self.current, &self.candidate) {
+            (Some(current), Some(candidate)) if current == candidate => SegmentChange::Same,
+            // Background colors pad the text with spaces, which is styling too
+            (Some(current), Some(candidate))
+                if strip_ansi(current).trim() == strip_ansi(candidate).trim() =>
+            {
+                SegmentChange::Restyled
+            }
+            (Some(_), Some(_)) => SegmentChange::Changed,
+            (None, _) => SegmentChange::Added,
+            (Some(_), None) => SegmentChange::Removed,
+        }
+    }
+}
+
+/// Both statuslines and their segment-by-segment comparison
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreviewDiff {
+    pub current: String,
+    pub candidate: String,
+    /// Current config's segments in order, then the ones only the candidate renders
+    pub segments: Vec<SegmentDiff>,
+}
+
+impl PreviewDiff {
+    /// Pair the segments rendered by each config (see `StatusLineGenerator::render_segments`)
+    pub fn new(
+        current: String,
+        candidate: String,
+        current_segments: Vec<(SegmentId, String)>,
+        candidate_segments: Vec<(SegmentId, String)>,
+    ) -> Self {
+        let mut candidate_segments = keyed(candidate_segments);
+        let mut segments: Vec<SegmentDiff> = keyed(current_segments)
+            .into_iter()
+            .map(|(name, text)| {
+                let candidate = candidate_segments
+                    .iter()
+                    .position(|(other, _)| *other == name)
+                    .map(|index| candidate_segments.remove(index).1);
+                SegmentDiff {
+                    name,
+                    current: Some(text),
+                    candidate,
+                }
+            })
+            .collect();
+        segments.extend(
+            candidate_segments
+                .into_iter()
+                .map(|(name, text)| SegmentDiff {
+                    name,
+                    current: None,
+                    candidate: Some(text),
+                }),
+        );
+
+        Self {
+            current,
+            candidate,
+            segments,
+        }
+    }
+
+    /// Whether both configs render the same statusline
+    pub fn is_identical(&self) -> bool {
+        self.current == self.candidate
+            && self
+                .segments
+                .iter()
+                .all(|segment| segment.change() == SegmentChange::Same)
+    }
+
+    /// Both statuslines, then a segment table; `plain` strips ANSI styling
+    pub fn render(&self, plain: bool) -> String {
+        let show = |text: &str| {
+            let text = text.replace('\n', " ");
+            if plain {
+                strip_ansi(&text)
+            } else {
+                text
+            }
+        };
+
+        let mut lines = vec!["Current:".to_string()];
+        lines.extend(self.current.lines().map(|line| format!("  {}", show(line))));
+        lines.push("Candidate:".to_string());
+        lines.extend(
+            self.candidate
+                .lines()
+                .map(|line| format!("  {}", show(line))),
+        );
+        lines.push(String::new());
+
+        if self.is_identical() {
+            lines.push("No differences".to_string());
+            return lines.join("\n");
+        }
+
+        let rows: Vec<[String; 4]> = self
+            .segments
+            .iter()
+            .map(|segment| {
+                [
+                    segment.name.clone(),
+                    segment.current.as_deref().map(show).unwrap_or_default(),
+                    segment.candidate.as_deref().map(show).unwrap_or_default(),
+                    segment.change().as_str().to_string(),
+                ]
+            })
+            .collect();
+        let header = ["Segment", "Current", "Candidate", "Change"].map(String::from);
+        let widths: Vec<usize> = (0..3)
+            .map(|column| {
+                std::iter::once(&header)
+                    .chain(&rows)
+                    .map(|row| visible_width(&row[column]))
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        for row in std::iter::once(&header).chain(&rows) {
+            let cells: Vec<String> = row[..3]
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| {
+                    // Pad by printable width, so escapes do not skew the columns
+                    let padding = width.saturating_sub(visible_width(cell));
+                    format!("{}{}", cell, " ".repeat(padding))
+                })
+                .collect();
+            lines.push(format!("{}  {}", cells.join("  "), row[3]));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Name each rendered segment, numbering repeated ids by their order
+fn keyed(segments: Vec<(SegmentId, String)>) -> Vec<(String, String)> {
+    let mut seen: Vec<SegmentId> = Vec::new();
+    segments
+        .into_iter()
+        .map(|(id, text)| {
+            seen.push(id);
+            let occurrence = seen.iter().filter(|other| **other == id).count();
+            let name = match occurrence {
+                1 => segment_name(id),
+                n => format!("{}#{}", segment_name(id), n),
+            };
+            (name, text)
+        })
+        .collect()
+}
@@ -0,0 +1,198 @@
+//! Recovery from corrupt persisted files
+//!
+//! ccstatus keeps its state in small JSON files that are rewritten on every
+//! render. A file that no longer parses (truncated by a crash or a full disk,
+//! damaged by a sync tool, edited by hand) used to be silently replaced by
+//! defaults, which hid chronic filesystem trouble. Loaders now go through
+//! [`load_json`]:
+//!
+//! - the corrupt file is quarantined as `<file>.corrupt-<timestamp>` so it can be inspected
+//! - the best-known state is restored: the temp file an interrupted write left
+//!   behind, when it parses, otherwise nothing (the caller's defaults)
+//! - the event is counted in `recovery.json` beside the file, which
+//!   `ccstatus doctor` reports
+//!
+//! Quarantined copies are deleted after 30 days by the daily housekeeping job
+//! (see [`crate::core::scheduler`]).
+//!
+//! User-authored files (`config.toml`, themes) are never quarantined; their
+//! parse errors are shown to the user instead.
+
+use chrono::Local;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Name of the recovery log kept in each state directory
+pub const RECOVERY_LOG_FILE: &str = "recovery.json";
+
+/// Recoveries of one persisted file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FileRecovery {
+    pub count: u64,
+    pub last_error: String,
+    /// When the file was last quarantined (local ISO-8601)
+    pub last_at: String,
+    /// Where the corrupt copy was moved (`None` if the rename failed)
+    pub quarantined_to: Option<PathBuf>,
+    /// Whether state was restored from an intact temp file
+    pub restored: bool,
+}
+
+/// Recovery counters of one state directory, persisted to `recovery.json`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecoveryLog {
+    /// Total corrupt files recovered in this directory
+    pub recovery_count: u64,
+    /// Per file name
+    pub files: BTreeMap<String, FileRecovery>,
+}
+
+impl RecoveryLog {
+    /// Log for the directory holding `path`
+    pub fn path_for(path: &Path) -> PathBuf {
+        path.parent()
+            .unwrap_or_else(|| Path::new("."))
+            .join(RECOVERY_LOG_FILE)
+    }
+
+    /// Load the log; a missing or corrupt log is empty (and is not itself quarantined)
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, content)?;
+        fs::rename(&tmp, path)
+    }
+
+    pub fn record(&mut self, name: &str, recovery: FileRecovery) {
+        self.recovery_count += 1;
+        let count = self.files.get(name).map_or(0, |file| file.count) + 1;
+        self.files
+            .insert(name.to_string(), FileRecovery { count, ..recovery });
+    }
+
+    /// Human-readable report for `ccstatus doctor`
+    pub fn render_report(&self) -> String {
+        if self.recovery_count == 0 {
+            return "No corrupt state files recovered".to_string();
+        }
+
+        let mut lines = vec![format!(
+            "{} corrupt state file{} recovered",
+            self.recovery_count,
+            if self.recovery_count == 1 { "" } else { "s" }
+        )];
+        for (name, file) in &self.files {
+            lines.push(format!(
+                "{} ({}x, last at {}): {}",
+                name, file.count, file.last_at, file.last_error
+            ));
+            let outcome = if file.restored {
+                "restored from temp file"
+            } else {
+                "reset to defaults"
+            };
+            match &file.quarantined_to {
+                Some(quarantined) => lines.push(format!(
+                    "           {}, corrupt copy at {}",
+                    outcome,
+                    quarantined.display()
+                )),
+                None => lines.push(format!("           {}", outcome)),
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+/// Read `path` as JSON, recovering from corruption
+///
+/// Returns `None` for a missing or unreadable file without touching anything.
+/// A file that reads but does not parse goes through [`recover`].
+pub fn load_json<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    parse_json(path, &fs::read(path).ok()?)
+}
+
+/// Parse `content` already read from `path`, recovering from corruption (for async readers)
+pub fn parse_json<T: DeserializeOwned>(path: &Path, content: &[u8]) -> Option<T> {
+    match serde_json::from_slice(content) {
+        Ok(value) => Some(value),
+        Err(e) => recover(path, &e.to_string()),
+    }
+}
+
+/// Quarantine the corrupt `path` and return the best-known state, if any
+///
+/// The file is renamed to `<file>.corrupt-<YYYYmmddTHHMMSS>`; an intact temp
+/// file left by an interrupted atomic write (`<file>.tmp` or `<file>.json.tmp`)
+/// is moved into its place and returned. Every call bumps `recovery_count`.
+pub fn recover<T: DeserializeOwned>(path: &Path, error: &str) -> Option<T> {
+    let now = Local::now();
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let quarantine =
+        path.with_file_name(format!("{}.corrupt-{}", name, now.format("%Y%m%dT%H%M%S")));
+    let quarantined_to = fs::rename(path, &quarantine).ok().map(|_| quarantine);
+
+    let restored = [path.with_extension("tmp"), path.with_extension("json.tmp")]
+        .into_iter()
+        .find_map(|tmp| {
+            let value = serde_json::from_str::<T>(&fs::read_to_string(&tmp).ok()?).ok()?;
+            fs::rename(&tmp, path).ok()?;
+            Some(value)
+        });
+
+    let log_path = RecoveryLog::path_for(path);
+    let mut log = RecoveryLog::load(&log_path);
+    log.record(
+        &name,
+        FileRecovery {
+            count: 0,
+            last_error: error.to_string(),
+            last_at: now.to_rfc3339(),
+            quarantined_to,
+            restored: restored.is_some(),
+        },
+    );
+    let _ = log.save(&log_path);
+
+    restored
+}
+
+/// Delete quarantined copies (`*.corrupt-*`) in `dir` last modified before `older_than`
+///
+/// Returns how many were deleted. The recovery log keeps its counts; only
+/// the copies themselves go.
+pub fn prune_quarantined(dir: &Path, older_than: SystemTime) -> usize {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().contains(".corrupt-"))
+        .filter(|entry| {
+            entry
+                .metadata()
+                .and_then(|metadata| metadata.modified())
+                .is_ok_and(|modified| modified < older_than)
+        })
+        .filter(|entry| fs::remove_file(entry.path()).is_ok())
+        .count()
+}
@@ -0,0 +1,207 @@
+//! Shared timing for periodic jobs
+//!
+//! Update checks, the geo verdict, standby rounds, state sync, the GREEN/RED
+//! probe windows and housekeeping all ask one of two questions: is a job that
+//! last ran at T due now ([`Schedule`]), and which fixed window of the session
+//! does this moment fall in ([`Cadence`]). Answering both in one place keeps
+//! the boundaries consistent: a job is due once *at least* its interval has
+//! elapsed, and a clock that went backwards never makes a job due.
+//!
+//! Jobs that keep no state file of their own record their last run in
+//! [`JobLedger`] (`~/.claude/ccstatus/scheduler.json`); [`run_maintenance`]
+//! runs the housekeeping jobs through it after each render.
+//!
+//! Jitter delays a job by a fixed share of its jitter range derived from the
+//! job key, so the delay is stable from run to run while jobs keyed per
+//! machine (state sync) spread out instead of firing on the same render.
+
+use crate::core::recovery;
+use crate::core::state_location::StateLocation;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Housekeeping job pruning quarantined `.corrupt-*` files
+pub const QUARANTINE_GC_JOB: &str = "quarantine-gc";
+
+/// How often housekeeping jobs run
+pub const MAINTENANCE_INTERVAL_SECS: u64 = 24 * 60 * 60;
+
+/// Age after which a quarantined copy is deleted
+pub const QUARANTINE_KEEP: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// FNV-1a of `bytes`: stable across builds and runs, unlike std's hasher
+pub fn stable_hash(bytes: impl IntoIterator<Item = u8>) -> u64 {
+    bytes
+        .into_iter()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+        })
+}
+
+/// Interval-based schedule for a job identified by a key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Schedule {
+    pub interval_secs: u64,
+    /// Upper bound of the extra delay added per job key
+    pub jitter_secs: u64,
+}
+
+impl Schedule {
+    pub const fn every(interval_secs: u64) -> Self {
+        Self {
+            interval_secs,
+            jitter_secs: 0,
+        }
+    }
+
+    pub const fn with_jitter(mut self, jitter_secs: u64) -> Self {
+        self.jitter_secs = jitter_secs;
+        self
+    }
+
+    /// Extra delay for `job`, between 0 and `jitter_secs` inclusive
+    pub fn jitter_for(&self, job: &str) -> u64 {
+        if self.jitter_secs == 0 {
+            return 0;
+        }
+        stable_hash(job.bytes()) % (self.jitter_secs + 1)
+    }
+
+    /// When `job` is next due (Unix seconds); None when it never ran and is due now
+    pub fn next_run(&self, job: &str, last_run: Option<i64>) -> Option<i64> {
+        let delay = self.interval_secs.saturating_add(self.jitter_for(job));
+        last_run.map(|last| last.saturating_add(delay.min(i64::MAX as u64) as i64))
+    }
+
+    /// Whether `job`, last run at `last_run`, is due at `now`
+    ///
+    /// A `last_run` in the future (clock moved backwards) is not due until
+    /// the interval has elapsed from it.
+    pub fn is_due(&self, job: &str, last_run: Option<i64>, now: i64) -> bool {
+        self.next_run(job, last_run).is_none_or(|next| now >= next)
+    }
+}
+
+/// Fixed windows counted from session start
+///
+/// Time is cut into periods of `period_ms`; a window is open during the first
+/// `width_ms` of a period. With a stride only every n-th period opens, and
+/// window IDs stay those of the unstrided cadence so deduplication carries
+/// over when the stride changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cadence {
+    pub period_ms: u64,
+    pub width_ms: u64,
+    pub stride: u64,
+}
+
+impl Cadence {
+    pub const fn new(period_ms: u64, width_ms: u64) -> Self {
+        Self {
+            period_ms,
+            width_ms,
+            stride: 1,
+        }
+    }
+
+    /// Open only every `stride`-th window (0 is treated as 1)
+    pub const fn with_stride(mut self, stride: u64) -> Self {
+        self.stride = if stride == 0 { 1 } else { stride };
+        self
+    }
+
+    /// ID of the period containing `elapsed_ms`
+    pub fn window_id(&self, elapsed_ms: u64) -> u64 {
+        elapsed_ms.checked_div(self.period_ms).unwrap_or(0)
+    }
+
+    /// ID of the window open at `elapsed_ms`; None between windows
+    pub fn open_window(&self, elapsed_ms: u64) -> Option<u64> {
+        if self.period_ms == 0 {
+            return None;
+        }
+        let window_id = self.window_id(elapsed_ms);
+        (elapsed_ms % self.period_ms < self.width_ms && window_id.is_multiple_of(self.stride))
+            .then_some(window_id)
+    }
+}
+
+/// Last run of each job without a state file of its own
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JobLedger {
+    /// Job key → Unix seconds of its last run
+    #[serde(default)]
+    pub last_run: BTreeMap<String, i64>,
+}
+
+impl JobLedger {
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| home.join(".claude").join("ccstatus").join("scheduler.json"))
+    }
+
+    /// Load the ledger; a missing file is empty, a corrupt one is quarantined
+    pub fn load(path: &Path) -> Self {
+        recovery::load_json(path).unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, content)?;
+        fs::rename(&tmp, path)
+    }
+
+    pub fn is_due(&self, job: &str, schedule: &Schedule, now: i64) -> bool {
+        schedule.is_due(job, self.last_run.get(job).copied(), now)
+    }
+
+    pub fn record(&mut self, job: &str, at: i64) {
+        self.last_run.insert(job.to_string(), at);
+    }
+}
+
+/// Run due housekeeping jobs for the state directory holding `ledger_path`
+///
+/// Returns the jobs that ran. Each job is recorded as run even when it fails,
+/// so a persistent failure is retried on the next interval rather than on
+/// every render.
+pub fn run_maintenance_at(ledger_path: &Path, now: SystemTime) -> Vec<&'static str> {
+    let now_secs = now
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default();
+    let schedule = Schedule::every(MAINTENANCE_INTERVAL_SECS);
+    let mut ledger = JobLedger::load(ledger_path);
+    let mut ran = Vec::new();
+
+    if ledger.is_due(QUARANTINE_GC_JOB, &schedule, now_secs) {
+        if let Some(dir) = ledger_path.parent() {
+            recovery::prune_quarantined(dir, now.checked_sub(QUARANTINE_KEEP).unwrap_or(now));
+        }
+        ledger.record(QUARANTINE_GC_JOB, now_secs);
+        ran.push(QUARANTINE_GC_JOB);
+    }
+
+    if !ran.is_empty() {
+        let _ = ledger.save(ledger_path);
+    }
+    ran
+}
+
+/// Run due housekeeping jobs in `~/.claude/ccstatus`, including the copy back of
+/// local monitoring state to `location`'s home directory (see [`crate::core::state_location`])
+pub fn run_maintenance(location: Option<&StateLocation>) {
+    if let Some(path) = JobLedger::default_path() {
+        run_maintenance_at(&path, SystemTime::now());
+        if let Some(location) = location {
+            location.sync_back_at(&path, SystemTime::now());
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use super::stopwatch::StopwatchSegment;
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use crate::core::i18n::{Locale, Text};
+use crate::core::recovery;
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Length of a subscription usage block
+pub const BLOCK_HOURS: i64 = 5;
+
+/// The current 5-hour usage block, persisted to `~/.claude/ccstatus/usage-block.json`
+///
+/// Blocks are account-wide while transcripts are per session, so the block a
+/// session joined is kept on disk: a session started late in a block (or a
+/// restarted one) still counts down to the block's real end.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageBlock {
+    pub started_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+}
+
+impl UsageBlock {
+    /// Block opened by a request at `at`: starts on the hour, lasts `BLOCK_HOURS`
+    pub fn starting_at(at: DateTime<Utc>) -> Self {
+        let started_at = at.duration_trunc(Duration::hours(1)).unwrap_or(at);
+        Self {
+            started_at,
+            ends_at: started_at + Duration::hours(BLOCK_HOURS),
+        }
+    }
+
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        now < self.ends_at
+    }
+
+    /// Block holding the last of `timestamps`, continuing from `previous`
+    ///
+    /// Timestamps before `previous` started are ignored; the first one at or after
+    /// a block's end opens the next block.
+    pub fn track(
+        previous: Option<UsageBlock>,
+        timestamps: impl IntoIterator<Item = DateTime<Utc>>,
+    ) -> Option<UsageBlock> {
+        let mut timestamps: Vec<_> = timestamps.into_iter().collect();
+        timestamps.sort();
+
+        let mut block = previous;
+        for at in timestamps {
+            match &block {
+                Some(current) if at < current.ends_at => {}
+                _ => block = Some(Self::starting_at(at)),
+            }
+        }
+        block
+    }
+
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| {
+            home.join(".claude")
+                .join("ccstatus")
+                .join("usage-block.json")
+        })
+    }
+
+    /// Load the persisted block; a missing file is no block, a corrupt one is quarantined
+    pub fn load(path: &Path) -> Option<Self> {
+        recovery::load_json(path)
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, content)?;
+        fs::rename(&tmp, path)
+    }
+}
+
+/// Time left in the current 5-hour usage block
+///
+/// The block starts on the hour of the first request after the previous block
+/// ended. Hidden when no block is active, i.e. nothing was sent for the last
+/// five hours.
+pub struct BlockSegment {
+    state_path: Option<PathBuf>,
+    /// Language of the countdown
+    locale: Locale,
+    now: Option<DateTime<Utc>>,
+}
+
+impl Default for BlockSegment {
+    fn default() -> Self {
+        Self {
+            state_path: UsageBlock::default_path(),
+            locale: Locale::default(),
+            now: None,
+        }
+    }
+}
+
+impl BlockSegment {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Where the block is persisted (None keeps it in memory only)
+    pub fn with_state_path(mut self, state_path: Option<PathBuf>) -> Self {
+        self.state_path = state_path;
+        self
+    }
+
+    /// Language of the countdown (`locale`)
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Use a fixed clock (for testing)
+    pub fn with_now(mut self, now: DateTime<Utc>) -> Self {
+        self.now = Some(now);
+        self
+    }
+}
+
+impl Segment for BlockSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let now = self.now.unwrap_or_else(Utc::now);
+        let persisted = self.state_path.as_deref().and_then(UsageBlock::load);
+
+        let timestamps = input
+            .usage_records()
+            .iter()
+            .filter_map(|record| record.timestamp)
+            .filter(|&at| at <= now);
+        let block = UsageBlock::track(persisted.clone(), timestamps)?;
+
+        // Only a new block touches disk
+        if persisted.as_ref() != Some(&block) {
+            if let Some(path) = &self.state_path {
+                let _ = block.save(path);
+            }
+        }
+        if !block.is_active(now) {
+            return None;
+        }
+
+        let remaining = (block.ends_at - now).num_seconds();
+        let elapsed = (now - block.started_at).num_seconds();
+        let mut metadata = HashMap::new();
+        metadata.insert("started_at".to_string(), block.started_at.to_rfc3339());
+        metadata.insert("ends_at".to_string(), block.ends_at.to_rfc3339());
+        metadata.insert("remaining_secs".to_string(), remaining.to_string());
+        metadata.insert(
+            "elapsed_percent".to_string(),
+            (elapsed * 100 / (BLOCK_HOURS * 3600)).to_string(),
+        );
+
+        Some(SegmentData {
+            primary: format!(
+                "{} {}",
+                StopwatchSegment::format_elapsed(remaining),
+                self.locale.text(Text::BlockLeft)
+            ),
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Block
+    }
+}
@@ -0,0 +1,226 @@
+use super::cost::PriceTable;
+use super::{Segment, SegmentData};
+use crate::config::{AnsiColor, InputData, SegmentConfig, SegmentId};
+use crate::core::format::NumberFormat;
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Rolling window the rate is measured over, unless `window_mins` is set
+const DEFAULT_WINDOW_MINS: i64 = 60;
+
+/// Shortest span a rate is extrapolated from, so one early request is no spike
+const MIN_SPAN_MINS: i64 = 5;
+
+/// What the rate counts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BurnUnit {
+    /// Spend per hour, priced like the cost segment (`$4.20/h`)
+    #[default]
+    Cost,
+    /// Input, output and cache-write tokens per hour (`31k tok/h`)
+    Tokens,
+}
+
+/// How the rate compares with `warn_at` / `critical_at`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BurnLevel {
+    Normal,
+    Warning,
+    Critical,
+}
+
+impl BurnLevel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BurnLevel::Normal => "normal",
+            BurnLevel::Warning => "warning",
+            BurnLevel::Critical => "critical",
+        }
+    }
+}
+
+/// Rolling spend or token throughput per hour
+///
+/// Sums the assistant messages of the last `window_mins` and divides by the time
+/// since the first of them (at least `MIN_SPAN_MINS`). Hidden while the window
+/// holds no usage, e.g. in an idle session.
+pub struct BurnRateSegment {
+    unit: BurnUnit,
+    window: Duration,
+    warn_at: Option<f64>,
+    critical_at: Option<f64>,
+    prices: PriceTable,
+    number_format: NumberFormat,
+    now: Option<DateTime<Utc>>,
+}
+
+impl Default for BurnRateSegment {
+    fn default() -> Self {
+        Self {
+            unit: BurnUnit::Cost,
+            window: Duration::minutes(DEFAULT_WINDOW_MINS),
+            warn_at: None,
+            critical_at: None,
+            prices: PriceTable::default(),
+            number_format: NumberFormat::default(),
+            now: None,
+        }
+    }
+}
+
+impl BurnRateSegment {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read `unit`, `window_mins`, `warn_at`, `critical_at` and `prices` from segment options
+    pub fn from_options(options: &HashMap<String, serde_json::Value>) -> Self {
+        let unit = options
+            .get("unit")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default();
+        let window_mins = options
+            .get("window_mins")
+            .and_then(|v| v.as_i64())
+            .filter(|&mins| mins > 0)
+            .unwrap_or(DEFAULT_WINDOW_MINS);
+
+        Self::new()
+            .with_unit(unit)
+            .with_window(Duration::minutes(window_mins))
+            .with_thresholds(
+                options.get("warn_at").and_then(|v| v.as_f64()),
+                options.get("critical_at").and_then(|v| v.as_f64()),
+            )
+            .with_prices(PriceTable::from_options(options))
+    }
+
+    pub fn with_unit(mut self, unit: BurnUnit) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    pub fn with_window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Rates per hour at which the segment turns `warn_color` / `critical_color`
+    pub fn with_thresholds(mut self, warn_at: Option<f64>, critical_at: Option<f64>) -> Self {
+        self.warn_at = warn_at;
+        self.critical_at = critical_at;
+        self
+    }
+
+    pub fn with_prices(mut self, prices: PriceTable) -> Self {
+        self.prices = prices;
+        self
+    }
+
+    /// Amount formatting (`[formatting]` table)
+    pub fn with_number_format(mut self, number_format: NumberFormat) -> Self {
+        self.number_format = number_format;
+        self
+    }
+
+    /// Use a fixed clock (for testing)
+    pub fn with_now(mut self, now: DateTime<Utc>) -> Self {
+        self.now = Some(now);
+        self
+    }
+
+    pub fn level(&self, rate: f64) -> BurnLevel {
+        if self.critical_at.is_some_and(|limit| rate >= limit) {
+            BurnLevel::Critical
+        } else if self.warn_at.is_some_and(|limit| rate >= limit) {
+            BurnLevel::Warning
+        } else {
+            BurnLevel::Normal
+        }
+    }
+
+    /// `31k`, `1.2M`: token counts as short as the usage segment's
+    pub fn format_tokens(tokens: f64) -> String {
+        if tokens >= 1_000_000.0 {
+            format!("{:.1}M", tokens / 1_000_000.0)
+        } else if tokens >= 10_000.0 {
+            format!("{:.0}k", tokens / 1000.0)
+        } else if tokens >= 1000.0 {
+            format!("{:.1}k", tokens / 1000.0)
+        } else {
+            format!("{:.0}", tokens)
+        }
+    }
+}
+
+impl Segment for BurnRateSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let now = self.now.unwrap_or_else(Utc::now);
+        let window_start = now - self.window;
+
+        let mut first: Option<DateTime<Utc>> = None;
+        let mut amount = 0.0;
+        for record in input.usage_records() {
+            let Some(timestamp) = record.timestamp.filter(|&at| at >= window_start) else {
+                continue;
+            };
+            first = Some(first.map_or(timestamp, |first| first.min(timestamp)));
+            amount += match self.unit {
+                BurnUnit::Cost => self
+                    .prices
+                    .lookup(&record.model)
+                    .map_or(0.0, |price| price.cost(&record.tokens)),
+                BurnUnit::Tokens => record.tokens.billable() as f64,
+            };
+        }
+
+        let span = (now - first?).max(Duration::minutes(MIN_SPAN_MINS));
+        let rate = amount * 3600.0 / span.num_seconds() as f64;
+        let level = self.level(rate);
+
+        let primary = match self.unit {
+            BurnUnit::Cost => format!("{}/h", self.number_format.cost(rate)),
+            BurnUnit::Tokens => format!("{} tok/h", Self::format_tokens(rate)),
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert("rate_per_hour".to_string(), format!("{:.6}", rate));
+        metadata.insert("window_amount".to_string(), format!("{:.6}", amount));
+        metadata.insert("span_secs".to_string(), span.num_seconds().to_string());
+        metadata.insert("level".to_string(), level.as_str().to_string());
+
+        Some(SegmentData {
+            primary,
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::BurnRate
+    }
+}
+
+/// `config` recolored for the level the segment reported
+///
+/// Icon and text take `options.warn_color` / `options.critical_color` (same
+/// shape as `colors.text`), bright yellow and bright red by default.
+pub fn level_colors(config: &SegmentConfig, data: &SegmentData) -> SegmentConfig {
+    let (key, fallback) = match data.metadata.get("level").map(String::as_str) {
+        Some("warning") => ("warn_color", 11),
+        Some("critical") => ("critical_color", 9),
+        _ => return config.clone(),
+    };
+    let color = config
+        .options
+        .get(key)
+        .and_then(|v| serde_json::from_value::<AnsiColor>(v.clone()).ok())
+        .unwrap_or(AnsiColor::Color16 { c16: fallback });
+
+    let mut config = config.clone();
+    config.colors.icon = Some(color.clone());
+    config.colors.text = Some(color);
+    config
+}
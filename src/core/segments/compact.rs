@@ -0,0 +1,200 @@
+use super::burn_rate::BurnLevel;
+use super::model::ModelSegment;
+use super::usage::parse_transcript_usage;
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use crate::core::i18n::{Locale, Text};
+use std::collections::HashMap;
+
+/// Tokens Claude Code keeps free below the context window before it auto-compacts
+pub const DEFAULT_COMPACT_BUFFER_TOKENS: u64 = 45_000;
+
+/// Share of the compaction threshold (percent) from which the segment warns
+const DEFAULT_WARN_PERCENT: f64 = 80.0;
+
+/// Share of the compaction threshold (percent) from which the warning is critical
+const DEFAULT_CRITICAL_PERCENT: f64 = 95.0;
+
+/// Where the context stands against the auto-compaction threshold
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompactEstimate {
+    /// Context tokens of the last assistant message
+    pub used_tokens: u64,
+    /// Context size at which Claude Code compacts
+    pub threshold_tokens: u64,
+}
+
+impl CompactEstimate {
+    /// Threshold is the context window less `buffer_tokens`
+    pub fn new(used_tokens: u64, context_tokens: u64, buffer_tokens: u64) -> Self {
+        Self {
+            used_tokens,
+            threshold_tokens: context_tokens.saturating_sub(buffer_tokens).max(1),
+        }
+    }
+
+    /// Tokens left before compaction, 0 once it is due
+    pub fn remaining_tokens(&self) -> u64 {
+        self.threshold_tokens.saturating_sub(self.used_tokens)
+    }
+
+    /// Used share of the threshold in percent (100 or more = compaction due)
+    pub fn percent(&self) -> f64 {
+        self.used_tokens as f64 * 100.0 / self.threshold_tokens as f64
+    }
+}
+
+/// Context left before Claude Code auto-compacts, with an escalating warning
+///
+/// Uses the context tokens of the last assistant message in the transcript and
+/// the model's context window (1M for `[1m]` models). Hidden below `warn_at`
+/// percent of the threshold unless `always_show` is set; the `level` metadata
+/// recolors it as the burn rate segment does.
+pub struct CompactSegment {
+    buffer_tokens: u64,
+    warn_percent: f64,
+    critical_percent: f64,
+    always_show: bool,
+    /// Language of the warning
+    locale: Locale,
+}
+
+impl Default for CompactSegment {
+    fn default() -> Self {
+        Self {
+            buffer_tokens: DEFAULT_COMPACT_BUFFER_TOKENS,
+            warn_percent: DEFAULT_WARN_PERCENT,
+            critical_percent: DEFAULT_CRITICAL_PERCENT,
+            always_show: false,
+            locale: Locale::default(),
+        }
+    }
+}
+
+impl CompactSegment {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read `buffer_tokens`, `warn_at`, `critical_at` and `always_show` from segment options
+    pub fn from_options(options: &HashMap<String, serde_json::Value>) -> Self {
+        let defaults = Self::default();
+        Self::new()
+            .with_buffer_tokens(
+                options
+                    .get("buffer_tokens")
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(defaults.buffer_tokens),
+            )
+            .with_thresholds(
+                options.get("warn_at").and_then(|v| v.as_f64()),
+                options.get("critical_at").and_then(|v| v.as_f64()),
+            )
+            .with_always_show(
+                options
+                    .get("always_show")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false),
+            )
+    }
+
+    /// Tokens below the context window at which compaction starts
+    pub fn with_buffer_tokens(mut self, buffer_tokens: u64) -> Self {
+        self.buffer_tokens = buffer_tokens;
+        self
+    }
+
+    /// Warning and critical levels in percent of the threshold; None keeps the default
+    pub fn with_thresholds(mut self, warn_at: Option<f64>, critical_at: Option<f64>) -> Self {
+        if let Some(warn_at) = warn_at.filter(|p| *p > 0.0) {
+            self.warn_percent = warn_at;
+        }
+        if let Some(critical_at) = critical_at.filter(|p| *p > 0.0) {
+            self.critical_percent = critical_at;
+        }
+        self
+    }
+
+    /// Show the remaining context even far from compaction
+    pub fn with_always_show(mut self, always_show: bool) -> Self {
+        self.always_show = always_show;
+        self
+    }
+
+    /// Language of the warning (`locale`)
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    pub fn level(&self, percent: f64) -> BurnLevel {
+        if percent >= self.critical_percent {
+            BurnLevel::Critical
+        } else if percent >= self.warn_percent {
+            BurnLevel::Warning
+        } else {
+            BurnLevel::Normal
+        }
+    }
+
+    fn format_tokens(tokens: u64) -> String {
+        if tokens >= 1000 {
+            format!("{}k", tokens / 1000)
+        } else {
+            tokens.to_string()
+        }
+    }
+}
+
+impl Segment for CompactSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let used_tokens = if input.transcript_path == "mock_preview" {
+            150_000
+        } else {
+            parse_transcript_usage(&input.transcript_path) as u64
+        };
+        if used_tokens == 0 {
+            return None;
+        }
+        let context_tokens =
+            ModelSegment::context_tokens(&input.model.id, &input.model.display_name);
+        let estimate = CompactEstimate::new(used_tokens, context_tokens, self.buffer_tokens);
+        let percent = estimate.percent();
+        let level = self.level(percent);
+        if level == BurnLevel::Normal && !self.always_show {
+            return None;
+        }
+
+        let primary = match estimate.remaining_tokens() {
+            0 => self.locale.text(Text::CompactDue).to_string(),
+            remaining => format!(
+                "{} {}",
+                Self::format_tokens(remaining),
+                self.locale.text(Text::ToCompact)
+            ),
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert("tokens".to_string(), used_tokens.to_string());
+        metadata.insert(
+            "threshold".to_string(),
+            estimate.threshold_tokens.to_string(),
+        );
+        metadata.insert(
+            "remaining".to_string(),
+            estimate.remaining_tokens().to_string(),
+        );
+        metadata.insert("percent".to_string(), format!("{:.1}", percent));
+        metadata.insert("level".to_string(), level.as_str().to_string());
+
+        Some(SegmentData {
+            primary,
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Compact
+    }
+}
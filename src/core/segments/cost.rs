@@ -0,0 +1,339 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, RawUsage, SegmentId};
+use crate::core::format::NumberFormat;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+
+/// Built-in list prices in USD per million tokens: (key, input, output, cache write, cache read)
+///
+/// Keys match anywhere in the model id; the longest matching key wins, so
+/// `opus-4-5` overrides `opus` for `claude-opus-4-5-20251101`.
+const DEFAULT_PRICES: [(&str, f64, f64, f64, f64); 6] = [
+    ("opus", 15.0, 75.0, 18.75, 1.50),
+    ("opus-4-5", 5.0, 25.0, 6.25, 0.50),
+    ("sonnet", 3.0, 15.0, 3.75, 0.30),
+    ("haiku", 0.80, 4.0, 1.0, 0.08),
+    ("haiku-4-5", 1.0, 5.0, 1.25, 0.10),
+    ("3-haiku", 0.25, 1.25, 0.30, 0.03),
+];
+
+/// Price of one model in USD per million tokens
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelPrice {
+    pub input: f64,
+    pub output: f64,
+    pub cache_write: f64,
+    pub cache_read: f64,
+}
+
+impl ModelPrice {
+    /// Cache prices follow Anthropic's ratios: writes 1.25x input, reads 0.1x input
+    pub fn new(input: f64, output: f64) -> Self {
+        Self {
+            input,
+            output,
+            cache_write: input * 1.25,
+            cache_read: input * 0.1,
+        }
+    }
+
+    /// Cost in USD of `tokens`
+    pub fn cost(&self, tokens: &ModelTokens) -> f64 {
+        (tokens.input as f64 * self.input
+            + tokens.output as f64 * self.output
+            + tokens.cache_write as f64 * self.cache_write
+            + tokens.cache_read as f64 * self.cache_read)
+            / 1_000_000.0
+    }
+}
+
+/// `options.prices` entry; omitted cache prices derive from `input`
+#[derive(Deserialize)]
+struct PriceOption {
+    input: f64,
+    output: f64,
+    cache_write: Option<f64>,
+    cache_read: Option<f64>,
+}
+
+/// Model id fragment → price
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceTable {
+    prices: BTreeMap<String, ModelPrice>,
+}
+
+impl Default for PriceTable {
+    fn default() -> Self {
+        let prices = DEFAULT_PRICES
+            .iter()
+            .map(|&(key, input, output, cache_write, cache_read)| {
+                let price = ModelPrice {
+                    input,
+                    output,
+                    cache_write,
+                    cache_read,
+                };
+                (key.to_string(), price)
+            })
+            .collect();
+        Self { prices }
+    }
+}
+
+impl PriceTable {
+    /// Built-in prices overlaid with the segment's `options.prices` table
+    ///
+    /// Invalid entries are skipped so a typo never hides the segment.
+    pub fn from_options(options: &HashMap<String, serde_json::Value>) -> Self {
+        let mut table = Self::default();
+        let Some(serde_json::Value::Object(entries)) = options.get("prices") else {
+            return table;
+        };
+        for (key, value) in entries {
+            if let Ok(option) = serde_json::from_value::<PriceOption>(value.clone()) {
+                let mut price = ModelPrice::new(option.input, option.output);
+                price.cache_write = option.cache_write.unwrap_or(price.cache_write);
+                price.cache_read = option.cache_read.unwrap_or(price.cache_read);
+                table.insert(key, price);
+            }
+        }
+        table
+    }
+
+    pub fn insert(&mut self, key: &str, price: ModelPrice) {
+        self.prices.insert(key.to_lowercase(), price);
+    }
+
+    /// Price for `model`: the longest key contained in the model id
+    pub fn lookup(&self, model: &str) -> Option<ModelPrice> {
+        let model = model.to_lowercase();
+        self.prices
+            .iter()
+            .filter(|(key, _)| model.contains(key.as_str()))
+            .max_by_key(|(key, _)| key.len())
+            .map(|(_, price)| *price)
+    }
+}
+
+/// Token totals of one model
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ModelTokens {
+    pub input: u64,
+    pub output: u64,
+    pub cache_write: u64,
+    pub cache_read: u64,
+}
+
+impl ModelTokens {
+    /// Tokens that make up new work: input, output and cache writes
+    ///
+    /// Cache reads are left out; they repeat the whole context on every turn.
+    pub fn billable(&self) -> u64 {
+        self.input + self.output + self.cache_write
+    }
+
+    pub fn merge(&mut self, other: &ModelTokens) {
+        self.input += other.input;
+        self.output += other.output;
+        self.cache_write += other.cache_write;
+        self.cache_read += other.cache_read;
+    }
+
+    fn add(&mut self, usage: RawUsage) {
+        let usage = usage.normalize();
+        self.input += usage.input_tokens as u64;
+        self.output += usage.output_tokens as u64;
+        self.cache_write += usage.cache_creation_input_tokens as u64;
+        self.cache_read += usage.cache_read_input_tokens as u64;
+    }
+}
+
+/// Transcript line fields needed to total usage
+#[derive(Deserialize)]
+struct UsageEntry {
+    r#type: Option<String>,
+    timestamp: Option<String>,
+    message: Option<UsageMessage>,
+}
+
+#[derive(Deserialize)]
+struct UsageMessage {
+    id: Option<String>,
+    model: Option<String>,
+    usage: Option<RawUsage>,
+}
+
+/// Usage of one assistant message
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsageRecord {
+    pub timestamp: Option<DateTime<Utc>>,
+    pub model: String,
+    pub tokens: ModelTokens,
+}
+
+/// Usage of every assistant message in the transcript, in order
+///
+/// Claude Code writes one line per content block of a response, each repeating
+/// the message's usage; lines are counted once per message id.
+pub fn usage_records<P: AsRef<Path>>(transcript_path: P) -> Vec<UsageRecord> {
+    let mut records = Vec::new();
+    let Ok(file) = fs::File::open(&transcript_path) else {
+        return records;
+    };
+
+    let mut seen = HashSet::new();
+    for line in BufReader::new(file).lines().map_while(Result::ok) {
+        let Ok(entry) = serde_json::from_str::<UsageEntry>(line.trim()) else {
+            continue;
+        };
+        if entry.r#type.as_deref() != Some("assistant") {
+            continue;
+        }
+        let Some(UsageMessage {
+            id,
+            model,
+            usage: Some(usage),
+        }) = entry.message
+        else {
+            continue;
+        };
+        if let Some(id) = id {
+            if !seen.insert(id) {
+                continue;
+            }
+        }
+
+        let mut tokens = ModelTokens::default();
+        tokens.add(usage);
+        records.push(UsageRecord {
+            timestamp: entry
+                .timestamp
+                .as_deref()
+                .and_then(|timestamp| DateTime::parse_from_rfc3339(timestamp).ok())
+                .map(|timestamp| timestamp.with_timezone(&Utc)),
+            model: model.unwrap_or_default(),
+            tokens,
+        });
+    }
+    records
+}
+
+/// Usage records of the session transcript, parsed on first use
+///
+/// Clones share the parsed records: each segment of a render is collected on
+/// its own thread with a clone of the input, and the transcript is read once.
+#[derive(Clone, Default)]
+pub struct UsageCache(Arc<OnceLock<Vec<UsageRecord>>>);
+
+impl UsageCache {
+    /// Records of `transcript_path`, parsed on the first call
+    pub fn records(&self, transcript_path: &str) -> &[UsageRecord] {
+        self.0.get_or_init(|| usage_records(transcript_path))
+    }
+}
+
+/// Token totals per model over the whole transcript
+pub fn session_usage<P: AsRef<Path>>(transcript_path: P) -> BTreeMap<String, ModelTokens> {
+    usage_by_model(&usage_records(transcript_path))
+}
+
+/// Token totals per model of `records`
+pub fn usage_by_model(records: &[UsageRecord]) -> BTreeMap<String, ModelTokens> {
+    let mut totals: BTreeMap<String, ModelTokens> = BTreeMap::new();
+    for record in records {
+        totals
+            .entry(record.model.clone())
+            .or_default()
+            .merge(&record.tokens);
+    }
+    totals
+}
+
+/// Cumulative session cost from transcript usage and a price table
+///
+/// Claude Code's own `cost.total_cost_usd` (when reported) is kept in the
+/// `reported_usd` metadata for comparison; the segment shows the transcript figure.
+#[derive(Default)]
+pub struct CostSegment {
+    prices: PriceTable,
+    number_format: NumberFormat,
+    reported_usd: Option<f64>,
+}
+
+impl CostSegment {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_prices(mut self, prices: PriceTable) -> Self {
+        self.prices = prices;
+        self
+    }
+
+    /// Amount formatting (`[formatting]` table)
+    pub fn with_number_format(mut self, number_format: NumberFormat) -> Self {
+        self.number_format = number_format;
+        self
+    }
+
+    /// Session cost reported by Claude Code (`cost.total_cost_usd`)
+    pub fn with_reported_usd(mut self, reported_usd: Option<f64>) -> Self {
+        self.reported_usd = reported_usd;
+        self
+    }
+}
+
+impl Segment for CostSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let usage = usage_by_model(input.usage_records());
+        if usage.is_empty() {
+            return None;
+        }
+
+        let mut total = 0.0;
+        let mut tokens = ModelTokens::default();
+        let mut unpriced = Vec::new();
+        for (model, model_tokens) in &usage {
+            match self.prices.lookup(model) {
+                Some(price) => total += price.cost(model_tokens),
+                None => unpriced.push(if model.is_empty() { "unknown" } else { model }),
+            }
+            tokens.merge(model_tokens);
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert("usd".to_string(), format!("{:.6}", total));
+        metadata.insert("models".to_string(), usage.len().to_string());
+        metadata.insert("input_tokens".to_string(), tokens.input.to_string());
+        metadata.insert("output_tokens".to_string(), tokens.output.to_string());
+        metadata.insert(
+            "cache_write_tokens".to_string(),
+            tokens.cache_write.to_string(),
+        );
+        metadata.insert(
+            "cache_read_tokens".to_string(),
+            tokens.cache_read.to_string(),
+        );
+        if !unpriced.is_empty() {
+            metadata.insert("unpriced_models".to_string(), unpriced.join(","));
+        }
+        if let Some(reported) = self.reported_usd {
+            metadata.insert("reported_usd".to_string(), format!("{:.6}", reported));
+        }
+
+        Some(SegmentData {
+            primary: self.number_format.cost(total),
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Cost
+    }
+}
@@ -0,0 +1,240 @@
+//! External command segments (`[[custom_segments]]` in config.toml)
+//!
+//! Each entry runs a shell command in the workspace directory and shows the
+//! first non-empty line of its stdout. Commands start together on their own
+//! threads, so they run concurrently with each other and with the built-in
+//! segments; one that outlives its `timeout_ms` is killed and rendered as a
+//! failure placeholder. Empty output hides the segment.
+//!
+//! The deadline covers the command's stdout as well as the shell: a child it
+//! backgrounds with stdout still open is killed with it, in its process group.
+//!
+//! With a layout template, all entries render at the `{custom}` placeholder.
+
+use super::health::SegmentFailure;
+use super::SegmentData;
+use crate::config::{
+    ColorConfig, CustomSegmentConfig, IconConfig, SegmentConfig, SegmentId, TextStyleConfig,
+};
+use crate::core::icons::{Icon, IconSet};
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// How often a running command is checked for exit
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// A custom segment whose command is running
+pub struct PendingCustomSegment {
+    config: CustomSegmentConfig,
+    result: mpsc::Receiver<Result<Option<String>, SegmentFailure>>,
+}
+
+impl PendingCustomSegment {
+    /// Start `config.command` in `cwd` (when it exists) on its own thread
+    pub fn spawn(config: &CustomSegmentConfig, cwd: &str) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let command = config.command.clone();
+        let timeout = Duration::from_millis(config.timeout_ms);
+        let cwd = Path::new(cwd).is_dir().then(|| cwd.to_string());
+
+        let spawned = std::thread::Builder::new()
+            .name(format!("custom-{}", config.name))
+            .spawn(move || {
+                let _ = tx.send(run_command(&command, cwd.as_deref(), timeout));
+            });
+        if let Err(e) = spawned {
+            let (tx, failed) = mpsc::channel();
+            let _ = tx.send(Err(SegmentFailure::Command(format!(
+                "failed to spawn worker: {}",
+                e
+            ))));
+            return Self {
+                config: config.clone(),
+                result: failed,
+            };
+        }
+
+        Self {
+            config: config.clone(),
+            result: rx,
+        }
+    }
+
+    pub fn config(&self) -> &CustomSegmentConfig {
+        &self.config
+    }
+
+    /// Key under which the segment's health is recorded, e.g. `custom:weather`
+    pub fn health_key(&self) -> String {
+        format!("custom:{}", self.config.name)
+    }
+
+    /// Block until the command finished or was killed
+    pub fn wait(&self) -> Result<Option<String>, SegmentFailure> {
+        self.result.recv().unwrap_or_else(|_| {
+            Err(SegmentFailure::Command(
+                "worker exited without a result".to_string(),
+            ))
+        })
+    }
+
+    /// Segment config rendering this entry: `Custom` id, the entry's icon, no colors
+    pub fn segment_config(&self) -> SegmentConfig {
+        SegmentConfig {
+            id: SegmentId::Custom,
+            enabled: true,
+            icon: IconConfig {
+                plain: self.config.icon.clone(),
+                nerd_font: self.config.icon.clone(),
+            },
+            colors: ColorConfig {
+                icon: None,
+                text: None,
+                background: None,
+            },
+            styles: TextStyleConfig::default(),
+            options: HashMap::new(),
+        }
+    }
+
+    /// Segment data for a command's output
+    pub fn segment_data(&self, output: String) -> SegmentData {
+        let mut metadata = HashMap::new();
+        metadata.insert("name".to_string(), self.config.name.clone());
+        SegmentData {
+            primary: output,
+            secondary: String::new(),
+            metadata,
+        }
+    }
+
+    /// Placeholder naming the entry, e.g. `⚠ weather`
+    pub fn failure_placeholder(&self, failure: &SegmentFailure, icons: IconSet) -> SegmentData {
+        let mut data = self.segment_data(format!(
+            "{} {}",
+            icons.glyph(Icon::Warning),
+            self.config.name
+        ));
+        data.metadata
+            .insert("error".to_string(), failure.to_string());
+        data
+    }
+}
+
+/// Run `command` through the platform shell; the first non-empty stdout line, if any
+pub fn run_command(
+    command: &str,
+    cwd: Option<&str>,
+    timeout: Duration,
+) -> Result<Option<String>, SegmentFailure> {
+    let deadline = Instant::now() + timeout;
+    let mut shell = shell_command(command);
+    shell
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null());
+    if let Some(cwd) = cwd {
+        shell.current_dir(cwd);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        shell.process_group(0);
+    }
+
+    let mut child = shell
+        .spawn()
+        .map_err(|e| SegmentFailure::Command(e.to_string()))?;
+
+    // Drain stdout on the side so a chatty command cannot block on a full pipe
+    let mut stdout = child.stdout.take();
+    let (tx, output) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut output = String::new();
+        if let Some(ref mut stdout) = stdout {
+            let _ = stdout.read_to_string(&mut output);
+        }
+        let _ = tx.send(output);
+    });
+
+    let status = wait_with_deadline(&mut child, deadline)
+        .map_err(|e| SegmentFailure::Command(e.to_string()))?;
+    let Some(status) = status else {
+        kill_process_group(&mut child);
+        let _ = child.wait();
+        return Err(SegmentFailure::Timeout(timeout));
+    };
+    if !status.success() {
+        kill_process_group(&mut child);
+        return Err(SegmentFailure::Command(status.to_string()));
+    }
+
+    // A backgrounded child may still hold stdout open after the shell exited
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    let Ok(output) = output.recv_timeout(remaining) else {
+        kill_process_group(&mut child);
+        return Err(SegmentFailure::Timeout(timeout));
+    };
+    Ok(output
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string))
+}
+
+fn wait_with_deadline(
+    child: &mut Child,
+    deadline: Instant,
+) -> std::io::Result<Option<std::process::ExitStatus>> {
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        if Instant::now() >= deadline {
+            return Ok(None);
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Kill `child` and every process left in its group
+#[cfg(unix)]
+fn kill_process_group(child: &mut Child) {
+    // The shell leads its own group (`process_group(0)`), so the group id is its pid
+    if let Ok(group) = libc::pid_t::try_from(child.id()) {
+        // SAFETY: killpg only sends a signal; a stale group id fails with ESRCH
+        unsafe {
+            libc::killpg(group, libc::SIGKILL);
+        }
+    }
+    let _ = child.kill();
+}
+
+/// Kill `child` and the processes it started
+#[cfg(windows)]
+fn kill_process_group(child: &mut Child) {
+    let _ = Command::new("taskkill")
+        .args(["/T", "/F", "/PID", &child.id().to_string()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+    let _ = child.kill();
+}
+
+#[cfg(windows)]
+pub(crate) fn shell_command(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.args(["/C", command]);
+    shell
+}
+
+#[cfg(not(windows))]
+pub(crate) fn shell_command(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.args(["-c", command]);
+    shell
+}
@@ -0,0 +1,205 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use crate::core::recovery;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+/// Sessions kept in the state file; the least recently updated are dropped
+const MAX_SESSIONS: usize = 50;
+
+/// Running line counts of one session's transcript
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionDiff {
+    /// Bytes of the transcript already counted
+    pub offset: u64,
+    pub added: u64,
+    pub removed: u64,
+    /// Unix seconds of the last change
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+impl SessionDiff {
+    /// Count the complete lines appended since `offset`
+    ///
+    /// A transcript shorter than `offset` was rewritten and is counted afresh;
+    /// a trailing line without its newline is left for the next render.
+    pub fn advance(&mut self, transcript_path: &Path) -> std::io::Result<bool> {
+        let mut file = fs::File::open(transcript_path)?;
+        if file.metadata()?.len() < self.offset {
+            *self = Self::default();
+        }
+        file.seek(SeekFrom::Start(self.offset))?;
+
+        let mut reader = BufReader::new(file);
+        let mut line = String::new();
+        let start = self.offset;
+        loop {
+            line.clear();
+            let read = reader.read_line(&mut line)?;
+            if read == 0 || !line.ends_with('\n') {
+                break;
+            }
+            self.offset += read as u64;
+
+            let Ok(entry) = serde_json::from_str::<Value>(line.trim()) else {
+                continue;
+            };
+            if let Some(result) = entry.get("toolUseResult") {
+                let (added, removed) = count_changes(result);
+                self.added += added;
+                self.removed += removed;
+            }
+        }
+        Ok(self.offset != start)
+    }
+}
+
+/// Lines added and removed by one Edit, MultiEdit or Write tool result
+///
+/// Counts the `+`/`-` lines of `structuredPatch`; a newly created file has no
+/// patch and counts all of its `content`. Results of other tools carry no
+/// `filePath` and count nothing.
+pub fn count_changes(result: &Value) -> (u64, u64) {
+    if result.get("filePath").is_none() {
+        return (0, 0);
+    }
+
+    let patch_lines: Vec<&str> = result
+        .get("structuredPatch")
+        .and_then(|patch| patch.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|hunk| hunk.get("lines")?.as_array())
+        .flatten()
+        .filter_map(|line| line.as_str())
+        .collect();
+    if !patch_lines.is_empty() {
+        let added = patch_lines.iter().filter(|l| l.starts_with('+')).count();
+        let removed = patch_lines.iter().filter(|l| l.starts_with('-')).count();
+        return (added as u64, removed as u64);
+    }
+
+    if result.get("type").and_then(|t| t.as_str()) == Some("create") {
+        let content = result.get("content").and_then(|c| c.as_str());
+        return (content.map_or(0, |c| c.lines().count() as u64), 0);
+    }
+    (0, 0)
+}
+
+/// Running counts by transcript, persisted to `~/.claude/ccstatus/diff-stat.json`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DiffStatState {
+    #[serde(default)]
+    pub sessions: BTreeMap<String, SessionDiff>,
+}
+
+impl DiffStatState {
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| home.join(".claude").join("ccstatus").join("diff-stat.json"))
+    }
+
+    /// Load the state; a missing file is empty, a corrupt one is quarantined
+    pub fn load(path: &Path) -> Self {
+        recovery::load_json(path).unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, content)?;
+        fs::rename(&tmp, path)
+    }
+
+    fn prune(&mut self) {
+        while self.sessions.len() > MAX_SESSIONS {
+            let oldest = self
+                .sessions
+                .iter()
+                .min_by_key(|(_, session)| session.updated_at)
+                .map(|(key, _)| key.clone());
+            match oldest {
+                Some(key) => self.sessions.remove(&key),
+                None => break,
+            };
+        }
+    }
+}
+
+/// Lines added and removed by Edit/Write tool calls this session (`+120 -34`)
+///
+/// Only the transcript appended since the last render is read; the running
+/// counts and read offset are kept per transcript. Hidden until the session
+/// has changed a line.
+pub struct DiffStatSegment {
+    state_path: Option<PathBuf>,
+}
+
+impl Default for DiffStatSegment {
+    fn default() -> Self {
+        Self {
+            state_path: DiffStatState::default_path(),
+        }
+    }
+}
+
+impl DiffStatSegment {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Where the counts are persisted (None re-reads the whole transcript each time)
+    pub fn with_state_path(mut self, state_path: Option<PathBuf>) -> Self {
+        self.state_path = state_path;
+        self
+    }
+}
+
+impl Segment for DiffStatSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let transcript = Path::new(&input.transcript_path);
+        let mut state = self
+            .state_path
+            .as_deref()
+            .map(DiffStatState::load)
+            .unwrap_or_default();
+
+        let key = input.transcript_path.clone();
+        let mut session = state.sessions.get(&key).cloned().unwrap_or_default();
+        // Only new transcript lines touch disk
+        if session.advance(transcript).ok()? {
+            session.updated_at = Utc::now().timestamp();
+            state.sessions.insert(key, session.clone());
+            state.prune();
+            if let Some(path) = &self.state_path {
+                let _ = state.save(path);
+            }
+        }
+        if session.added == 0 && session.removed == 0 {
+            return None;
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert("added".to_string(), session.added.to_string());
+        metadata.insert("removed".to_string(), session.removed.to_string());
+
+        Some(SegmentData {
+            primary: format!("+{} -{}", session.added, session.removed),
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::DiffStat
+    }
+}
@@ -1,13 +1,146 @@
 use super::{Segment, SegmentData};
 use crate::config::{InputData, SegmentId};
+use serde::Deserialize;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
-#[derive(Default)]
-pub struct DirectorySegment;
+/// How much of the working directory is shown
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PathStyle {
+    /// Last component only (`ccstatus`)
+    #[default]
+    Name,
+    /// Whole path, home shown as `~` (`~/projects/code/ccstatus`)
+    Full,
+    /// Fish-style: parents cut to their first letter (`~/p/c/ccstatus`)
+    Fish,
+}
+
+/// Remote execution context shown as a badge next to the directory name
+#[derive(Debug, Clone, PartialEq)]
+pub enum RemoteContext {
+    /// Dev container (VS Code Remote - Containers, Codespaces)
+    Container { name: String },
+    /// SSH session; `host` is the machine we are logged into
+    Ssh { host: String },
+}
+
+impl RemoteContext {
+    /// Detect from the process environment
+    pub fn detect() -> Option<Self> {
+        Self::from_env(|name| std::env::var(name).ok())
+    }
+
+    /// Detect from environment lookups: `REMOTE_CONTAINERS` / `CODESPACES`, then `SSH_CONNECTION`
+    ///
+    /// A dev container reached over SSH reports the container.
+    pub fn from_env(get: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        let get = |name: &str| get(name).filter(|value| !value.trim().is_empty());
+        let is_true =
+            |name: &str| get(name).is_some_and(|value| value.eq_ignore_ascii_case("true"));
+
+        if is_true("REMOTE_CONTAINERS") || is_true("CODESPACES") {
+            let name = get("CODESPACE_NAME")
+                .or_else(|| get("HOSTNAME"))
+                .unwrap_or_else(|| "devcontainer".to_string());
+            return Some(RemoteContext::Container { name });
+        }
+
+        // SSH_CONNECTION: "<client ip> <client port> <server ip> <server port>"
+        if let Some(connection) = get("SSH_CONNECTION") {
+            let host = get("HOSTNAME")
+                .or_else(|| connection.split_whitespace().nth(2).map(str::to_string))
+                .unwrap_or_else(|| "remote".to_string());
+            return Some(RemoteContext::Ssh { host });
+        }
+
+        None
+    }
+
+    /// Badge text, e.g. `ssh:buildbox` or `container:my-app`
+    pub fn badge(&self) -> String {
+        match self {
+            RemoteContext::Container { name } => format!("container:{}", name),
+            RemoteContext::Ssh { host } => format!("ssh:{}", host),
+        }
+    }
+}
+
+pub struct DirectorySegment {
+    remote: Option<RemoteContext>,
+    style: PathStyle,
+    repo_relative: bool,
+    max_width: Option<usize>,
+    home: Option<String>,
+}
+
+impl Default for DirectorySegment {
+    fn default() -> Self {
+        Self {
+            remote: None,
+            style: PathStyle::Name,
+            repo_relative: false,
+            max_width: None,
+            home: dirs::home_dir().map(|home| home.to_string_lossy().into_owned()),
+        }
+    }
+}
 
 impl DirectorySegment {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Read `style`, `repo_relative` and `max_width` from segment options
+    pub fn from_options(options: &HashMap<String, serde_json::Value>) -> Self {
+        let style = options
+            .get("style")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default();
+        let repo_relative = options
+            .get("repo_relative")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let max_width = options
+            .get("max_width")
+            .and_then(|v| v.as_u64())
+            .filter(|&width| width > 0)
+            .map(|width| width as usize);
+
+        Self::new()
+            .with_style(style)
+            .with_repo_relative(repo_relative)
+            .with_max_width(max_width)
+    }
+
+    pub fn with_style(mut self, style: PathStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Inside a git repository, show the path from the repository root (`ccstatus/src/core`)
+    pub fn with_repo_relative(mut self, repo_relative: bool) -> Self {
+        self.repo_relative = repo_relative;
+        self
+    }
+
+    /// Longest path in characters; leading components give way to `…` first
+    pub fn with_max_width(mut self, max_width: Option<usize>) -> Self {
+        self.max_width = max_width;
+        self
+    }
+
+    /// Home directory replaced by `~` (defaults to the user's)
+    pub fn with_home(mut self, home: Option<String>) -> Self {
+        self.home = home;
+        self
+    }
+
+    /// Show a remote context badge (dev container / SSH) after the directory name
+    pub fn with_remote_context(mut self, remote: Option<RemoteContext>) -> Self {
+        self.remote = remote;
+        self
     }
 
     /// Extract directory name from path, handling both Unix and Windows separators
@@ -34,22 +167,128 @@ impl DirectorySegment {
             result.to_string()
         }
     }
+
+    /// Nearest ancestor of `path` (itself included) holding a `.git` entry
+    pub fn find_repo_root(path: &Path) -> Option<PathBuf> {
+        path.ancestors()
+            .find(|dir| dir.join(".git").exists())
+            .map(Path::to_path_buf)
+    }
+
+    /// `path` as configured: anchored at the repository root or `~`, shortened per style
+    ///
+    /// `max_width` drops leading components first (`…/c/ccstatus`), then cuts the last one.
+    fn display_path(&self, path: &str, repo_root: Option<&Path>) -> String {
+        let separator = if !path.contains('/') && path.contains('\\') {
+            "\\"
+        } else {
+            "/"
+        };
+        let home = self.home.as_deref().filter(|home| !home.is_empty());
+
+        // The anchor (repository name, `~`, root separator) is never shortened
+        let (anchor, rest) = match (repo_root, home) {
+            (Some(root), _) => {
+                let root = root.to_string_lossy();
+                (Self::extract_directory_name(&root), &path[root.len()..])
+            }
+            (None, Some(home))
+                if path == home
+                    || path
+                        .strip_prefix(home)
+                        .is_some_and(|rest| rest.starts_with(['/', '\\'])) =>
+            {
+                ("~".to_string(), &path[home.len()..])
+            }
+            _ => (String::new(), path),
+        };
+
+        let mut components: Vec<String> = rest
+            .split(['/', '\\'])
+            .filter(|component| !component.is_empty())
+            .map(str::to_string)
+            .collect();
+        if self.style == PathStyle::Fish {
+            let parents = components.len().saturating_sub(1);
+            for component in &mut components[..parents] {
+                *component = Self::fish_abbreviate(component);
+            }
+        }
+
+        let mut parts: Vec<String> = if self.style == PathStyle::Name && repo_root.is_none() {
+            vec![Self::extract_directory_name(path)]
+        } else if anchor.is_empty() && !path.starts_with(['/', '\\']) {
+            components
+        } else {
+            // An empty anchor renders the leading separator of an absolute path
+            std::iter::once(anchor).chain(components).collect()
+        };
+        let mut display = parts.join(separator);
+        if display.is_empty() {
+            display = separator.to_string();
+        }
+        let Some(max_width) = self.max_width else {
+            return display;
+        };
+        while display.chars().count() > max_width && parts.len() > 1 {
+            parts.remove(0);
+            display = format!("…{}{}", separator, parts.join(separator));
+        }
+        if display.chars().count() > max_width {
+            // The last component alone is still too wide: keep its start
+            display = parts
+                .concat()
+                .chars()
+                .take(max_width.saturating_sub(1))
+                .collect();
+            display.push('…');
+        }
+        display
+    }
+
+    /// First character of a component, keeping the dot of hidden directories (`.config` → `.c`)
+    fn fish_abbreviate(component: &str) -> String {
+        let mut chars = component.chars();
+        match chars.next() {
+            Some('.') => chars.next().map_or(".".to_string(), |c| format!(".{}", c)),
+            Some(c) => c.to_string(),
+            None => String::new(),
+        }
+    }
 }
 
 impl Segment for DirectorySegment {
     fn collect(&self, input: &InputData) -> Option<SegmentData> {
         let current_dir = &input.workspace.current_dir;
 
-        // Handle cross-platform path separators manually for better compatibility
-        let dir_name = Self::extract_directory_name(current_dir);
-
         // Store the full path in metadata for potential use
         let mut metadata = HashMap::new();
         metadata.insert("full_path".to_string(), current_dir.clone());
 
+        let repo_root = if self.repo_relative {
+            Self::find_repo_root(Path::new(current_dir))
+        } else {
+            None
+        };
+        if let Some(ref root) = repo_root {
+            metadata.insert("repo_root".to_string(), root.to_string_lossy().into_owned());
+        }
+
+        // Handle cross-platform path separators manually for better compatibility
+        let dir_name = self.display_path(current_dir, repo_root.as_deref());
+
+        let secondary = match self.remote {
+            Some(ref remote) => {
+                let badge = remote.badge();
+                metadata.insert("remote".to_string(), badge.clone());
+                badge
+            }
+            None => String::new(),
+        };
+
         Some(SegmentData {
             primary: dir_name,
-            secondary: String::new(),
+            secondary,
             metadata,
         })
     }
@@ -0,0 +1,211 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Kind of Python environment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PythonEnvKind {
+    Venv,
+    Conda,
+}
+
+impl PythonEnvKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PythonEnvKind::Venv => "venv",
+            PythonEnvKind::Conda => "conda",
+        }
+    }
+}
+
+/// Active Python environment of the session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PythonEnv {
+    pub kind: PythonEnvKind,
+    pub name: String,
+}
+
+impl PythonEnv {
+    pub fn detect() -> Option<Self> {
+        Self::from_env(|name| std::env::var(name).ok())
+    }
+
+    /// Active virtualenv, else conda env; None when neither is active
+    ///
+    /// A virtualenv is named by its prompt (`VIRTUAL_ENV_PROMPT`) when set,
+    /// otherwise by its directory, as shell prompts do.
+    pub fn from_env(env: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        let env = |name: &str| env(name).filter(|value| !value.trim().is_empty());
+
+        if let Some(path) = env("VIRTUAL_ENV") {
+            let name = env("VIRTUAL_ENV_PROMPT")
+                .map(|prompt| {
+                    prompt
+                        .trim()
+                        .trim_start_matches('(')
+                        .trim_end_matches(')')
+                        .to_string()
+                })
+                .filter(|prompt| !prompt.is_empty())
+                .or_else(|| {
+                    Path::new(path.trim())
+                        .file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                })?;
+            return Some(Self {
+                kind: PythonEnvKind::Venv,
+                name,
+            });
+        }
+
+        env("CONDA_DEFAULT_ENV").map(|name| Self {
+            kind: PythonEnvKind::Conda,
+            name: name.trim().to_string(),
+        })
+    }
+}
+
+/// Node version the workspace asks for and where it was declared
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeVersion {
+    pub version: String,
+    /// `.nvmrc`, `.node-version` or `package.json`
+    pub source: &'static str,
+}
+
+impl NodeVersion {
+    /// Look in `dir` and its parents up to the repository root, like nvm
+    ///
+    /// In each directory `.nvmrc` wins over `.node-version`, which wins over
+    /// `engines.node` in `package.json`.
+    pub fn detect(dir: &Path) -> Option<Self> {
+        for dir in dir.ancestors() {
+            for source in [".nvmrc", ".node-version"] {
+                let version = fs::read_to_string(dir.join(source))
+                    .ok()
+                    .and_then(|content| content.lines().next().map(str::trim).map(String::from))
+                    .filter(|version| !version.is_empty());
+                if let Some(version) = version {
+                    return Some(Self {
+                        version: version.trim_start_matches('v').to_string(),
+                        source,
+                    });
+                }
+            }
+            let engine = fs::read_to_string(dir.join("package.json"))
+                .ok()
+                .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+                .and_then(|package| package["engines"]["node"].as_str().map(String::from))
+                .filter(|engine| !engine.trim().is_empty());
+            if let Some(engine) = engine {
+                return Some(Self {
+                    version: engine.trim().to_string(),
+                    source: "package.json",
+                });
+            }
+            if dir.join(".git").exists() {
+                break;
+            }
+        }
+        None
+    }
+}
+
+/// Python and Node environment of the workspace (`py proj · node 20`)
+///
+/// Python comes from the environment Claude Code was started in, Node from
+/// the workspace's version files. Hidden when neither applies.
+pub struct EnvSegment {
+    python: Option<PythonEnv>,
+    show_python: bool,
+    show_node: bool,
+    show_conda_base: bool,
+}
+
+impl Default for EnvSegment {
+    fn default() -> Self {
+        Self {
+            python: PythonEnv::detect(),
+            show_python: true,
+            show_node: true,
+            show_conda_base: false,
+        }
+    }
+}
+
+impl EnvSegment {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read `show_python`, `show_node` and `show_conda_base` from segment options
+    pub fn from_options(options: &HashMap<String, serde_json::Value>) -> Self {
+        let flag = |key: &str, default: bool| {
+            options
+                .get(key)
+                .and_then(|v| v.as_bool())
+                .unwrap_or(default)
+        };
+        let mut segment = Self::new();
+        segment.show_python = flag("show_python", true);
+        segment.show_node = flag("show_node", true);
+        segment.show_conda_base = flag("show_conda_base", false);
+        segment
+    }
+
+    /// Python environment to show instead of the detected one
+    pub fn with_python(mut self, python: Option<PythonEnv>) -> Self {
+        self.python = python;
+        self
+    }
+
+    /// Show conda's always-on `base` environment (hidden by default)
+    pub fn with_conda_base(mut self, show_conda_base: bool) -> Self {
+        self.show_conda_base = show_conda_base;
+        self
+    }
+}
+
+impl Segment for EnvSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let mut metadata = HashMap::new();
+        let mut parts = Vec::new();
+
+        let python = self.python.as_ref().filter(|python| {
+            self.show_python
+                && (self.show_conda_base
+                    || python.kind != PythonEnvKind::Conda
+                    || python.name != "base")
+        });
+        if let Some(python) = python {
+            metadata.insert("python_env".to_string(), python.name.clone());
+            metadata.insert("python_kind".to_string(), python.kind.as_str().to_string());
+            parts.push(format!("py {}", python.name));
+        }
+
+        let node = self
+            .show_node
+            .then(|| NodeVersion::detect(Path::new(&input.workspace.current_dir)))
+            .flatten();
+        if let Some(node) = node {
+            metadata.insert("node_version".to_string(), node.version.clone());
+            metadata.insert("node_source".to_string(), node.source.to_string());
+            parts.push(format!("node {}", node.version));
+        }
+
+        if parts.is_empty() {
+            return None;
+        }
+        Some(SegmentData {
+            primary: parts.join(" · "),
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Env
+    }
+}
@@ -1,5 +1,6 @@
 use super::{Segment, SegmentData};
 use crate::config::{InputData, SegmentId};
+use crate::core::icons::{Icon, IconSet};
 use std::collections::HashMap;
 use std::process::Command;
 
@@ -21,6 +22,7 @@ pub enum GitStatus {
 
 pub struct GitSegment {
     show_sha: bool,
+    icons: IconSet,
 }
 
 impl Default for GitSegment {
@@ -31,7 +33,10 @@ impl Default for GitSegment {
 
 impl GitSegment {
     pub fn new() -> Self {
-        Self { show_sha: false }
+        Self {
+            show_sha: false,
+            icons: IconSet::default(),
+        }
     }
 
     pub fn with_sha(mut self, show_sha: bool) -> Self {
@@ -39,6 +44,12 @@ impl GitSegment {
         self
     }
 
+    /// Glyphs for the clean/dirty/conflict and ahead/behind marks
+    pub fn with_icons(mut self, icons: IconSet) -> Self {
+        self.icons = icons;
+        self
+    }
+
     fn get_git_info(&self, working_dir: &str) -> Option<GitInfo> {
         if !self.is_git_repository(working_dir) {
             return None;
@@ -182,17 +193,26 @@ impl Segment for GitSegment {
         let primary = git_info.branch;
         let mut status_parts = Vec::new();
 
-        match git_info.status {
-            GitStatus::Clean => status_parts.push("✓".to_string()),
-            GitStatus::Dirty => status_parts.push("●".to_string()),
-            GitStatus::Conflicts => status_parts.push("⚠".to_string()),
-        }
+        let mark = match git_info.status {
+            GitStatus::Clean => Icon::Check,
+            GitStatus::Dirty => Icon::GitDirty,
+            GitStatus::Conflicts => Icon::Warning,
+        };
+        status_parts.push(self.icons.glyph(mark).to_string());
 
         if git_info.ahead > 0 {
-            status_parts.push(format!("↑{}", git_info.ahead));
+            status_parts.push(format!(
+                "{}{}",
+                self.icons.glyph(Icon::Ahead),
+                git_info.ahead
+            ));
         }
         if git_info.behind > 0 {
-            status_parts.push(format!("↓{}", git_info.behind));
+            status_parts.push(format!(
+                "{}{}",
+                self.icons.glyph(Icon::Behind),
+                git_info.behind
+            ));
         }
 
         if let Some(ref sha) = git_info.sha {
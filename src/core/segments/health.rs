@@ -0,0 +1,219 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use crate::core::icons::{Icon, IconSet};
+use crate::core::recovery;
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
+
+/// Budget for local segments (git, transcript parsing, ...)
+pub const DEFAULT_SEGMENT_TIMEOUT: Duration = Duration::from_millis(2000);
+
+/// Budget for the network segment, which may run a full probe
+pub const DEFAULT_NETWORK_SEGMENT_TIMEOUT: Duration = Duration::from_millis(10_000);
+
+/// Why a segment failed to produce output
+#[derive(Debug, Clone, PartialEq)]
+pub enum SegmentFailure {
+    Panic(String),
+    Timeout(Duration),
+    /// External command could not run or exited unsuccessfully
+    Command(String),
+}
+
+impl std::fmt::Display for SegmentFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SegmentFailure::Panic(message) => write!(f, "panicked: {}", message),
+            SegmentFailure::Timeout(budget) => {
+                write!(f, "timed out after {}ms", budget.as_millis())
+            }
+            SegmentFailure::Command(message) => write!(f, "command failed: {}", message),
+        }
+    }
+}
+
+/// Config/CLI name of a segment (`git`, `network`, ...)
+pub fn segment_name(id: SegmentId) -> String {
+    serde_json::to_value(id)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_else(|| format!("{:?}", id).to_lowercase())
+}
+
+/// Placeholder rendered in place of a failed segment, e.g. `⚠ git` (`! git` in ASCII)
+pub fn failure_placeholder(id: SegmentId, failure: &SegmentFailure, icons: IconSet) -> SegmentData {
+    let mut metadata = HashMap::new();
+    metadata.insert("error".to_string(), failure.to_string());
+
+    SegmentData {
+        primary: format!("{} {}", icons.glyph(Icon::Warning), segment_name(id)),
+        secondary: String::new(),
+        metadata,
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Collect a segment on its own thread so a panic or hang cannot take down the render
+///
+/// A timed-out worker is abandoned; it ends with the process once the statusline is printed.
+pub fn collect_isolated(
+    segment: Box<dyn Segment + Send>,
+    input: InputData,
+    timeout: Duration,
+) -> Result<Option<SegmentData>, SegmentFailure> {
+    let (tx, rx) = mpsc::channel();
+    let spawned = std::thread::Builder::new()
+        .name(format!("segment-{}", segment_name(segment.id())))
+        .spawn(move || {
+            let result = catch_unwind(AssertUnwindSafe(|| segment.collect(&input)));
+            let _ = tx.send(result.map_err(|payload| panic_message(payload.as_ref())));
+        });
+    if let Err(e) = spawned {
+        return Err(SegmentFailure::Panic(format!(
+            "failed to spawn worker: {}",
+            e
+        )));
+    }
+
+    match rx.recv_timeout(timeout) {
+        Ok(Ok(data)) => Ok(data),
+        Ok(Err(message)) => Err(SegmentFailure::Panic(message)),
+        Err(RecvTimeoutError::Timeout) => Err(SegmentFailure::Timeout(timeout)),
+        Err(RecvTimeoutError::Disconnected) => Err(SegmentFailure::Panic(
+            "worker exited without a result".to_string(),
+        )),
+    }
+}
+
+/// Async counterpart of [`collect_isolated`] for segments that run on the runtime
+#[cfg(feature = "network-monitoring")]
+pub async fn collect_isolated_async<F>(
+    future: F,
+    timeout: Duration,
+) -> Result<Option<SegmentData>, SegmentFailure>
+where
+    F: std::future::Future<Output = Option<SegmentData>>,
+{
+    use futures::FutureExt;
+
+    match crate::runtime::timeout(timeout, AssertUnwindSafe(future).catch_unwind()).await {
+        Ok(Ok(data)) => Ok(data),
+        Ok(Err(payload)) => Err(SegmentFailure::Panic(panic_message(payload.as_ref()))),
+        Err(_) => Err(SegmentFailure::Timeout(timeout)),
+    }
+}
+
+/// Last known health of one segment
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SegmentHealth {
+    /// Whether the most recent collection succeeded
+    pub healthy: bool,
+    /// Total failures observed since the map was created
+    pub failures: u64,
+    pub last_error: Option<String>,
+    pub last_failure_at: Option<String>,
+    pub last_recovered_at: Option<String>,
+}
+
+/// Per-segment health, persisted to `~/.claude/ccstatus/segment-health.json`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SegmentHealthMap {
+    pub segments: BTreeMap<String, SegmentHealth>,
+}
+
+impl SegmentHealthMap {
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| {
+            home.join(".claude")
+                .join("ccstatus")
+                .join("segment-health.json")
+        })
+    }
+
+    /// Load the map; a missing file is empty, a corrupt one is quarantined (see `recovery`)
+    pub fn load(path: &Path) -> Self {
+        recovery::load_json(path).unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, content)?;
+        fs::rename(&tmp, path)
+    }
+
+    /// Record one collection outcome; returns true when the map changed
+    ///
+    /// Healthy segments that were already healthy are not written, so the
+    /// common render path never touches disk.
+    pub fn record(&mut self, name: &str, outcome: Result<(), &SegmentFailure>) -> bool {
+        let now = Local::now().to_rfc3339();
+        match outcome {
+            Ok(()) => match self.segments.get_mut(name) {
+                Some(health) if !health.healthy => {
+                    health.healthy = true;
+                    health.last_recovered_at = Some(now);
+                    true
+                }
+                _ => false,
+            },
+            Err(failure) => {
+                let health = self.segments.entry(name.to_string()).or_default();
+                health.healthy = false;
+                health.failures += 1;
+                health.last_error = Some(failure.to_string());
+                health.last_failure_at = Some(now);
+                true
+            }
+        }
+    }
+
+    /// Human-readable report for `ccstatus doctor`
+    pub fn render_report(&self) -> String {
+        if self.segments.is_empty() {
+            return "No segment failures recorded".to_string();
+        }
+
+        self.segments
+            .iter()
+            .map(|(name, health)| {
+                let status = if health.healthy { "ok" } else { "failing" };
+                let mut line = format!(
+                    "{:<10} {} ({} failure{})",
+                    name,
+                    status,
+                    health.failures,
+                    if health.failures == 1 { "" } else { "s" }
+                );
+                if let (Some(error), Some(at)) = (&health.last_error, &health.last_failure_at) {
+                    line.push_str(&format!("\n           last error at {}: {}", at, error));
+                }
+                if let Some(at) = &health.last_recovered_at {
+                    line.push_str(&format!("\n           recovered at {}", at));
+                }
+                line
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
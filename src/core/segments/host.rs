@@ -0,0 +1,145 @@
+use super::directory::RemoteContext;
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use std::collections::HashMap;
+
+/// How this session is remote
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostKind {
+    Ssh,
+    Container,
+}
+
+impl HostKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HostKind::Ssh => "ssh",
+            HostKind::Container => "container",
+        }
+    }
+}
+
+/// User and machine of a remote session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostContext {
+    pub kind: HostKind,
+    pub user: Option<String>,
+    pub host: String,
+}
+
+impl HostContext {
+    /// Detect from the process environment and the container marker files
+    pub fn detect() -> Option<Self> {
+        Self::from_sources(
+            |name| std::env::var(name).ok(),
+            |path| std::fs::read_to_string(path).ok(),
+        )
+    }
+
+    /// Detect from environment and file lookups; None for a local session
+    ///
+    /// SSH and dev containers are recognized as for the directory badge
+    /// ([`RemoteContext`]); other containers by `/.dockerenv`,
+    /// `/run/.containerenv` or the `container` variable podman and
+    /// systemd-nspawn set. A container reached over SSH counts as a container.
+    pub fn from_sources(
+        env: impl Fn(&str) -> Option<String>,
+        file: impl Fn(&str) -> Option<String>,
+    ) -> Option<Self> {
+        let env = |name: &str| env(name).filter(|value| !value.trim().is_empty());
+        let file = |path: &str| file(path).map(|content| content.trim().to_string());
+
+        let remote = RemoteContext::from_env(env);
+        let in_container = matches!(remote, Some(RemoteContext::Container { .. }))
+            || env("container").is_some()
+            || file("/.dockerenv").is_some()
+            || file("/run/.containerenv").is_some();
+        let kind = match remote {
+            _ if in_container => HostKind::Container,
+            Some(RemoteContext::Ssh { .. }) => HostKind::Ssh,
+            _ => return None,
+        };
+
+        // HOSTNAME is rarely exported, so fall back to the kernel's name
+        let host = env("HOSTNAME")
+            .or_else(|| file("/etc/hostname").filter(|name| !name.is_empty()))
+            .or_else(|| file("/proc/sys/kernel/hostname").filter(|name| !name.is_empty()))
+            .or(remote.map(|remote| match remote {
+                RemoteContext::Ssh { host } => host,
+                RemoteContext::Container { name } => name,
+            }))
+            .unwrap_or_else(|| kind.as_str().to_string());
+        let user = env("USER").or_else(|| env("LOGNAME"));
+
+        Some(Self { kind, user, host })
+    }
+}
+
+/// `user@host` for SSH and container sessions; hidden when running locally
+pub struct HostSegment {
+    context: Option<HostContext>,
+    show_user: bool,
+}
+
+impl Default for HostSegment {
+    fn default() -> Self {
+        Self {
+            context: HostContext::detect(),
+            show_user: true,
+        }
+    }
+}
+
+impl HostSegment {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read `show_user` from segment options
+    pub fn from_options(options: &HashMap<String, serde_json::Value>) -> Self {
+        let show_user = options
+            .get("show_user")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+        Self::new().with_show_user(show_user)
+    }
+
+    /// Session to describe instead of the detected one
+    pub fn with_context(mut self, context: Option<HostContext>) -> Self {
+        self.context = context;
+        self
+    }
+
+    /// Include the user name (`dev@buildbox` rather than `buildbox`)
+    pub fn with_show_user(mut self, show_user: bool) -> Self {
+        self.show_user = show_user;
+        self
+    }
+}
+
+impl Segment for HostSegment {
+    fn collect(&self, _input: &InputData) -> Option<SegmentData> {
+        let context = self.context.as_ref()?;
+
+        let mut metadata = HashMap::new();
+        metadata.insert("kind".to_string(), context.kind.as_str().to_string());
+        metadata.insert("host".to_string(), context.host.clone());
+        let primary = match context.user.as_deref() {
+            Some(user) if self.show_user => {
+                metadata.insert("user".to_string(), user.to_string());
+                format!("{}@{}", user, context.host)
+            }
+            _ => context.host.clone(),
+        };
+
+        Some(SegmentData {
+            primary,
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Host
+    }
+}
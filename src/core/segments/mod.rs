@@ -1,8 +1,22 @@
+pub mod block;
+pub mod burn_rate;
+pub mod compact;
+pub mod cost;
+pub mod custom;
+pub mod diff_stat;
 pub mod directory;
+pub mod env;
 pub mod git;
+pub mod health;
+pub mod host;
+pub mod mode;
 pub mod model;
+pub mod project;
+pub mod quota;
+pub mod stopwatch;
 pub mod update;
 pub mod usage;
+pub mod version;
 
 #[cfg(feature = "network-monitoring")]
 pub mod network;
@@ -24,11 +38,25 @@ pub struct SegmentData {
 }
 
 // Re-export all segment types
-pub use directory::DirectorySegment;
+pub use block::BlockSegment;
+pub use burn_rate::BurnRateSegment;
+pub use compact::{CompactEstimate, CompactSegment};
+pub use cost::CostSegment;
+pub use custom::PendingCustomSegment;
+pub use diff_stat::DiffStatSegment;
+pub use directory::{DirectorySegment, PathStyle, RemoteContext};
+pub use env::EnvSegment;
 pub use git::GitSegment;
-pub use model::ModelSegment;
+pub use health::{SegmentFailure, SegmentHealth, SegmentHealthMap};
+pub use host::HostSegment;
+pub use mode::ModeSegment;
+pub use model::{ModelDisplay, ModelHint, ModelName, ModelSegment};
+pub use project::ProjectSegment;
+pub use quota::QuotaSegment;
+pub use stopwatch::StopwatchSegment;
 pub use update::UpdateSegment;
 pub use usage::UsageSegment;
+pub use version::VersionSegment;
 
 #[cfg(feature = "network-monitoring")]
 pub use network::NetworkSegmentWrapper;
@@ -0,0 +1,83 @@
+use super::{Segment, SegmentData};
+use crate::config::{AnsiColor, InputData, SegmentConfig, SegmentId};
+use std::collections::HashMap;
+
+/// Permission mode and output style (`auto-accept · Explanatory`)
+///
+/// Hidden while Claude Code runs in the default permission mode with the
+/// default output style, so the badge only appears when behaviour differs
+/// from usual. [`mode_colors`] highlights plan, auto-accept and bypass mode.
+#[derive(Default)]
+pub struct ModeSegment;
+
+impl ModeSegment {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Badge text for a permission mode; None for the default mode
+pub fn permission_label(mode: &str) -> Option<&str> {
+    match mode.trim() {
+        "" | "default" => None,
+        "plan" => Some("plan"),
+        "acceptEdits" => Some("auto-accept"),
+        "bypassPermissions" => Some("bypass"),
+        other => Some(other),
+    }
+}
+
+impl Segment for ModeSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let mut metadata = HashMap::new();
+        let mut parts = Vec::new();
+
+        if let Some(mode) = input.permission_mode.as_deref() {
+            metadata.insert("permission_mode".to_string(), mode.to_string());
+            parts.extend(permission_label(mode).map(str::to_string));
+        }
+        if let Some(style) = input.output_style.as_ref() {
+            let name = style.name.trim();
+            if !name.is_empty() && !name.eq_ignore_ascii_case("default") {
+                metadata.insert("output_style".to_string(), name.to_string());
+                parts.push(name.to_string());
+            }
+        }
+        if parts.is_empty() {
+            return None;
+        }
+
+        Some(SegmentData {
+            primary: parts.join(" · "),
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Mode
+    }
+}
+
+/// Recolor the badge by permission mode
+///
+/// `options.plan_color`, `options.accept_edits_color` and `options.bypass_color`
+/// override the defaults: bright cyan, bright yellow and bright red. Other
+/// modes keep the theme colors.
+pub fn mode_colors(config: &SegmentConfig, data: &SegmentData) -> SegmentConfig {
+    let (key, default) = match data.metadata.get("permission_mode").map(String::as_str) {
+        Some("plan") => ("plan_color", 14),
+        Some("acceptEdits") => ("accept_edits_color", 11),
+        Some("bypassPermissions") => ("bypass_color", 9),
+        _ => return config.clone(),
+    };
+    let color = config
+        .options
+        .get(key)
+        .and_then(|v| serde_json::from_value::<AnsiColor>(v.clone()).ok())
+        .unwrap_or(AnsiColor::Color16 { c16: default });
+    let mut config = config.clone();
+    config.colors.icon = Some(color.clone());
+    config.colors.text = Some(color);
+    config
+}
@@ -1,22 +1,247 @@
+use super::cost::PriceTable;
 use super::{Segment, SegmentData};
 use crate::config::{InputData, SegmentId};
+use serde::Deserialize;
 use std::collections::HashMap;
 
+/// Context window of models without a larger one advertised
+const DEFAULT_CONTEXT_TOKENS: u64 = 200_000;
+
+/// Extended context window (`[1m]` model ids, "1M context" display names)
+const EXTENDED_CONTEXT_TOKENS: u64 = 1_000_000;
+
+/// Capability hint shown after the model name (`options.annotate`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelHint {
+    /// Context window size (`200k`, `1M`)
+    Context,
+    /// List price per million input/output tokens (`$3/$15`)
+    Pricing,
+}
+
+/// How normalized model names are shown (`options.display`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelDisplay {
+    /// Family initial and version (`S4.5`)
+    Short,
+    /// Family and version (`Sonnet 4.5`)
+    #[default]
+    Standard,
+    /// Vendor, family and version (`Claude Sonnet 4.5`)
+    Long,
+}
+
+/// Model families names are normalized to
+const FAMILIES: [&str; 3] = ["Opus", "Sonnet", "Haiku"];
+
+/// Family and version parsed from a model id or display name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelName {
+    pub family: &'static str,
+    /// Dotted version (`4.5`), empty when the name has none
+    pub version: String,
+}
+
+impl ModelName {
+    /// Parse ids in either order (`claude-sonnet-4-5-20250929`,
+    /// `claude-3-5-sonnet-20241022`) and display names (`Sonnet 4.5`,
+    /// `Claude 3.5 Sonnet`); None without a known family
+    ///
+    /// Version numbers stop at the release date, so provider suffixes such as
+    /// `-v2:0` or `@20250514` are ignored.
+    pub fn parse(name: &str) -> Option<Self> {
+        let lower = name.to_lowercase();
+        let mut family = None;
+        let mut version = Vec::new();
+        for token in lower
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|token| !token.is_empty())
+        {
+            if token.bytes().all(|b| b.is_ascii_digit()) {
+                if token.len() > 2 {
+                    break;
+                }
+                version.push(token);
+            } else if let Some(known) = FAMILIES
+                .iter()
+                .find(|known| known.eq_ignore_ascii_case(token))
+            {
+                family = Some(*known);
+            }
+        }
+        Some(Self {
+            family: family?,
+            version: version.join("."),
+        })
+    }
+
+    pub fn render(&self, display: ModelDisplay) -> String {
+        let name = match display {
+            ModelDisplay::Short => return format!("{}{}", &self.family[..1], self.version),
+            ModelDisplay::Standard => self.family.to_string(),
+            ModelDisplay::Long => format!("Claude {}", self.family),
+        };
+        if self.version.is_empty() {
+            name
+        } else {
+            format!("{} {}", name, self.version)
+        }
+    }
+}
+
 #[derive(Default)]
-pub struct ModelSegment;
+pub struct ModelSegment {
+    /// Model id / display name fragment → alias, lowercased keys
+    aliases: Vec<(String, String)>,
+    display: ModelDisplay,
+    hint: Option<ModelHint>,
+    prices: PriceTable,
+}
 
 impl ModelSegment {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Read `aliases`, `display`, `annotate` and `prices` from segment options
+    pub fn from_options(options: &HashMap<String, serde_json::Value>) -> Self {
+        let aliases = options
+            .get("aliases")
+            .and_then(|v| v.as_object())
+            .map(|aliases| {
+                aliases
+                    .iter()
+                    .filter_map(|(key, alias)| Some((key.clone(), alias.as_str()?.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self::new()
+            .with_aliases(aliases)
+            .with_display(
+                options
+                    .get("display")
+                    .and_then(|v| serde_json::from_value(v.clone()).ok())
+                    .unwrap_or_default(),
+            )
+            .with_hint(
+                options
+                    .get("annotate")
+                    .and_then(|v| serde_json::from_value(v.clone()).ok()),
+            )
+            .with_prices(PriceTable::from_options(options))
+    }
+
+    /// Short names replacing the built-in ones, e.g. `claude-sonnet-4` → `S4`
+    ///
+    /// Keys match anywhere in the model id or display name, ignoring case; the
+    /// longest matching key wins.
+    pub fn with_aliases(mut self, aliases: Vec<(String, String)>) -> Self {
+        self.aliases = aliases
+            .into_iter()
+            .map(|(key, alias)| (key.to_lowercase(), alias))
+            .collect();
+        self
+    }
+
+    /// Style of normalized names; aliases are always shown as written
+    pub fn with_display(mut self, display: ModelDisplay) -> Self {
+        self.display = display;
+        self
+    }
+
+    pub fn with_hint(mut self, hint: Option<ModelHint>) -> Self {
+        self.hint = hint;
+        self
+    }
+
+    /// Prices for the `pricing` hint (same table as the cost segment)
+    pub fn with_prices(mut self, prices: PriceTable) -> Self {
+        self.prices = prices;
+        self
+    }
+
+    /// Alias for the model, matched against its id and display name
+    pub fn alias(&self, id: &str, display_name: &str) -> Option<&str> {
+        let id = id.to_lowercase();
+        let display_name = display_name.to_lowercase();
+        self.aliases
+            .iter()
+            .filter(|(key, _)| id.contains(key.as_str()) || display_name.contains(key.as_str()))
+            .max_by_key(|(key, _)| key.len())
+            .map(|(_, alias)| alias.as_str())
+    }
+
+    /// Context window in tokens
+    pub fn context_tokens(id: &str, display_name: &str) -> u64 {
+        if id.to_lowercase().ends_with("[1m]") || display_name.contains("1M") {
+            EXTENDED_CONTEXT_TOKENS
+        } else {
+            DEFAULT_CONTEXT_TOKENS
+        }
+    }
+
+    fn format_tokens(tokens: u64) -> String {
+        if tokens >= 1_000_000 && tokens.is_multiple_of(1_000_000) {
+            format!("{}M", tokens / 1_000_000)
+        } else {
+            format!("{}k", tokens / 1000)
+        }
+    }
+
+    fn format_price(usd: f64) -> String {
+        if usd.fract() == 0.0 {
+            format!("${:.0}", usd)
+        } else {
+            let formatted = format!("{:.2}", usd);
+            format!("${}", formatted.trim_end_matches('0'))
+        }
     }
 }
 
 impl Segment for ModelSegment {
     fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let model = &input.model;
+        let primary = match self.alias(&model.id, &model.display_name) {
+            Some(alias) => alias.to_string(),
+            None => self.format_model_name(&model.id, &model.display_name),
+        };
+
+        let mut metadata = HashMap::new();
+        let context = Self::context_tokens(&model.id, &model.display_name);
+        metadata.insert("context_tokens".to_string(), context.to_string());
+        // Ids carry the exact version; display names are the fallback
+        let price_key = if model.id.is_empty() {
+            &model.display_name
+        } else {
+            &model.id
+        };
+        let price = self.prices.lookup(price_key);
+        if let Some(price) = price {
+            metadata.insert("input_price".to_string(), price.input.to_string());
+            metadata.insert("output_price".to_string(), price.output.to_string());
+        }
+
+        let secondary = match self.hint {
+            Some(ModelHint::Context) => Self::format_tokens(context),
+            Some(ModelHint::Pricing) => price
+                .map(|price| {
+                    format!(
+                        "{}/{}",
+                        Self::format_price(price.input),
+                        Self::format_price(price.output)
+                    )
+                })
+                .unwrap_or_default(),
+            None => String::new(),
+        };
+
         Some(SegmentData {
-            primary: self.format_model_name(&input.model.display_name),
-            secondary: String::new(),
-            metadata: HashMap::new(),
+            primary,
+            secondary,
+            metadata,
         })
     }
 
@@ -26,17 +251,11 @@ impl Segment for ModelSegment {
 }
 
 impl ModelSegment {
-    fn format_model_name(&self, display_name: &str) -> String {
-        // Simplify model display names
-        match display_name {
-            name if name.contains("claude-3-5-sonnet") => "Sonnet 3.5".to_string(),
-            name if name.contains("claude-3-7-sonnet") => "Sonnet 3.7".to_string(),
-            name if name.contains("claude-3-sonnet") => "Sonnet 3".to_string(),
-            name if name.contains("claude-3-haiku") => "Haiku 3".to_string(),
-            name if name.contains("claude-4-sonnet") => "Sonnet 4".to_string(),
-            name if name.contains("claude-4-opus") => "Opus 4".to_string(),
-            name if name.contains("sonnet-4") => "Sonnet 4".to_string(),
-            _ => display_name.to_string(),
-        }
+    /// Normalized name from the id, else the display name, else the display name as sent
+    fn format_model_name(&self, id: &str, display_name: &str) -> String {
+        ModelName::parse(id)
+            .or_else(|| ModelName::parse(display_name))
+            .map(|name| name.render(self.display))
+            .unwrap_or_else(|| display_name.to_string())
     }
 }
@@ -5,12 +5,55 @@
 //! while maintaining backward compatibility and feature flag isolation.
 
 use super::{Segment, SegmentData};
-use crate::config::{InputData, SegmentId};
+use crate::config::{
+    AlertConfig, Flag, FlagSet, HostPolicy, InputData, NetworkConfig, NetworkTarget, ProxyPolicy,
+    SegmentId, SlaTarget, TlsPolicy,
+};
+use crate::core::attention::AttentionStyle;
+use crate::core::format::NumberFormat;
+use crate::core::i18n::{Locale, Text};
+use crate::core::icons::{Icon, IconSet};
 #[cfg(feature = "network-monitoring")]
-use crate::core::network::types::NetworkError;
+use crate::core::network::alerts::AlertDispatcher;
 #[cfg(feature = "network-monitoring")]
-use crate::core::network::{NetworkSegment, StatuslineInput};
+use crate::core::network::certificate::DEFAULT_WARN_DAYS;
+#[cfg(feature = "network-monitoring")]
+use crate::core::network::cookie_jar::PersistentCookieJar;
+#[cfg(feature = "network-monitoring")]
+use crate::core::network::daemon::{DaemonLease, DaemonSchedule, TICK_SECS};
+use crate::core::network::dns_cache::DnsCache;
+#[cfg(feature = "network-monitoring")]
+use crate::core::network::error_budget::ErrorBudgetPolicy;
+#[cfg(feature = "network-monitoring")]
+use crate::core::network::http_monitor::TransportPolicy;
+#[cfg(feature = "network-monitoring")]
+use crate::core::network::probe_payload::ProbeRequest;
+#[cfg(feature = "network-monitoring")]
+use crate::core::network::probe_profile::ProbeProfile;
+#[cfg(feature = "network-monitoring")]
+use crate::core::network::sla::{SlaLedger, SlaTracker};
+#[cfg(feature = "network-monitoring")]
+use crate::core::network::status_expression::StatusExpression;
+use crate::core::network::status_hooks::PauseAdvisory;
+#[cfg(feature = "network-monitoring")]
+use crate::core::network::types::{NetworkError, ProbeMode, UptimeWindow, WindowPolicy};
+#[cfg(feature = "network-monitoring")]
+use crate::core::network::{HttpMonitor, NetworkSegment, ProbeCheck, StatuslineInput};
+#[cfg(feature = "network-monitoring")]
+use crate::core::shutdown::{FileLock, ShutdownCoordinator};
+#[cfg(feature = "network-monitoring")]
+use crate::core::state_location::StateLocation;
+#[cfg(feature = "network-monitoring")]
+use crate::runtime;
 use std::collections::HashMap;
+#[cfg(feature = "network-monitoring")]
+use std::path::PathBuf;
+#[cfg(feature = "network-monitoring")]
+use std::time::{Duration, Instant};
+
+/// Time kept back from housekeeping for the statusline to print the rendered status
+#[cfg(feature = "network-monitoring")]
+const HOUSEKEEPING_MARGIN: Duration = Duration::from_millis(500);
 
 /// NetworkSegmentWrapper provides integration between NetworkSegment and the segment system
 ///
@@ -19,20 +62,226 @@ use std::collections::HashMap;
 /// the complete NetworkSegment workflow and returns the rendered status as SegmentData.
 #[cfg(feature = "network-monitoring")]
 pub struct NetworkSegmentWrapper {
-    // For now, keep it simple and just instantiate components as needed
-    _placeholder: (),
+    /// `[network]` settings from config.toml (probe consent, ...)
+    network_config: NetworkConfig,
+    /// `[proxy]`, `[network.socks5]` and the proxy environment
+    proxy: ProxyPolicy,
+    /// Emit OSC 8 links on error hints
+    hyperlinks: bool,
+    /// Subsystem kill switches (manifest `flags` + `[flags]`)
+    flags: FlagSet,
+    /// Glyphs for status lights and marks
+    icons: IconSet,
+    /// Language of status words
+    locale: Locale,
+    /// Latency units and separators (`[formatting]`)
+    number_format: NumberFormat,
+    /// Emphasis on the error state (`attention`)
+    attention: AttentionStyle,
+    /// Alert routes (`alerts`)
+    alerts: Vec<AlertConfig>,
+    /// Availability badge window (segment option `uptime`), hidden when unset
+    uptime_badge: Option<UptimeWindow>,
+    /// Time the statusline allows the whole segment (segment option `timeout_ms`)
+    budget: Duration,
 }
 
 #[cfg(feature = "network-monitoring")]
 impl NetworkSegmentWrapper {
     /// Create new NetworkSegmentWrapper
     pub fn new() -> Result<Self, NetworkError> {
-        Ok(Self { _placeholder: () })
+        Ok(Self {
+            network_config: NetworkConfig::default(),
+            proxy: ProxyPolicy::default(),
+            hyperlinks: false,
+            flags: FlagSet::default(),
+            icons: IconSet::default(),
+            locale: Locale::default(),
+            number_format: NumberFormat::default(),
+            attention: AttentionStyle::None,
+            alerts: Vec::new(),
+            uptime_badge: None,
+            budget: super::health::DEFAULT_NETWORK_SEGMENT_TIMEOUT,
+        })
     }
 
     /// Create NetworkSegmentWrapper with custom state path (for testing)
     pub fn with_state_path(_state_path: std::path::PathBuf) -> Result<Self, NetworkError> {
-        Ok(Self { _placeholder: () })
+        Self::new()
+    }
+
+    /// Apply `[network]` settings from the loaded config
+    pub fn with_config(mut self, network_config: &NetworkConfig) -> Self {
+        self.network_config = network_config.clone();
+        self
+    }
+
+    /// Send probes and proxy health checks through `proxy` (see [`ProxyPolicy::from_config`])
+    pub fn with_proxy(mut self, proxy: ProxyPolicy) -> Self {
+        self.proxy = proxy;
+        self
+    }
+
+    /// Apply subsystem kill switches to every probed target
+    pub fn with_flags(mut self, flags: FlagSet) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Make error hints clickable when the terminal supports OSC 8
+    pub fn with_hyperlinks(mut self, enabled: bool) -> Self {
+        self.hyperlinks = enabled;
+        self
+    }
+
+    /// Glyph set for status lights and marks (`style.icons`)
+    pub fn with_icons(mut self, icons: IconSet) -> Self {
+        self.icons = icons;
+        self
+    }
+
+    /// Language of status words (`locale`)
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Latency units and separators (`[formatting]`)
+    pub fn with_number_format(mut self, format: NumberFormat) -> Self {
+        self.number_format = format;
+        self
+    }
+
+    /// Emphasis on the error state (`attention`)
+    pub fn with_attention(mut self, attention: AttentionStyle) -> Self {
+        self.attention = attention;
+        self
+    }
+
+    /// Alert routes notified of outages, recoveries and updates (`alerts`)
+    pub fn with_alerts(mut self, alerts: &[AlertConfig]) -> Self {
+        self.alerts = alerts.to_vec();
+        self
+    }
+
+    /// Append rolling availability over `window` to the status, e.g. `| 99.2%`
+    pub fn with_uptime_badge(mut self, window: Option<UptimeWindow>) -> Self {
+        self.uptime_badge = window;
+        self
+    }
+
+    /// Time the segment may take in total; housekeeping only gets what the probe leaves
+    pub fn with_budget(mut self, budget: Duration) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Probe the primary endpoint once now, for `ccstatus net check`
+    ///
+    /// Configured like a statusline probe, but without status hooks: a CI check
+    /// neither sends alerts, writes the pause advisory nor counts toward the SLA.
+    pub async fn check(&self) -> Result<ProbeCheck, NetworkError> {
+        self.configure(
+            NetworkSegment::with_state_path(self.state_location()?.monitoring_path())?,
+            PersistentCookieJar::default_path().ok(),
+        )
+        .check_once()
+        .await
+    }
+
+    /// Probe on a timer until `shutdown`, for `ccstatus daemon`
+    ///
+    /// Probes the primary endpoint, or each named target, on the schedule of
+    /// `profile` (see `daemon`); `report` is told the outcome of every probe.
+    /// Refuses to start without probe consent or while another daemon runs.
+    pub async fn run_daemon<F>(
+        &self,
+        profile: ProbeProfile,
+        shutdown: &ShutdownCoordinator,
+        report: F,
+    ) -> Result<(), NetworkError>
+    where
+        F: Fn(&str, ProbeMode, &ProbeCheck),
+    {
+        use crate::core::network::debug_logger::get_debug_logger;
+        use crate::core::network::types::NetworkStatus;
+
+        if !self.network_config.probe_consent {
+            return Err(NetworkError::ValidationError(
+                "probing is off; run `ccstatus net enable` first".to_string(),
+            ));
+        }
+        let lease_path = DaemonLease::path_in(self.state_location()?.state_dir());
+        if let Some(parent) = lease_path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| NetworkError::StateFileError(e.to_string()))?;
+        }
+        let _lock = FileLock::try_acquire(shutdown, DaemonLease::lock_path(&lease_path))
+            .map_err(|e| NetworkError::StateFileError(e.to_string()))?
+            .ok_or_else(|| {
+                NetworkError::ValidationError("another ccstatus daemon is running".to_string())
+            })?;
+
+        let mut endpoints: Vec<(String, NetworkSegment)> = Vec::new();
+        if self.network_config.targets.is_empty() {
+            let segment = self.primary_segment().await?.with_probe_profile(profile);
+            endpoints.push((SlaTarget::PRIMARY.to_string(), segment));
+        }
+        for target in &self.network_config.targets {
+            if target.has_valid_name() {
+                let segment = self.target_segment(target)?.with_probe_profile(profile);
+                endpoints.push((target.name.clone(), segment));
+            }
+        }
+
+        let debug_logger = get_debug_logger();
+        let schedule = DaemonSchedule::new(profile);
+        let mut lease = DaemonLease::new(profile, chrono::Utc::now().timestamp());
+        let mut last: Vec<Option<(i64, NetworkStatus)>> = vec![None; endpoints.len()];
+
+        while !shutdown.is_requested() {
+            let now = chrono::Utc::now().timestamp();
+            lease.heartbeat_at = now;
+            if let Err(e) = lease.save(&lease_path) {
+                debug_logger
+                    .warn("Daemon", &format!("Failed to write lease: {}", e))
+                    .await;
+            }
+
+            for ((name, segment), last) in endpoints.iter_mut().zip(last.iter_mut()) {
+                let due = schedule.due(last.as_ref().map(|(at, status)| (*at, status)), now);
+                let Some(mode) = due else {
+                    continue;
+                };
+                // Hold off shutdown until the probe's state write is done
+                let _write = shutdown.begin_write();
+                let check = match segment.probe_scheduled(mode).await {
+                    Ok(check) => check,
+                    Err(e) => {
+                        debug_logger
+                            .warn("Daemon", &format!("{} probe failed: {}", name, e))
+                            .await;
+                        ProbeCheck {
+                            status: NetworkStatus::Unknown,
+                            credential_source: None,
+                            latency_ms: None,
+                            skip_reason: Some(e.to_string()),
+                        }
+                    }
+                };
+                report(name, mode, &check);
+                *last = Some((now, check.status));
+            }
+
+            let _ = runtime::timeout(
+                std::time::Duration::from_secs(TICK_SECS),
+                shutdown.requested(),
+            )
+            .await;
+        }
+
+        let _ = std::fs::remove_file(&lease_path);
+        Ok(())
     }
 
     /// Collect network monitoring data with full StatuslineInput
@@ -46,20 +295,24 @@ impl NetworkSegmentWrapper {
         // Execute orchestration workflow
         match self.run_orchestration(input).await {
             Ok(status_text) => Some(SegmentData {
-                primary: status_text,
+                primary: self.flag_insecure_tls(status_text),
                 secondary: String::new(),
-                metadata: HashMap::new(),
+                metadata: self.budget_metadata().await,
             }),
             Err(_) => {
                 // On orchestration error, fall back to existing state or unknown
                 match self.get_network_status().await {
                     Ok(status_text) => Some(SegmentData {
-                        primary: status_text,
+                        primary: self.flag_insecure_tls(status_text),
                         secondary: String::new(),
-                        metadata: HashMap::new(),
+                        metadata: self.budget_metadata().await,
                     }),
                     Err(_) => Some(SegmentData {
-                        primary: "⚪ Unknown".to_string(),
+                        primary: format!(
+                            "{} {}",
+                            self.icons.glyph(Icon::Unknown),
+                            self.locale.text(Text::Unknown)
+                        ),
                         secondary: String::new(),
                         metadata: HashMap::new(),
                     }),
@@ -68,6 +321,46 @@ impl NetworkSegmentWrapper {
         }
     }
 
+    /// Mark the status while `[network.tls] insecure_skip_verify` is on, so
+    /// unverified probes never look like verified ones
+    fn flag_insecure_tls(&self, status_text: String) -> String {
+        let insecure = self
+            .network_config
+            .tls
+            .as_ref()
+            .is_some_and(|tls| tls.insecure_skip_verify);
+        crate::core::network::StatusRenderer::new()
+            .with_icons(self.icons)
+            .with_locale(self.locale)
+            .render_with_insecure_tls(status_text, insecure)
+    }
+
+    /// `level` metadata recoloring the segment while the error budget burns too fast
+    ///
+    /// Follows the primary endpoint only, like the rendered budget badge.
+    async fn budget_metadata(&self) -> HashMap<String, String> {
+        let mut metadata = HashMap::new();
+        if self.network_config.error_budget.is_none() || !self.network_config.targets.is_empty() {
+            return metadata;
+        }
+        let Ok(monitor) = self
+            .state_location()
+            .and_then(|location| HttpMonitor::new(Some(location.monitoring_path())))
+        else {
+            return metadata;
+        };
+        let burning = monitor
+            .load_state()
+            .await
+            .ok()
+            .and_then(|state| state.error_budget)
+            .is_some_and(|budget| budget.burning);
+        if burning {
+            metadata.insert("level".to_string(), "warning".to_string());
+        }
+        metadata
+    }
+
     /// Execute NetworkSegment orchestration workflow and return rendered status
     ///
     /// This is the core integration method that bridges the gap between the wrapper
@@ -76,13 +369,39 @@ impl NetworkSegmentWrapper {
     async fn run_orchestration(&self, input: &StatuslineInput) -> Result<String, NetworkError> {
         use crate::core::network::debug_logger::get_debug_logger;
 
+        let started = Instant::now();
         let debug_logger = get_debug_logger();
         debug_logger
             .debug("NetworkWrapper", "Starting orchestration integration")
             .await;
+        if debug_logger.is_enabled() {
+            self.warn_network_filesystem();
+        }
 
-        // Create NetworkSegment instance
-        let mut segment = NetworkSegment::new()?;
+        let mut segment = self.primary_segment().await?;
+
+        // Without consent, stay transcript-only and never touch the network
+        if !segment.probe_consent() {
+            debug_logger
+                .debug("NetworkWrapper", "Probe consent not granted - passive mode")
+                .await;
+            let status_text = match segment.run_passive(input).await {
+                Ok(status_text) => status_text,
+                Err(_) => crate::core::network::StatusRenderer::new()
+                    .with_hyperlinks(self.hyperlinks)
+                    .with_icons(self.icons)
+                    .with_locale(self.locale)
+                    .with_number_format(self.number_format.clone())
+                    .with_attention(self.attention)
+                    .render_probes_off(None),
+            };
+            return Ok(status_text);
+        }
+
+        // Named targets replace the single default target with a compact strip
+        if !self.network_config.targets.is_empty() {
+            return self.run_targets(input, started).await;
+        }
 
         // Execute orchestration workflow with the provided input
         if let Err(e) = segment.run(input.clone()).await {
@@ -91,13 +410,450 @@ impl NetworkSegmentWrapper {
                 .await;
             return Err(e);
         }
+        if segment.oauth_off() {
+            return segment.render_oauth_off(input).await;
+        }
 
         debug_logger
             .debug("NetworkWrapper", "Orchestration completed successfully")
             .await;
 
-        // Read the updated state and render status
-        self.get_network_status().await
+        // Read the updated state and render status before any housekeeping
+        let status_text = self.get_network_status().await;
+        self.run_housekeeping(started, async {
+            // Encrypted state sync between machines (opt-in, rate limited by interval_secs)
+            #[cfg(feature = "state-sync")]
+            self.run_state_sync().await;
+            // Warm-standby region measurement (opt-in, rate limited by interval_secs)
+            self.run_standby().await;
+        })
+        .await;
+        status_text
+    }
+
+    /// Upkeep that must not hold up the status, run once it is rendered
+    ///
+    /// Bounded by what the probe left of the segment budget: work that runs out
+    /// of time is dropped and picked up by a later render once it is due again.
+    async fn run_housekeeping<F>(&self, started: Instant, housekeeping: F)
+    where
+        F: std::future::Future<Output = ()>,
+    {
+        use crate::core::network::debug_logger::get_debug_logger;
+
+        let Some(left) = self
+            .budget
+            .checked_sub(started.elapsed() + HOUSEKEEPING_MARGIN)
+        else {
+            get_debug_logger()
+                .debug(
+                    "NetworkWrapper",
+                    "Housekeeping skipped: segment budget spent",
+                )
+                .await;
+            return;
+        };
+
+        if runtime::timeout(left, housekeeping).await.is_err() {
+            get_debug_logger()
+                .debug(
+                    "NetworkWrapper",
+                    &format!("Housekeeping cut off after {}ms", left.as_millis()),
+                )
+                .await;
+        }
+    }
+
+    /// The primary endpoint's NetworkSegment with its status hooks
+    ///
+    /// SLA tracking, the pause advisory and alerts follow the primary endpoint;
+    /// targets would race on one advisory file.
+    async fn primary_segment(&self) -> Result<NetworkSegment, NetworkError> {
+        use crate::core::network::debug_logger::get_debug_logger;
+
+        let debug_logger = get_debug_logger();
+        let mut segment = self.track_sla(
+            self.configure(
+                NetworkSegment::with_state_path(self.state_location()?.monitoring_path())?,
+                PersistentCookieJar::default_path().ok(),
+            ),
+            SlaTarget::PRIMARY,
+        );
+        // The pause advisory follows the primary endpoint only; targets would race on one file
+        if let Some(ref advisory_config) = self.network_config.pause_advisory {
+            match PauseAdvisory::from_config(advisory_config) {
+                Ok(advisory) => segment = segment.with_status_hook(Box::new(advisory)),
+                Err(e) => {
+                    debug_logger
+                        .warn("NetworkWrapper", &format!("Pause advisory disabled: {}", e))
+                        .await
+                }
+            }
+        }
+        // Alerts follow the primary endpoint too
+        if !self.alerts.is_empty() {
+            match AlertDispatcher::from_config(
+                &self.alerts,
+                &HostPolicy::from_config(&self.network_config),
+            )
+            .await
+            {
+                Ok(dispatcher) if !dispatcher.is_empty() => {
+                    segment = segment.with_status_hook(Box::new(dispatcher.with_pending_update(
+                        crate::core::segments::UpdateSegment::pending_version(),
+                    )))
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    debug_logger
+                        .warn("NetworkWrapper", &format!("Alerts disabled: {}", e))
+                        .await
+                }
+            }
+        }
+
+        Ok(segment)
+    }
+
+    /// A named target's NetworkSegment, with its own cookie jar and SLA tally
+    fn target_segment(&self, target: &NetworkTarget) -> Result<NetworkSegment, NetworkError> {
+        let cookie_path = PersistentCookieJar::default_path()
+            .ok()
+            .map(|path| path.with_file_name(format!("cookies-{}.json", target.name)));
+        Ok(self.track_sla(
+            self.configure(
+                NetworkSegment::for_target(
+                    target,
+                    self.state_location()?.target_path(&target.name),
+                )?,
+                cookie_path,
+            ),
+            &target.name,
+        ))
+    }
+
+    /// Apply `[network]` settings shared by every target to a NetworkSegment
+    fn configure(&self, segment: NetworkSegment, cookie_path: Option<PathBuf>) -> NetworkSegment {
+        let segment = segment
+            .with_probe_consent(self.network_config.probe_consent)
+            .map_monitor(|monitor| self.configure_monitor(monitor))
+            .with_hyperlinks(self.hyperlinks)
+            .with_icons(self.icons)
+            .with_locale(self.locale)
+            .with_number_format(self.number_format.clone())
+            .with_attention(self.attention)
+            .with_flags(self.flags.clone());
+        let segment = match DnsCache::default_path() {
+            Ok(path) if self.network_config.dns_cache => segment.with_dns_cache(path),
+            _ => segment,
+        };
+        let segment = match self.state_location() {
+            Ok(location) => segment.with_daemon_lease(DaemonLease::path_in(location.state_dir())),
+            Err(_) => segment,
+        };
+        match cookie_path {
+            Some(path) if self.network_config.persist_cookies => segment.with_cookie_jar(path),
+            _ => segment,
+        }
+    }
+
+    /// Where monitoring state lives under `[network] local_state_dir`
+    ///
+    /// A newly configured local directory is seeded from the home copy first,
+    /// so switching to it keeps the rolling window.
+    fn state_location(&self) -> Result<StateLocation, NetworkError> {
+        let location = StateLocation::from_config(&self.network_config)
+            .ok_or(NetworkError::HomeDirNotFound)?;
+        let _ = location.seed_local();
+        Ok(location)
+    }
+
+    /// HttpMonitor at `state_path` (configured state file when `None`) that probes with the
+    /// statusline's `[network]`, `[proxy]` and `[network.tls]` settings
+    ///
+    /// For callers that probe without a NetworkSegment, such as the Python bindings.
+    /// Consent, status hooks, cookies and the DNS cache stay with the statusline.
+    pub fn monitor(&self, state_path: Option<PathBuf>) -> Result<HttpMonitor, NetworkError> {
+        let state_path = match state_path {
+            Some(path) => path,
+            None => self.state_location()?.monitoring_path(),
+        };
+        Ok(self
+            .configure_monitor(HttpMonitor::new(Some(state_path))?)
+            .with_proxy_health(self.flags.is_enabled(Flag::ProxyHealth)))
+    }
+
+    /// Apply the `[network]` probe settings shared by every target to an HttpMonitor
+    fn configure_monitor(&self, monitor: HttpMonitor) -> HttpMonitor {
+        monitor
+            .with_host_policy(HostPolicy::from_config(&self.network_config))
+            .with_transport(self.transport())
+            .with_aux_checks(self.network_config.aux_checks)
+            .with_sample_horizon(self.network_config.sample_horizon_secs)
+            .with_cert_warn_days(
+                self.network_config
+                    .cert_warn_days
+                    .unwrap_or(DEFAULT_WARN_DAYS),
+            )
+            .with_ip_family(self.network_config.ip_family)
+            .with_history_retention(self.network_config.history.unwrap_or_default())
+            .with_window_policy(self.window_policy())
+            .with_hysteresis(self.network_config.hysteresis.unwrap_or_default())
+            .with_error_budget(self.error_budget_policy())
+            .with_status_expression(self.status_expression())
+            .with_oauth_probe(self.network_config.oauth_probe_enabled())
+            .with_probe_request(self.probe_request())
+            .with_compare_endpoints(self.network_config.compare_endpoints.clone())
+    }
+
+    /// Proxy, TLS and address family settings of every probe and health check transfer
+    fn transport(&self) -> TransportPolicy {
+        TransportPolicy {
+            proxy: self.proxy.clone(),
+            tls: TlsPolicy::from_network(&self.network_config),
+            ip_family: crate::core::network::ip_family::forced_family(
+                self.network_config.ip_family,
+            ),
+        }
+    }
+
+    /// Tally the endpoint's probes into the SLA ledger when `[[network.sla]]` declares a target for it
+    fn track_sla(&self, segment: NetworkSegment, endpoint: &str) -> NetworkSegment {
+        let declared = self
+            .network_config
+            .sla
+            .iter()
+            .any(|sla| sla.endpoint == endpoint);
+        match SlaLedger::default_path() {
+            Ok(path) if declared => {
+                segment.with_status_hook(Box::new(SlaTracker::new(endpoint, path)))
+            }
+            _ => segment,
+        }
+    }
+
+    /// Parsed `network.status_expression`; an invalid expression is logged and ignored
+    fn status_expression(&self) -> Option<StatusExpression> {
+        use crate::core::network::debug_logger::get_debug_logger;
+
+        let source = self.network_config.status_expression.as_deref()?;
+        match StatusExpression::parse(source) {
+            Ok(expression) => Some(expression),
+            Err(e) => {
+                get_debug_logger().warn_sync("NetworkWrapper", "status_expression", &e.to_string());
+                None
+            }
+        }
+    }
+
+    /// `[network.probe]` overrides; an invalid section is logged and the defaults used
+    fn probe_request(&self) -> ProbeRequest {
+        use crate::core::network::debug_logger::get_debug_logger;
+
+        let Some(config) = &self.network_config.probe else {
+            return ProbeRequest::default();
+        };
+        ProbeRequest::from_config(config).unwrap_or_else(|e| {
+            get_debug_logger().warn_sync("NetworkWrapper", "probe", &e.to_string());
+            ProbeRequest::default()
+        })
+    }
+
+    /// `[network.window]` overrides; an invalid section is logged and the defaults used
+    fn window_policy(&self) -> WindowPolicy {
+        use crate::core::network::debug_logger::get_debug_logger;
+
+        let Some(config) = &self.network_config.window else {
+            return WindowPolicy::default();
+        };
+        WindowPolicy::from_config(config).unwrap_or_else(|e| {
+            get_debug_logger().warn_sync("NetworkWrapper", "window", &e.to_string());
+            WindowPolicy::default()
+        })
+    }
+
+    /// `[network.error_budget]`; an invalid section is logged and the budget left off
+    fn error_budget_policy(&self) -> Option<ErrorBudgetPolicy> {
+        use crate::core::network::debug_logger::get_debug_logger;
+
+        let config = self.network_config.error_budget.as_ref()?;
+        ErrorBudgetPolicy::from_config(config)
+            .inspect_err(|e| {
+                get_debug_logger().warn_sync("NetworkWrapper", "error_budget", &e.to_string())
+            })
+            .ok()
+    }
+
+    /// Warn in the debug log when the monitoring state is written to a network filesystem
+    ///
+    /// Only called with debug logging on: listing mounts may spawn `mount` on macOS.
+    fn warn_network_filesystem(&self) {
+        use crate::core::network::debug_logger::get_debug_logger;
+        use crate::core::state_location::detect_filesystem;
+
+        let Ok(location) = self.state_location() else {
+            return;
+        };
+        let Some(mount) = detect_filesystem(location.state_dir()) else {
+            return;
+        };
+        if mount.is_network() {
+            get_debug_logger().warn_sync(
+                "NetworkWrapper",
+                "network_filesystem",
+                &format!(
+                    "Monitoring state {} is on {} ({}); atomic renames and locks are unreliable there, set [network] local_state_dir to a local directory",
+                    location.state_dir().display(),
+                    mount.fstype,
+                    mount.mount_point.display()
+                ),
+            );
+        }
+    }
+
+    /// Orchestrate every `[[network.targets]]` entry and render the `A✓ R✗` strip
+    ///
+    /// Targets probe concurrently, each with its own credentials and state partition;
+    /// a failing target renders as unknown without affecting the others.
+    async fn run_targets(
+        &self,
+        input: &StatuslineInput,
+        started: Instant,
+    ) -> Result<String, NetworkError> {
+        use crate::core::network::debug_logger::get_debug_logger;
+        use crate::core::network::status_renderer::StatusRenderer;
+        use crate::core::network::types::NetworkStatus;
+
+        let debug_logger = get_debug_logger();
+        let mut targets = Vec::new();
+        for target in &self.network_config.targets {
+            if target.has_valid_name() {
+                targets.push(target);
+            } else {
+                debug_logger
+                    .debug(
+                        "NetworkWrapper",
+                        &format!("Skipping target with invalid name: {:?}", target.name),
+                    )
+                    .await;
+            }
+        }
+
+        // Targets probe concurrently so the strip costs one probe, not one per target
+        let probes = targets.into_iter().map(|target| {
+            let debug_logger = debug_logger.clone();
+            async move {
+                let mut segment = match self.target_segment(target) {
+                    Ok(segment) => segment,
+                    Err(_) => return (target.display_label(), NetworkStatus::Unknown),
+                };
+                if let Err(e) = segment.run(input.clone()).await {
+                    debug_logger
+                        .debug(
+                            "NetworkWrapper",
+                            &format!("Target {} orchestration failed: {}", target.name, e),
+                        )
+                        .await;
+                }
+
+                let monitor = self.state_location().and_then(|location| {
+                    HttpMonitor::new(Some(location.target_path(&target.name)))
+                });
+                let status = match monitor {
+                    Ok(monitor) => monitor
+                        .load_state()
+                        .await
+                        .map(|state| state.status)
+                        .unwrap_or(NetworkStatus::Unknown),
+                    Err(e) => {
+                        debug_logger
+                            .debug(
+                                "NetworkWrapper",
+                                &format!("Target {} state unreadable: {}", target.name, e),
+                            )
+                            .await;
+                        NetworkStatus::Unknown
+                    }
+                };
+                (target.display_label(), status)
+            }
+        });
+        let strip = futures::future::join_all(probes).await;
+
+        let status_text = StatusRenderer::new()
+            .with_icons(self.icons)
+            .with_locale(self.locale)
+            .with_number_format(self.number_format.clone())
+            .with_attention(self.attention)
+            .render_target_strip(&strip);
+        self.run_housekeeping(started, self.run_standby()).await;
+        Ok(status_text)
+    }
+
+    /// Merge monitoring history with other machines when `[network.sync]` is due
+    ///
+    /// Sync failures are logged only; they never affect the rendered status.
+    #[cfg(feature = "state-sync")]
+    async fn run_state_sync(&self) {
+        use crate::core::network::debug_logger::get_debug_logger;
+        use crate::core::network::state_sync::StateSync;
+
+        let Some(ref sync_config) = self.network_config.sync else {
+            return;
+        };
+
+        let result = match StateSync::from_config(sync_config).and_then(|state_sync| {
+            let monitor = HttpMonitor::new(Some(self.state_location()?.monitoring_path()))?;
+            Ok(state_sync.with_http_monitor(monitor))
+        }) {
+            Ok(state_sync) => {
+                state_sync
+                    .with_host_policy(HostPolicy::from_config(&self.network_config))
+                    .sync_if_due()
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+
+        let message = match result {
+            Ok(Some(report)) => format!(
+                "State sync: {} machines, {} shared samples",
+                report.machines, report.shared_samples
+            ),
+            Ok(None) => "State sync not due".to_string(),
+            Err(e) => format!("State sync failed: {}", e),
+        };
+        get_debug_logger().debug("NetworkWrapper", &message).await;
+    }
+
+    /// Measure `[network.standby]` regions when a round is due
+    ///
+    /// Runs as housekeeping after the status is rendered, so it never overlaps the
+    /// measured request nor delays the statusline; failures are logged only.
+    async fn run_standby(&self) {
+        use crate::core::network::debug_logger::get_debug_logger;
+        use crate::core::network::standby::StandbyMonitor;
+
+        let Some(ref standby_config) = self.network_config.standby else {
+            return;
+        };
+
+        let result = match StandbyMonitor::from_config(standby_config) {
+            Ok(monitor) => {
+                monitor
+                    .with_host_policy(HostPolicy::from_config(&self.network_config))
+                    .measure_if_due()
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+
+        let message = match result {
+            Ok(Some(measured)) => format!("Standby: measured {} regions", measured),
+            Ok(None) => "Standby not due".to_string(),
+            Err(e) => format!("Standby measurement failed: {}", e),
+        };
+        get_debug_logger().debug("NetworkWrapper", &message).await;
     }
 
     /// Get current network monitoring status by reading existing state
@@ -106,16 +862,31 @@ impl NetworkSegmentWrapper {
     /// Used as fallback when orchestration fails.
     async fn get_network_status(&self) -> Result<String, NetworkError> {
         // Create HttpMonitor and StatusRenderer to read current state
-        use crate::core::network::http_monitor::HttpMonitor;
-        use crate::core::network::status_renderer::StatusRenderer;
+        use crate::core::network::status_renderer::{rate_limit_countdown, StatusRenderer};
 
-        let http_monitor = HttpMonitor::new(None)?;
-        let status_renderer = StatusRenderer::new();
+        let http_monitor = HttpMonitor::new(Some(self.state_location()?.monitoring_path()))?;
+        let status_renderer = StatusRenderer::new()
+            .with_hyperlinks(self.hyperlinks)
+            .with_icons(self.icons)
+            .with_locale(self.locale)
+            .with_number_format(self.number_format.clone())
+            .with_attention(self.attention);
 
         let state = http_monitor.load_state().await.unwrap_or_default();
         let status_text =
             status_renderer.render_status(&state.status, &state.network, state.api_config.as_ref());
-        Ok(status_text)
+        let status_text = status_renderer.render_with_endpoints(status_text, &state);
+        let status_text = status_renderer.render_with_alternative(status_text, &state);
+        let status_text =
+            status_renderer.render_with_uptime(status_text, &state, self.uptime_badge);
+        let status_text = status_renderer.render_with_error_budget(status_text, &state);
+        // After a 429 the reset countdown says more than the probe cooldown
+        let now = crate::core::network::cookie_jar::unix_now_secs();
+        if rate_limit_countdown(&state, now).is_some() {
+            return Ok(status_renderer.render_with_rate_limit(status_text, &state, now));
+        }
+        Ok(status_renderer
+            .render_with_cooldown(status_text, http_monitor.penalty_remaining_secs(&state)))
     }
 }
 
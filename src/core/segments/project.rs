@@ -0,0 +1,296 @@
+use super::cost::PriceTable;
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use crate::core::format::NumberFormat;
+use crate::core::recovery;
+use crate::core::scheduler::stable_hash;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Sessions per project tracked individually; older ones are folded into the totals
+pub const MAX_OPEN_SESSIONS: usize = 20;
+
+/// Projects kept in the ledger; the least recently active are dropped
+pub const MAX_PROJECTS: usize = 200;
+
+/// Ledger key of a workspace: a hash of its directory
+pub fn project_key(current_dir: &str) -> String {
+    format!("{:016x}", stable_hash(current_dir.bytes()))
+}
+
+/// Spend of one session of a project
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionCost {
+    pub usd: f64,
+    /// Unix seconds of the session's last message
+    pub last_activity: i64,
+}
+
+/// Sessions, cost and last activity of one workspace
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProjectStats {
+    /// `workspace.current_dir` the key was derived from
+    pub path: String,
+    /// Unix seconds of the last message in any session
+    #[serde(default)]
+    pub last_activity: i64,
+    /// Sessions no longer tracked individually and their spend
+    #[serde(default)]
+    pub archived_sessions: u64,
+    #[serde(default)]
+    pub archived_usd: f64,
+    /// Transcript path → spend, for the most recent sessions
+    #[serde(default)]
+    pub sessions: BTreeMap<String, SessionCost>,
+}
+
+impl ProjectStats {
+    pub fn session_count(&self) -> u64 {
+        self.archived_sessions + self.sessions.len() as u64
+    }
+
+    pub fn total_usd(&self) -> f64 {
+        self.archived_usd
+            + self
+                .sessions
+                .values()
+                .map(|session| session.usd)
+                .sum::<f64>()
+    }
+
+    /// Set a session's spend, replacing what it reported before; returns whether anything changed
+    pub fn record(&mut self, transcript: &str, session: SessionCost) -> bool {
+        self.last_activity = self.last_activity.max(session.last_activity);
+        if self.sessions.insert(transcript.to_string(), session) == Some(session) {
+            return false;
+        }
+        while self.sessions.len() > MAX_OPEN_SESSIONS {
+            let oldest = self
+                .sessions
+                .iter()
+                .min_by_key(|(_, session)| session.last_activity)
+                .map(|(key, _)| key.clone());
+            let Some(session) = oldest.and_then(|key| self.sessions.remove(&key)) else {
+                break;
+            };
+            self.archived_sessions += 1;
+            self.archived_usd += session.usd;
+        }
+        true
+    }
+}
+
+/// Per-project statistics, persisted to `~/.claude/ccstatus/projects.json`
+///
+/// Each session stores its own cost, replaced on every render, so a transcript
+/// is never counted twice; beyond `MAX_OPEN_SESSIONS` the oldest sessions of a
+/// project are folded into its archived totals.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProjectLedger {
+    /// `project_key` → statistics
+    #[serde(default)]
+    pub projects: BTreeMap<String, ProjectStats>,
+}
+
+impl ProjectLedger {
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| home.join(".claude").join("ccstatus").join("projects.json"))
+    }
+
+    /// Load the ledger; a missing file is empty, a corrupt one is quarantined
+    pub fn load(path: &Path) -> Self {
+        recovery::load_json(path).unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, content)?;
+        fs::rename(&tmp, path)
+    }
+
+    pub fn project(&self, current_dir: &str) -> Option<&ProjectStats> {
+        self.projects.get(&project_key(current_dir))
+    }
+
+    /// Record a session of the project at `current_dir`; returns whether anything changed
+    pub fn record(&mut self, current_dir: &str, transcript: &str, session: SessionCost) -> bool {
+        let project = self
+            .projects
+            .entry(project_key(current_dir))
+            .or_insert_with(|| ProjectStats {
+                path: current_dir.to_string(),
+                ..ProjectStats::default()
+            });
+        let changed = project.record(transcript, session);
+        if changed {
+            self.prune();
+        }
+        changed
+    }
+
+    /// Projects by last activity, most recent first
+    pub fn by_activity(&self) -> Vec<&ProjectStats> {
+        let mut projects: Vec<_> = self.projects.values().collect();
+        projects.sort_by(|a, b| {
+            b.last_activity
+                .cmp(&a.last_activity)
+                .then_with(|| a.path.cmp(&b.path))
+        });
+        projects
+    }
+
+    /// Table for `ccstatus stats`
+    pub fn render_report(&self, number_format: &NumberFormat) -> String {
+        if self.projects.is_empty() {
+            return "No project activity recorded yet".to_string();
+        }
+        self.by_activity()
+            .into_iter()
+            .map(|project| {
+                let sessions = project.session_count();
+                format!(
+                    "{:>10}  {:>4} session{}  last {}  {}",
+                    number_format.cost(project.total_usd()),
+                    sessions,
+                    if sessions == 1 { " " } else { "s" },
+                    format_activity(project.last_activity),
+                    project.path
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn prune(&mut self) {
+        while self.projects.len() > MAX_PROJECTS {
+            let oldest = self
+                .projects
+                .iter()
+                .min_by_key(|(_, project)| project.last_activity)
+                .map(|(key, _)| key.clone());
+            match oldest {
+                Some(key) => self.projects.remove(&key),
+                None => break,
+            };
+        }
+    }
+}
+
+/// `2025-01-23 12:00` (UTC), or `-` before the first message
+fn format_activity(at: i64) -> String {
+    DateTime::<Utc>::from_timestamp(at, 0)
+        .filter(|_| at > 0)
+        .map(|at| at.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Cost and session count of the current workspace across sessions (`$52.30 · 14 sessions`)
+///
+/// The workspace is `workspace.current_dir`; the session's cost is priced like
+/// the cost segment. Hidden until the project has a priced message.
+pub struct ProjectSegment {
+    prices: PriceTable,
+    number_format: NumberFormat,
+    state_path: Option<PathBuf>,
+}
+
+impl Default for ProjectSegment {
+    fn default() -> Self {
+        Self {
+            prices: PriceTable::default(),
+            number_format: NumberFormat::default(),
+            state_path: ProjectLedger::default_path(),
+        }
+    }
+}
+
+impl ProjectSegment {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_prices(mut self, prices: PriceTable) -> Self {
+        self.prices = prices;
+        self
+    }
+
+    /// Amount formatting (`[formatting]` table)
+    pub fn with_number_format(mut self, number_format: NumberFormat) -> Self {
+        self.number_format = number_format;
+        self
+    }
+
+    /// Where the ledger is persisted (None counts this session only)
+    pub fn with_state_path(mut self, state_path: Option<PathBuf>) -> Self {
+        self.state_path = state_path;
+        self
+    }
+}
+
+impl Segment for ProjectSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let mut session = SessionCost::default();
+        for record in input.usage_records() {
+            if let Some(price) = self.prices.lookup(&record.model) {
+                session.usd += price.cost(&record.tokens);
+            }
+            if let Some(at) = record.timestamp {
+                session.last_activity = session.last_activity.max(at.timestamp());
+            }
+        }
+
+        let mut ledger = self
+            .state_path
+            .as_deref()
+            .map(ProjectLedger::load)
+            .unwrap_or_default();
+        let current_dir = &input.workspace.current_dir;
+        // Sessions without messages are not counted; only a changed one touches disk
+        if session != SessionCost::default()
+            && ledger.record(current_dir, &input.transcript_path, session)
+        {
+            if let Some(path) = &self.state_path {
+                let _ = ledger.save(path);
+            }
+        }
+
+        let project = ledger.project(current_dir)?;
+        let total = project.total_usd();
+        if total <= 0.0 {
+            return None;
+        }
+
+        let sessions = project.session_count();
+        let mut metadata = HashMap::new();
+        metadata.insert("project_key".to_string(), project_key(current_dir));
+        metadata.insert("sessions".to_string(), sessions.to_string());
+        metadata.insert("usd".to_string(), format!("{:.6}", total));
+        metadata.insert(
+            "last_activity".to_string(),
+            project.last_activity.to_string(),
+        );
+
+        Some(SegmentData {
+            primary: format!(
+                "{} · {} session{}",
+                self.number_format.cost(total),
+                sessions,
+                if sessions == 1 { "" } else { "s" }
+            ),
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Project
+    }
+}
@@ -0,0 +1,304 @@
+use super::burn_rate::{BurnLevel, BurnRateSegment, BurnUnit};
+use super::cost::PriceTable;
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use crate::core::format::NumberFormat;
+use crate::core::recovery;
+use chrono::{DateTime, Datelike, Duration, Local, TimeZone, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Share of the budget at which the segment turns `warn_color`, unless `warn_percent` is set
+const DEFAULT_WARN_PERCENT: f64 = 80.0;
+
+/// Start of the quota week holding `now`: midnight of the last `reset_day`
+///
+/// Midnight is taken in `now`'s time zone, so the week turns over at the
+/// user's local midnight rather than UTC's.
+pub fn week_start<Tz: TimeZone>(now: DateTime<Tz>, reset_day: Weekday) -> DateTime<Utc> {
+    let days_back =
+        (now.weekday().num_days_from_monday() + 7 - reset_day.num_days_from_monday()) % 7;
+    let date = now.date_naive() - Duration::days(days_back as i64);
+    date.and_hms_opt(0, 0, 0)
+        .and_then(|midnight| now.timezone().from_local_datetime(&midnight).earliest())
+        .map(|start| start.with_timezone(&Utc))
+        .unwrap_or_else(|| now.with_timezone(&Utc))
+}
+
+/// Spend of one session within the current week
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct WeekSpend {
+    pub usd: f64,
+    /// Input, output and cache-write tokens
+    pub tokens: u64,
+}
+
+impl WeekSpend {
+    pub fn amount(&self, unit: BurnUnit) -> f64 {
+        match unit {
+            BurnUnit::Cost => self.usd,
+            BurnUnit::Tokens => self.tokens as f64,
+        }
+    }
+}
+
+/// Week-to-date spend by transcript, persisted to `~/.claude/ccstatus/weekly-usage.json`
+///
+/// Each session stores its own total for the week, replaced on every render,
+/// so re-reading a transcript never counts it twice. The sessions are dropped
+/// when a new week starts.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QuotaLedger {
+    #[serde(default)]
+    pub week_start: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub sessions: BTreeMap<String, WeekSpend>,
+}
+
+impl QuotaLedger {
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| {
+            home.join(".claude")
+                .join("ccstatus")
+                .join("weekly-usage.json")
+        })
+    }
+
+    /// Load the ledger; a missing file is empty, a corrupt one is quarantined
+    pub fn load(path: &Path) -> Self {
+        recovery::load_json(path).unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, content)?;
+        fs::rename(&tmp, path)
+    }
+
+    /// Set the session's spend for the week starting at `week_start`
+    ///
+    /// A different week clears the other sessions first. Returns whether
+    /// anything changed.
+    pub fn record(&mut self, week_start: DateTime<Utc>, session: &str, spend: WeekSpend) -> bool {
+        let mut changed = false;
+        if self.week_start != Some(week_start) {
+            self.week_start = Some(week_start);
+            self.sessions.clear();
+            changed = true;
+        }
+        if spend == WeekSpend::default() {
+            return self.sessions.remove(session).is_some() || changed;
+        }
+        self.sessions.insert(session.to_string(), spend) != Some(spend) || changed
+    }
+
+    pub fn total(&self) -> WeekSpend {
+        self.sessions
+            .values()
+            .fold(WeekSpend::default(), |total, spend| WeekSpend {
+                usd: total.usd + spend.usd,
+                tokens: total.tokens + spend.tokens,
+            })
+    }
+}
+
+/// Week-to-date spend of all sessions against a weekly budget (`$12.40/$50.00 wk`)
+///
+/// The week starts at local midnight on `reset_day`. Only messages sent since
+/// then count, so a session spanning the reset contributes its new part.
+/// Hidden until something was spent this week.
+pub struct QuotaSegment {
+    unit: BurnUnit,
+    budget: Option<f64>,
+    reset_day: Weekday,
+    warn_percent: f64,
+    prices: PriceTable,
+    number_format: NumberFormat,
+    state_path: Option<PathBuf>,
+    now: Option<DateTime<Utc>>,
+}
+
+impl Default for QuotaSegment {
+    fn default() -> Self {
+        Self {
+            unit: BurnUnit::Cost,
+            budget: None,
+            reset_day: Weekday::Mon,
+            warn_percent: DEFAULT_WARN_PERCENT,
+            prices: PriceTable::default(),
+            number_format: NumberFormat::default(),
+            state_path: QuotaLedger::default_path(),
+            now: None,
+        }
+    }
+}
+
+impl QuotaSegment {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read `unit`, `budget`, `reset_day`, `warn_percent` and `prices` from segment options
+    pub fn from_options(options: &HashMap<String, serde_json::Value>) -> Self {
+        let unit = options
+            .get("unit")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default();
+        let reset_day = options
+            .get("reset_day")
+            .and_then(|v| v.as_str())
+            .and_then(|day| day.parse::<Weekday>().ok())
+            .unwrap_or(Weekday::Mon);
+
+        let mut segment = Self::new()
+            .with_unit(unit)
+            .with_budget(options.get("budget").and_then(|v| v.as_f64()))
+            .with_reset_day(reset_day)
+            .with_prices(PriceTable::from_options(options));
+        if let Some(percent) = options.get("warn_percent").and_then(|v| v.as_f64()) {
+            segment.warn_percent = percent;
+        }
+        segment
+    }
+
+    pub fn with_unit(mut self, unit: BurnUnit) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    /// Weekly allowance in the chosen unit; None shows the spend alone
+    pub fn with_budget(mut self, budget: Option<f64>) -> Self {
+        self.budget = budget.filter(|&budget| budget > 0.0);
+        self
+    }
+
+    /// Weekday the week starts on (Monday by default)
+    pub fn with_reset_day(mut self, reset_day: Weekday) -> Self {
+        self.reset_day = reset_day;
+        self
+    }
+
+    pub fn with_prices(mut self, prices: PriceTable) -> Self {
+        self.prices = prices;
+        self
+    }
+
+    /// Amount formatting (`[formatting]` table)
+    pub fn with_number_format(mut self, number_format: NumberFormat) -> Self {
+        self.number_format = number_format;
+        self
+    }
+
+    /// Where the weekly totals are persisted (None counts this session only)
+    pub fn with_state_path(mut self, state_path: Option<PathBuf>) -> Self {
+        self.state_path = state_path;
+        self
+    }
+
+    /// Use a fixed clock (for testing)
+    pub fn with_now(mut self, now: DateTime<Utc>) -> Self {
+        self.now = Some(now);
+        self
+    }
+
+    /// Normal below `warn_percent` of the budget, critical from 100%
+    pub fn level(&self, percent: f64) -> BurnLevel {
+        if percent >= 100.0 {
+            BurnLevel::Critical
+        } else if percent >= self.warn_percent {
+            BurnLevel::Warning
+        } else {
+            BurnLevel::Normal
+        }
+    }
+
+    fn format_amount(&self, amount: f64) -> String {
+        match self.unit {
+            BurnUnit::Cost => self.number_format.cost(amount),
+            BurnUnit::Tokens => BurnRateSegment::format_tokens(amount),
+        }
+    }
+}
+
+impl Segment for QuotaSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let now = self.now.unwrap_or_else(Utc::now);
+        let week_start = week_start(now.with_timezone(&Local), self.reset_day);
+
+        let mut spend = WeekSpend::default();
+        for record in input.usage_records() {
+            if !record
+                .timestamp
+                .is_some_and(|at| at >= week_start && at <= now)
+            {
+                continue;
+            }
+            if let Some(price) = self.prices.lookup(&record.model) {
+                spend.usd += price.cost(&record.tokens);
+            }
+            spend.tokens += record.tokens.billable();
+        }
+
+        let mut ledger = self
+            .state_path
+            .as_deref()
+            .map(QuotaLedger::load)
+            .unwrap_or_default();
+        // Only a changed total touches disk
+        if ledger.record(week_start, &input.transcript_path, spend) {
+            if let Some(path) = &self.state_path {
+                let _ = ledger.save(path);
+            }
+        }
+
+        let amount = ledger.total().amount(self.unit);
+        if amount <= 0.0 {
+            return None;
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert("week_start".to_string(), week_start.to_rfc3339());
+        metadata.insert("amount".to_string(), format!("{:.6}", amount));
+        metadata.insert("sessions".to_string(), ledger.sessions.len().to_string());
+
+        let unit = match self.unit {
+            BurnUnit::Cost => "",
+            BurnUnit::Tokens => " tok",
+        };
+        let primary = match self.budget {
+            Some(budget) => {
+                let percent = amount * 100.0 / budget;
+                metadata.insert("budget".to_string(), format!("{:.6}", budget));
+                metadata.insert("percent".to_string(), format!("{:.1}", percent));
+                metadata.insert(
+                    "level".to_string(),
+                    self.level(percent).as_str().to_string(),
+                );
+                format!(
+                    "{}/{}{} wk",
+                    self.format_amount(amount),
+                    self.format_amount(budget),
+                    unit
+                )
+            }
+            None => format!("{}{} wk", self.format_amount(amount), unit),
+        };
+
+        Some(SegmentData {
+            primary,
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Quota
+    }
+}
@@ -0,0 +1,216 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Stop reasons that end an assistant turn (anything else means Claude is still working)
+const FINAL_STOP_REASONS: [&str; 4] = ["end_turn", "stop_sequence", "max_tokens", "refusal"];
+
+/// Marker Claude Code writes when the user interrupts a turn
+const INTERRUPT_MARKER: &str = "[Request interrupted by user";
+
+/// Turns older than this are treated as abandoned (e.g. Claude Code was killed mid-turn)
+const MAX_TURN_SECS: i64 = 6 * 60 * 60;
+
+/// Bytes read per step while scanning the transcript backwards from its end
+const TAIL_CHUNK_BYTES: u64 = 64 * 1024;
+
+/// Transcript line fields needed to track the in-flight turn
+#[derive(Deserialize)]
+struct TurnEntry {
+    r#type: Option<String>,
+    timestamp: Option<String>,
+    message: Option<TurnMessage>,
+}
+
+#[derive(Deserialize)]
+struct TurnMessage {
+    stop_reason: Option<String>,
+    content: Option<serde_json::Value>,
+}
+
+impl TurnEntry {
+    fn stop_reason(&self) -> Option<&str> {
+        self.message.as_ref()?.stop_reason.as_deref()
+    }
+
+    fn content(&self) -> Option<&serde_json::Value> {
+        self.message.as_ref()?.content.as_ref()
+    }
+
+    /// User prompt (not a tool result fed back to the model)
+    fn is_prompt(&self) -> bool {
+        match self.content() {
+            Some(serde_json::Value::Array(items)) => !items
+                .iter()
+                .any(|item| item.get("type").and_then(|t| t.as_str()) == Some("tool_result")),
+            _ => true,
+        }
+    }
+
+    fn is_interrupt(&self) -> bool {
+        let text = match self.content() {
+            Some(serde_json::Value::String(text)) => text.clone(),
+            Some(serde_json::Value::Array(items)) => items
+                .iter()
+                .filter_map(|item| item.get("text").and_then(|t| t.as_str()))
+                .collect(),
+            _ => return false,
+        };
+        text.starts_with(INTERRUPT_MARKER)
+    }
+}
+
+/// Lines of a file from last to first, read in `TAIL_CHUNK_BYTES` chunks from the end
+///
+/// Only the chunks up to the line the caller stops at are read, so long
+/// transcripts cost what their last turn costs.
+struct RevLines {
+    file: File,
+    /// Start of the bytes not read yet
+    pos: u64,
+    /// Start of the earliest line read so far, which may continue before `pos`
+    carry: Vec<u8>,
+    /// Complete lines of the chunks read, in file order
+    pending: Vec<String>,
+}
+
+impl RevLines {
+    fn new(file: File) -> Option<Self> {
+        let pos = file.metadata().ok()?.len();
+        Some(Self {
+            file,
+            pos,
+            carry: Vec::new(),
+            pending: Vec::new(),
+        })
+    }
+}
+
+impl Iterator for RevLines {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        loop {
+            if let Some(line) = self.pending.pop() {
+                return Some(line);
+            }
+            if self.pos == 0 {
+                if self.carry.is_empty() {
+                    return None;
+                }
+                return Some(
+                    String::from_utf8_lossy(&std::mem::take(&mut self.carry)).into_owned(),
+                );
+            }
+
+            let len = TAIL_CHUNK_BYTES.min(self.pos);
+            self.pos -= len;
+            let mut chunk = vec![0; len as usize];
+            self.file.seek(SeekFrom::Start(self.pos)).ok()?;
+            self.file.read_exact(&mut chunk).ok()?;
+            chunk.append(&mut self.carry);
+
+            // The first piece may be the end of a line that starts in an earlier chunk
+            let mut pieces = chunk.split(|&byte| byte == b'\n');
+            self.carry = pieces.next().unwrap_or_default().to_vec();
+            self.pending = pieces
+                .map(|piece| String::from_utf8_lossy(piece).into_owned())
+                .collect();
+        }
+    }
+}
+
+/// Stopwatch for the in-flight assistant turn
+///
+/// Shows the time since the prompt that started the current turn while Claude is
+/// still working (streaming or running tools); hidden once the turn completes.
+#[derive(Default)]
+pub struct StopwatchSegment {
+    now: Option<DateTime<Utc>>,
+}
+
+impl StopwatchSegment {
+    pub fn new() -> Self {
+        Self { now: None }
+    }
+
+    /// Use a fixed clock (for testing)
+    pub fn with_now(mut self, now: DateTime<Utc>) -> Self {
+        self.now = Some(now);
+        self
+    }
+
+    /// Format elapsed seconds as `42s`, `3m 07s` or `1h 02m`
+    pub fn format_elapsed(secs: i64) -> String {
+        let secs = secs.max(0);
+        if secs < 60 {
+            format!("{}s", secs)
+        } else if secs < 3600 {
+            format!("{}m {:02}s", secs / 60, secs % 60)
+        } else {
+            format!("{}h {:02}m", secs / 3600, (secs % 3600) / 60)
+        }
+    }
+}
+
+/// Start of the in-flight turn, or None when the last turn has completed
+///
+/// Scans the transcript backwards from its end and stops at the prompt that
+/// opened the last turn, so earlier turns are never read.
+pub fn in_flight_turn_started_at<P: AsRef<Path>>(transcript_path: P) -> Option<DateTime<Utc>> {
+    let file = File::open(&transcript_path).ok()?;
+    let mut entries = RevLines::new(file)?.filter_map(|line| {
+        serde_json::from_str::<TurnEntry>(line.trim())
+            .ok()
+            .filter(|entry| matches!(entry.r#type.as_deref(), Some("user" | "assistant")))
+    });
+
+    let last = entries.next()?;
+    let completed = match last.r#type.as_deref() {
+        Some("assistant") => last
+            .stop_reason()
+            .is_some_and(|reason| FINAL_STOP_REASONS.contains(&reason)),
+        _ => last.is_interrupt(),
+    };
+    if completed {
+        return None;
+    }
+
+    // Walk back to the prompt that opened this turn
+    let start = std::iter::once(last)
+        .chain(entries)
+        .find(|entry| entry.r#type.as_deref() == Some("user") && entry.is_prompt())?;
+    let timestamp = start.timestamp.as_deref()?;
+    DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+impl Segment for StopwatchSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let started_at = in_flight_turn_started_at(&input.transcript_path)?;
+        let elapsed = (self.now.unwrap_or_else(Utc::now) - started_at).num_seconds();
+        if elapsed > MAX_TURN_SECS {
+            return None;
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert("elapsed_secs".to_string(), elapsed.max(0).to_string());
+        metadata.insert("started_at".to_string(), started_at.to_rfc3339());
+
+        Some(SegmentData {
+            primary: Self::format_elapsed(elapsed),
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Stopwatch
+    }
+}
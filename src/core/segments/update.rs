@@ -1,17 +1,62 @@
 use super::{Segment, SegmentData};
 use crate::config::{InputData, SegmentId};
+use crate::core::attention::AttentionStyle;
+use crate::core::i18n::{Locale, Text};
+use crate::core::icons::{Icon, IconSet};
 
-#[derive(Default)]
-pub struct UpdateSegment;
+pub struct UpdateSegment {
+    /// Link the notification to the release notes (OSC 8)
+    hyperlinks: bool,
+    /// Glyph set for the notification icon
+    icons: IconSet,
+    /// Language of the notification
+    locale: Locale,
+    /// Emphasis on the notification (plain unless set)
+    attention: AttentionStyle,
+}
+
+impl Default for UpdateSegment {
+    fn default() -> Self {
+        Self {
+            hyperlinks: false,
+            icons: IconSet::default(),
+            locale: Locale::default(),
+            attention: AttentionStyle::None,
+        }
+    }
+}
 
 impl UpdateSegment {
     pub fn new() -> Self {
-        Self
+        Self::default()
     }
-}
 
-impl Segment for UpdateSegment {
-    fn collect(&self, _input: &InputData) -> Option<SegmentData> {
+    /// Glyph set for the notification icon
+    pub fn with_icons(mut self, icons: IconSet) -> Self {
+        self.icons = icons;
+        self
+    }
+
+    /// Language of the notification (`locale`)
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Make the notification stand out per `attention` (e.g. blinking)
+    pub fn with_attention(mut self, attention: AttentionStyle) -> Self {
+        self.attention = attention;
+        self
+    }
+
+    /// Make the notification a clickable link to the release notes
+    pub fn with_hyperlinks(mut self, enabled: bool) -> Self {
+        self.hyperlinks = enabled;
+        self
+    }
+
+    /// Version prompted within the last hour, if any
+    pub fn pending_version() -> Option<String> {
         #[cfg(feature = "self-update")]
         {
             use chrono::{Duration, Utc};
@@ -20,33 +65,48 @@ impl Segment for UpdateSegment {
             let state_file = crate::updater::UpdateStateFile::load();
 
             // Check for recent version prompts (show notification for 1 hour after prompting)
-            let now = Utc::now();
-            let one_hour_ago = now - Duration::hours(1);
+            let one_hour_ago = Utc::now() - Duration::hours(1);
 
             // Find the most recently prompted version within the last hour
-            let recent_version = state_file
+            state_file
                 .version_prompt_dates
                 .iter()
                 .filter(|(_, prompt_date)| **prompt_date > one_hour_ago)
                 .max_by_key(|(_, prompt_date)| *prompt_date)
-                .map(|(version, _)| version.clone());
-
-            if let Some(version) = recent_version {
-                return Some(SegmentData {
-                    primary: format!("\u{f06b0} Update v{}!", version),
-                    secondary: String::new(),
-                    metadata: std::collections::HashMap::new(),
-                });
-            }
-
-            // No notification to show
-            None
+                .map(|(version, _)| version.clone())
         }
 
         #[cfg(not(feature = "self-update"))]
         None
     }
 
+    /// Notification text for `version`
+    pub fn notification(&self, version: &str) -> String {
+        use crate::core::hyperlink::{link, release_notes_url};
+
+        let text = format!(
+            "{} {} v{}!",
+            self.icons.glyph(Icon::UpdateAvailable),
+            self.locale.text(Text::UpdateAvailable),
+            version
+        );
+        link(
+            &self.attention.apply(&text),
+            &release_notes_url(version),
+            self.hyperlinks,
+        )
+    }
+}
+
+impl Segment for UpdateSegment {
+    fn collect(&self, _input: &InputData) -> Option<SegmentData> {
+        Self::pending_version().map(|version| SegmentData {
+            primary: self.notification(&version),
+            secondary: String::new(),
+            metadata: std::collections::HashMap::new(),
+        })
+    }
+
     fn id(&self) -> SegmentId {
         SegmentId::Update
     }
@@ -60,7 +60,7 @@ impl Segment for UsageSegment {
     }
 }
 
-fn parse_transcript_usage<P: AsRef<Path>>(transcript_path: P) -> u32 {
+pub(crate) fn parse_transcript_usage<P: AsRef<Path>>(transcript_path: P) -> u32 {
     let file = match fs::File::open(&transcript_path) {
         Ok(file) => file,
         Err(_) => return 0,
@@ -0,0 +1,144 @@
+use super::{Segment, SegmentData};
+use crate::config::{AnsiColor, InputData, SegmentConfig, SegmentId};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// Claude Code and ccstatus versions
+///
+/// Shows `CC 1.0.80 · ccstatus 2.2.7`; the Claude Code part needs the full
+/// stdin payload and is left out without it. When the last fetched update
+/// manifest names a newer ccstatus, the segment adds `↑<latest>` and
+/// [`outdated_colors`] highlights it.
+pub struct VersionSegment {
+    /// Claude Code version from stdin
+    client_version: Option<String>,
+    /// Version of this binary
+    current_version: String,
+    /// Version of the last fetched update manifest
+    latest_version: Option<String>,
+}
+
+impl Default for VersionSegment {
+    fn default() -> Self {
+        Self {
+            client_version: None,
+            current_version: env!("CARGO_PKG_VERSION").to_string(),
+            latest_version: None,
+        }
+    }
+}
+
+impl VersionSegment {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Claude Code version (`version` in the statusline input)
+    pub fn with_client_version(mut self, version: Option<String>) -> Self {
+        self.client_version = version.filter(|version| !version.trim().is_empty());
+        self
+    }
+
+    /// ccstatus version to report instead of this binary's
+    pub fn with_current_version(mut self, version: impl Into<String>) -> Self {
+        self.current_version = version.into();
+        self
+    }
+
+    /// Latest ccstatus version, normally the manifest version persisted by the updater
+    pub fn with_latest_version(mut self, version: Option<String>) -> Self {
+        self.latest_version = version;
+        self
+    }
+
+    /// Newer ccstatus release than the running one, if the manifest names one
+    pub fn newer_release(&self) -> Option<&str> {
+        self.latest_version
+            .as_deref()
+            .filter(|latest| compare_versions(latest, &self.current_version) == Ordering::Greater)
+    }
+}
+
+impl Segment for VersionSegment {
+    fn collect(&self, _input: &InputData) -> Option<SegmentData> {
+        let mut metadata = HashMap::new();
+        metadata.insert("ccstatus".to_string(), self.current_version.clone());
+
+        let mut parts = Vec::new();
+        if let Some(client) = &self.client_version {
+            metadata.insert("claude_code".to_string(), client.clone());
+            parts.push(format!("CC {}", client));
+        }
+        parts.push(format!("ccstatus {}", self.current_version));
+
+        let secondary = match self.newer_release() {
+            Some(latest) => {
+                metadata.insert("outdated".to_string(), "true".to_string());
+                metadata.insert("latest".to_string(), latest.to_string());
+                format!("↑{}", latest)
+            }
+            None => String::new(),
+        };
+
+        Some(SegmentData {
+            primary: parts.join(" · "),
+            secondary,
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Version
+    }
+}
+
+/// Compare dotted versions numerically (`2.10.0` > `2.9.1`)
+///
+/// A leading `v` and build metadata are ignored, missing components count as
+/// zero, and a pre-release (`2.3.0-beta.1`) sorts before its release.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    fn split(version: &str) -> (Vec<u64>, bool) {
+        let version = version.trim().trim_start_matches('v');
+        let version = version.split('+').next().unwrap_or_default();
+        let (release, pre) = match version.split_once('-') {
+            Some((release, _)) => (release, true),
+            None => (version, false),
+        };
+        let numbers = release
+            .split('.')
+            .map(|part| part.parse().unwrap_or(0))
+            .collect();
+        (numbers, pre)
+    }
+
+    let (a_numbers, a_pre) = split(a);
+    let (b_numbers, b_pre) = split(b);
+    let len = a_numbers.len().max(b_numbers.len());
+    (0..len)
+        .map(|i| {
+            let a = a_numbers.get(i).copied().unwrap_or(0);
+            let b = b_numbers.get(i).copied().unwrap_or(0);
+            a.cmp(&b)
+        })
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or_else(|| b_pre.cmp(&a_pre))
+}
+
+/// Highlight the segment while a newer ccstatus is available
+///
+/// Uses `options.outdated_color` for icon and text, bright yellow by default.
+pub fn outdated_colors(config: &SegmentConfig, data: &SegmentData) -> SegmentConfig {
+    if !data.metadata.contains_key("outdated") {
+        return config.clone();
+    }
+    let color = config
+        .options
+        .get("outdated_color")
+        .and_then(|v| serde_json::from_value::<AnsiColor>(v.clone()).ok())
+        .unwrap_or(AnsiColor::Color16 { c16: 11 });
+
+    let mut config = config.clone();
+    config.colors.icon = Some(color.clone());
+    config.colors.text = Some(color);
+    config
+}
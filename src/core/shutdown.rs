@@ -0,0 +1,298 @@
+//! Shared graceful shutdown for long-running modes
+//!
+//! A one-shot statusline render finishes in milliseconds, but long-running
+//! modes (TUI, daemon, watch, serve) must not die half-way through a state
+//! write or leave lock files behind on Ctrl-C / SIGTERM. They share one
+//! [`ShutdownCoordinator`]:
+//!
+//! - `install_signal_handler` turns SIGINT/SIGTERM (Ctrl-C and console close on
+//!   Windows) into a shutdown request; a second signal exits immediately
+//! - main loops await `requested()` or poll `is_requested()`
+//! - writers hold a [`WriteGuard`] from `begin_write()`, so shutdown waits for them
+//! - [`FileLock`]s register with the coordinator and are released on shutdown
+//!   even if their owner never gets to drop them
+//! - `on_shutdown` hooks (e.g. flushing state) run once writes have drained
+//!
+//! `ShutdownCoordinator::global()` is the process-wide instance used by the
+//! monitoring state writer and the debug logger.
+
+use fs2::FileExt;
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+/// Exit code used when a second signal interrupts a graceful shutdown
+pub const FORCED_EXIT_CODE: i32 = 130;
+
+/// Why shutdown was requested
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShutdownReason {
+    /// SIGINT / SIGTERM / Ctrl-C
+    Signal,
+    /// Requested by the program itself
+    Requested,
+}
+
+/// Outcome of [`ShutdownCoordinator::shutdown`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShutdownReport {
+    /// All in-flight writes finished within the grace period
+    pub drained: bool,
+    /// `on_shutdown` hooks that ran
+    pub hooks_run: usize,
+    /// Lock files still held and released by the coordinator
+    pub locks_released: usize,
+}
+
+type Hook = Box<dyn FnOnce() + Send>;
+
+#[derive(Default)]
+struct Shared {
+    reason: Option<ShutdownReason>,
+    wakers: Vec<Waker>,
+    hooks: Vec<Hook>,
+    locks: HashMap<u64, PathBuf>,
+    next_lock_id: u64,
+}
+
+#[derive(Default)]
+struct Inner {
+    requested: AtomicBool,
+    in_flight: AtomicUsize,
+    shared: Mutex<Shared>,
+}
+
+/// Cloneable handle coordinating shutdown across tasks and threads
+#[derive(Clone, Default)]
+pub struct ShutdownCoordinator {
+    inner: Arc<Inner>,
+}
+
+impl ShutdownCoordinator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Process-wide coordinator
+    pub fn global() -> &'static ShutdownCoordinator {
+        static GLOBAL: OnceLock<ShutdownCoordinator> = OnceLock::new();
+        GLOBAL.get_or_init(ShutdownCoordinator::new)
+    }
+
+    /// Route SIGINT/SIGTERM to this coordinator; a second signal exits with `FORCED_EXIT_CODE`
+    ///
+    /// Only one handler can be installed per process.
+    pub fn install_signal_handler(&self) -> std::io::Result<()> {
+        let coordinator = self.clone();
+        ctrlc::set_handler(move || {
+            if coordinator.is_requested() {
+                std::process::exit(FORCED_EXIT_CODE);
+            }
+            coordinator.request(ShutdownReason::Signal);
+        })
+        .map_err(std::io::Error::other)
+    }
+
+    /// Ask every participant to stop; the first reason wins
+    pub fn request(&self, reason: ShutdownReason) {
+        let mut shared = self.shared();
+        shared.reason.get_or_insert(reason);
+        self.inner.requested.store(true, Ordering::SeqCst);
+        wake_all(&mut shared);
+    }
+
+    pub fn is_requested(&self) -> bool {
+        self.inner.requested.load(Ordering::SeqCst)
+    }
+
+    pub fn reason(&self) -> Option<ShutdownReason> {
+        self.shared().reason
+    }
+
+    /// Resolves once shutdown has been requested
+    pub fn requested(&self) -> impl Future<Output = ()> + '_ {
+        WaitFor {
+            coordinator: self,
+            ready: |inner: &Inner| inner.requested.load(Ordering::SeqCst),
+        }
+    }
+
+    /// Mark a write as in flight until the guard is dropped
+    pub fn begin_write(&self) -> WriteGuard {
+        self.inner.in_flight.fetch_add(1, Ordering::SeqCst);
+        WriteGuard {
+            coordinator: self.clone(),
+        }
+    }
+
+    /// Writes currently in flight
+    pub fn writes_in_flight(&self) -> usize {
+        self.inner.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Run `hook` during shutdown, after in-flight writes have drained
+    pub fn on_shutdown<F>(&self, hook: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.shared().hooks.push(Box::new(hook));
+    }
+
+    /// Request shutdown, wait up to `grace` for in-flight writes, then run hooks and release locks
+    pub async fn shutdown(&self, grace: Duration) -> ShutdownReport {
+        self.request(ShutdownReason::Requested);
+
+        let drained = WaitFor {
+            coordinator: self,
+            ready: |inner: &Inner| inner.in_flight.load(Ordering::SeqCst) == 0,
+        };
+        let drained = crate::runtime::timeout(grace, drained).await.is_ok();
+
+        let hooks = std::mem::take(&mut self.shared().hooks);
+        let hooks_run = hooks.len();
+        for hook in hooks {
+            hook();
+        }
+
+        let locks = std::mem::take(&mut self.shared().locks);
+        for path in locks.values() {
+            let _ = std::fs::remove_file(path);
+        }
+
+        ShutdownReport {
+            drained,
+            hooks_run,
+            locks_released: locks.len(),
+        }
+    }
+
+    fn shared(&self) -> MutexGuard<'_, Shared> {
+        self.inner
+            .shared
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn register_lock(&self, path: &Path) -> u64 {
+        let mut shared = self.shared();
+        shared.next_lock_id += 1;
+        let id = shared.next_lock_id;
+        shared.locks.insert(id, path.to_path_buf());
+        id
+    }
+
+    /// Forget a lock; `false` when shutdown already released it
+    fn deregister_lock(&self, id: u64) -> bool {
+        self.shared().locks.remove(&id).is_some()
+    }
+}
+
+fn wake_all(shared: &mut Shared) {
+    for waker in shared.wakers.drain(..) {
+        waker.wake();
+    }
+}
+
+/// In-flight write; shutdown waits until it is dropped
+pub struct WriteGuard {
+    coordinator: ShutdownCoordinator,
+}
+
+impl Drop for WriteGuard {
+    fn drop(&mut self) {
+        if self
+            .coordinator
+            .inner
+            .in_flight
+            .fetch_sub(1, Ordering::SeqCst)
+            == 1
+        {
+            wake_all(&mut self.coordinator.shared());
+        }
+    }
+}
+
+/// Exclusive advisory lock file, removed when dropped or on shutdown
+pub struct FileLock {
+    file: File,
+    path: PathBuf,
+    id: u64,
+    coordinator: ShutdownCoordinator,
+}
+
+impl FileLock {
+    /// Create and lock `path`; `Ok(None)` when another holder has it
+    pub fn try_acquire(
+        coordinator: &ShutdownCoordinator,
+        path: impl Into<PathBuf>,
+    ) -> std::io::Result<Option<Self>> {
+        let path = path.into();
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&path)?;
+        if file.try_lock_exclusive().is_err() {
+            return Ok(None);
+        }
+
+        let id = coordinator.register_lock(&path);
+        Ok(Some(Self {
+            file,
+            path,
+            id,
+            coordinator: coordinator.clone(),
+        }))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        // After shutdown released the lock the path may belong to someone else
+        if self.coordinator.deregister_lock(self.id) {
+            let _ = std::fs::remove_file(&self.path);
+        }
+        let _ = FileExt::unlock(&self.file);
+    }
+}
+
+/// Resolves when `ready` holds, re-checked whenever the coordinator wakes its waiters
+struct WaitFor<'a, F> {
+    coordinator: &'a ShutdownCoordinator,
+    ready: F,
+}
+
+impl<F> Future for WaitFor<'_, F>
+where
+    F: Fn(&Inner) -> bool + Unpin,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let inner = &self.coordinator.inner;
+        if (self.ready)(inner) {
+            return Poll::Ready(());
+        }
+
+        let mut shared = self.coordinator.shared();
+        if !shared.wakers.iter().any(|w| w.will_wake(cx.waker())) {
+            shared.wakers.push(cx.waker().clone());
+        }
+        // Re-check under the lock so a wake between the first check and registration is not lost
+        if (self.ready)(inner) {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
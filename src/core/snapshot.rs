@@ -0,0 +1,161 @@
+//! Segment data snapshot for Claude Code hooks (`ccstatus snapshot`)
+//!
+//! Hook scripts want the statusline's data, not its rendering. A snapshot
+//! collects the configured segments like a statusline run and prints them as
+//! JSON with ANSI sequences stripped and keys that stay put between releases:
+//! `schema`, `version`, `segments`, `network` and `update` are always present
+//! (`null` when empty), segments are keyed by name (custom segments as
+//! `custom.<name>`) and every map is sorted. `--for-hook` prints it on a single
+//! line.
+//!
+//! The network block is read from the persisted monitoring state; taking a
+//! snapshot never sends a probe.
+
+use crate::config::{InputData, Model, OutputStyle, SegmentConfig, SegmentId, Workspace};
+use crate::core::output::strip_ansi;
+use crate::core::segments::health::segment_name;
+use crate::core::segments::SegmentData;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[cfg(feature = "network-monitoring")]
+use crate::core::network::types::MonitoringSnapshot;
+
+/// Bumped when a key is renamed or removed; new keys do not bump it
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Everything a hook script gets from `ccstatus snapshot`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HookSnapshot {
+    pub schema: u32,
+    /// ccstatus version that produced the snapshot
+    pub version: String,
+    pub segments: BTreeMap<String, SegmentSnapshot>,
+    pub network: Option<NetworkSnapshot>,
+    /// Version awaiting update, if any
+    pub update: Option<String>,
+}
+
+/// One segment's text and metadata, without styling
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SegmentSnapshot {
+    pub primary: String,
+    pub secondary: String,
+    pub metadata: BTreeMap<String, String>,
+}
+
+/// Last persisted probe result
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NetworkSnapshot {
+    /// `healthy`, `degraded`, `error`, `unknown` or `blocked_by_policy`
+    pub status: String,
+    /// HTTP status of the last probe (0 = none or connection error)
+    pub http_status: u16,
+    pub latency_ms: u32,
+    pub p95_latency_ms: u32,
+    pub error_type: Option<String>,
+    /// When the state was last written (local ISO-8601)
+    pub updated_at: String,
+}
+
+impl HookSnapshot {
+    /// Snapshot of collected segment data; segments with no text are left out
+    pub fn new(segments: &[(SegmentConfig, SegmentData)]) -> Self {
+        let segments = segments
+            .iter()
+            .filter_map(|(config, data)| {
+                let snapshot = SegmentSnapshot {
+                    primary: strip_ansi(&data.primary),
+                    secondary: strip_ansi(&data.secondary),
+                    metadata: data
+                        .metadata
+                        .iter()
+                        .map(|(key, value)| (key.clone(), strip_ansi(value)))
+                        .collect(),
+                };
+                if snapshot.primary.trim().is_empty() && snapshot.secondary.trim().is_empty() {
+                    return None;
+                }
+                let key = match (config.id, data.metadata.get("name")) {
+                    (SegmentId::Custom, Some(name)) => format!("custom.{}", name),
+                    (id, _) => segment_name(id),
+                };
+                Some((key, snapshot))
+            })
+            .collect();
+
+        Self {
+            schema: SCHEMA_VERSION,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            segments,
+            network: None,
+            update: None,
+        }
+    }
+
+    /// Include the network block from the persisted monitoring state
+    #[cfg(feature = "network-monitoring")]
+    pub fn with_network(mut self, state: &MonitoringSnapshot) -> Self {
+        self.network = Some(NetworkSnapshot {
+            status: state.status.as_str().to_string(),
+            http_status: state.network.last_http_status,
+            latency_ms: state.network.latency_ms,
+            p95_latency_ms: state.network.p95_latency_ms,
+            error_type: state.network.error_type.clone(),
+            updated_at: state.timestamp.clone(),
+        });
+        self
+    }
+
+    /// Include the pending update (`None` = up to date)
+    pub fn with_update(mut self, version: Option<String>) -> Self {
+        self.update = version.map(|version| version.trim_start_matches('v').to_string());
+        self
+    }
+
+    /// JSON text; `single_line` for hooks, indented otherwise
+    pub fn to_json(&self, single_line: bool) -> String {
+        let json = if single_line {
+            serde_json::to_string(self)
+        } else {
+            serde_json::to_string_pretty(self)
+        };
+        json.unwrap_or_default()
+    }
+}
+
+/// Segment input from whatever JSON the caller piped in
+///
+/// Hook events carry `cwd` and `transcript_path`; statusline input carries
+/// `workspace.current_dir` and `model.id` / `model.display_name`. Missing fields fall back to
+/// the current directory and an empty model, which leaves the model segment out.
+pub fn input_from_json(value: &serde_json::Value) -> InputData {
+    let string_at = |pointer: &str| value.pointer(pointer).and_then(|v| v.as_str());
+    let current_dir = string_at("/workspace/current_dir")
+        .or_else(|| string_at("/cwd"))
+        .map(str::to_string)
+        .unwrap_or_else(|| {
+            std::env::current_dir()
+                .map(|dir| dir.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        });
+
+    InputData {
+        model: Model {
+            id: string_at("/model/id").unwrap_or_default().to_string(),
+            display_name: string_at("/model/display_name")
+                .unwrap_or_default()
+                .to_string(),
+        },
+        workspace: Workspace { current_dir },
+        transcript_path: string_at("/transcript_path")
+            .unwrap_or_default()
+            .to_string(),
+        terminal_width: None,
+        output_style: string_at("/output_style/name").map(|name| OutputStyle {
+            name: name.to_string(),
+        }),
+        permission_mode: string_at("/permission_mode").map(str::to_string),
+        usage: Default::default(),
+    }
+}
@@ -0,0 +1,332 @@
+//! Where the hot-path monitoring state lives
+//!
+//! The monitoring state is rewritten on every render through a temp file and a
+//! rename. On NFS or SMB home directories that rename is not atomic for other
+//! clients, attribute caching can serve a stale file, and two machines sharing
+//! the home directory race each other. `[network] local_state_dir` moves the
+//! monitoring state and the per-target partitions to a local directory; the
+//! housekeeping run after each render copies them back to `~/.claude/ccstatus`
+//! every [`LOCAL_STATE_SYNC_SCHEDULE`], so readers of the home copy lag at most
+//! that long. Callers build the location from the config they loaded with
+//! [`StateLocation::from_config`] and pass it down.
+//!
+//! [`detect_filesystem`] tells `ccstatus doctor` and the debug log whether a
+//! path sits on a network filesystem.
+
+use crate::config::NetworkConfig;
+use crate::core::scheduler::{JobLedger, Schedule};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Job key of the copy back to the home directory in [`JobLedger`]
+pub const LOCAL_STATE_SYNC_JOB: &str = "local-state-sync";
+
+/// How often local state is copied back to the home directory
+pub const LOCAL_STATE_SYNC_SCHEDULE: Schedule = Schedule::every(5 * 60);
+
+/// Monitoring state of the default partition
+pub const MONITORING_STATE_FILE: &str = "ccstatus-monitoring.json";
+
+/// Subdirectory holding the per-target partitions
+const TARGETS_DIR: &str = "targets";
+
+/// Filesystem types whose rename and locking semantics are unreliable across clients
+const NETWORK_FILESYSTEMS: [&str; 17] = [
+    "nfs",
+    "nfs4",
+    "cifs",
+    "smb",
+    "smb2",
+    "smb3",
+    "smbfs",
+    "afs",
+    "ncpfs",
+    "sshfs",
+    "fuse.sshfs",
+    "davfs",
+    "fuse.davfs2",
+    "webdav",
+    "ceph",
+    "fuse.glusterfs",
+    "lustre",
+];
+
+/// A mounted filesystem
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mount {
+    pub mount_point: PathBuf,
+    /// e.g. `ext4`, `nfs4`, `smbfs`
+    pub fstype: String,
+}
+
+impl Mount {
+    pub fn is_network(&self) -> bool {
+        NETWORK_FILESYSTEMS.contains(&self.fstype.to_lowercase().as_str())
+    }
+}
+
+/// Mounts listed in `/proc/self/mounts` (`device mount_point fstype options ...`)
+pub fn parse_proc_mounts(content: &str) -> Vec<Mount> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let _device = fields.next()?;
+            let mount_point = unescape_octal(fields.next()?);
+            let fstype = fields.next()?;
+            Some(Mount {
+                mount_point: PathBuf::from(mount_point),
+                fstype: fstype.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Mounts listed by BSD/macOS `mount` (`device on /mount/point (fstype, options)`)
+pub fn parse_mount_output(content: &str) -> Vec<Mount> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let (_device, rest) = line.split_once(" on ")?;
+            let (mount_point, options) = rest.rsplit_once(" (")?;
+            let fstype = options.split([',', ')']).next()?.trim();
+            Some(Mount {
+                mount_point: PathBuf::from(mount_point),
+                fstype: fstype.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// `\040`-style escapes of spaces, tabs and newlines in `/proc/self/mounts`
+fn unescape_octal(field: &str) -> String {
+    let mut out = String::with_capacity(field.len());
+    let mut rest = field;
+    while let Some(index) = rest.find('\\') {
+        out.push_str(&rest[..index]);
+        let escape = rest.get(index + 1..index + 4);
+        match escape.and_then(|digits| u8::from_str_radix(digits, 8).ok()) {
+            Some(byte) => {
+                out.push(byte as char);
+                rest = &rest[index + 4..];
+            }
+            None => {
+                out.push('\\');
+                rest = &rest[index + 1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// The mount holding `path`: the one with the longest matching mount point
+pub fn mount_for<'a>(path: &Path, mounts: &'a [Mount]) -> Option<&'a Mount> {
+    mounts
+        .iter()
+        .filter(|mount| path.starts_with(&mount.mount_point))
+        .max_by_key(|mount| mount.mount_point.components().count())
+}
+
+/// The filesystem holding `path` (or its nearest existing ancestor)
+///
+/// `None` where mounts cannot be listed (Windows, or a failing `mount`).
+pub fn detect_filesystem(path: &Path) -> Option<Mount> {
+    let existing = path.ancestors().find(|ancestor| ancestor.exists())?;
+    let resolved = existing.canonicalize().ok()?;
+    mount_for(&resolved, &list_mounts()?).cloned()
+}
+
+#[cfg(target_os = "linux")]
+fn list_mounts() -> Option<Vec<Mount>> {
+    fs::read_to_string("/proc/self/mounts")
+        .ok()
+        .map(|content| parse_proc_mounts(&content))
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+fn list_mounts() -> Option<Vec<Mount>> {
+    let output = std::process::Command::new("/sbin/mount").output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| parse_mount_output(&String::from_utf8_lossy(&output.stdout)))
+}
+
+#[cfg(not(unix))]
+fn list_mounts() -> Option<Vec<Mount>> {
+    None
+}
+
+/// Home and (optional) local directory of the monitoring state
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateLocation {
+    /// `~/.claude/ccstatus`
+    home_dir: PathBuf,
+    /// `[network] local_state_dir`, when set
+    local_dir: Option<PathBuf>,
+}
+
+impl StateLocation {
+    pub fn new(home_dir: PathBuf, local_dir: Option<PathBuf>) -> Self {
+        Self {
+            home_dir,
+            local_dir,
+        }
+    }
+
+    /// Build from `[network]`, expanding a leading `~/`; None without a home directory
+    pub fn from_config(config: &NetworkConfig) -> Option<Self> {
+        let home = dirs::home_dir()?;
+        let local_dir = match config.local_state_dir.as_deref().map(str::trim) {
+            Some(dir) if !dir.is_empty() => Some(match dir.strip_prefix("~/") {
+                Some(rest) => home.join(rest),
+                None => PathBuf::from(dir),
+            }),
+            _ => None,
+        };
+        Some(Self::new(home.join(".claude").join("ccstatus"), local_dir))
+    }
+
+    pub fn home_dir(&self) -> &Path {
+        &self.home_dir
+    }
+
+    pub fn local_dir(&self) -> Option<&Path> {
+        self.local_dir.as_deref()
+    }
+
+    /// Directory the monitoring state is read from and written to
+    pub fn state_dir(&self) -> &Path {
+        self.local_dir.as_deref().unwrap_or(&self.home_dir)
+    }
+
+    /// Monitoring state of the default partition
+    pub fn monitoring_path(&self) -> PathBuf {
+        self.state_dir().join(MONITORING_STATE_FILE)
+    }
+
+    /// State partition of a named target: `<state dir>/targets/<name>.json`
+    pub fn target_path(&self, name: &str) -> PathBuf {
+        self.state_dir()
+            .join(TARGETS_DIR)
+            .join(format!("{}.json", name))
+    }
+
+    /// Copy home state files missing from the local directory; returns files copied
+    ///
+    /// Writers call this before their first write, so switching to a newly
+    /// configured local directory keeps the rolling window.
+    pub fn seed_local(&self) -> io::Result<usize> {
+        match self.local_dir {
+            Some(ref local) if !local.join(MONITORING_STATE_FILE).exists() => {
+                copy_state_files(&self.home_dir, local)
+            }
+            _ => Ok(0),
+        }
+    }
+
+    /// Copy the local state files back to the home directory; returns files copied
+    pub fn sync_back(&self) -> io::Result<usize> {
+        match self.local_dir {
+            Some(ref local) => copy_state_files(local, &self.home_dir),
+            None => Ok(0),
+        }
+    }
+
+    /// [`Self::sync_back`] when due per [`LOCAL_STATE_SYNC_SCHEDULE`]
+    ///
+    /// Returns None when nothing ran. The run is recorded even when it fails,
+    /// so a broken share is retried on the next interval, not on every render.
+    pub fn sync_back_at(&self, ledger_path: &Path, now: SystemTime) -> Option<io::Result<usize>> {
+        self.local_dir.as_ref()?;
+        let now_secs = now
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or_default();
+        let mut ledger = JobLedger::load(ledger_path);
+        if !ledger.is_due(LOCAL_STATE_SYNC_JOB, &LOCAL_STATE_SYNC_SCHEDULE, now_secs) {
+            return None;
+        }
+        let result = self.sync_back();
+        ledger.record(LOCAL_STATE_SYNC_JOB, now_secs);
+        let _ = ledger.save(ledger_path);
+        Some(result)
+    }
+
+    /// Report for `ccstatus doctor`, given the filesystems detected for both directories
+    pub fn render_report(&self, home_mount: Option<&Mount>, local_mount: Option<&Mount>) -> String {
+        let describe = |dir: &Path, mount: Option<&Mount>| match mount {
+            Some(mount) => format!(
+                "{} ({}{})",
+                dir.display(),
+                mount.fstype,
+                if mount.is_network() { ", network" } else { "" }
+            ),
+            None => format!("{} (filesystem unknown)", dir.display()),
+        };
+
+        let mut lines = vec![format!("Home:  {}", describe(&self.home_dir, home_mount))];
+        match self.local_dir {
+            Some(ref local) => {
+                lines.push(format!("Local: {}", describe(local, local_mount)));
+                lines.push(format!(
+                    "Monitoring state is kept locally and copied home every {} min",
+                    LOCAL_STATE_SYNC_SCHEDULE.interval_secs / 60
+                ));
+                if local_mount.is_some_and(Mount::is_network) {
+                    lines.push(
+                        "Warning: local_state_dir is on a network filesystem too".to_string(),
+                    );
+                }
+            }
+            None if home_mount.is_some_and(Mount::is_network) => lines.push(
+                "Warning: atomic renames and locks are unreliable on network filesystems; \
+                 set [network] local_state_dir to a local directory (e.g. \"~/.cache/ccstatus\" \
+                 on a local disk)"
+                    .to_string(),
+            ),
+            None => {}
+        }
+        lines.join("\n")
+    }
+}
+
+/// Copy the monitoring files of `from` (and its targets) into `to`, each through a temp file
+///
+/// Only the state files themselves and their probe histories move; temp files,
+/// quarantined copies and anything else in the directory stay put.
+fn copy_state_files(from: &Path, to: &Path) -> io::Result<usize> {
+    let mut copied = 0;
+    for (source_dir, dest_dir) in [
+        (from.to_path_buf(), to.to_path_buf()),
+        (from.join(TARGETS_DIR), to.join(TARGETS_DIR)),
+    ] {
+        let entries = match fs::read_dir(&source_dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let in_targets = source_dir != from;
+            let is_state = if in_targets {
+                name.ends_with(".json") || name.ends_with(".history.jsonl")
+            } else {
+                name == MONITORING_STATE_FILE || name == "ccstatus-monitoring.history.jsonl"
+            };
+            if !is_state || !entry.file_type().is_ok_and(|kind| kind.is_file()) {
+                continue;
+            }
+            fs::create_dir_all(&dest_dir)?;
+            let dest = dest_dir.join(&name);
+            let tmp = dest_dir.join(format!("{}.sync.tmp", name));
+            fs::copy(entry.path(), &tmp)?;
+            fs::rename(&tmp, &dest)?;
+            copied += 1;
+        }
+    }
+    Ok(copied)
+}
@@ -0,0 +1,311 @@
+//! Latency statistics shared by the monitor, the renderers and reports
+//!
+//! Samples are milliseconds (`u32`) in arrival order, oldest first. Percentiles
+//! use the nearest-rank method (`rank = ceil(p * n)`, 1-based), so they always
+//! return an observed sample and stay stable for the small rolling windows the
+//! monitor keeps. Empty inputs yield `0` / `None` instead of panicking.
+//!
+//! Reports over long histories (per-version and per-region summaries) feed a
+//! [`LatencySketch`] instead: exact up to `SKETCH_EXACT_SAMPLES` samples, then
+//! a DDSketch whose percentiles are within `SKETCH_RELATIVE_ACCURACY` of the
+//! nearest-rank sample, in memory bounded by the latency range rather than the
+//! sample count. Sketches serialize, so persisted tallies (the monthly SLA
+//! ledger) keep one too.
+
+use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
+use std::collections::BTreeMap;
+
+/// Bucket upper bounds used by `ccstatus net status` (a last bucket catches the rest)
+pub const LATENCY_BUCKETS_MS: [u32; 4] = [500, 1000, 2000, 5000];
+
+/// Relative change between window halves below which latency counts as steady
+pub const TREND_THRESHOLD_PERCENT: f64 = 10.0;
+
+/// Fewest samples for which a trend is reported
+pub const MIN_TREND_SAMPLES: usize = 4;
+
+/// Samples a [`LatencySketch`] keeps verbatim before switching to bins
+pub const SKETCH_EXACT_SAMPLES: usize = 256;
+
+/// Relative error bound of sketched percentiles (1%)
+pub const SKETCH_RELATIVE_ACCURACY: f64 = 0.01;
+
+/// Samples sorted without touching the heap for rolling-window sized inputs
+type Sorted = SmallVec<[u32; 32]>;
+
+/// Nearest-rank percentile of unsorted `samples`, `p` in `0.0..=1.0`
+pub fn percentile(samples: &[u32], p: f64) -> u32 {
+    let mut sorted: Sorted = SmallVec::from_slice(samples);
+    sorted.sort_unstable();
+    percentile_sorted(&sorted, p)
+}
+
+/// Nearest-rank percentile of already sorted `samples`
+pub fn percentile_sorted(sorted: &[u32], p: f64) -> u32 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = ((p.clamp(0.0, 1.0) * sorted.len() as f64).ceil() as usize).max(1);
+    sorted[rank.min(sorted.len()) - 1]
+}
+
+/// Several percentiles from one sort, in the order of `ps`
+pub fn percentiles<const N: usize>(samples: &[u32], ps: [f64; N]) -> [u32; N] {
+    let mut sorted: Sorted = SmallVec::from_slice(samples);
+    sorted.sort_unstable();
+    ps.map(|p| percentile_sorted(&sorted, p))
+}
+
+/// Percentile summary of an unbounded stream of latency samples
+///
+/// Holds the samples themselves while there are at most
+/// `SKETCH_EXACT_SAMPLES`, so small histories report the same nearest-rank
+/// values as [`percentile`]. Past that it folds them into DDSketch bins: bin
+/// `i` counts samples in `(γ^(i-1), γ^i]` with `γ = (1+α)/(1-α)`, and a
+/// percentile is the midpoint estimate of the bin holding its rank, clamped
+/// to the observed range. A minute-to-hour latency range needs a few hundred
+/// bins however many samples arrive. Sketches merge losslessly.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LatencySketch {
+    exact: Vec<u32>,
+    /// Bin index → count, once the sketch left exact mode
+    bins: BTreeMap<i32, u64>,
+    /// Samples of 0ms, which have no logarithm
+    zeros: u64,
+    count: u64,
+    min: u32,
+    max: u32,
+}
+
+impl Default for LatencySketch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LatencySketch {
+    pub fn new() -> Self {
+        Self {
+            exact: Vec::new(),
+            bins: BTreeMap::new(),
+            zeros: 0,
+            count: 0,
+            min: u32::MAX,
+            max: 0,
+        }
+    }
+
+    fn gamma() -> f64 {
+        (1.0 + SKETCH_RELATIVE_ACCURACY) / (1.0 - SKETCH_RELATIVE_ACCURACY)
+    }
+
+    /// Whether percentiles are still exact (at most `SKETCH_EXACT_SAMPLES` samples)
+    pub fn is_exact(&self) -> bool {
+        self.count as usize <= SKETCH_EXACT_SAMPLES && self.bins.is_empty() && self.zeros == 0
+    }
+
+    fn bin(&mut self, sample: u32, times: u64) {
+        if sample == 0 {
+            self.zeros += times;
+        } else {
+            let index = (sample as f64).ln() / Self::gamma().ln();
+            *self.bins.entry(index.ceil() as i32).or_default() += times;
+        }
+    }
+
+    /// Move the exact samples into bins
+    fn spill(&mut self) {
+        for sample in std::mem::take(&mut self.exact) {
+            self.bin(sample, 1);
+        }
+    }
+
+    pub fn add(&mut self, sample: u32) {
+        self.count += 1;
+        self.min = self.min.min(sample);
+        self.max = self.max.max(sample);
+        if self.is_exact() {
+            self.exact.push(sample);
+        } else {
+            self.spill();
+            self.bin(sample, 1);
+        }
+    }
+
+    /// Fold `other` into this sketch
+    pub fn merge(&mut self, other: &LatencySketch) {
+        if other.count == 0 {
+            return;
+        }
+        self.count += other.count;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        if self.is_exact() && other.is_exact() {
+            self.exact.extend_from_slice(&other.exact);
+            return;
+        }
+        self.spill();
+        for &sample in &other.exact {
+            self.bin(sample, 1);
+        }
+        for (&index, &times) in &other.bins {
+            *self.bins.entry(index).or_default() += times;
+        }
+        self.zeros += other.zeros;
+    }
+
+    /// Number of samples added
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Nearest-rank percentile, `p` in `0.0..=1.0`; 0 when empty
+    pub fn percentile(&self, p: f64) -> u32 {
+        if self.count == 0 {
+            return 0;
+        }
+        if self.is_exact() {
+            return percentile(&self.exact, p);
+        }
+
+        let rank = ((p.clamp(0.0, 1.0) * self.count as f64).ceil() as u64).max(1);
+        if rank <= self.zeros {
+            return 0;
+        }
+        let gamma = Self::gamma();
+        let mut seen = self.zeros;
+        for (&index, &times) in &self.bins {
+            seen += times;
+            if seen >= rank {
+                let estimate = 2.0 * gamma.powi(index) / (gamma + 1.0);
+                return (estimate.round() as u32).clamp(self.min, self.max);
+            }
+        }
+        self.max
+    }
+}
+
+impl FromIterator<u32> for LatencySketch {
+    fn from_iter<I: IntoIterator<Item = u32>>(samples: I) -> Self {
+        let mut sketch = Self::new();
+        for sample in samples {
+            sketch.add(sample);
+        }
+        sketch
+    }
+}
+
+/// Exponentially weighted moving average, newest sample weighted by `alpha`
+///
+/// `alpha` is clamped to `0.0..=1.0`; `None` for no samples.
+pub fn ewma(samples: &[u32], alpha: f64) -> Option<f64> {
+    let alpha = alpha.clamp(0.0, 1.0);
+    let (first, rest) = samples.split_first()?;
+    Some(rest.iter().fold(*first as f64, |average, &sample| {
+        alpha * sample as f64 + (1.0 - alpha) * average
+    }))
+}
+
+/// Change from `previous` to `current` in percent; `0.0` when `previous` is zero
+pub fn percent_change(previous: f64, current: f64) -> f64 {
+    if previous == 0.0 {
+        0.0
+    } else {
+        (current - previous) * 100.0 / previous
+    }
+}
+
+/// Direction latency is moving in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrendDirection {
+    Rising,
+    Falling,
+    Steady,
+}
+
+impl TrendDirection {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TrendDirection::Rising => "rising",
+            TrendDirection::Falling => "falling",
+            TrendDirection::Steady => "steady",
+        }
+    }
+}
+
+/// Latency trend: median of the newer half of a window against the older half
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Trend {
+    pub direction: TrendDirection,
+    pub change_percent: f64,
+}
+
+/// Trend of `samples`; `None` below `MIN_TREND_SAMPLES`
+///
+/// Changes within `threshold_percent` either way count as steady.
+pub fn trend(samples: &[u32], threshold_percent: f64) -> Option<Trend> {
+    if samples.len() < MIN_TREND_SAMPLES {
+        return None;
+    }
+    let (older, newer) = samples.split_at(samples.len() / 2);
+    let change_percent =
+        percent_change(percentile(older, 0.5) as f64, percentile(newer, 0.5) as f64);
+
+    let direction = if change_percent > threshold_percent {
+        TrendDirection::Rising
+    } else if change_percent < -threshold_percent {
+        TrendDirection::Falling
+    } else {
+        TrendDirection::Steady
+    };
+    Some(Trend {
+        direction,
+        change_percent,
+    })
+}
+
+/// Sample counts per latency bucket
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Histogram {
+    /// Ascending inclusive upper bounds
+    pub bounds: Vec<u32>,
+    /// One count per bound plus a last one for samples above every bound
+    pub counts: Vec<usize>,
+}
+
+impl Histogram {
+    /// Bucket `samples` by `bounds`, which are sorted and deduplicated first
+    pub fn new(bounds: &[u32], samples: &[u32]) -> Self {
+        let mut bounds = bounds.to_vec();
+        bounds.sort_unstable();
+        bounds.dedup();
+
+        let mut counts = vec![0; bounds.len() + 1];
+        for &sample in samples {
+            counts[bounds.partition_point(|&bound| bound < sample)] += 1;
+        }
+        Self { bounds, counts }
+    }
+
+    /// Number of samples bucketed
+    pub fn total(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    /// `≤500ms:3 ≤1000ms:5 >5000ms:1`, empty buckets left out
+    pub fn render(&self) -> String {
+        let labels = self
+            .bounds
+            .iter()
+            .map(|bound| format!("≤{}ms", bound))
+            .chain(self.bounds.last().map(|bound| format!(">{}ms", bound)));
+        labels
+            .zip(&self.counts)
+            .filter(|(_, &count)| count > 0)
+            .map(|(label, count)| format!("{}:{}", label, count))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
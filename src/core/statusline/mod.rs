@@ -0,0 +1,910 @@
+pub mod responsive;
+pub mod themes;
+
+use crate::config::{AnsiColor, Config, SegmentConfig, SegmentId, StyleMode};
+use crate::core::color::ColorLevel;
+use crate::core::layout::LayoutTemplate;
+use crate::core::segments::SegmentData;
+#[cfg(feature = "tui")]
+use responsive::visible_width;
+use themes::ColorScheme;
+
+#[cfg(feature = "network-monitoring")]
+use crate::core::network::types::UptimeWindow;
+#[cfg(feature = "network-monitoring")]
+use crate::core::network::StatuslineInput;
+#[cfg(feature = "network-monitoring")]
+use crate::core::segments::NetworkSegmentWrapper;
+
+pub struct StatusLineGenerator {
+    config: Config,
+    /// Built-in scheme named by `config.theme`, if any
+    scheme: Option<ColorScheme>,
+    /// Available terminal width; wider lines are shrunk (see `responsive`)
+    max_width: Option<usize>,
+    /// Color depth colors are downgraded to (see `core::color`)
+    color_level: ColorLevel,
+}
+
+impl StatusLineGenerator {
+    pub fn new(config: Config) -> Self {
+        let scheme = ColorScheme::from_name(&config.theme);
+        Self {
+            config,
+            scheme,
+            max_width: None,
+            color_level: ColorLevel::TrueColor,
+        }
+    }
+
+    /// Shrink lines to fit `width` columns instead of letting them wrap (`None` = no limit)
+    pub fn with_max_width(mut self, width: Option<usize>) -> Self {
+        self.max_width = width;
+        self
+    }
+
+    /// Downgrade colors to what the terminal can show (`ColorLevel::None` = no escapes)
+    pub fn with_color_level(mut self, level: ColorLevel) -> Self {
+        self.color_level = level;
+        self
+    }
+
+    /// Keep enabled segments, with colors resolved against the scheme and overrides
+    /// and downgraded to the color level
+    fn enabled_segments(
+        &self,
+        segments: Vec<(SegmentConfig, SegmentData)>,
+    ) -> Vec<(SegmentConfig, SegmentData)> {
+        segments
+            .into_iter()
+            .filter(|(config, _)| config.enabled)
+            .map(|(mut config, data)| {
+                themes::resolve_colors(&self.config, self.scheme, &mut config);
+                let colors = &mut config.colors;
+                for color in [&mut colors.icon, &mut colors.text, &mut colors.background] {
+                    *color = color.as_ref().and_then(|c| self.color_level.downgrade(c));
+                }
+                (config, data)
+            })
+            .collect()
+    }
+
+    /// Plain-text separator, colored by the scheme (white without one)
+    fn colored_separator(&self) -> String {
+        let color = match self.scheme {
+            Some(scheme) => scheme.separator_color(),
+            None => Some(AnsiColor::Color16 { c16: 7 }),
+        };
+        let color = color.and_then(|c| self.color_level.downgrade(&c));
+        self.apply_color(&self.config.style.separator, color.as_ref())
+    }
+
+    pub fn generate(&self, segments: Vec<(SegmentConfig, SegmentData)>) -> String {
+        let enabled_segments = self.enabled_segments(segments);
+
+        if enabled_segments.is_empty() {
+            return String::new();
+        }
+
+        match self.max_width {
+            Some(max_width) => self.fit_to_width(enabled_segments, max_width),
+            None => self.render_lines(&enabled_segments),
+        }
+    }
+
+    /// Each enabled segment rendered on its own (no separators or width limit), in order
+    pub fn render_segments(
+        &self,
+        segments: Vec<(SegmentConfig, SegmentData)>,
+    ) -> Vec<(SegmentId, String)> {
+        self.enabled_segments(segments)
+            .iter()
+            .map(|(config, data)| (config.id, self.render_segment(config, data)))
+            .collect()
+    }
+
+    /// Re-render with progressively shrunk segments until every line fits `max_width`
+    fn fit_to_width(
+        &self,
+        mut segments: Vec<(SegmentConfig, SegmentData)>,
+        max_width: usize,
+    ) -> String {
+        loop {
+            let output = self.render_lines(&segments);
+            let overflowing: Vec<&str> = output
+                .lines()
+                .filter(|line| responsive::visible_width(line) > max_width)
+                .collect();
+            if overflowing.is_empty() {
+                return output;
+            }
+
+            // Segments rendered on an overflowing line (a segment may span lines)
+            let crowded: Vec<usize> = segments
+                .iter()
+                .enumerate()
+                .filter(|(_, (config, data))| {
+                    self.render_segment(config, data)
+                        .lines()
+                        .filter(|part| !part.is_empty())
+                        .any(|part| overflowing.iter().any(|line| line.contains(part)))
+                })
+                .map(|(index, _)| index)
+                .collect();
+
+            if !Self::shrink_step(&mut segments, &crowded) {
+                return output;
+            }
+        }
+    }
+
+    /// Apply the cheapest remaining shrink to the `crowded` segments; `false` when none is left
+    fn shrink_step(segments: &mut Vec<(SegmentConfig, SegmentData)>, crowded: &[usize]) -> bool {
+        #[cfg(feature = "network-monitoring")]
+        for &index in crowded {
+            let (config, data) = &mut segments[index];
+            if config.id == SegmentId::Network {
+                let compressed = responsive::compress_breakdown(&data.primary);
+                if compressed != data.primary {
+                    data.primary = compressed;
+                    return true;
+                }
+            }
+        }
+
+        for &index in crowded {
+            let (config, data) = &mut segments[index];
+            if config.id == SegmentId::Directory {
+                let abbreviated =
+                    responsive::abbreviate_path(&data.primary, responsive::ABBREVIATED_DIR_LEN);
+                if abbreviated != data.primary {
+                    data.primary = abbreviated;
+                    return true;
+                }
+            }
+        }
+
+        // Never drop the last segment of a line
+        if crowded.len() < 2 {
+            return false;
+        }
+        let Some(lowest) = crowded
+            .iter()
+            .copied()
+            .min_by_key(|&index| responsive::drop_priority(segments[index].0.id))
+        else {
+            return false;
+        };
+        segments.remove(lowest);
+        true
+    }
+
+    /// Render all lines: the layout template when set, else non-network over network segments
+    fn render_lines(&self, segments: &[(SegmentConfig, SegmentData)]) -> String {
+        // A layout template replaces the fixed ordering below
+        if let Some(ref template) = self.config.template {
+            return self.render_template(&LayoutTemplate::parse(template), segments);
+        }
+
+        // Separate network segments from other segments
+        let (network_segments, other_segments): (Vec<_>, Vec<_>) =
+            segments.iter().cloned().partition(|(config, _)| {
+                #[cfg(feature = "network-monitoring")]
+                return matches!(config.id, crate::config::SegmentId::Network);
+                #[cfg(not(feature = "network-monitoring"))]
+                false
+            });
+
+        let mut lines = Vec::new();
+
+        // Render first line (non-network segments)
+        if let Some(line) = self.render_segments_line(&other_segments) {
+            lines.push(line);
+        }
+
+        // Render second line (network segments)
+        if let Some(line) = self.render_segments_line(&network_segments) {
+            lines.push(line);
+        }
+
+        lines.join("\n")
+    }
+
+    /// Render segments into the template's slots; segments it doesn't name are dropped
+    fn render_template(
+        &self,
+        template: &LayoutTemplate,
+        segments: &[(SegmentConfig, SegmentData)],
+    ) -> String {
+        // Only custom segments share an id; they render side by side
+        template.render(|id| {
+            segments
+                .iter()
+                .filter(|(config, _)| config.id == id)
+                .map(|(config, data)| self.render_segment(config, data))
+                .filter(|rendered| !rendered.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+    }
+
+    /// Helper method to render a line of segments, eliminating code duplication
+    fn render_segments_line(&self, segments: &[(SegmentConfig, SegmentData)]) -> Option<String> {
+        if segments.is_empty() {
+            return None;
+        }
+
+        let mut output = Vec::new();
+        for (config, data) in segments.iter() {
+            let rendered = self.render_segment(config, data);
+            if !rendered.is_empty() {
+                output.push(rendered);
+            }
+        }
+
+        if output.is_empty() {
+            return None;
+        }
+
+        let line = if self.config.style.separator == "\u{e0b0}" {
+            self.join_with_powerline_arrows(&output, segments)
+        } else {
+            self.join_with_separators(&output)
+        };
+
+        Some(line)
+    }
+
+    /// Generate statusline for TUI preview with proper width calculation
+    /// This method handles ANSI escape sequences properly for ratatui rendering
+    #[cfg(feature = "tui")]
+    pub fn generate_for_tui(
+        &self,
+        segments: Vec<(SegmentConfig, SegmentData)>,
+    ) -> ratatui::text::Line<'static> {
+        use ansi_to_tui::IntoText;
+        use ratatui::text::{Line, Span};
+
+        // Use the same generate method and convert to TUI
+        let full_output = self.generate(segments);
+
+        if let Ok(text) = full_output.into_text() {
+            if let Some(line) = text.lines.into_iter().next() {
+                return line;
+            }
+        }
+
+        // Fallback to raw text
+        Line::from(vec![Span::raw(full_output)])
+    }
+
+    /// Generate TUI-optimized text with intelligent wrapping by segment for preview
+    #[cfg(feature = "tui")]
+    pub fn generate_for_tui_preview(
+        &self,
+        segments: Vec<(SegmentConfig, SegmentData)>,
+        max_width: u16,
+    ) -> ratatui::text::Text<'_> {
+        use ansi_to_tui::IntoText;
+        use ratatui::text::{Line, Span, Text};
+
+        let enabled_segments = self.enabled_segments(segments);
+
+        if enabled_segments.is_empty() {
+            return Text::from(vec![Line::default()]);
+        }
+
+        // Render each segment individually
+        let mut rendered_segments = Vec::new();
+        let mut segment_configs = Vec::new();
+
+        for (config, data) in &enabled_segments {
+            let rendered = self.render_segment(config, data);
+            if !rendered.is_empty() {
+                rendered_segments.push(rendered);
+                segment_configs.push(config.clone());
+            }
+        }
+
+        if rendered_segments.is_empty() {
+            return Text::from(vec![Line::default()]);
+        }
+
+        // Pre-calculate separators between segments
+        let mut separators = Vec::new();
+        for i in 0..rendered_segments.len().saturating_sub(1) {
+            let separator = if self.config.style.separator == "\u{e0b0}" {
+                // Powerline arrows with color transition
+                let prev_bg = segment_configs
+                    .get(i)
+                    .and_then(|config| config.colors.background.as_ref());
+                let curr_bg = segment_configs
+                    .get(i + 1)
+                    .and_then(|config| config.colors.background.as_ref());
+                self.create_powerline_arrow(prev_bg, curr_bg)
+            } else {
+                // Regular separators in the scheme's separator color
+                self.colored_separator()
+            };
+            separators.push(separator);
+        }
+
+        // Intelligent line wrapping by segment
+        let mut lines: Vec<String> = Vec::new();
+        let mut current_line = String::new();
+        let mut current_width = 0usize;
+        let max_w = max_width as usize;
+
+        for i in 0..rendered_segments.len() {
+            let segment = &rendered_segments[i];
+            let segment_width = visible_width(segment);
+
+            // Check if adding this segment would exceed max_width
+            if current_width > 0 && current_width + segment_width > max_w {
+                // Current line would overflow, start a new line
+                lines.push(current_line.clone());
+                current_line.clear();
+                current_width = 0;
+            }
+
+            // Add the segment to current line
+            current_line.push_str(segment);
+            current_width += segment_width;
+
+            // Handle separator if not the last segment
+            if i < separators.len() {
+                let separator = &separators[i];
+                let separator_width = visible_width(separator);
+
+                // Check if next segment exists
+                if i + 1 < rendered_segments.len() {
+                    let next_segment = &rendered_segments[i + 1];
+                    let next_width = visible_width(next_segment);
+
+                    // Check if separator AND next segment both fit
+                    if current_width + separator_width + next_width <= max_w {
+                        // Both fit, add separator and continue on same line
+                        current_line.push_str(separator);
+                        current_width += separator_width;
+                    } else {
+                        // Separator and/or next segment don't fit
+                        // Don't add separator, just break line
+                        lines.push(current_line.clone());
+                        current_line.clear();
+                        current_width = 0;
+                    }
+                }
+            }
+        }
+
+        // Add the last line if it's not empty
+        if !current_line.is_empty() {
+            lines.push(current_line);
+        }
+
+        // Convert string lines to ratatui Text
+        let mut tui_lines = Vec::new();
+        for line in lines {
+            if let Ok(text) = line.into_text() {
+                for tui_line in text.lines {
+                    tui_lines.push(tui_line);
+                }
+            } else {
+                tui_lines.push(Line::from(vec![Span::raw(line)]));
+            }
+        }
+
+        // Ensure we have at least one line
+        if tui_lines.is_empty() {
+            tui_lines.push(Line::default());
+        }
+
+        Text::from(tui_lines)
+    }
+
+    fn render_segment(&self, config: &SegmentConfig, data: &SegmentData) -> String {
+        let icon = self.get_icon(config);
+
+        // Apply background color to the entire segment if set
+        if let Some(bg_color) = &config.colors.background {
+            let bg_code = self.apply_background_color(bg_color);
+
+            // Build the entire segment content first
+            let icon_colored = if let Some(icon_color) = &config.colors.icon {
+                self.apply_color(&icon, Some(icon_color))
+                    .replace("\x1b[0m", "")
+            } else {
+                icon.clone()
+            };
+
+            let text_styled = self
+                .apply_style(
+                    &data.primary,
+                    config.colors.text.as_ref(),
+                    config.styles.text_bold,
+                )
+                .replace("\x1b[0m", "");
+
+            let mut segment_content = format!(" {} {} ", icon_colored, text_styled);
+
+            if !data.secondary.is_empty() {
+                let secondary_styled = self
+                    .apply_style(
+                        &data.secondary,
+                        config.colors.text.as_ref(),
+                        config.styles.text_bold,
+                    )
+                    .replace("\x1b[0m", "");
+                segment_content.push_str(&format!("{} ", secondary_styled));
+            }
+
+            // Apply background to the entire content and reset at the end
+            format!("{}{}\x1b[49m", bg_code, segment_content)
+        } else {
+            // No background color, use original logic
+            let icon_colored = self.apply_color(&icon, config.colors.icon.as_ref());
+            let text_styled = self.apply_style(
+                &data.primary,
+                config.colors.text.as_ref(),
+                config.styles.text_bold,
+            );
+
+            let mut segment = format!("{} {}", icon_colored, text_styled);
+
+            if !data.secondary.is_empty() {
+                segment.push_str(&format!(
+                    " {}",
+                    self.apply_style(
+                        &data.secondary,
+                        config.colors.text.as_ref(),
+                        config.styles.text_bold
+                    )
+                ));
+            }
+
+            segment
+        }
+    }
+
+    fn get_icon(&self, config: &SegmentConfig) -> String {
+        match self.config.style.mode {
+            StyleMode::Plain => config.icon.plain.clone(),
+            StyleMode::NerdFont => config.icon.nerd_font.clone(),
+            StyleMode::Powerline => config.icon.nerd_font.clone(), // Future: use Powerline icons
+        }
+    }
+
+    fn apply_color(&self, text: &str, color: Option<&AnsiColor>) -> String {
+        match color {
+            Some(AnsiColor::Color16 { c16 }) => {
+                let code = if *c16 < 8 { 30 + c16 } else { 90 + (c16 - 8) };
+                format!("\x1b[{}m{}\x1b[0m", code, text)
+            }
+            Some(AnsiColor::Color256 { c256 }) => {
+                format!("\x1b[38;5;{}m{}\x1b[0m", c256, text)
+            }
+            Some(AnsiColor::Rgb { r, g, b }) => {
+                format!("\x1b[38;2;{};{};{}m{}\x1b[0m", r, g, b, text)
+            }
+            None => text.to_string(),
+        }
+    }
+
+    fn apply_style(&self, text: &str, color: Option<&AnsiColor>, bold: bool) -> String {
+        let mut codes = Vec::new();
+
+        // Add style codes
+        if bold && self.color_level.has_color() {
+            codes.push("1".to_string()); // Bold: \x1b[1m
+        }
+
+        // Add color codes
+        match color {
+            Some(AnsiColor::Color16 { c16 }) => {
+                let color_code = if *c16 < 8 { 30 + c16 } else { 90 + (c16 - 8) };
+                codes.push(color_code.to_string());
+            }
+            Some(AnsiColor::Color256 { c256 }) => {
+                codes.push("38".to_string());
+                codes.push("5".to_string());
+                codes.push(c256.to_string());
+            }
+            Some(AnsiColor::Rgb { r, g, b }) => {
+                codes.push("38".to_string());
+                codes.push("2".to_string());
+                codes.push(r.to_string());
+                codes.push(g.to_string());
+                codes.push(b.to_string());
+            }
+            None => {}
+        }
+
+        if codes.is_empty() {
+            text.to_string()
+        } else {
+            format!("\x1b[{}m{}\x1b[0m", codes.join(";"), text)
+        }
+    }
+
+    fn apply_background_color(&self, color: &AnsiColor) -> String {
+        match color {
+            AnsiColor::Color16 { c16 } => {
+                let code = if *c16 < 8 { 40 + c16 } else { 100 + (c16 - 8) };
+                format!("\x1b[{}m", code)
+            }
+            AnsiColor::Color256 { c256 } => {
+                format!("\x1b[48;5;{}m", c256)
+            }
+            AnsiColor::Rgb { r, g, b } => {
+                format!("\x1b[48;2;{};{};{}m", r, g, b)
+            }
+        }
+    }
+
+    /// Join segments with plain separators (non-Powerline)
+    fn join_with_separators(&self, rendered_segments: &[String]) -> String {
+        if rendered_segments.is_empty() {
+            return String::new();
+        }
+
+        rendered_segments.join(&self.colored_separator())
+    }
+
+    /// Join segments with Powerline arrow separators with proper color transitions
+    fn join_with_powerline_arrows(
+        &self,
+        rendered_segments: &[String],
+        segment_configs: &[(SegmentConfig, SegmentData)],
+    ) -> String {
+        if rendered_segments.is_empty() {
+            return String::new();
+        }
+
+        if rendered_segments.len() == 1 {
+            return rendered_segments[0].clone();
+        }
+
+        let mut result = rendered_segments[0].clone();
+
+        for (i, _) in rendered_segments.iter().enumerate().skip(1) {
+            let prev_bg = segment_configs
+                .get(i - 1)
+                .and_then(|(config, _)| config.colors.background.as_ref());
+            let curr_bg = segment_configs
+                .get(i)
+                .and_then(|(config, _)| config.colors.background.as_ref());
+
+            // Create Powerline arrow with color transition
+            let arrow = self.create_powerline_arrow(prev_bg, curr_bg);
+
+            result.push_str(&arrow);
+            result.push_str(&rendered_segments[i]);
+        }
+
+        // Reset colors at the end
+        if self.color_level.has_color() {
+            result.push_str("\x1b[0m");
+        }
+        result
+    }
+
+    /// Create a Powerline arrow with proper color transition
+    fn create_powerline_arrow(
+        &self,
+        prev_bg: Option<&AnsiColor>,
+        curr_bg: Option<&AnsiColor>,
+    ) -> String {
+        let arrow_char = "\u{e0b0}";
+
+        match (prev_bg, curr_bg) {
+            (Some(prev), Some(curr)) => {
+                // Arrow foreground = previous segment's background
+                // Arrow background = current segment's background
+                let fg_code = self.color_to_foreground_code(prev);
+                let bg_code = self.apply_background_color(curr);
+                format!("{}{}{}\x1b[0m", bg_code, fg_code, arrow_char)
+            }
+            (Some(prev), None) => {
+                // Previous segment has background, current doesn't
+                let fg_code = self.color_to_foreground_code(prev);
+                format!("{}{}\x1b[0m", fg_code, arrow_char)
+            }
+            (None, Some(curr)) => {
+                // Current segment has background, previous doesn't
+                let bg_code = self.apply_background_color(curr);
+                format!("{}{}\x1b[0m", bg_code, arrow_char)
+            }
+            (None, None) => {
+                // Neither segment has background color
+                arrow_char.to_string()
+            }
+        }
+    }
+
+    /// Convert AnsiColor to foreground color code
+    fn color_to_foreground_code(&self, color: &AnsiColor) -> String {
+        match color {
+            AnsiColor::Color16 { c16 } => {
+                let code = if *c16 < 8 { 30 + c16 } else { 90 + (c16 - 8) };
+                format!("\x1b[{}m", code)
+            }
+            AnsiColor::Color256 { c256 } => {
+                format!("\x1b[38;5;{}m", c256)
+            }
+            AnsiColor::Rgb { r, g, b } => {
+                format!("\x1b[38;2;{};{};{}m", r, g, b)
+            }
+        }
+    }
+}
+
+pub async fn collect_all_segments(
+    config: &Config,
+    input: &crate::config::InputData,
+    #[cfg(feature = "network-monitoring")] full_input: Option<&StatuslineInput>,
+    #[cfg(not(feature = "network-monitoring"))] _full_input: Option<&()>,
+) -> Vec<(SegmentConfig, SegmentData)> {
+    use crate::core::segments::health::{
+        collect_isolated, failure_placeholder, segment_name, DEFAULT_SEGMENT_TIMEOUT,
+    };
+    use crate::core::segments::*;
+
+    let mut results = Vec::new();
+    let health_path = SegmentHealthMap::default_path();
+    let mut health = health_path
+        .as_deref()
+        .map(SegmentHealthMap::load)
+        .unwrap_or_default();
+    let mut health_changed = false;
+    let capabilities = crate::core::capabilities::Capabilities::current();
+    let terminal_hyperlinks = capabilities.hyperlinks;
+    let icons = crate::core::icons::IconSet::for_terminal(&config.style, capabilities.emoji);
+    let locale = crate::core::i18n::Locale::for_config(config.locale);
+    let attention = crate::core::attention::AttentionStyle::for_config(config.attention);
+    let perf = crate::core::perf::PerfRecorder::global();
+    let layout = config.template.as_deref().map(LayoutTemplate::parse);
+
+    // External commands start first so they overlap with the built-in segments
+    let custom_segments: Vec<PendingCustomSegment> = if layout
+        .as_ref()
+        .is_none_or(|layout| layout.segment_ids().contains(&SegmentId::Custom))
+    {
+        config
+            .custom_segments
+            .iter()
+            .map(|entry| PendingCustomSegment::spawn(entry, &input.workspace.current_dir))
+            .collect()
+    } else {
+        Vec::new()
+    };
+    // `[[segments]]` index of each result, `None` for custom segments
+    let mut sources: Vec<Option<usize>> = Vec::new();
+
+    for (index, segment_config) in config.segments.iter().enumerate() {
+        // Segments a layout template leaves out are never collected
+        if let Some(ref layout) = layout {
+            if !layout.segment_ids().contains(&segment_config.id) {
+                continue;
+            }
+        }
+
+        let timeout = segment_config
+            .options
+            .get("timeout_ms")
+            .and_then(|v| v.as_u64())
+            .map(std::time::Duration::from_millis);
+        let hyperlinks = terminal_hyperlinks
+            && segment_config
+                .options
+                .get("hyperlinks")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+
+        let segment: Box<dyn Segment + Send> = match segment_config.id {
+            crate::config::SegmentId::Model => {
+                Box::new(ModelSegment::from_options(&segment_config.options))
+            }
+            crate::config::SegmentId::Directory => {
+                let show_remote = segment_config
+                    .options
+                    .get("show_remote")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(true);
+                let remote = if show_remote {
+                    RemoteContext::detect()
+                } else {
+                    None
+                };
+                Box::new(
+                    DirectorySegment::from_options(&segment_config.options)
+                        .with_remote_context(remote),
+                )
+            }
+            crate::config::SegmentId::Git => {
+                let show_sha = segment_config
+                    .options
+                    .get("show_sha")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
+                Box::new(GitSegment::new().with_sha(show_sha).with_icons(icons))
+            }
+            crate::config::SegmentId::Usage => Box::new(UsageSegment::new()),
+            crate::config::SegmentId::Update => Box::new(
+                UpdateSegment::new()
+                    .with_hyperlinks(hyperlinks)
+                    .with_icons(icons)
+                    .with_locale(locale)
+                    .with_attention(attention),
+            ),
+            crate::config::SegmentId::Stopwatch => Box::new(StopwatchSegment::new()),
+            crate::config::SegmentId::Cost => {
+                let segment = CostSegment::new()
+                    .with_prices(cost::PriceTable::from_options(&segment_config.options))
+                    .with_number_format(config.formatting.clone());
+                #[cfg(feature = "network-monitoring")]
+                let segment =
+                    segment.with_reported_usd(full_input.map(|input| input.cost.total_cost_usd));
+                Box::new(segment)
+            }
+            crate::config::SegmentId::Block => Box::new(BlockSegment::new().with_locale(locale)),
+            crate::config::SegmentId::Project => Box::new(
+                ProjectSegment::new()
+                    .with_prices(cost::PriceTable::from_options(&segment_config.options))
+                    .with_number_format(config.formatting.clone()),
+            ),
+            crate::config::SegmentId::Quota => Box::new(
+                QuotaSegment::from_options(&segment_config.options)
+                    .with_number_format(config.formatting.clone()),
+            ),
+            crate::config::SegmentId::DiffStat => Box::new(DiffStatSegment::new()),
+            crate::config::SegmentId::Mode => Box::new(ModeSegment::new()),
+            crate::config::SegmentId::Host => {
+                Box::new(HostSegment::from_options(&segment_config.options))
+            }
+            crate::config::SegmentId::Env => {
+                Box::new(EnvSegment::from_options(&segment_config.options))
+            }
+            crate::config::SegmentId::Version => {
+                let segment = VersionSegment::new()
+                    .with_latest_version(crate::config::RemoteFlags::load().manifest_version);
+                #[cfg(feature = "network-monitoring")]
+                let segment =
+                    segment.with_client_version(full_input.map(|input| input.version.clone()));
+                Box::new(segment)
+            }
+            crate::config::SegmentId::Compact => {
+                Box::new(CompactSegment::from_options(&segment_config.options).with_locale(locale))
+            }
+            crate::config::SegmentId::BurnRate => Box::new(
+                BurnRateSegment::from_options(&segment_config.options)
+                    .with_number_format(config.formatting.clone()),
+            ),
+            // Entries come from `[[custom_segments]]`, collected below
+            crate::config::SegmentId::Custom => continue,
+            #[cfg(feature = "network-monitoring")]
+            crate::config::SegmentId::Network => {
+                use crate::core::segments::health::{
+                    collect_isolated_async, DEFAULT_NETWORK_SEGMENT_TIMEOUT,
+                };
+
+                let Some(full_input) = full_input else {
+                    continue;
+                };
+                let budget = timeout.unwrap_or(DEFAULT_NETWORK_SEGMENT_TIMEOUT);
+                let started = std::time::Instant::now();
+                let outcome = collect_isolated_async(
+                    async {
+                        match NetworkSegmentWrapper::new() {
+                            Ok(wrapper) => {
+                                let mut wrapper = wrapper
+                                    .with_config(&config.network)
+                                    .with_proxy(crate::config::ProxyPolicy::from_config(config))
+                                    .with_alerts(&config.alert_routes())
+                                    .with_flags(crate::config::FlagSet::load(&config.flags))
+                                    .with_hyperlinks(hyperlinks)
+                                    .with_icons(icons)
+                                    .with_locale(locale)
+                                    .with_number_format(config.formatting.clone())
+                                    .with_attention(attention)
+                                    .with_uptime_badge(
+                                        segment_config
+                                            .options
+                                            .get("uptime")
+                                            .and_then(|v| v.as_str())
+                                            .and_then(UptimeWindow::parse),
+                                    )
+                                    .with_budget(budget);
+                                wrapper.collect_with_full_input(full_input).await
+                            }
+                            Err(_) => None,
+                        }
+                    },
+                    budget,
+                )
+                .await;
+                perf.record(
+                    &format!("segment.{}", segment_name(segment_config.id)),
+                    started.elapsed(),
+                );
+
+                health_changed |= health.record(
+                    &segment_name(segment_config.id),
+                    outcome.as_ref().map(|_| ()),
+                );
+                let data = outcome.unwrap_or_else(|failure| {
+                    Some(failure_placeholder(segment_config.id, &failure, icons))
+                });
+                if let Some(data) = data {
+                    // A burning error budget recolors the segment like burn rate warnings
+                    results.push((burn_rate::level_colors(segment_config, &data), data));
+                    sources.push(Some(index));
+                }
+                continue;
+            }
+        };
+
+        let outcome = perf.time(
+            &format!("segment.{}", segment_name(segment_config.id)),
+            || {
+                collect_isolated(
+                    segment,
+                    input.clone(),
+                    timeout.unwrap_or(DEFAULT_SEGMENT_TIMEOUT),
+                )
+            },
+        );
+        health_changed |= health.record(
+            &segment_name(segment_config.id),
+            outcome.as_ref().map(|_| ()),
+        );
+        let data = outcome.unwrap_or_else(|failure| {
+            Some(failure_placeholder(segment_config.id, &failure, icons))
+        });
+        if let Some(data) = data {
+            let segment_config = match segment_config.id {
+                SegmentId::BurnRate | SegmentId::Quota | SegmentId::Compact => {
+                    burn_rate::level_colors(segment_config, &data)
+                }
+                SegmentId::Mode => mode::mode_colors(segment_config, &data),
+                SegmentId::Version => version::outdated_colors(segment_config, &data),
+                _ => segment_config.clone(),
+            };
+            results.push((segment_config, data));
+            sources.push(Some(index));
+        }
+    }
+
+    for pending in &custom_segments {
+        // Commands run concurrently; this is the time spent still waiting on each
+        let outcome = perf.time(&format!("custom.{}", pending.config().name), || {
+            pending.wait()
+        });
+        health_changed |= health.record(&pending.health_key(), outcome.as_ref().map(|_| ()));
+        let data = match outcome {
+            Ok(Some(output)) => pending.segment_data(output),
+            Ok(None) => continue,
+            Err(failure) => pending.failure_placeholder(&failure, icons),
+        };
+
+        // Before the first built-in at or after `position`, behind earlier custom entries
+        let at = pending
+            .config()
+            .position
+            .and_then(|position| {
+                sources
+                    .iter()
+                    .position(|source| source.is_some_and(|index| index >= position))
+            })
+            .unwrap_or(results.len());
+        results.insert(at, (pending.segment_config(), data));
+        sources.insert(at, None);
+    }
+
+    if health_changed {
+        if let Some(path) = &health_path {
+            let _ = health.save(path);
+        }
+    }
+
+    results
+}
@@ -0,0 +1,80 @@
+//! Terminal-width-aware shrinking of the rendered statusline
+//!
+//! When the available width is known (`terminal_width` in Claude Code's input,
+//! otherwise `COLUMNS`), lines wider than it are shrunk step by step instead of
+//! wrapping, cheapest loss first:
+//!
+//! 1. the network timing breakdown is compressed to its `Total:` part
+//! 2. the directory name is abbreviated
+//! 3. the lowest-priority segment on the line is dropped
+//!    (update and custom, stopwatch, usage, git, directory, model, network)
+//!
+//! A line always keeps at least one segment; an unknown width leaves the output untouched.
+
+use crate::config::SegmentId;
+use crate::core::output::strip_ansi;
+use regex::Regex;
+use std::sync::OnceLock;
+use unicode_width::UnicodeWidthStr;
+
+/// Directory names longer than this are abbreviated when a line overflows
+pub const ABBREVIATED_DIR_LEN: usize = 12;
+
+/// Available width: the statusline input's `terminal_width`, else `COLUMNS`
+pub fn available_width(input_width: Option<u16>) -> Option<usize> {
+    width_from(input_width, std::env::var("COLUMNS").ok())
+}
+
+/// `available_width` with the `COLUMNS` lookup injected; zero counts as unknown
+pub fn width_from(input_width: Option<u16>, columns: Option<String>) -> Option<usize> {
+    input_width
+        .map(usize::from)
+        .or_else(|| columns?.trim().parse().ok())
+        .filter(|&width| width > 0)
+}
+
+/// Printable width of `text` in terminal columns, excluding ANSI styling and OSC 8
+/// link targets; wide glyphs (CJK, most emoji) take two columns
+pub fn visible_width(text: &str) -> usize {
+    UnicodeWidthStr::width(strip_ansi(text).as_str())
+}
+
+/// Shrink priority of a segment: lower values are dropped first
+pub fn drop_priority(id: SegmentId) -> u8 {
+    match id {
+        SegmentId::Update | SegmentId::Version | SegmentId::Custom => 0,
+        SegmentId::Stopwatch
+        | SegmentId::Cost
+        | SegmentId::BurnRate
+        | SegmentId::Block
+        | SegmentId::Quota
+        | SegmentId::Project
+        | SegmentId::DiffStat
+        | SegmentId::Compact => 1,
+        SegmentId::Usage | SegmentId::Mode | SegmentId::Host | SegmentId::Env => 2,
+        SegmentId::Git => 3,
+        SegmentId::Directory => 4,
+        SegmentId::Model => 5,
+        #[cfg(feature = "network-monitoring")]
+        SegmentId::Network => 6,
+    }
+}
+
+/// Reduce `DNS:20ms|TCP:30ms|TLS:40ms|TTFB:1324ms|Total:2650ms` runs to `Total:2650ms`
+pub fn compress_breakdown(text: &str) -> String {
+    static BREAKDOWN: OnceLock<Regex> = OnceLock::new();
+    let breakdown = BREAKDOWN.get_or_init(|| {
+        Regex::new(r"(?:[A-Za-z]+:\d+ms\|)+(Total:\d+ms)").expect("valid breakdown pattern")
+    });
+    breakdown.replace_all(text, "$1").into_owned()
+}
+
+/// Cut `name` to `max` characters, marking the cut with `…`
+pub fn abbreviate_path(name: &str, max: usize) -> String {
+    if name.chars().count() <= max || max == 0 {
+        return name.to_string();
+    }
+    let mut abbreviated: String = name.chars().take(max - 1).collect();
+    abbreviated.push('…');
+    abbreviated
+}
@@ -0,0 +1,196 @@
+//! Built-in color schemes selected by `theme` in config.toml
+//!
+//! When `theme` names a scheme (`gruvbox`, `nord`, `solarized`, `monochrome`),
+//! its palette replaces the colors stored on each segment, so one setting
+//! recolors the whole statusline. `[colors.<segment>]` tables then override
+//! individual slots on top of the scheme (or on top of the segment's own colors
+//! when no scheme is active):
+//!
+//! ```toml
+//! theme = "solarized"
+//!
+//! [colors.git]
+//! text = { c256 = 34 }
+//! ```
+//!
+//! `monochrome` emits no color codes at all, which keeps the statusline
+//! readable on light and dark terminals alike.
+
+use crate::config::{AnsiColor, ColorConfig, Config, SegmentConfig, SegmentId};
+
+/// Built-in color scheme
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorScheme {
+    Gruvbox,
+    Nord,
+    Solarized,
+    Monochrome,
+}
+
+impl ColorScheme {
+    pub const ALL: [ColorScheme; 4] = [
+        ColorScheme::Gruvbox,
+        ColorScheme::Nord,
+        ColorScheme::Solarized,
+        ColorScheme::Monochrome,
+    ];
+
+    /// Scheme named by `theme`, if it is a built-in one
+    pub fn from_name(theme: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|scheme| scheme.name().eq_ignore_ascii_case(theme.trim()))
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ColorScheme::Gruvbox => "gruvbox",
+            ColorScheme::Nord => "nord",
+            ColorScheme::Solarized => "solarized",
+            ColorScheme::Monochrome => "monochrome",
+        }
+    }
+
+    /// Palette entry for one segment
+    pub fn segment_colors(self, id: SegmentId) -> ColorConfig {
+        match self {
+            ColorScheme::Gruvbox => gruvbox(id),
+            ColorScheme::Nord => nord(id),
+            ColorScheme::Solarized => solarized(id),
+            ColorScheme::Monochrome => ColorConfig {
+                icon: None,
+                text: None,
+                background: None,
+            },
+        }
+    }
+
+    /// Color of the plain-text separator between segments (`None` = terminal default)
+    pub fn separator_color(self) -> Option<AnsiColor> {
+        match self {
+            ColorScheme::Gruvbox => Some(AnsiColor::Color16 { c16: 7 }),
+            ColorScheme::Nord => Some(rgb(216, 222, 233)),
+            ColorScheme::Solarized => Some(rgb(88, 110, 117)),
+            ColorScheme::Monochrome => None,
+        }
+    }
+}
+
+/// Resolve `segment`'s colors in place: scheme palette first, then `[colors.<segment>]`
+pub fn resolve_colors(config: &Config, scheme: Option<ColorScheme>, segment: &mut SegmentConfig) {
+    if let Some(scheme) = scheme {
+        segment.colors = scheme.segment_colors(segment.id);
+    }
+
+    if let Some(overrides) = config.colors.get(&segment.id) {
+        if let Some(ref icon) = overrides.icon {
+            segment.colors.icon = Some(icon.clone());
+        }
+        if let Some(ref text) = overrides.text {
+            segment.colors.text = Some(text.clone());
+        }
+        if let Some(ref background) = overrides.background {
+            segment.colors.background = Some(background.clone());
+        }
+    }
+}
+
+fn rgb(r: u8, g: u8, b: u8) -> AnsiColor {
+    AnsiColor::Rgb { r, g, b }
+}
+
+fn foreground(icon: AnsiColor, text: AnsiColor) -> ColorConfig {
+    ColorConfig {
+        icon: Some(icon),
+        text: Some(text),
+        background: None,
+    }
+}
+
+fn gruvbox(id: SegmentId) -> ColorConfig {
+    let c16 = |c16| AnsiColor::Color16 { c16 };
+    match id {
+        SegmentId::Model => foreground(c16(14), c16(14)),
+        SegmentId::Directory => foreground(c16(11), c16(10)),
+        SegmentId::Git => foreground(c16(4), c16(4)),
+        SegmentId::Usage => foreground(c16(5), c16(5)),
+        SegmentId::Update => foreground(c16(11), c16(11)),
+        SegmentId::Stopwatch => foreground(c16(12), c16(12)),
+        SegmentId::Cost => foreground(c16(3), c16(3)),
+        SegmentId::BurnRate => foreground(c16(6), c16(6)),
+        SegmentId::Block => foreground(c16(14), c16(14)),
+        SegmentId::Quota => foreground(c16(3), c16(3)),
+        SegmentId::Project => foreground(c16(12), c16(12)),
+        SegmentId::DiffStat => foreground(c16(2), c16(2)),
+        SegmentId::Mode => foreground(c16(7), c16(7)),
+        SegmentId::Host => foreground(c16(13), c16(13)),
+        SegmentId::Env => foreground(c16(10), c16(10)),
+        SegmentId::Version => foreground(c16(8), c16(8)),
+        SegmentId::Compact => foreground(c16(3), c16(3)),
+        SegmentId::Custom => foreground(c16(13), c16(13)),
+        #[cfg(feature = "network-monitoring")]
+        SegmentId::Network => foreground(c16(10), c16(10)),
+    }
+}
+
+fn nord(id: SegmentId) -> ColorConfig {
+    let (icon, text, background) = match id {
+        SegmentId::Model => (rgb(191, 97, 106), rgb(191, 97, 106), rgb(76, 86, 106)),
+        SegmentId::Directory => (rgb(235, 203, 139), rgb(163, 190, 140), rgb(67, 76, 94)),
+        SegmentId::Git => (rgb(136, 192, 208), rgb(136, 192, 208), rgb(59, 66, 82)),
+        SegmentId::Usage => (rgb(46, 52, 64), rgb(46, 52, 64), rgb(180, 142, 173)),
+        SegmentId::Update => (rgb(46, 52, 64), rgb(46, 52, 64), rgb(235, 203, 139)),
+        SegmentId::Stopwatch => (rgb(129, 161, 193), rgb(129, 161, 193), rgb(59, 66, 82)),
+        SegmentId::Cost => (rgb(46, 52, 64), rgb(46, 52, 64), rgb(163, 190, 140)),
+        SegmentId::BurnRate => (rgb(46, 52, 64), rgb(46, 52, 64), rgb(208, 135, 112)),
+        SegmentId::Block => (rgb(46, 52, 64), rgb(46, 52, 64), rgb(180, 142, 173)),
+        SegmentId::Quota => (rgb(46, 52, 64), rgb(46, 52, 64), rgb(235, 203, 139)),
+        SegmentId::Project => (rgb(46, 52, 64), rgb(46, 52, 64), rgb(129, 161, 193)),
+        SegmentId::DiffStat => (rgb(46, 52, 64), rgb(46, 52, 64), rgb(163, 190, 140)),
+        SegmentId::Mode => (rgb(229, 233, 240), rgb(229, 233, 240), rgb(67, 76, 94)),
+        SegmentId::Host => (rgb(46, 52, 64), rgb(46, 52, 64), rgb(208, 135, 112)),
+        SegmentId::Env => (rgb(46, 52, 64), rgb(46, 52, 64), rgb(143, 188, 187)),
+        SegmentId::Version => (rgb(216, 222, 233), rgb(216, 222, 233), rgb(59, 66, 82)),
+        SegmentId::Compact => (rgb(46, 52, 64), rgb(46, 52, 64), rgb(235, 203, 139)),
+        SegmentId::Custom => (rgb(216, 222, 233), rgb(216, 222, 233), rgb(67, 76, 94)),
+        #[cfg(feature = "network-monitoring")]
+        SegmentId::Network => (rgb(163, 190, 140), rgb(163, 190, 140), rgb(53, 57, 69)),
+    };
+    ColorConfig {
+        icon: Some(icon),
+        text: Some(text),
+        background: Some(background),
+    }
+}
+
+/// Solarized accents only, so the same palette works on base03 and base3 backgrounds
+fn solarized(id: SegmentId) -> ColorConfig {
+    let yellow = rgb(181, 137, 0);
+    let orange = rgb(203, 75, 22);
+    let violet = rgb(108, 113, 196);
+    let blue = rgb(38, 139, 210);
+    let green = rgb(133, 153, 0);
+    let magenta = rgb(211, 54, 130);
+    match id {
+        SegmentId::Model => foreground(violet.clone(), violet),
+        SegmentId::Directory => foreground(yellow, blue),
+        SegmentId::Git => foreground(green.clone(), green),
+        SegmentId::Usage => foreground(magenta.clone(), magenta),
+        SegmentId::Update => foreground(orange.clone(), orange),
+        SegmentId::Stopwatch => foreground(blue.clone(), blue),
+        SegmentId::Cost => foreground(yellow.clone(), yellow),
+        SegmentId::BurnRate => foreground(rgb(42, 161, 152), rgb(42, 161, 152)),
+        SegmentId::Block => foreground(rgb(108, 113, 196), rgb(108, 113, 196)),
+        SegmentId::Quota => foreground(rgb(181, 137, 0), rgb(181, 137, 0)),
+        SegmentId::Project => foreground(rgb(38, 139, 210), rgb(38, 139, 210)),
+        SegmentId::DiffStat => foreground(green.clone(), green),
+        SegmentId::Mode => foreground(rgb(147, 161, 161), rgb(147, 161, 161)),
+        SegmentId::Host => foreground(rgb(220, 50, 47), rgb(220, 50, 47)),
+        SegmentId::Env => foreground(rgb(42, 161, 152), rgb(42, 161, 152)),
+        SegmentId::Version => foreground(rgb(88, 110, 117), rgb(88, 110, 117)),
+        SegmentId::Compact => foreground(rgb(181, 137, 0), rgb(181, 137, 0)),
+        SegmentId::Custom => foreground(rgb(147, 161, 161), rgb(147, 161, 161)),
+        #[cfg(feature = "network-monitoring")]
+        SegmentId::Network => foreground(rgb(42, 161, 152), rgb(42, 161, 152)),
+    }
+}
@@ -0,0 +1,113 @@
+//! C ABI for editor plugins (`ffi` feature)
+//!
+//! Native modules (Neovim, VS Code) can read ccstatus state in-process instead
+//! of spawning the binary. Build the shared library with:
+//!
+//! ```sh
+//! cargo rustc --release --lib --features ffi --crate-type cdylib
+//! ```
+//!
+//! Declarations live in `include/ccstatus.h`. Semantics are stable for a given
+//! `ccstatus_abi_version()`:
+//!
+//! - Returned strings are NUL-terminated UTF-8, owned by the caller and
+//!   released with `ccstatus_string_free`
+//! - `NULL` signals an error; nothing is printed
+//! - Panics are caught at the boundary and never unwind into the host process
+
+use crate::config::{Config, InputData};
+use crate::core::capabilities::Capabilities;
+use crate::core::network::{HttpMonitor, StatuslineInput};
+use crate::core::state_location::StateLocation;
+use crate::core::statusline::responsive::available_width;
+use crate::core::{collect_all_segments, StatusLineGenerator};
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+/// Bumped whenever a function's signature or semantics change
+pub const CCSTATUS_FFI_ABI_VERSION: u32 = 1;
+
+/// ABI version of the loaded library (`CCSTATUS_FFI_ABI_VERSION`)
+#[no_mangle]
+pub extern "C" fn ccstatus_abi_version() -> u32 {
+    CCSTATUS_FFI_ABI_VERSION
+}
+
+/// Current monitoring snapshot as JSON (schema of `ccstatus-monitoring.json`)
+///
+/// Before the first probe this is the default snapshot with status `Unknown`.
+/// Returns `NULL` when the home directory is unknown or the state file is unreadable.
+#[no_mangle]
+pub extern "C" fn ccstatus_read_snapshot_json() -> *mut c_char {
+    guard(|| {
+        let config = Config::load().unwrap_or_else(|_| Config::default());
+        let location = StateLocation::from_config(&config.network)?;
+        let state = crate::runtime::block_on(async {
+            HttpMonitor::new(Some(location.monitoring_path()))?
+                .load_state()
+                .await
+        })
+        .ok()?;
+        serde_json::to_string(&state).ok()
+    })
+}
+
+/// Render the statusline for `input_json`, Claude Code's statusline stdin payload
+///
+/// Output matches `ccstatus --format plain` with the user's config.toml, including
+/// ANSI styling and any network probe the segment decides to run.
+///
+/// # Safety
+///
+/// `input_json` must be `NULL` or point to a NUL-terminated string that stays
+/// valid for the duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn ccstatus_render_statusline(input_json: *const c_char) -> *mut c_char {
+    if input_json.is_null() {
+        return ptr::null_mut();
+    }
+    let input_json = CStr::from_ptr(input_json);
+
+    guard(|| render_statusline(input_json.to_str().ok()?))
+}
+
+/// Release a string returned by this library; `NULL` is ignored
+///
+/// # Safety
+///
+/// `s` must be `NULL` or a pointer returned by a `ccstatus_*` function that has
+/// not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn ccstatus_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+fn render_statusline(input_json: &str) -> Option<String> {
+    let full_input: StatuslineInput = serde_json::from_str(input_json).ok()?;
+    let input = InputData::from(&full_input);
+    let config = Config::load().unwrap_or_else(|_| Config::default());
+
+    let segments_data =
+        crate::runtime::block_on(collect_all_segments(&config, &input, Some(&full_input)));
+    Some(
+        StatusLineGenerator::new(config)
+            .with_max_width(available_width(input.terminal_width))
+            .with_color_level(Capabilities::current().color)
+            .generate(segments_data),
+    )
+}
+
+/// Run `f` without letting a panic cross the boundary, handing its output to C
+fn guard<F>(f: F) -> *mut c_char
+where
+    F: FnOnce() -> Option<String>,
+{
+    catch_unwind(AssertUnwindSafe(f))
+        .ok()
+        .flatten()
+        .and_then(|s| CString::new(s).ok())
+        .map_or(ptr::null_mut(), CString::into_raw)
+}
@@ -1,7 +1,19 @@
 pub mod cli;
 pub mod config;
 pub mod core;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(any(feature = "runtime-tokio", feature = "runtime-smol"))]
+pub mod runtime;
 pub mod ui;
 
 #[cfg(feature = "self-update")]
 pub mod updater;
+
+#[cfg(all(
+    feature = "network-monitoring",
+    not(any(feature = "runtime-tokio", feature = "runtime-smol"))
+))]
+compile_error!(
+    "network-monitoring needs an async runtime: enable `runtime-tokio` or `runtime-smol`"
+);
@@ -1,15 +1,24 @@
-use ccstatus::cli::Cli;
+use ccstatus::cli::{Cli, Command, NetAction};
 use ccstatus::config::{Config, InputData};
+use ccstatus::core::capabilities::Capabilities;
+use ccstatus::core::detail_renderer::DetailRenderer;
+use ccstatus::core::heartbeat::{Heartbeat, HeartbeatWriter};
+use ccstatus::core::input::InputSource;
+use ccstatus::core::output::OutputFormat;
+use ccstatus::core::perf::PerfRecorder;
+use ccstatus::core::state_location::StateLocation;
+use ccstatus::core::statusline::responsive;
 use ccstatus::core::{collect_all_segments, StatusLineGenerator};
 use std::io;
 
+#[cfg(feature = "network-monitoring")]
+use ccstatus::core::network::debug_logger::{init_logging, LoggerConfig};
 #[cfg(feature = "network-monitoring")]
 use ccstatus::core::network::StatuslineInput;
 
 #[cfg(feature = "network-monitoring")]
-#[tokio::main(flavor = "current_thread")]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    main_impl().await
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ccstatus::runtime::block_on(main_impl())
 }
 
 #[cfg(not(feature = "network-monitoring"))]
@@ -18,9 +27,61 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 }
 
 async fn main_impl() -> Result<(), Box<dyn std::error::Error>> {
+    // Starts the clock for this invocation's perf report
+    let perf = PerfRecorder::global();
+    // One logger (and session id) shared by every module for this run
+    #[cfg(feature = "network-monitoring")]
+    init_logging(LoggerConfig::from_env());
     let cli = Cli::parse_args();
+    ccstatus::core::compat::record_all(ccstatus::core::compat::check_env(|name| {
+        std::env::var(name).ok()
+    }));
+
+    if cli.help_json {
+        println!("{}", serde_json::to_string_pretty(&Cli::help_json())?);
+        return Ok(());
+    }
 
     // Handle configuration commands
+    if let Some(Command::Net { action }) = cli.command {
+        return run_net_command(action).await;
+    }
+    if let Some(Command::Doctor) = cli.command {
+        return run_doctor_command();
+    }
+    if let Some(Command::About) = cli.command {
+        return run_about_command();
+    }
+    if let Some(Command::Env) = cli.command {
+        println!(
+            "{}",
+            ccstatus::core::env::render_report(|name| std::env::var(name).ok())
+        );
+        return Ok(());
+    }
+    if let Some(Command::Stats) = cli.command {
+        return run_stats_command();
+    }
+    if let Some(Command::Snapshot { for_hook }) = cli.command {
+        return run_snapshot_command(for_hook).await;
+    }
+    if let Some(Command::Export { prometheus, listen }) = cli.command {
+        return run_export_command(prometheus, listen).await;
+    }
+    if let Some(Command::Preview { ref diff, plain }) = cli.command {
+        return run_preview_command(diff, plain, cli.input.as_deref()).await;
+    }
+    if let Some(Command::Daemon { profile }) = cli.command {
+        return run_daemon_command(profile).await;
+    }
+    if let Some(Command::History {
+        since,
+        target,
+        recent,
+    }) = cli.command
+    {
+        return run_history_command(&since, target.as_deref(), recent);
+    }
 
     if cli.update {
         #[cfg(feature = "self-update")]
@@ -38,28 +99,41 @@ async fn main_impl() -> Result<(), Box<dyn std::error::Error>> {
     if cli.check_update {
         #[cfg(feature = "self-update")]
         {
+            use ccstatus::core::attention::AttentionStyle;
             use ccstatus::updater::{geo, manifest::ManifestClient, url_resolver};
 
-            // Perform immediate update check
+            // Perform immediate update check; an unreadable config.toml must not lift
+            // network.allowed_hosts
+            let hosts = ccstatus::config::HostPolicy::from_config(&Config::load()?.network);
             let mut state = ccstatus::updater::UpdateStateFile::load();
 
             // Get geographic detection
             let is_china = if state.is_geo_verdict_valid() {
                 state.geo_verdict.unwrap_or(false)
             } else {
-                let detected = geo::detect_china_ttl24h();
+                let detected = geo::detect_china_ttl24h(&hosts);
                 state.update_geo_verdict(detected);
                 state.save().ok();
                 detected
             };
 
-            // Resolve URLs for sequential trying with persistent caching
-            let urls = url_resolver::resolve_manifest_url(is_china);
+            // Resolve URLs for sequential trying with persistent caching,
+            // restricted to hosts allowed by network.allowed_hosts
+            let urls = url_resolver::filter_allowed_urls(
+                url_resolver::resolve_manifest_url(is_china),
+                &hosts,
+            );
+            if urls.is_empty() {
+                eprintln!(
+                    "Update check blocked by policy: no manifest host in network.allowed_hosts"
+                );
+                std::process::exit(1);
+            }
             let mut client = ManifestClient::new();
             let mut update_found = false;
 
             // Check for verbose debug output
-            let debug_enabled = std::env::var("CCSTATUS_DEBUG").is_ok();
+            let debug_enabled = ccstatus::core::env::DEBUG.is_enabled();
 
             if debug_enabled {
                 eprintln!(
@@ -98,6 +172,7 @@ async fn main_impl() -> Result<(), Box<dyn std::error::Error>> {
                     }
 
                     let manifest = manifest_opt.unwrap();
+                    manifest.remote_flags().save().ok();
 
                     // Update persistent cache if we have new headers
                     let host = url_resolver::extract_host_from_url(&successful_url)
@@ -119,20 +194,17 @@ async fn main_impl() -> Result<(), Box<dyn std::error::Error>> {
 
                     // Check if newer version available
                     if client.is_newer_version(&manifest.version).unwrap_or(false) {
-                        // Check if blinking output is enabled (default: true)
-                        let flash_enabled = std::env::var("CCSTATUS_FLASH")
-                            .map(|v| v.to_lowercase() != "0" && v.to_lowercase() != "false")
-                            .unwrap_or(true);
-
-                        let output = if flash_enabled {
-                            format!(
-                                "\x1b[5m v{} released \x1b[0m ({})",
-                                manifest.version, manifest.notes_url
-                            )
-                        } else {
-                            format!("v{} released ({})", manifest.version, manifest.notes_url)
-                        };
-                        eprintln!("{}", output);
+                        // Same emphasis as the statusline update notice (`attention`)
+                        let attention = AttentionStyle::for_config(
+                            Config::load()
+                                .map(|config| config.attention)
+                                .unwrap_or_default(),
+                        );
+                        eprintln!(
+                            "{} ({})",
+                            attention.apply(&format!("v{} released", manifest.version)),
+                            manifest.notes_url
+                        );
                         update_found = true;
                     }
 
@@ -165,32 +237,864 @@ async fn main_impl() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     // Load configuration
-    let config = Config::load().unwrap_or_else(|_| Config::default());
+    let config = perf.time("config_load", || {
+        Config::load().unwrap_or_else(|_| Config::default())
+    });
+
+    let source = InputSource::from_arg(cli.input.as_deref());
+    if cli.watch {
+        let Some(path) = source.path() else {
+            eprintln!("--watch needs --input <path>");
+            std::process::exit(1);
+        };
+        return watch_input(path, config, cli.format).await;
+    }
+
+    // Read Claude Code data from stdin (or --input)
+    let content = source.read()?;
+    let heartbeat = config
+        .heartbeat
+        .as_ref()
+        .and_then(HeartbeatWriter::from_config);
+    let state_location = StateLocation::from_config(&config.network);
+    println!("{}", render_statusline(&content, config, cli.format).await?);
+    write_heartbeat(heartbeat.as_ref());
+
+    // Housekeeping runs after the line is out, at most once a day
+    perf.time("maintenance", || {
+        ccstatus::core::scheduler::run_maintenance(state_location.as_ref())
+    });
+
+    #[cfg(feature = "network-monitoring")]
+    ccstatus::core::network::get_debug_logger().perf_report_sync(&perf.report());
 
-    // Read Claude Code data from stdin with two-tier data flow for network monitoring
-    let stdin = io::stdin();
+    Ok(())
+}
+
+/// Parse statusline JSON and render it, with the two-tier data flow for network monitoring
+async fn render_statusline(
+    content: &str,
+    config: Config,
+    format: OutputFormat,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let perf = PerfRecorder::global();
 
     #[cfg(feature = "network-monitoring")]
-    let (input, full_input) = {
-        let full_input: StatuslineInput = serde_json::from_reader(stdin.lock())?;
+    let (input, full_input) = perf.time("stdin_parse", || -> serde_json::Result<_> {
+        let full_input: StatuslineInput = serde_json::from_str(content)?;
         let input = InputData::from(&full_input);
-        (input, Some(full_input))
-    };
+        Ok((input, Some(full_input)))
+    })?;
 
     #[cfg(not(feature = "network-monitoring"))]
-    let (input, full_input) = {
-        let input: InputData = serde_json::from_reader(stdin.lock())?;
-        (input, None::<()>)
-    };
+    let (input, full_input) = perf.time("stdin_parse", || -> serde_json::Result<_> {
+        let input: InputData = serde_json::from_str(content)?;
+        Ok((input, None::<()>))
+    })?;
 
     // Collect segment data
     let segments_data = collect_all_segments(&config, &input, full_input.as_ref()).await;
+    let state_location = StateLocation::from_config(&config.network);
 
     // Render statusline
-    let generator = StatusLineGenerator::new(config);
-    let statusline = generator.generate(segments_data);
+    let generator = StatusLineGenerator::new(config)
+        .with_max_width(responsive::available_width(input.terminal_width))
+        .with_color_level(Capabilities::current().color);
+    let statusline = perf.time("render", || generator.generate(segments_data));
+
+    if format.is_structured() {
+        Ok(format.render(&statusline, &detail_renderer(state_location.as_ref()).await))
+    } else {
+        Ok(statusline)
+    }
+}
+
+/// Handle `--watch`: print a statusline each time the input file changes, until Ctrl-C
+///
+/// Unreadable or half-written input is reported on stderr and the previous
+/// line stays current until the next change.
+async fn watch_input(
+    path: &std::path::Path,
+    config: Config,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use ccstatus::core::input::{FileWatch, WATCH_POLL_INTERVAL};
+    use std::io::Write;
+
+    #[cfg(any(feature = "runtime-tokio", feature = "runtime-smol"))]
+    let shutdown = {
+        let coordinator = ccstatus::core::shutdown::ShutdownCoordinator::global();
+        coordinator.install_signal_handler()?;
+        coordinator
+    };
+
+    let heartbeat = config
+        .heartbeat
+        .as_ref()
+        .and_then(HeartbeatWriter::from_config);
+    let mut watch = FileWatch::new(path);
+    loop {
+        #[cfg(any(feature = "runtime-tokio", feature = "runtime-smol"))]
+        if shutdown.is_requested() {
+            shutdown.shutdown(std::time::Duration::from_secs(2)).await;
+            return Ok(());
+        }
+
+        if watch.poll() {
+            let rendered = match std::fs::read_to_string(path) {
+                Ok(content) => render_statusline(&content, config.clone(), format).await,
+                Err(e) => Err(e.into()),
+            };
+            match rendered {
+                Ok(statusline) => {
+                    println!("{}", statusline);
+                    io::stdout().flush()?;
+                    write_heartbeat(heartbeat.as_ref());
+                }
+                Err(e) => eprintln!("{}: {}", path.display(), e),
+            }
+        }
+
+        #[cfg(any(feature = "runtime-tokio", feature = "runtime-smol"))]
+        ccstatus::runtime::sleep(WATCH_POLL_INTERVAL).await;
+        #[cfg(not(any(feature = "runtime-tokio", feature = "runtime-smol")))]
+        std::thread::sleep(WATCH_POLL_INTERVAL);
+    }
+}
+
+/// Tell external watchdogs the statusline rendered (`[heartbeat]`); failures are only logged
+fn write_heartbeat(writer: Option<&HeartbeatWriter>) {
+    let Some(writer) = writer else {
+        return;
+    };
+    if let Err(e) = writer.write(&Heartbeat::now()) {
+        #[cfg(feature = "network-monitoring")]
+        ccstatus::core::network::get_debug_logger().warn_sync(
+            "Heartbeat",
+            "write_failed",
+            &format!("{}: {}", writer.path().display(), e),
+        );
+        #[cfg(not(feature = "network-monitoring"))]
+        let _ = e;
+    }
+}
+
+/// Detail block for structured output: persisted network state and pending update
+async fn detail_renderer(location: Option<&StateLocation>) -> DetailRenderer {
+    use ccstatus::core::segments::UpdateSegment;
+
+    let renderer = DetailRenderer::new().with_update(UpdateSegment::pending_version());
+
+    #[cfg(feature = "network-monitoring")]
+    if let Some(location) = location {
+        use ccstatus::core::network::HttpMonitor;
+
+        if let Ok(monitor) = HttpMonitor::new(Some(location.monitoring_path())) {
+            if let Ok(state) = monitor.load_state().await {
+                return renderer.with_snapshot(state);
+            }
+        }
+    }
+    #[cfg(not(feature = "network-monitoring"))]
+    let _ = location;
+
+    renderer
+}
+
+/// Handle `ccstatus snapshot`: collect segment data without probing and print it as JSON
+///
+/// Hook event JSON (or statusline input) piped on stdin supplies the working
+/// directory and transcript; without it the current directory is used.
+async fn run_snapshot_command(for_hook: bool) -> Result<(), Box<dyn std::error::Error>> {
+    use ccstatus::core::segments::UpdateSegment;
+    use ccstatus::core::snapshot::{input_from_json, HookSnapshot};
+    use std::io::{IsTerminal, Read};
+
+    let config = Config::load().unwrap_or_else(|_| Config::default());
+
+    let mut piped = String::new();
+    if !io::stdin().is_terminal() {
+        io::stdin().read_to_string(&mut piped)?;
+    }
+    let input = input_from_json(&serde_json::from_str(&piped).unwrap_or_default());
+
+    // No statusline input: the network segment is skipped, so nothing is probed
+    let segments_data = collect_all_segments(&config, &input, None).await;
+    let snapshot = HookSnapshot::new(&segments_data).with_update(UpdateSegment::pending_version());
+
+    #[cfg(feature = "network-monitoring")]
+    let snapshot = {
+        use ccstatus::core::network::HttpMonitor;
+
+        let state_path =
+            StateLocation::from_config(&config.network).map(|location| location.monitoring_path());
+        match HttpMonitor::new(state_path) {
+            Ok(monitor) => match monitor.load_state().await {
+                Ok(state) => snapshot.with_network(&state),
+                Err(_) => snapshot,
+            },
+            Err(_) => snapshot,
+        }
+    };
+
+    println!("{}", snapshot.to_json(for_hook));
+    Ok(())
+}
+
+/// Handle `ccstatus preview --diff`: render the current and a candidate config side by side
+///
+/// Uses `--input` or piped statusline JSON, else sample data. The network
+/// segment is skipped, so nothing is probed.
+async fn run_preview_command(
+    candidate_path: &std::path::Path,
+    plain: bool,
+    input_arg: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use ccstatus::config::ConfigLoader;
+    use ccstatus::core::preview::{sample_input, PreviewDiff};
+    use ccstatus::core::snapshot::input_from_json;
+    use std::io::IsTerminal;
+
+    let current = Config::load()?;
+    let candidate = ConfigLoader::load_from_path(candidate_path)
+        .map_err(|e| format!("{}: {}", candidate_path.display(), e))?;
+
+    let content = if input_arg.is_some() || !io::stdin().is_terminal() {
+        InputSource::from_arg(input_arg).read()?
+    } else {
+        String::new()
+    };
+    let json = match content.trim() {
+        "" => sample_input(),
+        content => serde_json::from_str(content)?,
+    };
+    let input = input_from_json(&json);
+
+    let mut rendered = Vec::new();
+    for config in [current, candidate] {
+        let segments_data = collect_all_segments(&config, &input, None).await;
+        let generator = StatusLineGenerator::new(config);
+        rendered.push((
+            generator.generate(segments_data.clone()),
+            generator.render_segments(segments_data),
+        ));
+    }
+    let (candidate_line, candidate_segments) = rendered.pop().unwrap_or_default();
+    let (current_line, current_segments) = rendered.pop().unwrap_or_default();
+
+    let diff = PreviewDiff::new(
+        current_line,
+        candidate_line,
+        current_segments,
+        candidate_segments,
+    );
+    println!("{}", diff.render(plain));
+    Ok(())
+}
+
+/// Handle `ccstatus about`: print the version, compiled-in features and effective flags
+fn run_about_command() -> Result<(), Box<dyn std::error::Error>> {
+    use ccstatus::config::FlagSet;
+
+    let config = Config::load()?;
+    let features: Vec<&str> = [
+        ("network-monitoring", cfg!(feature = "network-monitoring")),
+        ("self-update", cfg!(feature = "self-update")),
+        ("timings-curl", cfg!(feature = "timings-curl")),
+        ("tls-parity", cfg!(feature = "tls-parity")),
+        ("state-sync", cfg!(feature = "state-sync")),
+        ("ffi", cfg!(feature = "ffi")),
+        ("tui", cfg!(feature = "tui")),
+    ]
+    .into_iter()
+    .filter_map(|(name, enabled)| enabled.then_some(name))
+    .collect();
+
+    println!("ccstatus {}", env!("CARGO_PKG_VERSION"));
+    println!("Features: {}", features.join(", "));
+    println!("Flags (config.toml [flags] > manifest > default):");
+    println!("{}", FlagSet::load(&config.flags).render_report());
+    Ok(())
+}
+
+fn run_doctor_command() -> Result<(), Box<dyn std::error::Error>> {
+    use ccstatus::core::compat;
+    use ccstatus::core::recovery::{RecoveryLog, RECOVERY_LOG_FILE};
+    use ccstatus::core::segments::SegmentHealthMap;
+    use ccstatus::core::state_location::detect_filesystem;
+
+    let loaded = Config::load();
+    let network = loaded
+        .as_ref()
+        .map(|config| config.network.clone())
+        .unwrap_or_default();
+    let location = StateLocation::from_config(&network);
+
+    let path = SegmentHealthMap::default_path().ok_or("Could not determine home directory")?;
+    println!("Segment health ({}):", path.display());
+    println!("{}", SegmentHealthMap::load(&path).render_report());
+
+    println!("\nCapabilities:");
+    println!("{}", Capabilities::current().render_report());
+
+    if let Some(ref location) = location {
+        let home_mount = detect_filesystem(location.home_dir());
+        let local_mount = location.local_dir().and_then(detect_filesystem);
+        println!("\nState location:");
+        println!(
+            "{}",
+            location.render_report(home_mount.as_ref(), local_mount.as_ref())
+        );
+    }
+
+    println!("\nProxy:");
+    println!("{}", ccstatus::config::ProxyPolicy::load().render_report());
+
+    println!("\nTLS:");
+    println!("{}", ccstatus::config::TlsPolicy::load().render_report());
+
+    // State files live in the ccstatus directory, per-target network state one level down;
+    // with `local_state_dir` the monitoring state is recovered there as well
+    let state_dir = path.parent().ok_or("Could not determine state directory")?;
+    let mut dirs = vec![state_dir.to_path_buf(), state_dir.join("targets")];
+    if let Some(local) = location.as_ref().and_then(StateLocation::local_dir) {
+        dirs.extend([local.to_path_buf(), local.join("targets")]);
+    }
+    for dir in dirs {
+        let log_path = dir.join(RECOVERY_LOG_FILE);
+        if dir != state_dir && !log_path.exists() {
+            continue;
+        }
+        println!("\nState file recovery ({}):", log_path.display());
+        println!("{}", RecoveryLog::load(&log_path).render_report());
+    }
 
-    println!("{}", statusline);
+    // Loading the config (above) and update state records their compatibility warnings
+    if let Err(e) = loaded {
+        println!("\nConfig: {}", e);
+    }
+    #[cfg(feature = "self-update")]
+    ccstatus::updater::UpdateStateFile::load();
+    println!("\nCompatibility warnings:");
+    println!("{}", compat::render_report(&compat::warnings()));
+    Ok(())
+}
+
+fn run_stats_command() -> Result<(), Box<dyn std::error::Error>> {
+    use ccstatus::core::segments::project::ProjectLedger;
+
+    let config = Config::load()?;
+    let path = ProjectLedger::default_path().ok_or("Could not determine home directory")?;
+    println!("Projects ({}):", path.display());
+    println!(
+        "{}",
+        ProjectLedger::load(&path).render_report(&config.formatting)
+    );
+    Ok(())
+}
+
+/// `ccstatus export --prometheus`: print the metrics once, or serve them with `--listen`
+/// until Ctrl-C
+async fn run_export_command(
+    prometheus: bool,
+    listen: Option<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    // Prometheus is the only format so far; clap requires the flag
+    debug_assert!(prometheus);
+
+    #[cfg(feature = "network-monitoring")]
+    {
+        use ccstatus::core::network::prometheus::{load_partitions, render_metrics};
+
+        let config = Config::load()?;
+        let Some(addr) = listen else {
+            print!(
+                "{}",
+                render_metrics(&load_partitions(&config.network).await?)
+            );
+            return Ok(());
+        };
+
+        #[cfg(feature = "prometheus-listener")]
+        {
+            use ccstatus::core::network::probe_history::load_partition_histories;
+            use ccstatus::core::network::prometheus::serve;
+            use ccstatus::core::network::timeseries::render_timeseries;
+            use ccstatus::core::shutdown::ShutdownCoordinator;
+
+            let shutdown = ShutdownCoordinator::global();
+            shutdown.install_signal_handler()?;
+
+            let listener = std::net::TcpListener::bind(&addr)?;
+            let local_addr = listener.local_addr()?;
+            eprintln!("Serving metrics at http://{}/metrics", local_addr);
+            eprintln!(
+                "Serving time series at http://{}/api/timeseries?metric=latency&range=6h",
+                local_addr
+            );
+            // A plain thread: every scrape drives its own runtime, which cannot nest in this one
+            let network = config.network.clone();
+            std::thread::spawn(move || {
+                serve(
+                    listener,
+                    shutdown,
+                    || {
+                        ccstatus::runtime::block_on(load_partitions(&network))
+                            .map(|partitions| render_metrics(&partitions))
+                            .map_err(|e| e.to_string())
+                    },
+                    |query| {
+                        load_partition_histories(&network)
+                            .map(|histories| {
+                                render_timeseries(
+                                    &histories,
+                                    query,
+                                    chrono::Utc::now().timestamp_millis(),
+                                )
+                            })
+                            .map_err(|e| e.to_string())
+                    },
+                )
+            })
+            .join()
+            .map_err(|_| "metrics listener panicked")??;
+            shutdown.shutdown(std::time::Duration::from_secs(2)).await;
+            Ok(())
+        }
+        #[cfg(not(feature = "prometheus-listener"))]
+        {
+            let _ = addr;
+            eprintln!("Metrics listener not available (prometheus-listener feature disabled)");
+            std::process::exit(1);
+        }
+    }
+    #[cfg(not(feature = "network-monitoring"))]
+    {
+        let _ = listen;
+        eprintln!("Export not available (network-monitoring feature disabled)");
+        std::process::exit(1);
+    }
+}
+
+/// `ccstatus daemon`: probe on the profile's GREEN/RED cadence until Ctrl-C
+async fn run_daemon_command(profile: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(feature = "network-monitoring")]
+    {
+        use ccstatus::config::{FlagSet, ProxyPolicy};
+        use ccstatus::core::network::probe_profile::ProbeProfile;
+        use ccstatus::core::segments::NetworkSegmentWrapper;
+        use ccstatus::core::shutdown::ShutdownCoordinator;
+
+        let config = Config::load()?;
+        let profile = match profile.or_else(|| ccstatus::core::env::PROBE_PROFILE.raw()) {
+            Some(name) => name.parse::<ProbeProfile>()?,
+            None => ProbeProfile::default(),
+        };
+        let shutdown = ShutdownCoordinator::global();
+        shutdown.install_signal_handler()?;
+
+        eprintln!(
+            "Probing with the {} profile until Ctrl-C; the statusline reads the results meanwhile",
+            profile
+        );
+        let result = NetworkSegmentWrapper::new()?
+            .with_config(&config.network)
+            .with_proxy(ProxyPolicy::from_config(&config))
+            .with_alerts(&config.alert_routes())
+            .with_flags(FlagSet::load(&config.flags))
+            .run_daemon(profile, shutdown, |name, mode, check| {
+                let time = chrono::Local::now().format("%H:%M:%S");
+                let mode = format!("{:?}", mode).to_lowercase();
+                match check.skip_reason {
+                    Some(ref reason) => println!("{} {} {} skipped: {}", time, name, mode, reason),
+                    None => println!(
+                        "{} {} {} {} {}ms",
+                        time,
+                        name,
+                        mode,
+                        check.status.as_str(),
+                        check.latency_ms.unwrap_or_default()
+                    ),
+                }
+            })
+            .await;
+        shutdown.shutdown(std::time::Duration::from_secs(2)).await;
+        if let Err(e) = result {
+            eprintln!("Daemon failed: {}", e);
+            std::process::exit(1);
+        }
+        Ok(())
+    }
+    #[cfg(not(feature = "network-monitoring"))]
+    {
+        let _ = profile;
+        eprintln!("Daemon not available (network-monitoring feature disabled)");
+        std::process::exit(1);
+    }
+}
+
+fn run_history_command(
+    since: &str,
+    target: Option<&str>,
+    recent: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(feature = "network-monitoring")]
+    {
+        use ccstatus::core::network::probe_history::{
+            load_partition_histories, records_between, summarize,
+        };
+        use ccstatus::core::network::timeseries::parse_range;
+        use ccstatus::core::network::StatusRenderer;
+
+        let Some(range_secs) = parse_range(since) else {
+            eprintln!("Invalid --since '{}' (e.g. 30m, 6h, 7d)", since);
+            std::process::exit(1);
+        };
+        let mut histories = load_partition_histories(&Config::load()?.network)?;
+        if let Some(target) = target {
+            histories.retain(|(name, _)| name == target);
+            if histories.is_empty() {
+                eprintln!("Unknown target '{}'", target);
+                std::process::exit(1);
+            }
+        }
+
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let from_ms = now_ms.saturating_sub(range_secs.saturating_mul(1000));
+        let windows: Vec<(&str, Vec<_>)> = histories
+            .iter()
+            .map(|(name, records)| (name.as_str(), records_between(records, from_ms, now_ms)))
+            .collect();
+        let summaries: Vec<_> = windows
+            .iter()
+            .map(|(name, records)| (name.to_string(), summarize(records)))
+            .collect();
+        let mut newest: Vec<(&str, _)> = windows
+            .iter()
+            .flat_map(|(name, records)| records.iter().map(move |record| (*name, *record)))
+            .collect();
+        newest.sort_by_key(|(_, record)| record.timestamp_millis());
+        let newest = &newest[newest.len().saturating_sub(recent)..];
+
+        println!(
+            "{}",
+            StatusRenderer::new().render_history(since, &summaries, newest)
+        );
+        Ok(())
+    }
+    #[cfg(not(feature = "network-monitoring"))]
+    {
+        let _ = (since, target, recent);
+        eprintln!("History not available (network-monitoring feature disabled)");
+        std::process::exit(1);
+    }
+}
+
+/// `ccstatus net check`: probe once and return the exit code for the outcome
+///
+/// Anything that keeps the probe from running (an unreadable config, no state
+/// directory) exits 3 like an unknown status; the report that follows a probe
+/// is best-effort and never changes the code.
+async fn run_net_check() -> i32 {
+    #[cfg(feature = "network-monitoring")]
+    {
+        use ccstatus::config::{FlagSet, ProxyPolicy};
+        use ccstatus::core::network::{HttpMonitor, NetworkStatus, ProbeCheck, StatusRenderer};
+        use ccstatus::core::segments::NetworkSegmentWrapper;
+
+        async fn probe() -> Result<(ProbeCheck, Option<StateLocation>), Box<dyn std::error::Error>>
+        {
+            let config = Config::load()?;
+            let check = NetworkSegmentWrapper::new()?
+                .with_config(&config.network)
+                .with_proxy(ProxyPolicy::from_config(&config))
+                .with_flags(FlagSet::load(&config.flags))
+                .check()
+                .await?;
+            Ok((check, StateLocation::from_config(&config.network)))
+        }
+
+        let (check, location) = match probe().await {
+            Ok(probed) => probed,
+            Err(e) => {
+                eprintln!("Check failed: {}", e);
+                return NetworkStatus::Unknown.exit_code();
+            }
+        };
+        if let Some(ref reason) = check.skip_reason {
+            println!("Probe skipped: {}", reason);
+        } else {
+            println!(
+                "Probe: {} in {}ms ({} credentials)\n",
+                check.status.as_str(),
+                check.latency_ms.unwrap_or_default(),
+                check.credential_source.as_deref().unwrap_or("unknown")
+            );
+            // Proxy health, latency thresholds and the last error of the probe just sent
+            match HttpMonitor::new(location.map(|location| location.monitoring_path())) {
+                Ok(monitor) => match monitor.load_state().await {
+                    Ok(state) => println!("{}", StatusRenderer::new().render_report(&state)),
+                    Err(e) => eprintln!("Report unavailable: {}", e),
+                },
+                Err(e) => eprintln!("Report unavailable: {}", e),
+            }
+        }
+        check.status.exit_code()
+    }
+    #[cfg(not(feature = "network-monitoring"))]
+    {
+        eprintln!("Network monitoring not available (network-monitoring feature disabled)");
+        3
+    }
+}
+
+/// Ask `question` on the terminal; anything but `y`/`yes` (or no terminal) declines
+#[cfg(feature = "network-monitoring")]
+fn confirm(question: &str) -> io::Result<bool> {
+    use std::io::{IsTerminal, Write};
+
+    if !io::stdin().is_terminal() {
+        eprintln!(
+            "{} Refusing without a terminal; pass --yes to confirm",
+            question
+        );
+        return Ok(false);
+    }
+    print!("{} [y/N] ", question);
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Handle `ccstatus net <action>`: update the `[network]` section of config.toml or report status
+async fn run_net_command(action: NetAction) -> Result<(), Box<dyn std::error::Error>> {
+    // CI gates on the exit code, so an error there must read as unknown, not degraded
+    if action == NetAction::Check {
+        std::process::exit(run_net_check().await);
+    }
+    let mut config = Config::load()?;
+
+    match action {
+        NetAction::Enable => {
+            config.network.probe_consent = true;
+            config.save()?;
+            println!("Network probes enabled (each probe sends a minimal API request)");
+        }
+        NetAction::Disable => {
+            config.network.probe_consent = false;
+            config.save()?;
+            println!("Network probes disabled (transcript-only monitoring)");
+        }
+        NetAction::Status => {
+            let consent = if config.network.probe_consent {
+                "enabled"
+            } else {
+                "disabled (run `ccstatus net enable`)"
+            };
+            println!("Probes: {}", consent);
+
+            #[cfg(feature = "network-monitoring")]
+            {
+                use ccstatus::core::network::{HttpMonitor, StatusRenderer};
+
+                let location = StateLocation::from_config(&config.network)
+                    .ok_or("Could not determine home directory")?;
+                let tls = ccstatus::config::TlsPolicy::from_network(&config.network);
+                if let Some(reason) = tls.error() {
+                    println!(
+                        "ERROR: {}: {}\n",
+                        ccstatus::config::tls::INVALID_LABEL,
+                        reason
+                    );
+                } else if tls.skips_verification() {
+                    println!("WARNING: {}\n", ccstatus::config::tls::INSECURE_LABEL);
+                }
+                let state = HttpMonitor::new(Some(location.monitoring_path()))?
+                    .load_state()
+                    .await?;
+                println!("{}", StatusRenderer::new().render_report(&state));
+
+                for target in &config.network.targets {
+                    if !target.has_valid_name() {
+                        continue;
+                    }
+                    let state_path = location.target_path(&target.name);
+                    let state = HttpMonitor::new(Some(state_path))?.load_state().await?;
+                    println!("\nTarget {} ({}):", target.name, target.display_label());
+                    println!("{}", StatusRenderer::new().render_report(&state));
+                }
+            }
+            #[cfg(not(feature = "network-monitoring"))]
+            {
+                println!("Network monitoring not available (network-monitoring feature disabled)");
+            }
+        }
+        NetAction::Check => unreachable!("`net check` exits from run_net_check"),
+        NetAction::Report { compare_versions } => {
+            #[cfg(feature = "network-monitoring")]
+            {
+                use ccstatus::core::network::probe_history::{summarize_by_version, ProbeHistory};
+                use ccstatus::core::network::StatusRenderer;
+
+                let location = StateLocation::from_config(&config.network)
+                    .ok_or("Could not determine home directory")?;
+                let records = ProbeHistory::for_state_path(&location.monitoring_path()).load();
+                let mut summaries = summarize_by_version(&records);
+                if !compare_versions {
+                    summaries.retain(|summary| summary.version == env!("CARGO_PKG_VERSION"));
+                }
+                println!(
+                    "{}",
+                    StatusRenderer::new().render_version_comparison(&summaries)
+                );
+            }
+            #[cfg(not(feature = "network-monitoring"))]
+            {
+                let _ = compare_versions;
+                println!("Network monitoring not available (network-monitoring feature disabled)");
+            }
+        }
+        NetAction::Regions { measure } => {
+            #[cfg(feature = "network-monitoring")]
+            {
+                use ccstatus::config::HostPolicy;
+                use ccstatus::core::network::cookie_jar::unix_now_secs;
+                use ccstatus::core::network::standby::{StandbyMonitor, StandbyState};
+                use ccstatus::core::network::StatusRenderer;
+
+                let Some(ref standby_config) = config.network.standby else {
+                    eprintln!(
+                        "No standby regions configured (add [[network.standby.regions]] entries)"
+                    );
+                    std::process::exit(1);
+                };
+                if measure {
+                    let measured = StandbyMonitor::from_config(standby_config)?
+                        .with_host_policy(HostPolicy::from_config(&config.network))
+                        .measure()
+                        .await?;
+                    println!("Measured {} regions\n", measured);
+                }
+                let state = StandbyState::load(&StandbyState::default_path()?).await;
+                println!(
+                    "{}",
+                    StatusRenderer::new().render_region_report(&state.summarize(), unix_now_secs())
+                );
+            }
+            #[cfg(not(feature = "network-monitoring"))]
+            {
+                let _ = measure;
+                println!("Network monitoring not available (network-monitoring feature disabled)");
+            }
+        }
+        NetAction::Reset { all, yes } => {
+            #[cfg(feature = "network-monitoring")]
+            {
+                use ccstatus::core::network::{HttpMonitor, ResetScope};
+
+                let scope = if all {
+                    ResetScope::All
+                } else {
+                    ResetScope::Statistics
+                };
+                if scope == ResetScope::All
+                    && !yes
+                    && !confirm("Delete the whole monitoring state and probe history?")?
+                {
+                    println!("Reset cancelled");
+                    return Ok(());
+                }
+
+                let location = StateLocation::from_config(&config.network)
+                    .ok_or("Could not determine home directory")?;
+                let mut partitions = vec![("default".to_string(), location.monitoring_path())];
+                for target in &config.network.targets {
+                    if target.has_valid_name() {
+                        let path = location.target_path(&target.name);
+                        partitions.push((format!("target {}", target.name), path));
+                    }
+                }
+                for (name, state_path) in partitions {
+                    match HttpMonitor::new(Some(state_path))?
+                        .reset_state(scope)
+                        .await?
+                    {
+                        Some(backup) => println!("Reset {} (backup: {})", name, backup.display()),
+                        None => println!("Reset {} (no state yet)", name),
+                    }
+                }
+            }
+            #[cfg(not(feature = "network-monitoring"))]
+            {
+                let _ = (all, yes);
+                println!("Network monitoring not available (network-monitoring feature disabled)");
+            }
+        }
+        NetAction::Sla { month } => {
+            #[cfg(feature = "network-monitoring")]
+            {
+                use ccstatus::core::network::cookie_jar::unix_now_secs;
+                use ccstatus::core::network::sla::{month_of, parse_month, SlaLedger};
+                use ccstatus::core::network::StatusRenderer;
+
+                if config.network.sla.is_empty() {
+                    eprintln!("No SLA targets configured (add [[network.sla]] entries)");
+                    std::process::exit(1);
+                }
+                let month = match month {
+                    Some(text) => match parse_month(&text) {
+                        Some(month) => month,
+                        None => {
+                            eprintln!("Invalid month '{}' (expected YYYY-MM)", text);
+                            std::process::exit(1);
+                        }
+                    },
+                    None => month_of(unix_now_secs()),
+                };
+                let ledger = SlaLedger::load(&SlaLedger::default_path()?).await;
+                println!(
+                    "{}",
+                    StatusRenderer::new()
+                        .render_sla_report(&month, &ledger.compliance(&config.network.sla, &month))
+                );
+            }
+            #[cfg(not(feature = "network-monitoring"))]
+            {
+                let _ = month;
+                println!("Network monitoring not available (network-monitoring feature disabled)");
+            }
+        }
+        NetAction::Sync => {
+            #[cfg(feature = "state-sync")]
+            {
+                use ccstatus::config::HostPolicy;
+                use ccstatus::core::network::state_sync::StateSync;
+                use ccstatus::core::network::HttpMonitor;
+
+                let Some(ref sync_config) = config.network.sync else {
+                    eprintln!("State sync not configured (add a [network.sync] section)");
+                    std::process::exit(1);
+                };
+                let location = StateLocation::from_config(&config.network)
+                    .ok_or("Could not determine home directory")?;
+                let report = StateSync::from_config(sync_config)?
+                    .with_http_monitor(HttpMonitor::new(Some(location.monitoring_path()))?)
+                    .with_host_policy(HostPolicy::from_config(&config.network))
+                    .sync()
+                    .await?;
+                println!(
+                    "Synced: {} machines, {} shared samples",
+                    report.machines, report.shared_samples
+                );
+            }
+            #[cfg(not(feature = "state-sync"))]
+            {
+                eprintln!("State sync not available (state-sync feature disabled)");
+                std::process::exit(1);
+            }
+        }
+    }
 
     Ok(())
 }
@@ -0,0 +1,152 @@
+//! Thin async runtime shim
+//!
+//! Network monitoring only needs a handful of runtime services: offloading
+//! blocking work, file I/O, timers and fire-and-forget tasks. Routing them
+//! through this module keeps the rest of the crate executor-agnostic, so
+//! embedders can build against a runtime other than tokio:
+//!
+//! ```toml
+//! ccstatus = { version = "*", default-features = false, features = ["network-monitoring", "runtime-smol"] }
+//! ```
+//!
+//! `runtime-tokio` (default) wins when both backends are enabled.
+
+use std::fmt;
+use std::future::Future;
+use std::time::Duration;
+
+#[cfg(feature = "runtime-tokio")]
+use tokio as backend;
+
+#[cfg(all(feature = "runtime-smol", not(feature = "runtime-tokio")))]
+use smol as backend;
+
+/// Async filesystem operations of the active backend
+pub mod fs {
+    #[cfg(feature = "runtime-tokio")]
+    pub use tokio::fs::{
+        create_dir_all, read, read_to_string, remove_file, rename, set_permissions, write,
+    };
+
+    #[cfg(all(feature = "runtime-smol", not(feature = "runtime-tokio")))]
+    pub use smol::fs::{
+        create_dir_all, read, read_to_string, remove_file, rename, set_permissions, write,
+    };
+}
+
+/// Blocking task panicked or was cancelled
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JoinError(String);
+
+impl fmt::Display for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for JoinError {}
+
+/// Deadline passed before the future completed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elapsed;
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("deadline has elapsed")
+    }
+}
+
+impl std::error::Error for Elapsed {}
+
+/// Drive `future` to completion on a fresh single-threaded runtime
+#[cfg(feature = "runtime-tokio")]
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    backend::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("failed to build tokio runtime")
+        .block_on(future)
+}
+
+/// Drive `future` to completion on the calling thread
+#[cfg(all(feature = "runtime-smol", not(feature = "runtime-tokio")))]
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    backend::block_on(future)
+}
+
+/// Run `f` on the backend's blocking thread pool
+#[cfg(feature = "runtime-tokio")]
+pub async fn spawn_blocking<F, R>(f: F) -> Result<R, JoinError>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    backend::task::spawn_blocking(f)
+        .await
+        .map_err(|e| JoinError(e.to_string()))
+}
+
+/// Run `f` on the backend's blocking thread pool
+#[cfg(all(feature = "runtime-smol", not(feature = "runtime-tokio")))]
+pub async fn spawn_blocking<F, R>(f: F) -> Result<R, JoinError>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    use std::panic::AssertUnwindSafe;
+
+    backend::unblock(move || std::panic::catch_unwind(AssertUnwindSafe(f)))
+        .await
+        .map_err(|_| JoinError("blocking task panicked".to_string()))
+}
+
+/// Spawn a detached background task
+#[cfg(feature = "runtime-tokio")]
+pub fn spawn<F>(future: F)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    backend::spawn(future);
+}
+
+/// Spawn a detached background task
+#[cfg(all(feature = "runtime-smol", not(feature = "runtime-tokio")))]
+pub fn spawn<F>(future: F)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    backend::spawn(future).detach();
+}
+
+/// Wait for `duration`
+#[cfg(feature = "runtime-tokio")]
+pub async fn sleep(duration: Duration) {
+    backend::time::sleep(duration).await;
+}
+
+/// Wait for `duration`
+#[cfg(all(feature = "runtime-smol", not(feature = "runtime-tokio")))]
+pub async fn sleep(duration: Duration) {
+    backend::Timer::after(duration).await;
+}
+
+/// Await `future`, giving up after `duration`
+pub async fn timeout<F: Future>(duration: Duration, future: F) -> Result<F::Output, Elapsed> {
+    #[cfg(feature = "runtime-tokio")]
+    {
+        backend::time::timeout(duration, future)
+            .await
+            .map_err(|_| Elapsed)
+    }
+
+    #[cfg(all(feature = "runtime-smol", not(feature = "runtime-tokio")))]
+    {
+        use futures::future::{select, Either};
+
+        let future = std::pin::pin!(future);
+        match select(future, std::pin::pin!(sleep(duration))).await {
+            Either::Left((output, _)) => Ok(output),
+            Either::Right(_) => Err(Elapsed),
+        }
+    }
+}
@@ -76,9 +76,11 @@ impl App {
 
         // If a theme is specified, reload it to get the latest changes
         if !config.theme.is_empty() && config.theme != "default" {
-            if let Ok(theme_config) =
+            if let Ok(mut theme_config) =
                 crate::ui::themes::ThemePresets::load_theme_from_file(&config.theme)
             {
+                // Network settings are user-level, not part of a theme
+                theme_config.network = config.network.clone();
                 config = theme_config;
             }
         }
@@ -586,7 +588,9 @@ impl App {
     }
 
     fn switch_to_theme(&mut self, theme_name: &str) {
+        let network = self.config.network.clone();
         self.config = crate::ui::themes::ThemePresets::get_theme(theme_name);
+        self.config.network = network;
         self.selected_segment = 0;
         self.preview.update_preview(&self.config);
         self.status_message = Some(format!("Switched to {} theme", theme_name));
@@ -595,7 +599,9 @@ impl App {
     /// Reset current theme to its default configuration
     fn reset_to_theme_defaults(&mut self) {
         let current_theme = self.config.theme.clone();
+        let network = self.config.network.clone();
         self.config = crate::ui::themes::ThemePresets::get_theme(&current_theme);
+        self.config.network = network;
         self.selected_segment = 0;
         self.preview.update_preview(&self.config);
         self.status_message = Some(format!("Reset {} theme to defaults", current_theme));
@@ -668,6 +674,19 @@ impl App {
                 SegmentId::Git => "Git",
                 SegmentId::Usage => "Usage",
                 SegmentId::Update => "Update",
+                SegmentId::Stopwatch => "Stopwatch",
+                SegmentId::Cost => "Cost",
+                SegmentId::BurnRate => "Burn Rate",
+                SegmentId::Block => "Usage Block",
+                SegmentId::Quota => "Weekly Quota",
+                SegmentId::Project => "Project Stats",
+                SegmentId::DiffStat => "Lines Changed",
+                SegmentId::Mode => "Mode",
+                SegmentId::Host => "Host",
+                SegmentId::Env => "Environment",
+                SegmentId::Version => "Version",
+                SegmentId::Compact => "Compaction",
+                SegmentId::Custom => "Custom",
             };
             let is_enabled = segment.enabled;
             self.status_message = Some(format!(
@@ -149,6 +149,83 @@ impl PreviewComponent {
                         map
                     },
                 },
+                SegmentId::Stopwatch => SegmentData {
+                    primary: "1m 42s".to_string(),
+                    secondary: "".to_string(),
+                    metadata: {
+                        let mut map = HashMap::new();
+                        map.insert("elapsed_secs".to_string(), "102".to_string());
+                        map
+                    },
+                },
+                SegmentId::Cost => SegmentData {
+                    primary: "$1.27".to_string(),
+                    secondary: "".to_string(),
+                    metadata: {
+                        let mut map = HashMap::new();
+                        map.insert("usd".to_string(), "1.270000".to_string());
+                        map
+                    },
+                },
+                SegmentId::BurnRate => SegmentData {
+                    primary: "$4.20/h".to_string(),
+                    secondary: "".to_string(),
+                    metadata: {
+                        let mut map = HashMap::new();
+                        map.insert("level".to_string(), "normal".to_string());
+                        map
+                    },
+                },
+                SegmentId::Block => SegmentData {
+                    primary: "2h 13m left".to_string(),
+                    secondary: "".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::Quota => SegmentData {
+                    primary: "$12.40/$50.00 wk".to_string(),
+                    secondary: "".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::Project => SegmentData {
+                    primary: "$52.30 · 14 sessions".to_string(),
+                    secondary: "".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::DiffStat => SegmentData {
+                    primary: "+120 -34".to_string(),
+                    secondary: "".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::Mode => SegmentData {
+                    primary: "auto-accept".to_string(),
+                    secondary: "".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::Host => SegmentData {
+                    primary: "dev@buildbox".to_string(),
+                    secondary: "".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::Env => SegmentData {
+                    primary: "py .venv · node 20".to_string(),
+                    secondary: "".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::Version => SegmentData {
+                    primary: format!("CC 1.0.80 · ccstatus {}", env!("CARGO_PKG_VERSION")),
+                    secondary: "".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::Compact => SegmentData {
+                    primary: "5k to compact".to_string(),
+                    secondary: "".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::Custom => SegmentData {
+                    primary: "custom".to_string(),
+                    secondary: "".to_string(),
+                    metadata: HashMap::new(),
+                },
             };
 
             segments_data.push((segment_config.clone(), mock_data));
@@ -53,6 +53,19 @@ impl SegmentListComponent {
                     SegmentId::Git => "Git",
                     SegmentId::Usage => "Usage",
                     SegmentId::Update => "Update",
+                    SegmentId::Stopwatch => "Stopwatch",
+                    SegmentId::Cost => "Cost",
+                    SegmentId::BurnRate => "Burn Rate",
+                    SegmentId::Block => "Usage Block",
+                    SegmentId::Quota => "Weekly Quota",
+                    SegmentId::Project => "Project Stats",
+                    SegmentId::DiffStat => "Lines Changed",
+                    SegmentId::Mode => "Mode",
+                    SegmentId::Host => "Host",
+                    SegmentId::Env => "Environment",
+                    SegmentId::Version => "Version",
+                    SegmentId::Compact => "Compaction",
+                    SegmentId::Custom => "Custom",
                 };
 
                 if is_selected {
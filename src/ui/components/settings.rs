@@ -33,6 +33,19 @@ impl SettingsComponent {
                 SegmentId::Git => "Git",
                 SegmentId::Usage => "Usage",
                 SegmentId::Update => "Update",
+                SegmentId::Stopwatch => "Stopwatch",
+                SegmentId::Cost => "Cost",
+                SegmentId::BurnRate => "Burn Rate",
+                SegmentId::Block => "Usage Block",
+                SegmentId::Quota => "Weekly Quota",
+                SegmentId::Project => "Project Stats",
+                SegmentId::DiffStat => "Lines Changed",
+                SegmentId::Mode => "Mode",
+                SegmentId::Host => "Host",
+                SegmentId::Env => "Environment",
+                SegmentId::Version => "Version",
+                SegmentId::Compact => "Compaction",
+                SegmentId::Custom => "Custom",
             };
             let current_icon = match config.style.mode {
                 StyleMode::Plain => &segment.icon.plain,
@@ -1,9 +1,12 @@
 // Theme presets for TUI configuration
 
 use crate::config::{
-    AnsiColor, ColorConfig, Config, IconConfig, SegmentConfig, SegmentId, StyleConfig, StyleMode,
-    TextStyleConfig,
+    AnsiColor, ColorConfig, Config, FeatureFlags, IconConfig, NetworkConfig, SegmentConfig,
+    SegmentId, StyleConfig, StyleMode, TextStyleConfig,
 };
+use crate::core::attention::AttentionStyle;
+use crate::core::format::NumberFormat;
+use crate::core::statusline::themes::ColorScheme;
 use std::collections::HashMap;
 
 pub struct ThemePresets;
@@ -20,6 +23,8 @@ impl ThemePresets {
             "minimal" => Self::get_minimal(),
             "gruvbox" => Self::get_gruvbox(),
             "nord" => Self::get_nord(),
+            "solarized" => Self::get_solarized(),
+            "monochrome" => Self::get_monochrome(),
             "powerline-dark" => Self::get_powerline_dark(),
             "powerline-light" => Self::get_powerline_light(),
             "powerline-rose-pine" => Self::get_powerline_rose_pine(),
@@ -80,6 +85,8 @@ impl ThemePresets {
             "minimal".to_string(),
             "gruvbox".to_string(),
             "nord".to_string(),
+            "solarized".to_string(),
+            "monochrome".to_string(),
             "powerline-dark".to_string(),
             "powerline-light".to_string(),
             "powerline-rose-pine".to_string(),
@@ -109,6 +116,11 @@ impl ThemePresets {
             ("minimal", "Minimal theme with reduced colors"),
             ("gruvbox", "Gruvbox color scheme"),
             ("nord", "Nord color scheme"),
+            (
+                "solarized",
+                "Solarized accents, readable on light and dark terminals",
+            ),
+            ("monochrome", "No colors, terminal default foreground"),
             ("powerline-dark", "Dark powerline theme"),
             ("powerline-light", "Light powerline theme"),
             ("powerline-rose-pine", "Rose Pine powerline theme"),
@@ -121,6 +133,7 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::Plain,
                 separator: " | ".to_string(),
+                icons: None,
             },
             segments: {
                 let mut segments = vec![
@@ -134,6 +147,18 @@ impl ThemePresets {
                 segments
             },
             theme: "default".to_string(),
+            locale: None,
+            template: None,
+            colors: HashMap::new(),
+            formatting: NumberFormat::default(),
+            attention: AttentionStyle::default(),
+            network: NetworkConfig::default(),
+            flags: FeatureFlags::default(),
+            custom_segments: Vec::new(),
+            alerts: Vec::new(),
+            notifications: None,
+            heartbeat: None,
+            proxy: None,
         }
     }
 
@@ -237,6 +262,7 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::Plain,
                 separator: " │ ".to_string(), // Thin vertical bar
+                icons: None,
             },
             segments: {
                 let mut segments = vec![
@@ -250,6 +276,18 @@ impl ThemePresets {
                 segments
             },
             theme: "minimal".to_string(),
+            locale: None,
+            template: None,
+            colors: HashMap::new(),
+            formatting: NumberFormat::default(),
+            attention: AttentionStyle::default(),
+            network: NetworkConfig::default(),
+            flags: FeatureFlags::default(),
+            custom_segments: Vec::new(),
+            alerts: Vec::new(),
+            notifications: None,
+            heartbeat: None,
+            proxy: None,
         }
     }
 
@@ -258,6 +296,7 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: " | ".to_string(),
+                icons: None,
             },
             segments: {
                 let mut segments = vec![
@@ -271,6 +310,18 @@ impl ThemePresets {
                 segments
             },
             theme: "gruvbox".to_string(),
+            locale: None,
+            template: None,
+            colors: HashMap::new(),
+            formatting: NumberFormat::default(),
+            attention: AttentionStyle::default(),
+            network: NetworkConfig::default(),
+            flags: FeatureFlags::default(),
+            custom_segments: Vec::new(),
+            alerts: Vec::new(),
+            notifications: None,
+            heartbeat: None,
+            proxy: None,
         }
     }
 
@@ -279,6 +330,7 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: "".to_string(),
+                icons: None,
             },
             segments: {
                 let mut segments = vec![
@@ -292,9 +344,38 @@ impl ThemePresets {
                 segments
             },
             theme: "nord".to_string(),
+            locale: None,
+            template: None,
+            colors: HashMap::new(),
+            formatting: NumberFormat::default(),
+            attention: AttentionStyle::default(),
+            network: NetworkConfig::default(),
+            flags: FeatureFlags::default(),
+            custom_segments: Vec::new(),
+            alerts: Vec::new(),
+            notifications: None,
+            heartbeat: None,
+            proxy: None,
         }
     }
 
+    pub fn get_solarized() -> Config {
+        Self::with_color_scheme(Self::get_default(), ColorScheme::Solarized)
+    }
+
+    pub fn get_monochrome() -> Config {
+        Self::with_color_scheme(Self::get_default(), ColorScheme::Monochrome)
+    }
+
+    /// Recolor `config`'s segments with `scheme` and select it as the theme
+    fn with_color_scheme(mut config: Config, scheme: ColorScheme) -> Config {
+        for segment in &mut config.segments {
+            segment.colors = scheme.segment_colors(segment.id);
+        }
+        config.theme = scheme.name().to_string();
+        config
+    }
+
     // Minimal theme segments
     fn minimal_model_segment() -> SegmentConfig {
         SegmentConfig {
@@ -400,11 +481,7 @@ impl ThemePresets {
                 plain: "🤖".to_string(),
                 nerd_font: "\u{e26d}".to_string(),
             },
-            colors: ColorConfig {
-                icon: Some(AnsiColor::Color16 { c16: 14 }),
-                text: Some(AnsiColor::Color16 { c16: 14 }),
-                background: None,
-            },
+            colors: ColorScheme::Gruvbox.segment_colors(SegmentId::Model),
             styles: TextStyleConfig { text_bold: true },
             options: HashMap::new(),
         }
@@ -418,11 +495,7 @@ impl ThemePresets {
                 plain: "📁".to_string(),
                 nerd_font: "\u{f024b}".to_string(),
             },
-            colors: ColorConfig {
-                icon: Some(AnsiColor::Color16 { c16: 11 }),
-                text: Some(AnsiColor::Color16 { c16: 10 }),
-                background: None,
-            },
+            colors: ColorScheme::Gruvbox.segment_colors(SegmentId::Directory),
             styles: TextStyleConfig { text_bold: true },
             options: HashMap::new(),
         }
@@ -436,11 +509,7 @@ impl ThemePresets {
                 plain: "🌿".to_string(),
                 nerd_font: "\u{f02a2}".to_string(),
             },
-            colors: ColorConfig {
-                icon: Some(AnsiColor::Color16 { c16: 4 }),
-                text: Some(AnsiColor::Color16 { c16: 4 }),
-                background: None,
-            },
+            colors: ColorScheme::Gruvbox.segment_colors(SegmentId::Git),
             styles: TextStyleConfig { text_bold: true },
             options: {
                 let mut opts = HashMap::new();
@@ -458,11 +527,7 @@ impl ThemePresets {
                 plain: "⚡".to_string(),
                 nerd_font: "\u{f49b}".to_string(),
             },
-            colors: ColorConfig {
-                icon: Some(AnsiColor::Color16 { c16: 5 }),
-                text: Some(AnsiColor::Color16 { c16: 5 }),
-                background: None,
-            },
+            colors: ColorScheme::Gruvbox.segment_colors(SegmentId::Usage),
             styles: TextStyleConfig { text_bold: true },
             options: HashMap::new(),
         }
@@ -477,11 +542,7 @@ impl ThemePresets {
                 plain: "📶".to_string(),
                 nerd_font: "\u{f1eb}".to_string(), // Wi-Fi icon
             },
-            colors: ColorConfig {
-                icon: Some(AnsiColor::Color16 { c16: 10 }), // Green
-                text: Some(AnsiColor::Color16 { c16: 10 }),
-                background: None,
-            },
+            colors: ColorScheme::Gruvbox.segment_colors(SegmentId::Network),
             styles: TextStyleConfig { text_bold: true },
             options: HashMap::new(),
         }
@@ -496,23 +557,7 @@ impl ThemePresets {
                 plain: "🤖".to_string(),
                 nerd_font: "\u{e26d}".to_string(),
             },
-            colors: ColorConfig {
-                icon: Some(AnsiColor::Rgb {
-                    r: 191,
-                    g: 97,
-                    b: 106,
-                }),
-                text: Some(AnsiColor::Rgb {
-                    r: 191,
-                    g: 97,
-                    b: 106,
-                }),
-                background: Some(AnsiColor::Rgb {
-                    r: 76,
-                    g: 86,
-                    b: 106,
-                }),
-            },
+            colors: ColorScheme::Nord.segment_colors(SegmentId::Model),
             styles: TextStyleConfig::default(),
             options: HashMap::new(),
         }
@@ -526,23 +571,7 @@ impl ThemePresets {
                 plain: "📁".to_string(),
                 nerd_font: "\u{f024b}".to_string(),
             },
-            colors: ColorConfig {
-                icon: Some(AnsiColor::Rgb {
-                    r: 235,
-                    g: 203,
-                    b: 139,
-                }),
-                text: Some(AnsiColor::Rgb {
-                    r: 163,
-                    g: 190,
-                    b: 140,
-                }),
-                background: Some(AnsiColor::Rgb {
-                    r: 67,
-                    g: 76,
-                    b: 94,
-                }),
-            },
+            colors: ColorScheme::Nord.segment_colors(SegmentId::Directory),
             styles: TextStyleConfig::default(),
             options: HashMap::new(),
         }
@@ -556,23 +585,7 @@ impl ThemePresets {
                 plain: "🌿".to_string(),
                 nerd_font: "\u{f02a2}".to_string(),
             },
-            colors: ColorConfig {
-                icon: Some(AnsiColor::Rgb {
-                    r: 136,
-                    g: 192,
-                    b: 208,
-                }),
-                text: Some(AnsiColor::Rgb {
-                    r: 136,
-                    g: 192,
-                    b: 208,
-                }),
-                background: Some(AnsiColor::Rgb {
-                    r: 59,
-                    g: 66,
-                    b: 82,
-                }),
-            },
+            colors: ColorScheme::Nord.segment_colors(SegmentId::Git),
             styles: TextStyleConfig::default(),
             options: {
                 let mut opts = HashMap::new();
@@ -590,23 +603,7 @@ impl ThemePresets {
                 plain: "⚡".to_string(),
                 nerd_font: "\u{f49b}".to_string(),
             },
-            colors: ColorConfig {
-                icon: Some(AnsiColor::Rgb {
-                    r: 46,
-                    g: 52,
-                    b: 64,
-                }),
-                text: Some(AnsiColor::Rgb {
-                    r: 46,
-                    g: 52,
-                    b: 64,
-                }),
-                background: Some(AnsiColor::Rgb {
-                    r: 180,
-                    g: 142,
-                    b: 173,
-                }),
-            },
+            colors: ColorScheme::Nord.segment_colors(SegmentId::Usage),
             styles: TextStyleConfig::default(),
             options: HashMap::new(),
         }
@@ -621,23 +618,7 @@ impl ThemePresets {
                 plain: "📶".to_string(),
                 nerd_font: "\u{f1eb}".to_string(), // Wi-Fi icon
             },
-            colors: ColorConfig {
-                icon: Some(AnsiColor::Rgb {
-                    r: 163,
-                    g: 190,
-                    b: 140,
-                }),
-                text: Some(AnsiColor::Rgb {
-                    r: 163,
-                    g: 190,
-                    b: 140,
-                }),
-                background: Some(AnsiColor::Rgb {
-                    r: 53,
-                    g: 57,
-                    b: 69,
-                }),
-            },
+            colors: ColorScheme::Nord.segment_colors(SegmentId::Network),
             styles: TextStyleConfig::default(),
             options: HashMap::new(),
         }
@@ -649,6 +630,7 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: "".to_string(),
+                icons: None,
             },
             segments: {
                 let mut segments = vec![
@@ -662,6 +644,18 @@ impl ThemePresets {
                 segments
             },
             theme: "powerline-dark".to_string(),
+            locale: None,
+            template: None,
+            colors: HashMap::new(),
+            formatting: NumberFormat::default(),
+            attention: AttentionStyle::default(),
+            network: NetworkConfig::default(),
+            flags: FeatureFlags::default(),
+            custom_segments: Vec::new(),
+            alerts: Vec::new(),
+            notifications: None,
+            heartbeat: None,
+            proxy: None,
         }
     }
 
@@ -826,6 +820,7 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: "".to_string(),
+                icons: None,
             },
             segments: {
                 let mut segments = vec![
@@ -839,6 +834,18 @@ impl ThemePresets {
                 segments
             },
             theme: "powerline-light".to_string(),
+            locale: None,
+            template: None,
+            colors: HashMap::new(),
+            formatting: NumberFormat::default(),
+            attention: AttentionStyle::default(),
+            network: NetworkConfig::default(),
+            flags: FeatureFlags::default(),
+            custom_segments: Vec::new(),
+            alerts: Vec::new(),
+            notifications: None,
+            heartbeat: None,
+            proxy: None,
         }
     }
 
@@ -995,6 +1002,7 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: "".to_string(),
+                icons: None,
             },
             segments: {
                 let mut segments = vec![
@@ -1008,6 +1016,18 @@ impl ThemePresets {
                 segments
             },
             theme: "powerline-rose-pine".to_string(),
+            locale: None,
+            template: None,
+            colors: HashMap::new(),
+            formatting: NumberFormat::default(),
+            attention: AttentionStyle::default(),
+            network: NetworkConfig::default(),
+            flags: FeatureFlags::default(),
+            custom_segments: Vec::new(),
+            alerts: Vec::new(),
+            notifications: None,
+            heartbeat: None,
+            proxy: None,
         }
     }
 
@@ -1172,6 +1192,7 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: "".to_string(),
+                icons: None,
             },
             segments: {
                 let mut segments = vec![
@@ -1185,6 +1206,18 @@ impl ThemePresets {
                 segments
             },
             theme: "powerline-tokyo-night".to_string(),
+            locale: None,
+            template: None,
+            colors: HashMap::new(),
+            formatting: NumberFormat::default(),
+            attention: AttentionStyle::default(),
+            network: NetworkConfig::default(),
+            flags: FeatureFlags::default(),
+            custom_segments: Vec::new(),
+            alerts: Vec::new(),
+            notifications: None,
+            heartbeat: None,
+            proxy: None,
         }
     }
 
@@ -1,3 +1,8 @@
+use crate::config::HostPolicy;
+
+const GEO_PRIMARY_URL: &str = "http://myip.ipip.net";
+const GEO_FALLBACK_URL: &str = "http://cip.cc";
+
 /// Geographic detection result
 #[derive(Debug, Clone)]
 pub struct GeoResult {
@@ -5,48 +10,53 @@ pub struct GeoResult {
     pub detected_at: chrono::DateTime<chrono::Utc>,
 }
 
-/// Detect if user is in China by checking IP geolocation
+/// Detect if user is in China by checking IP geolocation, asking only lookup
+/// endpoints `policy` allows
 /// TTL: 24 hours, with CI environment mocking support
-pub fn detect_china_ttl24h() -> bool {
+pub fn detect_china_ttl24h(policy: &HostPolicy) -> bool {
     // Mock geo detection in CI environments to avoid real network calls
     if std::env::var("CI").is_ok() || std::env::var("GITHUB_ACTIONS").is_ok() {
         // Default to false (non-China) in CI unless explicitly overridden
-        let mock_value = std::env::var("CCSTATUS_TEST_CHINA_GEO")
-            .map(|v| v.to_lowercase() == "true")
-            .unwrap_or(false);
-        return mock_value;
+        return crate::core::env::TEST_CHINA_GEO.is_enabled();
     }
 
     // Try to detect China location by checking myip.ipip.net
-    detect_china_online().unwrap_or_default()
+    detect_china_online(policy).unwrap_or_default()
 }
 
 /// Perform online China detection with fallback endpoints
-fn detect_china_online() -> Result<bool, Box<dyn std::error::Error>> {
+fn detect_china_online(policy: &HostPolicy) -> Result<bool, Box<dyn std::error::Error>> {
+    // Respect network.allowed_hosts: skip lookup endpoints outside the allowlist
     let client: ureq::Agent = ureq::Agent::config_builder()
         .timeout_global(Some(std::time::Duration::from_secs(3)))
         .build()
         .into();
 
     // Primary: myip.ipip.net (plaintext with Chinese location info)
-    match client
-        .get("http://myip.ipip.net")
-        .header(
-            "User-Agent",
-            &format!("CCstatus/{}", env!("CARGO_PKG_VERSION")),
-        )
-        .call()
-    {
-        Ok(mut response) if response.status().as_u16() == 200 => {
-            let body = response.body_mut().read_to_string()?;
-            return Ok(body.contains("中国"));
+    if policy.is_url_allowed(GEO_PRIMARY_URL) {
+        match client
+            .get(GEO_PRIMARY_URL)
+            .header(
+                "User-Agent",
+                &format!("CCstatus/{}", env!("CARGO_PKG_VERSION")),
+            )
+            .call()
+        {
+            Ok(mut response) if response.status().as_u16() == 200 => {
+                let body = response.body_mut().read_to_string()?;
+                return Ok(body.contains("中国"));
+            }
+            _ => {}
         }
-        _ => {}
     }
 
     // Fallback: cip.cc (plaintext, includes lines like "地址 : 中国 ...")
+    if !policy.is_url_allowed(GEO_FALLBACK_URL) {
+        return Err("blocked_by_policy: geo endpoints not in network.allowed_hosts".into());
+    }
+
     match client
-        .get("http://cip.cc")
+        .get(GEO_FALLBACK_URL)
         .header(
             "User-Agent",
             &format!("CCstatus/{}", env!("CARGO_PKG_VERSION")),
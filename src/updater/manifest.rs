@@ -1,3 +1,4 @@
+use crate::config::{FeatureFlags, ProxyPolicy, RemoteFlags};
 use serde::{Deserialize, Serialize};
 
 /// Manifest structure for update information
@@ -9,6 +10,9 @@ pub struct Manifest {
     pub published_at: String,
     #[serde(default)]
     pub assets: Vec<ManifestAsset>,
+    /// Remote kill switches (e.g. `{"red_probing": false}`); local `[flags]` take precedence
+    #[serde(default, skip_serializing_if = "FeatureFlags::is_empty")]
+    pub flags: FeatureFlags,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,22 +33,45 @@ impl Manifest {
     pub fn to_json(&self) -> Result<String, serde_json::Error> {
         serde_json::to_string_pretty(self)
     }
+
+    /// Flags to persist from this manifest; an absent section clears earlier kill switches
+    pub fn remote_flags(&self) -> RemoteFlags {
+        RemoteFlags {
+            flags: self.flags.clone(),
+            manifest_version: Some(self.version.clone()),
+            fetched_at: Some(chrono::Utc::now().to_rfc3339()),
+        }
+    }
 }
 
 /// Manifest client for fetching update information
 pub struct ManifestClient {
     client: ureq::Agent,
+    proxy: ProxyPolicy,
 }
 
 impl ManifestClient {
-    /// Create new manifest client with strict timeouts for silent failure
+    /// Create new manifest client with strict timeouts for silent failure, going
+    /// through the proxy of the user's config.toml
     pub fn new() -> Self {
-        let client: ureq::Agent = ureq::Agent::config_builder()
+        Self::with_proxy(ProxyPolicy::load())
+    }
+
+    /// Client going through `proxy` rather than the configured one
+    pub fn with_proxy(proxy: ProxyPolicy) -> Self {
+        Self {
+            client: Self::agent(None),
+            proxy,
+        }
+    }
+
+    /// Agent with the strict timeout, connecting through `proxy` or directly
+    fn agent(proxy: Option<ureq::Proxy>) -> ureq::Agent {
+        ureq::Agent::config_builder()
             .timeout_global(Some(std::time::Duration::from_secs(3)))
+            .proxy(proxy)
             .build()
-            .into();
-
-        Self { client }
+            .into()
     }
 
     /// Compare version with current using semver
@@ -66,7 +93,16 @@ impl ManifestClient {
 
         let host = url_resolver::extract_host_from_url(url).unwrap_or_else(|| url.to_string());
 
-        let mut request = self.client.get(url).header(
+        // ureq reads no NO_PROXY, so hosts that bypass the proxy get a direct agent
+        let proxied;
+        let client = match self.proxy.proxy_for(url) {
+            Some(proxy) => {
+                proxied = Self::agent(Some(ureq::Proxy::new(proxy)?));
+                &proxied
+            }
+            None => &self.client,
+        };
+        let mut request = client.get(url).header(
             "User-Agent",
             &format!("CCstatus/{}", env!("CARGO_PKG_VERSION")),
         );
@@ -1,3 +1,5 @@
+use crate::core::i18n::{Locale, Text};
+use crate::core::icons::{Icon, IconSet};
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "self-update")]
@@ -42,15 +44,31 @@ pub struct UpdateState {
 }
 
 impl UpdateState {
-    /// Get status bar display text
-    pub fn status_text(&self) -> Option<String> {
+    /// Get status bar display text, with glyphs from `icons` and words in `locale`
+    pub fn status_text(&self, icons: IconSet, locale: Locale) -> Option<String> {
         match &self.status {
             #[cfg(feature = "self-update")]
-            UpdateStatus::Ready { version, .. } => Some(format!("\u{f06b0} Update v{}!", version)),
+            UpdateStatus::Ready { version, .. } => Some(format!(
+                "{} {} v{}!",
+                icons.glyph(Icon::UpdateAvailable),
+                locale.text(Text::UpdateAvailable),
+                version
+            )),
             #[cfg(not(feature = "self-update"))]
-            UpdateStatus::Ready { version, .. } => Some(format!("\u{f06b0} Update v{}!", version)),
-            UpdateStatus::Downloading { progress } => Some(format!("\u{f01da} {}%", progress)),
-            UpdateStatus::Installing => Some("\u{f01da} Installing...".to_string()),
+            UpdateStatus::Ready { version, .. } => Some(format!(
+                "{} {} v{}!",
+                icons.glyph(Icon::UpdateAvailable),
+                locale.text(Text::UpdateAvailable),
+                version
+            )),
+            UpdateStatus::Downloading { progress } => {
+                Some(format!("{} {}%", icons.glyph(Icon::Downloading), progress))
+            }
+            UpdateStatus::Installing => Some(format!(
+                "{} {}",
+                icons.glyph(Icon::Downloading),
+                locale.text(Text::Installing)
+            )),
             #[cfg(feature = "self-update")]
             UpdateStatus::Completed {
                 version,
@@ -60,15 +78,23 @@ impl UpdateState {
                 let now = Utc::now();
                 let seconds_passed = now.signed_duration_since(*completed_at).num_seconds();
                 if seconds_passed < 10 {
-                    Some(format!("\u{f058} Updated v{}!", version))
+                    Some(format!(
+                        "{} {} v{}!",
+                        icons.glyph(Icon::Updated),
+                        locale.text(Text::Updated),
+                        version
+                    ))
                 } else {
                     None
                 }
             }
             #[cfg(not(feature = "self-update"))]
-            UpdateStatus::Completed { version, .. } => {
-                Some(format!("\u{f058} Updated v{}!", version))
-            }
+            UpdateStatus::Completed { version, .. } => Some(format!(
+                "{} {} v{}!",
+                icons.glyph(Icon::Updated),
+                locale.text(Text::Updated),
+                version
+            )),
             _ => None,
         }
     }
@@ -238,13 +264,11 @@ impl UpdateState {
         }
 
         // Check time interval (1 hour)
-        if let Some(last_check) = self.last_check {
-            let now = Utc::now();
-            let hours_passed = now.signed_duration_since(last_check).num_hours();
-            hours_passed >= 1
-        } else {
-            true
-        }
+        state::UPDATE_CHECK_SCHEDULE.is_due(
+            "update-check",
+            self.last_check.map(|last| last.timestamp()),
+            Utc::now().timestamp(),
+        )
     }
 
     /// Check if update check should be triggered (disabled when not using legacy-update)
@@ -1,7 +1,20 @@
+use crate::config::HostPolicy;
+use crate::core::compat::{self, CompatKind, CompatWarning};
+use crate::core::recovery;
+use crate::core::scheduler::Schedule;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Minimum spacing of update checks
+pub const UPDATE_CHECK_SCHEDULE: Schedule = Schedule::every(60 * 60);
+
+/// Age of the last check after which a GREEN tick forces one
+pub const GREEN_OVERDUE_SCHEDULE: Schedule = Schedule::every(30 * 60);
+
+/// Lifetime of the geographic verdict
+pub const GEO_VERDICT_SCHEDULE: Schedule = Schedule::every(24 * 60 * 60);
+
 /// Update state file structure (stored as ccstatus-update.json)
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct UpdateStateFile {
@@ -25,6 +38,15 @@ pub struct UpdateStateFile {
     pub last_prompted_version: Option<String>,
 }
 
+/// Debug log entry of the update state machine (`CCSTATUS_DEBUG` or `init_logging`)
+fn debug_log(event: &str, message: &str) {
+    crate::core::network::debug_logger::get_debug_logger().debug_sync(
+        "UpdateStateFile",
+        event,
+        message,
+    );
+}
+
 impl UpdateStateFile {
     /// Load state from ccstatus-update.json with backward compatibility migration
     pub fn load() -> Self {
@@ -35,10 +57,16 @@ impl UpdateStateFile {
 
         let state_file = config_dir.join("ccstatus-update.json");
 
-        if let Ok(content) = std::fs::read_to_string(&state_file) {
-            let mut state = serde_json::from_str::<UpdateStateFile>(&content).unwrap_or_default();
+        if let Ok(content) = std::fs::read(&state_file) {
+            let mut state =
+                recovery::parse_json::<UpdateStateFile>(&state_file, &content).unwrap_or_default();
             // Migrate legacy last_prompted_version to version_prompt_dates
             if let Some(legacy_version) = state.last_prompted_version.take() {
+                compat::record(CompatWarning::new(
+                    CompatKind::StateField,
+                    "last_prompted_version",
+                    "migrated to version_prompt_dates in ccstatus-update.json",
+                ));
                 // Use yesterday's date to ensure it doesn't block today's prompt
                 let yesterday = Utc::now() - chrono::Duration::days(1);
                 state.version_prompt_dates.insert(legacy_version, yesterday);
@@ -49,165 +77,157 @@ impl UpdateStateFile {
         }
     }
 
-    /// Update system triggered from COLD window
-    pub fn tick_from_cold(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        // Debug logging when CCSTATUS_DEBUG=true
-        if crate::core::network::types::parse_env_bool("CCSTATUS_DEBUG") {
-            eprintln!(
-                "[DEBUG] UpdateStateFile::tick_from_cold() - COLD window update trigger activated"
-            );
-        }
+    /// Update system triggered from COLD window; requests go only to hosts `hosts` allows
+    pub fn tick_from_cold(&mut self, hosts: &HostPolicy) -> Result<(), Box<dyn std::error::Error>> {
+        debug_log("tick_from_cold", "COLD window update trigger activated");
 
         if !self.should_check_for_updates() {
-            if crate::core::network::types::parse_env_bool("CCSTATUS_DEBUG") {
-                eprintln!(
-                    "[DEBUG] UpdateStateFile::tick_from_cold() - throttled, skipping update check"
-                );
-            }
+            debug_log("tick_from_cold", "throttled, skipping update check");
             return Ok(()); // Throttled
         }
 
-        if crate::core::network::types::parse_env_bool("CCSTATUS_DEBUG") {
-            eprintln!("[DEBUG] UpdateStateFile::tick_from_cold() - performing update check");
-        }
+        debug_log("tick_from_cold", "performing update check");
 
         // Perform update check with short timeout and silent failure
-        match self.check_for_updates_internal() {
+        match self.check_for_updates_internal(hosts) {
             Ok(update_available) => {
-                if crate::core::network::types::parse_env_bool("CCSTATUS_DEBUG") {
-                    eprintln!("[DEBUG] UpdateStateFile::tick_from_cold() - update check succeeded, update_available: {}", update_available);
-                }
+                debug_log(
+                    "tick_from_cold",
+                    &format!(
+                        "update check succeeded, update_available: {}",
+                        update_available
+                    ),
+                );
                 self.update_last_check();
                 self.save().ok(); // Silent failure on save
             }
             Err(e) => {
-                if crate::core::network::types::parse_env_bool("CCSTATUS_DEBUG") {
-                    eprintln!(
-                        "[DEBUG] UpdateStateFile::tick_from_cold() - update check failed: {}",
-                        e
-                    );
-                }
+                debug_log("tick_from_cold", &format!("update check failed: {}", e));
                 // Silent failure as specified in plan
             }
         }
         Ok(())
     }
 
-    /// Update system triggered from GREEN window
+    /// Update system triggered from GREEN window; requests go only to hosts `hosts` allows
     pub fn tick_from_green(
         &mut self,
         _green_window_id: &str,
+        hosts: &HostPolicy,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        // Debug logging when CCSTATUS_DEBUG=true
-        if crate::core::network::types::parse_env_bool("CCSTATUS_DEBUG") {
-            eprintln!("[DEBUG] UpdateStateFile::tick_from_green() - GREEN window update trigger activated");
-        }
+        debug_log("tick_from_green", "GREEN window update trigger activated");
 
         self.increment_green_ticks();
 
-        if crate::core::network::types::parse_env_bool("CCSTATUS_DEBUG") {
-            eprintln!(
-                "[DEBUG] UpdateStateFile::tick_from_green() - green_ticks_since_check: {}",
-                self.green_ticks_since_check
-            );
-        }
+        debug_log(
+            "tick_from_green",
+            &format!("green_ticks_since_check: {}", self.green_ticks_since_check),
+        );
 
         // Time-based fallback: if last_check is older than 30 minutes, force a check
-        let max_age_minutes: i64 = 30;
-        let is_overdue = match self.last_check {
-            Some(last) => {
-                let now = Utc::now();
-                now.signed_duration_since(last).num_minutes() >= max_age_minutes
-            }
-            None => true,
-        };
+        let is_overdue = GREEN_OVERDUE_SCHEDULE.is_due(
+            "update-check",
+            self.last_check.map(|last| last.timestamp()),
+            Utc::now().timestamp(),
+        );
 
         if is_overdue || self.should_trigger_green_check() {
-            if crate::core::network::types::parse_env_bool("CCSTATUS_DEBUG") {
-                if is_overdue {
-                    eprintln!(
-                        "[DEBUG] UpdateStateFile::tick_from_green() - last_check overdue (>= {} min), performing update check",
-                        max_age_minutes
-                    );
-                } else {
-                    eprintln!("[DEBUG] UpdateStateFile::tick_from_green() - threshold reached (12 ticks), performing update check");
-                }
+            if is_overdue {
+                debug_log(
+                    "tick_from_green",
+                    &format!(
+                        "last_check overdue (>= {} min), performing update check",
+                        GREEN_OVERDUE_SCHEDULE.interval_secs / 60
+                    ),
+                );
+            } else {
+                debug_log(
+                    "tick_from_green",
+                    "threshold reached (12 ticks), performing update check",
+                );
             }
             // Perform update check when threshold reached
-            match self.check_for_updates_internal() {
+            match self.check_for_updates_internal(hosts) {
                 Ok(update_available) => {
-                    if crate::core::network::types::parse_env_bool("CCSTATUS_DEBUG") {
-                        eprintln!("[DEBUG] UpdateStateFile::tick_from_green() - update check succeeded, update_available: {}", update_available);
-                    }
+                    debug_log(
+                        "tick_from_green",
+                        &format!(
+                            "update check succeeded, update_available: {}",
+                            update_available
+                        ),
+                    );
                     self.update_last_check();
                 }
                 Err(e) => {
-                    if crate::core::network::types::parse_env_bool("CCSTATUS_DEBUG") {
-                        eprintln!(
-                            "[DEBUG] UpdateStateFile::tick_from_green() - update check failed: {}",
-                            e
-                        );
-                    }
+                    debug_log("tick_from_green", &format!("update check failed: {}", e));
                 }
             }
             self.reset_green_ticks();
             self.save().ok(); // Silent failure on save
         } else {
-            if crate::core::network::types::parse_env_bool("CCSTATUS_DEBUG") {
-                eprintln!("[DEBUG] UpdateStateFile::tick_from_green() - threshold not reached, waiting for more GREEN ticks");
-            }
+            debug_log(
+                "tick_from_green",
+                "threshold not reached, waiting for more GREEN ticks",
+            );
         }
         Ok(())
     }
 
     /// Internal update check implementation
-    fn check_for_updates_internal(&mut self) -> Result<bool, Box<dyn std::error::Error>> {
+    fn check_for_updates_internal(
+        &mut self,
+        hosts: &HostPolicy,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
         use crate::updater::{geo, manifest::ManifestClient, url_resolver};
 
-        if crate::core::network::types::parse_env_bool("CCSTATUS_DEBUG") {
-            eprintln!(
-                "[DEBUG] UpdateStateFile::check_for_updates_internal() - starting update check"
-            );
-        }
+        debug_log("check_for_updates_internal", "starting update check");
 
         // Get or update geographic detection
         let is_china = if self.is_geo_verdict_valid() {
             let cached_verdict = self.geo_verdict.unwrap_or(false);
-            if crate::core::network::types::parse_env_bool("CCSTATUS_DEBUG") {
-                eprintln!("[DEBUG] UpdateStateFile::check_for_updates_internal() - using cached geo verdict: is_china={}", cached_verdict);
-            }
+            debug_log(
+                "check_for_updates_internal",
+                &format!("using cached geo verdict: is_china={}", cached_verdict),
+            );
             cached_verdict
         } else {
-            if crate::core::network::types::parse_env_bool("CCSTATUS_DEBUG") {
-                eprintln!("[DEBUG] UpdateStateFile::check_for_updates_internal() - geo cache expired, detecting location");
-            }
-            let detected = geo::detect_china_ttl24h();
-            if crate::core::network::types::parse_env_bool("CCSTATUS_DEBUG") {
-                eprintln!("[DEBUG] UpdateStateFile::check_for_updates_internal() - geo detection completed: is_china={}", detected);
-            }
+            debug_log(
+                "check_for_updates_internal",
+                "geo cache expired, detecting location",
+            );
+            let detected = geo::detect_china_ttl24h(hosts);
+            debug_log(
+                "check_for_updates_internal",
+                &format!("geo detection completed: is_china={}", detected),
+            );
             self.update_geo_verdict(detected);
             detected
         };
 
-        // Resolve URLs based on geography
-        let urls = url_resolver::resolve_manifest_url(is_china);
-        if crate::core::network::types::parse_env_bool("CCSTATUS_DEBUG") {
-            eprintln!("[DEBUG] UpdateStateFile::check_for_updates_internal() - resolved {} URLs for is_china={}", urls.len(), is_china);
+        // Resolve URLs based on geography, keeping only hosts allowed by network.allowed_hosts
+        let urls =
+            url_resolver::filter_allowed_urls(url_resolver::resolve_manifest_url(is_china), hosts);
+        if urls.is_empty() {
+            debug_log(
+                "check_for_updates_internal",
+                "blocked_by_policy: no manifest URL allowed by network.allowed_hosts",
+            );
+            return Ok(false);
         }
+        debug_log(
+            "check_for_updates_internal",
+            &format!("resolved {} URLs for is_china={}", urls.len(), is_china),
+        );
 
         // Try each URL in sequence with persistent caching
         let mut client = ManifestClient::new();
 
         // Try URLs manually to track which one succeeds for proper host caching
         for (index, url) in urls.iter().enumerate() {
-            if crate::core::network::types::parse_env_bool("CCSTATUS_DEBUG") {
-                eprintln!(
-                    "[DEBUG] UpdateStateFile::check_for_updates_internal() - trying URL {}/{}: {}",
-                    index + 1,
-                    urls.len(),
-                    url
-                );
-            }
+            debug_log(
+                "check_for_updates_internal",
+                &format!("trying URL {}/{}: {}", index + 1, urls.len(), url),
+            );
 
             match client.fetch_manifest_with_persistent_cache(
                 url,
@@ -215,9 +235,17 @@ impl UpdateStateFile {
                 &self.last_modified_map,
             ) {
                 Ok((Some(manifest), new_etag, new_last_modified)) => {
-                    if crate::core::network::types::parse_env_bool("CCSTATUS_DEBUG") {
-                        eprintln!("[DEBUG] UpdateStateFile::check_for_updates_internal() - manifest fetched successfully from URL {}, version: {}", index + 1, manifest.version);
-                    }
+                    debug_log(
+                        "check_for_updates_internal",
+                        &format!(
+                            "manifest fetched successfully from URL {}, version: {}",
+                            index + 1,
+                            manifest.version
+                        ),
+                    );
+
+                    // Persist manifest kill switches for the monitoring subsystems
+                    manifest.remote_flags().save().ok();
 
                     // Update persistent caches with new headers from successful URL
                     if let Some(host) = url_resolver::extract_host_from_url(url) {
@@ -233,39 +261,53 @@ impl UpdateStateFile {
                     if client.is_newer_version(&manifest.version)?
                         && self.should_prompt_for_version(&manifest.version)
                     {
-                        if crate::core::network::types::parse_env_bool("CCSTATUS_DEBUG") {
-                            eprintln!("[DEBUG] UpdateStateFile::check_for_updates_internal() - newer version available: {}, marking for prompt", manifest.version);
-                        }
+                        debug_log(
+                            "check_for_updates_internal",
+                            &format!(
+                                "newer version available: {}, marking for prompt",
+                                manifest.version
+                            ),
+                        );
                         self.mark_version_prompted(manifest.version.clone());
                         // In V1, we only check and save state, no actual update
                         return Ok(true);
                     }
 
-                    if crate::core::network::types::parse_env_bool("CCSTATUS_DEBUG") {
-                        eprintln!("[DEBUG] UpdateStateFile::check_for_updates_internal() - version {} is not newer or already prompted today", manifest.version);
-                    }
+                    debug_log(
+                        "check_for_updates_internal",
+                        &format!(
+                            "version {} is not newer or already prompted today",
+                            manifest.version
+                        ),
+                    );
                     return Ok(false);
                 }
                 Ok((None, _, _)) => {
-                    if crate::core::network::types::parse_env_bool("CCSTATUS_DEBUG") {
-                        eprintln!("[DEBUG] UpdateStateFile::check_for_updates_internal() - URL {} returned 304 Not Modified, no update available", index + 1);
-                    }
+                    debug_log(
+                        "check_for_updates_internal",
+                        &format!(
+                            "URL {} returned 304 Not Modified, no update available",
+                            index + 1
+                        ),
+                    );
                     // 304 Not Modified - short-circuit since all URLs point to same resource
                     return Ok(false);
                 }
                 Err(e) => {
-                    if crate::core::network::types::parse_env_bool("CCSTATUS_DEBUG") {
-                        eprintln!("[DEBUG] UpdateStateFile::check_for_updates_internal() - URL {} failed: {}, trying next", index + 1, e);
-                    }
+                    debug_log(
+                        "check_for_updates_internal",
+                        &format!("URL {} failed: {}, trying next", index + 1, e),
+                    );
                     // This URL failed, try next one
                     continue;
                 }
             }
         }
 
-        if crate::core::network::types::parse_env_bool("CCSTATUS_DEBUG") {
-            eprintln!("[DEBUG] UpdateStateFile::check_for_updates_internal() - all URLs failed, update check unsuccessful");
-        }
+        debug_log(
+            "check_for_updates_internal",
+            "all URLs failed, update check unsuccessful",
+        );
 
         // All URLs failed
         Ok(false)
@@ -288,13 +330,11 @@ impl UpdateStateFile {
 
     /// Check if we should throttle update checks (minimum 60 minutes)
     pub fn should_check_for_updates(&self) -> bool {
-        if let Some(last_check) = self.last_check {
-            let now = Utc::now();
-            let minutes_passed = now.signed_duration_since(last_check).num_minutes();
-            minutes_passed >= 60
-        } else {
-            true
-        }
+        UPDATE_CHECK_SCHEDULE.is_due(
+            "update-check",
+            self.last_check.map(|last| last.timestamp()),
+            Utc::now().timestamp(),
+        )
     }
 
     /// Check if we should prompt for this version (only once per day per version)
@@ -342,13 +382,13 @@ impl UpdateStateFile {
 
     /// Check if geographic detection is still valid (TTL 24h)
     pub fn is_geo_verdict_valid(&self) -> bool {
-        if let Some(checked_at) = self.geo_checked_at {
-            let now = Utc::now();
-            let hours_passed = now.signed_duration_since(checked_at).num_hours();
-            hours_passed < 24
-        } else {
-            false
-        }
+        self.geo_checked_at.is_some_and(|checked_at| {
+            !GEO_VERDICT_SCHEDULE.is_due(
+                "geo-verdict",
+                Some(checked_at.timestamp()),
+                Utc::now().timestamp(),
+            )
+        })
     }
 
     /// Update geographic detection result
@@ -8,6 +8,7 @@
 /// Strategy:
 /// - China users: hk.gh-proxy.com → jsDelivr CDN → GitHub Raw
 /// - Non-China users: GitHub Raw → jsDelivr CDN
+use crate::config::HostPolicy;
 use std::fmt;
 use url::Url;
 
@@ -74,6 +75,16 @@ pub fn resolve_manifest_url(is_china: bool) -> Vec<String> {
     }
 }
 
+/// Drop URLs whose host is not allowed by the `network.allowed_hosts` policy
+///
+/// Order is preserved so the geographic fallback priority still applies to the
+/// remaining URLs. An unrestricted policy returns the list unchanged.
+pub fn filter_allowed_urls(urls: Vec<String>, policy: &HostPolicy) -> Vec<String> {
+    urls.into_iter()
+        .filter(|url| policy.is_url_allowed(url))
+        .collect()
+}
+
 /// Extract hostname from URL for caching and debugging purposes
 ///
 /// Used for:
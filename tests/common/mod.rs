@@ -1,7 +1,10 @@
 //! Common test utilities and helpers for network monitoring tests
 
+use chrono::{DateTime, Utc};
 use std::env;
-use tempfile::TempDir;
+use std::io::Write;
+use std::path::Path;
+use tempfile::{NamedTempFile, TempDir};
 
 /// Test helper to create a temporary directory for test files
 pub fn create_temp_dir() -> TempDir {
@@ -155,11 +158,56 @@ impl Drop for IsolatedEnv {
 pub fn create_test_input_data() -> ccstatus::config::InputData {
     ccstatus::config::InputData {
         model: ccstatus::config::types::Model {
+            id: String::new(),
             display_name: "test-model".to_string(),
         },
         workspace: ccstatus::config::types::Workspace {
             current_dir: "/test".to_string(),
         },
         transcript_path: "/test/transcript.json".to_string(),
+        terminal_width: None,
+        output_style: None,
+        permission_mode: None,
+        usage: Default::default(),
     }
 }
+
+/// Temporary transcript with one JSONL line per entry
+pub fn transcript<S: AsRef<str>>(lines: &[S]) -> NamedTempFile {
+    let mut file = NamedTempFile::new().unwrap();
+    for line in lines {
+        writeln!(file, "{}", line.as_ref()).unwrap();
+    }
+    file
+}
+
+/// Sonnet assistant transcript line at `timestamp` with the given `usage` object
+pub fn assistant_with_usage(id: &str, timestamp: &str, usage: &str) -> String {
+    format!(
+        r#"{{"type":"assistant","timestamp":"{}","message":{{"id":"{}","model":"claude-sonnet-4","usage":{}}}}}"#,
+        timestamp, id, usage
+    )
+}
+
+/// Sonnet message costing $0.018: 1000 input and 1000 output tokens
+pub fn assistant(id: &str, timestamp: &str) -> String {
+    assistant_with_usage(
+        id,
+        timestamp,
+        r#"{"input_tokens":1000,"output_tokens":1000}"#,
+    )
+}
+
+/// Test InputData reading the transcript at `path`
+pub fn input_for(path: impl AsRef<Path>) -> ccstatus::config::InputData {
+    let mut input = create_test_input_data();
+    input.transcript_path = path.as_ref().to_string_lossy().to_string();
+    input
+}
+
+/// UTC time from an RFC 3339 timestamp
+pub fn at(timestamp: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .unwrap()
+        .with_timezone(&Utc)
+}
@@ -0,0 +1,113 @@
+use ccstatus::config::alerts::{parse_duration_secs, TriggerTarget};
+use ccstatus::config::{
+    AlertConfig, AlertEvent, AlertSeverity, AlertSinkKind, AlertTrigger, Config,
+    NotificationsConfig,
+};
+
+#[test]
+fn test_parse_triggers() {
+    let trigger: AlertTrigger = "outage>5m".parse().unwrap();
+    assert_eq!(trigger.target, TriggerTarget::Event(AlertEvent::Outage));
+    assert_eq!(trigger.min_secs, 300);
+    assert_eq!(trigger.to_string(), "outage>5m");
+
+    let trigger: AlertTrigger = " Warning > 90s ".parse().unwrap();
+    assert_eq!(
+        trigger.target,
+        TriggerTarget::Severity(AlertSeverity::Warning)
+    );
+    assert_eq!(trigger.to_string(), "warning>90s");
+    assert_eq!("update".parse::<AlertTrigger>().unwrap().min_secs, 0);
+
+    assert!("outage>soon".parse::<AlertTrigger>().is_err());
+    assert!("meltdown".parse::<AlertTrigger>().is_err());
+    assert_eq!(parse_duration_secs("2h"), Some(7200));
+    assert_eq!(parse_duration_secs("45"), Some(45));
+    assert_eq!(parse_duration_secs("5d"), None);
+}
+
+#[test]
+fn test_trigger_matching_by_event_severity_and_duration() {
+    let outage: AlertTrigger = "outage>5m".parse().unwrap();
+    assert!(!outage.matches(AlertEvent::Outage, 299));
+    assert!(outage.matches(AlertEvent::Outage, 300));
+    assert!(!outage.matches(AlertEvent::Degraded, 600));
+
+    // Severities match their level and above
+    let warning: AlertTrigger = "warning".parse().unwrap();
+    assert!(warning.matches(AlertEvent::Degraded, 0));
+    assert!(warning.matches(AlertEvent::Outage, 0));
+    assert!(!warning.matches(AlertEvent::Update, 0));
+    assert!(!warning.matches(AlertEvent::Recovered, 0));
+}
+
+#[test]
+fn test_alert_routes_from_toml() {
+    let mut config = Config::default();
+    let parsed: Config = toml::from_str(&format!(
+        "alerts = [{{ sink = \"slack\", url = \"https://hooks.slack.com/services/T/B/X\", on = [\"outage>5m\", \"update\"] }}]\n{}",
+        toml::to_string(&config).unwrap()
+    ))
+    .unwrap();
+    assert_eq!(parsed.alerts.len(), 1);
+    let route = &parsed.alerts[0];
+    assert_eq!(route.sink, AlertSinkKind::Slack);
+    assert_eq!(route.on.len(), 2);
+    assert_eq!(route.debounce_secs, 300);
+    assert!(parsed.check().is_ok());
+
+    // Triggers round-trip through their string form
+    let written = toml::to_string(&parsed).unwrap();
+    assert!(written.contains("on = [\"outage>5m\", \"update\"]"));
+
+    // Unknown triggers fail to load, routes without a target fail `check`
+    assert!(toml::from_str::<AlertConfig>("sink = \"desktop\"\non = [\"meltdown\"]").is_err());
+    config.alerts.push(AlertConfig {
+        sink: AlertSinkKind::Webhook,
+        on: vec!["critical".parse().unwrap()],
+        url: None,
+        command: None,
+        debounce_secs: 300,
+    });
+    assert_eq!(
+        config.check().unwrap_err().to_string(),
+        "webhook alert needs a url"
+    );
+
+    let discord: AlertConfig =
+        toml::from_str("sink = \"discord\"\non = [\"outage\", \"recovered\"]").unwrap();
+    assert_eq!(discord.sink, AlertSinkKind::Discord);
+    assert_eq!(discord.validate().unwrap_err(), "discord alert needs a url");
+}
+
+#[test]
+fn test_notifications_become_a_desktop_route() {
+    let mut config = Config::default();
+    assert!(config.alert_routes().is_empty());
+
+    let parsed: Config = toml::from_str(&format!(
+        "{}\n[notifications]\nrecovered = false\n",
+        toml::to_string(&config).unwrap()
+    ))
+    .unwrap();
+    let notifications = parsed.notifications.clone().unwrap();
+    assert!(notifications.outage && notifications.update && !notifications.recovered);
+    assert_eq!(notifications.debounce_secs, 300);
+
+    let routes = parsed.alert_routes();
+    assert_eq!(routes.len(), 1);
+    assert_eq!(routes[0].sink, AlertSinkKind::Desktop);
+    let on: Vec<String> = routes[0].on.iter().map(ToString::to_string).collect();
+    assert_eq!(on, vec!["outage", "update"]);
+    assert!(routes[0].validate().is_ok());
+
+    // Explicit routes come first; with every event off there is no desktop route
+    config.alerts.push(routes[0].clone());
+    config.notifications = Some(NotificationsConfig {
+        outage: false,
+        recovered: false,
+        update: false,
+        ..Default::default()
+    });
+    assert_eq!(config.alert_routes(), config.alerts);
+}
@@ -0,0 +1,70 @@
+use ccstatus::config::{Config, FeatureFlags, Flag, FlagSet, FlagSource, RemoteFlags};
+use std::collections::BTreeMap;
+use tempfile::TempDir;
+
+fn flags(entries: &[(&str, bool)]) -> FeatureFlags {
+    FeatureFlags(
+        entries
+            .iter()
+            .map(|(name, enabled)| (name.to_string(), *enabled))
+            .collect::<BTreeMap<_, _>>(),
+    )
+}
+
+#[test]
+fn test_flag_precedence_local_over_manifest_over_default() {
+    let remote = RemoteFlags {
+        flags: flags(&[("red_probing", false), ("proxy_health", false)]),
+        manifest_version: Some("2.2.9".to_string()),
+        fetched_at: Some("2025-09-01T00:00:00Z".to_string()),
+    };
+    let set = FlagSet::new(flags(&[("proxy_health", true)]), remote);
+
+    assert_eq!(set.resolve(Flag::RedProbing), (false, FlagSource::Manifest));
+    assert_eq!(set.resolve(Flag::ProxyHealth), (true, FlagSource::Local));
+    assert_eq!(set.resolve(Flag::GreenProbing), (true, FlagSource::Default));
+
+    let report = set.render_report();
+    assert!(report.contains("red_probing    off (manifest)"));
+    assert!(report.contains("proxy_health   on  (config.toml)"));
+    assert!(report.contains("manifest v2.2.9"));
+}
+
+#[test]
+fn test_remote_flags_round_trip_keeps_unknown_names() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("manifest-flags.json");
+    let remote = RemoteFlags {
+        flags: flags(&[("red_probing", false), ("future_subsystem", false)]),
+        manifest_version: Some("3.0.0".to_string()),
+        fetched_at: None,
+    };
+
+    remote.save_to(&path).unwrap();
+    assert_eq!(RemoteFlags::load_from(&path), remote);
+    assert_eq!(
+        RemoteFlags::load_from(&temp_dir.path().join("missing.json")),
+        RemoteFlags::default()
+    );
+}
+
+#[test]
+fn test_local_flags_table_in_config() {
+    let config: Config = toml::from_str(
+        r#"
+        theme = "default"
+        segments = []
+
+        [style]
+        mode = "plain"
+        separator = " | "
+
+        [flags]
+        red_probing = false
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(config.flags.get(Flag::RedProbing), Some(false));
+    assert_eq!(config.flags.get(Flag::ColdProbing), None);
+}
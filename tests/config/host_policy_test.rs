@@ -0,0 +1,65 @@
+use ccstatus::config::{HostPolicy, NetworkConfig};
+
+#[test]
+fn test_empty_allowlist_allows_everything() {
+    let policy = HostPolicy::from_config(&NetworkConfig::default());
+    assert!(!policy.is_restricted());
+    assert!(policy.is_url_allowed("https://api.anthropic.com"));
+    assert!(policy.is_url_allowed("not a url"));
+}
+
+#[test]
+fn test_exact_and_wildcard_patterns() {
+    let policy = HostPolicy::new(vec![
+        "api.anthropic.com".to_string(),
+        "*.corp.example".to_string(),
+        "gateway-?.internal".to_string(),
+    ]);
+
+    assert!(policy.is_restricted());
+    assert!(policy.is_host_allowed("api.anthropic.com"));
+    assert!(policy.is_host_allowed("proxy.corp.example"));
+    assert!(policy.is_host_allowed("a.b.corp.example"));
+    assert!(policy.is_host_allowed("gateway-1.internal"));
+
+    assert!(!policy.is_host_allowed("anthropic.com"));
+    assert!(!policy.is_host_allowed("corp.example"));
+    assert!(!policy.is_host_allowed("gateway-10.internal"));
+    assert!(!policy.is_host_allowed("api.anthropic.com.evil.test"));
+}
+
+#[test]
+fn test_matching_is_case_insensitive() {
+    let policy = HostPolicy::new(vec!["  API.Anthropic.COM ".to_string()]);
+    assert!(policy.is_host_allowed("api.anthropic.com"));
+    assert!(policy.is_url_allowed("https://Api.Anthropic.Com/v1/messages"));
+}
+
+#[test]
+fn test_url_host_extraction() {
+    let policy = HostPolicy::new(vec!["*.anthropic.com".to_string()]);
+
+    assert!(policy.is_url_allowed("https://api.anthropic.com:8443/v1"));
+    assert!(!policy.is_url_allowed("https://raw.githubusercontent.com/x"));
+    // Userinfo must not be mistaken for the host
+    assert!(!policy.is_url_allowed("https://api.anthropic.com@evil.test/"));
+}
+
+#[test]
+fn test_invalid_url_rejected_when_restricted() {
+    let policy = HostPolicy::new(vec!["*".to_string()]);
+    assert!(policy.is_url_allowed("https://anything.test"));
+    assert!(!policy.is_url_allowed("not a url"));
+    assert!(!policy.is_url_allowed("file:///etc/passwd"));
+}
+
+#[test]
+fn test_allowed_hosts_round_trip() {
+    let mut config = ccstatus::config::Config::default();
+    assert!(!toml::to_string(&config).unwrap().contains("allowed_hosts"));
+
+    config.network.allowed_hosts = vec!["*.anthropic.com".to_string()];
+    let content = toml::to_string_pretty(&config).unwrap();
+    let parsed: ccstatus::config::Config = toml::from_str(&content).unwrap();
+    assert_eq!(parsed.network.allowed_hosts, vec!["*.anthropic.com"]);
+}
@@ -0,0 +1,8 @@
+//! Config module tests
+
+pub mod alerts_test;
+pub mod flags_test;
+pub mod host_policy_test;
+pub mod proxy_test;
+pub mod tls_test;
+pub mod types_test;
@@ -0,0 +1,152 @@
+use ccstatus::config::{NetworkConfig, TlsConfig, TlsPolicy};
+use tempfile::TempDir;
+
+const PIN: &str = "sha256//AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=";
+
+#[test]
+fn test_no_tls_table_keeps_defaults() {
+    let policy = TlsPolicy::from_config(None, None).unwrap();
+    assert_eq!(policy, TlsPolicy::default());
+    assert!(policy.ca_file().is_none());
+    assert!(!policy.is_pinned());
+    assert!(!policy.skips_verification());
+    assert_eq!(policy.render_report(), "CA:     system trust store");
+}
+
+#[test]
+fn test_ca_file_expands_home_and_must_exist() {
+    let home = TempDir::new().unwrap();
+    std::fs::write(
+        home.path().join("corp-ca.pem"),
+        "-----BEGIN CERTIFICATE-----",
+    )
+    .unwrap();
+
+    let config = TlsConfig {
+        ca_file: Some("~/corp-ca.pem".to_string()),
+        ..Default::default()
+    };
+    let policy = TlsPolicy::from_config(Some(&config), Some(home.path())).unwrap();
+    assert_eq!(
+        policy.ca_file(),
+        Some(home.path().join("corp-ca.pem").as_path())
+    );
+
+    let config = TlsConfig {
+        ca_file: Some("~/missing.pem".to_string()),
+        ..Default::default()
+    };
+    let error = TlsPolicy::from_config(Some(&config), Some(home.path())).unwrap_err();
+    assert!(error.contains("missing.pem"));
+}
+
+#[test]
+fn test_pins_are_normalized_and_validated() {
+    let bare = PIN.trim_start_matches("sha256//");
+    let other = "sha256//ZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZ+/8=";
+    let config = TlsConfig {
+        pinned_spki: vec![bare.to_string(), other.to_string()],
+        ..Default::default()
+    };
+    let policy = TlsPolicy::from_config(Some(&config), None).unwrap();
+    assert!(policy.is_pinned());
+    assert_eq!(
+        policy.pinned_public_key().as_deref(),
+        Some(format!("{};{}", PIN, other).as_str())
+    );
+
+    for pin in [
+        "sha256//tooshort=",
+        "sha1//AAAA",
+        "",
+        &PIN.replace('=', "A"),
+    ] {
+        let config = TlsConfig {
+            pinned_spki: vec![pin.to_string()],
+            ..Default::default()
+        };
+        assert!(
+            TlsPolicy::from_config(Some(&config), None).is_err(),
+            "{pin} should be rejected"
+        );
+    }
+}
+
+#[test]
+fn test_insecure_skip_verify_is_labelled() {
+    let config: NetworkConfig = toml::from_str(
+        "[tls]\ninsecure_skip_verify = true\npinned_spki = [\"sha256//AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=\"]",
+    )
+    .unwrap();
+    let policy = TlsPolicy::from_config(config.tls.as_ref(), None).unwrap();
+    assert!(policy.skips_verification());
+
+    let report = policy.render_report();
+    assert!(report.contains(&format!("pinned: {}", PIN)));
+    assert!(report.contains("WARNING: TLS verification DISABLED"));
+}
+
+#[test]
+fn test_invalid_tls_table_is_kept_as_an_error() {
+    let config: NetworkConfig =
+        toml::from_str("[tls]\nca_file = \"/nonexistent/ccstatus-ca.pem\"").unwrap();
+    let policy = TlsPolicy::from_network(&config);
+    assert!(policy.error().unwrap().contains("ccstatus-ca.pem"));
+    assert!(policy.render_report().starts_with("ERROR: "));
+
+    let policy = TlsPolicy::from_network(&NetworkConfig::default());
+    assert!(policy.error().is_none());
+}
+
+#[test]
+fn test_client_identity_for_mutual_tls() {
+    let home = TempDir::new().unwrap();
+    for name in ["client.pem", "client.key", "bundle.pem"] {
+        std::fs::write(home.path().join(name), "-----BEGIN-----").unwrap();
+    }
+    let get = |name: &str| (name == "CLIENT_KEY_PASS").then(|| "hunter2".to_string());
+
+    let config = TlsConfig {
+        client_cert: Some("~/client.pem".to_string()),
+        client_key: Some("~/client.key".to_string()),
+        client_key_password_env: Some("CLIENT_KEY_PASS".to_string()),
+        ..Default::default()
+    };
+    let policy = TlsPolicy::from_config_with(Some(&config), Some(home.path()), get).unwrap();
+    let identity = policy.client_identity().unwrap();
+    assert_eq!(identity.cert, home.path().join("client.pem"));
+    assert_eq!(identity.key, home.path().join("client.key"));
+    assert_eq!(identity.key_password.as_deref(), Some("hunter2"));
+    let report = policy.render_report();
+    assert!(report.contains("client: ") && report.contains("(key "));
+    assert!(!report.contains("hunter2"));
+
+    // Certificate and key in one file
+    let config = TlsConfig {
+        client_cert: Some("~/bundle.pem".to_string()),
+        ..Default::default()
+    };
+    let policy = TlsPolicy::from_config_with(Some(&config), Some(home.path()), get).unwrap();
+    let identity = policy.client_identity().unwrap();
+    assert_eq!(identity.key, identity.cert);
+    assert_eq!(identity.key_password, None);
+
+    let invalid = [
+        TlsConfig {
+            client_key: Some("~/client.key".to_string()),
+            ..Default::default()
+        },
+        TlsConfig {
+            client_cert: Some("~/missing.pem".to_string()),
+            ..Default::default()
+        },
+        TlsConfig {
+            client_cert: Some("~/client.pem".to_string()),
+            client_key_password_env: Some("UNSET_PASS".to_string()),
+            ..Default::default()
+        },
+    ];
+    for config in invalid {
+        assert!(TlsPolicy::from_config_with(Some(&config), Some(home.path()), get).is_err());
+    }
+}
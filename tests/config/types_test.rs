@@ -0,0 +1,111 @@
+use ccstatus::config::{Config, NetworkConfig, NetworkTarget, SlaTarget};
+
+#[test]
+fn test_network_section_defaults_when_missing() {
+    // Configs written before the [network] table existed must still load
+    let mut value = toml::Value::try_from(Config::default()).unwrap();
+    value.as_table_mut().unwrap().remove("network");
+    let content = toml::to_string(&value).unwrap();
+
+    let config: Config = toml::from_str(&content).unwrap();
+    assert_eq!(config.network, NetworkConfig::default());
+    assert!(!config.network.probe_consent);
+}
+
+#[test]
+fn test_probe_consent_round_trip() {
+    let mut config = Config::default();
+    config.network.probe_consent = true;
+
+    let content = toml::to_string_pretty(&config).unwrap();
+    assert!(content.contains("[network]"));
+    assert!(content.contains("probe_consent = true"));
+
+    let parsed: Config = toml::from_str(&content).unwrap();
+    assert!(parsed.network.probe_consent);
+}
+
+#[test]
+fn test_network_targets_parse() {
+    let content = r#"
+[[targets]]
+name = "anthropic"
+
+[[targets]]
+name = "relay"
+label = "R"
+base_url = "https://relay.example.com"
+token_env = "RELAY_TOKEN"
+"#;
+    let network: NetworkConfig = toml::from_str(content).unwrap();
+
+    assert_eq!(network.targets.len(), 2);
+    assert_eq!(network.targets[0].base_url, None);
+    assert_eq!(network.targets[0].display_label(), "A");
+    assert_eq!(
+        network.targets[1].base_url.as_deref(),
+        Some("https://relay.example.com")
+    );
+    assert_eq!(network.targets[1].token_env.as_deref(), Some("RELAY_TOKEN"));
+    assert_eq!(network.targets[1].display_label(), "R");
+}
+
+#[test]
+fn test_network_target_name_validation() {
+    let target = |name: &str| NetworkTarget {
+        name: name.to_string(),
+        ..Default::default()
+    };
+
+    assert!(target("relay_2").has_valid_name());
+    assert!(target("open-router").has_valid_name());
+    assert!(!target("").has_valid_name());
+    assert!(!target("../escape").has_valid_name());
+    assert!(!target("a b").has_valid_name());
+}
+
+#[test]
+fn test_oauth_probe_switch() {
+    assert!(NetworkConfig::default().oauth_probe_enabled());
+
+    let off: NetworkConfig = toml::from_str("oauth_probe = false").unwrap();
+    assert!(!off.oauth_probe_enabled());
+    let on: NetworkConfig = toml::from_str("oauth_probe = true").unwrap();
+    assert!(on.oauth_probe_enabled());
+}
+
+#[test]
+fn test_sla_targets_parse_and_validate() {
+    let content = r#"
+[[sla]]
+p95_ms = 2000
+availability = 99.0
+
+[[sla]]
+endpoint = "relay"
+availability = 99.5
+"#;
+    let network: NetworkConfig = toml::from_str(content).unwrap();
+    assert_eq!(network.sla[0].endpoint, SlaTarget::PRIMARY);
+    assert_eq!(network.sla[0].p95_ms, Some(2000));
+    assert_eq!(network.sla[1].p95_ms, None);
+    assert!(network.sla.iter().all(|sla| sla.validate().is_ok()));
+
+    let empty = SlaTarget::default();
+    assert_eq!(
+        empty.validate().unwrap_err(),
+        "SLA for primary needs p95_ms or availability"
+    );
+    let over = SlaTarget {
+        availability: Some(150.0),
+        ..Default::default()
+    };
+    assert!(over.validate().is_err());
+
+    let mut config = Config::default();
+    config.network.sla = vec![network.sla[0].clone(), network.sla[0].clone()];
+    assert_eq!(
+        config.check().unwrap_err().to_string(),
+        "Duplicate SLA for endpoint: primary"
+    );
+}
@@ -0,0 +1,100 @@
+use ccstatus::config::Config;
+use ccstatus::core::attention::{resolve_from_env, AttentionStyle};
+use ccstatus::core::icons::IconSet;
+use ccstatus::core::segments::UpdateSegment;
+use std::collections::HashMap;
+
+fn resolve(configured: AttentionStyle, vars: &[(&str, &str)]) -> AttentionStyle {
+    let vars: HashMap<String, String> = vars
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    resolve_from_env(configured, |name| vars.get(name).cloned())
+}
+
+#[test]
+fn test_attention_parse() {
+    assert_eq!(AttentionStyle::parse("blink"), Some(AttentionStyle::Blink));
+    assert_eq!(AttentionStyle::parse(" Bold "), Some(AttentionStyle::Bold));
+    assert_eq!(
+        AttentionStyle::parse("reverse"),
+        Some(AttentionStyle::Inverse)
+    );
+    assert_eq!(AttentionStyle::parse("off"), Some(AttentionStyle::None));
+    assert_eq!(AttentionStyle::parse("underline"), None);
+}
+
+#[test]
+fn test_attention_closes_with_its_own_off_code() {
+    assert_eq!(AttentionStyle::Blink.apply("x"), "\x1b[5mx\x1b[25m");
+    assert_eq!(AttentionStyle::Bold.apply("x"), "\x1b[1mx\x1b[22m");
+    assert_eq!(AttentionStyle::Inverse.apply("x"), "\x1b[7mx\x1b[27m");
+    assert_eq!(AttentionStyle::None.apply("x"), "x");
+}
+
+#[test]
+fn test_attention_resolution_respects_environment() {
+    let color = [("COLORTERM", "truecolor")];
+    assert_eq!(
+        resolve(AttentionStyle::Blink, &color),
+        AttentionStyle::Blink
+    );
+    assert_eq!(resolve(AttentionStyle::Bold, &color), AttentionStyle::Bold);
+
+    // No escapes at all, or the legacy flash switch turned off
+    assert_eq!(
+        resolve(AttentionStyle::Blink, &[("NO_COLOR", "1")]),
+        AttentionStyle::None
+    );
+    assert_eq!(
+        resolve(AttentionStyle::Bold, &[("TERM", "dumb")]),
+        AttentionStyle::None
+    );
+    assert_eq!(
+        resolve(
+            AttentionStyle::Blink,
+            &[("COLORTERM", "truecolor"), ("CCSTATUS_FLASH", "false")]
+        ),
+        AttentionStyle::None
+    );
+    assert_eq!(
+        resolve(
+            AttentionStyle::Inverse,
+            &[("COLORTERM", "truecolor"), ("CCSTATUS_FLASH", "1")]
+        ),
+        AttentionStyle::Inverse
+    );
+}
+
+#[test]
+fn test_config_attention() {
+    let config: Config = toml::from_str(
+        r#"
+theme = "default"
+attention = "bold"
+segments = []
+
+[style]
+mode = "plain"
+separator = " | "
+"#,
+    )
+    .expect("config with attention parses");
+    assert_eq!(config.attention, AttentionStyle::Bold);
+    assert_eq!(Config::default().attention, AttentionStyle::Blink);
+    // The default is left out when saving
+    let saved = toml::to_string(&Config::default()).unwrap();
+    assert!(!saved.contains("attention"));
+}
+
+#[test]
+fn test_update_notice_attention() {
+    let segment = UpdateSegment::new().with_icons(IconSet::Ascii);
+    assert_eq!(segment.notification("2.2.9"), "^ Update v2.2.9!");
+    assert_eq!(
+        segment
+            .with_attention(AttentionStyle::Bold)
+            .notification("2.2.9"),
+        "\x1b[1m^ Update v2.2.9!\x1b[22m"
+    );
+}
@@ -0,0 +1,62 @@
+use ccstatus::core::capabilities::{fingerprint_from_env, supports_emoji_from_env, Capabilities};
+use ccstatus::core::color::ColorLevel;
+use std::collections::HashMap;
+use tempfile::TempDir;
+
+fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+    let vars: HashMap<String, String> = vars
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    move |name| vars.get(name).cloned()
+}
+
+#[test]
+fn test_detection_reuses_terminal_probes() {
+    let get = env(&[("TERM_PROGRAM", "WezTerm"), ("TERM", "xterm-256color")]);
+    let capabilities = Capabilities::detect_with(&get, |_| true, 1_000);
+    assert_eq!(capabilities.color, ColorLevel::TrueColor);
+    assert!(capabilities.hyperlinks);
+    assert!(capabilities.emoji);
+    assert_eq!(capabilities.keychain, cfg!(target_os = "macos"));
+    assert_eq!(capabilities.fingerprint, fingerprint_from_env(&get));
+
+    let capabilities = Capabilities::detect_with(env(&[("TERM", "linux")]), |_| false, 1_000);
+    assert_eq!(capabilities.color, ColorLevel::Ansi16);
+    assert!(!capabilities.hyperlinks);
+    assert!(!capabilities.emoji);
+    assert!(!capabilities.keychain);
+    assert_eq!(capabilities.notifier, None);
+    assert!(capabilities
+        .render_report()
+        .contains("Notifications: unavailable"));
+
+    assert!(supports_emoji_from_env(env(&[
+        ("TERM", "linux"),
+        ("CCSTATUS_EMOJI", "always")
+    ])));
+}
+
+#[test]
+fn test_cache_is_keyed_by_environment_and_expires_daily() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("capabilities.json");
+    let wezterm = env(&[("TERM_PROGRAM", "WezTerm")]);
+
+    let first = Capabilities::load_or_detect(Some(&path), &wezterm, 1_000);
+    assert!(path.exists());
+    assert_eq!(Capabilities::load(&path), Some(first.clone()));
+
+    // Same environment within the day: served from the cache
+    let cached = Capabilities::load_or_detect(Some(&path), &wezterm, 1_000 + 3_600);
+    assert_eq!(cached.detected_at, 1_000);
+
+    // Another terminal re-detects at once
+    let other = Capabilities::load_or_detect(Some(&path), env(&[("NO_COLOR", "1")]), 2_000);
+    assert_eq!(other.color, ColorLevel::None);
+    assert_eq!(other.detected_at, 2_000);
+
+    let stale =
+        Capabilities::load_or_detect(Some(&path), env(&[("NO_COLOR", "1")]), 2_000 + 86_400);
+    assert_eq!(stale.detected_at, 2_000 + 86_400);
+}
@@ -0,0 +1,140 @@
+use ccstatus::config::{AnsiColor, Config, SegmentConfig, SegmentId};
+use ccstatus::core::color::{detect_from_env, rgb_to_256, ColorLevel};
+use ccstatus::core::segments::SegmentData;
+use ccstatus::core::StatusLineGenerator;
+use std::collections::HashMap;
+
+fn detect(vars: &[(&str, &str)]) -> ColorLevel {
+    let vars: HashMap<String, String> = vars
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    detect_from_env(|name| vars.get(name).cloned())
+}
+
+#[test]
+fn test_detection_from_environment() {
+    assert_eq!(
+        detect(&[("COLORTERM", "truecolor"), ("TERM", "xterm-256color")]),
+        ColorLevel::TrueColor
+    );
+    assert_eq!(
+        detect(&[("TERM_PROGRAM", "iTerm.app")]),
+        ColorLevel::TrueColor
+    );
+    // SSH typically forwards TERM but not COLORTERM
+    assert_eq!(
+        detect(&[
+            ("TERM", "xterm-256color"),
+            ("SSH_CONNECTION", "10.0.0.1 22")
+        ]),
+        ColorLevel::Ansi256
+    );
+    assert_eq!(detect(&[("TERM", "xterm")]), ColorLevel::Ansi16);
+    assert_eq!(detect(&[("CI", "true")]), ColorLevel::Ansi16);
+    assert_eq!(detect(&[("TERM", "dumb")]), ColorLevel::None);
+    assert_eq!(
+        detect(&[("NO_COLOR", "1"), ("COLORTERM", "truecolor")]),
+        ColorLevel::None
+    );
+}
+
+#[test]
+fn test_override_wins_over_detection() {
+    assert_eq!(
+        detect(&[("CCSTATUS_COLOR", "16"), ("COLORTERM", "truecolor")]),
+        ColorLevel::Ansi16
+    );
+    assert_eq!(
+        detect(&[("CCSTATUS_COLOR", "truecolor"), ("NO_COLOR", "1")]),
+        ColorLevel::TrueColor
+    );
+    assert_eq!(
+        detect(&[("CCSTATUS_COLOR", "none"), ("COLORTERM", "24bit")]),
+        ColorLevel::None
+    );
+    // Unknown values fall back to detection
+    assert_eq!(
+        detect(&[("CCSTATUS_COLOR", "bogus"), ("TERM", "xterm-256color")]),
+        ColorLevel::Ansi256
+    );
+}
+
+#[test]
+fn test_rgb_maps_to_nearest_palette_entry() {
+    assert_eq!(rgb_to_256(255, 0, 0), 196);
+    assert_eq!(rgb_to_256(0, 0, 0), 16);
+    assert_eq!(rgb_to_256(128, 128, 128), 244);
+    assert_eq!(rgb_to_256(95, 135, 175), 67);
+
+    let nord_red = AnsiColor::Rgb {
+        r: 191,
+        g: 97,
+        b: 106,
+    };
+    assert_eq!(
+        ColorLevel::Ansi16.downgrade(&nord_red),
+        Some(AnsiColor::Color16 { c16: 1 })
+    );
+    assert_eq!(
+        ColorLevel::Ansi16.downgrade(&AnsiColor::Color256 { c256: 196 }),
+        Some(AnsiColor::Color16 { c16: 9 })
+    );
+    assert_eq!(
+        ColorLevel::Ansi256.downgrade(&AnsiColor::Color16 { c16: 4 }),
+        Some(AnsiColor::Color16 { c16: 4 })
+    );
+    assert_eq!(ColorLevel::TrueColor.downgrade(&nord_red), Some(nord_red));
+    assert_eq!(
+        ColorLevel::None.downgrade(&AnsiColor::Color16 { c16: 4 }),
+        None
+    );
+}
+
+fn nord_statusline(level: ColorLevel) -> String {
+    let config = Config {
+        theme: "nord".to_string(),
+        ..Config::default()
+    };
+    let segments: Vec<(SegmentConfig, SegmentData)> = config
+        .segments
+        .iter()
+        .filter(|s| s.id == SegmentId::Model || s.id == SegmentId::Directory)
+        .map(|s| {
+            (
+                s.clone(),
+                SegmentData {
+                    primary: "text".to_string(),
+                    secondary: String::new(),
+                    metadata: HashMap::new(),
+                },
+            )
+        })
+        .collect();
+    StatusLineGenerator::new(config)
+        .with_color_level(level)
+        .generate(segments)
+}
+
+#[test]
+fn test_generator_downgrades_colors() {
+    let truecolor = nord_statusline(ColorLevel::TrueColor);
+    assert!(truecolor.contains("\x1b[38;2;"));
+    assert!(truecolor.contains("\x1b[48;2;"));
+
+    let ansi256 = nord_statusline(ColorLevel::Ansi256);
+    assert!(!ansi256.contains(";2;"), "{:?}", ansi256);
+    assert!(ansi256.contains("\x1b[48;5;"));
+
+    let ansi16 = nord_statusline(ColorLevel::Ansi16);
+    assert!(
+        !ansi16.contains(";2;") && !ansi16.contains(";5;"),
+        "{:?}",
+        ansi16
+    );
+    assert!(ansi16.contains("\x1b["));
+
+    let none = nord_statusline(ColorLevel::None);
+    assert!(!none.contains('\x1b'), "{:?}", none);
+    assert!(none.contains("text"));
+}
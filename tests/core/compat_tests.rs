@@ -0,0 +1,130 @@
+use ccstatus::core::compat::{
+    self, check_config, check_env, config_keys, render_report, CompatKind, CompatWarning,
+};
+use std::collections::HashMap;
+
+fn lookup(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+    let vars: HashMap<String, String> = vars
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    move |name| vars.get(name).cloned()
+}
+
+#[test]
+fn test_config_keys_follow_config_fields() {
+    let keys = config_keys();
+    for key in [
+        "style",
+        "segments",
+        "theme",
+        "attention",
+        "network",
+        "heartbeat",
+    ] {
+        assert!(keys.contains(&key), "{} missing from {:?}", key, keys);
+    }
+}
+
+#[test]
+fn test_unknown_config_keys_are_reported() {
+    let raw: toml::Table = toml::from_str(
+        r#"
+        theme = "nord"
+        flash = false
+        attention = "bold"
+
+        [network]
+        probe_consent = true
+
+        [refresh]
+        interval = 5
+        "#,
+    )
+    .unwrap();
+
+    let subjects: Vec<String> = check_config(&raw)
+        .into_iter()
+        .map(|warning| {
+            assert_eq!(warning.kind, CompatKind::ConfigKey);
+            warning.subject
+        })
+        .collect();
+    assert_eq!(subjects, vec!["flash", "refresh"]);
+}
+
+#[test]
+fn test_legacy_and_superseded_env_vars_are_reported() {
+    let warnings = check_env(lookup(&[
+        ("ccstatus_TIMEOUT_MS", "3000"),
+        ("CCSTATUS_COLD_WINDOW_MS", "8000"),
+        ("ccstatus_COLD_WINDOW_MS", "9000"),
+        ("CCSTATUS_FLASH", "0"),
+    ]));
+
+    assert_eq!(
+        warnings[0],
+        CompatWarning::new(
+            CompatKind::EnvVar,
+            "ccstatus_TIMEOUT_MS",
+            "deprecated, use CCSTATUS_TIMEOUT_MS"
+        )
+    );
+    // The current name wins, so the legacy one is not read
+    assert_eq!(warnings.len(), 2);
+    assert_eq!(warnings[1].subject, "CCSTATUS_FLASH");
+    assert!(check_env(lookup(&[("CCSTATUS_DEBUG", "1")])).is_empty());
+}
+
+#[test]
+fn test_record_keeps_first_notice_per_subject() {
+    compat::record(CompatWarning::new(
+        CompatKind::StateField,
+        "compat_test_field",
+        "migrated",
+    ));
+    compat::record(CompatWarning::new(
+        CompatKind::StateField,
+        "compat_test_field",
+        "migrated again",
+    ));
+
+    let recorded: Vec<CompatWarning> = compat::warnings()
+        .into_iter()
+        .filter(|warning| warning.subject == "compat_test_field")
+        .collect();
+    assert_eq!(recorded.len(), 1);
+    assert_eq!(recorded[0].message, "migrated");
+}
+
+#[test]
+fn test_render_report_groups_by_kind() {
+    assert_eq!(render_report(&[]), "No compatibility warnings");
+
+    let report = render_report(&[
+        CompatWarning::new(
+            CompatKind::StateField,
+            "last_prompted_version",
+            "migrated to version_prompt_dates in ccstatus-update.json",
+        ),
+        CompatWarning::new(
+            CompatKind::ConfigKey,
+            "refresh",
+            "not a ccstatus setting, ignored",
+        ),
+        CompatWarning::new(
+            CompatKind::EnvVar,
+            "ccstatus_TIMEOUT_MS",
+            "deprecated, use CCSTATUS_TIMEOUT_MS",
+        ),
+    ]);
+    assert_eq!(
+        report,
+        [
+            "env    ccstatus_TIMEOUT_MS    deprecated, use CCSTATUS_TIMEOUT_MS",
+            "config refresh                not a ccstatus setting, ignored",
+            "state  last_prompted_version  migrated to version_prompt_dates in ccstatus-update.json",
+        ]
+        .join("\n")
+    );
+}
@@ -0,0 +1,96 @@
+use ccstatus::core::env::{
+    self, parse_bool, parse_override, render_report, EnvValue, COLD_WINDOW_MS, DEBUG, TIMEOUT_MS,
+    VARIABLES,
+};
+use std::collections::HashMap;
+
+fn lookup(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+    let vars: HashMap<String, String> = vars
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    move |name| vars.get(name).cloned()
+}
+
+#[test]
+fn test_parse_bool_accepts_common_spellings() {
+    for value in ["1", "true", "TRUE", " yes ", "On"] {
+        assert_eq!(parse_bool(value), Some(true), "{}", value);
+    }
+    for value in ["0", "false", "No", "off"] {
+        assert_eq!(parse_bool(value), Some(false), "{}", value);
+    }
+    assert_eq!(parse_bool("maybe"), None);
+    assert_eq!(parse_bool(""), None);
+
+    assert_eq!(parse_override("always"), Some(true));
+    assert_eq!(parse_override("NEVER"), Some(false));
+    assert_eq!(parse_override("1"), Some(true));
+    assert_eq!(parse_override("auto"), None);
+}
+
+#[test]
+fn test_lookup_prefers_current_name_over_legacy() {
+    assert_eq!(
+        TIMEOUT_MS.lookup_with(lookup(&[("ccstatus_TIMEOUT_MS", "3000")])),
+        Some(EnvValue {
+            name: "ccstatus_TIMEOUT_MS",
+            value: "3000".to_string(),
+            deprecated: true,
+        })
+    );
+    let both = lookup(&[
+        ("CCSTATUS_TIMEOUT_MS", "2000"),
+        ("ccstatus_TIMEOUT_MS", "3000"),
+    ]);
+    let found = TIMEOUT_MS.lookup_with(both).unwrap();
+    assert_eq!(found.value, "2000");
+    assert!(!found.deprecated);
+
+    // Variables without a legacy spelling ignore lowercase names
+    assert_eq!(DEBUG.lookup_with(lookup(&[("ccstatus_DEBUG", "1")])), None);
+}
+
+#[test]
+fn test_variables_are_unique_and_prefixed() {
+    let mut names: Vec<_> = VARIABLES.iter().map(|var| var.name).collect();
+    assert!(names.iter().all(|name| name.starts_with("CCSTATUS_")));
+    names.sort_unstable();
+    names.dedup();
+    assert_eq!(names.len(), VARIABLES.len());
+    assert!(VARIABLES.contains(&env::JSONL_TAIL_KB));
+    // OAuth header overrides are listed by `ccstatus env` like the rest
+    assert!(VARIABLES.contains(&env::USER_AGENT));
+    assert!(VARIABLES.contains(&env::TEST_HEADERS_FILE));
+}
+
+#[test]
+fn test_render_report_flags_invalid_and_deprecated_values() {
+    let report = render_report(lookup(&[
+        ("CCSTATUS_DEBUG", "maybe"),
+        ("CCSTATUS_JSONL_TAIL_KB", "lots"),
+        ("ccstatus_COLD_WINDOW_MS", "8000"),
+    ]));
+    assert!(report.contains("\"maybe\" (not a boolean, ignored)"));
+    assert!(report.contains("\"lots\" (not a number, ignored)"));
+    let cold = report
+        .lines()
+        .find(|line| line.starts_with(COLD_WINDOW_MS.name))
+        .unwrap();
+    assert!(cold.ends_with("\"8000\" (from deprecated ccstatus_COLD_WINDOW_MS)"));
+    assert!(report.contains("unset (default: 1800)"));
+}
+
+#[cfg(feature = "network-monitoring")]
+#[test]
+fn test_probe_profile_description_lists_every_profile() {
+    use ccstatus::core::network::probe_profile::ProbeProfile;
+
+    let names: Vec<&str> = ProbeProfile::ALL.iter().map(|p| p.as_str()).collect();
+    let expected = format!("({})", names.join(", "));
+    assert!(
+        env::PROBE_PROFILE.description.ends_with(&expected),
+        "{}",
+        env::PROBE_PROFILE.description
+    );
+}
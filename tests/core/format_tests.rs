@@ -0,0 +1,101 @@
+use ccstatus::config::Config;
+use ccstatus::core::attention::AttentionStyle;
+use ccstatus::core::format::{LatencyUnit, NumberFormat};
+
+fn format(latency_unit: LatencyUnit, separator: &str, decimals: Option<u8>) -> NumberFormat {
+    NumberFormat {
+        latency_unit,
+        thousands_separator: separator.to_string(),
+        decimals,
+        ..NumberFormat::default()
+    }
+}
+
+#[test]
+fn test_defaults_match_historical_output() {
+    let format = NumberFormat::default();
+    assert_eq!(format.latency_ms(1200), "1200ms");
+    assert_eq!(format.latency_ms(0), "0ms");
+    assert_eq!(format.cost(0.42), "$0.42");
+    assert_eq!(format.integer(1_234_567), "1234567");
+    assert!(format.is_default());
+}
+
+#[test]
+fn test_latency_units() {
+    assert_eq!(format(LatencyUnit::S, "", None).latency_ms(1250), "1.2s");
+    assert_eq!(
+        format(LatencyUnit::S, "", Some(2)).latency_ms(1250),
+        "1.25s"
+    );
+    assert_eq!(format(LatencyUnit::S, "", Some(0)).latency_ms(450), "0s");
+    assert_eq!(format(LatencyUnit::Auto, "", None).latency_ms(999), "999ms");
+    assert_eq!(format(LatencyUnit::Auto, "", None).latency_ms(2650), "2.6s");
+}
+
+#[test]
+fn test_thousands_separator() {
+    let format = format(LatencyUnit::Ms, ",", None);
+    assert_eq!(format.latency_ms(12_500), "12,500ms");
+    assert_eq!(format.latency_ms(999), "999ms");
+    assert_eq!(format.integer(1_234_567), "1,234,567");
+    assert_eq!(format.cost(1234.5), "$1,234.50");
+
+    let thin = NumberFormat {
+        thousands_separator: "\u{202f}".to_string(),
+        latency_unit: LatencyUnit::S,
+        ..NumberFormat::default()
+    };
+    assert_eq!(thin.latency_ms(1_234_000), "1\u{202f}234.0s");
+}
+
+#[test]
+fn test_cost_symbol_decimals_and_sign() {
+    let euro = NumberFormat {
+        currency_symbol: "€".to_string(),
+        decimals: Some(3),
+        ..NumberFormat::default()
+    };
+    assert_eq!(euro.cost(0.4219), "€0.422");
+    assert_eq!(NumberFormat::default().cost(-1.5), "-$1.50");
+    // Rounds to zero: no negative zero
+    assert_eq!(NumberFormat::default().cost(-0.001), "$0.00");
+    // Decimal places are capped
+    let many = NumberFormat {
+        decimals: Some(40),
+        ..NumberFormat::default()
+    };
+    assert_eq!(many.cost(1.0), "$1.000000");
+}
+
+#[test]
+fn test_formatting_table_in_config() {
+    let config: Config = toml::from_str(
+        r#"
+theme = "default"
+segments = []
+
+[style]
+mode = "plain"
+separator = " | "
+
+[formatting]
+latency_unit = "auto"
+thousands_separator = ","
+currency_symbol = "¥"
+"#,
+    )
+    .expect("config with [formatting] parses");
+    assert_eq!(config.formatting.latency_unit, LatencyUnit::Auto);
+    assert_eq!(config.formatting.decimals, None);
+    assert_eq!(config.formatting.cost(12.5), "¥12.50");
+
+    // Untouched defaults are not written back
+    let saved = toml::to_string(&Config {
+        formatting: NumberFormat::default(),
+        attention: AttentionStyle::default(),
+        ..config
+    })
+    .unwrap();
+    assert!(!saved.contains("[formatting]"));
+}
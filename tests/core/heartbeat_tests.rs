@@ -0,0 +1,60 @@
+use ccstatus::config::{Config, HeartbeatConfig};
+use ccstatus::core::heartbeat::{Heartbeat, HeartbeatWriter};
+use tempfile::TempDir;
+
+#[test]
+fn test_heartbeat_write_replaces_previous_beat() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("nested").join("heartbeat.json");
+    let writer = HeartbeatWriter::new(path.clone());
+    assert_eq!(writer.load(), None);
+
+    let mut beat = Heartbeat::now();
+    assert_eq!(beat.pid, std::process::id());
+    assert_eq!(beat.version, env!("CARGO_PKG_VERSION"));
+    writer.write(&beat).unwrap();
+    assert_eq!(writer.load(), Some(beat.clone()));
+
+    beat.unix += 60;
+    writer.write(&beat).unwrap();
+    assert_eq!(writer.load().unwrap().unix, beat.unix);
+
+    // Only the heartbeat itself is left behind, no temp files
+    let entries = std::fs::read_dir(path.parent().unwrap()).unwrap().count();
+    assert_eq!(entries, 1);
+}
+
+#[test]
+fn test_heartbeat_age_and_config() {
+    let beat = Heartbeat {
+        timestamp: "2025-01-25T10:30:00-08:00".to_string(),
+        unix: 1_737_829_800,
+        pid: 42,
+        version: "1.0.0".to_string(),
+    };
+    assert_eq!(beat.age_secs(1_737_829_800 + 90), 90);
+    assert_eq!(beat.age_secs(1_737_829_800 - 5), 0);
+
+    let config: Config = toml::from_str(
+        r#"
+theme = "default"
+segments = []
+
+[style]
+mode = "plain"
+separator = " | "
+
+[heartbeat]
+path = "/tmp/ccstatus-heartbeat.json"
+"#,
+    )
+    .unwrap();
+    let writer = HeartbeatWriter::from_config(config.heartbeat.as_ref().unwrap()).unwrap();
+    assert_eq!(
+        writer.path(),
+        std::path::Path::new("/tmp/ccstatus-heartbeat.json")
+    );
+
+    let default = HeartbeatWriter::from_config(&HeartbeatConfig::default()).unwrap();
+    assert!(default.path().ends_with(".claude/ccstatus/heartbeat.json"));
+}
@@ -0,0 +1,77 @@
+use ccstatus::core::hyperlink::{
+    error_help_url, link, release_notes_url, supports_hyperlinks_from_env,
+};
+use ccstatus::core::icons::IconSet;
+use ccstatus::core::segments::UpdateSegment;
+use std::collections::HashMap;
+
+fn supports(vars: &[(&str, &str)]) -> bool {
+    let vars: HashMap<String, String> = vars
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    supports_hyperlinks_from_env(|name| vars.get(name).cloned())
+}
+
+#[test]
+fn test_hyperlink_support_detection() {
+    assert!(!supports(&[]));
+    assert!(!supports(&[("TERM", "xterm-256color")]));
+    assert!(supports(&[("TERM_PROGRAM", "iTerm.app")]));
+    assert!(supports(&[("TERM_PROGRAM", "vscode")]));
+    assert!(supports(&[("WT_SESSION", "abc")]));
+    assert!(supports(&[("TERM", "xterm-kitty")]));
+    assert!(supports(&[("VTE_VERSION", "7600")]));
+    assert!(!supports(&[("VTE_VERSION", "4803")]));
+    assert!(!supports(&[("TERM", "dumb"), ("TERM_PROGRAM", "vscode")]));
+
+    // Explicit override wins both ways
+    assert!(supports(&[("CCSTATUS_HYPERLINKS", "always")]));
+    assert!(!supports(&[
+        ("CCSTATUS_HYPERLINKS", "never"),
+        ("TERM_PROGRAM", "iTerm.app")
+    ]));
+}
+
+#[test]
+fn test_link_and_plain_fallback() {
+    assert_eq!(
+        link("docs", "https://example.com", true),
+        "\x1b]8;;https://example.com\x1b\\docs\x1b]8;;\x1b\\"
+    );
+    assert_eq!(link("docs", "https://example.com", false), "docs");
+}
+
+#[test]
+fn test_link_targets() {
+    assert_eq!(
+        release_notes_url("v2.2.9"),
+        "https://github.com/MaurUppi/CCstatus/releases/tag/v2.2.9"
+    );
+    assert_eq!(release_notes_url("2.2.9"), release_notes_url("v2.2.9"));
+    assert_eq!(
+        error_help_url("overloaded_error"),
+        "https://status.anthropic.com"
+    );
+    assert_eq!(
+        error_help_url("authentication_error"),
+        "https://github.com/MaurUppi/CCstatus/blob/main/README_EN.md#authentication_error"
+    );
+}
+
+#[test]
+fn test_update_notification_links_release_notes() {
+    assert_eq!(
+        UpdateSegment::new()
+            .with_icons(IconSet::NerdFont)
+            .notification("2.2.9"),
+        "\u{f06b0} Update v2.2.9!"
+    );
+    assert_eq!(
+        UpdateSegment::new()
+            .with_hyperlinks(true)
+            .with_icons(IconSet::NerdFont)
+            .notification("2.2.9"),
+        "\x1b]8;;https://github.com/MaurUppi/CCstatus/releases/tag/v2.2.9\x1b\\\u{f06b0} Update v2.2.9!\x1b]8;;\x1b\\"
+    );
+}
@@ -0,0 +1,109 @@
+use ccstatus::config::Config;
+use ccstatus::core::i18n::{detect_from_env, Locale, Text};
+use ccstatus::core::icons::IconSet;
+use ccstatus::core::segments::UpdateSegment;
+use ccstatus::updater::{UpdateState, UpdateStatus};
+use std::collections::HashMap;
+
+fn detect(vars: &[(&str, &str)]) -> Locale {
+    let vars: HashMap<String, String> = vars
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    detect_from_env(|name| vars.get(name).cloned())
+}
+
+#[test]
+fn test_locale_parse() {
+    assert_eq!(Locale::parse("zh"), Some(Locale::Zh));
+    assert_eq!(Locale::parse("zh_CN.UTF-8"), Some(Locale::Zh));
+    assert_eq!(Locale::parse("zh-Hans"), Some(Locale::Zh));
+    assert_eq!(Locale::parse("en_US.UTF-8"), Some(Locale::En));
+    assert_eq!(Locale::parse("C"), Some(Locale::En));
+    assert_eq!(Locale::parse("fr_FR.UTF-8"), None);
+}
+
+#[test]
+fn test_locale_detection_follows_posix_precedence() {
+    assert_eq!(detect(&[]), Locale::En);
+    assert_eq!(detect(&[("LANG", "zh_CN.UTF-8")]), Locale::Zh);
+    assert_eq!(
+        detect(&[("LANG", "zh_CN.UTF-8"), ("LC_ALL", "en_US.UTF-8")]),
+        Locale::En
+    );
+    assert_eq!(
+        detect(&[("LANG", "en_US.UTF-8"), ("LC_MESSAGES", "zh_TW.UTF-8")]),
+        Locale::Zh
+    );
+    // Empty variables are skipped, unknown languages fall back to English
+    assert_eq!(detect(&[("LC_ALL", ""), ("LANG", "zh_CN")]), Locale::Zh);
+    assert_eq!(detect(&[("LANG", "de_DE.UTF-8")]), Locale::En);
+}
+
+#[test]
+fn test_config_locale_overrides_environment() {
+    let config: Config = toml::from_str(
+        r#"
+theme = "default"
+locale = "zh"
+segments = []
+
+[style]
+mode = "plain"
+separator = " | "
+"#,
+    )
+    .expect("config with locale parses");
+    assert_eq!(config.locale, Some(Locale::Zh));
+    assert_eq!(Locale::for_config(config.locale), Locale::Zh);
+    assert_eq!(Config::default().locale, None);
+}
+
+#[test]
+fn test_update_words_are_localized() {
+    assert_eq!(
+        UpdateSegment::new()
+            .with_icons(IconSet::Ascii)
+            .with_locale(Locale::Zh)
+            .notification("2.2.9"),
+        "^ 更新 v2.2.9!"
+    );
+
+    let state = UpdateState {
+        status: UpdateStatus::Installing,
+        ..Default::default()
+    };
+    assert_eq!(
+        state.status_text(IconSet::Ascii, Locale::En).as_deref(),
+        Some("v Installing...")
+    );
+    assert_eq!(
+        state.status_text(IconSet::Ascii, Locale::Zh).as_deref(),
+        Some("v 安装中...")
+    );
+}
+
+#[test]
+fn test_every_text_is_translated() {
+    let texts = [
+        Text::UpdateAvailable,
+        Text::Installing,
+        Text::Updated,
+        Text::Unknown,
+        Text::EnvVarsNotFound,
+        Text::ProbesOff,
+        Text::ApiError,
+        Text::Cooldown,
+        Text::ResetsIn,
+        Text::Budget,
+        Text::Alternative,
+        Text::BotChallenge,
+        Text::OAuthMode,
+        Text::OAuthOff,
+        Text::TlsInsecure,
+    ];
+    for text in texts {
+        assert!(!Locale::Zh.text(text).is_empty());
+        assert_ne!(Locale::Zh.text(text), Locale::En.text(text), "{:?}", text);
+    }
+}
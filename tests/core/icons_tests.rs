@@ -0,0 +1,91 @@
+use ccstatus::config::{StyleConfig, StyleMode};
+use ccstatus::core::icons::{Icon, IconSet};
+
+const ALL_ICONS: [Icon; 15] = [
+    Icon::Healthy,
+    Icon::Degraded,
+    Icon::Error,
+    Icon::Unknown,
+    Icon::Blocked,
+    Icon::BotChallenge,
+    Icon::Check,
+    Icon::Cross,
+    Icon::Warning,
+    Icon::GitDirty,
+    Icon::Ahead,
+    Icon::Behind,
+    Icon::UpdateAvailable,
+    Icon::Downloading,
+    Icon::Updated,
+];
+
+fn style(mode: StyleMode, icons: Option<IconSet>) -> StyleConfig {
+    StyleConfig {
+        mode,
+        separator: " | ".to_string(),
+        icons,
+    }
+}
+
+#[test]
+fn test_icon_set_follows_style_mode_unless_set() {
+    assert_eq!(
+        IconSet::for_style(&style(StyleMode::Plain, None)),
+        IconSet::Unicode
+    );
+    assert_eq!(
+        IconSet::for_style(&style(StyleMode::NerdFont, None)),
+        IconSet::NerdFont
+    );
+    assert_eq!(
+        IconSet::for_style(&style(StyleMode::NerdFont, Some(IconSet::Ascii))),
+        IconSet::Ascii
+    );
+
+    // Without emoji a derived set drops to ASCII; an explicit one is kept
+    assert_eq!(
+        IconSet::for_terminal(&style(StyleMode::Plain, None), false),
+        IconSet::Ascii
+    );
+    assert_eq!(
+        IconSet::for_terminal(&style(StyleMode::Plain, Some(IconSet::Unicode)), false),
+        IconSet::Unicode
+    );
+}
+
+#[test]
+fn test_ascii_set_is_printable_ascii() {
+    for icon in ALL_ICONS {
+        let glyph = IconSet::Ascii.glyph(icon);
+        assert!(
+            !glyph.is_empty() && glyph.chars().all(|c| c.is_ascii_graphic()),
+            "{:?} -> {:?}",
+            icon,
+            glyph
+        );
+    }
+}
+
+#[test]
+fn test_only_nerd_font_set_uses_private_use_glyphs() {
+    let private_use = |glyph: &str| {
+        glyph
+            .chars()
+            .any(|c| ('\u{e000}'..='\u{f8ff}').contains(&c) || c >= '\u{f0000}')
+    };
+    for icon in ALL_ICONS {
+        assert!(!private_use(IconSet::Unicode.glyph(icon)), "{:?}", icon);
+    }
+    assert_eq!(IconSet::NerdFont.glyph(Icon::UpdateAvailable), "\u{f06b0}");
+    assert_eq!(IconSet::Unicode.glyph(Icon::Healthy), "🟢");
+}
+
+#[test]
+fn test_style_icons_parse_from_toml() {
+    let parsed: StyleConfig =
+        toml::from_str("mode = \"nerd_font\"\nseparator = \" | \"\nicons = \"ascii\"").unwrap();
+    assert_eq!(parsed.icons, Some(IconSet::Ascii));
+
+    let parsed: StyleConfig = toml::from_str("mode = \"plain\"\nseparator = \" | \"").unwrap();
+    assert_eq!(parsed.icons, None);
+}
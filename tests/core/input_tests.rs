@@ -0,0 +1,54 @@
+use ccstatus::cli::Cli;
+use ccstatus::core::input::{FileWatch, InputSource};
+use clap::Parser;
+use std::fs;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+#[test]
+fn test_input_argument_forms() {
+    assert_eq!(InputSource::from_arg(None), InputSource::Stdin);
+    assert_eq!(InputSource::from_arg(Some("-")), InputSource::Stdin);
+    assert_eq!(
+        InputSource::from_arg(Some(" {\"model\": {}} ")),
+        InputSource::Inline("{\"model\": {}}".to_string())
+    );
+    let file = InputSource::from_arg(Some("status.json"));
+    assert_eq!(file, InputSource::File(PathBuf::from("status.json")));
+    assert_eq!(file.path(), Some(PathBuf::from("status.json").as_path()));
+    assert_eq!(InputSource::from_arg(Some("{}")).path(), None);
+
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("input.json");
+    fs::write(&path, "{\"version\": \"1.0.80\"}").unwrap();
+    let source = InputSource::from_arg(path.to_str());
+    assert_eq!(source.read().unwrap(), "{\"version\": \"1.0.80\"}");
+
+    // --watch only makes sense with an input to watch
+    assert!(Cli::try_parse_from(["ccstatus", "--watch"]).is_err());
+    let cli = Cli::try_parse_from(["ccstatus", "--input", "status.json", "--watch"]).unwrap();
+    assert_eq!(cli.input.as_deref(), Some("status.json"));
+    assert!(cli.watch);
+}
+
+#[test]
+fn test_file_watch_reports_each_change_once() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("input.json");
+    let mut watch = FileWatch::new(&path);
+
+    assert!(!watch.poll(), "Missing file is no change");
+    fs::write(&path, "{}").unwrap();
+    assert!(watch.poll(), "First sight of the file renders");
+    assert!(!watch.poll());
+
+    // Same mtime granularity is possible in fast tests; the size still differs
+    fs::write(&path, "{\"model\": {}}").unwrap();
+    assert!(watch.poll());
+    assert!(!watch.poll());
+
+    fs::remove_file(&path).unwrap();
+    assert!(!watch.poll());
+    fs::write(&path, "{\"model\": {}}").unwrap();
+    assert!(watch.poll(), "A recreated file renders again");
+}
@@ -0,0 +1,89 @@
+use ccstatus::config::{Config, SegmentConfig, SegmentId, StyleMode};
+use ccstatus::core::layout::LayoutTemplate;
+use ccstatus::core::segments::SegmentData;
+use ccstatus::core::StatusLineGenerator;
+use std::collections::HashMap;
+
+fn render(template: &str, shown: &[(SegmentId, &str)]) -> String {
+    LayoutTemplate::parse(template).render(|id| {
+        shown
+            .iter()
+            .find(|(shown_id, _)| *shown_id == id)
+            .map(|(_, text)| text.to_string())
+            .unwrap_or_default()
+    })
+}
+
+#[test]
+fn test_template_orders_segments() {
+    let shown = [
+        (SegmentId::Model, "M"),
+        (SegmentId::Directory, "D"),
+        (SegmentId::Git, "G"),
+    ];
+    assert_eq!(
+        render("{git} :: {model} [{directory}]", &shown),
+        "G :: M [D]"
+    );
+    assert_eq!(render("{model}\n{directory} {git}", &shown), "M\nD G");
+}
+
+#[test]
+fn test_template_drops_separator_of_empty_segment() {
+    let shown = [(SegmentId::Model, "M"), (SegmentId::Git, "G")];
+    assert_eq!(render("{model} | {directory} | {git}", &shown), "M | G");
+    assert_eq!(render("{directory} | {model}", &shown), "M");
+    assert_eq!(render("<{model} | {usage}>", &shown), "<M>");
+
+    // A row whose segments are all empty disappears
+    assert_eq!(render("{model}\n{usage} {update}", &shown), "M");
+}
+
+#[test]
+fn test_template_literals_and_unknown_placeholders() {
+    let shown = [(SegmentId::Model, "M")];
+    assert_eq!(render("{{{model}}}", &shown), "{M}");
+    assert_eq!(render("{model} {nope}", &shown), "M {nope}");
+    assert_eq!(render("{model", &shown), "{model");
+    assert_eq!(
+        LayoutTemplate::parse("{ model } {git} {nope}").segment_ids(),
+        vec![SegmentId::Model, SegmentId::Git]
+    );
+}
+
+#[test]
+fn test_generator_uses_config_template() {
+    let mut config = Config::default();
+    config.style.mode = StyleMode::Plain;
+    config.template = Some("{git} / {model}".to_string());
+
+    let segment = |id: SegmentId, text: &str| {
+        let mut segment_config: SegmentConfig = config
+            .segments
+            .iter()
+            .find(|s| s.id == id)
+            .cloned()
+            .unwrap();
+        segment_config.icon.plain = String::new();
+        segment_config.colors.icon = None;
+        segment_config.colors.text = None;
+        segment_config.colors.background = None;
+        segment_config.styles.text_bold = false;
+        (
+            segment_config,
+            SegmentData {
+                primary: text.to_string(),
+                secondary: String::new(),
+                metadata: HashMap::new(),
+            },
+        )
+    };
+    let segments = vec![
+        segment(SegmentId::Model, "Sonnet 4"),
+        segment(SegmentId::Directory, "crate"),
+        segment(SegmentId::Git, "main"),
+    ];
+
+    let statusline = StatusLineGenerator::new(config).generate(segments);
+    assert_eq!(statusline, " main /  Sonnet 4");
+}
@@ -2,5 +2,31 @@
 //!
 //! Tests for core functionality components
 
+pub mod attention_tests;
+pub mod capabilities_tests;
+pub mod color_tests;
+pub mod compat_tests;
+pub mod env_tests;
+pub mod format_tests;
+pub mod heartbeat_tests;
+pub mod hyperlink_tests;
+#[cfg(feature = "self-update")]
+pub mod i18n_tests;
+pub mod icons_tests;
+pub mod input_tests;
+pub mod layout_tests;
 #[cfg(feature = "network-monitoring")]
 pub mod network;
+pub mod output_tests;
+pub mod perf_tests;
+pub mod preview_tests;
+pub mod recovery_tests;
+pub mod responsive_tests;
+pub mod scheduler_tests;
+pub mod segments;
+#[cfg(any(feature = "runtime-tokio", feature = "runtime-smol"))]
+pub mod shutdown_tests;
+pub mod snapshot_tests;
+pub mod state_location_tests;
+pub mod stats_tests;
+pub mod themes_tests;
@@ -0,0 +1,218 @@
+#![cfg(feature = "network-monitoring")]
+
+//! Tests for alert routing, debounce and dedup (`alerts`)
+
+use ccstatus::config::{AlertEvent, AlertSinkKind, AlertTrigger, HostPolicy};
+use ccstatus::core::network::alerts::{
+    Alert, AlertDispatcher, AlertLog, AlertRoute, AlertSink, WebhookSink,
+};
+use ccstatus::core::network::status_hooks::StatusChange;
+use ccstatus::core::network::*;
+use std::sync::{Arc, Mutex};
+use tempfile::TempDir;
+
+/// Records delivered alert keys; fails while `failing` is set
+#[derive(Clone, Default)]
+struct RecordingSink {
+    sent: Arc<Mutex<Vec<String>>>,
+    failing: Arc<Mutex<bool>>,
+}
+
+#[async_trait::async_trait]
+impl AlertSink for RecordingSink {
+    fn name(&self) -> &'static str {
+        "recording"
+    }
+
+    async fn send(&self, alert: &Alert) -> Result<(), NetworkError> {
+        if *self.failing.lock().unwrap() {
+            return Err(NetworkError::HttpError("unreachable".to_string()));
+        }
+        self.sent.lock().unwrap().push(alert.key.clone());
+        Ok(())
+    }
+}
+
+fn triggers(on: &[&str]) -> Vec<AlertTrigger> {
+    on.iter().map(|trigger| trigger.parse().unwrap()).collect()
+}
+
+fn state(status: NetworkStatus, since: &str) -> MonitoringSnapshot {
+    let mut state = MonitoringSnapshot {
+        status,
+        timestamp: "2025-01-25T10:40:00-08:00".to_string(),
+        ..Default::default()
+    };
+    state.monitoring_state.status_since = Some(since.to_string());
+    state.network.last_http_status = 529;
+    state.network.error_type = Some("overloaded_error".to_string());
+    state
+}
+
+#[test]
+fn test_alerts_describe_status_and_pending_update() {
+    let temp_dir = TempDir::new().unwrap();
+    let dispatcher = AlertDispatcher::new(Vec::new(), temp_dir.path().join("alerts.json"))
+        .with_pending_update(Some("2.3.0".to_string()));
+
+    let error = state(NetworkStatus::Error, "2025-01-25T10:30:00-08:00");
+    let alerts = dispatcher.alerts_for(&StatusChange {
+        previous: &NetworkStatus::Error,
+        state: &error,
+        status_secs: 600,
+    });
+    assert_eq!(alerts.len(), 2);
+    assert_eq!(alerts[0].event, AlertEvent::Outage);
+    assert_eq!(alerts[0].key, "outage:2025-01-25T10:30:00-08:00");
+    assert_eq!(
+        alerts[0].message,
+        "Claude API has been failing for 10m 00s (HTTP 529 overloaded_error)"
+    );
+    assert_eq!(alerts[1].key, "update:2.3.0");
+
+    // Healthy is only worth an alert right after trouble
+    let healthy = state(NetworkStatus::Healthy, "2025-01-25T10:45:00-08:00");
+    let dispatcher = AlertDispatcher::new(Vec::new(), temp_dir.path().join("alerts.json"));
+    let recovered = dispatcher.alerts_for(&StatusChange {
+        previous: &NetworkStatus::Error,
+        state: &healthy,
+        status_secs: 0,
+    });
+    assert_eq!(recovered[0].event, AlertEvent::Recovered);
+    assert!(dispatcher
+        .alerts_for(&StatusChange {
+            previous: &NetworkStatus::Healthy,
+            state: &healthy,
+            status_secs: 60,
+        })
+        .is_empty());
+}
+
+#[tokio::test]
+async fn test_routes_by_trigger_and_dedups_per_occurrence() {
+    let temp_dir = TempDir::new().unwrap();
+    let log_path = temp_dir.path().join("alerts.json");
+    let pager = RecordingSink::default();
+    let chat = RecordingSink::default();
+    let dispatcher = AlertDispatcher::new(
+        vec![
+            AlertRoute::new(Box::new(pager.clone()), triggers(&["critical>5m"]), 0),
+            AlertRoute::new(
+                Box::new(chat.clone()),
+                triggers(&["update", "recovered"]),
+                0,
+            ),
+        ],
+        log_path.clone(),
+    )
+    .with_pending_update(Some("2.3.0".to_string()));
+
+    let error = state(NetworkStatus::Error, "2025-01-25T10:30:00-08:00");
+    let mut change = StatusChange {
+        previous: &NetworkStatus::Error,
+        state: &error,
+        status_secs: 120,
+    };
+    let alerts = dispatcher.alerts_for(&change);
+    assert_eq!(dispatcher.dispatch(&alerts, 1_000).await, 1);
+    assert!(
+        pager.sent.lock().unwrap().is_empty(),
+        "Outage not yet 5m old"
+    );
+    assert_eq!(*chat.sent.lock().unwrap(), vec!["update:2.3.0"]);
+
+    // Later probes of the same outage and update deliver nothing new
+    change.status_secs = 360;
+    let alerts = dispatcher.alerts_for(&change);
+    assert_eq!(dispatcher.dispatch(&alerts, 1_240).await, 1);
+    assert_eq!(dispatcher.dispatch(&alerts, 1_300).await, 0);
+    assert_eq!(
+        *pager.sent.lock().unwrap(),
+        vec!["outage:2025-01-25T10:30:00-08:00"]
+    );
+    assert_eq!(chat.sent.lock().unwrap().len(), 1);
+
+    let log = AlertLog::load(&log_path).await;
+    assert_eq!(log.routes["0:recording"].last_sent["outage"], 1_240);
+    assert!(log.routes["1:recording"].keys.contains_key("update:2.3.0"));
+}
+
+#[tokio::test]
+async fn test_debounce_and_retry_after_failure() {
+    let temp_dir = TempDir::new().unwrap();
+    let sink = RecordingSink::default();
+    let dispatcher = AlertDispatcher::new(
+        vec![AlertRoute::new(
+            Box::new(sink.clone()),
+            triggers(&["outage"]),
+            600,
+        )],
+        temp_dir.path().join("alerts.json"),
+    );
+    let outage = |since: &str| {
+        vec![Alert::new(
+            AlertEvent::Outage,
+            format!("outage:{}", since),
+            30,
+            "failing".to_string(),
+        )]
+    };
+
+    // A failed delivery is not recorded, so the next probe retries it
+    *sink.failing.lock().unwrap() = true;
+    assert_eq!(dispatcher.dispatch(&outage("a"), 1_000).await, 0);
+    *sink.failing.lock().unwrap() = false;
+    assert_eq!(dispatcher.dispatch(&outage("a"), 1_010).await, 1);
+
+    // A flapping API opens new outages, but the route waits out its debounce
+    assert_eq!(dispatcher.dispatch(&outage("b"), 1_300).await, 0);
+    assert_eq!(dispatcher.dispatch(&outage("c"), 1_610).await, 1);
+    assert_eq!(*sink.sent.lock().unwrap(), vec!["outage:a", "outage:c"]);
+}
+
+#[tokio::test]
+async fn test_webhook_payloads_and_host_policy() {
+    let alert = Alert::new(
+        AlertEvent::Outage,
+        "outage:a".to_string(),
+        600,
+        "Claude API has been failing for 10m 00s".to_string(),
+    );
+
+    let slack = WebhookSink::new(
+        "https://hooks.slack.com/services/T/B/X".to_string(),
+        AlertSinkKind::Slack,
+        HostPolicy::default(),
+    );
+    let body: serde_json::Value = serde_json::from_str(&slack.body(&alert).unwrap()).unwrap();
+    assert_eq!(
+        body["text"],
+        "*Claude API outage* (critical)\nClaude API has been failing for 10m 00s"
+    );
+
+    let discord = WebhookSink::new(
+        "https://discord.com/api/webhooks/1/X".to_string(),
+        AlertSinkKind::Discord,
+        HostPolicy::default(),
+    );
+    assert_eq!(discord.name(), "discord");
+    let body: serde_json::Value = serde_json::from_str(&discord.body(&alert).unwrap()).unwrap();
+    assert_eq!(
+        body["content"],
+        "**Claude API outage** (critical)\nClaude API has been failing for 10m 00s"
+    );
+
+    let webhook = WebhookSink::new(
+        "https://alerts.example.com/hook".to_string(),
+        AlertSinkKind::Webhook,
+        HostPolicy::new(vec!["api.anthropic.com".to_string()]),
+    );
+    let body: serde_json::Value = serde_json::from_str(&webhook.body(&alert).unwrap()).unwrap();
+    assert_eq!(body["event"], "outage");
+    assert_eq!(body["severity"], "critical");
+    assert_eq!(body["duration_secs"], 600);
+    assert!(matches!(
+        webhook.send(&alert).await,
+        Err(NetworkError::BlockedByPolicy(_))
+    ));
+}
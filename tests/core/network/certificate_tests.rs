@@ -0,0 +1,100 @@
+#![cfg(feature = "network-monitoring")]
+
+//! Tests for certificate chains read from curl probes (`certificate`)
+
+use ccstatus::core::network::certificate::{format_date, CertificateChain, CertificateInfo};
+
+/// 2026-10-01T00:00:00Z
+const NOW: i64 = 1_790_812_800;
+const DAY: i64 = 86_400;
+
+fn entries(lines: &[&str]) -> Vec<String> {
+    lines.iter().map(|line| line.to_string()).collect()
+}
+
+fn chain(expiries: &[i64], trusted: bool) -> CertificateChain {
+    CertificateChain {
+        certs: expiries
+            .iter()
+            .map(|not_after| CertificateInfo {
+                not_after: Some(*not_after),
+                ..Default::default()
+            })
+            .collect(),
+        trusted,
+    }
+}
+
+#[test]
+fn test_parses_curl_certinfo() {
+    let chain = CertificateChain::from_certinfo(
+        &[
+            entries(&[
+                "Subject:CN = gateway.example.com",
+                "Issuer:C = US, O = Let's Encrypt, CN = R11",
+                "Version:2",
+                "Expire date:Oct  8 12:30:00 2026 GMT",
+                "X509v3 Subject Alternative Name:DNS:gateway.example.com, DNS:*.example.com",
+                "Cert:-----BEGIN CERTIFICATE-----",
+            ]),
+            entries(&[
+                "Subject:C = US, O = Let's Encrypt, CN = R11",
+                "Issuer:C = US, O = Internet Security Research Group, CN = ISRG Root X1",
+                "Expire date:2027-03-12 23:59:59 GMT",
+            ]),
+        ],
+        true,
+    )
+    .unwrap();
+
+    let leaf = chain.leaf().unwrap();
+    assert_eq!(leaf.subject, "CN = gateway.example.com");
+    assert_eq!(leaf.issuer, "C = US, O = Let's Encrypt, CN = R11");
+    assert_eq!(
+        leaf.sans,
+        vec!["DNS:gateway.example.com", "DNS:*.example.com"]
+    );
+    assert_eq!(leaf.not_after, Some(NOW + 7 * DAY + 45_000));
+    assert_eq!(format_date(chain.certs[1].not_after.unwrap()), "2027-03-12");
+    assert_eq!(chain.not_after(), leaf.not_after);
+
+    assert!(CertificateChain::from_certinfo(&[], true).is_none());
+    let unreadable = CertificateInfo::from_certinfo(&entries(&["Expire date:soon"]));
+    assert_eq!(unreadable.not_after, None);
+}
+
+#[test]
+fn test_warns_before_expiry() {
+    assert_eq!(chain(&[NOW + 30 * DAY], true).warning(NOW, 14), None);
+    assert_eq!(
+        chain(&[NOW + 30 * DAY, NOW + 5 * DAY + 60], true).warning(NOW, 14),
+        Some("certificate expires in 5 days (2026-10-06)".to_string())
+    );
+    assert_eq!(
+        chain(&[NOW + DAY], true).warning(NOW, 14),
+        Some("certificate expires in 1 day (2026-10-02)".to_string())
+    );
+    assert_eq!(
+        chain(&[NOW + 3600], true).warning(NOW, 14),
+        Some("certificate expires today (2026-10-01)".to_string())
+    );
+    assert_eq!(
+        chain(&[NOW - DAY], true).warning(NOW, 14),
+        Some("certificate expired on 2026-09-30".to_string())
+    );
+    // 0 turns off expiry warnings, but not for certificates already expired
+    assert_eq!(chain(&[NOW + DAY], true).warning(NOW, 0), None);
+    assert!(chain(&[NOW - DAY], true).warning(NOW, 0).is_some());
+}
+
+#[test]
+fn test_untrusted_chain_always_warns() {
+    assert_eq!(
+        chain(&[NOW + 365 * DAY], false).warning(NOW, 0),
+        Some("untrusted certificate chain".to_string())
+    );
+    assert_eq!(
+        chain(&[], false).warning(NOW, 14),
+        Some("untrusted certificate chain".to_string())
+    );
+}
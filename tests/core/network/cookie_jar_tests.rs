@@ -0,0 +1,187 @@
+#![cfg(feature = "network-monitoring")]
+
+//! Tests for the persisted cookie jar (`network.persist_cookies`)
+
+use ccstatus::core::network::cookie_jar::PersistentCookieJar;
+use ccstatus::core::network::*;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tempfile::TempDir;
+
+const NOW: i64 = 1_760_000_000;
+
+#[tokio::test]
+async fn test_missing_jar_is_empty() {
+    let temp_dir = TempDir::new().unwrap();
+    let jar = PersistentCookieJar::load(
+        &temp_dir.path().join("cookies.json"),
+        "api.example.com",
+        NOW,
+    )
+    .await;
+
+    assert_eq!(jar.host(), "api.example.com");
+    assert!(jar.cookies().is_empty());
+    assert_eq!(jar.cookie_header(), None);
+}
+
+#[tokio::test]
+async fn test_round_trip_and_host_scoping() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("cookies.json");
+
+    let mut jar = PersistentCookieJar::load(&path, "API.example.com", NOW).await;
+    jar.store_set_cookie_headers(
+        "cf_clearance=abc; Path=/; Max-Age=3600; Secure; HttpOnly\n__cf_bm=xyz; Domain=.example.com",
+        NOW,
+    );
+    jar.save().await.unwrap();
+
+    let reloaded = PersistentCookieJar::load(&path, "api.example.com", NOW).await;
+    assert_eq!(
+        reloaded.cookie_header().as_deref(),
+        Some("cf_clearance=abc; __cf_bm=xyz")
+    );
+
+    // A jar recorded for another host is never sent elsewhere
+    let other = PersistentCookieJar::load(&path, "other.example.net", NOW).await;
+    assert!(other.cookies().is_empty());
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_jar_file_is_private() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("cookies.json");
+
+    let mut jar = PersistentCookieJar::load(&path, "api.example.com", NOW).await;
+    jar.store_set_cookie("cf_clearance=abc", NOW);
+    jar.save().await.unwrap();
+
+    let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+}
+
+#[tokio::test]
+async fn test_foreign_domain_cookies_ignored() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut jar = PersistentCookieJar::load(
+        &temp_dir.path().join("cookies.json"),
+        "api.example.com",
+        NOW,
+    )
+    .await;
+
+    jar.store_set_cookie("tracker=1; Domain=ads.test", NOW);
+    jar.store_set_cookie("sibling=1; Domain=www.example.com", NOW);
+    assert!(jar.cookies().is_empty());
+}
+
+#[tokio::test]
+async fn test_expiry_and_replacement() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("cookies.json");
+
+    let mut jar = PersistentCookieJar::load(&path, "api.example.com", NOW).await;
+    jar.store_set_cookie("short=1; Max-Age=60", NOW);
+    jar.store_set_cookie("legacy=1; Expires=Thu, 01-Jan-37 00:00:00 GMT", NOW);
+    jar.store_set_cookie("gone=1; Expires=Wed, 21 Oct 2015 07:28:00 GMT", NOW);
+    jar.store_set_cookie("session=old", NOW);
+    jar.store_set_cookie("session=new", NOW);
+    assert_eq!(
+        jar.cookie_header().as_deref(),
+        Some("short=1; legacy=1; session=new")
+    );
+
+    // Max-Age=0 deletes
+    jar.store_set_cookie("legacy=; Max-Age=0", NOW);
+    jar.save().await.unwrap();
+
+    // Expired cookies are dropped on load
+    let later = PersistentCookieJar::load(&path, "api.example.com", NOW + 120).await;
+    assert_eq!(later.cookie_header().as_deref(), Some("session=new"));
+}
+
+/// HTTP client that records request headers and replies with Set-Cookie
+#[derive(Clone, Default)]
+struct RecordingHttpClient {
+    requests: Arc<Mutex<Vec<HashMap<String, String>>>>,
+}
+
+#[async_trait::async_trait]
+impl HttpClientTrait for RecordingHttpClient {
+    async fn execute_request(
+        &self,
+        _url: String,
+        headers: HashMap<String, String>,
+        _body: Vec<u8>,
+        _timeout_ms: u32,
+    ) -> Result<
+        (
+            u16,
+            Duration,
+            String,
+            HashMap<String, String>,
+            Option<String>,
+        ),
+        String,
+    > {
+        self.requests.lock().unwrap().push(headers);
+        let mut response_headers = HashMap::new();
+        response_headers.insert(
+            "set-cookie".to_string(),
+            "cf_clearance=token; Max-Age=3600".to_string(),
+        );
+        Ok((
+            200,
+            Duration::from_millis(100),
+            "Total:100ms".to_string(),
+            response_headers,
+            None,
+        ))
+    }
+}
+
+#[tokio::test]
+async fn test_probe_persists_and_replays_cookies() {
+    let temp_dir = TempDir::new().unwrap();
+    let jar_path = temp_dir.path().join("cookies.json");
+    let client = RecordingHttpClient::default();
+
+    let credentials = ApiCredentials {
+        base_url: "https://api.anthropic.com".to_string(),
+        auth_token: "test-token".to_string(),
+        source: CredentialSource::Environment,
+        expires_at: None,
+    };
+
+    for _ in 0..2 {
+        let monitor = HttpMonitor::new(Some(temp_dir.path().join("monitoring.json")))
+            .unwrap()
+            .with_http_client(Box::new(client.clone()))
+            .with_cookie_jar(jar_path.clone());
+        #[cfg(feature = "timings-curl")]
+        let monitor = monitor.without_curl_runner();
+        let mut monitor = monitor;
+
+        monitor
+            .probe(ProbeMode::Green, credentials.clone(), None)
+            .await
+            .unwrap();
+    }
+
+    let requests = client.requests.lock().unwrap();
+    assert_eq!(requests.len(), 2);
+    assert!(
+        !requests[0].contains_key("Cookie"),
+        "First run starts empty"
+    );
+    assert_eq!(
+        requests[1].get("Cookie").map(String::as_str),
+        Some("cf_clearance=token"),
+        "Second run replays the persisted clearance cookie"
+    );
+}
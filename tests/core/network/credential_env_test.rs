@@ -249,3 +249,34 @@ async fn test_env_empty_values() {
         "Should not find credentials with empty token"
     );
 }
+
+#[tokio::test]
+async fn test_target_credentials_use_own_token_env() {
+    let _isolated = IsolatedEnv::new();
+
+    env::set_var("ANTHROPIC_BASE_URL", "https://api.anthropic.com");
+    env::set_var("ANTHROPIC_AUTH_TOKEN", "anthropic-token");
+    env::set_var("CCSTATUS_TEST_RELAY_TOKEN", "relay-token");
+
+    let cm = CredentialManager::new()
+        .expect("Failed to create CredentialManager")
+        .with_target(
+            "https://relay.example.com".to_string(),
+            Some("CCSTATUS_TEST_RELAY_TOKEN".to_string()),
+        );
+    let creds = cm
+        .get_credentials()
+        .await
+        .expect("Target lookup should not fail")
+        .expect("Target credentials should be found");
+    assert_eq!(creds.base_url, "https://relay.example.com");
+    assert_eq!(creds.auth_token, "relay-token");
+
+    // Unset token variable: no credentials, no fallback to the regular chain
+    env::remove_var("CCSTATUS_TEST_RELAY_TOKEN");
+    let creds = cm
+        .get_credentials()
+        .await
+        .expect("Target lookup should not fail");
+    assert!(creds.is_none());
+}
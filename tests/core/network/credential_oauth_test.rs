@@ -1,3 +1,5 @@
+#![cfg(feature = "oauth-probe")]
+
 use ccstatus::core::network::{
     credential::CredentialManager,
     oauth_masquerade::testing::{
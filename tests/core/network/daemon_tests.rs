@@ -0,0 +1,84 @@
+#![cfg(feature = "network-monitoring")]
+
+//! Tests for the standalone daemon's schedule and lease (`daemon`)
+
+use ccstatus::core::network::daemon::{DaemonLease, DaemonSchedule, STALE_AFTER_SECS};
+use ccstatus::core::network::probe_profile::ProbeProfile;
+use ccstatus::core::network::*;
+use tempfile::TempDir;
+
+/// 2026-10-01T00:00:00Z
+const NOW: i64 = 1_790_812_800;
+
+#[test]
+fn test_schedule_follows_profile_periods() {
+    let schedule = DaemonSchedule::new(ProbeProfile::Standard);
+    assert_eq!(schedule.due(None, NOW), Some(ProbeMode::Cold));
+
+    let healthy = NetworkStatus::Healthy;
+    assert_eq!(schedule.due(Some((NOW - 299, &healthy)), NOW), None);
+    assert_eq!(
+        schedule.due(Some((NOW - 300, &healthy)), NOW),
+        Some(ProbeMode::Green)
+    );
+
+    let error = NetworkStatus::Error;
+    assert_eq!(schedule.due(Some((NOW - 5, &error)), NOW), None);
+    assert_eq!(
+        schedule.due(Some((NOW - 10, &error)), NOW),
+        Some(ProbeMode::Red)
+    );
+
+    // A degraded endpoint still answers; only failures switch to RED
+    let degraded = NetworkStatus::Degraded;
+    assert_eq!(schedule.due(Some((NOW - 10, &degraded)), NOW), None);
+}
+
+#[test]
+fn test_minimal_profile_never_probes_red() {
+    let schedule = DaemonSchedule::new(ProbeProfile::Minimal);
+    let error = NetworkStatus::Error;
+    assert_eq!(schedule.due(Some((NOW - 600, &error)), NOW), None);
+    assert_eq!(
+        schedule.due(Some((NOW - 1800, &error)), NOW),
+        Some(ProbeMode::Green)
+    );
+}
+
+#[test]
+fn test_lease_round_trip_and_freshness() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = DaemonLease::path_in(&temp_dir.path().join("ccstatus"));
+    assert_eq!(
+        path,
+        temp_dir
+            .path()
+            .join("ccstatus")
+            .join("ccstatus-daemon.json")
+    );
+    assert!(DaemonLease::load(&path).is_none());
+    assert!(!DaemonLease::running_at(&path, NOW));
+
+    let mut lease = DaemonLease::new(ProbeProfile::Aggressive, NOW - 600);
+    lease.heartbeat_at = NOW - 30;
+    lease.save(&path).unwrap();
+    assert_eq!(DaemonLease::load(&path), Some(lease.clone()));
+    assert!(DaemonLease::running_at(&path, NOW));
+    assert!(!DaemonLease::running_at(
+        &path,
+        NOW - 30 + STALE_AFTER_SECS + 1
+    ));
+    assert!(!path.with_extension("json.tmp").exists());
+
+    assert_eq!(
+        DaemonLease::lock_path(&path),
+        temp_dir
+            .path()
+            .join("ccstatus")
+            .join("ccstatus-daemon.lock")
+    );
+
+    std::fs::write(&path, "not json").unwrap();
+    assert!(DaemonLease::load(&path).is_none());
+    assert!(!path.exists(), "corrupt lease is quarantined");
+}
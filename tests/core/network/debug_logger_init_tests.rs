@@ -0,0 +1,58 @@
+use ccstatus::core::network::debug_logger::{
+    get_debug_logger, init_logging, reset_logging, LogLevel, LogRotation, LoggerConfig,
+};
+use serial_test::serial;
+use tempfile::TempDir;
+
+#[test]
+#[serial]
+fn test_init_logging_installs_shared_logger() {
+    let dir = TempDir::new().unwrap();
+    let debug_log = dir.path().join("debug.log");
+    init_logging(
+        LoggerConfig::from_env()
+            .with_debug_log(debug_log.clone())
+            .with_jsonl_path(dir.path().join("ops.jsonl"))
+            .with_level(LogLevel::Warn)
+            .with_rotation(LogRotation {
+                max_size_mb: 1,
+                max_archives: 2,
+            }),
+    );
+
+    let first = get_debug_logger();
+    let second = get_debug_logger();
+    assert!(first.is_enabled());
+    assert_eq!(first.get_session_id(), second.get_session_id());
+
+    first.debug_sync("Test", "below_level", "dropped");
+    second.warn_sync("Test", "at_level", "kept");
+    first.error_sync("Test", "above_level", "kept too");
+    let log = std::fs::read_to_string(&debug_log).unwrap();
+    assert!(!log.contains("below_level"));
+    assert!(log.contains("\"at_level\""));
+    assert!(log.contains("\"above_level\""));
+
+    reset_logging();
+    assert_ne!(
+        get_debug_logger().get_session_id(),
+        first.get_session_id(),
+        "Falls back to a fresh logger from the environment"
+    );
+}
+
+#[test]
+#[serial]
+fn test_logger_config_from_env_reflects_debug_flag() {
+    std::env::remove_var("CCSTATUS_DEBUG");
+    assert!(LoggerConfig::from_env().debug_log_path.is_none());
+
+    std::env::set_var("CCSTATUS_DEBUG", "true");
+    let config = LoggerConfig::from_env();
+    std::env::remove_var("CCSTATUS_DEBUG");
+    assert!(config
+        .debug_log_path
+        .is_some_and(|path| path.ends_with("ccstatus-debug.log")));
+    assert_eq!(config.level, LogLevel::Debug);
+    assert_eq!(config.rotation, LogRotation::default());
+}
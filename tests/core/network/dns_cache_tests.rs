@@ -0,0 +1,242 @@
+#![cfg(feature = "network-monitoring")]
+
+//! Tests for the probe host DNS cache (`network.dns_cache`)
+
+use ccstatus::core::network::dns_cache::{
+    resolve, DnsCache, DnsCacheStatus, DnsPin, MAX_TTL_SECS, TTL_SECS,
+};
+use ccstatus::core::network::*;
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tempfile::TempDir;
+
+const NOW: i64 = 1_760_000_000;
+
+#[tokio::test]
+async fn test_resolve_uses_system_resolver() {
+    // `localhost` comes from the hosts file, which a direct DNS query never sees
+    let resolved = resolve("localhost", Duration::from_secs(5)).await.unwrap();
+    assert!(resolved.addrs.iter().all(IpAddr::is_loopback));
+    assert!(!resolved.addrs.is_empty());
+    assert_eq!(resolved.ttl_secs, TTL_SECS);
+
+    assert!(resolve("missing.invalid", Duration::from_secs(5))
+        .await
+        .is_err());
+}
+
+#[test]
+fn test_pin_resolve_entry() {
+    let pin = DnsPin {
+        host: "api.example.com".to_string(),
+        port: 443,
+        addr: IpAddr::V4(Ipv4Addr::new(192, 0, 2, 10)),
+    };
+    assert_eq!(pin.resolve_entry(), "api.example.com:443:192.0.2.10");
+}
+
+#[tokio::test]
+async fn test_cache_round_trip_and_expiry() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("dns-cache.json");
+    let addr = IpAddr::V4(Ipv4Addr::new(192, 0, 2, 10));
+
+    let mut cache = DnsCache::load(&path, NOW).await;
+    assert!(cache.get("api.example.com", NOW).is_none());
+    cache.insert("API.example.com", vec![addr], 300, NOW);
+    cache.insert("short.example.com", vec![addr], 10, NOW);
+    cache.save().await.unwrap();
+
+    let cache = DnsCache::load(&path, NOW + 100).await;
+    assert_eq!(cache.get("api.example.com", NOW + 100), Some(&[addr][..]));
+    assert!(
+        cache.get("short.example.com", NOW + 100).is_none(),
+        "Expired on load"
+    );
+    assert!(
+        cache.get("api.example.com", NOW + 300).is_none(),
+        "Stale once the TTL passes"
+    );
+}
+
+#[tokio::test]
+async fn test_cache_ttl_cap_and_removal() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut cache = DnsCache::load(&temp_dir.path().join("dns-cache.json"), NOW).await;
+    let addr = IpAddr::V4(Ipv4Addr::new(192, 0, 2, 10));
+
+    cache.insert("api.example.com", vec![addr], u32::MAX, NOW);
+    assert!(cache
+        .get("api.example.com", NOW + MAX_TTL_SECS as i64 - 1)
+        .is_some());
+    assert!(cache
+        .get("api.example.com", NOW + MAX_TTL_SECS as i64)
+        .is_none());
+
+    cache.insert("api.example.com", vec![addr], 0, NOW);
+    assert!(
+        cache.get("api.example.com", NOW).is_none(),
+        "Zero TTL drops"
+    );
+
+    cache.insert("api.example.com", vec![addr], 300, NOW);
+    cache.remove("api.example.com");
+    assert!(cache.get("api.example.com", NOW).is_none());
+}
+
+/// HTTP client that records the pin of each request
+#[derive(Clone, Default)]
+struct PinRecordingClient {
+    pins: Arc<Mutex<Vec<Option<DnsPin>>>>,
+}
+
+#[async_trait::async_trait]
+impl HttpClientTrait for PinRecordingClient {
+    async fn execute_request(
+        &self,
+        url: String,
+        headers: HashMap<String, String>,
+        body: Vec<u8>,
+        timeout_ms: u32,
+    ) -> Result<
+        (
+            u16,
+            Duration,
+            String,
+            HashMap<String, String>,
+            Option<String>,
+        ),
+        String,
+    > {
+        self.execute_request_pinned(url, headers, body, timeout_ms, None)
+            .await
+    }
+
+    async fn execute_request_pinned(
+        &self,
+        _url: String,
+        _headers: HashMap<String, String>,
+        _body: Vec<u8>,
+        _timeout_ms: u32,
+        pin: Option<DnsPin>,
+    ) -> Result<
+        (
+            u16,
+            Duration,
+            String,
+            HashMap<String, String>,
+            Option<String>,
+        ),
+        String,
+    > {
+        self.pins.lock().unwrap().push(pin);
+        Ok((
+            200,
+            Duration::from_millis(100),
+            "Total:100ms".to_string(),
+            HashMap::new(),
+            None,
+        ))
+    }
+}
+
+fn credentials(base_url: &str) -> ApiCredentials {
+    ApiCredentials {
+        base_url: base_url.to_string(),
+        auth_token: "test-token".to_string(),
+        source: CredentialSource::Environment,
+        expires_at: None,
+    }
+}
+
+async fn probe_with_cache(
+    temp_dir: &TempDir,
+    client: &PinRecordingClient,
+    mode: ProbeMode,
+    base_url: &str,
+) -> MonitoringSnapshot {
+    let state_path = temp_dir.path().join("monitoring.json");
+    let monitor = HttpMonitor::new(Some(state_path.clone()))
+        .unwrap()
+        .with_http_client(Box::new(client.clone()))
+        .with_dns_cache(temp_dir.path().join("dns-cache.json"));
+    #[cfg(feature = "timings-curl")]
+    let monitor = monitor.without_curl_runner();
+    let mut monitor = monitor;
+
+    monitor
+        .probe(mode, credentials(base_url), None)
+        .await
+        .unwrap();
+    serde_json::from_str(&std::fs::read_to_string(state_path).unwrap()).unwrap()
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+#[tokio::test]
+async fn test_probe_hit_pins_and_red_bypasses() {
+    let temp_dir = TempDir::new().unwrap();
+    let cache_path = temp_dir.path().join("dns-cache.json");
+    let addr = IpAddr::V4(Ipv4Addr::new(192, 0, 2, 10));
+    let mut cache = DnsCache::load(&cache_path, unix_now()).await;
+    cache.insert("api.example.com", vec![addr], 300, unix_now());
+    cache.save().await.unwrap();
+
+    let client = PinRecordingClient::default();
+    let state = probe_with_cache(
+        &temp_dir,
+        &client,
+        ProbeMode::Green,
+        "https://api.example.com",
+    )
+    .await;
+    assert_eq!(state.network.dns_cache, Some(DnsCacheStatus::Hit));
+    assert_eq!(state.network.dns_lookup_ms, None);
+    assert_eq!(
+        client.pins.lock().unwrap()[0],
+        Some(DnsPin {
+            host: "api.example.com".to_string(),
+            port: 443,
+            addr,
+        })
+    );
+
+    let state = probe_with_cache(
+        &temp_dir,
+        &client,
+        ProbeMode::Red,
+        "https://api.example.com",
+    )
+    .await;
+    assert_eq!(state.network.dns_cache, Some(DnsCacheStatus::Bypass));
+    assert_eq!(client.pins.lock().unwrap()[1], None, "RED resolves afresh");
+    assert!(
+        DnsCache::load(&cache_path, unix_now())
+            .await
+            .get("api.example.com", unix_now())
+            .is_none(),
+        "RED drops the cached address"
+    );
+}
+
+#[tokio::test]
+async fn test_ip_literal_host_skips_cache() {
+    let temp_dir = TempDir::new().unwrap();
+    let client = PinRecordingClient::default();
+    let state = probe_with_cache(
+        &temp_dir,
+        &client,
+        ProbeMode::Green,
+        "http://127.0.0.1:8080",
+    )
+    .await;
+    assert_eq!(state.network.dns_cache, None);
+    assert_eq!(client.pins.lock().unwrap()[0], None);
+}
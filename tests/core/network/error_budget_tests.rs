@@ -0,0 +1,147 @@
+#![cfg(feature = "network-monitoring")]
+
+//! Tests for the failed-probe budget and its burn rate (`error_budget`)
+
+use ccstatus::config::ErrorBudgetConfig;
+use ccstatus::core::network::error_budget::{ErrorBudget, ErrorBudgetPolicy, MAX_WINDOW_HOURS};
+use ccstatus::core::network::probe_history::ProbeRecord;
+use ccstatus::core::network::*;
+
+/// 2026-10-01T00:00:00Z
+const NOW: i64 = 1_790_812_800;
+
+fn policy() -> ErrorBudgetPolicy {
+    ErrorBudgetPolicy::from_config(&ErrorBudgetConfig::default()).unwrap()
+}
+
+/// `count` probes with `status`, one every five minutes ending `ago_secs` before `NOW`
+fn probes(count: i64, status: NetworkStatus, ago_secs: i64) -> Vec<(i64, NetworkStatus)> {
+    (0..count)
+        .map(|i| (NOW - ago_secs - i * 300, status.clone()))
+        .collect()
+}
+
+fn evaluate(probes: &[(i64, NetworkStatus)]) -> ErrorBudget {
+    policy().evaluate(probes.iter().map(|(at, status)| (*at, status)), NOW)
+}
+
+#[test]
+fn test_config_validation() {
+    assert_eq!(ErrorBudgetPolicy::default(), policy());
+
+    let invalid = [
+        ErrorBudgetConfig {
+            allowed_failure_percent: 0.0,
+            ..Default::default()
+        },
+        ErrorBudgetConfig {
+            allowed_failure_percent: 101.0,
+            ..Default::default()
+        },
+        ErrorBudgetConfig {
+            window_hours: MAX_WINDOW_HOURS + 1,
+            ..Default::default()
+        },
+        ErrorBudgetConfig {
+            window_hours: 1,
+            burn_window_minutes: 61,
+            ..Default::default()
+        },
+        ErrorBudgetConfig {
+            burn_window_minutes: 0,
+            ..Default::default()
+        },
+        ErrorBudgetConfig {
+            burn_alert: f64::NAN,
+            ..Default::default()
+        },
+    ];
+    for config in invalid {
+        assert!(
+            matches!(
+                ErrorBudgetPolicy::from_config(&config),
+                Err(NetworkError::ValidationError(_))
+            ),
+            "{:?} should be rejected",
+            config
+        );
+    }
+
+    let config: ErrorBudgetConfig = toml::from_str("allowed_failure_percent = 0.5").unwrap();
+    assert_eq!(config.window_hours, 24);
+    assert_eq!(config.burn_alert, 10.0);
+}
+
+#[test]
+fn test_budget_counts_failures_within_window() {
+    // A healthy day with two failures in the morning; yesterday's outage is out of the window
+    let mut history = probes(286, NetworkStatus::Healthy, 0);
+    history.extend(probes(2, NetworkStatus::Error, 20 * 3600));
+    history.extend(probes(12, NetworkStatus::Error, 25 * 3600));
+    history.extend(probes(5, NetworkStatus::Unknown, 3600));
+    history.extend(probes(1, NetworkStatus::BlockedByPolicy, 0));
+
+    let budget = evaluate(&history);
+    assert_eq!((budget.probes, budget.failed), (288, 2));
+    assert!((budget.allowed_failures() - 2.88).abs() < 1e-9);
+    assert!((budget.remaining_percent() - 30.555).abs() < 0.01);
+    // The last hour was clean
+    assert_eq!((budget.recent_probes, budget.recent_failed), (13, 0));
+    assert_eq!(budget.burn_rate, 0.0);
+    assert!(!budget.burning);
+    assert_eq!(budget.evaluated_at, NOW);
+
+    history.extend(probes(3, NetworkStatus::Error, 21 * 3600));
+    assert_eq!(evaluate(&history).remaining_percent(), 0.0);
+    assert_eq!(evaluate(&[]).remaining_percent(), 100.0);
+}
+
+#[test]
+fn test_burn_rate_warns_at_threshold() {
+    // 1 failure in 12 probes is 8.3% against 1% allowed: below the 10x default
+    let mut history = probes(11, NetworkStatus::Healthy, 300);
+    history.extend(probes(1, NetworkStatus::Error, 0));
+    let budget = evaluate(&history);
+    assert_eq!((budget.recent_probes, budget.recent_failed), (12, 1));
+    assert!((budget.burn_rate - 8.33).abs() < 0.01);
+    assert!(!budget.burning);
+
+    history.extend(probes(1, NetworkStatus::Error, 1800));
+    let budget = evaluate(&history);
+    assert!((budget.burn_rate - 15.38).abs() < 0.01);
+    assert!(budget.burning);
+
+    // A lone failed probe after a quiet spell does not warn on its own
+    let budget = evaluate(&probes(1, NetworkStatus::Error, 0));
+    assert_eq!(budget.burn_rate, 100.0);
+    assert!(!budget.burning);
+}
+
+#[test]
+fn test_evaluate_history_includes_current_probe() {
+    let record = |ago_secs: i64, status: NetworkStatus| ProbeRecord {
+        timestamp: chrono::DateTime::from_timestamp(NOW - ago_secs, 0)
+            .unwrap()
+            .to_rfc3339(),
+        version: "1.0.0".to_string(),
+        mode: "green".to_string(),
+        http_status: if status == NetworkStatus::Error {
+            500
+        } else {
+            200
+        },
+        latency_ms: 900,
+        status,
+        error_type: None,
+    };
+    let records = vec![
+        record(900, NetworkStatus::Error),
+        record(600, NetworkStatus::Error),
+        record(300, NetworkStatus::Healthy),
+    ];
+
+    let budget = policy().evaluate_history(&records, &NetworkStatus::Error, NOW);
+    assert_eq!((budget.probes, budget.failed), (4, 3));
+    assert_eq!(budget.burn_rate, 75.0);
+    assert!(budget.burning);
+}
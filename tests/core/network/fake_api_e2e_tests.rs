@@ -0,0 +1,258 @@
+//! End-to-end probes against the bundled fake Anthropic API (`ccstatus-fake-api`)
+//!
+//! Unlike the mock-client tests these drive the real transport (isahc, or curl
+//! with `timings-curl`), cookie jar, proxy health check and state persistence
+//! over a local socket.
+
+use ccstatus::core::network::proxy_health::ProxyHealthLevel;
+use ccstatus::core::network::*;
+use ccstatus::runtime;
+use ccstatus_fake_api::{FakeApi, HealthBehavior, MessagesBehavior, RETRY_AFTER_SECS};
+use std::time::Duration;
+use tempfile::TempDir;
+
+struct Harness {
+    api: FakeApi,
+    dir: TempDir,
+}
+
+impl Harness {
+    fn new() -> Self {
+        Self {
+            api: FakeApi::start().unwrap(),
+            dir: TempDir::new().unwrap(),
+        }
+    }
+
+    fn monitor(&self) -> HttpMonitor {
+        HttpMonitor::new(Some(self.dir.path().join("ccstatus-monitoring.json"))).unwrap()
+    }
+
+    fn creds(&self) -> ApiCredentials {
+        ApiCredentials::builder(
+            self.api.base_url(),
+            "sk-ant-fake",
+            CredentialSource::Environment,
+        )
+        .build()
+        .unwrap()
+    }
+
+    fn probe(&self, monitor: &mut HttpMonitor, mode: ProbeMode) -> ProbeOutcome {
+        runtime::block_on(monitor.probe(mode, self.creds(), None)).unwrap()
+    }
+
+    fn state(&self, monitor: &HttpMonitor) -> MonitoringSnapshot {
+        runtime::block_on(monitor.load_state()).unwrap()
+    }
+}
+
+#[test]
+fn test_green_probe_records_healthy_sample() {
+    let harness = Harness::new();
+    let mut monitor = harness.monitor();
+
+    let outcome = harness.probe(&mut monitor, ProbeMode::Green);
+    assert_eq!(outcome.status, NetworkStatus::Healthy);
+    assert_eq!(outcome.metrics.last_http_status, 200);
+    assert_eq!(outcome.metrics.error_type, None);
+    assert_eq!(outcome.rolling_len, 1);
+
+    let requests = harness.api.requests_to("/v1/messages");
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].header("x-api-key"), Some("sk-ant-fake"));
+    assert_eq!(requests[0].header("anthropic-version"), Some("2023-06-01"));
+    let payload: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+    assert!(payload.get("model").is_some());
+
+    let state = harness.state(&monitor);
+    assert_eq!(state.status, NetworkStatus::Healthy);
+    assert_eq!(state.network.rolling_totals.len(), 1);
+}
+
+#[test]
+fn test_proxy_health_endpoint_is_checked() {
+    let harness = Harness::new();
+    let mut monitor = harness.monitor();
+
+    harness.probe(&mut monitor, ProbeMode::Green);
+    let state = harness.state(&monitor);
+    assert_eq!(
+        state.network.proxy_health_level,
+        Some(ProxyHealthLevel::Healthy)
+    );
+    assert!(!harness.api.requests_to("/health").is_empty());
+
+    harness.api.set_health(HealthBehavior::Degraded);
+    harness.probe(&mut monitor, ProbeMode::Green);
+    assert_eq!(
+        harness.state(&monitor).network.proxy_health_level,
+        Some(ProxyHealthLevel::Degraded)
+    );
+}
+
+#[test]
+fn test_rate_limit_degrades_and_starts_penalty() {
+    let harness = Harness::new();
+    harness.api.set_rate_limit(Some(1));
+    let mut monitor = harness.monitor();
+
+    assert_eq!(
+        harness.probe(&mut monitor, ProbeMode::Green).status,
+        NetworkStatus::Healthy
+    );
+
+    let outcome = harness.probe(&mut monitor, ProbeMode::Green);
+    assert_eq!(outcome.metrics.last_http_status, 429);
+    assert_eq!(
+        outcome.metrics.error_type.as_deref(),
+        Some("rate_limit_error")
+    );
+    assert_eq!(outcome.status, NetworkStatus::Degraded);
+
+    let state = harness.state(&monitor);
+    let error = state
+        .last_probe_error
+        .as_ref()
+        .expect("failed probe is recorded");
+    assert_eq!(
+        error.headers.get("retry-after"),
+        Some(&RETRY_AFTER_SECS.to_string())
+    );
+    assert!(monitor.penalty_remaining_secs(&state).is_some());
+}
+
+#[test]
+fn test_cloudflare_challenge_is_classified_as_bot_challenge() {
+    for status in [403, 429, 503] {
+        let harness = Harness::new();
+        harness
+            .api
+            .set_messages(MessagesBehavior::CloudflareChallenge { status });
+        let mut monitor = harness.monitor();
+
+        let outcome = harness.probe(&mut monitor, ProbeMode::Green);
+        assert_eq!(outcome.metrics.last_http_status, status);
+        assert_eq!(
+            outcome.metrics.error_type.as_deref(),
+            Some("bot_challenge"),
+            "status {}",
+            status
+        );
+        assert_ne!(outcome.status, NetworkStatus::Healthy);
+    }
+}
+
+#[test]
+fn test_challenge_cookie_is_persisted_and_replayed() {
+    let harness = Harness::new();
+    harness
+        .api
+        .set_messages(MessagesBehavior::CloudflareChallenge { status: 403 });
+    let jar = harness.dir.path().join("cookies.json");
+
+    let mut monitor = harness.monitor().with_cookie_jar(jar.clone());
+    harness.probe(&mut monitor, ProbeMode::Green);
+    assert!(jar.exists());
+
+    // A fresh monitor, like the next statusline invocation
+    harness.api.set_messages(MessagesBehavior::Ok);
+    let mut monitor = harness.monitor().with_cookie_jar(jar);
+    harness.probe(&mut monitor, ProbeMode::Green);
+
+    let requests = harness.api.requests_to("/v1/messages");
+    let cookie = requests
+        .last()
+        .unwrap()
+        .header("cookie")
+        .unwrap_or_default();
+    assert!(
+        cookie.contains("__cf_bm=fake-clearance"),
+        "cookie: {}",
+        cookie
+    );
+}
+
+#[test]
+fn test_api_errors_are_classified() {
+    let cases = [
+        (MessagesBehavior::Unauthorized, 401, "authentication_error"),
+        (MessagesBehavior::Overloaded, 529, "overloaded_error"),
+        (MessagesBehavior::ServerError, 500, "api_error"),
+    ];
+    for (behavior, status, error_type) in cases {
+        let harness = Harness::new();
+        harness.api.set_messages(behavior);
+        let mut monitor = harness.monitor();
+
+        let outcome = harness.probe(&mut monitor, ProbeMode::Green);
+        assert_eq!(outcome.metrics.last_http_status, status);
+        assert_eq!(outcome.metrics.error_type.as_deref(), Some(error_type));
+        assert_eq!(outcome.status, NetworkStatus::Error);
+    }
+}
+
+#[test]
+fn test_timeout_and_hangup_error_types() {
+    let harness = Harness::new();
+    harness
+        .api
+        .set_messages(MessagesBehavior::Slow(Duration::from_secs(2)));
+    let mut monitor = harness.monitor().with_timeout_override_ms(200);
+
+    let outcome = harness.probe(&mut monitor, ProbeMode::Green);
+    assert_eq!(outcome.metrics.last_http_status, 0);
+    assert_eq!(outcome.metrics.error_type.as_deref(), Some("timeout"));
+
+    harness.api.set_messages(MessagesBehavior::Hangup);
+    let outcome = harness.probe(&mut harness.monitor(), ProbeMode::Green);
+    assert_eq!(
+        outcome.metrics.error_type.as_deref(),
+        Some("connection_error")
+    );
+}
+
+#[test]
+fn test_closed_port_is_connect_refused() {
+    let harness = Harness::new();
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let creds = ApiCredentials::builder(
+        format!("http://127.0.0.1:{}", port),
+        "sk-ant-fake",
+        CredentialSource::Environment,
+    )
+    .build()
+    .unwrap();
+    let mut monitor = harness.monitor();
+
+    let outcome = runtime::block_on(monitor.probe(ProbeMode::Green, creds, None)).unwrap();
+    assert_eq!(outcome.status, NetworkStatus::Error);
+    assert_eq!(
+        outcome.metrics.error_type.as_deref(),
+        Some("connect_refused")
+    );
+    assert_eq!(
+        harness.state(&monitor).network.error_type.as_deref(),
+        Some("connect_refused")
+    );
+}
+
+#[test]
+fn test_aux_checks_hit_models_endpoint() {
+    let harness = Harness::new();
+    let mut monitor = harness.monitor().with_aux_checks(true);
+
+    harness.probe(&mut monitor, ProbeMode::Green);
+    let models = harness.api.requests_to("/v1/models");
+    assert_eq!(models.len(), 1);
+    assert_eq!(models[0].method, "GET");
+
+    let state = harness.state(&monitor);
+    assert!(state.endpoints.get("models").is_some_and(|e| e.up));
+    assert!(state.health_score.is_some());
+}
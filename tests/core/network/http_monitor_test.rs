@@ -345,6 +345,9 @@ impl FakeCurlRunner {
             ttfb_ms,
             total_ttfb_ms: dns_ms + tcp_ms + tls_ms + ttfb_ms, // End-to-end TTFB
             total_ms,
+            set_cookies: Vec::new(),
+            headers: Default::default(),
+            connection: Default::default(),
         };
         self.add_response(Ok(phase_timings)).await;
     }
@@ -376,6 +379,9 @@ impl CurlProbeRunner for FakeCurlRunner {
                 ttfb_ms: 1500, // ttfb_ms becomes latency_ms in the outcome
                 total_ttfb_ms: 25 + 30 + 35 + 1500, // End-to-end TTFB
                 total_ms: 1590, // total should be sum of all phases (25+30+35+1500)
+                set_cookies: Vec::new(),
+                headers: Default::default(),
+                connection: Default::default(),
             })
         })
     }
@@ -930,8 +936,8 @@ async fn test_connection_error_handling() {
     );
     assert_eq!(
         result.metrics.error_type.as_deref(),
-        Some("connection_error"),
-        "Timeout should map to connection_error"
+        Some("timeout"),
+        "Timeout should map to the timeout error type"
     );
     assert!(
         matches!(result.status, NetworkStatus::Error),
@@ -939,6 +945,40 @@ async fn test_connection_error_handling() {
     );
 }
 
+#[test]
+fn test_transport_error_classification() {
+    use ccstatus::core::network::error_tracker::ErrorTracker;
+
+    let cases = [
+        // curl errors carry their libcurl code
+        ("Request perform failed: [6] Couldn't resolve host name (Could not resolve host: api.example.com)", "dns_error"),
+        ("[5] Couldn't resolve proxy name", "dns_error"),
+        ("[7] Couldn't connect to server (Failed to connect to 127.0.0.1 port 9: Connection refused)", "connect_refused"),
+        ("[28] Timeout was reached (Operation timed out after 2001 milliseconds)", "timeout"),
+        ("[35] SSL connect error", "tls_error"),
+        ("[60] SSL peer certificate or SSH remote key was not OK", "tls_error"),
+        // isahc error kinds
+        ("HTTP error: Request failed: failed to resolve host name", "dns_error"),
+        ("HTTP error: Request failed: failed to connect to the server", "connect_refused"),
+        ("HTTP error: Request failed: the server certificate could not be validated", "tls_error"),
+        ("HTTP error: Request failed: error ocurred in the secure socket engine", "tls_error"),
+        ("HTTP error: Request failed: request or operation took longer than the configured timeout time", "timeout"),
+        ("HTTP error: Request failed: failed to connect to the server: Connection timed out", "timeout"),
+        // Nothing says which phase failed
+        ("HTTP error: Request failed: the server made an unrecoverable HTTP protocol violation", "connection_error"),
+        ("[52] Server returned nothing (no headers, no data)", "connection_error"),
+        ("", "connection_error"),
+    ];
+    for (message, expected) in cases {
+        assert_eq!(
+            ErrorTracker::classify_transport_error(message),
+            expected,
+            "{}",
+            message
+        );
+    }
+}
+
 #[tokio::test]
 async fn test_write_unknown_preserves_rolling_stats() {
     let temp_dir = TempDir::new().unwrap();
@@ -983,6 +1023,139 @@ async fn test_write_unknown_preserves_rolling_stats() {
     );
 }
 
+#[tokio::test]
+async fn test_reset_state_backs_up_and_clears_statistics() {
+    let temp_dir = TempDir::new().unwrap();
+    let (mut monitor, http_client, _clock) = create_test_monitor(&temp_dir);
+    assert!(monitor
+        .reset_state(ResetScope::Statistics)
+        .await
+        .unwrap()
+        .is_none());
+
+    http_client.add_success(200, 1000).await;
+    http_client.add_success(429, 300).await;
+    monitor
+        .probe(ProbeMode::Green, test_credentials(), None)
+        .await
+        .unwrap();
+    monitor
+        .probe(ProbeMode::Red, test_credentials(), None)
+        .await
+        .unwrap();
+    monitor.set_green_window_id(42).await.unwrap();
+    let before = monitor.load_state().await.unwrap();
+    assert_eq!(before.network.rolling_totals.len(), 1);
+
+    let backup = monitor
+        .reset_state(ResetScope::Statistics)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(backup
+        .to_string_lossy()
+        .ends_with("monitoring.json.backup-20250125T103000"));
+    let backed_up: MonitoringSnapshot =
+        serde_json::from_str(&std::fs::read_to_string(&backup).unwrap()).unwrap();
+    assert_eq!(backed_up.monitoring_state.last_green_window_id, Some(42));
+
+    let after = monitor.load_state().await.unwrap();
+    assert!(matches!(after.status, NetworkStatus::Unknown));
+    assert!(after.network.rolling_totals.is_empty());
+    assert_eq!(after.network.p95_latency_ms, 0);
+    assert_eq!(after.monitoring_state.last_green_window_id, None);
+    assert_eq!(after.monitoring_state.consecutive_failures, 0);
+    assert!(after.last_probe_error.is_none());
+    // Probe counters, the endpoint and the 429 backoff survive
+    assert!(!before.monitoring_state.penalty.is_clear());
+    assert_eq!(
+        after.monitoring_state.penalty,
+        before.monitoring_state.penalty
+    );
+    assert_eq!(
+        after.monitoring_state.ledger,
+        before.monitoring_state.ledger
+    );
+    assert_eq!(
+        after.api_config.map(|config| config.endpoint),
+        before.api_config.map(|config| config.endpoint)
+    );
+
+    monitor.reset_state(ResetScope::All).await.unwrap().unwrap();
+    assert!(!temp_dir.path().join("monitoring.json").exists());
+    assert!(!monitor.probe_history().path().exists());
+    assert!(backup.exists());
+}
+
+// URL-routed responses exist for the isahc client only
+#[cfg(not(feature = "timings-curl"))]
+#[tokio::test]
+async fn test_compare_endpoints_probes_alternatives() {
+    let temp_dir = TempDir::new().unwrap();
+    let (monitor, http_client, _clock) = create_test_monitor(&temp_dir);
+    let mut monitor = monitor.with_compare_endpoints(vec![
+        "https://proxy.example.com/".to_string(),
+        "https://down.example.com".to_string(),
+    ]);
+    let route = |status, ms| {
+        Ok((
+            status,
+            Duration::from_millis(ms),
+            String::new(),
+            HashMap::new(),
+            None,
+        ))
+    };
+
+    http_client.add_success(200, 1000).await;
+    http_client
+        .http_client
+        .add_response_for_url(
+            MockHttpMethod::Post,
+            "https://proxy.example.com/v1/messages",
+            route(200, 400),
+        )
+        .await;
+    http_client
+        .http_client
+        .add_response_for_url(
+            MockHttpMethod::Post,
+            "https://down.example.com/v1/messages",
+            Err("Connection refused".to_string()),
+        )
+        .await;
+    monitor
+        .probe(ProbeMode::Green, test_credentials(), None)
+        .await
+        .unwrap();
+
+    let state = monitor.load_state().await.unwrap();
+    let comparison = state.comparison.as_ref().unwrap();
+    assert_eq!(comparison.active, "https://api.anthropic.com");
+    assert_eq!(comparison.endpoints.len(), 3);
+    assert_eq!(
+        comparison.endpoints["https://down.example.com"].http_status,
+        0
+    );
+    let (url, best) = comparison.better_alternative().unwrap();
+    assert_eq!(url, "https://proxy.example.com");
+    assert_eq!(best.median_ms(), 400);
+    // The active probe still drives the status
+    assert_eq!(state.network.rolling_totals.len(), 1);
+
+    let rendered = StatusRenderer::new().render_with_alternative("🟢".to_string(), &state);
+    assert_eq!(rendered, "🟢 | alt proxy.example.com 400ms");
+
+    // RED probes leave the comparison alone
+    http_client.add_success(500, 200).await;
+    monitor
+        .probe(ProbeMode::Red, test_credentials(), None)
+        .await
+        .unwrap();
+    let state = monitor.load_state().await.unwrap();
+    assert_eq!(state.comparison.as_ref(), Some(comparison));
+}
+
 #[tokio::test]
 async fn test_load_state_nonexistent_file() {
     let temp_dir = TempDir::new().unwrap();
@@ -2243,10 +2416,7 @@ mod curl_timing_tests {
 
         // Curl errors should be handled gracefully
         assert_eq!(result.metrics.last_http_status, 0);
-        assert_eq!(
-            result.metrics.error_type.as_deref(),
-            Some("connection_error")
-        );
+        assert_eq!(result.metrics.error_type.as_deref(), Some("timeout"));
         assert!(matches!(result.status, NetworkStatus::Error));
 
         // Breakdown should indicate connection failure
@@ -2470,12 +2640,9 @@ mod curl_timing_tests {
 
         let result = monitor.probe(ProbeMode::Red, creds, None).await.unwrap();
 
-        // Verify timeout is handled as connection error
+        // Verify timeout is classified as such
         assert_eq!(result.metrics.last_http_status, 0);
-        assert_eq!(
-            result.metrics.error_type.as_deref(),
-            Some("connection_error")
-        );
+        assert_eq!(result.metrics.error_type.as_deref(), Some("timeout"));
         assert!(matches!(result.status, NetworkStatus::Error));
 
         // Verify breakdown shows zero timings for failed connection
@@ -2713,12 +2880,14 @@ mod curl_timing_tests {
             last_http_status: 401, // Expected for OAuth dummy key
             error_type: Some("authentication_error".to_string()),
             http_version: Some("HTTP/2.0".to_string()),
+            error_headers: Default::default(),
+            connection: None,
         };
 
         // This should not panic even though we provided a panic health client,
         // because OAuth mode should skip the proxy health check
         let result = monitor
-            .process_probe_results(ProbeMode::Green, oauth_creds, metrics, None)
+            .process_probe_results(ProbeMode::Green, oauth_creds, metrics, None, None)
             .await;
 
         assert!(
@@ -2760,10 +2929,12 @@ mod curl_timing_tests {
             last_http_status: 200,
             error_type: None,
             http_version: Some("HTTP/2.0".to_string()),
+            error_headers: Default::default(),
+            connection: None,
         };
 
         let result = monitor
-            .process_probe_results(ProbeMode::Green, env_creds, metrics, None)
+            .process_probe_results(ProbeMode::Green, env_creds, metrics, None, None)
             .await;
 
         assert!(result.is_ok(), "Non-OAuth probe should succeed");
@@ -2791,3 +2962,1093 @@ impl HealthCheckClient for PanicHealthCheckClient {
         panic!("PanicHealthCheckClient was called - OAuth should skip proxy health check");
     }
 }
+
+#[tokio::test]
+async fn test_probe_blocked_by_host_policy() {
+    let temp_dir = TempDir::new().unwrap();
+    let (monitor, http_client, clock) = create_test_monitor(&temp_dir);
+    let mut monitor = monitor.with_host_policy(ccstatus::config::HostPolicy::new(vec![
+        "*.corp.example".to_string(),
+    ]));
+
+    http_client.add_success(200, 1000).await;
+    clock.add_timestamp("2025-01-25T10:30:00-08:00").await;
+
+    let outcome = monitor
+        .probe(ProbeMode::Green, test_credentials(), None)
+        .await
+        .expect("blocked probe still records state");
+
+    assert!(matches!(outcome.status, NetworkStatus::BlockedByPolicy));
+    assert!(outcome.state_written);
+    assert_eq!(outcome.rolling_len, 0, "Blocked probe must not add samples");
+    assert_eq!(
+        outcome.metrics.error_type.as_deref(),
+        Some("blocked_by_policy")
+    );
+
+    let state = monitor.load_state().await.unwrap();
+    assert!(matches!(state.status, NetworkStatus::BlockedByPolicy));
+    assert_eq!(state.network.last_http_status, 0);
+    assert_eq!(
+        state.api_config.unwrap().endpoint,
+        "https://api.anthropic.com/v1/messages"
+    );
+}
+
+#[tokio::test]
+async fn test_probe_allowed_by_host_policy() {
+    let temp_dir = TempDir::new().unwrap();
+    let (monitor, http_client, clock) = create_test_monitor(&temp_dir);
+    let mut monitor = monitor.with_host_policy(ccstatus::config::HostPolicy::new(vec![
+        "*.anthropic.com".to_string(),
+    ]));
+
+    http_client.add_success(200, 1000).await;
+    clock.add_timestamp("2025-01-25T10:30:00-08:00").await;
+
+    let outcome = monitor
+        .probe(ProbeMode::Green, test_credentials(), None)
+        .await
+        .unwrap();
+
+    assert!(matches!(outcome.status, NetworkStatus::Healthy));
+    assert_eq!(outcome.metrics.last_http_status, 200);
+}
+
+#[test]
+fn test_penalty_backoff_is_exponential_and_capped() {
+    assert_eq!(PenaltyState::backoff_secs(0), 0);
+    assert_eq!(PenaltyState::backoff_secs(1), PENALTY_BASE_SECS);
+    assert_eq!(PenaltyState::backoff_secs(2), PENALTY_BASE_SECS * 2);
+    assert_eq!(PenaltyState::backoff_secs(3), PENALTY_BASE_SECS * 4);
+    assert_eq!(PenaltyState::backoff_secs(10), PENALTY_MAX_SECS);
+    assert_eq!(PenaltyState::backoff_secs(u32::MAX), PENALTY_MAX_SECS);
+}
+
+#[test]
+fn test_penalty_remaining_secs() {
+    let mut penalty = PenaltyState::default();
+    assert!(penalty.is_clear());
+    assert_eq!(penalty.remaining_secs("2025-01-25T10:30:00-08:00"), None);
+
+    penalty.escalate("rate_limit", "2025-01-25T10:30:00-08:00");
+    assert_eq!(penalty.until.as_deref(), Some("2025-01-25T10:31:00-08:00"));
+    assert_eq!(
+        penalty.remaining_secs("2025-01-25T10:30:15-08:00"),
+        Some(45)
+    );
+    assert_eq!(penalty.remaining_secs("2025-01-25T10:31:00-08:00"), None);
+
+    penalty.clear();
+    assert!(penalty.is_clear());
+}
+
+#[tokio::test]
+async fn test_rate_limit_escalates_penalty_and_success_clears_it() {
+    let temp_dir = TempDir::new().unwrap();
+    let (mut monitor, http_client, clock) = create_test_monitor(&temp_dir);
+
+    for expected_level in 1..=2 {
+        http_client.add_success(429, 1000).await;
+        clock.add_timestamp("2025-01-25T10:30:00-08:00").await;
+        monitor
+            .probe(ProbeMode::Green, test_credentials(), None)
+            .await
+            .unwrap();
+
+        let state = monitor.load_state().await.unwrap();
+        let penalty = &state.monitoring_state.penalty;
+        assert_eq!(penalty.level, expected_level);
+        assert_eq!(penalty.reason.as_deref(), Some("rate_limit"));
+        assert_eq!(
+            monitor.penalty_remaining_secs(&state),
+            Some(PenaltyState::backoff_secs(expected_level))
+        );
+    }
+
+    http_client.add_success(200, 1000).await;
+    clock.add_timestamp("2025-01-25T10:30:00-08:00").await;
+    monitor
+        .probe(ProbeMode::Green, test_credentials(), None)
+        .await
+        .unwrap();
+
+    let state = monitor.load_state().await.unwrap();
+    assert!(state.monitoring_state.penalty.is_clear());
+    assert_eq!(monitor.penalty_remaining_secs(&state), None);
+}
+
+#[cfg(not(feature = "timings-curl"))]
+#[tokio::test]
+async fn test_rate_limit_headers_persist_and_hold_probes_until_retry_after() {
+    let temp_dir = TempDir::new().unwrap();
+    let (mut monitor, http_client, _clock) = create_test_monitor(&temp_dir);
+
+    let headers: HashMap<String, String> = [
+        ("retry-after", "300"),
+        ("anthropic-ratelimit-requests-limit", "50"),
+        ("anthropic-ratelimit-requests-remaining", "0"),
+        ("anthropic-ratelimit-requests-reset", "2025-01-25T18:31:00Z"),
+        ("anthropic-ratelimit-tokens-remaining", "39000"),
+    ]
+    .into_iter()
+    .map(|(name, value)| (name.to_string(), value.to_string()))
+    .collect();
+    http_client
+        .http_client
+        .add_response(Ok((
+            429,
+            Duration::from_millis(300),
+            "Total:300ms".to_string(),
+            headers,
+            Some("HTTP/2.0".to_string()),
+        )))
+        .await;
+    monitor
+        .probe(ProbeMode::Green, test_credentials(), None)
+        .await
+        .unwrap();
+
+    let state = monitor.load_state().await.unwrap();
+    let rate_limit = state.network.rate_limit.as_ref().unwrap();
+    assert_eq!(rate_limit.retry_after_secs, Some(300));
+    assert_eq!(rate_limit.quotas["requests"].remaining, Some(0));
+    assert_eq!(rate_limit.quotas["requests"].limit, Some(50));
+    assert_eq!(rate_limit.quotas["tokens"].remaining, Some(39000));
+    // retry-after outlasts the first backoff step, so probes wait for it
+    assert_eq!(state.monitoring_state.penalty.level, 1);
+    assert_eq!(monitor.penalty_remaining_secs(&state), Some(300));
+
+    // A response without rate limit headers clears the stale quota
+    http_client.add_success(200, 1000).await;
+    monitor
+        .probe(ProbeMode::Green, test_credentials(), None)
+        .await
+        .unwrap();
+    let state = monitor.load_state().await.unwrap();
+    assert_eq!(state.network.rate_limit, None);
+}
+
+#[cfg(not(feature = "timings-curl"))]
+#[tokio::test]
+async fn test_reset_statistics_keeps_rate_limit_backoff() {
+    let temp_dir = TempDir::new().unwrap();
+    let (mut monitor, http_client, _clock) = create_test_monitor(&temp_dir);
+
+    let headers: HashMap<String, String> = [
+        ("retry-after", "300"),
+        ("anthropic-ratelimit-requests-remaining", "0"),
+        ("anthropic-ratelimit-requests-reset", "2025-01-25T18:31:00Z"),
+    ]
+    .into_iter()
+    .map(|(name, value)| (name.to_string(), value.to_string()))
+    .collect();
+    http_client
+        .http_client
+        .add_response(Ok((
+            429,
+            Duration::from_millis(300),
+            "Total:300ms".to_string(),
+            headers,
+            Some("HTTP/2.0".to_string()),
+        )))
+        .await;
+    monitor
+        .probe(ProbeMode::Green, test_credentials(), None)
+        .await
+        .unwrap();
+    let before = monitor.load_state().await.unwrap();
+
+    monitor
+        .reset_state(ResetScope::Statistics)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // `net reset` right after a 429 must not resume probing early
+    let after = monitor.load_state().await.unwrap();
+    assert!(after.network.rolling_totals.is_empty());
+    assert_eq!(after.network.rate_limit, before.network.rate_limit);
+    assert_eq!(
+        after.monitoring_state.penalty,
+        before.monitoring_state.penalty
+    );
+    assert_eq!(monitor.penalty_remaining_secs(&after), Some(300));
+}
+
+#[tokio::test]
+async fn test_bot_challenge_escalates_penalty() {
+    let temp_dir = TempDir::new().unwrap();
+    let (mut monitor, http_client, clock) = create_test_monitor(&temp_dir);
+
+    http_client.add_success(403, 1000).await;
+    clock.add_timestamp("2025-01-25T10:30:00-08:00").await;
+    monitor
+        .probe(ProbeMode::Green, test_credentials(), None)
+        .await
+        .unwrap();
+
+    let state = monitor.load_state().await.unwrap();
+    assert_eq!(state.monitoring_state.penalty.level, 1);
+    assert_eq!(
+        state.monitoring_state.penalty.reason.as_deref(),
+        Some("bot_challenge")
+    );
+
+    // Non-penalty errors neither escalate nor clear the penalty
+    http_client.add_success(500, 1000).await;
+    monitor
+        .probe(ProbeMode::Green, test_credentials(), None)
+        .await
+        .unwrap();
+    let state = monitor.load_state().await.unwrap();
+    assert_eq!(state.monitoring_state.penalty.level, 1);
+}
+
+#[test]
+fn test_snapshot_error_headers_is_sanitized() {
+    let mut headers = HashMap::new();
+    headers.insert("CF-Ray".to_string(), "8a1b2c3d4e5f-LAX".to_string());
+    headers.insert("retry-after".to_string(), " 30\r\n".to_string());
+    headers.insert("server".to_string(), "cloudflare".to_string());
+    headers.insert("via".to_string(), "x".repeat(500));
+    headers.insert("set-cookie".to_string(), "cf_clearance=secret".to_string());
+    headers.insert("x-api-key".to_string(), "sk-ant-secret".to_string());
+
+    let snapshot = snapshot_error_headers(&headers);
+
+    assert_eq!(
+        snapshot.keys().map(String::as_str).collect::<Vec<_>>(),
+        vec!["cf-ray", "retry-after", "server", "via"]
+    );
+    assert_eq!(snapshot["cf-ray"], "8a1b2c3d4e5f-LAX");
+    assert_eq!(snapshot["retry-after"], "30");
+    assert_eq!(snapshot["via"].len(), 200);
+}
+
+#[cfg(not(feature = "timings-curl"))]
+#[tokio::test]
+async fn test_probe_error_records_header_snapshot() {
+    let temp_dir = TempDir::new().unwrap();
+    let (mut monitor, http_client, clock) = create_test_monitor(&temp_dir);
+
+    let mut headers = HashMap::new();
+    headers.insert("cf-ray".to_string(), "8a1b2c3d4e5f-LAX".to_string());
+    headers.insert("retry-after".to_string(), "30".to_string());
+    headers.insert("content-type".to_string(), "application/json".to_string());
+    http_client
+        .http_client
+        .add_response(Ok((
+            429,
+            Duration::from_millis(300),
+            "Total:300ms".to_string(),
+            headers,
+            Some("HTTP/2.0".to_string()),
+        )))
+        .await;
+    clock.add_timestamp("2025-01-25T10:30:00-08:00").await;
+
+    let outcome = monitor
+        .probe(ProbeMode::Green, test_credentials(), None)
+        .await
+        .unwrap();
+    assert_eq!(outcome.metrics.error_headers.len(), 2);
+
+    let state = monitor.load_state().await.unwrap();
+    let error = state.last_probe_error.expect("error details persisted");
+    assert_eq!(error.http_status, 429);
+    // cf-ray on a 429 marks it as a Cloudflare challenge
+    assert_eq!(error.error_type, "bot_challenge");
+    assert_eq!(error.headers["cf-ray"], "8a1b2c3d4e5f-LAX");
+    assert!(!error.headers.contains_key("content-type"));
+
+    // A later success keeps the last error details for `ccstatus net status`
+    http_client.add_success(200, 1000).await;
+    let outcome = monitor
+        .probe(ProbeMode::Green, test_credentials(), None)
+        .await
+        .unwrap();
+    assert!(outcome.metrics.error_headers.is_empty());
+    let state = monitor.load_state().await.unwrap();
+    assert_eq!(state.last_probe_error.unwrap().http_status, 429);
+}
+
+#[tokio::test]
+async fn test_aux_checks_record_composite_health() {
+    let temp_dir = TempDir::new().unwrap();
+    let (monitor, client, clock) = create_test_monitor(&temp_dir);
+    let mut monitor = monitor.with_aux_checks(true);
+
+    // Messages down, models still answering (unauthenticated 401 = up)
+    client.add_success(503, 1000).await;
+    client
+        .health_client
+        .add_health_response(401, 80, "{}")
+        .await;
+    clock.add_timestamp("2025-01-25T10:30:00-08:00").await;
+
+    monitor
+        .probe(ProbeMode::Green, test_credentials(), None)
+        .await
+        .unwrap();
+
+    let state = monitor.load_state().await.unwrap();
+    assert_eq!(state.health_score, Some(50));
+    assert!(!state.endpoints["messages"].up);
+    assert_eq!(state.endpoints["messages"].http_status, 503);
+    assert!(state.endpoints["models"].up);
+    assert_eq!(state.endpoints["models"].http_status, 401);
+
+    let renderer = StatusRenderer::new();
+    assert!(renderer
+        .render_with_endpoints("🔴".to_string(), &state)
+        .ends_with(" | messages ✗ models ✓"));
+}
+
+#[tokio::test]
+async fn test_aux_checks_disabled_by_default() {
+    let temp_dir = TempDir::new().unwrap();
+    let (mut monitor, client, clock) = create_test_monitor(&temp_dir);
+
+    client.add_success(200, 1000).await;
+    clock.add_timestamp("2025-01-25T10:30:00-08:00").await;
+    monitor
+        .probe(ProbeMode::Green, test_credentials(), None)
+        .await
+        .unwrap();
+
+    let state = monitor.load_state().await.unwrap();
+    assert!(state.endpoints.is_empty());
+    assert_eq!(state.health_score, None);
+}
+
+/// Health client answering over IPv4 and refusing over IPv6
+struct DualStackHealthClient {
+    families: Arc<Mutex<Vec<ccstatus::core::network::ip_family::IpFamily>>>,
+}
+
+#[async_trait::async_trait]
+impl HealthCheckClient for DualStackHealthClient {
+    async fn get_health(&self, _url: String, _timeout_ms: u32) -> Result<HealthResponse, String> {
+        Err("family not chosen".to_string())
+    }
+
+    async fn get_health_over(
+        &self,
+        _url: String,
+        family: ccstatus::core::network::ip_family::IpFamily,
+        _timeout_ms: u32,
+    ) -> Result<HealthResponse, String> {
+        use ccstatus::core::network::ip_family::IpFamily;
+
+        self.families.lock().await.push(family);
+        match family {
+            IpFamily::V4 => Ok(HealthResponse {
+                status_code: 401,
+                body: Vec::new(),
+                duration: Duration::from_millis(90),
+                headers: HashMap::new(),
+            }),
+            IpFamily::V6 => Err("Connection refused".to_string()),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_ip_family_compare_flags_failing_family() {
+    use ccstatus::config::IpFamilyMode;
+    use ccstatus::core::network::ip_family::IpFamily;
+
+    let temp_dir = TempDir::new().unwrap();
+    let (monitor, client, clock) = create_test_monitor(&temp_dir);
+    let families = Arc::new(Mutex::new(Vec::new()));
+    let mut monitor = monitor
+        .with_health_client(Box::new(DualStackHealthClient {
+            families: families.clone(),
+        }))
+        .with_ip_family(IpFamilyMode::Compare);
+
+    for _ in 0..3 {
+        client.add_success(200, 1000).await;
+        clock.add_timestamp("2025-01-25T10:30:00-08:00").await;
+        monitor
+            .probe(ProbeMode::Green, test_credentials(), None)
+            .await
+            .unwrap();
+    }
+
+    assert_eq!(
+        *families.lock().await,
+        [IpFamily::V4, IpFamily::V6].repeat(3)
+    );
+    let state = monitor.load_state().await.unwrap();
+    let comparison = state.ip_families.clone().expect("families compared");
+    assert_eq!(comparison.v4.samples_ms, vec![90, 90, 90]);
+    assert_eq!(comparison.v6.consecutive_failures, 3);
+    assert_eq!(
+        comparison.verdict().as_deref(),
+        Some("IPv6 failing (3 checks in a row: Connection refused) while IPv4 connects")
+    );
+
+    let report = StatusRenderer::new().render_report(&state);
+    assert!(report.contains("  IPv4: median 90ms over 3 checks"));
+    assert!(report.contains("  IPv6: no successful checks, last 3 failed (Connection refused)"));
+    assert!(report.contains("  Warning: IPv6 failing"));
+
+    // Leaving compare mode drops the comparison
+    let mut monitor = monitor.with_ip_family(IpFamilyMode::Auto);
+    client.add_success(200, 1000).await;
+    clock.add_timestamp("2025-01-25T10:31:00-08:00").await;
+    monitor
+        .probe(ProbeMode::Green, test_credentials(), None)
+        .await
+        .unwrap();
+    assert!(monitor.load_state().await.unwrap().ip_families.is_none());
+    assert_eq!(families.lock().await.len(), 6);
+}
+
+#[tokio::test]
+async fn test_probe_profile_payload_strategy() {
+    use ccstatus::core::network::probe_profile::ProbeProfile;
+
+    // `aggressive` adds the models check even with aux checks off
+    let temp_dir = TempDir::new().unwrap();
+    let (mut monitor, client, clock) = create_test_monitor(&temp_dir);
+    monitor.set_probe_profile(ProbeProfile::Aggressive);
+    client.add_success(200, 1000).await;
+    client
+        .health_client
+        .add_health_response(401, 80, "{}")
+        .await;
+    clock.add_timestamp("2025-01-25T10:30:00-08:00").await;
+    monitor
+        .probe(ProbeMode::Green, test_credentials(), None)
+        .await
+        .unwrap();
+    assert!(monitor
+        .load_state()
+        .await
+        .unwrap()
+        .endpoints
+        .contains_key("models"));
+
+    // `minimal` sends the messages probe alone
+    let temp_dir = TempDir::new().unwrap();
+    let (monitor, client, clock) = create_test_monitor(&temp_dir);
+    let mut monitor = monitor.with_aux_checks(true);
+    monitor.set_probe_profile(ProbeProfile::Minimal);
+    client.add_success(200, 1000).await;
+    clock.add_timestamp("2025-01-25T10:30:00-08:00").await;
+    monitor
+        .probe(ProbeMode::Green, test_credentials(), None)
+        .await
+        .unwrap();
+    let state = monitor.load_state().await.unwrap();
+    assert!(state.endpoints.is_empty());
+    assert_eq!(state.network.proxy_health_level, None);
+}
+
+#[tokio::test]
+async fn test_pin_probe_profile_persists_for_session() {
+    use ccstatus::core::network::probe_profile::ProbeProfile;
+
+    let temp_dir = TempDir::new().unwrap();
+    let (monitor, _client, _clock) = create_test_monitor(&temp_dir);
+    monitor
+        .pin_probe_profile("session-a", ProbeProfile::Conservative)
+        .await
+        .unwrap();
+
+    let state = monitor.load_state().await.unwrap();
+    let pinned = state.monitoring_state.probe_profile.as_ref().unwrap();
+    assert_eq!(pinned.profile, ProbeProfile::Conservative);
+    assert_eq!(pinned.session_id, "session-a");
+    assert!(StatusRenderer::new().render_report(&state).contains(
+        "Probe profile: conservative (session session-a since 2025-01-25T10:30:00-08:00)"
+    ));
+}
+
+#[tokio::test]
+async fn test_corrupt_state_file_is_quarantined() {
+    use ccstatus::core::recovery::RecoveryLog;
+
+    let temp_dir = TempDir::new().unwrap();
+    let (monitor, _client, _clock) = create_test_monitor(&temp_dir);
+    let state_path = temp_dir.path().join("monitoring.json");
+    std::fs::write(&state_path, "{\"status\": \"Healthy\", \"network\": {").unwrap();
+
+    let state = monitor.load_state().await.unwrap();
+    assert_eq!(state.status, NetworkStatus::Unknown);
+    assert!(!state_path.exists());
+    assert_eq!(
+        RecoveryLog::load(&RecoveryLog::path_for(&state_path)).recovery_count,
+        1
+    );
+}
+
+#[test]
+fn test_endpoint_health_classification() {
+    assert!(EndpointHealth::is_up_status(200));
+    assert!(EndpointHealth::is_up_status(401));
+    assert!(EndpointHealth::is_up_status(429));
+    assert!(!EndpointHealth::is_up_status(0));
+    assert!(!EndpointHealth::is_up_status(403));
+    assert!(!EndpointHealth::is_up_status(503));
+
+    let mut endpoints = std::collections::BTreeMap::new();
+    assert_eq!(composite_health_score(&endpoints), None);
+    endpoints.insert("messages".to_string(), EndpointHealth::new(200, 10, ""));
+    endpoints.insert("models".to_string(), EndpointHealth::new(200, 10, ""));
+    assert_eq!(composite_health_score(&endpoints), Some(100));
+}
+
+#[tokio::test]
+async fn test_red_streak_tracks_transcript_error() {
+    let temp_dir = TempDir::new().unwrap();
+    let (mut monitor, http_client, clock) = create_test_monitor(&temp_dir);
+    let error_at = |timestamp: &str| JsonlError {
+        timestamp: timestamp.to_string(),
+        code: 529,
+        message: "Overloaded".to_string(),
+    };
+
+    http_client.add_success(200, 500).await;
+    clock.add_timestamp("2025-01-25T10:30:00-08:00").await;
+    monitor
+        .probe(
+            ProbeMode::Red,
+            test_credentials(),
+            Some(error_at("2025-01-25T18:00:00Z")),
+        )
+        .await
+        .unwrap();
+
+    let state = monitor.load_state().await.unwrap();
+    assert_eq!(
+        state.monitoring_state.red_error_at.as_deref(),
+        Some("2025-01-25T18:00:00Z")
+    );
+    assert_eq!(
+        state.monitoring_state.red_since.as_deref(),
+        Some("2025-01-25T10:30:00-08:00")
+    );
+
+    monitor.set_red_watchdog_triggered().await.unwrap();
+    assert!(
+        monitor
+            .load_state()
+            .await
+            .unwrap()
+            .monitoring_state
+            .red_watchdog_triggered
+    );
+
+    // A new transcript error starts a fresh streak and resets the watchdog
+    http_client.add_success(200, 500).await;
+    monitor
+        .probe(
+            ProbeMode::Red,
+            test_credentials(),
+            Some(error_at("2025-01-25T18:20:00Z")),
+        )
+        .await
+        .unwrap();
+
+    let state = monitor.load_state().await.unwrap();
+    assert_eq!(
+        state.monitoring_state.red_error_at.as_deref(),
+        Some("2025-01-25T18:20:00Z")
+    );
+    assert!(!state.monitoring_state.red_watchdog_triggered);
+}
+
+#[test]
+fn test_red_watchdog_expired_after_ceiling() {
+    let temp_dir = TempDir::new().unwrap();
+    let (monitor, _http_client, _clock) = create_test_monitor(&temp_dir);
+
+    // TestClock reports 2025-01-25T10:30:00-08:00
+    let mut state = MonitoringSnapshot::default();
+    state.monitoring_state.red_error_at = Some("2025-01-25T17:55:00Z".to_string());
+    state.monitoring_state.red_since = Some("2025-01-25T10:00:00-08:00".to_string());
+
+    assert!(monitor.red_watchdog_expired(&state, "2025-01-25T17:55:00Z", 1800));
+    assert!(!monitor.red_watchdog_expired(&state, "2025-01-25T17:55:00Z", 1801));
+    // A different transcript error is a new streak, never expired
+    assert!(!monitor.red_watchdog_expired(&state, "2025-01-25T18:25:00Z", 1800));
+
+    state.monitoring_state.red_since = None;
+    assert!(!monitor.red_watchdog_expired(&state, "2025-01-25T17:55:00Z", 0));
+}
+
+#[tokio::test]
+async fn test_probe_ledger_counts_probes_and_errors() {
+    let temp_dir = TempDir::new().unwrap();
+    let (mut monitor, http_client, clock) = create_test_monitor(&temp_dir);
+
+    for status in [200, 500, 200] {
+        http_client.add_success(status, 1000).await;
+        clock.add_timestamp("2025-01-25T10:30:00-08:00").await;
+        monitor
+            .probe(ProbeMode::Green, test_credentials(), None)
+            .await
+            .unwrap();
+    }
+
+    let state = monitor.load_state().await.unwrap();
+    assert_eq!(state.monitoring_state.ledger.probes, 3);
+    assert_eq!(state.monitoring_state.ledger.errors, 1);
+}
+
+#[tokio::test]
+async fn test_shared_baseline_widens_green_thresholds() {
+    let temp_dir = TempDir::new().unwrap();
+    let (mut monitor, http_client, clock) = create_test_monitor(&temp_dir);
+
+    monitor
+        .set_shared_baseline(Some(SharedBaseline {
+            totals: vec![2000, 2100, 2200, 2300],
+            machines: 1,
+            synced_at: "2025-01-25T10:00:00-08:00".to_string(),
+        }))
+        .await
+        .unwrap();
+
+    // Thresholds use local + shared samples; rolling_totals keeps local samples only
+    http_client.add_success(200, 1500).await;
+    clock.add_timestamp("2025-01-25T10:30:00-08:00").await;
+    monitor
+        .probe(ProbeMode::Green, test_credentials(), None)
+        .await
+        .unwrap();
+
+    let state = monitor.load_state().await.unwrap();
+    assert_eq!(state.network.rolling_totals.len(), 1);
+    assert_eq!(state.status, NetworkStatus::Healthy);
+    assert_eq!(state.network.p95_latency_ms, 2300);
+}
+
+#[tokio::test]
+async fn test_probe_appends_version_tagged_history() {
+    let temp_dir = TempDir::new().unwrap();
+    let (mut monitor, http_client, clock) = create_test_monitor(&temp_dir);
+
+    http_client.add_success(200, 1500).await;
+    clock.add_timestamp("2025-01-25T10:30:00-08:00").await;
+    monitor
+        .probe(ProbeMode::Green, test_credentials(), None)
+        .await
+        .unwrap();
+
+    let history = monitor.probe_history();
+    assert_eq!(
+        history.path(),
+        temp_dir.path().join("monitoring.history.jsonl")
+    );
+    let records = history.load();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(records[0].mode, "green");
+    assert_eq!(records[0].http_status, 200);
+    assert_eq!(records[0].status, NetworkStatus::Healthy);
+}
+
+#[tokio::test]
+async fn test_status_expression_overrides_classification() {
+    use ccstatus::core::network::status_expression::StatusExpression;
+
+    let temp_dir = TempDir::new().unwrap();
+    let (monitor, http_client, clock) = create_test_monitor(&temp_dir);
+    let expression =
+        StatusExpression::parse("error if consecutive_failures > 1 else healthy").unwrap();
+    let mut monitor = monitor.with_status_expression(Some(expression));
+
+    // A single 429 would be degraded by default; the expression says healthy
+    http_client.add_success(429, 1000).await;
+    clock.add_timestamp("2025-01-25T10:30:00-08:00").await;
+    let outcome = monitor
+        .probe(ProbeMode::Green, test_credentials(), None)
+        .await
+        .unwrap();
+    assert_eq!(outcome.status, NetworkStatus::Healthy);
+
+    http_client.add_success(429, 1000).await;
+    clock.add_timestamp("2025-01-25T10:35:00-08:00").await;
+    monitor
+        .probe(ProbeMode::Green, test_credentials(), None)
+        .await
+        .unwrap();
+    let state = monitor.load_state().await.unwrap();
+    assert_eq!(state.monitoring_state.consecutive_failures, 2);
+    assert_eq!(state.status, NetworkStatus::Error);
+    assert_eq!(state.monitoring_state.state, NetworkStatus::Error);
+}
+
+#[tokio::test]
+async fn test_hysteresis_holds_status_through_single_outliers() {
+    use ccstatus::config::HysteresisConfig;
+
+    let temp_dir = TempDir::new().unwrap();
+    let (monitor, http_client, clock) = create_test_monitor(&temp_dir);
+    let mut monitor = monitor.with_hysteresis(HysteresisConfig {
+        degrade_after: 2,
+        recover_after: 2,
+    });
+    let mut minute = 30;
+    let mut probe = |status: u16| {
+        minute += 5;
+        let (http_client, clock) = (http_client.clone(), clock.clone());
+        let at = format!("2025-01-25T10:{}:00-08:00", minute);
+        async move {
+            http_client.add_success(status, 1000).await;
+            clock.add_timestamp(&at).await;
+        }
+    };
+
+    // The first classification is shown at once
+    probe(200).await;
+    let outcome = monitor
+        .probe(ProbeMode::Green, test_credentials(), None)
+        .await
+        .unwrap();
+    assert_eq!(outcome.status, NetworkStatus::Healthy);
+
+    // One 529 is held back, the second flips the status
+    probe(529).await;
+    let outcome = monitor
+        .probe(ProbeMode::Green, test_credentials(), None)
+        .await
+        .unwrap();
+    assert_eq!(outcome.status, NetworkStatus::Healthy);
+    let state = monitor.load_state().await.unwrap();
+    assert_eq!(
+        state.monitoring_state.pending_status,
+        Some(NetworkStatus::Error)
+    );
+    assert_eq!(state.monitoring_state.pending_count, 1);
+
+    probe(529).await;
+    let outcome = monitor
+        .probe(ProbeMode::Green, test_credentials(), None)
+        .await
+        .unwrap();
+    assert_eq!(outcome.status, NetworkStatus::Error);
+
+    // Recovery needs two healthy probes as well
+    probe(200).await;
+    let outcome = monitor
+        .probe(ProbeMode::Green, test_credentials(), None)
+        .await
+        .unwrap();
+    assert_eq!(outcome.status, NetworkStatus::Error);
+    probe(200).await;
+    monitor
+        .probe(ProbeMode::Green, test_credentials(), None)
+        .await
+        .unwrap();
+    let state = monitor.load_state().await.unwrap();
+    assert_eq!(state.status, NetworkStatus::Healthy);
+    assert_eq!(state.monitoring_state.state, NetworkStatus::Healthy);
+    assert_eq!(state.monitoring_state.pending_status, None);
+}
+
+#[tokio::test]
+async fn test_probes_record_status_spans() {
+    let temp_dir = TempDir::new().unwrap();
+    let (mut monitor, http_client, _clock) = create_test_monitor(&temp_dir);
+
+    for status in [200, 200, 529, 200] {
+        http_client.add_success(status, 1000).await;
+        monitor
+            .probe(ProbeMode::Green, test_credentials(), None)
+            .await
+            .unwrap();
+    }
+
+    // One span per status change, stamped with the clock's time
+    let state = monitor.load_state().await.unwrap();
+    let statuses: Vec<NetworkStatus> = state
+        .uptime
+        .spans
+        .iter()
+        .map(|span| span.status.clone())
+        .collect();
+    assert_eq!(
+        statuses,
+        vec![
+            NetworkStatus::Healthy,
+            NetworkStatus::Error,
+            NetworkStatus::Healthy
+        ]
+    );
+    let now = chrono::DateTime::parse_from_rfc3339("2025-01-25T10:30:00-08:00")
+        .unwrap()
+        .timestamp();
+    assert_eq!(state.uptime.last_seen, now);
+    // No time has passed on the test clock, so nothing was observed yet
+    assert_eq!(state.uptime.availability_24h, None);
+}
+
+#[tokio::test]
+async fn test_probes_evaluate_error_budget_over_history() {
+    use ccstatus::core::network::error_budget::ErrorBudgetPolicy;
+
+    let temp_dir = TempDir::new().unwrap();
+    let (monitor, http_client, _clock) = create_test_monitor(&temp_dir);
+    let mut monitor = monitor.with_error_budget(Some(ErrorBudgetPolicy::default()));
+
+    http_client.add_success(200, 1000).await;
+    monitor
+        .probe(ProbeMode::Green, test_credentials(), None)
+        .await
+        .unwrap();
+    let budget = monitor.load_state().await.unwrap().error_budget.unwrap();
+    assert_eq!((budget.probes, budget.failed), (1, 0));
+    assert!(!budget.burning);
+
+    for status in [529, 529] {
+        http_client.add_success(status, 1000).await;
+        monitor
+            .probe(ProbeMode::Green, test_credentials(), None)
+            .await
+            .unwrap();
+    }
+    // The history holds the earlier probes, the state the one just made
+    let budget = monitor.load_state().await.unwrap().error_budget.unwrap();
+    assert_eq!((budget.probes, budget.failed), (3, 2));
+    assert_eq!((budget.recent_probes, budget.recent_failed), (3, 2));
+    assert!(budget.burning);
+
+    // Without a policy the budget is dropped from the state
+    let mut monitor = monitor.with_error_budget(None);
+    http_client.add_success(200, 1000).await;
+    monitor
+        .probe(ProbeMode::Green, test_credentials(), None)
+        .await
+        .unwrap();
+    assert_eq!(monitor.load_state().await.unwrap().error_budget, None);
+}
+
+/// HTTP client that answers 200 after a real delay
+struct DelayedHttpClient(Duration);
+
+#[async_trait::async_trait]
+impl HttpClientTrait for DelayedHttpClient {
+    async fn execute_request(
+        &self,
+        _url: String,
+        _headers: HashMap<String, String>,
+        _body: Vec<u8>,
+        _timeout_ms: u32,
+    ) -> Result<
+        (
+            u16,
+            Duration,
+            String,
+            HashMap<String, String>,
+            Option<String>,
+        ),
+        String,
+    > {
+        tokio::time::sleep(self.0).await;
+        Ok((
+            200,
+            self.0,
+            format!("Total:{}ms", self.0.as_millis()),
+            HashMap::new(),
+            Some("HTTP/2.0".to_string()),
+        ))
+    }
+}
+
+/// Health client that reports healthy after a real delay
+struct DelayedHealthCheckClient(Duration);
+
+#[async_trait::async_trait]
+impl HealthCheckClient for DelayedHealthCheckClient {
+    async fn get_health(&self, _url: String, _timeout_ms: u32) -> Result<HealthResponse, String> {
+        tokio::time::sleep(self.0).await;
+        Ok(HealthResponse {
+            status_code: 200,
+            body: br#"{"status": "healthy"}"#.to_vec(),
+            duration: self.0,
+            headers: HashMap::new(),
+        })
+    }
+}
+
+#[cfg(not(feature = "timings-curl"))]
+#[tokio::test]
+async fn test_probe_runs_proxy_health_concurrently() {
+    let temp_dir = TempDir::new().unwrap();
+    let delay = Duration::from_millis(300);
+    let mut monitor = HttpMonitor::new(Some(temp_dir.path().join("monitoring.json")))
+        .unwrap()
+        .with_http_client(Box::new(DelayedHttpClient(delay)))
+        .with_health_client(Box::new(DelayedHealthCheckClient(delay)))
+        .with_clock(Box::new(TestClock::new()));
+
+    let creds = ApiCredentials {
+        base_url: "https://proxy.example.com".to_string(),
+        ..test_credentials()
+    };
+    let started = Instant::now();
+    let outcome = monitor.probe(ProbeMode::Green, creds, None).await.unwrap();
+    let elapsed = started.elapsed();
+
+    assert_eq!(outcome.status, NetworkStatus::Healthy);
+    assert!(
+        elapsed < delay * 2,
+        "probe and health check should overlap, took {:?}",
+        elapsed
+    );
+    let state = monitor.load_state().await.unwrap();
+    assert_eq!(state.network.proxy_healthy, Some(true));
+}
+
+#[tokio::test]
+async fn test_sample_horizon_evicts_stale_samples() {
+    let temp_dir = TempDir::new().unwrap();
+    let (monitor, http_client, clock) = create_test_monitor(&temp_dir);
+    let mut monitor = monitor.with_sample_horizon(Some(3600));
+
+    // TestClock reports 2025-01-25T10:30:00-08:00; two samples predate the horizon
+    let now = chrono::DateTime::parse_from_rfc3339("2025-01-25T10:30:00-08:00")
+        .unwrap()
+        .timestamp();
+    let mut state = MonitoringSnapshot::default();
+    state.network.rolling_totals = [4000, 4200, 900].into_iter().collect();
+    state.network.rolling_sampled_at = [now - 7200, now - 5400, now - 600].into_iter().collect();
+    state.network.p95_latency_ms = 4200;
+    std::fs::write(
+        temp_dir.path().join("monitoring.json"),
+        serde_json::to_string(&state).unwrap(),
+    )
+    .unwrap();
+
+    http_client.add_success(200, 1000).await;
+    clock.add_timestamp("2025-01-25T10:30:00-08:00").await;
+    let outcome = monitor
+        .probe(ProbeMode::Green, test_credentials(), None)
+        .await
+        .unwrap();
+
+    assert_eq!(outcome.rolling_len, 2);
+    assert_eq!(outcome.p95_latency_ms, 1000);
+    let state = monitor.load_state().await.unwrap();
+    assert_eq!(&state.network.rolling_totals[..], &[900, 1000]);
+    assert_eq!(&state.network.rolling_sampled_at[..], &[now - 600, now]);
+}
+
+#[tokio::test]
+async fn test_oauth_probe_switch_skips_oauth_credentials() {
+    let temp_dir = TempDir::new().unwrap();
+    let state_path = temp_dir.path().join("monitoring.json");
+    let monitor = HttpMonitor::new(Some(state_path.clone()))
+        .unwrap()
+        .with_http_client(Box::new(DelayedHttpClient(Duration::from_millis(1))))
+        .with_oauth_probe(false);
+    #[cfg(feature = "timings-curl")]
+    let monitor = monitor.without_curl_runner();
+    let mut monitor = monitor;
+    assert!(!monitor.oauth_probe_enabled());
+
+    let creds = ApiCredentials {
+        source: CredentialSource::OAuth,
+        ..test_credentials()
+    };
+    let outcome = monitor.probe(ProbeMode::Green, creds, None).await.unwrap();
+    assert_eq!(outcome.status, NetworkStatus::Unknown);
+    assert!(!state_path.exists(), "Skipped probes write no state");
+
+    // Environment credentials are unaffected by the switch
+    monitor
+        .probe(ProbeMode::Green, test_credentials(), None)
+        .await
+        .unwrap();
+    assert!(state_path.exists());
+}
+
+/// Client that only records the transport handed to it
+struct TransportRecorder(Arc<std::sync::Mutex<Option<TransportPolicy>>>);
+
+#[async_trait::async_trait]
+impl HealthCheckClient for TransportRecorder {
+    async fn get_health(&self, _url: String, _timeout_ms: u32) -> Result<HealthResponse, String> {
+        Err("not sent".to_string())
+    }
+
+    fn set_transport(&mut self, transport: &TransportPolicy) {
+        *self.0.lock().unwrap() = Some(transport.clone());
+    }
+}
+
+#[test]
+fn test_transport_reaches_clients_injected_before_and_after() {
+    let temp_dir = TempDir::new().unwrap();
+    let transport = TransportPolicy {
+        proxy: ccstatus::config::ProxyPolicy::new(
+            Some("http://proxy.internal:8080".to_string()),
+            None,
+            Vec::new(),
+        ),
+        ..Default::default()
+    };
+    let (before, after) = (Arc::default(), Arc::default());
+
+    let _monitor = HttpMonitor::new(Some(temp_dir.path().join("monitoring.json")))
+        .unwrap()
+        .with_health_client(Box::new(TransportRecorder(Arc::clone(&before))))
+        .with_transport(transport.clone());
+    assert_eq!(before.lock().unwrap().as_ref(), Some(&transport));
+
+    let _monitor = HttpMonitor::new(Some(temp_dir.path().join("monitoring.json")))
+        .unwrap()
+        .with_transport(transport.clone())
+        .with_health_client(Box::new(TransportRecorder(Arc::clone(&after))));
+    assert_eq!(after.lock().unwrap().as_ref(), Some(&transport));
+}
+
+#[test]
+fn test_forced_ip_family_reaches_clients_through_transport() {
+    use ccstatus::config::IpFamilyMode;
+    use ccstatus::core::network::ip_family::IpFamily;
+
+    let temp_dir = TempDir::new().unwrap();
+    let recorded = Arc::default();
+    let _monitor = HttpMonitor::new(Some(temp_dir.path().join("monitoring.json")))
+        .unwrap()
+        .with_health_client(Box::new(TransportRecorder(Arc::clone(&recorded))))
+        .with_ip_family(IpFamilyMode::V6);
+    let transport = recorded.lock().unwrap().clone().unwrap();
+    assert_eq!(transport.ip_family, Some(IpFamily::V6));
+
+    let _monitor = HttpMonitor::new(Some(temp_dir.path().join("monitoring.json")))
+        .unwrap()
+        .with_health_client(Box::new(TransportRecorder(Arc::clone(&recorded))))
+        .with_ip_family(IpFamilyMode::Compare);
+    let transport = recorded.lock().unwrap().clone().unwrap();
+    assert_eq!(transport.ip_family, None);
+}
+
+#[tokio::test]
+async fn test_invalid_tls_policy_refuses_probe_before_sending() {
+    let mut client = ccstatus::core::network::http_monitor::IsahcHttpClient::new().unwrap();
+    client.set_transport(&TransportPolicy {
+        tls: ccstatus::config::TlsPolicy::invalid("ca_file missing".to_string()),
+        ..Default::default()
+    });
+
+    let error = client
+        .execute_request(
+            "https://127.0.0.1:9/v1/messages".to_string(),
+            HashMap::new(),
+            Vec::new(),
+            1000,
+        )
+        .await
+        .unwrap_err();
+    assert!(error.contains("ca_file missing"), "{}", error);
+    assert!(error.contains("not sent"), "{}", error);
+}
@@ -0,0 +1,104 @@
+use ccstatus::config::{IpFamilyMode, NetworkConfig};
+use ccstatus::core::network::ip_family::{
+    forced_family, FamilyStats, IpFamily, IpFamilyComparison, FAMILY_WINDOW,
+};
+use ccstatus::core::network::ConnectionInfo;
+
+#[test]
+fn test_family_of_addresses() {
+    assert_eq!(IpFamily::of_str("160.79.104.10"), Some(IpFamily::V4));
+    assert_eq!(IpFamily::of_str("2607:6bc0::10"), Some(IpFamily::V6));
+    assert_eq!(IpFamily::of_str("[2607:6bc0::10]"), Some(IpFamily::V6));
+    // IPv4-mapped addresses travel over IPv4
+    assert_eq!(IpFamily::of_str("::ffff:160.79.104.10"), Some(IpFamily::V4));
+    assert_eq!(IpFamily::of_str("unknown"), None);
+
+    let connection = ConnectionInfo {
+        primary_ip: Some("2607:6bc0::10".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(connection.ip_family(), Some(IpFamily::V6));
+    assert_eq!(ConnectionInfo::default().ip_family(), None);
+}
+
+#[test]
+fn test_ip_family_mode_config() {
+    let parse = |value: &str| {
+        toml::from_str::<NetworkConfig>(&format!("ip_family = \"{}\"", value))
+            .unwrap()
+            .ip_family
+    };
+    assert_eq!(NetworkConfig::default().ip_family, IpFamilyMode::Auto);
+    assert_eq!(parse("v4"), IpFamilyMode::V4);
+    assert_eq!(parse("ipv6"), IpFamilyMode::V6);
+    assert_eq!(parse("compare"), IpFamilyMode::Compare);
+    assert!(toml::from_str::<NetworkConfig>("ip_family = \"v5\"").is_err());
+
+    assert_eq!(forced_family(IpFamilyMode::V4), Some(IpFamily::V4));
+    assert_eq!(forced_family(IpFamilyMode::V6), Some(IpFamily::V6));
+    assert_eq!(forced_family(IpFamilyMode::Auto), None);
+    assert_eq!(forced_family(IpFamilyMode::Compare), None);
+}
+
+#[test]
+fn test_family_stats_window_and_median() {
+    let mut stats = FamilyStats::default();
+    assert_eq!(stats.median_ms(), None);
+    for latency in 1..=(FAMILY_WINDOW as u32 + 2) {
+        stats.record(Ok(latency * 10));
+    }
+    assert_eq!(stats.samples_ms.len(), FAMILY_WINDOW);
+    assert_eq!(stats.samples_ms[0], 30);
+    assert_eq!(stats.median_ms(), Some(75));
+
+    stats.record(Err("timeout".to_string()));
+    stats.record(Err("Connection refused".to_string()));
+    assert_eq!(stats.consecutive_failures, 2);
+    assert_eq!(stats.last_error.as_deref(), Some("Connection refused"));
+    stats.record(Ok(40));
+    assert_eq!(stats.consecutive_failures, 0);
+    assert_eq!(stats.last_error, None);
+}
+
+#[test]
+fn test_verdict_flags_consistently_slower_family() {
+    let mut comparison = IpFamilyComparison::default();
+    for _ in 0..4 {
+        comparison.record(IpFamily::V4, Ok(100));
+        comparison.record(IpFamily::V6, Ok(400));
+    }
+    // Too few checks to judge
+    assert_eq!(comparison.verdict(), None);
+
+    comparison.record(IpFamily::V4, Ok(120));
+    comparison.record(IpFamily::V6, Ok(380));
+    assert_eq!(
+        comparison.verdict().as_deref(),
+        Some("IPv6 consistently slower: median 400ms vs 100ms over IPv4")
+    );
+
+    // A large ratio on tiny latencies is not worth flagging
+    let mut comparison = IpFamilyComparison::default();
+    for _ in 0..5 {
+        comparison.record(IpFamily::V4, Ok(40));
+        comparison.record(IpFamily::V6, Ok(20));
+    }
+    assert_eq!(comparison.verdict(), None);
+}
+
+#[test]
+fn test_verdict_needs_working_other_family() {
+    let mut comparison = IpFamilyComparison::default();
+    for _ in 0..3 {
+        comparison.record(IpFamily::V4, Err("No route to host".to_string()));
+        comparison.record(IpFamily::V6, Err("No route to host".to_string()));
+    }
+    // Both down is an outage, not a family problem
+    assert_eq!(comparison.verdict(), None);
+
+    comparison.record(IpFamily::V6, Ok(150));
+    assert_eq!(
+        comparison.verdict().as_deref(),
+        Some("IPv4 failing (3 checks in a row: No route to host) while IPv6 connects")
+    );
+}
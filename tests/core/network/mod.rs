@@ -3,12 +3,34 @@
 //! Tests for network monitoring components including HTTP probes,
 //! state persistence, and rolling statistics
 
+pub mod alerts_tests;
+pub mod certificate_tests;
+pub mod cookie_jar_tests;
 pub mod credential_env_test;
 pub mod credential_oauth_test;
 pub mod credential_tests;
+pub mod daemon_tests;
+pub mod debug_logger_init_tests;
+pub mod dns_cache_tests;
+pub mod error_budget_tests;
+pub mod fake_api_e2e_tests;
 pub mod http_monitor_test;
+pub mod ip_family_tests;
 pub mod jsonl_monitor_tests;
+pub mod net_check_cli_tests;
 pub mod network_segment_tests;
 pub mod oauth_masquerade_tests;
+pub mod probe_history_tests;
+pub mod probe_payload_tests;
+pub mod probe_profile_tests;
+pub mod prometheus_tests;
 pub mod proxy_health;
+pub mod sla_tests;
+pub mod standby_tests;
+pub mod state_sync_tests;
+pub mod status_expression_tests;
+pub mod status_hooks_tests;
 pub mod status_renderer_tests;
+pub mod timeseries_tests;
+pub mod tls_profile_tests;
+pub mod types_tests;
@@ -0,0 +1,19 @@
+use std::process::Command;
+
+#[test]
+fn test_net_check_exits_unknown_when_config_is_unreadable() {
+    let home = tempfile::tempdir().unwrap();
+    let config_dir = home.path().join(".claude").join("ccstatus");
+    std::fs::create_dir_all(&config_dir).unwrap();
+    std::fs::write(config_dir.join("config.toml"), "[network\nprobe_consent = ").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_ccstatus"))
+        .args(["net", "check"])
+        .env("HOME", home.path())
+        .output()
+        .unwrap();
+
+    // 1 would tell a CI gate the API is degraded; a broken setup is unknown
+    assert_eq!(output.status.code(), Some(3));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Check failed"));
+}
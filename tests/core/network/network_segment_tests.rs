@@ -26,6 +26,8 @@ fn create_test_input(
             total_lines_removed: 0,
         },
         exceeds_200k_tokens: false,
+        terminal_width: None,
+        permission_mode: None,
     }
 }
 
@@ -133,6 +135,127 @@ async fn test_window_calculation_no_active() {
     assert_eq!(decision.probe_mode, None);
 }
 
+#[test]
+fn test_classify_activity_from_transcript_mtime() {
+    use ccstatus::core::network::types::ActivityClass;
+    use std::time::{Duration, SystemTime};
+
+    let temp_dir = TempDir::new().unwrap();
+    let transcript = temp_dir.path().join("transcript.jsonl");
+    fs::write(&transcript, "{}\n").unwrap();
+    let path = transcript.to_str().unwrap();
+    let modified = fs::metadata(&transcript).unwrap().modified().unwrap();
+
+    assert_eq!(
+        NetworkSegment::classify_activity(path, modified + Duration::from_secs(30), 600),
+        ActivityClass::Active
+    );
+    assert_eq!(
+        NetworkSegment::classify_activity(path, modified + Duration::from_secs(600), 600),
+        ActivityClass::Idle
+    );
+    // Unknown recency never stretches the cadence
+    assert_eq!(
+        NetworkSegment::classify_activity("/nonexistent/transcript.jsonl", SystemTime::now(), 600),
+        ActivityClass::Active
+    );
+}
+
+#[tokio::test]
+async fn test_idle_session_stretches_green_windows() {
+    use ccstatus::core::network::types::ActivityClass;
+    use ccstatus::core::network::HttpMonitor;
+    use std::time::{Duration, SystemTime};
+
+    let temp_dir = TempDir::new().unwrap();
+    let state_path = temp_dir.path().join("monitoring.json");
+    let transcript = temp_dir.path().join("transcript.jsonl");
+    let file = fs::File::create(&transcript).unwrap();
+    file.set_modified(SystemTime::now() - Duration::from_secs(3600))
+        .unwrap();
+    let path = transcript.to_str().unwrap();
+    let mut segment = NetworkSegment::with_state_path(state_path.clone()).unwrap();
+
+    // Idle: only every 3rd GREEN window (900s) probes
+    let decision = segment
+        .calculate_window_decision(&create_test_input("s", 301_000, path), Some(false))
+        .await
+        .unwrap();
+    assert_eq!(decision.activity, ActivityClass::Idle);
+    assert_eq!(decision.probe_mode, None);
+
+    let decision = segment
+        .calculate_window_decision(&create_test_input("s", 901_000, path), Some(false))
+        .await
+        .unwrap();
+    assert_eq!(decision.probe_mode, Some(ProbeMode::Green));
+    assert_eq!(decision.green_window_id, Some(3));
+
+    let state = HttpMonitor::new(Some(state_path.clone()))
+        .unwrap()
+        .load_state()
+        .await
+        .unwrap();
+    assert_eq!(state.monitoring_state.activity, ActivityClass::Idle);
+    assert!(state.monitoring_state.activity_changed_at.is_some());
+
+    // New activity restores the regular cadence immediately
+    file.set_modified(SystemTime::now()).unwrap();
+    let decision = segment
+        .calculate_window_decision(&create_test_input("s", 1_201_000, path), Some(false))
+        .await
+        .unwrap();
+    assert_eq!(decision.activity, ActivityClass::Active);
+    assert_eq!(decision.probe_mode, Some(ProbeMode::Green));
+}
+
+#[tokio::test]
+async fn test_probe_profile_sets_window_cadence() {
+    use ccstatus::core::network::probe_profile::ProbeProfile;
+
+    let temp_dir = TempDir::new().unwrap();
+    let transcript = temp_dir.path().join("transcript.jsonl");
+    fs::write(&transcript, "").unwrap();
+    let path = transcript.to_str().unwrap();
+    let segment_with = |profile: ProbeProfile, name: &str| {
+        NetworkSegment::with_state_path(temp_dir.path().join(name))
+            .unwrap()
+            .with_probe_profile(profile)
+    };
+
+    // `aggressive` probes GREEN every minute
+    let mut segment = segment_with(ProbeProfile::Aggressive, "aggressive.json");
+    let decision = segment
+        .calculate_window_decision(&create_test_input("s", 61_000, path), Some(false))
+        .await
+        .unwrap();
+    assert_eq!(decision.probe_mode, Some(ProbeMode::Green));
+    assert_eq!(decision.green_window_id, Some(1));
+
+    // `conservative` spaces RED windows 30s apart
+    let mut segment = segment_with(ProbeProfile::Conservative, "conservative.json");
+    let decision = segment
+        .calculate_window_decision(&create_test_input("s", 20_500, path), Some(true))
+        .await
+        .unwrap();
+    assert_eq!(decision.probe_mode, None);
+    let decision = segment
+        .calculate_window_decision(&create_test_input("s", 30_500, path), Some(true))
+        .await
+        .unwrap();
+    assert_eq!(decision.probe_mode, Some(ProbeMode::Red));
+    assert_eq!(decision.red_window_id, Some(1));
+
+    // `minimal` never probes RED
+    let mut segment = segment_with(ProbeProfile::Minimal, "minimal.json");
+    let decision = segment
+        .calculate_window_decision(&create_test_input("s", 10_500, path), Some(true))
+        .await
+        .unwrap();
+    assert!(!decision.is_red_window);
+    assert_eq!(decision.probe_mode, None);
+}
+
 #[tokio::test]
 async fn test_cold_probe_deduplication() {
     let temp_dir = TempDir::new().unwrap();
@@ -327,6 +450,8 @@ fn create_enhancement_test_input(total_duration_ms: u64, session_id: &str) -> St
             total_lines_removed: 5,
         },
         exceeds_200k_tokens: false,
+        terminal_width: None,
+        permission_mode: None,
     }
 }
 
@@ -548,3 +673,145 @@ async fn test_enhancement_error_handling() {
     assert!(!decision_large.is_green_window);
     assert_eq!(decision_large.probe_mode, None);
 }
+
+#[tokio::test]
+async fn test_probe_consent_defaults_to_off() {
+    let temp_dir = TempDir::new().unwrap();
+    let state_path = temp_dir.path().join("monitoring.json");
+
+    let segment = NetworkSegment::with_state_path(state_path.clone()).unwrap();
+    assert!(!segment.probe_consent());
+
+    let segment = segment.with_probe_consent(true);
+    assert!(segment.probe_consent());
+}
+
+#[tokio::test]
+async fn test_passive_mode_without_consent_never_probes() {
+    let temp_dir = TempDir::new().unwrap();
+    let state_path = temp_dir.path().join("monitoring.json");
+    let mut segment = NetworkSegment::with_state_path(state_path.clone()).unwrap();
+
+    // COLD window would normally probe; without consent nothing is written
+    let input = create_test_input("session1", 1000, "/nonexistent/transcript.jsonl");
+    segment.run(input.clone()).await.unwrap();
+    assert!(!state_path.exists());
+
+    let status = segment.run_passive(&input).await.unwrap();
+    assert_eq!(status, "⚪ probes off");
+}
+
+#[tokio::test]
+async fn test_passive_mode_reports_transcript_errors() {
+    let temp_dir = TempDir::new().unwrap();
+    let state_path = temp_dir.path().join("monitoring.json");
+    let transcript_path = temp_dir.path().join("transcript.jsonl");
+    let error_entry = r#"{"isApiErrorMessage":true,"timestamp":"2024-01-01T12:00:00Z","message":{"content":[{"text":"API Error: 529 Overloaded"}]}}"#;
+    fs::write(&transcript_path, format!("{}\n", error_entry)).unwrap();
+
+    let segment = NetworkSegment::with_state_path(state_path.clone()).unwrap();
+    let input = create_test_input("session1", 60_000, transcript_path.to_str().unwrap());
+
+    let status = segment.run_passive(&input).await.unwrap();
+    assert_eq!(status, "🔴 probes off | API error 529");
+    assert!(!state_path.exists());
+}
+
+fn dry_run_target(name: &str, token_env: &str) -> ccstatus::config::NetworkTarget {
+    ccstatus::config::NetworkTarget {
+        name: name.to_string(),
+        label: None,
+        base_url: Some("https://dryrun.example.com".to_string()),
+        token_env: Some(token_env.to_string()),
+    }
+}
+
+#[tokio::test]
+async fn test_dry_run_records_would_probe_without_state() {
+    std::env::set_var("CCSTATUS_TEST_DRYRUN_TOKEN", "sk-dry-run");
+    let target = dry_run_target("dryrun-cold-test", "CCSTATUS_TEST_DRYRUN_TOKEN");
+    let temp_dir = TempDir::new().unwrap();
+    let state_path = temp_dir
+        .path()
+        .join("targets")
+        .join(format!("{}.json", target.name));
+    let mut segment = NetworkSegment::for_target(&target, state_path.clone())
+        .unwrap()
+        .with_probe_consent(true)
+        .with_dry_run(true);
+
+    let input = create_test_input("dry-session", 1000, "/nonexistent/transcript.jsonl");
+    segment.run(input).await.unwrap();
+
+    let record = segment.last_dry_run().expect("dry run record");
+    assert_eq!(record.session_id, "dry-session");
+    assert_eq!(record.credential_source.as_deref(), Some("environment"));
+    assert!(record.is_cold_window);
+    assert_eq!(record.would_probe.as_deref(), Some("cold"));
+    assert!(record.timeout_ms.is_some());
+    assert_eq!(record.skip_reason, None);
+    assert!(!state_path.exists());
+
+    // Outside every window nothing would probe
+    let input = create_test_input("dry-session", 65_000, "/nonexistent/transcript.jsonl");
+    segment.run(input).await.unwrap();
+    let record = segment.last_dry_run().unwrap();
+    assert_eq!(record.would_probe, None);
+    assert_eq!(record.timeout_ms, None);
+    assert_eq!(record.skip_reason.as_deref(), Some("no_active_window"));
+    assert!(!state_path.exists());
+
+    std::env::remove_var("CCSTATUS_TEST_DRYRUN_TOKEN");
+}
+
+#[tokio::test]
+async fn test_dry_run_without_credentials_skips_unknown_write() {
+    let target = dry_run_target("dryrun-nocreds-test", "CCSTATUS_TEST_DRYRUN_UNSET_TOKEN");
+    let temp_dir = TempDir::new().unwrap();
+    let state_path = temp_dir
+        .path()
+        .join("targets")
+        .join(format!("{}.json", target.name));
+    let mut segment = NetworkSegment::for_target(&target, state_path.clone())
+        .unwrap()
+        .with_probe_consent(true)
+        .with_dry_run(true);
+
+    let input = create_test_input("dry-session", 1000, "/nonexistent/transcript.jsonl");
+    segment.run(input).await.unwrap();
+
+    let record = segment.last_dry_run().expect("dry run record");
+    assert_eq!(record.credential_source, None);
+    assert_eq!(record.skip_reason.as_deref(), Some("no_credentials"));
+    assert!(!state_path.exists());
+}
+
+#[tokio::test]
+async fn test_fresh_daemon_lease_skips_probing() {
+    use ccstatus::core::network::daemon::DaemonLease;
+    use ccstatus::core::network::probe_profile::ProbeProfile;
+
+    let temp_dir = TempDir::new().unwrap();
+    let lease_path = temp_dir.path().join("ccstatus-daemon.json");
+    let segment = || {
+        NetworkSegment::with_state_path(temp_dir.path().join("monitoring.json"))
+            .unwrap()
+            .with_probe_consent(true)
+            .with_dry_run(true)
+            .with_daemon_lease(lease_path.clone())
+    };
+    let input = create_test_input("daemon-session", 1000, "/nonexistent/transcript.jsonl");
+
+    // No lease yet: the statusline plans its own COLD probe
+    let mut without_daemon = segment();
+    without_daemon.run(input.clone()).await.unwrap();
+    assert!(without_daemon.last_dry_run().is_some());
+
+    let mut segment = segment();
+    let now = chrono::Utc::now().timestamp();
+    DaemonLease::new(ProbeProfile::Standard, now)
+        .save(&lease_path)
+        .unwrap();
+    segment.run(input).await.unwrap();
+    assert!(segment.last_dry_run().is_none());
+}
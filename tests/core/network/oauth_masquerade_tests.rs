@@ -1,3 +1,5 @@
+#![cfg(feature = "oauth-probe")]
+
 use ccstatus::core::network::http_monitor::HttpClientTrait;
 use ccstatus::core::network::oauth_masquerade::{
     run_probe, OauthMasqueradeOptions, OauthMasqueradeResult,
@@ -76,6 +78,7 @@ fn test_oauth_masquerade_result_creation() {
         breakdown: "DNS:5ms|TCP:10ms|TLS:50ms|ServerTTFB:200ms|Total:265ms".to_string(),
         response_headers: headers,
         http_version: Some("HTTP/2.0".to_string()),
+        connection: None,
     };
 
     assert_eq!(result.status, 200);
@@ -341,6 +344,9 @@ mod curl_tests {
                 ttfb_ms: 100,
                 total_ttfb_ms: 160,
                 total_ms: 190,
+                set_cookies: Vec::new(),
+                headers: Default::default(),
+                connection: Default::default(),
             })
         }
     }
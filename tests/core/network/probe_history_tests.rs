@@ -0,0 +1,183 @@
+use ccstatus::config::HistoryConfig;
+use ccstatus::core::network::probe_history::{
+    records_between, summarize, summarize_by_version, ProbeHistory, ProbeRecord, COMPACT_AT_BYTES,
+};
+use ccstatus::core::network::types::NetworkStatus;
+use ccstatus::core::network::StatusRenderer;
+use tempfile::TempDir;
+
+fn record(version: &str, latency_ms: u32, status: NetworkStatus) -> ProbeRecord {
+    let http_status = if status == NetworkStatus::Error {
+        529
+    } else {
+        200
+    };
+    ProbeRecord {
+        timestamp: "2025-01-25T10:30:00-08:00".to_string(),
+        version: version.to_string(),
+        mode: "green".to_string(),
+        http_status,
+        latency_ms,
+        status,
+        error_type: None,
+    }
+}
+
+#[test]
+fn test_summarize_by_version() {
+    let records = vec![
+        record("2.2.7", 1000, NetworkStatus::Healthy),
+        record("2.2.7", 1200, NetworkStatus::Healthy),
+        record("2.2.8", 1500, NetworkStatus::Degraded),
+        record("2.2.8", 9000, NetworkStatus::Error),
+        record("2.2.8", 1700, NetworkStatus::Healthy),
+    ];
+
+    let summaries = summarize_by_version(&records);
+    assert_eq!(summaries.len(), 2);
+    assert_eq!(summaries[0].version, "2.2.7");
+    assert_eq!(summaries[0].p50_latency_ms, 1000);
+    assert_eq!(summaries[0].p95_latency_ms, 1200);
+
+    // Failed probes count toward classification but not latency
+    assert_eq!(summaries[1].probes, 3);
+    assert_eq!(summaries[1].p95_latency_ms, 1700);
+    assert_eq!(
+        (
+            summaries[1].healthy,
+            summaries[1].degraded,
+            summaries[1].error
+        ),
+        (1, 1, 1)
+    );
+}
+
+#[test]
+fn test_version_comparison_flags_regression() {
+    let renderer = StatusRenderer::new();
+    assert_eq!(
+        renderer.render_version_comparison(&[]),
+        "No probe history recorded yet"
+    );
+
+    let records = vec![
+        record("2.2.7", 1000, NetworkStatus::Healthy),
+        record("2.2.8", 1500, NetworkStatus::Healthy),
+    ];
+    let report = renderer.render_version_comparison(&summarize_by_version(&records));
+    assert!(report.contains("2.2.7 → 2.2.8: P50 +500ms, P95 +500ms (+50%), error rate +0.0pp"));
+    assert!(report.ends_with("⚠ possible regression"));
+
+    let records = vec![
+        record("2.2.7", 1000, NetworkStatus::Healthy),
+        record("2.2.8", 1050, NetworkStatus::Healthy),
+    ];
+    let report = renderer.render_version_comparison(&summarize_by_version(&records));
+    assert!(!report.contains("regression"));
+}
+
+#[test]
+fn test_history_append_and_compaction() {
+    let temp_dir = TempDir::new().unwrap();
+    let history = ProbeHistory::for_state_path(&temp_dir.path().join("relay.json")).with_retention(
+        HistoryConfig {
+            retention_days: 30,
+            max_records: 100,
+        },
+    );
+    assert_eq!(history.path(), temp_dir.path().join("relay.history.jsonl"));
+
+    history
+        .append(&record("1.0.0", 100, NetworkStatus::Healthy))
+        .unwrap();
+    assert_eq!(history.load().len(), 1);
+
+    // Grow the file past the compaction threshold, then append once more
+    let line = serde_json::to_string(&record("1.0.0", 100, NetworkStatus::Healthy)).unwrap() + "\n";
+    let lines = (COMPACT_AT_BYTES as usize / line.len()) + 1;
+    std::fs::write(history.path(), line.repeat(lines)).unwrap();
+    history
+        .append(&record("1.0.1", 100, NetworkStatus::Healthy))
+        .unwrap();
+
+    let records = history.load();
+    assert_eq!(records.len(), 100);
+    assert_eq!(records.last().unwrap().version, "1.0.1");
+}
+
+fn record_at(timestamp: &str, latency_ms: u32, status: NetworkStatus) -> ProbeRecord {
+    ProbeRecord {
+        timestamp: timestamp.to_string(),
+        ..record("1.0.0", latency_ms, status)
+    }
+}
+
+#[test]
+fn test_history_drops_records_past_retention() {
+    let temp_dir = TempDir::new().unwrap();
+    let history = ProbeHistory::for_state_path(&temp_dir.path().join("state.json")).with_retention(
+        HistoryConfig {
+            retention_days: 7,
+            max_records: 1000,
+        },
+    );
+    let old = record_at("2025-01-11T12:00:00+00:00", 100, NetworkStatus::Healthy);
+    let week = record_at("2025-01-19T10:00:00+00:00", 200, NetworkStatus::Healthy);
+    history.append(&old).unwrap();
+    history.append(&week).unwrap();
+    assert_eq!(
+        history.load().len(),
+        2,
+        "Within retention plus a day of slack"
+    );
+
+    history
+        .append(&record_at(
+            "2025-01-25T10:00:00+00:00",
+            300,
+            NetworkStatus::Healthy,
+        ))
+        .unwrap();
+    let latencies: Vec<u32> = history.load().iter().map(|r| r.latency_ms).collect();
+    assert_eq!(latencies, vec![200, 300]);
+}
+
+#[test]
+fn test_summarize_window() {
+    let records = vec![
+        record_at("2025-01-25T10:00:00+00:00", 3000, NetworkStatus::Healthy),
+        record_at("2025-01-25T09:00:00+00:00", 900, NetworkStatus::Healthy),
+        record_at("2025-01-25T09:30:00+00:00", 1100, NetworkStatus::Degraded),
+        record_at("2025-01-25T09:45:00+00:00", 5000, NetworkStatus::Error),
+        record_at("2025-01-24T09:00:00+00:00", 100, NetworkStatus::Healthy),
+    ];
+    // 2025-01-25T09:00:00Z ..= 2025-01-25T10:00:00Z
+    let window = records_between(&records, 1_737_795_600_000, 1_737_799_200_000);
+    let latencies: Vec<u32> = window.iter().map(|r| r.latency_ms).collect();
+    assert_eq!(latencies, vec![900, 1100, 5000, 3000], "Oldest first");
+
+    let summary = summarize(&window);
+    assert_eq!(summary.probes, 4);
+    assert_eq!(summary.successful, 3);
+    assert_eq!(summary.p50_latency_ms, 1100);
+    assert_eq!(summary.p95_latency_ms, 3000);
+    assert_eq!(
+        (summary.healthy, summary.degraded, summary.error),
+        (2, 1, 1)
+    );
+
+    let report = StatusRenderer::new().render_history(
+        "1h",
+        &[
+            ("primary".to_string(), summary),
+            ("relay".to_string(), summarize(&[])),
+        ],
+        &[("primary", window[3])],
+    );
+    assert!(report.starts_with("Probe history, last 1h"));
+    assert!(
+        report.contains("primary           4   1100ms   3000ms   3000ms      50%      25%    25%")
+    );
+    assert!(report.contains("relay             0  no probes in range"));
+    assert!(report.ends_with("primary      green 200   3000ms  healthy"));
+}
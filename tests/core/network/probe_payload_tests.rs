@@ -0,0 +1,163 @@
+// Tests for the probe payload sanitization contract
+use ccstatus::config::ProbeConfig;
+#[cfg(feature = "oauth-probe")]
+use ccstatus::core::network::oauth_masquerade::CLAUDE_CODE_SYSTEM_PROMPT;
+use ccstatus::core::network::probe_payload::{
+    build_probe_payload, encode_probe_payload, payload_checksum, validate_probe_payload,
+    ProbePayloadKind, ProbeRequest, PROBE_MESSAGE, PROBE_MODEL,
+};
+use serde_json::json;
+
+#[test]
+fn test_api_key_payload_is_fixed() {
+    let request = ProbeRequest::default();
+    let payload = build_probe_payload(ProbePayloadKind::ApiKey, &request);
+
+    assert_eq!(
+        payload,
+        json!({
+            "model": PROBE_MODEL,
+            "max_tokens": 1,
+            "messages": [{"role": "user", "content": PROBE_MESSAGE}]
+        })
+    );
+    assert!(validate_probe_payload(&payload, &request).is_ok());
+}
+
+#[cfg(feature = "oauth-probe")]
+#[test]
+fn test_oauth_payload_passes_contract() {
+    let request = ProbeRequest::default();
+    for stream in [false, true] {
+        let payload = build_probe_payload(ProbePayloadKind::OauthMasquerade { stream }, &request);
+        assert!(validate_probe_payload(&payload, &request).is_ok());
+        assert_eq!(payload["system"][0]["text"], CLAUDE_CODE_SYSTEM_PROMPT);
+        assert_eq!(payload.get("stream").is_some(), stream);
+    }
+}
+
+#[test]
+fn test_payload_is_deterministic() {
+    let request = ProbeRequest::default();
+    // Only constants and the request feed the body, so every build encodes identically
+    let first = encode_probe_payload(
+        &build_probe_payload(ProbePayloadKind::ApiKey, &request),
+        &request,
+    )
+    .unwrap();
+    let second = encode_probe_payload(
+        &build_probe_payload(ProbePayloadKind::ApiKey, &request),
+        &request,
+    )
+    .unwrap();
+
+    assert_eq!(first, second);
+    assert_eq!(payload_checksum(&first), payload_checksum(&second));
+}
+
+#[test]
+fn test_contract_rejects_workspace_context() {
+    let request = ProbeRequest::default();
+    let mut leaked_message = build_probe_payload(ProbePayloadKind::ApiKey, &request);
+    leaked_message["messages"][0]["content"] = json!("Hi from /home/alice/secret-project");
+    assert!(validate_probe_payload(&leaked_message, &request).is_err());
+
+    let mut extra_key = build_probe_payload(ProbePayloadKind::ApiKey, &request);
+    extra_key["metadata"] = json!({"user_id": "alice"});
+    assert!(validate_probe_payload(&extra_key, &request).is_err());
+
+    let mut other_model = build_probe_payload(ProbePayloadKind::ApiKey, &request);
+    other_model["model"] = json!("claude-opus-4");
+    assert!(validate_probe_payload(&other_model, &request).is_err());
+
+    let mut more_tokens = build_probe_payload(ProbePayloadKind::ApiKey, &request);
+    more_tokens["max_tokens"] = json!(1024);
+    assert!(validate_probe_payload(&more_tokens, &request).is_err());
+
+    #[cfg(feature = "oauth-probe")]
+    {
+        let mut custom_system = build_probe_payload(
+            ProbePayloadKind::OauthMasquerade { stream: false },
+            &request,
+        );
+        custom_system["system"][0]["text"] = json!("cwd=/home/alice");
+        assert!(validate_probe_payload(&custom_system, &request).is_err());
+    }
+
+    assert!(encode_probe_payload(&extra_key, &request).is_err());
+}
+
+#[test]
+fn test_configured_probe_request() {
+    let config = ProbeConfig {
+        model: Some("gpt-4o-mini".to_string()),
+        max_tokens: Some(4),
+        path: Some("/api/anthropic/v1/messages".to_string()),
+    };
+    let request = ProbeRequest::from_config(&config).unwrap();
+
+    let payload = build_probe_payload(ProbePayloadKind::ApiKey, &request);
+    assert_eq!(payload["model"], "gpt-4o-mini");
+    assert_eq!(payload["max_tokens"], 4);
+    assert!(validate_probe_payload(&payload, &request).is_ok());
+    // The default contract still rejects the configured model
+    assert!(validate_probe_payload(&payload, &ProbeRequest::default()).is_err());
+
+    assert_eq!(
+        request.endpoint("https://gateway.example.com/"),
+        "https://gateway.example.com/api/anthropic/v1/messages"
+    );
+    // The default path keeps build_messages_endpoint's /v1 handling
+    assert_eq!(
+        ProbeRequest::default().endpoint("https://proxy.example.com/v1"),
+        "https://proxy.example.com/v1/messages"
+    );
+}
+
+#[test]
+fn test_probe_config_validation() {
+    let invalid = [
+        ProbeConfig {
+            model: Some("model with spaces".to_string()),
+            ..ProbeConfig::default()
+        },
+        ProbeConfig {
+            max_tokens: Some(0),
+            ..ProbeConfig::default()
+        },
+        ProbeConfig {
+            max_tokens: Some(1024),
+            ..ProbeConfig::default()
+        },
+        ProbeConfig {
+            path: Some("v1/messages".to_string()),
+            ..ProbeConfig::default()
+        },
+        ProbeConfig {
+            path: Some("/v1/../admin".to_string()),
+            ..ProbeConfig::default()
+        },
+        ProbeConfig {
+            path: Some("/v1/messages?beta=true".to_string()),
+            ..ProbeConfig::default()
+        },
+    ];
+    for config in &invalid {
+        assert!(ProbeRequest::from_config(config).is_err(), "{:?}", config);
+    }
+
+    assert_eq!(
+        ProbeRequest::from_config(&ProbeConfig::default()).unwrap(),
+        ProbeRequest::default()
+    );
+}
+
+#[test]
+fn test_payload_checksum_is_sha256_hex() {
+    let checksum = payload_checksum(b"");
+    assert_eq!(
+        checksum,
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    );
+    assert_eq!(payload_checksum(b"probe").len(), 64);
+}
@@ -0,0 +1,78 @@
+use ccstatus::core::network::probe_profile::{PayloadStrategy, ProbeProfile, SessionProfile};
+
+fn pinned(profile: ProbeProfile, session_id: &str) -> SessionProfile {
+    SessionProfile {
+        profile,
+        session_id: session_id.to_string(),
+        since: "2025-01-25T10:30:00-08:00".to_string(),
+    }
+}
+
+#[test]
+fn test_parse_profile_names() {
+    assert_eq!(
+        " Aggressive ".parse::<ProbeProfile>(),
+        Ok(ProbeProfile::Aggressive)
+    );
+    assert_eq!("minimal".parse::<ProbeProfile>(), Ok(ProbeProfile::Minimal));
+    assert!("turbo".parse::<ProbeProfile>().is_err());
+
+    for profile in ProbeProfile::ALL {
+        assert_eq!(profile.to_string().parse::<ProbeProfile>(), Ok(profile));
+    }
+}
+
+#[test]
+fn test_standard_profile_keeps_builtin_cadence() {
+    let profile = ProbeProfile::default();
+    assert_eq!(profile, ProbeProfile::Standard);
+    assert_eq!(profile.green_interval_secs(), 300);
+    assert_eq!(profile.red_interval_secs(), Some(10));
+    assert_eq!(profile.red_timeout_ms(), 2000);
+    assert_eq!(profile.adaptive_timeout_ms(), (3500, 2500, 4000));
+    assert_eq!(profile.payload_strategy(), PayloadStrategy::Configured);
+
+    assert_eq!(ProbeProfile::Minimal.red_interval_secs(), None);
+    assert_eq!(
+        ProbeProfile::Minimal.payload_strategy(),
+        PayloadStrategy::ProbeOnly
+    );
+    assert_eq!(
+        ProbeProfile::Aggressive.payload_strategy(),
+        PayloadStrategy::Full
+    );
+}
+
+#[test]
+fn test_resolve_pins_env_profile_to_session() {
+    // Env selection is pinned once, then kept quietly
+    assert_eq!(
+        ProbeProfile::resolve(Some("aggressive"), None, "s1"),
+        (ProbeProfile::Aggressive, true)
+    );
+    let pin = pinned(ProbeProfile::Aggressive, "s1");
+    assert_eq!(
+        ProbeProfile::resolve(Some("aggressive"), Some(&pin), "s1"),
+        (ProbeProfile::Aggressive, false)
+    );
+
+    // Without the variable the session keeps its pin; an invalid value is ignored
+    assert_eq!(
+        ProbeProfile::resolve(None, Some(&pin), "s1"),
+        (ProbeProfile::Aggressive, false)
+    );
+    assert_eq!(
+        ProbeProfile::resolve(Some("turbo"), Some(&pin), "s1"),
+        (ProbeProfile::Aggressive, false)
+    );
+
+    // Reverting is explicit, and other sessions never inherit the pin
+    assert_eq!(
+        ProbeProfile::resolve(Some("standard"), Some(&pin), "s1"),
+        (ProbeProfile::Standard, true)
+    );
+    assert_eq!(
+        ProbeProfile::resolve(None, Some(&pin), "s2"),
+        (ProbeProfile::Standard, false)
+    );
+}
@@ -0,0 +1,103 @@
+use ccstatus::core::network::prometheus::render_metrics;
+use ccstatus::core::network::proxy_health::ProxyHealthLevel;
+use ccstatus::core::network::{MonitoringSnapshot, NetworkStatus};
+
+fn snapshot(status: NetworkStatus) -> MonitoringSnapshot {
+    let mut state = MonitoringSnapshot {
+        status,
+        timestamp: "2025-01-25T10:30:45+00:00".to_string(),
+        ..Default::default()
+    };
+    state.network.latency_ms = 812;
+    state.network.p95_latency_ms = 1400;
+    state.network.last_http_status = 529;
+    state.network.rolling_totals.extend([700, 812, 1400]);
+    state.monitoring_state.ledger.probes = 42;
+    state.monitoring_state.ledger.errors = 3;
+    state.monitoring_state.consecutive_failures = 1;
+    state
+}
+
+#[test]
+fn test_render_metrics_per_partition() {
+    let mut relay = snapshot(NetworkStatus::Healthy);
+    relay.network.proxy_health_level = Some(ProxyHealthLevel::Degraded);
+    let metrics = render_metrics(&[
+        ("primary".to_string(), snapshot(NetworkStatus::Error)),
+        ("re\"lay".to_string(), relay),
+    ]);
+
+    for expected in [
+        "# TYPE ccstatus_status gauge",
+        "ccstatus_status{target=\"primary\"} 2",
+        "ccstatus_status{target=\"re\\\"lay\"} 0",
+        "ccstatus_status_info{target=\"primary\",status=\"error\"} 1",
+        "ccstatus_latency_ms{target=\"primary\"} 812",
+        "ccstatus_p95_latency_ms{target=\"primary\"} 1400",
+        "ccstatus_rolling_samples{target=\"primary\"} 3",
+        "ccstatus_last_http_status{target=\"primary\"} 529",
+        "# TYPE ccstatus_probes_total counter",
+        "ccstatus_probes_total{target=\"primary\"} 42",
+        "ccstatus_probe_errors_total{target=\"primary\"} 3",
+        "ccstatus_consecutive_failures{target=\"primary\"} 1",
+        "ccstatus_proxy_health{target=\"re\\\"lay\"} 1",
+        "ccstatus_updated_timestamp_seconds{target=\"primary\"} 1737801045",
+    ] {
+        assert!(
+            metrics.lines().any(|line| line == expected),
+            "missing {:?} in:\n{}",
+            expected,
+            metrics
+        );
+    }
+    // No proxy checked and no health score: no sample rather than a fake zero
+    assert!(!metrics.contains("ccstatus_proxy_health{target=\"primary\"}"));
+    assert!(!metrics.contains("ccstatus_health_score{"));
+}
+
+#[cfg(feature = "prometheus-listener")]
+#[test]
+fn test_serve_answers_metrics_scrapes() {
+    use ccstatus::core::network::prometheus::serve;
+    use ccstatus::core::shutdown::{ShutdownCoordinator, ShutdownReason};
+    use std::io::{Read, Write};
+    use std::net::{TcpListener, TcpStream};
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let shutdown = ShutdownCoordinator::new();
+    let server = {
+        let shutdown = shutdown.clone();
+        std::thread::spawn(move || {
+            serve(
+                listener,
+                &shutdown,
+                || Ok("ccstatus_status 0\n".to_string()),
+                |query| Ok(format!("[{{\"range\":{}}}]", query.range_secs)),
+            )
+        })
+    };
+
+    let get = |path: &str| {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(stream, "GET {} HTTP/1.1\r\nHost: test\r\n\r\n", path).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    };
+    let metrics = get("/metrics");
+    assert!(metrics.starts_with("HTTP/1.1 200 OK"));
+    assert!(metrics.contains("text/plain; version=0.0.4"));
+    assert!(metrics.ends_with("ccstatus_status 0\n"));
+    assert!(get("/other").starts_with("HTTP/1.1 404"));
+
+    let series = get("/api/timeseries?metric=latency&range=2h");
+    assert!(series.starts_with("HTTP/1.1 200 OK"));
+    assert!(series.contains("application/json"));
+    assert!(series.ends_with("[{\"range\":7200}]"));
+    assert!(get("/api/timeseries?metric=cpu").starts_with("HTTP/1.1 400"));
+
+    // Stops accepting once shutdown is requested
+    shutdown.request(ShutdownReason::Signal);
+    server.join().unwrap().unwrap();
+}
@@ -111,4 +111,45 @@ async fn test_assess_with_fallback() {
     );
 }
 
+#[tokio::test]
+async fn test_bad_level_keeps_excerpt_and_guidance() {
+    let mut client = MockHealthClient::default();
+    client.add_response(
+        "https://proxy.com/api/health",
+        200,
+        "<html><title>Sign in to Corp Network</title></html>",
+    );
+
+    let outcome = assess_proxy_health(
+        "https://proxy.com/api",
+        &ProxyHealthOptions::default(),
+        &client,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(outcome.level, Some(ProxyHealthLevel::Bad));
+    let detail = outcome.detail.unwrap();
+    assert_eq!(detail.reason.as_deref(), Some("invalid_json_200"));
+    assert_eq!(
+        detail.body_excerpt.as_deref(),
+        Some("Sign in to Corp Network")
+    );
+    assert!(detail.guidance.unwrap().contains("HTML login page"));
+
+    // Healthy responses carry neither
+    let mut client = MockHealthClient::default();
+    client.add_response("https://proxy.com/api/health", 200, r#"{"status": "ok"}"#);
+    let detail = assess_proxy_health(
+        "https://proxy.com/api",
+        &ProxyHealthOptions::default(),
+        &client,
+    )
+    .await
+    .unwrap()
+    .detail
+    .unwrap();
+    assert!(detail.body_excerpt.is_none() && detail.guidance.is_none());
+}
+
 // Note: validate_redirect_host is now private, tested indirectly through assess_proxy_health
@@ -390,6 +390,9 @@ async fn test_centralized_field_mapping_consistency() {
         checked_at: "2025-08-28T10:30:00-07:00".to_string(),
         response_time_ms: 100,
         reason: None,
+        body_excerpt: None,
+        guidance: None,
+        certificate_warning: None,
     };
 
     metrics.set_proxy_health(Some(ProxyHealthLevel::Healthy), Some(detail.clone()));
@@ -7,7 +7,10 @@ legacy validation, and Cloudflare challenge detection.
 */
 
 use ccstatus::core::network::proxy_health::config::ProxyHealthLevel;
-use ccstatus::core::network::proxy_health::parsing::{parse_health_response, validate_health_json};
+use ccstatus::core::network::proxy_health::parsing::{
+    bad_health_guidance, body_excerpt, parse_health_response, validate_health_json,
+};
+use std::collections::HashMap;
 
 #[test]
 fn test_parse_status_field() {
@@ -118,3 +121,44 @@ fn test_validate_health_json_legacy() {
     assert!(!validate_health_json(br#"{"status": "unhealthy"}"#));
     assert!(!validate_health_json(b"invalid json"));
 }
+
+#[test]
+fn test_body_excerpt_sanitizes_html_and_secrets() {
+    let page = br#"<!DOCTYPE html><html><head><style>body { color: red }</style>
+<script>var token = "abc";</script><title>Corp   VPN</title></head>
+<body><h1>Please sign in</h1><p>api_key=sk-live-123456 Bearer eyJhbGciOiJIUzI1NiJ9xxxxxxxxxxxxxxxxxxxxx</p></body></html>"#;
+    assert_eq!(
+        body_excerpt(page, 160).as_deref(),
+        Some("Corp VPN Please sign in api_key=[REDACTED] Bearer [REDACTED]")
+    );
+
+    assert_eq!(
+        body_excerpt(b"upstream connect error", 10).as_deref(),
+        Some("upstream …")
+    );
+    assert_eq!(body_excerpt(b"<html><br/></html>\n", 160), None);
+}
+
+#[test]
+fn test_bad_health_guidance() {
+    let html = HashMap::from([(
+        "Content-Type".to_string(),
+        "text/html; charset=utf-8".to_string(),
+    )]);
+    assert_eq!(
+        bad_health_guidance(200, &html, b"<form>Login</form>"),
+        "proxy returned an HTML login page \u{2014} check VPN or network sign-in"
+    );
+    assert!(bad_health_guidance(200, &html, b"<h1>Welcome to nginx</h1>").contains("HTML page"));
+    // HTML is recognized without a content type too
+    assert!(
+        bad_health_guidance(502, &HashMap::new(), b"<!DOCTYPE html><p>Sign in</p>")
+            .contains("login page")
+    );
+
+    let none = HashMap::new();
+    assert!(bad_health_guidance(502, &none, b"Bad Gateway").contains("upstream"));
+    assert!(bad_health_guidance(503, &none, b"").contains("unavailable"));
+    assert!(bad_health_guidance(200, &none, b"OK").contains("did not return JSON"));
+    assert!(bad_health_guidance(200, &none, br#"{"status":"down"}"#).contains("error status"));
+}
@@ -7,8 +7,8 @@ path-based health URLs, normalization, and official endpoint detection.
 */
 
 use ccstatus::core::network::proxy_health::url::{
-    build_path_health_url, build_root_health_url, extract_host, is_official_base_url,
-    normalize_base_url,
+    build_models_endpoint, build_path_health_url, build_root_health_url, extract_host,
+    is_official_base_url, normalize_base_url,
 };
 
 #[test]
@@ -82,3 +82,19 @@ fn test_extract_host() {
 
     assert!(extract_host("not-a-url").is_err());
 }
+
+#[test]
+fn test_build_models_endpoint() {
+    assert_eq!(
+        build_models_endpoint("https://api.anthropic.com"),
+        "https://api.anthropic.com/v1/models"
+    );
+    assert_eq!(
+        build_models_endpoint("https://proxy.com/api/"),
+        "https://proxy.com/api/v1/models"
+    );
+    assert_eq!(
+        build_models_endpoint("https://proxy.com/api/v1/"),
+        "https://proxy.com/api/v1/models"
+    );
+}
@@ -0,0 +1,174 @@
+#![cfg(feature = "network-monitoring")]
+
+//! Tests for the monthly SLA ledger and `ccstatus net sla` report (`sla`)
+
+use ccstatus::config::SlaTarget;
+use ccstatus::core::network::sla::{month_of, parse_month, SlaLedger, SlaTracker, MAX_MONTHS};
+use ccstatus::core::network::status_hooks::{StatusChange, StatusHook};
+use ccstatus::core::network::*;
+use tempfile::TempDir;
+
+/// 2026-10-01T00:00:00Z
+const OCT_2026: i64 = 1_790_812_800;
+
+fn target(endpoint: &str, p95_ms: Option<u32>, availability: Option<f64>) -> SlaTarget {
+    SlaTarget {
+        endpoint: endpoint.to_string(),
+        p95_ms,
+        availability,
+    }
+}
+
+#[test]
+fn test_ledger_tallies_probes_by_month() {
+    assert_eq!(month_of(OCT_2026), "2026-10");
+    assert_eq!(month_of(OCT_2026 - 1), "2026-09");
+    assert_eq!(parse_month(" 2026-3 ").as_deref(), Some("2026-03"));
+    assert_eq!(parse_month("2026-13"), None);
+
+    let mut ledger = SlaLedger::default();
+    // 95 fast probes, 4 slow ones and a single failure
+    for i in 0..95 {
+        ledger.record("primary", &NetworkStatus::Healthy, 850, OCT_2026 + i);
+    }
+    for i in 0..4 {
+        ledger.record(
+            "primary",
+            &NetworkStatus::Degraded,
+            4200,
+            OCT_2026 + 100 + i,
+        );
+    }
+    ledger.record("primary", &NetworkStatus::Error, 0, OCT_2026 + 200);
+    // Probes that never reached the vendor are not counted
+    assert!(!ledger.record("primary", &NetworkStatus::Unknown, 0, OCT_2026));
+    assert!(!ledger.record("primary", &NetworkStatus::BlockedByPolicy, 0, OCT_2026));
+
+    let stats = &ledger.endpoints["primary"]["2026-10"];
+    assert_eq!((stats.probes, stats.up), (100, 99));
+    assert_eq!(stats.availability_percent(), Some(99.0));
+    // 95 of 99 successes took 850ms
+    assert_eq!(stats.p95_latency_ms(), Some(850));
+    assert_eq!((stats.first_at, stats.last_at), (OCT_2026, OCT_2026 + 200));
+
+    // Only the most recent months are kept
+    for month in 1..=MAX_MONTHS as i64 {
+        ledger.record(
+            "primary",
+            &NetworkStatus::Healthy,
+            500,
+            OCT_2026 + month * 31 * 86_400,
+        );
+    }
+    assert_eq!(ledger.endpoints["primary"].len(), MAX_MONTHS);
+    assert!(!ledger.endpoints["primary"].contains_key("2026-10"));
+}
+
+#[test]
+fn test_compliance_and_report() {
+    let mut ledger = SlaLedger::default();
+    for i in 0..99 {
+        ledger.record("primary", &NetworkStatus::Healthy, 1_500, OCT_2026 + i);
+    }
+    ledger.record("primary", &NetworkStatus::Error, 0, OCT_2026 + 99);
+    for i in 0..10 {
+        ledger.record("relay", &NetworkStatus::Error, 0, OCT_2026 + i);
+    }
+
+    let targets = vec![
+        target("primary", Some(2000), Some(99.0)),
+        target("relay", Some(2000), None),
+        target("backup", None, Some(99.9)),
+    ];
+    let compliance = ledger.compliance(&targets, "2026-10");
+    assert_eq!(compliance[0].availability_met(), Some(true));
+    assert_eq!(compliance[0].p95_met(), Some(true));
+    assert_eq!(compliance[0].is_met(), Some(true));
+    // Every probe failed, so there is no latency to meet the P95 target with
+    assert_eq!(compliance[1].p95_met(), None);
+    assert_eq!(compliance[1].is_met(), Some(false));
+    assert_eq!(compliance[2].is_met(), None);
+
+    // A stricter target on the same month is missed
+    let strict = ledger.compliance(&[target("primary", Some(1400), Some(99.5))], "2026-10");
+    assert_eq!(strict[0].availability_met(), Some(false));
+    assert_eq!(strict[0].p95_met(), Some(false));
+
+    let report = StatusRenderer::new().render_sla_report("2026-10", &compliance);
+    let lines: Vec<&str> = report.lines().collect();
+    assert_eq!(lines[0], "SLA compliance for 2026-10 (UTC)");
+    assert!(lines[2].starts_with("primary") && lines[2].contains("99.00%"));
+    assert!(lines[2].contains("1500ms") && lines[2].ends_with("met"));
+    assert!(lines[3].ends_with("missed"));
+    assert!(lines[4].contains("≥99.90%") && lines[4].ends_with("no data"));
+    assert!(report.ends_with("1 of 2 endpoints met their SLA"));
+}
+
+#[tokio::test]
+async fn test_busy_month_keeps_p95_across_save() {
+    use ccstatus::core::stats::SKETCH_RELATIVE_ACCURACY;
+
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("sla.json");
+    let mut ledger = SlaLedger::default();
+    // A probe every few minutes all month: far past the sketch's exact samples
+    for i in 0..10_000 {
+        ledger.record(
+            "primary",
+            &NetworkStatus::Healthy,
+            1 + i % 1_000,
+            OCT_2026 + i as i64,
+        );
+    }
+    ledger.save(&path).await.unwrap();
+
+    let loaded = SlaLedger::load(&path).await;
+    assert_eq!(loaded, ledger);
+    let p95 = loaded.endpoints["primary"]["2026-10"]
+        .p95_latency_ms()
+        .unwrap() as f64;
+    assert!(
+        (p95 - 950.0).abs() <= 950.0 * SKETCH_RELATIVE_ACCURACY + 1.0,
+        "{}",
+        p95
+    );
+}
+
+#[tokio::test]
+async fn test_tracker_records_probes_into_ledger() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("sla.json");
+    let tracker = SlaTracker::new("relay", path.clone());
+
+    let mut state = MonitoringSnapshot {
+        status: NetworkStatus::Healthy,
+        ..Default::default()
+    };
+    state.network.latency_ms = 320;
+    for _ in 0..2 {
+        tracker
+            .on_probe(&StatusChange {
+                previous: &NetworkStatus::Healthy,
+                state: &state,
+                status_secs: 0,
+            })
+            .await
+            .unwrap();
+    }
+    state.status = NetworkStatus::Unknown;
+    tracker
+        .on_probe(&StatusChange {
+            previous: &NetworkStatus::Healthy,
+            state: &state,
+            status_secs: 0,
+        })
+        .await
+        .unwrap();
+
+    let ledger = SlaLedger::load(&path).await;
+    let months = &ledger.endpoints["relay"];
+    assert_eq!(months.len(), 1);
+    let stats = months.values().next().unwrap();
+    assert_eq!((stats.probes, stats.up), (2, 2));
+    assert_eq!(stats.p95_latency_ms(), Some(320));
+}
@@ -0,0 +1,231 @@
+#![cfg(feature = "network-monitoring")]
+
+//! Tests for warm-standby region measurement (`[network.standby]`)
+
+use ccstatus::config::{HostPolicy, StandbyConfig, StandbyRegion};
+use ccstatus::core::network::standby::{
+    HeadClient, RegionSample, StandbyMonitor, StandbyState, MAX_REGION_SAMPLES,
+};
+use ccstatus::core::network::StatusRenderer;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tempfile::TempDir;
+
+const NOW: i64 = 1_760_000_000;
+
+/// HEAD client answering from a URL → (status, latency) table and recording calls
+#[derive(Clone, Default)]
+struct TableHeadClient {
+    answers: HashMap<String, (u16, u64)>,
+    calls: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait::async_trait]
+impl HeadClient for TableHeadClient {
+    async fn head(&self, url: String, _timeout_ms: u32) -> Result<(u16, Duration), String> {
+        self.calls.lock().unwrap().push(url.clone());
+        match self.answers.get(&url) {
+            Some(&(status, ms)) => Ok((status, Duration::from_millis(ms))),
+            None => Err("Connection refused".to_string()),
+        }
+    }
+}
+
+fn region(name: &str, url: &str) -> StandbyRegion {
+    StandbyRegion {
+        name: name.to_string(),
+        url: url.to_string(),
+    }
+}
+
+fn monitor(
+    temp_dir: &TempDir,
+    regions: Vec<StandbyRegion>,
+    client: &TableHeadClient,
+) -> StandbyMonitor {
+    StandbyMonitor::from_config(&StandbyConfig {
+        interval_secs: 3600,
+        regions,
+    })
+    .unwrap()
+    .with_client(Box::new(client.clone()))
+    .with_path(temp_dir.path().join("standby-regions.json"))
+}
+
+fn sample(at: i64, http_status: u16, latency_ms: u32) -> RegionSample {
+    RegionSample {
+        at,
+        http_status,
+        latency_ms,
+    }
+}
+
+#[tokio::test]
+async fn test_measure_records_every_region() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut client = TableHeadClient::default();
+    client
+        .answers
+        .insert("https://eu.example.com".to_string(), (401, 180));
+    client
+        .answers
+        .insert("https://us.example.com".to_string(), (503, 90));
+
+    let regions = vec![
+        region("eu", "https://eu.example.com"),
+        region("us", "https://us.example.com"),
+        region("ap", "https://ap.example.com"),
+    ];
+    let measured = monitor(&temp_dir, regions, &client)
+        .measure()
+        .await
+        .unwrap();
+    assert_eq!(measured, 3);
+
+    let state = StandbyState::load(&temp_dir.path().join("standby-regions.json")).await;
+    assert!(state.last_run_at.is_some());
+    let last = |name: &str| state.regions[name].samples.last().unwrap().clone();
+    assert_eq!(last("eu").http_status, 401);
+    assert_eq!(last("eu").latency_ms, 180);
+    assert!(
+        last("eu").is_reachable(),
+        "An anonymous 401 proves the path works"
+    );
+    assert!(!last("us").is_reachable());
+    assert_eq!(
+        last("ap").http_status,
+        0,
+        "Connection errors record status 0"
+    );
+}
+
+#[tokio::test]
+async fn test_measure_if_due_honours_interval() {
+    let temp_dir = TempDir::new().unwrap();
+    let client = TableHeadClient::default();
+    let monitor = monitor(
+        &temp_dir,
+        vec![region("eu", "https://eu.example.com")],
+        &client,
+    );
+
+    assert_eq!(monitor.measure_if_due().await.unwrap(), Some(1));
+    assert_eq!(monitor.measure_if_due().await.unwrap(), None);
+    assert_eq!(client.calls.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_measure_respects_host_policy_and_prunes_removed_regions() {
+    let temp_dir = TempDir::new().unwrap();
+    let client = TableHeadClient::default();
+
+    monitor(
+        &temp_dir,
+        vec![
+            region("eu", "https://eu.example.com"),
+            region("old", "https://old.example.com"),
+        ],
+        &client,
+    )
+    .measure()
+    .await
+    .unwrap();
+
+    let measured = monitor(
+        &temp_dir,
+        vec![
+            region("eu", "https://eu.example.com"),
+            region("blocked", "https://blocked.example.org"),
+        ],
+        &client,
+    )
+    .with_host_policy(HostPolicy::new(vec!["*.example.com".to_string()]))
+    .measure()
+    .await
+    .unwrap();
+    assert_eq!(measured, 1);
+
+    let state = StandbyState::load(&temp_dir.path().join("standby-regions.json")).await;
+    assert_eq!(
+        state.regions.keys().collect::<Vec<_>>(),
+        vec!["eu"],
+        "Removed and blocked regions leave no history"
+    );
+    assert_eq!(state.regions["eu"].samples.len(), 2);
+    assert!(!client
+        .calls
+        .lock()
+        .unwrap()
+        .contains(&"https://blocked.example.org".to_string()));
+}
+
+#[test]
+fn test_history_is_capped_and_reset_on_url_change() {
+    let mut state = StandbyState::default();
+    let eu = region("eu", "https://eu.example.com");
+    for i in 0..(MAX_REGION_SAMPLES as i64 + 5) {
+        state.record(&eu, sample(NOW + i, 200, 100));
+    }
+    assert_eq!(state.regions["eu"].samples.len(), MAX_REGION_SAMPLES);
+    assert_eq!(state.regions["eu"].samples[0].at, NOW + 5, "Oldest dropped");
+
+    state.record(
+        &region("eu", "https://eu2.example.com"),
+        sample(NOW, 200, 50),
+    );
+    assert_eq!(state.regions["eu"].samples.len(), 1);
+    assert_eq!(state.regions["eu"].url, "https://eu2.example.com");
+}
+
+#[test]
+fn test_summarize_uses_reachable_samples() {
+    let mut state = StandbyState::default();
+    let eu = region("eu", "https://eu.example.com");
+    state.record(&eu, sample(NOW, 200, 100));
+    state.record(&eu, sample(NOW + 1, 0, 0));
+    state.record(&eu, sample(NOW + 2, 200, 300));
+
+    let summaries = state.summarize();
+    assert_eq!(summaries.len(), 1);
+    assert_eq!(summaries[0].samples, 3);
+    assert_eq!(summaries[0].reachable, 2);
+    assert_eq!(summaries[0].p50_latency_ms, 100);
+    assert_eq!(summaries[0].p95_latency_ms, 300);
+    assert_eq!(summaries[0].last.at, NOW + 2);
+}
+
+#[test]
+fn test_render_region_report() {
+    let renderer = StatusRenderer::new();
+    assert_eq!(
+        renderer.render_region_report(&[], NOW),
+        "No standby regions measured yet"
+    );
+
+    let mut state = StandbyState::default();
+    state.record(
+        &region("slow", "https://slow.example.com"),
+        sample(NOW - 7200, 200, 400),
+    );
+    state.record(
+        &region("down", "https://down.example.com"),
+        sample(NOW - 60, 0, 0),
+    );
+    state.record(
+        &region("fast", "https://fast.example.com"),
+        sample(NOW - 30, 200, 90),
+    );
+
+    let report = renderer.render_region_report(&state.summarize(), NOW);
+    let names: Vec<&str> = report
+        .lines()
+        .skip(1)
+        .map(|line| line.split_whitespace().next().unwrap())
+        .collect();
+    assert_eq!(names, vec!["fast", "slow", "down"], "{}", report);
+    assert!(report.contains("90ms 30s ago"), "{}", report);
+    assert!(report.contains("400ms 2h ago"), "{}", report);
+    assert!(report.contains("down 1m ago"), "{}", report);
+    assert!(report.contains("https://fast.example.com"));
+}
@@ -0,0 +1,222 @@
+#![cfg(feature = "state-sync")]
+
+use ccstatus::config::{SyncBackendKind, SyncConfig};
+use ccstatus::core::network::state_sync::{
+    decrypt, encrypt, sigv4_headers, MachineHistory, StateSync, SyncBackend, SyncDocument,
+};
+use ccstatus::core::network::types::{NetworkError, ProbeLedger};
+use ccstatus::core::network::HttpMonitor;
+use smallvec::smallvec;
+use std::sync::{Arc, Mutex};
+use tempfile::TempDir;
+
+/// In-memory backend shared between simulated machines
+#[derive(Clone, Default)]
+struct MemoryBackend {
+    blob: Arc<Mutex<Option<Vec<u8>>>>,
+}
+
+#[async_trait::async_trait]
+impl SyncBackend for MemoryBackend {
+    async fn fetch(&self) -> Result<Option<Vec<u8>>, NetworkError> {
+        Ok(self.blob.lock().unwrap().clone())
+    }
+
+    async fn store(&self, blob: Vec<u8>) -> Result<(), NetworkError> {
+        *self.blob.lock().unwrap() = Some(blob);
+        Ok(())
+    }
+
+    fn object_url(&self) -> &str {
+        "https://dav.example.com/ccstatus-sync.bin"
+    }
+}
+
+fn history(updated_at: i64, totals: &[u32], probes: u64) -> MachineHistory {
+    MachineHistory {
+        updated_at,
+        rolling_totals: totals.to_vec(),
+        p95_latency_ms: totals.iter().copied().max().unwrap_or(0),
+        ledger: ProbeLedger { probes, errors: 0 },
+    }
+}
+
+fn document(entries: &[(&str, MachineHistory)]) -> SyncDocument {
+    SyncDocument {
+        machines: entries
+            .iter()
+            .map(|(id, history)| (id.to_string(), history.clone()))
+            .collect(),
+    }
+}
+
+#[test]
+fn test_encrypt_decrypt_round_trip() {
+    let blob = encrypt("correct horse", b"{\"machines\":{}}").unwrap();
+    assert!(blob.starts_with(b"CCS1"));
+    assert!(!blob
+        .windows(b"machines".len())
+        .any(|window| window == b"machines"));
+
+    assert_eq!(
+        decrypt("correct horse", &blob).unwrap(),
+        b"{\"machines\":{}}"
+    );
+    assert!(decrypt("wrong passphrase", &blob).is_err());
+    assert!(decrypt("correct horse", b"plain text").is_err());
+}
+
+#[test]
+fn test_merge_is_last_writer_wins_with_max_ledger() {
+    let older = history(100, &[900, 1000], 40);
+    let newer = history(200, &[1200], 35);
+
+    let merged = older.merge(&newer);
+    assert_eq!(merged.updated_at, 200);
+    assert_eq!(merged.rolling_totals, vec![1200]);
+    assert_eq!(merged.ledger.probes, 40);
+}
+
+#[test]
+fn test_document_merge_is_conflict_free() {
+    let a = document(&[
+        ("desktop", history(100, &[900], 10)),
+        ("laptop", history(50, &[2000], 3)),
+    ]);
+    let b = document(&[
+        ("laptop", history(150, &[1800], 5)),
+        ("server", history(120, &[700], 1)),
+    ]);
+    let c = document(&[("desktop", history(100, &[950], 12))]);
+
+    // Commutative, associative, idempotent
+    assert_eq!(a.merge(&b), b.merge(&a));
+    assert_eq!(a.merge(&b).merge(&c), a.merge(&b.merge(&c)));
+    assert_eq!(a.merge(&a), a);
+
+    let merged = a.merge(&b);
+    assert_eq!(merged.machines.len(), 3);
+    assert_eq!(merged.machines["laptop"].rolling_totals, vec![1800]);
+}
+
+#[test]
+fn test_shared_baseline_excludes_self_and_stale_machines() {
+    let now = 1_000_000;
+    let doc = document(&[
+        ("desktop", history(now - 60, &[900, 950], 10)),
+        ("laptop", history(now - 30, &[1800], 5)),
+        ("old", history(now - 3 * 24 * 60 * 60, &[5000], 5)),
+    ]);
+
+    let (totals, machines) = doc.shared_baseline("desktop", now).unwrap();
+    assert_eq!(totals, vec![1800]);
+    assert_eq!(machines, 1);
+
+    let lonely = document(&[("desktop", history(now, &[900], 1))]);
+    assert!(lonely.shared_baseline("desktop", now).is_none());
+}
+
+#[test]
+fn test_sigv4_headers_shape() {
+    let headers = sigv4_headers(
+        "PUT",
+        "https://s3.example.com:9000/bucket/ccstatus-sync.bin",
+        "us-east-1",
+        "AKIDEXAMPLE",
+        "secret",
+        b"payload",
+        "20250125T103000Z",
+    )
+    .unwrap();
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.clone())
+            .unwrap()
+    };
+
+    assert_eq!(header("host"), "s3.example.com:9000");
+    assert_eq!(header("x-amz-date"), "20250125T103000Z");
+    let authorization = header("authorization");
+    assert!(authorization.starts_with(
+        "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20250125/us-east-1/s3/aws4_request, \
+         SignedHeaders=host;x-amz-content-sha256;x-amz-date, Signature="
+    ));
+    let signature = authorization.rsplit('=').next().unwrap();
+    assert_eq!(signature.len(), 64);
+
+    // Signing is deterministic for identical inputs
+    let again = sigv4_headers(
+        "PUT",
+        "https://s3.example.com:9000/bucket/ccstatus-sync.bin",
+        "us-east-1",
+        "AKIDEXAMPLE",
+        "secret",
+        b"payload",
+        "20250125T103000Z",
+    )
+    .unwrap();
+    assert_eq!(headers, again);
+}
+
+#[tokio::test]
+async fn test_sync_shares_baseline_between_machines() {
+    std::env::set_var("CCSTATUS_TEST_SYNC_PASSPHRASE", "shared secret");
+    let config = SyncConfig {
+        backend: SyncBackendKind::Webdav,
+        url: "https://dav.example.com".to_string(),
+        object: "ccstatus-sync.bin".to_string(),
+        region: "us-east-1".to_string(),
+        passphrase_env: "CCSTATUS_TEST_SYNC_PASSPHRASE".to_string(),
+        interval_secs: 900,
+        ..Default::default()
+    };
+    let backend = MemoryBackend::default();
+
+    let desktop_dir = TempDir::new().unwrap();
+    let laptop_dir = TempDir::new().unwrap();
+    let machine = |dir: &TempDir| {
+        StateSync::from_config(&config)
+            .unwrap()
+            .with_backend(Box::new(backend.clone()))
+            .with_http_monitor(HttpMonitor::new(Some(dir.path().join("monitoring.json"))).unwrap())
+            .with_meta_path(dir.path().join("sync-meta.json"))
+    };
+
+    // Desktop publishes its window
+    let desktop_monitor =
+        HttpMonitor::new(Some(desktop_dir.path().join("monitoring.json"))).unwrap();
+    let mut state = desktop_monitor.load_state().await.unwrap();
+    state.network.rolling_totals = smallvec![800, 900, 1000];
+    std::fs::write(
+        desktop_dir.path().join("monitoring.json"),
+        serde_json::to_string(&state).unwrap(),
+    )
+    .unwrap();
+
+    let report = machine(&desktop_dir).sync().await.unwrap();
+    assert_eq!(report.machines, 1);
+    assert_eq!(report.shared_samples, 0);
+
+    // Laptop receives the desktop window as shared baseline
+    let report = machine(&laptop_dir).sync().await.unwrap();
+    assert_eq!(report.machines, 2);
+    assert_eq!(report.shared_samples, 3);
+
+    let laptop_state = HttpMonitor::new(Some(laptop_dir.path().join("monitoring.json")))
+        .unwrap()
+        .load_state()
+        .await
+        .unwrap();
+    let shared = laptop_state.shared_baseline.expect("baseline shared");
+    assert_eq!(shared.totals, vec![800, 900, 1000]);
+    assert_eq!(shared.machines, 1);
+
+    // Not due again until the interval elapses
+    assert!(machine(&laptop_dir).sync_if_due().await.unwrap().is_none());
+
+    // The stored blob is opaque without the passphrase
+    let blob = backend.blob.lock().unwrap().clone().unwrap();
+    assert!(decrypt("other", &blob).is_err());
+}
@@ -0,0 +1,95 @@
+use ccstatus::core::network::status_expression::{StatusExpression, StatusMetrics};
+use ccstatus::core::network::types::{NetworkError, NetworkStatus};
+
+fn metrics(consecutive_failures: u32, p95: u32) -> StatusMetrics {
+    StatusMetrics {
+        latency_ms: 1200,
+        p95,
+        http_status: 200,
+        consecutive_failures,
+        rolling_len: 6,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_chained_conditions() {
+    let expression = StatusExpression::parse(
+        "error if consecutive_failures>2 else degraded if p95>2500 else healthy",
+    )
+    .unwrap();
+    let default = NetworkStatus::Unknown;
+
+    assert_eq!(
+        expression.evaluate(&metrics(3, 1000), &default),
+        NetworkStatus::Error
+    );
+    assert_eq!(
+        expression.evaluate(&metrics(0, 3000), &default),
+        NetworkStatus::Degraded
+    );
+    assert_eq!(
+        expression.evaluate(&metrics(2, 2500), &default),
+        NetworkStatus::Healthy
+    );
+}
+
+#[test]
+fn test_default_keyword_keeps_builtin_status() {
+    let expression =
+        StatusExpression::parse("error if consecutive_failures >= 3 else default").unwrap();
+    assert_eq!(
+        expression.evaluate(&metrics(1, 1000), &NetworkStatus::Degraded),
+        NetworkStatus::Degraded
+    );
+    assert_eq!(
+        expression.evaluate(&metrics(3, 1000), &NetworkStatus::Healthy),
+        NetworkStatus::Error
+    );
+}
+
+#[test]
+fn test_boolean_operators_and_grouping() {
+    let expression = StatusExpression::parse(
+        "Degraded IF (p95 > 2000 or latency_ms > 5000) and not http_status != 200 else healthy",
+    )
+    .unwrap();
+    let default = NetworkStatus::Unknown;
+
+    assert_eq!(
+        expression.evaluate(&metrics(0, 2100), &default),
+        NetworkStatus::Degraded
+    );
+    let failed = StatusMetrics {
+        http_status: 529,
+        ..metrics(0, 2100)
+    };
+    assert_eq!(
+        expression.evaluate(&failed, &default),
+        NetworkStatus::Healthy
+    );
+}
+
+#[test]
+fn test_parse_errors() {
+    for source in [
+        "",
+        "healthy if",
+        "error if p95 > 100",
+        "error if bogus > 1 else healthy",
+        "fine",
+        "error if p95 => 1 else healthy",
+        "healthy extra",
+        "error if (p95 > 1 else healthy",
+    ] {
+        let result = StatusExpression::parse(source);
+        assert!(
+            matches!(result, Err(NetworkError::ConfigParseError(_))),
+            "expected parse error for {:?}",
+            source
+        );
+    }
+
+    let error = StatusExpression::parse("error if bogus > 1 else healthy").unwrap_err();
+    assert!(error.to_string().contains("unknown metric 'bogus'"));
+}
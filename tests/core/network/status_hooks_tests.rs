@@ -0,0 +1,218 @@
+#![cfg(feature = "network-monitoring")]
+
+//! Tests for status hooks and the pause advisory (`[network.pause_advisory]`)
+
+use ccstatus::config::PauseAdvisoryConfig;
+use ccstatus::core::network::status_hooks::{Advisory, PauseAdvisory, StatusChange, StatusHook};
+use ccstatus::core::network::*;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tempfile::TempDir;
+
+fn error_state(since: &str) -> MonitoringSnapshot {
+    let mut state = MonitoringSnapshot {
+        status: NetworkStatus::Error,
+        timestamp: "2025-01-25T10:35:00-08:00".to_string(),
+        ..Default::default()
+    };
+    state.monitoring_state.status_since = Some(since.to_string());
+    state.network.last_http_status = 529;
+    state.network.error_type = Some("overloaded_error".to_string());
+    state
+}
+
+#[tokio::test]
+async fn test_advisory_written_only_after_threshold() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("advice").join("pause-advisory.json");
+    let advisory = PauseAdvisory::new(path.clone(), 300);
+    let state = error_state("2025-01-25T10:30:00-08:00");
+
+    let mut change = StatusChange {
+        previous: &NetworkStatus::Error,
+        state: &state,
+        status_secs: 299,
+    };
+    advisory.on_probe(&change).await.unwrap();
+    assert!(!path.exists(), "Short errors leave no advisory");
+
+    change.status_secs = 300;
+    advisory.on_probe(&change).await.unwrap();
+    let written: Advisory = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(written.status, "error");
+    assert_eq!(written.since, "2025-01-25T10:30:00-08:00");
+    assert_eq!(written.updated_at, "2025-01-25T10:35:00-08:00");
+    assert_eq!(written.http_status, 529);
+    assert_eq!(written.error_type.as_deref(), Some("overloaded_error"));
+}
+
+#[tokio::test]
+async fn test_advisory_removed_on_recovery() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("pause-advisory.json");
+    let advisory = PauseAdvisory::new(path.clone(), 0);
+
+    let error = error_state("2025-01-25T10:30:00-08:00");
+    advisory
+        .on_probe(&StatusChange {
+            previous: &NetworkStatus::Healthy,
+            state: &error,
+            status_secs: 0,
+        })
+        .await
+        .unwrap();
+    assert!(path.exists());
+
+    let degraded = MonitoringSnapshot {
+        status: NetworkStatus::Degraded,
+        ..Default::default()
+    };
+    let change = StatusChange {
+        previous: &NetworkStatus::Error,
+        state: &degraded,
+        status_secs: 0,
+    };
+    assert!(change.is_transition());
+    advisory.on_probe(&change).await.unwrap();
+    assert!(!path.exists(), "Any non-error status clears the advisory");
+
+    // Nothing to remove is not an error
+    advisory.on_probe(&change).await.unwrap();
+}
+
+#[test]
+fn test_from_config_paths() {
+    let home = dirs::home_dir().unwrap();
+    let default = PauseAdvisory::from_config(&PauseAdvisoryConfig::default()).unwrap();
+    assert_eq!(
+        default.path(),
+        home.join(".claude/ccstatus/pause-advisory.json")
+    );
+
+    let tilde = PauseAdvisory::from_config(&PauseAdvisoryConfig {
+        after_secs: 60,
+        path: Some("~/pause.json".to_string()),
+    })
+    .unwrap();
+    assert_eq!(tilde.path(), home.join("pause.json"));
+
+    let absolute = PauseAdvisory::from_config(&PauseAdvisoryConfig {
+        after_secs: 60,
+        path: Some("/tmp/pause.json".to_string()),
+    })
+    .unwrap();
+    assert_eq!(absolute.path(), std::path::Path::new("/tmp/pause.json"));
+}
+
+#[test]
+fn test_pause_advisory_config_defaults() {
+    let config: ccstatus::config::NetworkConfig =
+        toml::from_str("[pause_advisory]\npath = \"~/p.json\"\n").unwrap();
+    let advisory = config.pause_advisory.unwrap();
+    assert_eq!(advisory.after_secs, 300);
+    assert_eq!(advisory.path.as_deref(), Some("~/p.json"));
+}
+
+/// HTTP client answering every probe with a fixed status
+struct FixedStatusClient(u16);
+
+#[async_trait::async_trait]
+impl HttpClientTrait for FixedStatusClient {
+    async fn execute_request(
+        &self,
+        _url: String,
+        _headers: HashMap<String, String>,
+        _body: Vec<u8>,
+        _timeout_ms: u32,
+    ) -> Result<
+        (
+            u16,
+            Duration,
+            String,
+            HashMap<String, String>,
+            Option<String>,
+        ),
+        String,
+    > {
+        Ok((
+            self.0,
+            Duration::from_millis(100),
+            "Total:100ms".to_string(),
+            HashMap::new(),
+            None,
+        ))
+    }
+}
+
+/// Hook recording (previous, current, transition) for each probe
+#[derive(Clone, Default)]
+struct RecordingHook {
+    calls: Arc<Mutex<Vec<(NetworkStatus, NetworkStatus, bool)>>>,
+}
+
+#[async_trait::async_trait]
+impl StatusHook for RecordingHook {
+    fn name(&self) -> &'static str {
+        "recording"
+    }
+
+    async fn on_probe(&self, change: &StatusChange<'_>) -> Result<(), NetworkError> {
+        self.calls.lock().unwrap().push((
+            change.previous.clone(),
+            change.state.status.clone(),
+            change.is_transition(),
+        ));
+        Err(NetworkError::HttpError(
+            "hook failures are ignored".to_string(),
+        ))
+    }
+}
+
+async fn probe(temp_dir: &TempDir, status: u16, hook: &RecordingHook) -> MonitoringSnapshot {
+    let state_path = temp_dir.path().join("monitoring.json");
+    let monitor = HttpMonitor::new(Some(state_path.clone()))
+        .unwrap()
+        .with_http_client(Box::new(FixedStatusClient(status)))
+        .with_status_hook(Box::new(hook.clone()));
+    #[cfg(feature = "timings-curl")]
+    let monitor = monitor.without_curl_runner();
+    let mut monitor = monitor;
+
+    let credentials = ApiCredentials {
+        base_url: "https://api.example.com".to_string(),
+        auth_token: "test-token".to_string(),
+        source: CredentialSource::Environment,
+        expires_at: None,
+    };
+    monitor
+        .probe(ProbeMode::Red, credentials, None)
+        .await
+        .unwrap();
+    serde_json::from_str(&std::fs::read_to_string(state_path).unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn test_monitor_runs_hooks_and_tracks_status_since() {
+    let temp_dir = TempDir::new().unwrap();
+    let hook = RecordingHook::default();
+
+    let first = probe(&temp_dir, 500, &hook).await;
+    assert_eq!(first.status, NetworkStatus::Error);
+    let since = first.monitoring_state.status_since.clone();
+    assert_eq!(since.as_deref(), Some(first.timestamp.as_str()));
+
+    let second = probe(&temp_dir, 500, &hook).await;
+    assert_eq!(
+        second.monitoring_state.status_since, since,
+        "Unchanged status keeps its start time"
+    );
+
+    assert_eq!(
+        *hook.calls.lock().unwrap(),
+        vec![
+            (NetworkStatus::Unknown, NetworkStatus::Error, true),
+            (NetworkStatus::Error, NetworkStatus::Error, false),
+        ]
+    );
+}
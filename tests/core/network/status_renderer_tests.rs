@@ -1,5 +1,8 @@
+use ccstatus::core::icons::IconSet;
+use ccstatus::core::network::proxy_health::ProxyHealthLevel;
 use ccstatus::core::network::types::{NetworkMetrics, NetworkStatus};
 use ccstatus::core::network::StatusRenderer;
+use smallvec::smallvec;
 
 #[test]
 fn test_status_renderer_creation() {
@@ -16,7 +19,8 @@ fn test_healthy_status_rendering() {
         breakdown: "DNS:5ms|TCP:10ms|TLS:15ms|TTFB:120ms|Total:150ms".to_string(),
         last_http_status: 200,
         error_type: None,
-        rolling_totals: vec![100, 120, 150],
+        rolling_totals: smallvec![100, 120, 150],
+        rolling_sampled_at: smallvec![],
         p95_latency_ms: 145,
         breakdown_source: None,
         connection_reused: None,
@@ -24,6 +28,11 @@ fn test_healthy_status_rendering() {
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: None,
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     let result = renderer.render_status(&NetworkStatus::Healthy, &metrics, None);
@@ -42,7 +51,8 @@ fn test_degraded_status_rendering() {
         breakdown: "DNS:10ms|TCP:20ms|TLS:30ms|TTFB:740ms|Total:800ms".to_string(),
         last_http_status: 200,
         error_type: Some("HighLatency".to_string()),
-        rolling_totals: vec![600, 700, 800],
+        rolling_totals: smallvec![600, 700, 800],
+        rolling_sampled_at: smallvec![],
         p95_latency_ms: 750,
         breakdown_source: None,
         connection_reused: None,
@@ -50,6 +60,11 @@ fn test_degraded_status_rendering() {
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: None,
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     let result = renderer.render_status(&NetworkStatus::Degraded, &metrics, None);
@@ -71,7 +86,8 @@ fn test_degraded_rate_limit_rendering() {
         breakdown: "Total:200ms".to_string(),
         last_http_status: 429,
         error_type: Some("RateLimit".to_string()),
-        rolling_totals: vec![150, 180, 200],
+        rolling_totals: smallvec![150, 180, 200],
+        rolling_sampled_at: smallvec![],
         p95_latency_ms: 190,
         breakdown_source: None,
         connection_reused: None,
@@ -79,6 +95,11 @@ fn test_degraded_rate_limit_rendering() {
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: None,
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     let result = renderer.render_status(&NetworkStatus::Degraded, &metrics, None);
@@ -100,7 +121,8 @@ fn test_error_status_rendering() {
         breakdown: "Total:1500ms|Error:Timeout".to_string(),
         last_http_status: 500,
         error_type: Some("ServerError".to_string()),
-        rolling_totals: vec![1200, 1300, 1500],
+        rolling_totals: smallvec![1200, 1300, 1500],
+        rolling_sampled_at: smallvec![],
         p95_latency_ms: 1400,
         breakdown_source: None,
         connection_reused: None,
@@ -108,6 +130,11 @@ fn test_error_status_rendering() {
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: None,
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     let result = renderer.render_status(&NetworkStatus::Error, &metrics, None);
@@ -129,7 +156,8 @@ fn test_error_timeout_rendering() {
         breakdown: "Total:3000ms".to_string(),
         last_http_status: 0, // Timeout
         error_type: None,
-        rolling_totals: vec![2000, 2500, 3000],
+        rolling_totals: smallvec![2000, 2500, 3000],
+        rolling_sampled_at: smallvec![],
         p95_latency_ms: 2800,
         breakdown_source: None,
         connection_reused: None,
@@ -137,6 +165,11 @@ fn test_error_timeout_rendering() {
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: None,
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     let result = renderer.render_status(&NetworkStatus::Error, &metrics, None);
@@ -157,7 +190,8 @@ fn test_error_http_status_rendering() {
         breakdown: "DNS:5ms|TCP:10ms|TLS:15ms|TTFB:470ms|Total:500ms".to_string(),
         last_http_status: 404,
         error_type: Some("ClientError".to_string()),
-        rolling_totals: vec![400, 450, 500],
+        rolling_totals: smallvec![400, 450, 500],
+        rolling_sampled_at: smallvec![],
         p95_latency_ms: 475,
         breakdown_source: None,
         connection_reused: None,
@@ -165,6 +199,11 @@ fn test_error_http_status_rendering() {
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: None,
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     let result = renderer.render_status(&NetworkStatus::Error, &metrics, None);
@@ -185,7 +224,8 @@ fn test_unknown_status_rendering() {
         breakdown: "".to_string(),
         last_http_status: 0,
         error_type: None,
-        rolling_totals: vec![],
+        rolling_totals: smallvec![],
+        rolling_sampled_at: smallvec![],
         p95_latency_ms: 0,
         breakdown_source: None,
         connection_reused: None,
@@ -193,6 +233,11 @@ fn test_unknown_status_rendering() {
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: None,
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     let result = renderer.render_status(&NetworkStatus::Unknown, &metrics, None);
@@ -210,7 +255,8 @@ fn test_empty_breakdown_handling() {
         breakdown: "".to_string(), // Empty breakdown
         last_http_status: 200,
         error_type: Some("TestError".to_string()),
-        rolling_totals: vec![180, 190, 200],
+        rolling_totals: smallvec![180, 190, 200],
+        rolling_sampled_at: smallvec![],
         p95_latency_ms: 195,
         breakdown_source: None,
         connection_reused: None,
@@ -218,6 +264,11 @@ fn test_empty_breakdown_handling() {
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: None,
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     let result = renderer.render_status(&NetworkStatus::Degraded, &metrics, None);
@@ -239,7 +290,8 @@ fn test_no_error_type_handling() {
         breakdown: "Total:300ms".to_string(),
         last_http_status: 200,
         error_type: None, // No error type
-        rolling_totals: vec![250, 275, 300],
+        rolling_totals: smallvec![250, 275, 300],
+        rolling_sampled_at: smallvec![],
         p95_latency_ms: 285,
         breakdown_source: None,
         connection_reused: None,
@@ -247,6 +299,11 @@ fn test_no_error_type_handling() {
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: None,
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     let result = renderer.render_status(&NetworkStatus::Degraded, &metrics, None);
@@ -268,7 +325,8 @@ fn test_edge_case_zero_p95() {
         breakdown: "Total:100ms".to_string(),
         last_http_status: 200,
         error_type: None,
-        rolling_totals: vec![100],
+        rolling_totals: smallvec![100],
+        rolling_sampled_at: smallvec![],
         p95_latency_ms: 0, // Zero P95 (not enough samples)
         breakdown_source: None,
         connection_reused: None,
@@ -276,6 +334,11 @@ fn test_edge_case_zero_p95() {
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: None,
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     let result = renderer.render_status(&NetworkStatus::Healthy, &metrics, None);
@@ -294,7 +357,8 @@ fn test_very_high_latencies() {
         breakdown: "Total:9999ms".to_string(),
         last_http_status: 200,
         error_type: None,
-        rolling_totals: vec![8000, 9000, 9999],
+        rolling_totals: smallvec![8000, 9000, 9999],
+        rolling_sampled_at: smallvec![],
         p95_latency_ms: 9500,
         breakdown_source: None,
         connection_reused: None,
@@ -302,6 +366,11 @@ fn test_very_high_latencies() {
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: None,
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     let result = renderer.render_status(&NetworkStatus::Healthy, &metrics, None);
@@ -320,7 +389,8 @@ fn test_special_characters_in_error_type() {
         breakdown: "Total:500ms".to_string(),
         last_http_status: 500,
         error_type: Some("Server-Error_With.Special&Chars".to_string()),
-        rolling_totals: vec![400, 450, 500],
+        rolling_totals: smallvec![400, 450, 500],
+        rolling_sampled_at: smallvec![],
         p95_latency_ms: 475,
         breakdown_source: None,
         connection_reused: None,
@@ -328,6 +398,11 @@ fn test_special_characters_in_error_type() {
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: None,
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     let result = renderer.render_status(&NetworkStatus::Error, &metrics, None);
@@ -350,7 +425,8 @@ fn test_long_breakdown_strings() {
         breakdown: long_breakdown.to_string(),
         last_http_status: 200,
         error_type: Some("HighLatency".to_string()),
-        rolling_totals: vec![2000, 2250, 2500],
+        rolling_totals: smallvec![2000, 2250, 2500],
+        rolling_sampled_at: smallvec![],
         p95_latency_ms: 2400,
         breakdown_source: None,
         connection_reused: None,
@@ -358,6 +434,11 @@ fn test_long_breakdown_strings() {
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: None,
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     let result = renderer.render_status(&NetworkStatus::Degraded, &metrics, None);
@@ -416,7 +497,8 @@ fn test_line_wrapping_behavior() {
         breakdown: long_breakdown.to_string(),
         last_http_status: 200,
         error_type: None,
-        rolling_totals: vec![3000, 3100, 3200],
+        rolling_totals: smallvec![3000, 3100, 3200],
+        rolling_sampled_at: smallvec![],
         p95_latency_ms: 3100,
         breakdown_source: None,
         connection_reused: None,
@@ -424,6 +506,11 @@ fn test_line_wrapping_behavior() {
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: None,
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     let result = renderer.render_status(&NetworkStatus::Degraded, &metrics, None);
@@ -453,7 +540,8 @@ fn test_no_line_wrapping_for_short_content() {
         breakdown: short_breakdown.to_string(),
         last_http_status: 200,
         error_type: None,
-        rolling_totals: vec![120, 135, 150],
+        rolling_totals: smallvec![120, 135, 150],
+        rolling_sampled_at: smallvec![],
         p95_latency_ms: 145,
         breakdown_source: None,
         connection_reused: None,
@@ -461,6 +549,11 @@ fn test_no_line_wrapping_for_short_content() {
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: None,
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     let result = renderer.render_status(&NetworkStatus::Degraded, &metrics, None);
@@ -484,7 +577,8 @@ fn test_zero_p95_in_degraded_status() {
         breakdown: "Total:200ms".to_string(),
         last_http_status: 200,
         error_type: None,
-        rolling_totals: vec![200],
+        rolling_totals: smallvec![200],
+        rolling_sampled_at: smallvec![],
         p95_latency_ms: 0, // Zero P95 (insufficient samples)
         breakdown_source: None,
         connection_reused: None,
@@ -492,6 +586,11 @@ fn test_zero_p95_in_degraded_status() {
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: None,
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     let result = renderer.render_status(&NetworkStatus::Degraded, &metrics, None);
@@ -512,7 +611,8 @@ fn test_empty_breakdown_in_error_status() {
         breakdown: "".to_string(), // Empty breakdown
         last_http_status: 500,
         error_type: None,
-        rolling_totals: vec![900, 950, 1000],
+        rolling_totals: smallvec![900, 950, 1000],
+        rolling_sampled_at: smallvec![],
         p95_latency_ms: 980,
         breakdown_source: None,
         connection_reused: None,
@@ -520,6 +620,11 @@ fn test_empty_breakdown_in_error_status() {
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: None,
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     let result = renderer.render_status(&NetworkStatus::Error, &metrics, None);
@@ -541,7 +646,8 @@ fn test_proxy_healthy_prefix_none() {
         breakdown: "DNS:5ms|TCP:10ms|TLS:15ms|TTFB:120ms|Total:150ms".to_string(),
         last_http_status: 200,
         error_type: None,
-        rolling_totals: vec![100, 120, 150],
+        rolling_totals: smallvec![100, 120, 150],
+        rolling_sampled_at: smallvec![],
         p95_latency_ms: 145,
         connection_reused: None,
         breakdown_source: None,
@@ -549,6 +655,11 @@ fn test_proxy_healthy_prefix_none() {
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: None,
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     let result = renderer.render_status(&NetworkStatus::Healthy, &metrics, None);
@@ -567,7 +678,8 @@ fn test_proxy_healthy_prefix_true() {
         breakdown: "DNS:5ms|TCP:10ms|TLS:15ms|TTFB:120ms|Total:150ms".to_string(),
         last_http_status: 200,
         error_type: None,
-        rolling_totals: vec![100, 120, 150],
+        rolling_totals: smallvec![100, 120, 150],
+        rolling_sampled_at: smallvec![],
         p95_latency_ms: 145,
         connection_reused: None,
         breakdown_source: None,
@@ -575,6 +687,11 @@ fn test_proxy_healthy_prefix_true() {
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: None,
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     let result = renderer.render_status(&NetworkStatus::Healthy, &metrics, None);
@@ -593,7 +710,8 @@ fn test_proxy_unhealthy_prefix_false() {
         breakdown: "DNS:10ms|TCP:20ms|TLS:30ms|TTFB:740ms|Total:800ms".to_string(),
         last_http_status: 200,
         error_type: None,
-        rolling_totals: vec![600, 700, 800],
+        rolling_totals: smallvec![600, 700, 800],
+        rolling_sampled_at: smallvec![],
         p95_latency_ms: 750,
         connection_reused: None,
         breakdown_source: None,
@@ -601,6 +719,11 @@ fn test_proxy_unhealthy_prefix_false() {
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: None,
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     let result = renderer.render_status(&NetworkStatus::Degraded, &metrics, None);
@@ -620,7 +743,8 @@ fn test_proxy_healthy_with_error_status() {
         breakdown: "Total:1500ms|Error:Timeout".to_string(),
         last_http_status: 500,
         error_type: Some("ServerError".to_string()),
-        rolling_totals: vec![1200, 1300, 1500],
+        rolling_totals: smallvec![1200, 1300, 1500],
+        rolling_sampled_at: smallvec![],
         p95_latency_ms: 1400,
         connection_reused: None,
         breakdown_source: None,
@@ -628,6 +752,11 @@ fn test_proxy_healthy_with_error_status() {
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: None,
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     let result = renderer.render_status(&NetworkStatus::Error, &metrics, None);
@@ -645,7 +774,8 @@ fn test_proxy_unhealthy_with_unknown_status() {
         breakdown: "".to_string(),
         last_http_status: 0,
         error_type: None,
-        rolling_totals: vec![],
+        rolling_totals: smallvec![],
+        rolling_sampled_at: smallvec![],
         p95_latency_ms: 0,
         connection_reused: None,
         breakdown_source: None,
@@ -653,6 +783,11 @@ fn test_proxy_unhealthy_with_unknown_status() {
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: None,
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     let result = renderer.render_status(&NetworkStatus::Unknown, &metrics, None);
@@ -672,7 +807,8 @@ fn test_proxy_health_with_line_wrapping() {
         breakdown: long_breakdown.to_string(),
         last_http_status: 200,
         error_type: None,
-        rolling_totals: vec![3000, 3100, 3200],
+        rolling_totals: smallvec![3000, 3100, 3200],
+        rolling_sampled_at: smallvec![],
         p95_latency_ms: 3100,
         connection_reused: None,
         breakdown_source: None,
@@ -680,6 +816,11 @@ fn test_proxy_health_with_line_wrapping() {
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: None,
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     let result = renderer.render_status(&NetworkStatus::Degraded, &metrics, None);
@@ -702,7 +843,8 @@ fn test_bot_challenge_both_blocked() {
         breakdown: "DNS:25ms|TCP:30ms|TLS:35ms|TTFB:1410ms|Total:1500ms".to_string(),
         last_http_status: 429,
         error_type: Some("bot_challenge".to_string()),
-        rolling_totals: vec![1200, 1400, 1500],
+        rolling_totals: smallvec![1200, 1400, 1500],
+        rolling_sampled_at: smallvec![],
         p95_latency_ms: 1450,
         connection_reused: None,
         breakdown_source: None,
@@ -710,6 +852,11 @@ fn test_bot_challenge_both_blocked() {
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: None,
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     // Test the render_bot_challenge method directly through reflection or by triggering the right conditions
@@ -730,14 +877,20 @@ fn test_bot_challenge_get_only_with_zero_p95() {
         breakdown: "DNS:25ms|TCP:30ms|TLS:35ms|TTFB:710ms|Total:800ms".to_string(),
         last_http_status: 429,
         error_type: Some("bot_challenge".to_string()),
-        rolling_totals: vec![800], // Only one sample, so P95 should be 0
-        p95_latency_ms: 0,         // Zero P95 due to insufficient samples
+        rolling_totals: smallvec![800], // Only one sample, so P95 should be 0
+        rolling_sampled_at: smallvec![],
+        p95_latency_ms: 0, // Zero P95 due to insufficient samples
         connection_reused: None,
         breakdown_source: None,
         proxy_healthy: None,
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: None,
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     let result = renderer.render_status(&NetworkStatus::Error, &metrics, None);
@@ -757,7 +910,8 @@ fn test_bot_challenge_post_only_high_latency() {
         breakdown: "DNS:100ms|TCP:200ms|TLS:300ms|TTFB:9399ms|Total:9999ms".to_string(),
         last_http_status: 429,
         error_type: Some("bot_challenge".to_string()),
-        rolling_totals: vec![8000, 9000, 9999],
+        rolling_totals: smallvec![8000, 9000, 9999],
+        rolling_sampled_at: smallvec![],
         p95_latency_ms: 9500,
         connection_reused: None,
         breakdown_source: None,
@@ -765,6 +919,11 @@ fn test_bot_challenge_post_only_high_latency() {
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: None,
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     let result = renderer.render_status(&NetworkStatus::Error, &metrics, None);
@@ -783,7 +942,8 @@ fn test_bot_challenge_edge_case_empty_breakdown() {
         breakdown: "".to_string(), // Empty breakdown
         last_http_status: 429,
         error_type: Some("bot_challenge".to_string()),
-        rolling_totals: vec![1000, 1100, 1200],
+        rolling_totals: smallvec![1000, 1100, 1200],
+        rolling_sampled_at: smallvec![],
         p95_latency_ms: 1150,
         connection_reused: None,
         breakdown_source: None,
@@ -791,6 +951,11 @@ fn test_bot_challenge_edge_case_empty_breakdown() {
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: None,
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     let result = renderer.render_status(&NetworkStatus::Error, &metrics, None);
@@ -808,7 +973,8 @@ fn test_bot_challenge_neither_blocked_fallback() {
         breakdown: "Total:500ms".to_string(),
         last_http_status: 429,
         error_type: Some("bot_challenge".to_string()),
-        rolling_totals: vec![400, 450, 500],
+        rolling_totals: smallvec![400, 450, 500],
+        rolling_sampled_at: smallvec![],
         p95_latency_ms: 475,
         connection_reused: None,
         breakdown_source: None,
@@ -816,6 +982,11 @@ fn test_bot_challenge_neither_blocked_fallback() {
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: None,
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     let result = renderer.render_status(&NetworkStatus::Error, &metrics, None);
@@ -833,7 +1004,8 @@ fn test_shield_rendering_with_proxy_health_combination() {
         breakdown: "DNS:10ms|TCP:15ms|TLS:20ms|TTFB:555ms|Total:600ms".to_string(),
         last_http_status: 429,
         error_type: Some("bot_challenge".to_string()),
-        rolling_totals: vec![500, 550, 600],
+        rolling_totals: smallvec![500, 550, 600],
+        rolling_sampled_at: smallvec![],
         p95_latency_ms: 580,
         connection_reused: None,
         breakdown_source: None,
@@ -841,6 +1013,11 @@ fn test_shield_rendering_with_proxy_health_combination() {
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: None,
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     let result = renderer.render_status(&NetworkStatus::Error, &metrics, None);
@@ -860,7 +1037,8 @@ fn test_shield_minimal_latency_values() {
         breakdown: "Total:1ms".to_string(),
         last_http_status: 429,
         error_type: Some("bot_challenge".to_string()),
-        rolling_totals: vec![1],
+        rolling_totals: smallvec![1],
+        rolling_sampled_at: smallvec![],
         p95_latency_ms: 0, // Zero due to single sample
         connection_reused: None,
         breakdown_source: None,
@@ -868,6 +1046,11 @@ fn test_shield_minimal_latency_values() {
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: None,
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     let result = renderer.render_status(&NetworkStatus::Error, &metrics, None);
@@ -888,7 +1071,8 @@ fn test_post_bot_challenge_breakdown_suppression_with_timings_curl() {
         breakdown: "DNS:50ms|TCP:75ms|TLS:100ms|TTFB:2275ms|Total:2500ms".to_string(),
         last_http_status: 429,
         error_type: Some("bot_challenge".to_string()),
-        rolling_totals: vec![2000, 2200, 2500],
+        rolling_totals: smallvec![2000, 2200, 2500],
+        rolling_sampled_at: smallvec![],
         p95_latency_ms: 2400,
         connection_reused: Some(false), // Not reused - would show full timing details
         breakdown_source: Some("measured".to_string()), // From timings-curl
@@ -896,6 +1080,11 @@ fn test_post_bot_challenge_breakdown_suppression_with_timings_curl() {
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: Some("HTTP/2.0".to_string()),
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     let result = renderer.render_status(&NetworkStatus::Error, &metrics, None);
@@ -952,7 +1141,8 @@ fn test_oauth_mode_hides_status_lights_and_proxy_health() {
         breakdown: "DNS:5ms|TCP:10ms|TLS:15ms|TTFB:120ms|Total:150ms".to_string(),
         last_http_status: 401, // Expected for OAuth dummy key
         error_type: Some("authentication_error".to_string()),
-        rolling_totals: vec![100, 120, 150],
+        rolling_totals: smallvec![100, 120, 150],
+        rolling_sampled_at: smallvec![],
         p95_latency_ms: 145,
         breakdown_source: Some("measured".to_string()),
         connection_reused: Some(false),
@@ -960,6 +1150,11 @@ fn test_oauth_mode_hides_status_lights_and_proxy_health() {
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: Some("HTTP/2.0".to_string()),
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     let result = renderer.render_status(&NetworkStatus::Error, &metrics, Some(&oauth_config));
@@ -1018,7 +1213,8 @@ fn test_oauth_mode_with_minimal_metrics() {
         breakdown: "".to_string(),
         last_http_status: 401,
         error_type: Some("authentication_error".to_string()),
-        rolling_totals: vec![],
+        rolling_totals: smallvec![],
+        rolling_sampled_at: smallvec![],
         p95_latency_ms: 0,
         breakdown_source: None,
         connection_reused: None,
@@ -1026,6 +1222,11 @@ fn test_oauth_mode_with_minimal_metrics() {
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: None,
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     let result = renderer.render_status(&NetworkStatus::Error, &metrics, Some(&oauth_config));
@@ -1053,7 +1254,8 @@ fn test_non_oauth_mode_unchanged() {
         breakdown: "DNS:5ms|TCP:10ms|TLS:15ms|TTFB:120ms|Total:150ms".to_string(),
         last_http_status: 200,
         error_type: None,
-        rolling_totals: vec![100, 120, 150],
+        rolling_totals: smallvec![100, 120, 150],
+        rolling_sampled_at: smallvec![],
         p95_latency_ms: 145,
         breakdown_source: None,
         connection_reused: None,
@@ -1061,6 +1263,11 @@ fn test_non_oauth_mode_unchanged() {
         proxy_health_level: None,
         proxy_health_detail: None,
         http_version: None,
+        dns_cache: None,
+        dns_lookup_ms: None,
+        connection: None,
+        window_percentiles: Default::default(),
+        rate_limit: None,
     };
 
     let result = renderer.render_status(&NetworkStatus::Healthy, &metrics, Some(&env_config));
@@ -1082,3 +1289,587 @@ fn test_non_oauth_mode_unchanged() {
         "Non-OAuth config should behave same as None config"
     );
 }
+
+#[test]
+fn test_probes_off_rendering() {
+    use ccstatus::core::network::types::JsonlError;
+
+    let renderer = StatusRenderer::new();
+    assert_eq!(renderer.render_probes_off(None), "⚪ probes off");
+
+    let error = JsonlError {
+        timestamp: "2024-01-01T12:00:00Z".to_string(),
+        code: 529,
+        message: "Overloaded".to_string(),
+    };
+    assert_eq!(
+        renderer.render_probes_off(Some(&error)),
+        "🔴 probes off | API error 529"
+    );
+}
+
+#[test]
+fn test_oauth_off_rendering() {
+    use ccstatus::core::network::types::JsonlError;
+
+    let renderer = StatusRenderer::new();
+    assert_eq!(renderer.render_oauth_off(None), "⚪ oauth off");
+
+    let error = JsonlError {
+        timestamp: "2024-01-01T12:00:00Z".to_string(),
+        code: 401,
+        message: "Unauthorized".to_string(),
+    };
+    assert_eq!(
+        renderer.render_oauth_off(Some(&error)),
+        "🔴 oauth off | API error 401"
+    );
+}
+
+#[test]
+fn test_blocked_by_policy_rendering() {
+    use ccstatus::core::network::types::ApiConfig;
+
+    let renderer = StatusRenderer::new();
+    let metrics = NetworkMetrics::default();
+    let api_config = ApiConfig {
+        endpoint: "https://gateway.example.com/v1/messages".to_string(),
+        source: "environment".to_string(),
+    };
+
+    assert_eq!(
+        renderer.render_status(&NetworkStatus::BlockedByPolicy, &metrics, Some(&api_config)),
+        "⛔ blocked_by_policy (gateway.example.com)"
+    );
+    assert_eq!(
+        renderer.render_status(&NetworkStatus::BlockedByPolicy, &metrics, None),
+        "⛔ blocked_by_policy"
+    );
+}
+
+#[test]
+fn test_error_hints_as_hyperlinks() {
+    use ccstatus::core::network::types::JsonlError;
+
+    let renderer = StatusRenderer::new().with_hyperlinks(true);
+    let metrics = NetworkMetrics {
+        breakdown: "Total:3000ms".to_string(),
+        error_type: Some("rate_limit_error".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        renderer.render_status(&NetworkStatus::Error, &metrics, None),
+        "\x1b]8;;https://github.com/MaurUppi/CCstatus/blob/main/README_EN.md#rate_limit_error\x1b\\\
+         🔴 Total:3000ms\x1b]8;;\x1b\\"
+    );
+
+    // Server-side outages link to the status page
+    let error = JsonlError {
+        timestamp: "2024-01-01T12:00:00Z".to_string(),
+        code: 529,
+        message: "Overloaded".to_string(),
+    };
+    assert_eq!(
+        renderer.render_probes_off(Some(&error)),
+        "🔴 probes off | \x1b]8;;https://status.anthropic.com\x1b\\API error 529\x1b]8;;\x1b\\"
+    );
+
+    // Plain fallback is the default
+    assert_eq!(
+        StatusRenderer::new().render_status(&NetworkStatus::Error, &metrics, None),
+        "🔴 Total:3000ms"
+    );
+}
+
+#[test]
+fn test_insecure_tls_marker_rendering() {
+    let renderer = StatusRenderer::new();
+    let status = "🟢 P95:420ms".to_string();
+
+    assert_eq!(
+        renderer.render_with_insecure_tls(status.clone(), false),
+        status
+    );
+    assert_eq!(
+        renderer.render_with_insecure_tls(status, true),
+        "🟢 P95:420ms | ⚠ TLS unverified"
+    );
+}
+
+#[test]
+fn test_cooldown_hint_rendering() {
+    let renderer = StatusRenderer::new();
+    let status = "🟡 P95:1200ms".to_string();
+
+    assert_eq!(renderer.render_with_cooldown(status.clone(), None), status);
+    assert_eq!(
+        renderer.render_with_cooldown(status.clone(), Some(45)),
+        "🟡 P95:1200ms | cooldown 45s"
+    );
+    assert_eq!(
+        renderer.render_with_cooldown(status, Some(200)),
+        "🟡 P95:1200ms | cooldown 4m"
+    );
+}
+
+#[test]
+fn test_status_report_includes_header_snapshot() {
+    use ccstatus::core::network::types::{ApiConfig, MonitoringSnapshot, ProbeErrorDetails};
+
+    let renderer = StatusRenderer::new();
+    let mut state = MonitoringSnapshot {
+        status: NetworkStatus::Degraded,
+        api_config: Some(ApiConfig {
+            endpoint: "https://api.anthropic.com/v1/messages".to_string(),
+            source: "environment".to_string(),
+        }),
+        timestamp: "2025-01-25T10:30:00-08:00".to_string(),
+        ..Default::default()
+    };
+
+    let report = renderer.render_report(&state);
+    assert!(report.contains("Endpoint: https://api.anthropic.com/v1/messages (environment)"));
+    assert!(report.contains("Last probe error: none"));
+
+    state.last_probe_error = Some(ProbeErrorDetails {
+        timestamp: "2025-01-25T10:30:00-08:00".to_string(),
+        http_status: 403,
+        error_type: "bot_challenge".to_string(),
+        headers: [
+            ("cf-ray".to_string(), "8a1b2c3d4e5f-LAX".to_string()),
+            ("server".to_string(), "cloudflare".to_string()),
+        ]
+        .into_iter()
+        .collect(),
+    });
+
+    let report = renderer.render_report(&state);
+    assert!(
+        report.contains("Last probe error: HTTP 403 bot_challenge at 2025-01-25T10:30:00-08:00")
+    );
+    assert!(report.contains("\n  cf-ray: 8a1b2c3d4e5f-LAX\n  server: cloudflare"));
+}
+
+#[test]
+fn test_status_report_includes_trend_and_distribution() {
+    use ccstatus::core::network::types::MonitoringSnapshot;
+
+    let renderer = StatusRenderer::new();
+    let mut state = MonitoringSnapshot::default();
+    state
+        .network
+        .rolling_totals
+        .extend([400, 450, 420, 900, 1200, 6000]);
+
+    let report = renderer.render_report(&state);
+    assert!(report.contains("Trend: rising (+186%)"), "{}", report);
+    assert!(report.contains("Distribution: ≤500ms:3 ≤1000ms:1 ≤2000ms:1 >5000ms:1"));
+
+    state.network.rolling_totals.clear();
+    let report = renderer.render_report(&state);
+    assert!(!report.contains("Trend:"));
+    assert!(!report.contains("Distribution:"));
+    assert!(!report.contains("Percentiles:"));
+
+    state.network.window_percentiles = [
+        ("p99.9".to_string(), 5900),
+        ("p100".to_string(), 6000),
+        ("p50".to_string(), 660),
+    ]
+    .into_iter()
+    .collect();
+    let report = renderer.render_report(&state);
+    assert!(report.contains("Percentiles: P50 660ms, P99.9 5900ms, P100 6000ms"));
+}
+
+#[test]
+fn test_status_report_includes_dns_cache() {
+    use ccstatus::core::network::dns_cache::DnsCacheStatus;
+    use ccstatus::core::network::types::MonitoringSnapshot;
+
+    let renderer = StatusRenderer::new();
+    let mut state = MonitoringSnapshot::default();
+    assert!(!renderer.render_report(&state).contains("DNS cache:"));
+
+    state.network.dns_cache = Some(DnsCacheStatus::Miss);
+    state.network.dns_lookup_ms = Some(23);
+    assert!(renderer
+        .render_report(&state)
+        .contains("DNS cache: miss (lookup 23ms)"));
+
+    state.network.dns_cache = Some(DnsCacheStatus::Hit);
+    state.network.dns_lookup_ms = None;
+    assert!(renderer.render_report(&state).contains("DNS cache: hit\n"));
+}
+
+#[test]
+fn test_status_report_includes_connection_details() {
+    use ccstatus::core::network::types::{ConnectionInfo, MonitoringSnapshot, NetworkMetrics};
+
+    let renderer = StatusRenderer::new();
+    let mut state = MonitoringSnapshot::default();
+    assert!(!renderer.render_report(&state).contains("Served by:"));
+
+    state.network.connection = Some(ConnectionInfo {
+        primary_ip: Some("160.79.104.10".to_string()),
+        num_connects: 1,
+        redirect_count: 2,
+        redirect_ms: 120,
+        certificate: None,
+    });
+    assert!(renderer
+        .render_report(&state)
+        .contains("Served by: 160.79.104.10 (IPv4, new connection, 2 redirects +120ms)"));
+
+    state.network.connection = Some(ConnectionInfo::default());
+    assert!(renderer
+        .render_report(&state)
+        .contains("Served by: unknown address (reused connection)"));
+
+    // Persisted with the metrics; older state files without it still load
+    let json = serde_json::to_string(&state.network).unwrap();
+    let restored: NetworkMetrics = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.connection, Some(ConnectionInfo::default()));
+    let legacy: NetworkMetrics = serde_json::from_str(
+        r#"{"latency_ms":0,"breakdown":"","last_http_status":0,"error_type":null,"rolling_totals":[],"p95_latency_ms":0}"#,
+    )
+    .unwrap();
+    assert_eq!(legacy.connection, None);
+}
+
+#[test]
+fn test_status_report_explains_bad_proxy() {
+    use ccstatus::core::network::types::{MonitoringSnapshot, ProxyHealthDetail};
+
+    let mut state = MonitoringSnapshot::default();
+    state.network.set_proxy_health(
+        Some(ProxyHealthLevel::Bad),
+        Some(ProxyHealthDetail {
+            primary_url: "https://proxy.example.com/health".to_string(),
+            fallback_url: None,
+            redirect_url: None,
+            success_method: Some("primary".to_string()),
+            checked_at: "2025-01-25T10:30:00-08:00".to_string(),
+            response_time_ms: 120,
+            reason: Some("invalid_json_200".to_string()),
+            body_excerpt: Some("Sign in to Corp Network".to_string()),
+            guidance: Some("proxy returned an HTML login page \u{2014} check VPN".to_string()),
+            certificate_warning: None,
+        }),
+    );
+
+    let report = StatusRenderer::new().render_report(&state);
+    assert!(report.contains(
+        "Proxy: bad (invalid_json_200)\n  Hint: proxy returned an HTML login page \u{2014} check VPN\n  Response: Sign in to Corp Network"
+    ));
+}
+
+#[test]
+fn test_render_target_strip() {
+    let renderer = StatusRenderer::new();
+    let strip = renderer.render_target_strip(&[
+        ("A".to_string(), NetworkStatus::Healthy),
+        ("R".to_string(), NetworkStatus::Error),
+        ("O".to_string(), NetworkStatus::Degraded),
+        ("X".to_string(), NetworkStatus::Unknown),
+    ]);
+    assert_eq!(strip, "A✓ R✗ O~ X?");
+    assert_eq!(renderer.render_target_strip(&[]), "");
+}
+
+#[test]
+fn test_chinese_locale_translates_status_words() {
+    use ccstatus::core::i18n::Locale;
+    use ccstatus::core::network::types::JsonlError;
+
+    let renderer = StatusRenderer::new().with_locale(Locale::Zh);
+    let error = JsonlError {
+        timestamp: "2024-01-01T12:00:00Z".to_string(),
+        code: 529,
+        message: "Overloaded".to_string(),
+    };
+    assert_eq!(
+        renderer.render_probes_off(Some(&error)),
+        "🔴 探测已关闭 | API 错误 529"
+    );
+    assert_eq!(
+        renderer.render_with_cooldown("🟡 P95:1200ms".to_string(), Some(200)),
+        "🟡 P95:1200ms | 冷却 4m"
+    );
+    assert_eq!(
+        renderer.render_status(&NetworkStatus::Unknown, &NetworkMetrics::default(), None),
+        "⚪ 未找到环境变量"
+    );
+    // Numbers and P95 labels are never translated
+    let metrics = NetworkMetrics {
+        p95_latency_ms: 2400,
+        ..Default::default()
+    };
+    assert_eq!(
+        renderer.render_status(&NetworkStatus::Healthy, &metrics, None),
+        "🟢 P95:2400ms"
+    );
+}
+
+#[test]
+fn test_number_format_applies_to_latencies() {
+    use ccstatus::core::format::{LatencyUnit, NumberFormat};
+
+    let renderer = StatusRenderer::new().with_number_format(NumberFormat {
+        latency_unit: LatencyUnit::S,
+        ..NumberFormat::default()
+    });
+    let metrics = NetworkMetrics {
+        latency_ms: 2500,
+        breakdown: "DNS:20ms|Total:2500ms".to_string(),
+        p95_latency_ms: 2400,
+        ..Default::default()
+    };
+    // The phase breakdown keeps its milliseconds
+    assert_eq!(
+        renderer.render_status(&NetworkStatus::Degraded, &metrics, None),
+        "🟡 P95:2.4s DNS:20ms|Total:2500ms"
+    );
+
+    let grouped = StatusRenderer::new().with_number_format(NumberFormat {
+        thousands_separator: ",".to_string(),
+        ..NumberFormat::default()
+    });
+    assert_eq!(
+        grouped.render_status(&NetworkStatus::Healthy, &metrics, None),
+        "🟢 P95:2,400ms"
+    );
+}
+
+#[test]
+fn test_ascii_icons_render_without_emoji() {
+    let renderer = StatusRenderer::new().with_icons(IconSet::Ascii);
+    let metrics = NetworkMetrics {
+        latency_ms: 2500,
+        breakdown: "Total:2500ms".to_string(),
+        last_http_status: 200,
+        p95_latency_ms: 2400,
+        proxy_health_level: Some(ProxyHealthLevel::Healthy),
+        ..Default::default()
+    };
+
+    let degraded = renderer.render_status(&NetworkStatus::Degraded, &metrics, None);
+    assert_eq!(degraded, "[ok] | [~] P95:2400ms Total:2500ms");
+    assert_eq!(renderer.render_probes_off(None), "[?] probes off");
+
+    let strip = renderer.render_target_strip(&[
+        ("A".to_string(), NetworkStatus::Healthy),
+        ("R".to_string(), NetworkStatus::Error),
+    ]);
+    assert_eq!(strip, "A+ Rx");
+}
+
+#[test]
+fn test_transport_error_hints() {
+    use ccstatus::core::i18n::Locale;
+
+    let metrics = |error_type: &str| NetworkMetrics {
+        latency_ms: 40,
+        breakdown: "Total:40ms".to_string(),
+        error_type: Some(error_type.to_string()),
+        ..Default::default()
+    };
+    let renderer = StatusRenderer::new();
+    let render = |error_type: &str| {
+        renderer.render_status(&NetworkStatus::Error, &metrics(error_type), None)
+    };
+    assert_eq!(render("dns_error"), "🔴 DNS failed Total:40ms");
+    assert_eq!(render("connect_refused"), "🔴 refused Total:40ms");
+    assert_eq!(render("tls_error"), "🔴 TLS failed Total:40ms");
+    assert_eq!(render("timeout"), "🔴 timeout Total:40ms");
+    // No hint when the failed phase is unknown
+    assert_eq!(render("connection_error"), "🔴 Total:40ms");
+
+    let zh = StatusRenderer::new().with_locale(Locale::Zh);
+    assert_eq!(
+        zh.render_status(&NetworkStatus::Error, &metrics("dns_error"), None),
+        "🔴 DNS 解析失败 Total:40ms"
+    );
+}
+
+#[test]
+fn test_error_attention_wraps_light_and_hint() {
+    use ccstatus::core::attention::AttentionStyle;
+
+    let metrics = NetworkMetrics {
+        latency_ms: 40,
+        breakdown: "Total:40ms".to_string(),
+        error_type: Some("timeout".to_string()),
+        ..Default::default()
+    };
+    let renderer = StatusRenderer::new().with_attention(AttentionStyle::Blink);
+    assert_eq!(
+        renderer.render_status(&NetworkStatus::Error, &metrics, None),
+        "\x1b[5m🔴 timeout\x1b[25m Total:40ms"
+    );
+    // Only the error state stands out
+    assert_eq!(
+        renderer.render_status(&NetworkStatus::Healthy, &metrics, None),
+        "🟢 P95:N/A"
+    );
+}
+
+#[test]
+fn test_error_budget_badge_and_report_line() {
+    use ccstatus::core::network::error_budget::ErrorBudget;
+    use ccstatus::core::network::types::MonitoringSnapshot;
+
+    let renderer = StatusRenderer::new();
+    let mut state = MonitoringSnapshot::default();
+    let status = "🟡 P95:4200ms".to_string();
+    assert_eq!(
+        renderer.render_with_error_budget(status.clone(), &state),
+        status
+    );
+
+    state.error_budget = Some(ErrorBudget {
+        allowed_failure_percent: 1.0,
+        window_hours: 24,
+        probes: 200,
+        failed: 1,
+        burn_window_minutes: 60,
+        recent_probes: 13,
+        recent_failed: 1,
+        burn_rate: 7.69,
+        burn_alert: 10.0,
+        burning: false,
+        evaluated_at: 0,
+    });
+    assert_eq!(
+        renderer.render_with_error_budget(status.clone(), &state),
+        status
+    );
+    assert!(renderer
+        .render_report(&state)
+        .contains("Error budget: 1% over 24h, 1/200 failed (50.0% left); burn 7.7x over 60m\n"));
+
+    state.error_budget.as_mut().unwrap().burning = true;
+    assert_eq!(
+        renderer.render_with_error_budget(status.clone(), &state),
+        "🟡 P95:4200ms | budget 7.7x"
+    );
+    assert!(renderer
+        .render_report(&state)
+        .contains("burn 7.7x over 60m, at or above 10x"));
+}
+
+#[test]
+fn test_rate_limit_countdown_after_429() {
+    use ccstatus::core::network::types::{MonitoringSnapshot, RateLimitInfo};
+
+    let renderer = StatusRenderer::new();
+    let received_at = 1_737_829_800;
+    let mut state = MonitoringSnapshot::default();
+    state.network.last_http_status = 429;
+    state.network.rate_limit = Some(RateLimitInfo {
+        retry_after_secs: Some(300),
+        received_at,
+        ..Default::default()
+    });
+    let status = "🔴 rate limited".to_string();
+
+    assert_eq!(
+        renderer.render_with_rate_limit(status.clone(), &state, received_at + 258),
+        "🔴 rate limited | resets in 42s"
+    );
+    assert_eq!(
+        renderer.render_with_rate_limit(status.clone(), &state, received_at + 10),
+        "🔴 rate limited | resets in 5m"
+    );
+    assert_eq!(
+        renderer.render_with_rate_limit(status.clone(), &state, received_at + 300),
+        status
+    );
+    assert!(renderer
+        .render_report(&state)
+        .contains("Rate limit: no quota headers; retry after 300s"));
+
+    // Quota headers on a healthy probe are reported but never counted down
+    state.network.last_http_status = 200;
+    assert_eq!(
+        renderer.render_with_rate_limit(status.clone(), &state, received_at),
+        status
+    );
+}
+
+#[test]
+fn test_uptime_badge_and_report_line() {
+    use ccstatus::core::network::types::{MonitoringSnapshot, UptimeWindow};
+
+    let renderer = StatusRenderer::new();
+    let mut state = MonitoringSnapshot::default();
+    let status = "🟢 P95:420ms".to_string();
+    assert_eq!(
+        renderer.render_with_uptime(status.clone(), &state, Some(UptimeWindow::Day)),
+        status
+    );
+
+    state.uptime.availability_24h = Some(99.26);
+    state.uptime.availability_7d = Some(100.0);
+    assert_eq!(
+        renderer.render_with_uptime(status.clone(), &state, Some(UptimeWindow::Day)),
+        "🟢 P95:420ms | 99.2%"
+    );
+    assert_eq!(
+        renderer.render_with_uptime(status.clone(), &state, Some(UptimeWindow::Week)),
+        "🟢 P95:420ms | 100%"
+    );
+    assert_eq!(
+        renderer.render_with_uptime(status.clone(), &state, None),
+        status
+    );
+
+    state.uptime.availability_7d = Some(99.97);
+    assert!(renderer
+        .render_report(&state)
+        .contains("Availability: 24h 99.2%, 7d 99.9%"));
+}
+
+#[test]
+fn test_status_report_shows_certificate() {
+    use ccstatus::core::network::certificate::{CertificateChain, CertificateInfo};
+    use ccstatus::core::network::types::{ConnectionInfo, MonitoringSnapshot, ProxyHealthDetail};
+
+    let mut state = MonitoringSnapshot::default();
+    state.network.connection = Some(ConnectionInfo {
+        primary_ip: Some("203.0.113.7".to_string()),
+        num_connects: 1,
+        certificate: Some(CertificateChain {
+            certs: vec![CertificateInfo {
+                subject: "CN = gateway.example.com".to_string(),
+                issuer: "CN = Corp CA".to_string(),
+                sans: vec!["DNS:gateway.example.com".to_string()],
+                // 2026-10-06
+                not_after: Some(1_791_244_800),
+            }],
+            trusted: false,
+        }),
+        ..Default::default()
+    });
+    state.network.set_proxy_health(
+        Some(ProxyHealthLevel::Healthy),
+        Some(ProxyHealthDetail {
+            primary_url: "https://gateway.example.com/health".to_string(),
+            fallback_url: None,
+            redirect_url: None,
+            success_method: Some("primary".to_string()),
+            checked_at: "2026-10-01T10:30:00+00:00".to_string(),
+            response_time_ms: 80,
+            reason: None,
+            body_excerpt: None,
+            guidance: None,
+            certificate_warning: Some("untrusted certificate chain".to_string()),
+        }),
+    );
+
+    let report = StatusRenderer::new().render_report(&state);
+    assert!(report.contains(
+        "Certificate: CN = gateway.example.com (issuer CN = Corp CA, expires 2026-10-06, untrusted)"
+    ));
+    assert!(report.contains("Proxy: healthy\n  Certificate: untrusted certificate chain"));
+}
@@ -0,0 +1,80 @@
+use ccstatus::core::network::probe_history::ProbeRecord;
+use ccstatus::core::network::timeseries::{
+    parse_range, render_timeseries, TimeseriesMetric, TimeseriesQuery, DEFAULT_RANGE_SECS,
+};
+use ccstatus::core::network::NetworkStatus;
+
+fn record(timestamp: &str, latency_ms: u32, status: NetworkStatus) -> ProbeRecord {
+    ProbeRecord {
+        timestamp: timestamp.to_string(),
+        version: "1.0.0".to_string(),
+        mode: "green".to_string(),
+        http_status: 200,
+        latency_ms,
+        status,
+        error_type: None,
+    }
+}
+
+#[test]
+fn test_parse_query_and_range() {
+    assert_eq!(parse_range("30s"), Some(30));
+    assert_eq!(parse_range("6h"), Some(6 * 3600));
+    assert_eq!(parse_range("7d"), Some(7 * 86400));
+    assert_eq!(parse_range("0h"), None);
+    assert_eq!(parse_range("6"), None);
+    assert_eq!(parse_range("h"), None);
+
+    assert_eq!(
+        TimeseriesQuery::parse("metric=latency&range=15m&target=relay").unwrap(),
+        TimeseriesQuery {
+            metric: TimeseriesMetric::Latency,
+            range_secs: 900,
+            target: Some("relay".to_string()),
+        }
+    );
+    let query = TimeseriesQuery::parse("metric=status").unwrap();
+    assert_eq!(query.range_secs, DEFAULT_RANGE_SECS);
+    assert_eq!(query.target, None);
+    assert!(TimeseriesQuery::parse("range=6h").is_err());
+    assert!(TimeseriesQuery::parse("metric=cpu").is_err());
+    assert!(TimeseriesQuery::parse("metric=latency&range=soon").is_err());
+}
+
+#[test]
+fn test_render_timeseries_filters_range_and_target() {
+    // 2025-01-25T10:30:00Z
+    let now_ms = 1_737_801_000_000;
+    let histories = vec![
+        (
+            "primary".to_string(),
+            vec![
+                record("2025-01-25T18:20:00+08:00", 900, NetworkStatus::Degraded),
+                record("2025-01-25T03:00:00+00:00", 500, NetworkStatus::Healthy),
+                record("not a time", 1, NetworkStatus::Healthy),
+                record("2025-01-25T10:00:00+00:00", 700, NetworkStatus::Healthy),
+            ],
+        ),
+        ("relay".to_string(), vec![]),
+    ];
+
+    let query = TimeseriesQuery::parse("metric=latency&range=1h").unwrap();
+    let payload: serde_json::Value =
+        serde_json::from_str(&render_timeseries(&histories, &query, now_ms)).unwrap();
+    assert_eq!(
+        payload,
+        serde_json::json!([
+            {
+                "target": "primary",
+                "datapoints": [[700.0, 1_737_799_200_000_i64], [900.0, 1_737_800_400_000_i64]]
+            },
+            { "target": "relay", "datapoints": [] }
+        ])
+    );
+
+    let query = TimeseriesQuery::parse("metric=status&range=1h&target=primary").unwrap();
+    let payload: serde_json::Value =
+        serde_json::from_str(&render_timeseries(&histories, &query, now_ms)).unwrap();
+    assert_eq!(payload.as_array().unwrap().len(), 1);
+    assert_eq!(payload[0]["datapoints"][1][0], 1.0);
+}
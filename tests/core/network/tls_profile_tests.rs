@@ -0,0 +1,17 @@
+#![cfg(feature = "tls-parity")]
+
+use ccstatus::core::network::tls_profile::{apply_claude_code_tls_profile, NODE_TLS12_CIPHER_LIST};
+
+#[test]
+fn test_cipher_list_follows_node_order() {
+    assert!(NODE_TLS12_CIPHER_LIST.starts_with("ECDHE-RSA-AES128-GCM-SHA256:"));
+    assert!(NODE_TLS12_CIPHER_LIST.ends_with("!CAMELLIA"));
+    // TLS 1.3 suites are left to OpenSSL defaults, not the 1.2 cipher list
+    assert!(!NODE_TLS12_CIPHER_LIST.contains("TLS_AES"));
+}
+
+#[test]
+fn test_profile_applies_to_curl_handle() {
+    let mut handle = curl::easy::Easy::new();
+    assert!(apply_claude_code_tls_profile(&mut handle).is_ok());
+}
@@ -0,0 +1,443 @@
+use ccstatus::config::{HysteresisConfig, WindowConfig};
+use ccstatus::core::network::proxy_health::config::{MAX_HEALTH_TIMEOUT_MS, MIN_HEALTH_TIMEOUT_MS};
+use ccstatus::core::network::proxy_health::ProxyHealthOptions;
+use ccstatus::core::network::types::{
+    ApiConfig, ApiCredentials, CredentialSource, MonitoringState, NetworkError, NetworkMetrics,
+    NetworkStatus, PenaltyState, ProbeMetrics, ProbeMode, ProbeOutcome, RateLimitInfo,
+    RateLimitQuota, UptimeStats, UptimeWindow, WindowPolicy, MAX_OBSERVATION_GAP_SECS,
+    MAX_ROLLING_WINDOW_LEN, ROLLING_WINDOW_LEN,
+};
+use std::collections::HashMap;
+
+#[test]
+fn test_credentials_builder_trims_and_builds() {
+    let creds = ApiCredentials::builder(
+        " https://api.example.com ",
+        "sk-test\n",
+        CredentialSource::Environment,
+    )
+    .with_expires_at(Some(1_700_000_000_000))
+    .build()
+    .unwrap();
+
+    assert_eq!(creds.base_url, "https://api.example.com");
+    assert_eq!(creds.auth_token, "sk-test");
+    assert_eq!(creds.source, CredentialSource::Environment);
+    assert_eq!(creds.expires_at, Some(1_700_000_000_000));
+}
+
+#[test]
+fn test_credentials_builder_rejects_invalid_input() {
+    let cases = [
+        ("", "token"),
+        ("   ", "token"),
+        ("api.example.com", "token"),
+        ("ftp://api.example.com", "token"),
+        ("https://", "token"),
+        ("https://api.example.com", ""),
+        ("https://api.example.com", "  "),
+    ];
+    for (base_url, token) in cases {
+        let result =
+            ApiCredentials::builder(base_url, token, CredentialSource::Environment).build();
+        assert!(
+            matches!(result, Err(NetworkError::ValidationError(_))),
+            "expected validation error for ({:?}, {:?})",
+            base_url,
+            token
+        );
+    }
+}
+
+#[test]
+fn test_credentials_builder_accepts_http_proxy() {
+    let creds =
+        ApiCredentials::builder("http://localhost:8080/v1", "token", CredentialSource::OAuth)
+            .build()
+            .unwrap();
+    assert_eq!(creds.base_url, "http://localhost:8080/v1");
+    assert_eq!(creds.expires_at, None);
+}
+
+#[test]
+fn test_proxy_health_options_builder() {
+    let options = ProxyHealthOptions::builder()
+        .with_root_urls(true)
+        .with_fallback(false)
+        .with_redirect_once(true)
+        .with_timeout_ms(2000)
+        .build()
+        .unwrap();
+
+    assert!(options.use_root_urls);
+    assert!(!options.try_fallback);
+    assert!(options.follow_redirect_once);
+    assert_eq!(options.timeout_ms, 2000);
+
+    let defaults = ProxyHealthOptions::builder().build().unwrap();
+    assert_eq!(
+        defaults.timeout_ms,
+        ProxyHealthOptions::default().timeout_ms
+    );
+}
+
+#[test]
+fn test_proxy_health_options_builder_bounds_timeout() {
+    for timeout_ms in [MIN_HEALTH_TIMEOUT_MS, MAX_HEALTH_TIMEOUT_MS] {
+        assert!(ProxyHealthOptions::builder()
+            .with_timeout_ms(timeout_ms)
+            .build()
+            .is_ok());
+    }
+    for timeout_ms in [0, MIN_HEALTH_TIMEOUT_MS - 1, MAX_HEALTH_TIMEOUT_MS + 1] {
+        assert!(matches!(
+            ProxyHealthOptions::builder()
+                .with_timeout_ms(timeout_ms)
+                .build(),
+            Err(NetworkError::ValidationError(_))
+        ));
+    }
+}
+
+#[test]
+fn test_probe_outcome_builder() {
+    let outcome = ProbeOutcome::builder(ProbeMode::Green, NetworkStatus::Healthy)
+        .with_metrics(ProbeMetrics {
+            latency_ms: 420,
+            last_http_status: 200,
+            ..ProbeMetrics::default()
+        })
+        .with_p95(900, 5)
+        .with_api_config(ApiConfig {
+            endpoint: "https://api.example.com/v1/messages".to_string(),
+            source: "environment".to_string(),
+        })
+        .with_state_written(true)
+        .with_timestamp("2025-01-25T10:30:45-08:00")
+        .build()
+        .unwrap();
+
+    assert_eq!(outcome.mode, ProbeMode::Green);
+    assert_eq!(outcome.status, NetworkStatus::Healthy);
+    assert_eq!(outcome.metrics.latency_ms, 420);
+    assert_eq!(outcome.p95_latency_ms, 900);
+    assert_eq!(outcome.rolling_len, 5);
+    assert_eq!(outcome.api_config.source, "environment");
+    assert!(outcome.state_written);
+    assert_eq!(outcome.timestamp_local, "2025-01-25T10:30:45-08:00");
+}
+
+#[test]
+fn test_probe_outcome_builder_bounds_rolling_window() {
+    let full = ProbeOutcome::builder(ProbeMode::Red, NetworkStatus::Error)
+        .with_p95(0, MAX_ROLLING_WINDOW_LEN)
+        .build();
+    assert!(full.is_ok());
+
+    let overflow = ProbeOutcome::builder(ProbeMode::Red, NetworkStatus::Error)
+        .with_p95(0, MAX_ROLLING_WINDOW_LEN + 1)
+        .build();
+    assert!(matches!(overflow, Err(NetworkError::ValidationError(_))));
+}
+
+#[test]
+fn test_push_sample_keeps_times_aligned_at_capacity() {
+    // Two legacy samples written before sample times were tracked
+    let mut metrics = NetworkMetrics {
+        rolling_totals: [700, 800].into_iter().collect(),
+        ..Default::default()
+    };
+    for i in 0..ROLLING_WINDOW_LEN as i64 {
+        metrics.push_sample(1000, 100 + i);
+    }
+
+    assert_eq!(metrics.rolling_totals.len(), ROLLING_WINDOW_LEN);
+    assert_eq!(metrics.rolling_sampled_at.len(), ROLLING_WINDOW_LEN);
+    assert_eq!(metrics.rolling_sampled_at[0], 100);
+    assert!(metrics.rolling_totals.iter().all(|&ms| ms == 1000));
+}
+
+#[test]
+fn test_evict_samples_before_handles_untimed_samples() {
+    let mut metrics = NetworkMetrics {
+        rolling_totals: [700, 800].into_iter().collect(),
+        ..Default::default()
+    };
+    metrics.push_sample(900, 100);
+    metrics.push_sample(1000, 200);
+
+    // Nothing timed is stale, so untimed samples may still be recent
+    assert_eq!(metrics.evict_samples_before(100), 0);
+    assert_eq!(metrics.rolling_totals.len(), 4);
+
+    // Untimed samples are older than the stale timed one
+    assert_eq!(metrics.evict_samples_before(150), 3);
+    assert_eq!(&metrics.rolling_totals[..], &[1000]);
+    assert_eq!(&metrics.rolling_sampled_at[..], &[200]);
+
+    assert_eq!(metrics.evict_samples_before(300), 1);
+    assert!(metrics.rolling_totals.is_empty());
+    assert!(metrics.rolling_sampled_at.is_empty());
+}
+
+#[test]
+fn test_push_sample_within_shrinks_to_capacity() {
+    let mut metrics = NetworkMetrics::default();
+    for i in 0..5 {
+        metrics.push_sample(100 * (i + 1), i as i64);
+    }
+    metrics.push_sample_within(600, 5, 3);
+    assert_eq!(&metrics.rolling_totals[..], &[400, 500, 600]);
+    assert_eq!(&metrics.rolling_sampled_at[..], &[3, 4, 5]);
+}
+
+#[test]
+fn test_window_policy_from_config() {
+    let policy = WindowPolicy::from_config(&WindowConfig::default()).unwrap();
+    assert_eq!(policy, WindowPolicy::default());
+    assert_eq!(policy.samples, ROLLING_WINDOW_LEN);
+
+    let policy = WindowPolicy::from_config(&WindowConfig {
+        samples: Some(60),
+        healthy_percentile: Some(90.0),
+        percentiles: vec![50.0, 99.0],
+        ..Default::default()
+    })
+    .unwrap();
+    assert_eq!(policy.samples, 60);
+    assert_eq!(policy.healthy_percentile, 90.0);
+    assert_eq!(policy.degraded_percentile, 95.0);
+    assert_eq!(policy.percentiles, vec![50.0, 99.0]);
+
+    for invalid in [
+        WindowConfig {
+            samples: Some(0),
+            ..Default::default()
+        },
+        WindowConfig {
+            samples: Some(MAX_ROLLING_WINDOW_LEN + 1),
+            ..Default::default()
+        },
+        WindowConfig {
+            percentiles: vec![150.0],
+            ..Default::default()
+        },
+        WindowConfig {
+            healthy_percentile: Some(99.0),
+            degraded_percentile: Some(90.0),
+            ..Default::default()
+        },
+    ] {
+        assert!(matches!(
+            WindowPolicy::from_config(&invalid),
+            Err(NetworkError::ValidationError(_))
+        ));
+    }
+}
+
+#[test]
+fn test_network_status_exit_codes() {
+    assert_eq!(NetworkStatus::Healthy.exit_code(), 0);
+    assert_eq!(NetworkStatus::Degraded.exit_code(), 1);
+    assert_eq!(NetworkStatus::Error.exit_code(), 2);
+    assert_eq!(NetworkStatus::Unknown.exit_code(), 3);
+    // Never reached the API, so health is unknown
+    assert_eq!(NetworkStatus::BlockedByPolicy.exit_code(), 3);
+}
+
+#[test]
+fn test_debounce_status_counts_streaks_per_direction() {
+    let policy = HysteresisConfig {
+        degrade_after: 3,
+        recover_after: 2,
+    };
+    let mut state = MonitoringState::default();
+    let healthy = NetworkStatus::Healthy;
+
+    // Degraded and error both count toward leaving healthy; the latest is shown
+    assert_eq!(
+        state.debounce_status(&healthy, NetworkStatus::Degraded, &policy),
+        healthy
+    );
+    assert_eq!(
+        state.debounce_status(&healthy, NetworkStatus::Error, &policy),
+        healthy
+    );
+    assert_eq!(
+        state.debounce_status(&healthy, NetworkStatus::Error, &policy),
+        NetworkStatus::Error
+    );
+    assert_eq!(state.pending_count, 0);
+
+    // A probe matching the shown status resets the streak
+    state.debounce_status(&healthy, NetworkStatus::Error, &policy);
+    state.debounce_status(&healthy, NetworkStatus::Healthy, &policy);
+    assert_eq!(state.pending_status, None);
+
+    let error = NetworkStatus::Error;
+    assert_eq!(
+        state.debounce_status(&error, NetworkStatus::Healthy, &policy),
+        error
+    );
+    assert_eq!(
+        state.debounce_status(&error, NetworkStatus::Degraded, &policy),
+        NetworkStatus::Degraded
+    );
+
+    // Unknown and policy-blocked are never held back
+    assert_eq!(
+        state.debounce_status(&NetworkStatus::Unknown, NetworkStatus::Error, &policy),
+        NetworkStatus::Error
+    );
+    assert_eq!(
+        state.debounce_status(&healthy, NetworkStatus::Unknown, &policy),
+        NetworkStatus::Unknown
+    );
+    assert_eq!(
+        state.debounce_status(&healthy, NetworkStatus::BlockedByPolicy, &policy),
+        NetworkStatus::BlockedByPolicy
+    );
+    assert_eq!(
+        state.debounce_status(
+            &NetworkStatus::BlockedByPolicy,
+            NetworkStatus::Degraded,
+            &policy
+        ),
+        NetworkStatus::Degraded
+    );
+}
+
+#[test]
+fn test_rate_limit_info_parses_anthropic_headers() {
+    let received_at = 1_737_829_800; // 2025-01-25T18:30:00Z
+    let headers: HashMap<String, String> = [
+        ("Anthropic-RateLimit-Requests-Limit", "50"),
+        ("anthropic-ratelimit-requests-remaining", "0"),
+        ("anthropic-ratelimit-requests-reset", "2025-01-25T18:31:00Z"),
+        ("anthropic-ratelimit-input-tokens-limit", "40000"),
+        ("anthropic-ratelimit-input-tokens-remaining", "12000"),
+        (
+            "anthropic-ratelimit-input-tokens-reset",
+            "2025-01-25T18:30:20Z",
+        ),
+        ("content-type", "application/json"),
+    ]
+    .into_iter()
+    .map(|(name, value)| (name.to_string(), value.to_string()))
+    .collect();
+
+    let info = RateLimitInfo::from_headers(&headers, received_at).unwrap();
+    assert_eq!(info.quotas.len(), 2);
+    assert_eq!(
+        info.quotas["input-tokens"],
+        RateLimitQuota {
+            limit: Some(40000),
+            remaining: Some(12000),
+            reset: Some("2025-01-25T18:30:20Z".to_string()),
+        }
+    );
+    assert_eq!(info.retry_after_secs, None);
+    // Without retry-after the exhausted quota's reset decides
+    assert_eq!(info.retry_at(), Some(received_at + 60));
+    assert_eq!(info.retry_in_secs(received_at + 15), Some(45));
+    assert_eq!(info.retry_in_secs(received_at + 60), None);
+
+    // retry-after wins, as delay seconds or an HTTP date
+    let mut with_retry = headers.clone();
+    with_retry.insert("Retry-After".to_string(), "120".to_string());
+    let info = RateLimitInfo::from_headers(&with_retry, received_at).unwrap();
+    assert_eq!(info.retry_at(), Some(received_at + 120));
+    with_retry.insert(
+        "Retry-After".to_string(),
+        "Sat, 25 Jan 2025 18:35:00 GMT".to_string(),
+    );
+    let info = RateLimitInfo::from_headers(&with_retry, received_at).unwrap();
+    assert_eq!(info.retry_after_secs, Some(300));
+
+    let plain: HashMap<String, String> = [("server".to_string(), "cloudflare".to_string())].into();
+    assert_eq!(RateLimitInfo::from_headers(&plain, received_at), None);
+}
+
+#[test]
+fn test_penalty_hold_for_only_extends() {
+    let now = "2025-01-25T10:30:00-08:00";
+    let mut penalty = PenaltyState::default();
+    penalty.escalate("rate_limit", now);
+    assert_eq!(penalty.until.as_deref(), Some("2025-01-25T10:31:00-08:00"));
+
+    penalty.hold_for(30, now);
+    assert_eq!(penalty.until.as_deref(), Some("2025-01-25T10:31:00-08:00"));
+    penalty.hold_for(300, now);
+    assert_eq!(penalty.until.as_deref(), Some("2025-01-25T10:35:00-08:00"));
+}
+
+#[test]
+fn test_uptime_tracks_status_durations() {
+    let mut uptime = UptimeStats::default();
+    let start = 1_700_000_000;
+    // 5 minute probes: 90 minutes healthy, 10 minutes error, back to healthy
+    for i in 0..=21 {
+        let status = match i {
+            18 | 19 => NetworkStatus::Error,
+            _ => NetworkStatus::Healthy,
+        };
+        uptime.record(&status, start + i * 300);
+    }
+
+    assert_eq!(uptime.spans.len(), 3);
+    let durations = uptime.durations(start);
+    assert_eq!(durations.healthy_secs, 5400 + 300);
+    assert_eq!(durations.error_secs, 600);
+    let expected = 5700.0 * 100.0 / 6300.0;
+    assert!((uptime.availability_24h.unwrap() - expected).abs() < 1e-9);
+    assert_eq!(uptime.availability_7d, uptime.availability_24h);
+
+    // Only the last 24 hours count toward the 24h figure
+    let now = start + 6000;
+    assert_eq!(
+        uptime.availability(UptimeWindow::Day, now),
+        uptime.availability_24h
+    );
+}
+
+#[test]
+fn test_uptime_excludes_unobserved_and_unknown_time() {
+    let mut uptime = UptimeStats::default();
+    let start = 1_700_000_000;
+    uptime.record(&NetworkStatus::Error, start);
+    uptime.record(&NetworkStatus::Error, start + 600);
+    // ccstatus did not run overnight: the gap counts as unknown, not as error
+    let morning = start + 600 + MAX_OBSERVATION_GAP_SECS + 3600 * 8;
+    uptime.record(&NetworkStatus::Healthy, morning);
+    uptime.record(&NetworkStatus::Healthy, morning + 1800);
+    uptime.record(&NetworkStatus::Unknown, morning + 2400);
+
+    let durations = uptime.durations(start);
+    assert_eq!(durations.error_secs, 600);
+    assert_eq!(durations.healthy_secs, 2400);
+    assert!(durations.unknown_secs > 3600 * 8);
+    assert_eq!(uptime.availability_24h, Some(80.0));
+
+    // Out-of-order records are ignored
+    uptime.record(&NetworkStatus::Error, morning);
+    assert_eq!(uptime.availability_24h, Some(80.0));
+}
+
+#[test]
+fn test_uptime_drops_spans_older_than_a_week() {
+    let mut uptime = UptimeStats::default();
+    let start = 1_700_000_000;
+    uptime.record(&NetworkStatus::Error, start);
+    uptime.record(&NetworkStatus::Healthy, start + 300);
+    let later = start + UptimeWindow::Week.secs() + 3600;
+    for at in (start + 300..=later).step_by(1200) {
+        uptime.record(&NetworkStatus::Healthy, at);
+    }
+
+    assert_eq!(uptime.spans.len(), 1);
+    assert_eq!(uptime.spans[0].status, NetworkStatus::Healthy);
+    assert_eq!(uptime.availability_7d, Some(100.0));
+    assert_eq!(UptimeWindow::parse("7D"), Some(UptimeWindow::Week));
+    assert_eq!(UptimeWindow::parse("24h"), Some(UptimeWindow::Day));
+    assert_eq!(UptimeWindow::parse("monthly"), None);
+}
@@ -1,3 +1,4 @@
+use ccstatus::config::HostPolicy;
 use ccstatus::updater::state::UpdateStateFile;
 use std::fs;
 use tempfile::TempDir;
@@ -22,14 +23,14 @@ fn test_cold_window_triggers_update_check() {
     assert!(state.should_check_for_updates());
     
     // Simulate COLD trigger
-    let result = state.tick_from_cold();
+    let result = state.tick_from_cold(&HostPolicy::default());
     assert!(result.is_ok());
     
     // After COLD trigger, last_check should be updated
     assert!(state.last_check.is_some());
     
     // Subsequent COLD trigger within 60 minutes should be throttled
-    let result = state.tick_from_cold();
+    let result = state.tick_from_cold(&HostPolicy::default());
     assert!(result.is_ok()); // Should not fail, just return early due to throttling
 }
 
@@ -45,14 +46,14 @@ fn test_green_window_accumulation_triggers_check() {
     
     // Simulate 11 GREEN window triggers - should not trigger check
     for i in 1..=11 {
-        let result = state.tick_from_green(&format!("green_{}", i));
+        let result = state.tick_from_green(&format!("green_{}", i), &HostPolicy::default());
         assert!(result.is_ok());
         assert_eq!(state.green_ticks_since_check, i);
         assert!(!state.should_trigger_green_check());
     }
     
     // 12th GREEN trigger should trigger check and reset counter
-    let result = state.tick_from_green("green_12");
+    let result = state.tick_from_green("green_12", &HostPolicy::default());
     assert!(result.is_ok());
     
     // Counter should be reset after triggering check
@@ -86,10 +87,10 @@ fn test_update_trigger_silent_failure() {
     // Trigger update checks should not panic or propagate errors
     // according to the plan (silent failure for network issues)
     
-    let cold_result = state.tick_from_cold();
+    let cold_result = state.tick_from_cold(&HostPolicy::default());
     assert!(cold_result.is_ok());
     
-    let green_result = state.tick_from_green("test_green");
+    let green_result = state.tick_from_green("test_green", &HostPolicy::default());
     assert!(green_result.is_ok());
     
     // Both should complete without errors even if network is unavailable
@@ -106,7 +107,7 @@ fn test_update_trigger_throttling_behavior() {
     state.last_check = Some(chrono::Utc::now() - chrono::Duration::minutes(30));
     
     // COLD trigger should be throttled (less than 60 minutes)
-    let result = state.tick_from_cold();
+    let result = state.tick_from_cold(&HostPolicy::default());
     assert!(result.is_ok());
     
     // Should exit early due to throttling without changing last_check
@@ -125,9 +126,9 @@ fn test_window_id_isolation() {
     
     // Test that different GREEN window IDs are handled correctly
     let green_results = vec![
-        state.tick_from_green("window_1"),
-        state.tick_from_green("window_2"), 
-        state.tick_from_green("window_3"),
+        state.tick_from_green("window_1", &HostPolicy::default()),
+        state.tick_from_green("window_2", &HostPolicy::default()), 
+        state.tick_from_green("window_3", &HostPolicy::default()),
     ];
     
     // All should succeed
@@ -0,0 +1,76 @@
+use ccstatus::core::detail_renderer::DetailRenderer;
+use ccstatus::core::hyperlink::link;
+use ccstatus::core::output::{strip_ansi, OutputFormat};
+use smallvec::smallvec;
+
+#[test]
+fn test_strip_ansi_removes_styling_and_links() {
+    let styled = format!(
+        "\x1b[1;32mmodel\x1b[0m | {}",
+        link("🔴 error", "https://status.anthropic.com", true)
+    );
+    assert_eq!(strip_ansi(&styled), "model | 🔴 error");
+    assert_eq!(strip_ansi("plain text"), "plain text");
+}
+
+#[test]
+fn test_plain_format_is_unchanged() {
+    let detail = DetailRenderer::new();
+    assert_eq!(
+        OutputFormat::Plain.render("\x1b[32mok\x1b[0m", &detail),
+        "\x1b[32mok\x1b[0m"
+    );
+    assert!(!OutputFormat::Plain.is_structured());
+}
+
+#[test]
+fn test_structured_formats_carry_tooltip() {
+    let detail = DetailRenderer::new().with_update(Some("2.3.0".to_string()));
+    let statusline = "\x1b[32mmodel\x1b[0m\n🟢 P95:1200ms";
+
+    let json: serde_json::Value =
+        serde_json::from_str(&OutputFormat::Json.render(statusline, &detail)).unwrap();
+    assert_eq!(json["text"], statusline);
+    assert_eq!(json["tooltip"], "Update\n  v2.3.0 available");
+    assert_eq!(json["class"], "unknown");
+
+    let waybar: serde_json::Value =
+        serde_json::from_str(&OutputFormat::Waybar.render(statusline, &detail)).unwrap();
+    assert_eq!(waybar["text"], "model 🟢 P95:1200ms");
+    assert_eq!(waybar["tooltip"], json["tooltip"]);
+}
+
+#[cfg(feature = "network-monitoring")]
+#[test]
+fn test_detail_groups_network_state() {
+    use ccstatus::core::network::types::{MonitoringSnapshot, NetworkStatus, ProbeErrorDetails};
+
+    let mut snapshot = MonitoringSnapshot {
+        status: NetworkStatus::Degraded,
+        timestamp: "2025-01-25T10:30:00-08:00".to_string(),
+        ..Default::default()
+    };
+    snapshot.network.last_http_status = 429;
+    snapshot.network.latency_ms = 900;
+    snapshot.network.p95_latency_ms = 1500;
+    snapshot.network.rolling_totals = smallvec![1000, 1500];
+    snapshot.network.breakdown = "Total:900ms".to_string();
+    snapshot.last_probe_error = Some(ProbeErrorDetails {
+        timestamp: "2025-01-25T10:30:00-08:00".to_string(),
+        http_status: 429,
+        error_type: "rate_limit_error".to_string(),
+        headers: Default::default(),
+    });
+
+    let detail = DetailRenderer::new().with_snapshot(snapshot);
+    assert_eq!(detail.status_class(), "degraded");
+    assert_eq!(
+        detail.render(),
+        format!(
+            "Network\n  Status: degraded (HTTP 429, 900ms)\n  P95: 1500ms (2 samples)\n  \
+             Breakdown: Total:900ms\n  Last error: HTTP 429 rate_limit_error at 2025-01-25T10:30:00-08:00\n  \
+             Updated: 2025-01-25T10:30:00-08:00\nUpdate\n  v{} (up to date)",
+            env!("CARGO_PKG_VERSION")
+        )
+    );
+}
@@ -0,0 +1,88 @@
+use ccstatus::core::perf::{PerfRecorder, PerfReport, StageTiming};
+use std::time::Duration;
+
+fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+#[test]
+fn test_recorder_sums_repeated_stages_in_first_seen_order() {
+    let recorder = PerfRecorder::new();
+    recorder.record("state_load", ms(2));
+    recorder.record("segment.git", ms(10));
+    recorder.record("state_load", ms(3));
+    let value = recorder.time("render", || 42);
+    assert_eq!(value, 42);
+
+    let report = recorder.report();
+    let stages: Vec<&str> = report.stages.iter().map(|t| t.stage.as_str()).collect();
+    assert_eq!(stages, ["state_load", "segment.git", "render"]);
+    assert_eq!(report.stage("state_load"), Some(ms(5)));
+    assert_eq!(
+        report.slowest().map(|t| t.stage.as_str()),
+        Some("segment.git")
+    );
+    assert!(report.total >= Duration::ZERO);
+}
+
+#[test]
+fn test_summary_round_trips_through_parse() {
+    let report = PerfReport {
+        stages: vec![
+            StageTiming {
+                stage: "stdin_parse".to_string(),
+                elapsed: Duration::from_micros(300),
+            },
+            StageTiming {
+                stage: "segment.network".to_string(),
+                elapsed: ms(38),
+            },
+        ],
+        total: Duration::from_micros(52_700),
+    };
+    let summary = report.summary();
+    assert_eq!(
+        summary,
+        "stdin_parse=0.3ms segment.network=38.0ms total=52.7ms"
+    );
+
+    let parsed = PerfReport::parse(&summary).expect("summary parses");
+    assert_eq!(parsed.stages.len(), 2);
+    assert_eq!(parsed.stage("segment.network"), Some(ms(38)));
+    assert_eq!(parsed.total.as_micros(), 52_700);
+
+    assert!(
+        PerfReport::parse("render=1.0ms").is_none(),
+        "total is required"
+    );
+    assert!(PerfReport::parse("render=fast total=1.0ms").is_none());
+}
+
+#[cfg(feature = "network-monitoring")]
+#[test]
+fn test_perf_report_appended_to_debug_log() {
+    use ccstatus::core::network::{EnhancedDebugLogger, JsonlLoggerConfig};
+    use ccstatus::core::perf::PERF_EVENT;
+
+    let dir = tempfile::tempdir().unwrap();
+    let debug_log_path = dir.path().join("ccstatus-debug.log");
+    let logger = EnhancedDebugLogger::from_config(JsonlLoggerConfig {
+        jsonl_path: dir.path().join("ccstatus-jsonl-error.json"),
+        debug_log_path: debug_log_path.clone(),
+        debug_enabled: true,
+    });
+
+    let recorder = PerfRecorder::new();
+    recorder.record("credentials", ms(4));
+    logger.perf_report_sync(&recorder.report());
+
+    let log = std::fs::read_to_string(&debug_log_path).unwrap();
+    let line = log.lines().last().expect("perf line written");
+    assert!(
+        line.contains(&format!("[Perf] \"{}\"", PERF_EVENT)),
+        "{}",
+        line
+    );
+    assert!(line.contains("credentials=4.0ms total="), "{}", line);
+    assert!(line.contains("slowest=credentials"), "{}", line);
+}
@@ -0,0 +1,137 @@
+use ccstatus::config::{Config, SegmentConfig, SegmentId};
+use ccstatus::core::preview::{sample_input, PreviewDiff, SegmentChange};
+use ccstatus::core::segments::SegmentData;
+use ccstatus::core::snapshot::input_from_json;
+use ccstatus::core::StatusLineGenerator;
+use std::collections::HashMap;
+
+fn rendered(segments: &[(SegmentId, &str)]) -> Vec<(SegmentId, String)> {
+    segments
+        .iter()
+        .map(|(id, text)| (*id, text.to_string()))
+        .collect()
+}
+
+#[test]
+fn test_segments_paired_by_id_and_order() {
+    let diff = PreviewDiff::new(
+        "current".to_string(),
+        "candidate".to_string(),
+        rendered(&[
+            (SegmentId::Model, "Sonnet 4"),
+            (SegmentId::Directory, "\x1b[34mCCstatus\x1b[0m"),
+            (SegmentId::Git, "main"),
+            (SegmentId::Usage, "75%"),
+            (SegmentId::Custom, "21°C"),
+        ]),
+        rendered(&[
+            (SegmentId::Custom, "21°C"),
+            (SegmentId::Git, "main ●"),
+            (SegmentId::Directory, "\x1b[44m CCstatus \x1b[49m"),
+            (SegmentId::Model, "Sonnet 4"),
+            (SegmentId::Custom, "VPN"),
+        ]),
+    );
+
+    let changes: Vec<(&str, SegmentChange)> = diff
+        .segments
+        .iter()
+        .map(|segment| (segment.name.as_str(), segment.change()))
+        .collect();
+    assert_eq!(
+        changes,
+        vec![
+            ("model", SegmentChange::Same),
+            ("directory", SegmentChange::Restyled),
+            ("git", SegmentChange::Changed),
+            ("usage", SegmentChange::Removed),
+            ("custom", SegmentChange::Same),
+            ("custom#2", SegmentChange::Added),
+        ]
+    );
+    assert!(!diff.is_identical());
+}
+
+#[test]
+fn test_render_aligns_plain_table() {
+    let diff = PreviewDiff::new(
+        "\x1b[36mSonnet 4\x1b[0m | main".to_string(),
+        "\x1b[36mSonnet 4\x1b[0m | main ●".to_string(),
+        rendered(&[
+            (SegmentId::Model, "\x1b[36mSonnet 4\x1b[0m"),
+            (SegmentId::Git, "main"),
+        ]),
+        rendered(&[
+            (SegmentId::Model, "\x1b[36mSonnet 4\x1b[0m"),
+            (SegmentId::Git, "main ●"),
+        ]),
+    );
+
+    assert_eq!(
+        diff.render(true),
+        [
+            "Current:",
+            "  Sonnet 4 | main",
+            "Candidate:",
+            "  Sonnet 4 | main ●",
+            "",
+            "Segment  Current   Candidate  Change",
+            "model    Sonnet 4  Sonnet 4   same",
+            "git      main      main ●     text",
+        ]
+        .join("\n")
+    );
+    // Styled output keeps the escapes but pads by printable width
+    let styled = diff.render(false);
+    assert!(styled.contains("model    \x1b[36mSonnet 4\x1b[0m  \x1b[36mSonnet 4\x1b[0m   same"));
+}
+
+#[test]
+fn test_identical_configs_report_no_differences() {
+    let segments = rendered(&[(SegmentId::Model, "Sonnet 4")]);
+    let diff = PreviewDiff::new(
+        "Sonnet 4".to_string(),
+        "Sonnet 4".to_string(),
+        segments.clone(),
+        segments,
+    );
+    assert!(diff.is_identical());
+    assert!(diff.render(true).ends_with("\n\nNo differences"));
+}
+
+#[test]
+fn test_render_segments_skips_disabled() {
+    let base = Config::default().segments[0].clone();
+    let segment = |id: SegmentId, enabled: bool, primary: &str| {
+        (
+            SegmentConfig {
+                id,
+                enabled,
+                ..base.clone()
+            },
+            SegmentData {
+                primary: primary.to_string(),
+                secondary: String::new(),
+                metadata: HashMap::new(),
+            },
+        )
+    };
+
+    let generator = StatusLineGenerator::new(Config::default());
+    let segments = generator.render_segments(vec![
+        segment(SegmentId::Model, true, "Sonnet 4"),
+        segment(SegmentId::Git, false, "main"),
+        segment(SegmentId::Directory, true, "CCstatus"),
+    ]);
+    let ids: Vec<SegmentId> = segments.iter().map(|(id, _)| *id).collect();
+    assert_eq!(ids, vec![SegmentId::Model, SegmentId::Directory]);
+    assert!(segments[1].1.contains("CCstatus"));
+}
+
+#[test]
+fn test_sample_input_uses_mock_transcript() {
+    let input = input_from_json(&sample_input());
+    assert_eq!(input.transcript_path, "mock_preview");
+    assert_eq!(input.model.display_name, "Sonnet 4");
+    assert!(!input.workspace.current_dir.is_empty());
+}
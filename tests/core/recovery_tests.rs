@@ -0,0 +1,208 @@
+use ccstatus::core::heartbeat::{Heartbeat, HeartbeatWriter};
+use ccstatus::core::recovery::{self, RecoveryLog};
+use ccstatus::core::segments::block::UsageBlock;
+use ccstatus::core::segments::{SegmentHealth, SegmentHealthMap};
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use std::fmt::Debug;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn health_map() -> SegmentHealthMap {
+    let mut map = SegmentHealthMap::default();
+    map.segments.insert(
+        "git".to_string(),
+        SegmentHealth {
+            healthy: false,
+            failures: 3,
+            last_error: Some("timed out after 200ms".to_string()),
+            last_failure_at: Some("2025-01-25T10:30:00-08:00".to_string()),
+            last_recovered_at: None,
+        },
+    );
+    map
+}
+
+fn quarantined(dir: &Path) -> Vec<String> {
+    fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+        .filter(|name| name.contains(".corrupt-"))
+        .collect()
+}
+
+#[test]
+fn test_corrupt_file_is_quarantined_and_counted() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("segment-health.json");
+    fs::write(&path, "{\"segments\": {\"git\": ").unwrap();
+
+    assert_eq!(SegmentHealthMap::load(&path), SegmentHealthMap::default());
+    assert!(!path.exists());
+    let corrupt = quarantined(temp_dir.path());
+    assert_eq!(corrupt.len(), 1);
+    assert!(corrupt[0].starts_with("segment-health.json.corrupt-"));
+
+    let log = RecoveryLog::load(&RecoveryLog::path_for(&path));
+    assert_eq!(log.recovery_count, 1);
+    let file = &log.files["segment-health.json"];
+    assert!(!file.restored);
+    assert!(file.last_error.contains("EOF"));
+    assert!(log
+        .render_report()
+        .starts_with("1 corrupt state file recovered\nsegment-health.json (1x"));
+
+    // A missing file is not a recovery
+    assert_eq!(SegmentHealthMap::load(&path), SegmentHealthMap::default());
+    assert_eq!(
+        RecoveryLog::load(&RecoveryLog::path_for(&path)).recovery_count,
+        1
+    );
+    assert_eq!(
+        RecoveryLog::default().render_report(),
+        "No corrupt state files recovered"
+    );
+}
+
+#[test]
+fn test_recovery_restores_intact_temp_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("usage-block.json");
+    let at: DateTime<Utc> = "2025-01-25T14:10:00Z".parse().unwrap();
+    let block = UsageBlock::starting_at(at);
+
+    // The rename of an atomic write never happened and the old file got truncated
+    block.save(&path).unwrap();
+    fs::rename(&path, path.with_extension("json.tmp")).unwrap();
+    fs::write(&path, "{\"started_at\": \"2025-01").unwrap();
+
+    assert_eq!(UsageBlock::load(&path), Some(block.clone()));
+    assert!(!path.with_extension("json.tmp").exists());
+    assert_eq!(UsageBlock::load(&path), Some(block), "restored in place");
+
+    let log = RecoveryLog::load(&RecoveryLog::path_for(&path));
+    assert!(log.files["usage-block.json"].restored);
+    assert!(log.render_report().contains("restored from temp file"));
+}
+
+#[test]
+fn test_truncation_fuzz_never_panics_and_counts_every_recovery() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("segment-health.json");
+    let content = serde_json::to_string_pretty(&health_map()).unwrap();
+
+    let mut corrupt = 0;
+    for len in 0..content.len() {
+        fs::write(&path, &content[..len]).unwrap();
+        let loaded = SegmentHealthMap::load(&path);
+        if path.exists() {
+            // Prefixes that still parse are kept as they are
+            assert_eq!(
+                serde_json::from_str::<SegmentHealthMap>(&content[..len]).ok(),
+                Some(loaded)
+            );
+        } else {
+            assert_eq!(loaded, SegmentHealthMap::default());
+            corrupt += 1;
+        }
+    }
+    assert!(corrupt > 0);
+
+    let log = RecoveryLog::load(&RecoveryLog::path_for(&path));
+    assert_eq!(log.recovery_count, corrupt);
+    assert_eq!(log.files["segment-health.json"].count, corrupt);
+}
+
+/// Load every prefix of `content` from `path`; returns how many were quarantined
+fn fuzz_truncations<T, F>(path: &Path, content: &str, load: F) -> u64
+where
+    T: DeserializeOwned + PartialEq + Debug,
+    F: Fn(&Path) -> Option<T>,
+{
+    let mut corrupt = 0;
+    for len in 0..content.len() {
+        fs::write(path, &content[..len]).unwrap();
+        let loaded = load(path);
+        if path.exists() {
+            assert_eq!(serde_json::from_str::<T>(&content[..len]).ok(), loaded);
+        } else {
+            assert_eq!(loaded, None);
+            corrupt += 1;
+        }
+    }
+    corrupt
+}
+
+#[test]
+fn test_truncation_fuzz_covers_heartbeat() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("heartbeat.json");
+    let content = serde_json::to_string(&Heartbeat::now()).unwrap();
+
+    let corrupt = fuzz_truncations(&path, &content, |path| {
+        HeartbeatWriter::new(path.to_path_buf()).load()
+    });
+    assert!(corrupt > 0);
+    let log = RecoveryLog::load(&RecoveryLog::path_for(&path));
+    assert_eq!(log.files["heartbeat.json"].count, corrupt);
+}
+
+#[cfg(feature = "network-monitoring")]
+#[test]
+fn test_truncation_fuzz_covers_daemon_lease() {
+    use ccstatus::core::network::daemon::DaemonLease;
+    use ccstatus::core::network::probe_profile::ProbeProfile;
+
+    let temp_dir = TempDir::new().unwrap();
+    let path = DaemonLease::path_in(temp_dir.path());
+    let lease = DaemonLease::new(ProbeProfile::Standard, 1_737_800_000);
+    let content = serde_json::to_string(&lease).unwrap();
+
+    let corrupt = fuzz_truncations(&path, &content, DaemonLease::load);
+    assert!(corrupt > 0);
+    let log = RecoveryLog::load(&RecoveryLog::path_for(&path));
+    assert_eq!(log.files["ccstatus-daemon.json"].count, corrupt);
+}
+
+#[test]
+fn test_byte_flip_fuzz_loads_valid_or_default() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("segment-health.json");
+    let content = serde_json::to_vec(&health_map()).unwrap();
+
+    // Deterministic LCG so failures are reproducible
+    let mut seed: u64 = 0x5eed;
+    for _ in 0..500 {
+        let mut bytes = content.clone();
+        for _ in 0..3 {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let index = (seed >> 33) as usize % bytes.len();
+            bytes[index] = (seed >> 17) as u8;
+        }
+        fs::write(&path, &bytes).unwrap();
+
+        let loaded = SegmentHealthMap::load(&path);
+        match serde_json::from_slice::<SegmentHealthMap>(&bytes) {
+            Ok(parsed) => assert_eq!(loaded, parsed),
+            Err(_) => {
+                // Invalid UTF-8 included: quarantined and reset
+                assert!(!path.exists());
+                assert_eq!(loaded, SegmentHealthMap::default());
+            }
+        }
+    }
+}
+
+#[test]
+fn test_parse_json_recovers_content_read_elsewhere() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("dns-cache.json");
+    fs::write(&path, "[not json").unwrap();
+
+    let parsed: Option<serde_json::Value> = recovery::parse_json(&path, b"[not json");
+    assert_eq!(parsed, None);
+    assert_eq!(quarantined(temp_dir.path()).len(), 1);
+}
@@ -0,0 +1,113 @@
+use ccstatus::config::{Config, SegmentConfig, SegmentId};
+use ccstatus::core::segments::SegmentData;
+use ccstatus::core::statusline::responsive::{
+    abbreviate_path, compress_breakdown, drop_priority, visible_width, width_from,
+};
+use ccstatus::core::StatusLineGenerator;
+use std::collections::HashMap;
+
+fn segment(config: &Config, id: SegmentId, primary: &str) -> (SegmentConfig, SegmentData) {
+    let mut segment_config = config
+        .segments
+        .iter()
+        .find(|s| s.id == id)
+        .cloned()
+        .unwrap();
+    segment_config.enabled = true;
+    (
+        segment_config,
+        SegmentData {
+            primary: primary.to_string(),
+            secondary: String::new(),
+            metadata: HashMap::new(),
+        },
+    )
+}
+
+fn first_line_segments(config: &Config) -> Vec<(SegmentConfig, SegmentData)> {
+    vec![
+        segment(config, SegmentId::Model, "Sonnet 4"),
+        segment(config, SegmentId::Directory, "a-rather-long-project-name"),
+        segment(config, SegmentId::Git, "main ✓"),
+        segment(config, SegmentId::Usage, "42.1% · 84.2k tokens"),
+    ]
+}
+
+#[test]
+fn test_width_prefers_input_over_columns() {
+    assert_eq!(width_from(Some(100), Some("80".to_string())), Some(100));
+    assert_eq!(width_from(None, Some(" 80 ".to_string())), Some(80));
+    assert_eq!(width_from(None, Some("wide".to_string())), None);
+    assert_eq!(width_from(Some(0), None), None);
+    assert_eq!(width_from(None, None), None);
+}
+
+#[test]
+fn test_compress_breakdown_keeps_total() {
+    assert_eq!(
+        compress_breakdown("🟡 P95:900ms DNS:20ms|TCP:30ms|TLS:40ms|TTFB:1324ms|Total:2650ms"),
+        "🟡 P95:900ms Total:2650ms"
+    );
+    assert_eq!(compress_breakdown("🔴 Total:2650ms"), "🔴 Total:2650ms");
+}
+
+#[test]
+fn test_visible_width_counts_terminal_columns() {
+    assert_eq!(visible_width("\x1b[1;32mmain\x1b[0m ✓"), 6);
+    // CJK and emoji glyphs take two columns each
+    assert_eq!(visible_width("项目"), 4);
+    assert_eq!(visible_width("\x1b[31m🟢 ok\x1b[0m"), 5);
+}
+
+#[test]
+fn test_abbreviate_path_marks_cut() {
+    assert_eq!(abbreviate_path("CCstatus", 12), "CCstatus");
+    assert_eq!(abbreviate_path("a-rather-long-project", 8), "a-rathe…");
+    assert_eq!(
+        abbreviate_path("a-rather-long-project", 8).chars().count(),
+        8
+    );
+}
+
+#[test]
+fn test_update_segment_dropped_before_model() {
+    assert!(drop_priority(SegmentId::Update) < drop_priority(SegmentId::Stopwatch));
+    assert!(drop_priority(SegmentId::Git) < drop_priority(SegmentId::Model));
+}
+
+#[test]
+fn test_generate_without_width_is_unchanged() {
+    let config = Config::default();
+    let unbounded = StatusLineGenerator::new(config.clone()).generate(first_line_segments(&config));
+    assert!(unbounded.contains("a-rather-long-project-name"));
+    assert!(unbounded.contains("84.2k tokens"));
+}
+
+#[test]
+fn test_generate_shrinks_to_width() {
+    let config = Config::default();
+    let full = StatusLineGenerator::new(config.clone()).generate(first_line_segments(&config));
+    let full_width = visible_width(&full);
+
+    // Just too narrow: abbreviating the directory is enough
+    let output = StatusLineGenerator::new(config.clone())
+        .with_max_width(Some(full_width - 5))
+        .generate(first_line_segments(&config));
+    assert!(visible_width(&output) <= full_width - 5);
+    assert!(output.contains("a-rather-lo…"));
+    assert!(output.contains("84.2k tokens"));
+
+    // Much narrower: low-priority segments go, the model stays
+    let output = StatusLineGenerator::new(config.clone())
+        .with_max_width(Some(30))
+        .generate(first_line_segments(&config));
+    assert!(visible_width(&output) <= 30);
+    assert!(!output.contains("84.2k"));
+    assert!(output.contains("Sonnet 4"));
+
+    // A single segment is never dropped, even when it cannot fit
+    let output = StatusLineGenerator::new(config.clone())
+        .with_max_width(Some(3))
+        .generate(first_line_segments(&config));
+    assert!(output.contains("Sonnet 4"));
+}
@@ -0,0 +1,76 @@
+use ccstatus::core::scheduler::{
+    run_maintenance_at, Cadence, JobLedger, Schedule, QUARANTINE_GC_JOB, QUARANTINE_KEEP,
+};
+use std::fs;
+use std::time::{Duration, SystemTime};
+use tempfile::TempDir;
+
+#[test]
+fn test_schedule_due_boundaries_and_jitter() {
+    let hourly = Schedule::every(3600);
+    assert!(hourly.is_due("job", None, 0), "Never run is due now");
+    assert!(!hourly.is_due("job", Some(1_000), 1_000 + 3599));
+    assert!(hourly.is_due("job", Some(1_000), 1_000 + 3600));
+    // A last run in the future (clock moved back) is not due
+    assert!(!hourly.is_due("job", Some(10_000), 5_000));
+
+    let jittered = hourly.with_jitter(360);
+    let delay = jittered.jitter_for("state-sync:a");
+    assert!(delay <= 360);
+    assert_eq!(delay, jittered.jitter_for("state-sync:a"), "Stable per key");
+    assert_eq!(
+        jittered.next_run("state-sync:a", Some(0)),
+        Some(3600 + delay as i64)
+    );
+    let delays: Vec<_> = (0..8)
+        .map(|i| jittered.jitter_for(&format!("state-sync:{}", i)))
+        .collect();
+    assert!(delays.iter().any(|&d| d != delays[0]), "Spread across keys");
+}
+
+#[test]
+fn test_cadence_windows_and_stride() {
+    let green = Cadence::new(300_000, 10_000);
+    assert_eq!(green.open_window(0), Some(0));
+    assert_eq!(green.open_window(9_999), Some(0));
+    assert_eq!(green.open_window(10_000), None);
+    assert_eq!(green.open_window(600_500), Some(2));
+
+    // Idle stride keeps the unstrided IDs
+    let idle = green.with_stride(2);
+    assert_eq!(idle.open_window(300_000), None);
+    assert_eq!(idle.open_window(600_000), Some(2));
+
+    assert_eq!(Cadence::new(0, 1_000).open_window(500), None);
+}
+
+#[test]
+fn test_maintenance_prunes_old_quarantine_once_per_day() {
+    let temp_dir = TempDir::new().unwrap();
+    let ledger_path = temp_dir.path().join("scheduler.json");
+    let quarantined = temp_dir.path().join("sla.json.corrupt-20250101T000000");
+    let state = temp_dir.path().join("sla.json");
+    fs::write(&quarantined, "{").unwrap();
+    fs::write(&state, "{}").unwrap();
+
+    // Not old enough yet
+    let now = SystemTime::now();
+    assert_eq!(
+        run_maintenance_at(&ledger_path, now),
+        vec![QUARANTINE_GC_JOB]
+    );
+    assert!(quarantined.exists());
+
+    // Within the day the job is not due again
+    let later = now + QUARANTINE_KEEP + Duration::from_secs(60);
+    let ledger = JobLedger::load(&ledger_path);
+    assert!(ledger.last_run.contains_key(QUARANTINE_GC_JOB));
+    assert!(run_maintenance_at(&ledger_path, now + Duration::from_secs(3600)).is_empty());
+
+    assert_eq!(
+        run_maintenance_at(&ledger_path, later),
+        vec![QUARANTINE_GC_JOB]
+    );
+    assert!(!quarantined.exists());
+    assert!(state.exists(), "Live state files are kept");
+}
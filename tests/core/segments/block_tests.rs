@@ -0,0 +1,87 @@
+use ccstatus::core::i18n::Locale;
+use ccstatus::core::segments::block::UsageBlock;
+use ccstatus::core::segments::{BlockSegment, Segment};
+use tempfile::TempDir;
+
+use crate::common::{assistant, at, input_for, transcript};
+
+#[test]
+fn test_track_starts_blocks_on_the_hour() {
+    let block = UsageBlock::track(
+        None,
+        [
+            at("2025-01-25T14:10:00Z"),
+            at("2025-01-25T09:42:00Z"),
+            at("2025-01-25T13:59:00Z"),
+        ],
+    )
+    .unwrap();
+    assert_eq!(
+        block.started_at,
+        at("2025-01-25T14:00:00Z"),
+        "14:10 falls after the 09:00 block ended at 14:00"
+    );
+    assert_eq!(block.ends_at, at("2025-01-25T19:00:00Z"));
+
+    assert!(UsageBlock::track(None, []).is_none());
+}
+
+#[test]
+fn test_track_continues_persisted_block() {
+    let persisted = UsageBlock::starting_at(at("2025-01-25T08:30:00Z"));
+    // A new session's first request lands inside the persisted block
+    let block = UsageBlock::track(Some(persisted.clone()), [at("2025-01-25T11:15:00Z")]);
+    assert_eq!(block, Some(persisted.clone()));
+
+    let next = UsageBlock::track(Some(persisted), [at("2025-01-25T13:05:00Z")]).unwrap();
+    assert_eq!(next.started_at, at("2025-01-25T13:00:00Z"));
+}
+
+#[test]
+fn test_segment_shows_time_left_and_persists_block() {
+    let temp_dir = TempDir::new().unwrap();
+    let state_path = temp_dir.path().join("usage-block.json");
+    let file = transcript(&[
+        assistant("a", "2025-01-25T14:10:00Z"),
+        assistant("b", "2025-01-25T15:30:00Z"),
+    ]);
+
+    let data = BlockSegment::new()
+        .with_state_path(Some(state_path.clone()))
+        .with_now(at("2025-01-25T16:47:00Z"))
+        .collect(&input_for(&file))
+        .unwrap();
+    assert_eq!(data.primary, "2h 13m left");
+    assert_eq!(data.metadata["remaining_secs"], "7980");
+    assert_eq!(data.metadata["elapsed_percent"], "55");
+    assert_eq!(
+        UsageBlock::load(&state_path).unwrap().started_at,
+        at("2025-01-25T14:00:00Z")
+    );
+
+    // After a restart the persisted block is kept even if the new transcript starts later
+    let later = transcript(&[assistant("c", "2025-01-25T17:20:00Z")]);
+    let data = BlockSegment::new()
+        .with_state_path(Some(state_path.clone()))
+        .with_now(at("2025-01-25T17:30:00Z"))
+        .collect(&input_for(&later))
+        .unwrap();
+    assert_eq!(data.primary, "1h 30m left");
+
+    let data = BlockSegment::new()
+        .with_state_path(Some(state_path))
+        .with_locale(Locale::Zh)
+        .with_now(at("2025-01-25T17:30:00Z"))
+        .collect(&input_for(&later))
+        .unwrap();
+    assert_eq!(data.primary, "1h 30m 后结束");
+}
+
+#[test]
+fn test_segment_hidden_after_block_ends() {
+    let file = transcript(&[assistant("a", "2025-01-25T09:10:00Z")]);
+    let segment = BlockSegment::new()
+        .with_state_path(None)
+        .with_now(at("2025-01-25T14:00:00Z"));
+    assert!(segment.collect(&input_for(&file)).is_none());
+}
@@ -0,0 +1,124 @@
+use ccstatus::config::{AnsiColor, Config, SegmentConfig, SegmentId};
+use ccstatus::core::segments::burn_rate::{level_colors, BurnLevel, BurnUnit};
+use ccstatus::core::segments::{BurnRateSegment, Segment, SegmentData};
+use serde_json::json;
+use std::collections::HashMap;
+
+use crate::common::{assistant_with_usage, at, input_for, transcript};
+
+/// Sonnet message at `timestamp`: 4000 output tokens ($0.06) and 100k cache reads ($0.03)
+fn sonnet(id: &str, timestamp: &str) -> String {
+    assistant_with_usage(
+        id,
+        timestamp,
+        r#"{"input_tokens":0,"output_tokens":4000,"cache_read_input_tokens":100000}"#,
+    )
+}
+
+#[test]
+fn test_cost_rate_over_rolling_window() {
+    let file = transcript(&[
+        sonnet("old", "2025-01-25T16:00:00Z"),
+        sonnet("a", "2025-01-25T17:30:00Z"),
+        sonnet("b", "2025-01-25T17:45:00Z"),
+    ]);
+    // Two $0.09 messages in the last hour, 30 minutes apart from now
+    let data = BurnRateSegment::new()
+        .with_now(at("2025-01-25T18:00:00Z"))
+        .collect(&input_for(&file))
+        .unwrap();
+    assert_eq!(data.primary, "$0.36/h");
+    assert_eq!(data.metadata["span_secs"], "1800");
+    assert_eq!(data.metadata["level"], "normal");
+}
+
+#[test]
+fn test_token_rate_excludes_cache_reads() {
+    let file = transcript(&[sonnet("a", "2025-01-25T17:50:00Z")]);
+    let data = BurnRateSegment::new()
+        .with_unit(BurnUnit::Tokens)
+        .with_now(at("2025-01-25T18:00:00Z"))
+        .collect(&input_for(&file))
+        .unwrap();
+    // 4000 tokens over 10 minutes
+    assert_eq!(data.primary, "24k tok/h");
+
+    assert_eq!(BurnRateSegment::format_tokens(950.0), "950");
+    assert_eq!(BurnRateSegment::format_tokens(1500.0), "1.5k");
+    assert_eq!(BurnRateSegment::format_tokens(2_400_000.0), "2.4M");
+}
+
+#[test]
+fn test_short_span_and_idle_window() {
+    let file = transcript(&[sonnet("a", "2025-01-25T17:59:30Z")]);
+    let segment = BurnRateSegment::new().with_now(at("2025-01-25T18:00:00Z"));
+    let data = segment.collect(&input_for(&file)).unwrap();
+    assert_eq!(
+        data.metadata["span_secs"], "300",
+        "A single fresh request is spread over the minimum span"
+    );
+    assert_eq!(data.primary, "$1.08/h");
+
+    let idle = BurnRateSegment::new().with_now(at("2025-01-25T19:00:00Z"));
+    assert!(idle.collect(&input_for(&file)).is_none());
+}
+
+#[test]
+fn test_options_and_levels() {
+    let mut options = HashMap::new();
+    options.insert("unit".to_string(), json!("tokens"));
+    options.insert("window_mins".to_string(), json!(15));
+    options.insert("warn_at".to_string(), json!(20000));
+    options.insert("critical_at".to_string(), json!(50000));
+    let segment = BurnRateSegment::from_options(&options);
+
+    assert_eq!(segment.level(19_999.0), BurnLevel::Normal);
+    assert_eq!(segment.level(20_000.0), BurnLevel::Warning);
+    assert_eq!(segment.level(80_000.0), BurnLevel::Critical);
+
+    let file = transcript(&[
+        sonnet("old", "2025-01-25T17:40:00Z"),
+        sonnet("a", "2025-01-25T17:50:00Z"),
+    ]);
+    let data = segment
+        .with_now(at("2025-01-25T18:00:00Z"))
+        .collect(&input_for(&file))
+        .unwrap();
+    assert_eq!(
+        data.primary, "24k tok/h",
+        "Only the 15-minute window counts"
+    );
+    assert_eq!(data.metadata["level"], "warning");
+}
+
+#[test]
+fn test_level_colors() {
+    let base = Config::default().segments[0].clone();
+    let mut config = SegmentConfig {
+        id: SegmentId::BurnRate,
+        ..base
+    };
+    config.options.insert(
+        "critical_color".to_string(),
+        json!({"r": 255, "g": 0, "b": 0}),
+    );
+    let data = |level: &str| SegmentData {
+        primary: "$4.20/h".to_string(),
+        secondary: String::new(),
+        metadata: HashMap::from([("level".to_string(), level.to_string())]),
+    };
+
+    let normal = level_colors(&config, &data("normal"));
+    assert_eq!(
+        format!("{:?}", normal.colors.text),
+        format!("{:?}", config.colors.text)
+    );
+    assert!(matches!(
+        level_colors(&config, &data("warning")).colors.text,
+        Some(AnsiColor::Color16 { c16: 11 })
+    ));
+    assert!(matches!(
+        level_colors(&config, &data("critical")).colors.icon,
+        Some(AnsiColor::Rgb { r: 255, g: 0, b: 0 })
+    ));
+}
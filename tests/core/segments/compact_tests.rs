@@ -0,0 +1,104 @@
+use ccstatus::config::InputData;
+use ccstatus::core::i18n::Locale;
+use ccstatus::core::segments::{CompactEstimate, CompactSegment, Segment};
+use serde_json::json;
+use std::collections::HashMap;
+use tempfile::NamedTempFile;
+
+use crate::common::{input_for, transcript};
+
+/// Transcript whose last assistant message has `context_tokens` of context
+fn context_transcript(context_tokens: u64) -> NamedTempFile {
+    transcript(&[format!(
+        r#"{{"type":"assistant","message":{{"usage":{{"input_tokens":10,"output_tokens":0,"cache_read_input_tokens":{}}}}}}}"#,
+        context_tokens - 10
+    )])
+}
+
+fn input_with_model(file: &NamedTempFile, model_id: &str) -> InputData {
+    let mut input = input_for(file);
+    input.model.id = model_id.to_string();
+    input.model.display_name = String::new();
+    input
+}
+
+#[test]
+fn test_compact_estimate() {
+    let estimate = CompactEstimate::new(124_000, 200_000, 45_000);
+    assert_eq!(estimate.threshold_tokens, 155_000);
+    assert_eq!(estimate.remaining_tokens(), 31_000);
+    assert_eq!(estimate.percent(), 80.0);
+
+    let over = CompactEstimate::new(160_000, 200_000, 45_000);
+    assert_eq!(over.remaining_tokens(), 0);
+    assert!(over.percent() > 100.0);
+}
+
+#[test]
+fn test_warning_escalates_toward_threshold() {
+    let segment = CompactSegment::new();
+
+    let far = context_transcript(60_000);
+    assert!(
+        segment
+            .collect(&input_with_model(&far, "claude-sonnet-4"))
+            .is_none(),
+        "hidden far from compaction"
+    );
+
+    let near = context_transcript(130_000);
+    let data = segment
+        .collect(&input_with_model(&near, "claude-sonnet-4"))
+        .unwrap();
+    assert_eq!(data.primary, "25k to compact");
+    assert_eq!(data.metadata["level"], "warning");
+
+    let due = context_transcript(150_000);
+    let data = segment
+        .collect(&input_with_model(&due, "claude-sonnet-4"))
+        .unwrap();
+    assert_eq!(data.metadata["level"], "critical");
+
+    let over = context_transcript(170_000);
+    let data = segment
+        .collect(&input_with_model(&over, "claude-sonnet-4"))
+        .unwrap();
+    assert_eq!(data.primary, "compact due");
+
+    let zh = CompactSegment::new().with_locale(Locale::Zh);
+    let data = zh
+        .collect(&input_with_model(&over, "claude-sonnet-4"))
+        .unwrap();
+    assert_eq!(data.primary, "即将压缩");
+    let data = zh
+        .collect(&input_with_model(&near, "claude-sonnet-4"))
+        .unwrap();
+    assert_eq!(data.primary, "25k 后压缩");
+
+    // A 1M window is nowhere near compaction at the same usage
+    assert!(segment
+        .collect(&input_with_model(&over, "claude-sonnet-4[1m]"))
+        .is_none());
+}
+
+#[test]
+fn test_options() {
+    let mut options = HashMap::new();
+    options.insert("buffer_tokens".to_string(), json!(100_000));
+    options.insert("warn_at".to_string(), json!(50));
+    let segment = CompactSegment::from_options(&options);
+    let file = context_transcript(60_000);
+    let data = segment
+        .collect(&input_with_model(&file, "claude-sonnet-4"))
+        .unwrap();
+    assert_eq!(data.primary, "40k to compact");
+    assert_eq!(data.metadata["threshold"], "100000");
+    assert_eq!(data.metadata["level"], "warning");
+
+    let data = CompactSegment::new()
+        .with_always_show(true)
+        .collect(&input_with_model(&file, "claude-sonnet-4"))
+        .unwrap();
+    assert_eq!(data.primary, "95k to compact");
+    assert_eq!(data.metadata["level"], "normal");
+}
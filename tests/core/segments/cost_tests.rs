@@ -0,0 +1,127 @@
+use ccstatus::core::format::NumberFormat;
+use ccstatus::core::segments::cost::{session_usage, ModelPrice, ModelTokens, PriceTable};
+use ccstatus::core::segments::{CostSegment, Segment};
+use serde_json::json;
+use std::collections::HashMap;
+
+use crate::common::{input_for, transcript};
+
+const PROMPT: &str = r#"{"type":"user","message":{"role":"user","content":"hi"}}"#;
+// Claude Code writes one line per content block, repeating the message usage
+const SONNET_TEXT: &str = r#"{"type":"assistant","message":{"id":"msg_1","model":"claude-sonnet-4-20250514","content":[{"type":"text"}],"usage":{"input_tokens":1000,"output_tokens":2000,"cache_creation_input_tokens":4000,"cache_read_input_tokens":10000}}}"#;
+const SONNET_TOOL: &str = r#"{"type":"assistant","message":{"id":"msg_1","model":"claude-sonnet-4-20250514","content":[{"type":"tool_use"}],"usage":{"input_tokens":1000,"output_tokens":2000,"cache_creation_input_tokens":4000,"cache_read_input_tokens":10000}}}"#;
+const OPUS: &str = r#"{"type":"assistant","message":{"id":"msg_2","model":"claude-opus-4-1-20250805","usage":{"input_tokens":100,"output_tokens":1000}}}"#;
+const MYSTERY: &str = r#"{"type":"assistant","message":{"id":"msg_3","model":"gpt-5","usage":{"input_tokens":500,"output_tokens":500}}}"#;
+
+#[test]
+fn test_session_usage_sums_per_model_once_per_message() {
+    let file = transcript(&[PROMPT, SONNET_TEXT, SONNET_TOOL, PROMPT, OPUS, "not json"]);
+    let usage = session_usage(file.path());
+
+    assert_eq!(usage.len(), 2);
+    assert_eq!(
+        usage["claude-sonnet-4-20250514"],
+        ModelTokens {
+            input: 1000,
+            output: 2000,
+            cache_write: 4000,
+            cache_read: 10000,
+        }
+    );
+    assert_eq!(usage["claude-opus-4-1-20250805"].output, 1000);
+    assert!(session_usage("/nonexistent/transcript.jsonl").is_empty());
+}
+
+#[test]
+fn test_usage_records_are_parsed_once_per_input() {
+    let file = transcript(&[PROMPT, SONNET_TEXT, SONNET_TOOL, OPUS]);
+    let input = input_for(file.path());
+    let clone = input.clone();
+    assert_eq!(input.usage_records().len(), 2);
+
+    // Clones handed to other segments reuse the records instead of rereading the file
+    let path = file.path().to_path_buf();
+    file.close().unwrap();
+    assert!(!path.exists());
+    assert_eq!(clone.usage_records().len(), 2);
+    assert_eq!(
+        CostSegment::new().collect(&clone).unwrap().metadata["models"],
+        "2"
+    );
+}
+
+#[test]
+fn test_price_lookup_prefers_longest_key() {
+    let table = PriceTable::default();
+    assert_eq!(
+        table.lookup("claude-opus-4-1-20250805").unwrap().output,
+        75.0
+    );
+    assert_eq!(
+        table.lookup("claude-opus-4-5-20251101").unwrap().output,
+        25.0
+    );
+    assert_eq!(table.lookup("Claude-Sonnet-4").unwrap().input, 3.0);
+    assert!(table.lookup("gpt-5").is_none());
+}
+
+#[test]
+fn test_price_options_override_and_extend_defaults() {
+    let mut options = HashMap::new();
+    options.insert(
+        "prices".to_string(),
+        json!({
+            "sonnet": {"input": 2.0, "output": 10.0},
+            "gpt-5": {"input": 1.25, "output": 10.0, "cache_read": 0.125},
+            "broken": {"input": "free"},
+        }),
+    );
+    let table = PriceTable::from_options(&options);
+
+    assert_eq!(
+        table.lookup("claude-sonnet-4").unwrap(),
+        ModelPrice::new(2.0, 10.0)
+    );
+    let gpt = table.lookup("gpt-5").unwrap();
+    assert_eq!(gpt.cache_read, 0.125);
+    assert_eq!(
+        gpt.cache_write, 1.5625,
+        "Omitted cache price derives from input"
+    );
+    assert!(table.lookup("broken").is_none());
+    assert!(table.lookup("claude-opus-4").is_some(), "Defaults kept");
+}
+
+#[test]
+fn test_cost_segment_renders_session_total() {
+    let file = transcript(&[PROMPT, SONNET_TEXT, SONNET_TOOL, OPUS, MYSTERY]);
+    let data = CostSegment::new()
+        .with_reported_usd(Some(0.5))
+        .collect(&input_for(&file))
+        .unwrap();
+
+    // Sonnet: 1000×3 + 2000×15 + 4000×3.75 + 10000×0.30 = 51000 µ$
+    // Opus:   100×15 + 1000×75 = 76500 µ$
+    assert_eq!(data.primary, "$0.13");
+    assert_eq!(data.metadata["usd"], "0.127500");
+    assert_eq!(data.metadata["models"], "3");
+    assert_eq!(data.metadata["input_tokens"], "1600");
+    assert_eq!(data.metadata["unpriced_models"], "gpt-5");
+    assert_eq!(data.metadata["reported_usd"], "0.500000");
+
+    let euros = CostSegment::new()
+        .with_number_format(NumberFormat {
+            currency_symbol: "€".to_string(),
+            decimals: Some(3),
+            ..Default::default()
+        })
+        .collect(&input_for(&file))
+        .unwrap();
+    assert_eq!(euros.primary, "€0.128");
+}
+
+#[test]
+fn test_cost_segment_hidden_without_usage() {
+    let file = transcript(&[PROMPT]);
+    assert!(CostSegment::new().collect(&input_for(&file)).is_none());
+}
@@ -0,0 +1,153 @@
+use ccstatus::config::{Config, CustomSegmentConfig, SegmentId, StyleMode};
+use ccstatus::core::icons::IconSet;
+use ccstatus::core::segments::custom::run_command;
+use ccstatus::core::segments::health::SegmentFailure;
+use ccstatus::core::segments::PendingCustomSegment;
+use ccstatus::core::StatusLineGenerator;
+use std::time::{Duration, Instant};
+
+fn entry(name: &str, command: &str) -> CustomSegmentConfig {
+    toml::from_str(&format!("name = {:?}\ncommand = {:?}", name, command)).unwrap()
+}
+
+#[test]
+fn test_custom_segment_defaults() {
+    let entry = entry("weather", "echo sunny");
+    assert_eq!(entry.timeout_ms, 500);
+    assert_eq!(entry.position, None);
+    assert!(!entry.icon.is_empty());
+}
+
+#[test]
+fn test_custom_segments_config_section() {
+    let mut value = toml::Value::try_from(Config::default()).unwrap();
+    let custom: toml::Value = toml::from_str(
+        r#"
+[[custom_segments]]
+name = "weather"
+command = "curl -s wttr.in?format=3"
+timeout_ms = 800
+position = 1
+"#,
+    )
+    .unwrap();
+    value.as_table_mut().unwrap().insert(
+        "custom_segments".to_string(),
+        custom["custom_segments"].clone(),
+    );
+
+    let config: Config = toml::from_str(&toml::to_string(&value).unwrap()).unwrap();
+    assert_eq!(config.custom_segments.len(), 1);
+    assert_eq!(config.custom_segments[0].timeout_ms, 800);
+    assert_eq!(config.custom_segments[0].position, Some(1));
+    assert!(config.check().is_ok());
+
+    let mut duplicated = config.clone();
+    duplicated
+        .custom_segments
+        .push(config.custom_segments[0].clone());
+    assert!(duplicated.check().is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_command_takes_first_non_empty_line() {
+    let output = run_command(
+        "printf '\\n  sunny  \\nrain\\n'",
+        None,
+        Duration::from_secs(5),
+    );
+    assert_eq!(output, Ok(Some("sunny".to_string())));
+
+    // No output hides the segment
+    assert_eq!(run_command("true", None, Duration::from_secs(5)), Ok(None));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_command_failures() {
+    let failed = run_command("echo partial; exit 3", None, Duration::from_secs(5));
+    assert!(matches!(failed, Err(SegmentFailure::Command(_))));
+
+    let started = Instant::now();
+    let timed_out = run_command("sleep 5", None, Duration::from_millis(50));
+    assert_eq!(
+        timed_out,
+        Err(SegmentFailure::Timeout(Duration::from_millis(50)))
+    );
+    assert!(started.elapsed() < Duration::from_secs(2));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_command_times_out_on_backgrounded_child_holding_stdout() {
+    let started = Instant::now();
+    let timed_out = run_command("sleep 8 & echo hi", None, Duration::from_millis(300));
+    assert_eq!(
+        timed_out,
+        Err(SegmentFailure::Timeout(Duration::from_millis(300)))
+    );
+    assert!(started.elapsed() < Duration::from_secs(2));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_custom_segments_run_concurrently() {
+    let mut slow = entry("slow", "sleep 0.3; echo done");
+    slow.timeout_ms = 5000;
+
+    let started = Instant::now();
+    let pending: Vec<_> = (0..3)
+        .map(|_| PendingCustomSegment::spawn(&slow, "/"))
+        .collect();
+    for segment in &pending {
+        assert_eq!(segment.wait(), Ok(Some("done".to_string())));
+    }
+    assert!(started.elapsed() < Duration::from_millis(800));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_custom_segment_runs_in_workspace_dir() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let pending = PendingCustomSegment::spawn(&entry("cwd", "pwd"), &dir.path().to_string_lossy());
+    let output = pending.wait().unwrap().unwrap();
+    assert!(output.ends_with(&*dir.path().file_name().unwrap().to_string_lossy()));
+}
+
+#[test]
+fn test_failure_placeholder_names_the_entry() {
+    let pending = PendingCustomSegment::spawn(&entry("weather", "exit 1"), "/");
+    let failure = pending.wait().unwrap_err();
+    let data = pending.failure_placeholder(&failure, IconSet::Ascii);
+
+    assert!(data.primary.ends_with("weather"));
+    assert!(data.metadata.contains_key("error"));
+    assert_eq!(pending.health_key(), "custom:weather");
+    assert_eq!(pending.segment_config().id, SegmentId::Custom);
+}
+
+#[test]
+fn test_template_renders_all_custom_segments() {
+    let mut config = Config::default();
+    config.style.mode = StyleMode::Plain;
+    config.template = Some("[{custom}]".to_string());
+
+    let segments = ["a", "b"]
+        .iter()
+        .map(|name| {
+            let mut pending = entry(name, "true");
+            pending.icon = "*".to_string();
+            let pending = PendingCustomSegment::spawn(&pending, "/");
+            (
+                pending.segment_config(),
+                pending.segment_data(name.to_uppercase()),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        StatusLineGenerator::new(config).generate(segments),
+        "[* A * B]"
+    );
+}
@@ -0,0 +1,119 @@
+use ccstatus::core::segments::diff_stat::{count_changes, DiffStatState};
+use ccstatus::core::segments::{DiffStatSegment, Segment};
+use serde_json::json;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use tempfile::TempDir;
+
+use crate::common::input_for;
+
+/// User entry carrying an Edit result that replaces `removed` lines with `added` ones
+fn edit_result(file: &str, added: usize, removed: usize) -> String {
+    let mut lines = vec![" context".to_string()];
+    lines.extend((0..removed).map(|i| format!("-old {}", i)));
+    lines.extend((0..added).map(|i| format!("+new {}", i)));
+    json!({
+        "type": "user",
+        "message": {"role": "user", "content": [{"type": "tool_result", "tool_use_id": "toolu_1"}]},
+        "toolUseResult": {
+            "filePath": file,
+            "oldString": "old",
+            "newString": "new",
+            "structuredPatch": [{"oldStart": 1, "oldLines": removed + 1, "newStart": 1, "newLines": added + 1, "lines": lines}]
+        }
+    })
+    .to_string()
+}
+
+fn append(path: &Path, text: &str) {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .unwrap();
+    write!(file, "{}", text).unwrap();
+}
+
+#[test]
+fn test_count_changes_from_tool_results() {
+    let created = json!({"type": "create", "filePath": "/p/new.rs", "content": "a\nb\nc\n", "structuredPatch": []});
+    assert_eq!(count_changes(&created), (3, 0));
+
+    let entry: serde_json::Value = serde_json::from_str(&edit_result("/p/lib.rs", 2, 5)).unwrap();
+    assert_eq!(count_changes(&entry["toolUseResult"]), (2, 5));
+
+    // Other tools' results and error strings count nothing
+    assert_eq!(
+        count_changes(&json!({"stdout": "+1\n-1", "stderr": ""})),
+        (0, 0)
+    );
+    assert_eq!(count_changes(&json!("Error: file not found")), (0, 0));
+}
+
+#[test]
+fn test_running_counter_reads_only_the_tail() {
+    let temp_dir = TempDir::new().unwrap();
+    let transcript = temp_dir.path().join("session.jsonl");
+    let state_path = temp_dir.path().join("diff-stat.json");
+    let segment = DiffStatSegment::new().with_state_path(Some(state_path.clone()));
+
+    append(&transcript, "{\"type\":\"assistant\"}\n");
+    assert!(
+        segment.collect(&input_for(&transcript)).is_none(),
+        "Hidden before any edit"
+    );
+
+    append(&transcript, &format!("{}\n", edit_result("/p/a.rs", 10, 2)));
+    let data = segment.collect(&input_for(&transcript)).unwrap();
+    assert_eq!(data.primary, "+10 -2");
+
+    // A half-written line waits for its newline
+    let next = edit_result("/p/b.rs", 3, 1);
+    let (head, tail) = next.split_at(40);
+    append(&transcript, head);
+    assert_eq!(
+        segment.collect(&input_for(&transcript)).unwrap().primary,
+        "+10 -2"
+    );
+    append(&transcript, &format!("{}\n", tail));
+    let data = segment.collect(&input_for(&transcript)).unwrap();
+    assert_eq!(data.primary, "+13 -3");
+    assert_eq!(data.metadata["added"], "13");
+
+    let state = DiffStatState::load(&state_path);
+    let session = &state.sessions[&transcript.to_string_lossy().to_string()];
+    assert_eq!(session.offset, fs::metadata(&transcript).unwrap().len());
+    assert_eq!((session.added, session.removed), (13, 3));
+}
+
+#[test]
+fn test_rewritten_transcript_is_counted_afresh() {
+    let temp_dir = TempDir::new().unwrap();
+    let transcript = temp_dir.path().join("session.jsonl");
+    let segment =
+        DiffStatSegment::new().with_state_path(Some(temp_dir.path().join("diff-stat.json")));
+
+    append(
+        &transcript,
+        &format!("{}\n", edit_result("/p/a.rs", 40, 40)),
+    );
+    assert_eq!(
+        segment.collect(&input_for(&transcript)).unwrap().primary,
+        "+40 -40"
+    );
+
+    fs::write(&transcript, format!("{}\n", edit_result("/p/a.rs", 1, 0))).unwrap();
+    assert_eq!(
+        segment.collect(&input_for(&transcript)).unwrap().primary,
+        "+1 -0"
+    );
+
+    // Sessions are kept apart
+    let other = temp_dir.path().join("other.jsonl");
+    append(&other, &format!("{}\n", edit_result("/p/b.rs", 0, 7)));
+    assert_eq!(
+        segment.collect(&input_for(&other)).unwrap().primary,
+        "+0 -7"
+    );
+}
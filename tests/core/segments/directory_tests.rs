@@ -0,0 +1,160 @@
+use ccstatus::core::segments::{DirectorySegment, PathStyle, RemoteContext, Segment};
+use serde_json::json;
+use std::collections::HashMap;
+use tempfile::TempDir;
+
+use crate::common::create_test_input_data;
+
+fn detect(vars: &[(&str, &str)]) -> Option<RemoteContext> {
+    let vars: HashMap<String, String> = vars
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    RemoteContext::from_env(|name| vars.get(name).cloned())
+}
+
+#[test]
+fn test_remote_context_detection() {
+    assert_eq!(detect(&[]), None);
+    assert_eq!(
+        detect(&[
+            ("SSH_CONNECTION", "10.0.0.2 51234 10.0.0.9 22"),
+            ("HOSTNAME", "buildbox")
+        ]),
+        Some(RemoteContext::Ssh {
+            host: "buildbox".to_string()
+        })
+    );
+    // Without HOSTNAME the server address from SSH_CONNECTION is used
+    assert_eq!(
+        detect(&[("SSH_CONNECTION", "10.0.0.2 51234 10.0.0.9 22")]),
+        Some(RemoteContext::Ssh {
+            host: "10.0.0.9".to_string()
+        })
+    );
+    // A dev container reached over SSH reports the container
+    assert_eq!(
+        detect(&[
+            ("REMOTE_CONTAINERS", "true"),
+            ("HOSTNAME", "my-app"),
+            ("SSH_CONNECTION", "10.0.0.2 51234 10.0.0.9 22")
+        ]),
+        Some(RemoteContext::Container {
+            name: "my-app".to_string()
+        })
+    );
+    assert_eq!(
+        detect(&[("CODESPACES", "true"), ("CODESPACE_NAME", "fluffy-space")])
+            .map(|remote| remote.badge()),
+        Some("container:fluffy-space".to_string())
+    );
+    assert_eq!(detect(&[("REMOTE_CONTAINERS", "false")]), None);
+}
+
+#[test]
+fn test_directory_segment_remote_badge() {
+    let input = create_test_input_data();
+
+    let local = DirectorySegment::new().collect(&input).unwrap();
+    assert_eq!(local.primary, "test");
+    assert!(local.secondary.is_empty());
+
+    let remote = DirectorySegment::new()
+        .with_remote_context(Some(RemoteContext::Ssh {
+            host: "buildbox".to_string(),
+        }))
+        .collect(&input)
+        .unwrap();
+    assert_eq!(remote.primary, "test");
+    assert_eq!(remote.secondary, "ssh:buildbox");
+    assert_eq!(remote.metadata["remote"], "ssh:buildbox");
+}
+
+fn shown(segment: DirectorySegment, current_dir: &str) -> String {
+    let mut input = create_test_input_data();
+    input.workspace.current_dir = current_dir.to_string();
+    segment.collect(&input).unwrap().primary
+}
+
+#[test]
+fn test_path_styles() {
+    let home = || DirectorySegment::new().with_home(Some("/home/me".to_string()));
+    let dir = "/home/me/projects/code/ccstatus";
+
+    assert_eq!(shown(home(), dir), "ccstatus");
+    assert_eq!(
+        shown(home().with_style(PathStyle::Full), dir),
+        "~/projects/code/ccstatus"
+    );
+    assert_eq!(
+        shown(home().with_style(PathStyle::Fish), dir),
+        "~/p/c/ccstatus"
+    );
+    assert_eq!(
+        shown(
+            home().with_style(PathStyle::Fish),
+            "/home/me/.config/ccstatus"
+        ),
+        "~/.c/ccstatus"
+    );
+    assert_eq!(shown(home().with_style(PathStyle::Fish), "/home/me"), "~");
+    // Outside home (and not a sibling with the same prefix) the root stays
+    assert_eq!(
+        shown(home().with_style(PathStyle::Fish), "/home/meg/src/app"),
+        "/h/m/s/app"
+    );
+    assert_eq!(
+        shown(
+            home().with_style(PathStyle::Fish),
+            "C:\\Users\\me\\src\\app"
+        ),
+        "C\\U\\m\\s\\app"
+    );
+}
+
+#[test]
+fn test_max_width_drops_leading_components() {
+    let segment = |width| {
+        DirectorySegment::new()
+            .with_home(Some("/home/me".to_string()))
+            .with_style(PathStyle::Full)
+            .with_max_width(Some(width))
+    };
+    let dir = "/home/me/projects/code/ccstatus";
+
+    assert_eq!(shown(segment(40), dir), "~/projects/code/ccstatus");
+    assert_eq!(shown(segment(16), dir), "…/code/ccstatus");
+    assert_eq!(shown(segment(6), dir), "ccsta…");
+}
+
+#[test]
+fn test_repo_relative_path() {
+    let temp_dir = TempDir::new().unwrap();
+    let repo = temp_dir.path().join("ccstatus");
+    let nested = repo.join("src").join("core");
+    std::fs::create_dir_all(repo.join(".git")).unwrap();
+    std::fs::create_dir_all(&nested).unwrap();
+
+    let mut options = HashMap::new();
+    options.insert("style".to_string(), json!("fish"));
+    options.insert("repo_relative".to_string(), json!(true));
+    let mut input = create_test_input_data();
+    input.workspace.current_dir = nested.to_string_lossy().to_string();
+
+    let data = DirectorySegment::from_options(&options)
+        .collect(&input)
+        .unwrap();
+    assert_eq!(data.primary, "ccstatus/s/core");
+    assert_eq!(data.metadata["repo_root"], repo.to_string_lossy());
+
+    // Outside a repository the style applies to the whole path
+    let outside = temp_dir.path().join("scratch");
+    std::fs::create_dir_all(&outside).unwrap();
+    input.workspace.current_dir = outside.to_string_lossy().to_string();
+    let data = DirectorySegment::from_options(&options)
+        .with_home(None)
+        .collect(&input)
+        .unwrap();
+    assert!(data.primary.ends_with("/scratch"));
+    assert!(!data.metadata.contains_key("repo_root"));
+}
@@ -0,0 +1,101 @@
+use ccstatus::config::InputData;
+use ccstatus::core::segments::env::{NodeVersion, PythonEnv, PythonEnvKind};
+use ccstatus::core::segments::{EnvSegment, Segment};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+use crate::common::create_test_input_data;
+
+fn python(env: &[(&str, &str)]) -> Option<PythonEnv> {
+    let env: HashMap<String, String> = env
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    PythonEnv::from_env(|name| env.get(name).cloned())
+}
+
+fn input_in(dir: &Path) -> InputData {
+    let mut input = create_test_input_data();
+    input.workspace.current_dir = dir.to_string_lossy().to_string();
+    input
+}
+
+#[test]
+fn test_python_env_detection() {
+    assert_eq!(python(&[]), None);
+
+    let venv = python(&[("VIRTUAL_ENV", "/home/dev/proj/.venv")]).unwrap();
+    assert_eq!(venv.kind, PythonEnvKind::Venv);
+    assert_eq!(venv.name, ".venv");
+
+    // The prompt name wins, and an active venv wins over conda
+    let venv = python(&[
+        ("VIRTUAL_ENV", "/home/dev/proj/.venv"),
+        ("VIRTUAL_ENV_PROMPT", "(proj) "),
+        ("CONDA_DEFAULT_ENV", "base"),
+    ])
+    .unwrap();
+    assert_eq!(venv.name, "proj");
+
+    let conda = python(&[("CONDA_DEFAULT_ENV", "ml")]).unwrap();
+    assert_eq!(conda.kind, PythonEnvKind::Conda);
+    assert_eq!(conda.name, "ml");
+}
+
+#[test]
+fn test_node_version_search_stops_at_repository_root() {
+    let temp_dir = TempDir::new().unwrap();
+    let repo = temp_dir.path().join("repo");
+    let package = repo.join("packages").join("web");
+    fs::create_dir_all(repo.join(".git")).unwrap();
+    fs::create_dir_all(&package).unwrap();
+    fs::write(temp_dir.path().join(".nvmrc"), "16\n").unwrap();
+    assert_eq!(NodeVersion::detect(&package), None, "Outside the repo");
+
+    fs::write(package.join("package.json"), r#"{"name": "web"}"#).unwrap();
+    fs::write(repo.join(".nvmrc"), "v20.11.0\n").unwrap();
+    let node = NodeVersion::detect(&package).unwrap();
+    assert_eq!(node.version, "20.11.0");
+    assert_eq!(node.source, ".nvmrc");
+
+    fs::write(
+        package.join("package.json"),
+        r#"{"name": "web", "engines": {"node": ">=18"}}"#,
+    )
+    .unwrap();
+    let node = NodeVersion::detect(&package).unwrap();
+    assert_eq!(node.version, ">=18");
+    assert_eq!(node.source, "package.json");
+}
+
+#[test]
+fn test_env_segment_rendering() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir(temp_dir.path().join(".git")).unwrap();
+    let input = input_in(temp_dir.path());
+
+    let segment = EnvSegment::new().with_python(None);
+    assert!(
+        segment.collect(&input).is_none(),
+        "Hidden with nothing active"
+    );
+
+    // conda's base env is always on, so it only shows when asked
+    let base = Some(PythonEnv {
+        kind: PythonEnvKind::Conda,
+        name: "base".to_string(),
+    });
+    let segment = EnvSegment::new().with_python(base.clone());
+    assert!(segment.collect(&input).is_none());
+    let segment = EnvSegment::new().with_python(base).with_conda_base(true);
+    assert_eq!(segment.collect(&input).unwrap().primary, "py base");
+
+    fs::write(temp_dir.path().join(".node-version"), "22\n").unwrap();
+    let segment = EnvSegment::new().with_python(python(&[("VIRTUAL_ENV", "/p/.venv")]));
+    let data = segment.collect(&input).unwrap();
+    assert_eq!(data.primary, "py .venv · node 22");
+    assert_eq!(data.metadata["python_kind"], "venv");
+    assert_eq!(data.metadata["node_source"], ".node-version");
+}
@@ -0,0 +1,123 @@
+use ccstatus::config::{InputData, SegmentId};
+use ccstatus::core::icons::IconSet;
+use ccstatus::core::segments::health::{collect_isolated, failure_placeholder, segment_name};
+use ccstatus::core::segments::{Segment, SegmentData, SegmentFailure, SegmentHealthMap};
+use std::collections::HashMap;
+use std::time::Duration;
+use tempfile::TempDir;
+
+use crate::common::create_test_input_data;
+
+/// Segment with scripted behaviour
+enum Scripted {
+    Ok,
+    Panic,
+    Hang,
+}
+
+impl Segment for Scripted {
+    fn collect(&self, _input: &InputData) -> Option<SegmentData> {
+        match self {
+            Scripted::Ok => Some(SegmentData {
+                primary: "main".to_string(),
+                secondary: String::new(),
+                metadata: HashMap::new(),
+            }),
+            Scripted::Panic => panic!("git index corrupted"),
+            Scripted::Hang => {
+                std::thread::sleep(Duration::from_secs(5));
+                None
+            }
+        }
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Git
+    }
+}
+
+fn collect(segment: Scripted) -> Result<Option<SegmentData>, SegmentFailure> {
+    collect_isolated(
+        Box::new(segment),
+        create_test_input_data(),
+        Duration::from_millis(200),
+    )
+}
+
+#[test]
+fn test_collect_isolated_outcomes() {
+    assert_eq!(collect(Scripted::Ok).unwrap().unwrap().primary, "main");
+    assert_eq!(
+        collect(Scripted::Panic).unwrap_err(),
+        SegmentFailure::Panic("git index corrupted".to_string())
+    );
+    assert_eq!(
+        collect(Scripted::Hang).unwrap_err(),
+        SegmentFailure::Timeout(Duration::from_millis(200))
+    );
+}
+
+#[test]
+fn test_release_profile_unwinds() {
+    // With `panic = "abort"` the isolation above only holds in debug builds
+    let manifest: toml::Value = toml::from_str(include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/Cargo.toml"
+    )))
+    .unwrap();
+    assert_eq!(
+        manifest["profile"]["release"]["panic"].as_str(),
+        Some("unwind")
+    );
+}
+
+#[test]
+fn test_failure_placeholder() {
+    let failure = SegmentFailure::Timeout(Duration::from_millis(2000));
+    let data = failure_placeholder(SegmentId::Git, &failure, IconSet::Unicode);
+    assert_eq!(data.primary, "⚠ git");
+    assert_eq!(data.metadata["error"], "timed out after 2000ms");
+    assert_eq!(segment_name(SegmentId::Stopwatch), "stopwatch");
+    assert_eq!(segment_name(SegmentId::Cost), "cost");
+    assert_eq!(segment_name(SegmentId::BurnRate), "burn_rate");
+    assert_eq!(segment_name(SegmentId::Block), "block");
+    assert_eq!(segment_name(SegmentId::Quota), "quota");
+    assert_eq!(segment_name(SegmentId::Project), "project");
+    assert_eq!(segment_name(SegmentId::DiffStat), "diff_stat");
+    assert_eq!(segment_name(SegmentId::Mode), "mode");
+    assert_eq!(segment_name(SegmentId::Host), "host");
+    assert_eq!(segment_name(SegmentId::Env), "env");
+    assert_eq!(segment_name(SegmentId::Version), "version");
+    assert_eq!(segment_name(SegmentId::Compact), "compact");
+}
+
+#[test]
+fn test_health_map_records_failures_and_recovery() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("segment-health.json");
+    let mut health = SegmentHealthMap::load(&path);
+
+    // Healthy segments never dirty the map
+    assert!(!health.record("git", Ok(())));
+    assert!(health.segments.is_empty());
+
+    let failure = SegmentFailure::Panic("boom".to_string());
+    assert!(health.record("git", Err(&failure)));
+    assert!(health.record("git", Err(&failure)));
+    health.save(&path).unwrap();
+
+    let mut health = SegmentHealthMap::load(&path);
+    let git = &health.segments["git"];
+    assert!(!git.healthy);
+    assert_eq!(git.failures, 2);
+    assert_eq!(git.last_error.as_deref(), Some("panicked: boom"));
+    assert!(health
+        .render_report()
+        .contains("git        failing (2 failures)"));
+
+    assert!(health.record("git", Ok(())));
+    assert!(!health.record("git", Ok(())));
+    let git = &health.segments["git"];
+    assert!(git.healthy);
+    assert!(git.last_recovered_at.is_some());
+}
@@ -0,0 +1,93 @@
+use ccstatus::core::segments::host::{HostContext, HostKind};
+use ccstatus::core::segments::{HostSegment, Segment};
+use serde_json::json;
+use std::collections::HashMap;
+
+use crate::common::create_test_input_data;
+
+fn detect(env: &[(&str, &str)], files: &[(&str, &str)]) -> Option<HostContext> {
+    let env: HashMap<String, String> = env
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    let files: HashMap<String, String> = files
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    HostContext::from_sources(
+        |name| env.get(name).cloned(),
+        |path| files.get(path).cloned(),
+    )
+}
+
+#[test]
+fn test_local_sessions_are_not_detected() {
+    assert_eq!(
+        detect(&[("USER", "dev")], &[("/etc/hostname", "laptop\n")]),
+        None
+    );
+
+    let segment = HostSegment::new().with_context(None);
+    assert!(segment.collect(&create_test_input_data()).is_none());
+}
+
+#[test]
+fn test_ssh_and_container_detection() {
+    let ssh = detect(
+        &[
+            ("SSH_CONNECTION", "10.0.0.5 51234 10.0.0.9 22"),
+            ("USER", "dev"),
+        ],
+        &[("/etc/hostname", "buildbox\n")],
+    )
+    .unwrap();
+    assert_eq!(ssh.kind, HostKind::Ssh);
+    assert_eq!(ssh.user.as_deref(), Some("dev"));
+    assert_eq!(ssh.host, "buildbox");
+
+    // Without a hostname source the server address stands in
+    let ssh = detect(&[("SSH_CONNECTION", "10.0.0.5 51234 10.0.0.9 22")], &[]).unwrap();
+    assert_eq!(ssh.host, "10.0.0.9");
+    assert_eq!(ssh.user, None);
+
+    let docker = detect(
+        &[("LOGNAME", "root")],
+        &[("/.dockerenv", ""), ("/etc/hostname", "3f2a9c1b7d4e\n")],
+    )
+    .unwrap();
+    assert_eq!(docker.kind, HostKind::Container);
+    assert_eq!(docker.user.as_deref(), Some("root"));
+    assert_eq!(docker.host, "3f2a9c1b7d4e");
+
+    // A container entered over SSH is still a container
+    let podman = detect(
+        &[("container", "podman"), ("SSH_CONNECTION", "1 2 3 4")],
+        &[("/proc/sys/kernel/hostname", "toolbox")],
+    )
+    .unwrap();
+    assert_eq!(podman.kind, HostKind::Container);
+    assert_eq!(podman.host, "toolbox");
+}
+
+#[test]
+fn test_segment_shows_user_at_host() {
+    let context = HostContext {
+        kind: HostKind::Ssh,
+        user: Some("dev".to_string()),
+        host: "buildbox".to_string(),
+    };
+    let data = HostSegment::new()
+        .with_context(Some(context.clone()))
+        .collect(&create_test_input_data())
+        .unwrap();
+    assert_eq!(data.primary, "dev@buildbox");
+    assert_eq!(data.metadata["kind"], "ssh");
+
+    let mut options = HashMap::new();
+    options.insert("show_user".to_string(), json!(false));
+    let data = HostSegment::from_options(&options)
+        .with_context(Some(context))
+        .collect(&create_test_input_data())
+        .unwrap();
+    assert_eq!(data.primary, "buildbox");
+}
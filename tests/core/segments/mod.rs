@@ -0,0 +1,20 @@
+//! Segment tests
+//!
+//! Tests for statusline segment data collection
+
+pub mod block_tests;
+pub mod burn_rate_tests;
+pub mod compact_tests;
+pub mod cost_tests;
+pub mod custom_tests;
+pub mod diff_stat_tests;
+pub mod directory_tests;
+pub mod env_tests;
+pub mod health_tests;
+pub mod host_tests;
+pub mod mode_tests;
+pub mod model_tests;
+pub mod project_tests;
+pub mod quota_tests;
+pub mod stopwatch_tests;
+pub mod version_tests;
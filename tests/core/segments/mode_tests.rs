@@ -0,0 +1,96 @@
+use ccstatus::config::types::OutputStyle;
+use ccstatus::config::{AnsiColor, Config, InputData, SegmentConfig, SegmentId};
+use ccstatus::core::segments::mode::{mode_colors, permission_label};
+use ccstatus::core::segments::{ModeSegment, Segment};
+use serde_json::json;
+
+use crate::common::create_test_input_data;
+
+fn input(permission_mode: Option<&str>, output_style: Option<&str>) -> InputData {
+    let mut input = create_test_input_data();
+    input.permission_mode = permission_mode.map(str::to_string);
+    input.output_style = output_style.map(|name| OutputStyle {
+        name: name.to_string(),
+    });
+    input
+}
+
+#[test]
+fn test_badge_hidden_in_default_mode() {
+    let segment = ModeSegment::new();
+    assert!(segment.collect(&input(None, None)).is_none());
+    assert!(segment
+        .collect(&input(Some("default"), Some("default")))
+        .is_none());
+
+    let data = segment
+        .collect(&input(Some("acceptEdits"), Some("Explanatory")))
+        .unwrap();
+    assert_eq!(data.primary, "auto-accept · Explanatory");
+    assert_eq!(data.metadata["permission_mode"], "acceptEdits");
+
+    assert_eq!(
+        segment
+            .collect(&input(None, Some("Learning")))
+            .unwrap()
+            .primary,
+        "Learning"
+    );
+    assert_eq!(permission_label("plan"), Some("plan"));
+    assert_eq!(permission_label("bypassPermissions"), Some("bypass"));
+    assert_eq!(permission_label("someFutureMode"), Some("someFutureMode"));
+}
+
+#[test]
+fn test_modes_parse_from_statusline_json() {
+    let input: InputData = serde_json::from_value(json!({
+        "model": {"display_name": "Opus"},
+        "workspace": {"current_dir": "/work"},
+        "transcript_path": "/tmp/t.jsonl",
+        "output_style": {"name": "Explanatory"},
+        "permission_mode": "plan"
+    }))
+    .unwrap();
+    assert_eq!(input.permission_mode.as_deref(), Some("plan"));
+    assert_eq!(input.output_style.unwrap().name, "Explanatory");
+
+    // Older hosts send neither field
+    let input: InputData = serde_json::from_value(json!({
+        "model": {"display_name": "Opus"},
+        "workspace": {"current_dir": "/work"},
+        "transcript_path": "/tmp/t.jsonl"
+    }))
+    .unwrap();
+    assert!(ModeSegment::new().collect(&input).is_none());
+}
+
+#[test]
+fn test_mode_colors_flag_auto_accept_and_bypass() {
+    let base = Config::default().segments[0].clone();
+    let mut config = SegmentConfig {
+        id: SegmentId::Mode,
+        ..base
+    };
+    let segment = ModeSegment::new();
+
+    let data = segment
+        .collect(&input(Some("bypassPermissions"), None))
+        .unwrap();
+    assert_eq!(
+        mode_colors(&config, &data).colors.text,
+        Some(AnsiColor::Color16 { c16: 9 })
+    );
+
+    config
+        .options
+        .insert("accept_edits_color".to_string(), json!({"c256": 208}));
+    let data = segment.collect(&input(Some("acceptEdits"), None)).unwrap();
+    assert_eq!(
+        mode_colors(&config, &data).colors.icon,
+        Some(AnsiColor::Color256 { c256: 208 })
+    );
+
+    // An output style alone keeps the theme colors
+    let data = segment.collect(&input(None, Some("Learning"))).unwrap();
+    assert_eq!(mode_colors(&config, &data).colors.text, config.colors.text);
+}
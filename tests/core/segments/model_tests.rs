@@ -0,0 +1,154 @@
+use ccstatus::config::InputData;
+use ccstatus::core::segments::{ModelDisplay, ModelHint, ModelName, ModelSegment, Segment};
+use serde_json::json;
+use std::collections::HashMap;
+
+use crate::common::create_test_input_data;
+
+fn input(id: &str, display_name: &str) -> InputData {
+    let mut input = create_test_input_data();
+    input.model.id = id.to_string();
+    input.model.display_name = display_name.to_string();
+    input
+}
+
+#[test]
+fn test_aliases_replace_builtin_names() {
+    let mut options = HashMap::new();
+    options.insert(
+        "aliases".to_string(),
+        json!({"claude-sonnet-4": "S4", "Sonnet": "S", "claude-opus-4-5": "O4.5"}),
+    );
+    let segment = ModelSegment::from_options(&options);
+
+    let data = segment
+        .collect(&input("claude-sonnet-4-20250514", "Sonnet 4"))
+        .unwrap();
+    assert_eq!(data.primary, "S4", "the longest matching key wins");
+    assert_eq!(data.secondary, "");
+    assert_eq!(
+        segment.alias("", "Sonnet 3.7"),
+        Some("S"),
+        "display names match without an id, ignoring case"
+    );
+    assert_eq!(
+        segment
+            .collect(&input("", "claude-3-haiku"))
+            .unwrap()
+            .primary,
+        "Haiku 3"
+    );
+}
+
+#[test]
+fn test_model_names_normalize_across_sources() {
+    for (name, family, version) in [
+        ("claude-sonnet-4-20250514", "Sonnet", "4"),
+        ("Sonnet 4", "Sonnet", "4"),
+        ("claude-sonnet-4-5-20250929[1m]", "Sonnet", "4.5"),
+        ("claude-3-5-sonnet-20241022", "Sonnet", "3.5"),
+        ("Claude 3.5 Sonnet", "Sonnet", "3.5"),
+        ("anthropic.claude-3-5-haiku-20241022-v1:0", "Haiku", "3.5"),
+        ("claude-opus-4-1@20250805", "Opus", "4.1"),
+        ("Opus", "Opus", ""),
+    ] {
+        assert_eq!(
+            ModelName::parse(name),
+            Some(ModelName {
+                family,
+                version: version.to_string()
+            }),
+            "{}",
+            name
+        );
+    }
+    assert_eq!(ModelName::parse("gpt-4o"), None);
+}
+
+#[test]
+fn test_display_modes() {
+    let sonnet = input(
+        "claude-sonnet-4-5-20250929",
+        "Sonnet 4.5 (with 1M token context)",
+    );
+    let primary = |segment: ModelSegment| segment.collect(&sonnet).unwrap().primary;
+    assert_eq!(primary(ModelSegment::new()), "Sonnet 4.5");
+    assert_eq!(
+        primary(ModelSegment::new().with_display(ModelDisplay::Short)),
+        "S4.5"
+    );
+    assert_eq!(
+        primary(ModelSegment::new().with_display(ModelDisplay::Long)),
+        "Claude Sonnet 4.5"
+    );
+
+    let mut options = HashMap::new();
+    options.insert("display".to_string(), json!("short"));
+    options.insert("aliases".to_string(), json!({"opus": "Big"}));
+    let segment = ModelSegment::from_options(&options);
+    assert_eq!(
+        segment
+            .collect(&input("", "Claude 3 Haiku"))
+            .unwrap()
+            .primary,
+        "H3"
+    );
+    assert_eq!(
+        segment
+            .collect(&input("claude-opus-4-1", "Opus 4.1"))
+            .unwrap()
+            .primary,
+        "Big",
+        "aliases are shown as written"
+    );
+    assert_eq!(
+        segment
+            .collect(&input("", "my-proxy-model"))
+            .unwrap()
+            .primary,
+        "my-proxy-model",
+        "unknown names pass through"
+    );
+}
+
+#[test]
+fn test_context_hint() {
+    let segment = ModelSegment::new().with_hint(Some(ModelHint::Context));
+    let data = segment
+        .collect(&input("claude-sonnet-4-20250514", "Sonnet 4"))
+        .unwrap();
+    assert_eq!(data.secondary, "200k");
+    assert_eq!(data.metadata["context_tokens"], "200000");
+
+    let data = segment
+        .collect(&input(
+            "claude-sonnet-4-20250514[1m]",
+            "Sonnet 4 (1M context)",
+        ))
+        .unwrap();
+    assert_eq!(data.secondary, "1M");
+}
+
+#[test]
+fn test_pricing_hint_uses_price_table() {
+    let mut options = HashMap::new();
+    options.insert("annotate".to_string(), json!("pricing"));
+    options.insert(
+        "prices".to_string(),
+        json!({"my-proxy-model": {"input": 0.25, "output": 1.5}}),
+    );
+    let segment = ModelSegment::from_options(&options);
+
+    let data = segment
+        .collect(&input("claude-opus-4-5-20251101", "Opus 4.5"))
+        .unwrap();
+    assert_eq!(data.secondary, "$5/$25");
+    assert_eq!(data.metadata["input_price"], "5");
+
+    let data = segment.collect(&input("my-proxy-model", "Proxy")).unwrap();
+    assert_eq!(data.secondary, "$0.25/$1.5");
+
+    // Unknown models get no hint rather than a wrong one
+    let data = segment.collect(&input("", "mystery")).unwrap();
+    assert_eq!(data.secondary, "");
+}
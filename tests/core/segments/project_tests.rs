@@ -0,0 +1,78 @@
+use ccstatus::config::InputData;
+use ccstatus::core::format::NumberFormat;
+use ccstatus::core::segments::project::{
+    project_key, ProjectLedger, ProjectStats, SessionCost, MAX_OPEN_SESSIONS,
+};
+use ccstatus::core::segments::{ProjectSegment, Segment};
+use tempfile::{NamedTempFile, TempDir};
+
+use crate::common::{assistant, input_for, transcript};
+
+fn input_in(file: &NamedTempFile, dir: &str) -> InputData {
+    let mut input = input_for(file);
+    input.workspace.current_dir = dir.to_string();
+    input
+}
+
+#[test]
+fn test_project_stats_archive_oldest_sessions() {
+    let mut project = ProjectStats::default();
+    let session = |at| SessionCost {
+        usd: 1.0,
+        last_activity: at,
+    };
+    assert!(project.record("a", session(100)));
+    assert!(
+        !project.record("a", session(100)),
+        "Same spend is no change"
+    );
+    for i in 0..MAX_OPEN_SESSIONS as i64 {
+        project.record(&format!("s{}", i), session(200 + i));
+    }
+
+    assert_eq!(project.sessions.len(), MAX_OPEN_SESSIONS);
+    assert!(
+        !project.sessions.contains_key("a"),
+        "Oldest session archived"
+    );
+    assert_eq!(project.archived_sessions, 1);
+    assert_eq!(project.session_count(), MAX_OPEN_SESSIONS as u64 + 1);
+    assert_eq!(project.total_usd(), MAX_OPEN_SESSIONS as f64 + 1.0);
+    assert_eq!(project.last_activity, 200 + MAX_OPEN_SESSIONS as i64 - 1);
+}
+
+#[test]
+fn test_project_segment_accumulates_sessions_per_workspace() {
+    let temp_dir = TempDir::new().unwrap();
+    let state_path = temp_dir.path().join("projects.json");
+    let segment = ProjectSegment::new().with_state_path(Some(state_path.clone()));
+
+    let first = transcript(&[assistant("a", "2025-01-22T12:00:00Z")]);
+    let second = transcript(&[
+        assistant("b", "2025-01-23T09:00:00Z"),
+        assistant("c", "2025-01-23T10:00:00Z"),
+    ]);
+    let other = transcript(&[assistant("d", "2025-01-24T10:00:00Z")]);
+
+    let data = segment.collect(&input_in(&first, "/work/api")).unwrap();
+    assert_eq!(data.primary, "$0.02 · 1 session");
+    // Rendering again does not count the session twice
+    segment.collect(&input_in(&first, "/work/api")).unwrap();
+    let data = segment.collect(&input_in(&second, "/work/api")).unwrap();
+    assert_eq!(data.primary, "$0.05 · 2 sessions");
+    assert_eq!(data.metadata["project_key"], project_key("/work/api"));
+
+    let data = segment.collect(&input_in(&other, "/work/web")).unwrap();
+    assert_eq!(data.primary, "$0.02 · 1 session");
+
+    let ledger = ProjectLedger::load(&state_path);
+    let api = ledger.project("/work/api").unwrap();
+    assert_eq!(api.session_count(), 2);
+    assert_eq!(api.path, "/work/api");
+
+    let report = ledger.render_report(&NumberFormat::default());
+    let lines: Vec<_> = report.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].ends_with("/work/web"), "Most recent first");
+    assert!(lines[1].contains("2 sessions  last 2025-01-23 10:00  /work/api"));
+}
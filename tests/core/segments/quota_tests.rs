@@ -0,0 +1,86 @@
+use ccstatus::core::segments::burn_rate::BurnUnit;
+use ccstatus::core::segments::quota::{week_start, QuotaLedger, WeekSpend};
+use ccstatus::core::segments::{QuotaSegment, Segment};
+use chrono::{FixedOffset, Weekday};
+use tempfile::TempDir;
+
+use crate::common::{assistant, at, input_for, transcript};
+
+#[test]
+fn test_week_start_uses_local_midnight_of_reset_day() {
+    // Thursday
+    let now = at("2025-01-23T15:00:00Z");
+    assert_eq!(week_start(now, Weekday::Mon), at("2025-01-20T00:00:00Z"));
+    assert_eq!(week_start(now, Weekday::Thu), at("2025-01-23T00:00:00Z"));
+    assert_eq!(week_start(now, Weekday::Fri), at("2025-01-17T00:00:00Z"));
+
+    // 01:00 Monday in UTC+2 is still Sunday in UTC
+    let utc_plus_two = FixedOffset::east_opt(2 * 3600).unwrap();
+    let now = at("2025-01-19T23:00:00Z").with_timezone(&utc_plus_two);
+    assert_eq!(week_start(now, Weekday::Mon), at("2025-01-19T22:00:00Z"));
+}
+
+#[test]
+fn test_ledger_replaces_session_totals_and_resets_weekly() {
+    let week = at("2025-01-20T00:00:00Z");
+    let spend = WeekSpend {
+        usd: 1.5,
+        tokens: 1000,
+    };
+    let mut ledger = QuotaLedger::default();
+    assert!(ledger.record(week, "a", spend));
+    assert!(!ledger.record(week, "a", spend), "Same total is no change");
+    assert!(ledger.record(week, "b", spend));
+    assert_eq!(ledger.total().tokens, 2000);
+
+    let next_week = at("2025-01-27T00:00:00Z");
+    assert!(ledger.record(next_week, "b", WeekSpend::default()));
+    assert!(ledger.sessions.is_empty());
+    assert_eq!(ledger.week_start, Some(next_week));
+}
+
+#[test]
+fn test_quota_segment_sums_sessions_against_budget() {
+    let temp_dir = TempDir::new().unwrap();
+    let state_path = temp_dir.path().join("weekly-usage.json");
+    let now = at("2025-01-23T12:00:00Z");
+    let segment = |budget| {
+        QuotaSegment::new()
+            .with_budget(budget)
+            .with_state_path(Some(state_path.clone()))
+            .with_now(now)
+    };
+
+    let first = transcript(&[
+        // Last week's message is not counted
+        assistant("old", "2025-01-16T12:00:00Z"),
+        assistant("a", "2025-01-21T12:00:00Z"),
+        assistant("b", "2025-01-22T12:00:00Z"),
+    ]);
+    let second = transcript(&[assistant("c", "2025-01-23T09:00:00Z")]);
+
+    let data = segment(None).collect(&input_for(&first)).unwrap();
+    assert_eq!(data.primary, "$0.04 wk");
+    // Rendering again does not count the session twice
+    segment(None).collect(&input_for(&first)).unwrap();
+
+    let data = segment(Some(0.06)).collect(&input_for(&second)).unwrap();
+    assert_eq!(data.primary, "$0.05/$0.06 wk");
+    assert_eq!(data.metadata["sessions"], "2");
+    assert_eq!(data.metadata["percent"], "90.0");
+    assert_eq!(data.metadata["level"], "warning");
+
+    let tokens = segment(Some(4000.0))
+        .with_unit(BurnUnit::Tokens)
+        .collect(&input_for(&second))
+        .unwrap();
+    assert_eq!(tokens.primary, "6.0k/4.0k tok wk");
+    assert_eq!(tokens.metadata["level"], "critical");
+
+    // A new week starts from zero
+    let next_week = QuotaSegment::new()
+        .with_state_path(Some(state_path.clone()))
+        .with_now(at("2025-01-29T12:00:00Z"));
+    assert!(next_week.collect(&input_for(&second)).is_none());
+    assert!(QuotaLedger::load(&state_path).sessions.is_empty());
+}
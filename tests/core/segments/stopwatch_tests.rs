@@ -0,0 +1,91 @@
+use ccstatus::core::segments::stopwatch::in_flight_turn_started_at;
+use ccstatus::core::segments::{Segment, StopwatchSegment};
+
+use crate::common::{at, input_for, transcript};
+
+const PROMPT: &str = r#"{"type":"user","timestamp":"2025-01-25T18:00:00.000Z","message":{"role":"user","content":"refactor the parser"}}"#;
+const TOOL_CALL: &str = r#"{"type":"assistant","timestamp":"2025-01-25T18:00:05.000Z","message":{"role":"assistant","stop_reason":"tool_use","content":[{"type":"tool_use","id":"t1"}]}}"#;
+const TOOL_RESULT: &str = r#"{"type":"user","timestamp":"2025-01-25T18:00:09.000Z","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"t1"}]}}"#;
+const FINAL_ANSWER: &str = r#"{"type":"assistant","timestamp":"2025-01-25T18:01:00.000Z","message":{"role":"assistant","stop_reason":"end_turn","content":[{"type":"text","text":"done"}]}}"#;
+const INTERRUPT: &str = r#"{"type":"user","timestamp":"2025-01-25T18:00:30.000Z","message":{"role":"user","content":[{"type":"text","text":"[Request interrupted by user for tool use]"}]}}"#;
+
+#[test]
+fn test_in_flight_turn_starts_at_prompt() {
+    // Waiting for the first token
+    let file = transcript(&[PROMPT]);
+    assert_eq!(
+        in_flight_turn_started_at(file.path()),
+        Some(at("2025-01-25T18:00:00Z"))
+    );
+
+    // Tool round trips stay in the same turn
+    let file = transcript(&[PROMPT, TOOL_CALL, TOOL_RESULT]);
+    assert_eq!(
+        in_flight_turn_started_at(file.path()),
+        Some(at("2025-01-25T18:00:00Z"))
+    );
+}
+
+#[test]
+fn test_completed_or_interrupted_turn_has_no_stopwatch() {
+    let file = transcript(&[PROMPT, TOOL_CALL, TOOL_RESULT, FINAL_ANSWER]);
+    assert_eq!(in_flight_turn_started_at(file.path()), None);
+
+    let file = transcript(&[PROMPT, TOOL_CALL, INTERRUPT]);
+    assert_eq!(in_flight_turn_started_at(file.path()), None);
+
+    assert_eq!(
+        in_flight_turn_started_at("/nonexistent/transcript.jsonl"),
+        None
+    );
+}
+
+#[test]
+fn test_long_turn_is_found_across_chunks_without_reading_earlier_turns() {
+    use std::io::Write;
+
+    // Tool output far larger than one read chunk, with the prompt at the very start
+    let output = format!(
+        r#"{{"type":"user","timestamp":"2025-01-25T18:00:09.000Z","message":{{"role":"user","content":[{{"type":"tool_result","tool_use_id":"t1","content":"{}"}}]}}}}"#,
+        "x".repeat(200 * 1024)
+    );
+    let file = transcript(&[PROMPT, TOOL_CALL, output.as_str(), TOOL_CALL]);
+    assert_eq!(
+        in_flight_turn_started_at(file.path()),
+        Some(at("2025-01-25T18:00:00Z"))
+    );
+
+    // An earlier turn that is not valid UTF-8 is never reached
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(b"\xff\xfe not a transcript line\n").unwrap();
+    writeln!(file, "{}\n{}\n{}", PROMPT, TOOL_CALL, TOOL_RESULT).unwrap();
+    assert_eq!(
+        in_flight_turn_started_at(file.path()),
+        Some(at("2025-01-25T18:00:00Z"))
+    );
+}
+
+#[test]
+fn test_stopwatch_segment_renders_elapsed() {
+    let file = transcript(&[PROMPT, TOOL_CALL]);
+    let data = StopwatchSegment::new()
+        .with_now(at("2025-01-25T18:03:07Z"))
+        .collect(&input_for(&file))
+        .unwrap();
+    assert_eq!(data.primary, "3m 07s");
+    assert_eq!(data.metadata["elapsed_secs"], "187");
+
+    // Abandoned turns (e.g. Claude Code killed mid-turn) are hidden
+    let stale = StopwatchSegment::new()
+        .with_now(at("2025-01-26T06:00:00Z"))
+        .collect(&input_for(&file));
+    assert!(stale.is_none());
+}
+
+#[test]
+fn test_format_elapsed() {
+    assert_eq!(StopwatchSegment::format_elapsed(42), "42s");
+    assert_eq!(StopwatchSegment::format_elapsed(187), "3m 07s");
+    assert_eq!(StopwatchSegment::format_elapsed(3720), "1h 02m");
+    assert_eq!(StopwatchSegment::format_elapsed(-5), "0s");
+}
@@ -0,0 +1,78 @@
+use ccstatus::config::{AnsiColor, Config, SegmentConfig, SegmentId};
+use ccstatus::core::segments::version::{compare_versions, outdated_colors};
+use ccstatus::core::segments::{Segment, VersionSegment};
+use serde_json::json;
+use std::cmp::Ordering;
+
+use crate::common::create_test_input_data;
+
+#[test]
+fn test_shows_client_and_ccstatus_versions() {
+    let data = VersionSegment::new()
+        .with_client_version(Some("1.0.80".to_string()))
+        .with_current_version("2.2.7")
+        .with_latest_version(Some("2.2.7".to_string()))
+        .collect(&create_test_input_data())
+        .unwrap();
+    assert_eq!(data.primary, "CC 1.0.80 · ccstatus 2.2.7");
+    assert_eq!(data.secondary, "");
+    assert_eq!(data.metadata["claude_code"], "1.0.80");
+    assert!(!data.metadata.contains_key("outdated"));
+
+    // Without the full stdin payload only ccstatus is shown
+    let data = VersionSegment::new()
+        .with_client_version(Some(String::new()))
+        .with_current_version("2.2.7")
+        .collect(&create_test_input_data())
+        .unwrap();
+    assert_eq!(data.primary, "ccstatus 2.2.7");
+}
+
+#[test]
+fn test_outdated_binary_is_highlighted() {
+    let segment = VersionSegment::new()
+        .with_current_version("2.2.7")
+        .with_latest_version(Some("2.10.0".to_string()));
+    assert_eq!(segment.newer_release(), Some("2.10.0"));
+    let data = segment.collect(&create_test_input_data()).unwrap();
+    assert_eq!(data.secondary, "↑2.10.0");
+    assert_eq!(data.metadata["latest"], "2.10.0");
+
+    let base = Config::default().segments[0].clone();
+    let mut config = SegmentConfig {
+        id: SegmentId::Version,
+        ..base
+    };
+    assert_eq!(
+        outdated_colors(&config, &data).colors.text,
+        Some(AnsiColor::Color16 { c16: 11 })
+    );
+    config
+        .options
+        .insert("outdated_color".to_string(), json!({"c256": 208}));
+    assert_eq!(
+        outdated_colors(&config, &data).colors.icon,
+        Some(AnsiColor::Color256 { c256: 208 })
+    );
+
+    // A manifest older than a development build is no reason to highlight
+    let segment = VersionSegment::new()
+        .with_current_version("2.3.0")
+        .with_latest_version(Some("2.2.7".to_string()));
+    assert_eq!(segment.newer_release(), None);
+    let data = segment.collect(&create_test_input_data()).unwrap();
+    assert_eq!(
+        outdated_colors(&config, &data).colors.text,
+        config.colors.text
+    );
+}
+
+#[test]
+fn test_compare_versions() {
+    assert_eq!(compare_versions("2.10.0", "2.9.1"), Ordering::Greater);
+    assert_eq!(compare_versions("v2.2.7", "2.2.7"), Ordering::Equal);
+    assert_eq!(compare_versions("2.3", "2.3.0"), Ordering::Equal);
+    assert_eq!(compare_versions("2.3.0-beta.1", "2.3.0"), Ordering::Less);
+    assert_eq!(compare_versions("2.3.0", "2.2.9-rc.1"), Ordering::Greater);
+    assert_eq!(compare_versions("2.2.7+build.5", "2.2.7"), Ordering::Equal);
+}
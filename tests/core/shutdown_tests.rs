@@ -0,0 +1,92 @@
+use ccstatus::core::shutdown::{FileLock, ShutdownCoordinator, ShutdownReason};
+use ccstatus::runtime;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::TempDir;
+
+#[test]
+fn test_file_lock_is_exclusive_and_removed_on_drop() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("rotation.lock");
+    let coordinator = ShutdownCoordinator::new();
+
+    let lock = FileLock::try_acquire(&coordinator, &path).unwrap().unwrap();
+    assert!(path.exists());
+    assert!(FileLock::try_acquire(&coordinator, &path)
+        .unwrap()
+        .is_none());
+
+    drop(lock);
+    assert!(!path.exists());
+
+    // Nothing left for shutdown to release
+    let report = runtime::block_on(coordinator.shutdown(Duration::from_millis(10)));
+    assert_eq!(report.locks_released, 0);
+}
+
+#[test]
+fn test_shutdown_releases_held_locks() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("rotation.lock");
+    let coordinator = ShutdownCoordinator::new();
+
+    let lock = FileLock::try_acquire(&coordinator, &path).unwrap().unwrap();
+    let report = runtime::block_on(coordinator.shutdown(Duration::from_millis(10)));
+    assert_eq!(report.locks_released, 1);
+    assert!(!path.exists());
+
+    // A later holder's file survives the stale guard being dropped
+    std::fs::write(&path, "").unwrap();
+    drop(lock);
+    assert!(path.exists());
+}
+
+#[test]
+fn test_shutdown_waits_for_in_flight_writes() {
+    let coordinator = ShutdownCoordinator::new();
+    let write = coordinator.begin_write();
+    assert_eq!(coordinator.writes_in_flight(), 1);
+
+    let finish_write = async move {
+        runtime::sleep(Duration::from_millis(20)).await;
+        drop(write);
+    };
+    let (report, ()) = runtime::block_on(async {
+        futures::join!(coordinator.shutdown(Duration::from_secs(5)), finish_write)
+    });
+
+    assert!(report.drained);
+    assert_eq!(coordinator.writes_in_flight(), 0);
+}
+
+#[test]
+fn test_shutdown_gives_up_after_grace_period() {
+    let coordinator = ShutdownCoordinator::new();
+    let _write = coordinator.begin_write();
+
+    let report = runtime::block_on(coordinator.shutdown(Duration::from_millis(20)));
+    assert!(!report.drained);
+}
+
+#[test]
+fn test_hooks_run_once_after_request() {
+    let coordinator = ShutdownCoordinator::new();
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    coordinator.on_shutdown(move || {
+        counter.fetch_add(1, Ordering::SeqCst);
+    });
+
+    coordinator.request(ShutdownReason::Signal);
+    runtime::block_on(coordinator.requested());
+    assert!(coordinator.is_requested());
+
+    let report = runtime::block_on(coordinator.shutdown(Duration::from_millis(10)));
+    assert_eq!(report.hooks_run, 1);
+    // The signal that started shutdown stays the recorded reason
+    assert_eq!(coordinator.reason(), Some(ShutdownReason::Signal));
+
+    runtime::block_on(coordinator.shutdown(Duration::from_millis(10)));
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
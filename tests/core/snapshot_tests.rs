@@ -0,0 +1,118 @@
+use ccstatus::config::{Config, SegmentConfig, SegmentId};
+use ccstatus::core::segments::SegmentData;
+use ccstatus::core::snapshot::{input_from_json, HookSnapshot, SCHEMA_VERSION};
+use serde_json::json;
+use std::collections::HashMap;
+
+fn segment(
+    id: SegmentId,
+    primary: &str,
+    metadata: &[(&str, &str)],
+) -> (SegmentConfig, SegmentData) {
+    let base = Config::default().segments[0].clone();
+    (
+        SegmentConfig { id, ..base },
+        SegmentData {
+            primary: primary.to_string(),
+            secondary: String::new(),
+            metadata: metadata
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect::<HashMap<_, _>>(),
+        },
+    )
+}
+
+#[test]
+fn test_segments_keyed_by_name_without_ansi() {
+    let snapshot = HookSnapshot::new(&[
+        segment(SegmentId::Git, "\x1b[32mmain\x1b[0m", &[("branch", "main")]),
+        segment(SegmentId::Custom, "21°C", &[("name", "weather")]),
+        segment(SegmentId::Model, "", &[]),
+    ]);
+
+    assert_eq!(snapshot.schema, SCHEMA_VERSION);
+    assert_eq!(
+        snapshot.segments.keys().collect::<Vec<_>>(),
+        vec!["custom.weather", "git"],
+        "Empty segments are left out"
+    );
+    assert_eq!(snapshot.segments["git"].primary, "main");
+    assert_eq!(snapshot.segments["git"].metadata["branch"], "main");
+}
+
+#[test]
+fn test_hook_json_is_one_line_with_stable_keys() {
+    let snapshot = HookSnapshot::new(&[segment(SegmentId::Directory, "crate", &[])])
+        .with_update(Some("v2.3.0".to_string()));
+    let line = snapshot.to_json(true);
+    assert!(!line.contains('\n'));
+    assert!(!line.contains('\x1b'));
+
+    let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+    assert_eq!(value["update"], "2.3.0");
+    assert_eq!(value["segments"]["directory"]["primary"], "crate");
+    for key in ["schema", "version", "segments", "network", "update"] {
+        assert!(value.get(key).is_some(), "missing key {}", key);
+    }
+
+    assert!(HookSnapshot::new(&[])
+        .to_json(false)
+        .contains("\n  \"network\": null"));
+}
+
+#[cfg(feature = "network-monitoring")]
+#[test]
+fn test_network_block_from_persisted_state() {
+    use ccstatus::core::network::types::{MonitoringSnapshot, NetworkStatus};
+
+    let mut state = MonitoringSnapshot {
+        status: NetworkStatus::Error,
+        timestamp: "2025-01-25T10:30:00-08:00".to_string(),
+        ..Default::default()
+    };
+    state.network.last_http_status = 529;
+    state.network.latency_ms = 1200;
+    state.network.error_type = Some("overloaded_error".to_string());
+
+    let value: serde_json::Value =
+        serde_json::from_str(&HookSnapshot::new(&[]).with_network(&state).to_json(true)).unwrap();
+    assert_eq!(
+        value["network"],
+        json!({
+            "status": "error",
+            "http_status": 529,
+            "latency_ms": 1200,
+            "p95_latency_ms": 0,
+            "error_type": "overloaded_error",
+            "updated_at": "2025-01-25T10:30:00-08:00",
+        })
+    );
+}
+
+#[test]
+fn test_input_from_hook_and_statusline_json() {
+    let hook = input_from_json(&json!({
+        "session_id": "abc",
+        "cwd": "/work/project",
+        "transcript_path": "/tmp/t.jsonl",
+        "hook_event_name": "Stop",
+    }));
+    assert_eq!(hook.workspace.current_dir, "/work/project");
+    assert_eq!(hook.transcript_path, "/tmp/t.jsonl");
+    assert_eq!(hook.model.display_name, "");
+
+    let statusline = input_from_json(&json!({
+        "cwd": "/ignored",
+        "workspace": {"current_dir": "/work/other"},
+        "model": {"display_name": "Opus"},
+    }));
+    assert_eq!(statusline.workspace.current_dir, "/work/other");
+    assert_eq!(statusline.model.display_name, "Opus");
+
+    let empty = input_from_json(&serde_json::Value::Null);
+    assert!(
+        !empty.workspace.current_dir.is_empty(),
+        "Falls back to the current directory"
+    );
+}
@@ -0,0 +1,130 @@
+use ccstatus::core::scheduler::JobLedger;
+use ccstatus::core::state_location::{
+    mount_for, parse_mount_output, parse_proc_mounts, Mount, StateLocation, LOCAL_STATE_SYNC_JOB,
+    MONITORING_STATE_FILE,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tempfile::TempDir;
+
+#[test]
+fn test_parse_mounts_and_pick_longest_mount_point() {
+    let proc_mounts = "\
+/dev/sda1 / ext4 rw,relatime 0 0
+server:/export/home /home nfs4 rw,vers=4.2 0 0
+//nas/share /mnt/my\\040share cifs rw 0 0
+tmpfs /home/alice/.cache tmpfs rw 0 0
+";
+    let mounts = parse_proc_mounts(proc_mounts);
+    assert_eq!(mounts.len(), 4);
+    assert_eq!(mounts[2].mount_point, PathBuf::from("/mnt/my share"));
+
+    let home = mount_for(Path::new("/home/alice/.claude/ccstatus"), &mounts).unwrap();
+    assert_eq!(home.fstype, "nfs4");
+    assert!(home.is_network());
+    let cache = mount_for(Path::new("/home/alice/.cache/ccstatus"), &mounts).unwrap();
+    assert_eq!(cache.fstype, "tmpfs");
+    assert!(!cache.is_network());
+    // `/homework` is not under `/home`
+    assert_eq!(
+        mount_for(Path::new("/homework"), &mounts).unwrap().fstype,
+        "ext4"
+    );
+
+    let macos = "\
+/dev/disk3s1s1 on / (apfs, sealed, local, read-only, journaled)
+//alice@nas/home on /Volumes/home dir (smbfs, nodev, nosuid, mounted by alice)
+";
+    let mounts = parse_mount_output(macos);
+    assert_eq!(
+        mounts[1],
+        Mount {
+            mount_point: PathBuf::from("/Volumes/home dir"),
+            fstype: "smbfs".to_string(),
+        }
+    );
+    assert!(mounts[1].is_network());
+    assert!(!mounts[0].is_network());
+}
+
+#[test]
+fn test_local_state_seeds_from_home_and_syncs_back() {
+    let temp = TempDir::new().unwrap();
+    let home = temp.path().join("home");
+    let local = temp.path().join("local");
+    fs::create_dir_all(home.join("targets")).unwrap();
+    fs::write(home.join(MONITORING_STATE_FILE), "{\"home\":1}").unwrap();
+    fs::write(home.join("targets").join("relay.json"), "{}").unwrap();
+    fs::write(home.join("config.toml"), "").unwrap();
+
+    let plain = StateLocation::new(home.clone(), None);
+    assert_eq!(plain.monitoring_path(), home.join(MONITORING_STATE_FILE));
+    assert_eq!(plain.sync_back().unwrap(), 0);
+
+    let location = StateLocation::new(home.clone(), Some(local.clone()));
+    assert_eq!(
+        location.monitoring_path(),
+        local.join(MONITORING_STATE_FILE)
+    );
+    assert_eq!(
+        location.target_path("relay"),
+        local.join("targets").join("relay.json")
+    );
+
+    assert_eq!(
+        location.seed_local().unwrap(),
+        2,
+        "State and target, not config"
+    );
+    assert!(!local.join("config.toml").exists());
+    assert_eq!(location.seed_local().unwrap(), 0, "Seeds only once");
+
+    fs::write(local.join(MONITORING_STATE_FILE), "{\"local\":2}").unwrap();
+    fs::write(local.join("ccstatus-monitoring.tmp"), "half").unwrap();
+    let ledger = home.join("scheduler.json");
+    let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+    assert_eq!(location.sync_back_at(&ledger, now).unwrap().unwrap(), 2);
+    assert_eq!(
+        fs::read_to_string(home.join(MONITORING_STATE_FILE)).unwrap(),
+        "{\"local\":2}"
+    );
+    assert!(!home.join("ccstatus-monitoring.tmp").exists());
+    assert!(JobLedger::load(&ledger)
+        .last_run
+        .contains_key(LOCAL_STATE_SYNC_JOB));
+
+    // Not due again within the interval
+    assert!(location
+        .sync_back_at(&ledger, now + Duration::from_secs(60))
+        .is_none());
+    assert!(location
+        .sync_back_at(&ledger, now + Duration::from_secs(300))
+        .is_some());
+}
+
+#[test]
+fn test_render_report_warns_on_network_home() {
+    let nfs = Mount {
+        mount_point: PathBuf::from("/home"),
+        fstype: "nfs4".to_string(),
+    };
+    let ext4 = Mount {
+        mount_point: PathBuf::from("/"),
+        fstype: "ext4".to_string(),
+    };
+
+    let plain = StateLocation::new(PathBuf::from("/home/alice/.claude/ccstatus"), None);
+    let report = plain.render_report(Some(&nfs), None);
+    assert!(report.contains("nfs4, network"));
+    assert!(report.contains("local_state_dir"));
+    assert!(!plain.render_report(Some(&ext4), None).contains("Warning"));
+
+    let local = StateLocation::new(
+        PathBuf::from("/home/alice/.claude/ccstatus"),
+        Some(PathBuf::from("/var/tmp/ccstatus")),
+    );
+    let report = local.render_report(Some(&nfs), Some(&ext4));
+    assert!(report.contains("Local: /var/tmp/ccstatus (ext4)"));
+    assert!(!report.contains("Warning"));
+}
@@ -0,0 +1,208 @@
+use ccstatus::core::stats::{
+    ewma, percent_change, percentile, percentile_sorted, percentiles, trend, Histogram,
+    LatencySketch, TrendDirection, LATENCY_BUCKETS_MS, MIN_TREND_SAMPLES, SKETCH_EXACT_SAMPLES,
+    SKETCH_RELATIVE_ACCURACY, TREND_THRESHOLD_PERCENT,
+};
+
+const CASES: usize = 500;
+
+/// Random sample windows from a fixed seed, so failures reproduce
+fn random_windows(seed: u64) -> impl Iterator<Item = Vec<u32>> {
+    let mut rng = fastrand::Rng::with_seed(seed);
+    (0..CASES).map(move |_| {
+        let len = rng.usize(0..=64);
+        (0..len).map(|_| rng.u32(0..10_000)).collect()
+    })
+}
+
+#[test]
+fn test_percentile_nearest_rank_examples() {
+    let samples = [100, 200, 300, 400, 500, 600, 700, 800, 900, 1000];
+    assert_eq!(percentile(&samples, 0.95), 1000);
+    assert_eq!(percentile(&samples, 0.80), 800);
+    assert_eq!(percentile(&samples, 0.50), 500);
+    assert_eq!(percentile(&[3000, 1000, 2000], 0.50), 2000);
+    assert_eq!(percentile(&[], 0.95), 0);
+    assert_eq!(percentile(&[42], 0.0), 42);
+    assert_eq!(percentiles(&samples, [0.95, 0.80]), [1000, 800]);
+}
+
+#[test]
+fn test_percentile_properties() {
+    for samples in random_windows(1) {
+        let mut sorted = samples.clone();
+        sorted.sort_unstable();
+
+        let mut previous = 0;
+        for step in 0..=20 {
+            let p = step as f64 / 20.0;
+            let value = percentile(&samples, p);
+            assert_eq!(value, percentile_sorted(&sorted, p));
+            if samples.is_empty() {
+                assert_eq!(value, 0);
+                continue;
+            }
+            // An observed sample, never decreasing as p grows
+            assert!(samples.contains(&value), "{} not in {:?}", value, samples);
+            assert!(value >= previous, "p={} went down in {:?}", p, samples);
+            previous = value;
+
+            // Nearest rank: at least p of the samples are at or below it
+            let at_or_below = sorted.iter().filter(|&&s| s <= value).count();
+            assert!(at_or_below as f64 >= p * sorted.len() as f64);
+        }
+        if let (Some(min), Some(max)) = (sorted.first(), sorted.last()) {
+            assert_eq!(percentile(&samples, 0.0), *min);
+            assert_eq!(percentile(&samples, 1.0), *max);
+        }
+    }
+}
+
+#[test]
+fn test_ewma_properties() {
+    assert_eq!(ewma(&[], 0.3), None);
+    assert_eq!(ewma(&[100, 200], 1.0), Some(200.0));
+    assert_eq!(ewma(&[100, 200], 0.0), Some(100.0));
+    assert_eq!(ewma(&[100, 200], 0.5), Some(150.0));
+
+    let mut rng = fastrand::Rng::with_seed(2);
+    for samples in random_windows(3).filter(|s| !s.is_empty()) {
+        let alpha = rng.f64();
+        let average = ewma(&samples, alpha).unwrap();
+        let min = *samples.iter().min().unwrap() as f64;
+        let max = *samples.iter().max().unwrap() as f64;
+        assert!(
+            average >= min - 1e-6 && average <= max + 1e-6,
+            "{} outside {}..={}",
+            average,
+            min,
+            max
+        );
+    }
+}
+
+#[test]
+fn test_percent_change() {
+    assert_eq!(percent_change(1000.0, 1200.0), 20.0);
+    assert_eq!(percent_change(1000.0, 500.0), -50.0);
+    assert_eq!(percent_change(0.0, 500.0), 0.0);
+}
+
+#[test]
+fn test_trend_direction() {
+    assert_eq!(trend(&[100, 200, 300], TREND_THRESHOLD_PERCENT), None);
+
+    let rising = trend(
+        &[1000, 1000, 1000, 1500, 1500, 1500],
+        TREND_THRESHOLD_PERCENT,
+    )
+    .unwrap();
+    assert_eq!(rising.direction, TrendDirection::Rising);
+    assert_eq!(rising.change_percent, 50.0);
+
+    let falling = trend(&[2000, 2000, 1000, 1000], TREND_THRESHOLD_PERCENT).unwrap();
+    assert_eq!(falling.direction, TrendDirection::Falling);
+
+    let steady = trend(&[1000, 1050, 1020, 1030], TREND_THRESHOLD_PERCENT).unwrap();
+    assert_eq!(steady.direction, TrendDirection::Steady);
+}
+
+#[test]
+fn test_trend_properties() {
+    for samples in random_windows(4) {
+        let Some(forward) = trend(&samples, TREND_THRESHOLD_PERCENT) else {
+            assert!(samples.len() < MIN_TREND_SAMPLES);
+            continue;
+        };
+        // A constant window never trends
+        let flat = vec![samples[0]; samples.len()];
+        assert_eq!(
+            trend(&flat, TREND_THRESHOLD_PERCENT).unwrap().direction,
+            TrendDirection::Steady
+        );
+        // Direction agrees with the sign of the change
+        match forward.direction {
+            TrendDirection::Rising => assert!(forward.change_percent > TREND_THRESHOLD_PERCENT),
+            TrendDirection::Falling => assert!(forward.change_percent < -TREND_THRESHOLD_PERCENT),
+            TrendDirection::Steady => {
+                assert!(forward.change_percent.abs() <= TREND_THRESHOLD_PERCENT)
+            }
+        }
+    }
+}
+
+#[test]
+fn test_histogram_buckets() {
+    let histogram = Histogram::new(&LATENCY_BUCKETS_MS, &[120, 500, 501, 1800, 6000, 7000]);
+    assert_eq!(histogram.counts, vec![2, 1, 1, 0, 2]);
+    assert_eq!(histogram.render(), "≤500ms:2 ≤1000ms:1 ≤2000ms:1 >5000ms:2");
+
+    let unsorted = Histogram::new(&[1000, 500, 1000], &[700]);
+    assert_eq!(unsorted.bounds, vec![500, 1000]);
+    assert_eq!(unsorted.counts, vec![0, 1, 0]);
+}
+
+#[test]
+fn test_histogram_properties() {
+    for samples in random_windows(5) {
+        let histogram = Histogram::new(&LATENCY_BUCKETS_MS, &samples);
+        assert_eq!(histogram.total(), samples.len());
+        assert_eq!(histogram.counts.len(), LATENCY_BUCKETS_MS.len() + 1);
+        let at_or_below_first = samples
+            .iter()
+            .filter(|&&s| s <= LATENCY_BUCKETS_MS[0])
+            .count();
+        assert_eq!(histogram.counts[0], at_or_below_first);
+    }
+}
+
+#[test]
+fn test_sketch_is_exact_for_small_histories() {
+    for samples in random_windows(6) {
+        let sketch: LatencySketch = samples.iter().copied().collect();
+        assert!(sketch.is_exact());
+        assert_eq!(sketch.count(), samples.len() as u64);
+        for step in 0..=20 {
+            let p = step as f64 / 20.0;
+            assert_eq!(sketch.percentile(p), percentile(&samples, p));
+        }
+    }
+}
+
+#[test]
+fn test_sketch_stays_within_relative_accuracy() {
+    let mut rng = fastrand::Rng::with_seed(7);
+    for _ in 0..20 {
+        // Long histories with a heavy tail and some 0ms (connection error) samples
+        let len = rng.usize(SKETCH_EXACT_SAMPLES + 1..5_000);
+        let samples: Vec<u32> = (0..len)
+            .map(|_| match rng.u8(0..20) {
+                0 => 0,
+                1 => rng.u32(5_000..60_000),
+                _ => rng.u32(80..3_000),
+            })
+            .collect();
+
+        // Built in two halves and merged, which must not lose anything
+        let (left, right) = samples.split_at(len / 3);
+        let mut sketch: LatencySketch = left.iter().copied().collect();
+        sketch.merge(&right.iter().copied().collect());
+        assert!(!sketch.is_exact());
+        assert_eq!(sketch.count(), len as u64);
+
+        let mut sorted = samples.clone();
+        sorted.sort_unstable();
+        for p in [0.0, 0.05, 0.5, 0.8, 0.95, 0.99, 1.0] {
+            let exact = percentile_sorted(&sorted, p) as f64;
+            let estimate = sketch.percentile(p) as f64;
+            assert!(
+                (estimate - exact).abs() <= exact * SKETCH_RELATIVE_ACCURACY + 1.0,
+                "p{} of {} samples: {} vs exact {}",
+                p,
+                len,
+                estimate,
+                exact
+            );
+        }
+    }
+}
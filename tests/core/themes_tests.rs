@@ -0,0 +1,121 @@
+use ccstatus::config::{AnsiColor, ColorConfig, Config, SegmentConfig, SegmentId};
+use ccstatus::core::segments::SegmentData;
+use ccstatus::core::statusline::themes::{resolve_colors, ColorScheme};
+use ccstatus::core::StatusLineGenerator;
+use std::collections::HashMap;
+
+fn segment(config: &Config, id: SegmentId) -> (SegmentConfig, SegmentData) {
+    let segment_config = config
+        .segments
+        .iter()
+        .find(|s| s.id == id)
+        .cloned()
+        .unwrap();
+    (
+        segment_config,
+        SegmentData {
+            primary: "text".to_string(),
+            secondary: String::new(),
+            metadata: HashMap::new(),
+        },
+    )
+}
+
+#[test]
+fn test_scheme_lookup_by_theme_name() {
+    assert_eq!(ColorScheme::from_name("nord"), Some(ColorScheme::Nord));
+    assert_eq!(
+        ColorScheme::from_name(" Solarized "),
+        Some(ColorScheme::Solarized)
+    );
+    assert_eq!(ColorScheme::from_name("default"), None);
+    assert_eq!(ColorScheme::from_name("powerline-dark"), None);
+    for scheme in ColorScheme::ALL {
+        assert_eq!(ColorScheme::from_name(scheme.name()), Some(scheme));
+    }
+}
+
+#[test]
+fn test_scheme_replaces_segment_colors_and_overrides_apply_on_top() {
+    let mut config = Config::default();
+    config.colors.insert(
+        SegmentId::Git,
+        ColorConfig {
+            icon: None,
+            text: Some(AnsiColor::Color256 { c256: 34 }),
+            background: None,
+        },
+    );
+
+    let (mut git, _) = segment(&config, SegmentId::Git);
+    resolve_colors(&config, Some(ColorScheme::Solarized), &mut git);
+    assert_eq!(
+        git.colors.icon,
+        Some(AnsiColor::Rgb {
+            r: 133,
+            g: 153,
+            b: 0
+        })
+    );
+    assert_eq!(git.colors.text, Some(AnsiColor::Color256 { c256: 34 }));
+
+    // Without a scheme, overrides still apply to the segment's own colors
+    let (mut git, _) = segment(&config, SegmentId::Git);
+    let own_icon = git.colors.icon.clone();
+    resolve_colors(&config, None, &mut git);
+    assert_eq!(git.colors.icon, own_icon);
+    assert_eq!(git.colors.text, Some(AnsiColor::Color256 { c256: 34 }));
+}
+
+#[test]
+fn test_monochrome_emits_no_color_codes() {
+    let config = Config {
+        theme: "monochrome".to_string(),
+        ..Default::default()
+    };
+    let segments = vec![
+        segment(&config, SegmentId::Model),
+        segment(&config, SegmentId::Directory),
+    ];
+
+    let statusline = StatusLineGenerator::new(config).generate(segments);
+    assert!(!statusline.contains("\x1b["), "{:?}", statusline);
+    assert!(statusline.contains(" | "));
+}
+
+#[test]
+fn test_default_theme_keeps_white_separator() {
+    let config = Config::default();
+    let segments = vec![
+        segment(&config, SegmentId::Model),
+        segment(&config, SegmentId::Directory),
+    ];
+
+    let statusline = StatusLineGenerator::new(config).generate(segments);
+    assert!(statusline.contains("\x1b[37m | \x1b[0m"));
+}
+
+#[test]
+fn test_color_overrides_parse_from_toml() {
+    let mut config = Config {
+        theme: "gruvbox".to_string(),
+        ..Default::default()
+    };
+    config.colors.insert(
+        SegmentId::Model,
+        ColorConfig {
+            icon: None,
+            text: Some(AnsiColor::Rgb { r: 1, g: 2, b: 3 }),
+            background: None,
+        },
+    );
+
+    let toml = toml::to_string(&config).unwrap();
+    assert!(toml.contains("[colors.model"), "{}", toml);
+    let parsed: Config = toml::from_str(&toml).unwrap();
+    assert_eq!(
+        parsed.colors[&SegmentId::Model].text,
+        Some(AnsiColor::Rgb { r: 1, g: 2, b: 3 })
+    );
+    assert_eq!(parsed.colors[&SegmentId::Model].icon, None);
+}
@@ -0,0 +1,47 @@
+#![cfg(feature = "ffi")]
+
+use ccstatus::core::network::MonitoringSnapshot;
+use ccstatus::ffi::*;
+use std::ffi::{CStr, CString};
+
+/// Copy a returned string and release it
+fn take(ptr: *mut std::ffi::c_char) -> Option<String> {
+    if ptr.is_null() {
+        return None;
+    }
+    let text = unsafe { CStr::from_ptr(ptr) }.to_str().unwrap().to_string();
+    unsafe { ccstatus_string_free(ptr) };
+    Some(text)
+}
+
+#[test]
+fn test_abi_version_matches_header_constant() {
+    assert_eq!(ccstatus_abi_version(), CCSTATUS_FFI_ABI_VERSION);
+
+    let header = include_str!("../include/ccstatus.h");
+    assert!(header.contains(&format!(
+        "#define CCSTATUS_FFI_ABI_VERSION {}",
+        CCSTATUS_FFI_ABI_VERSION
+    )));
+}
+
+#[test]
+fn test_render_statusline_rejects_bad_input() {
+    assert!(take(unsafe { ccstatus_render_statusline(std::ptr::null()) }).is_none());
+
+    let invalid = CString::new("{not json").unwrap();
+    assert!(take(unsafe { ccstatus_render_statusline(invalid.as_ptr()) }).is_none());
+}
+
+#[test]
+fn test_read_snapshot_json_parses_as_snapshot() {
+    // Reads the real state file; a missing one yields the default snapshot
+    if let Some(json) = take(ccstatus_read_snapshot_json()) {
+        assert!(serde_json::from_str::<MonitoringSnapshot>(&json).is_ok());
+    }
+}
+
+#[test]
+fn test_string_free_ignores_null() {
+    unsafe { ccstatus_string_free(std::ptr::null_mut()) };
+}
@@ -5,5 +5,10 @@
 //! targeted testing.
 
 mod common;
+mod config;
 mod core;
+#[cfg(feature = "ffi")]
+mod ffi_tests;
+#[cfg(any(feature = "runtime-tokio", feature = "runtime-smol"))]
+mod runtime_tests;
 mod updater;
@@ -0,0 +1,44 @@
+use ccstatus::runtime;
+use std::time::Duration;
+
+#[test]
+fn test_spawn_blocking_returns_closure_result() {
+    let result = runtime::block_on(runtime::spawn_blocking(|| 21 * 2));
+    assert_eq!(result, Ok(42));
+}
+
+#[test]
+fn test_spawn_blocking_reports_panic_as_join_error() {
+    let result = runtime::block_on(runtime::spawn_blocking(|| -> u32 { panic!("boom") }));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_timeout_passes_through_ready_future() {
+    let result = runtime::block_on(runtime::timeout(Duration::from_secs(1), async { "done" }));
+    assert_eq!(result, Ok("done"));
+}
+
+#[test]
+fn test_timeout_elapses_on_slow_future() {
+    let result = runtime::block_on(runtime::timeout(
+        Duration::from_millis(10),
+        runtime::sleep(Duration::from_secs(5)),
+    ));
+    assert_eq!(result, Err(runtime::Elapsed));
+}
+
+#[test]
+fn test_fs_round_trip() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("nested").join("state.json");
+
+    let content = runtime::block_on(async {
+        runtime::fs::create_dir_all(path.parent().unwrap())
+            .await
+            .unwrap();
+        runtime::fs::write(&path, "{}").await.unwrap();
+        runtime::fs::read_to_string(&path).await.unwrap()
+    });
+    assert_eq!(content, "{}");
+}
@@ -138,3 +138,89 @@ fn test_ccstatus_flash_environment_variable() {
     // Cleanup
     std::env::remove_var("CCSTATUS_FLASH");
 }
+
+#[test]
+fn test_help_json_describes_cli_surface() {
+    use ccstatus::cli::{Cli, HELP_JSON_SCHEMA};
+    use clap::Parser;
+
+    assert!(
+        Cli::try_parse_from(vec!["ccstatus", "--help-json"])
+            .unwrap()
+            .help_json
+    );
+
+    let help = Cli::help_json();
+    assert_eq!(help["name"], "ccstatus");
+    assert_eq!(help["schema"], HELP_JSON_SCHEMA);
+
+    let args = help["args"].as_array().unwrap();
+    let format = args.iter().find(|arg| arg["id"] == "format").unwrap();
+    assert_eq!(format["long"], "format");
+    assert_eq!(format["default"][0], "plain");
+    assert!(format["possible_values"]
+        .as_array()
+        .unwrap()
+        .contains(&"json".into()));
+    let update = args.iter().find(|arg| arg["id"] == "update").unwrap();
+    assert_eq!(update["short"], "u");
+    assert_eq!(update["takes_value"], false);
+    assert!(update["possible_values"].as_array().unwrap().is_empty());
+
+    let net = help["subcommands"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|command| command["name"] == "net")
+        .unwrap();
+    let report = net["subcommands"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|command| command["name"] == "report")
+        .unwrap();
+    assert_eq!(report["args"][0]["long"], "compare-versions");
+    assert!(report["about"].as_str().unwrap().starts_with("Summarize"));
+
+    let preview = Cli::try_parse_from(vec!["ccstatus", "preview", "--diff", "candidate.toml"])
+        .unwrap()
+        .command;
+    assert!(matches!(
+        preview,
+        Some(ccstatus::cli::Command::Preview { ref diff, plain: false })
+            if diff.as_os_str() == "candidate.toml"
+    ));
+    assert!(Cli::try_parse_from(vec!["ccstatus", "preview"]).is_err());
+
+    let daemon = Cli::try_parse_from(vec!["ccstatus", "daemon", "--profile", "minimal"])
+        .unwrap()
+        .command;
+    assert!(matches!(
+        daemon,
+        Some(ccstatus::cli::Command::Daemon { profile: Some(ref name) }) if name == "minimal"
+    ));
+}
+
+#[cfg(feature = "self-update")]
+#[test]
+fn test_check_update_refuses_when_config_is_unreadable() {
+    let home = tempfile::tempdir().unwrap();
+    let config_dir = home.path().join(".claude").join("ccstatus");
+    std::fs::create_dir_all(&config_dir).unwrap();
+    std::fs::write(
+        config_dir.join("config.toml"),
+        "[network]\nallowed_hosts = [\"api.anthropic.com\"\n",
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_ccstatus"))
+        .arg("--check-update")
+        .env("HOME", home.path())
+        .stdin(std::process::Stdio::null())
+        .output()
+        .unwrap();
+
+    // A broken allowlist must not fall back to allowing every host
+    assert!(!output.status.success());
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("Update"));
+}
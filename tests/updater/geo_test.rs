@@ -1,10 +1,11 @@
+use ccstatus::config::HostPolicy;
 use ccstatus::updater::geo::detect_china_ttl24h;
 
 #[test]
 fn test_detect_china_ttl24h_returns_boolean() {
     // This test verifies the function returns a valid boolean
     // In CI environments, this should use mocked values and not make real network calls
-    let result = detect_china_ttl24h();
+    let result = detect_china_ttl24h(&HostPolicy::default());
 
     // Result should be boolean (true or false)
     assert!(result == true || result == false);
@@ -16,7 +17,7 @@ fn test_ci_mocking_default_false() {
     std::env::set_var("CI", "true");
     std::env::remove_var("CCSTATUS_TEST_CHINA_GEO");
 
-    let result = detect_china_ttl24h();
+    let result = detect_china_ttl24h(&HostPolicy::default());
     assert_eq!(result, false);
 
     std::env::remove_var("CI");
@@ -28,7 +29,7 @@ fn test_ci_mocking_override_true() {
     std::env::set_var("CI", "true");
     std::env::set_var("CCSTATUS_TEST_CHINA_GEO", "true");
 
-    let result = detect_china_ttl24h();
+    let result = detect_china_ttl24h(&HostPolicy::default());
     assert_eq!(result, true);
 
     std::env::remove_var("CI");
@@ -41,7 +42,7 @@ fn test_ci_mocking_github_actions() {
     std::env::set_var("GITHUB_ACTIONS", "true");
     std::env::remove_var("CCSTATUS_TEST_CHINA_GEO");
 
-    let result = detect_china_ttl24h();
+    let result = detect_china_ttl24h(&HostPolicy::default());
     assert_eq!(result, false);
 
     std::env::remove_var("GITHUB_ACTIONS");
@@ -65,6 +66,6 @@ fn test_detect_china_ttl24h_failure_fallback() {
 
     // We test that the function doesn't panic on network issues
     // by ensuring it always returns a boolean value
-    let result = detect_china_ttl24h();
+    let result = detect_china_ttl24h(&HostPolicy::default());
     assert!(result == true || result == false);
 }
@@ -18,6 +18,28 @@ fn test_manifest_parse_json() {
     assert!(manifest.assets.is_empty());
 }
 
+#[test]
+fn test_manifest_flags_section() {
+    let json = r#"{
+        "version": "2.2.9",
+        "notes_url": "https://github.com/MaurUppi/CCstatus/releases/tag/v2.2.9",
+        "channel": "stable",
+        "published_at": "2025-09-01T00:00:00Z",
+        "flags": {"red_probing": false}
+    }"#;
+
+    let manifest = Manifest::from_json(json).unwrap();
+    let remote = manifest.remote_flags();
+    assert_eq!(remote.flags.0.get("red_probing"), Some(&false));
+    assert_eq!(remote.manifest_version.as_deref(), Some("2.2.9"));
+
+    // Manifests without flags parse as before and serialize without the section
+    let json = manifest.to_json().unwrap();
+    let mut plain = Manifest::from_json(&json).unwrap();
+    plain.flags = Default::default();
+    assert!(!plain.to_json().unwrap().contains("flags"));
+}
+
 #[test]
 fn test_manifest_serialize_json() {
     let manifest = Manifest {
@@ -26,6 +48,7 @@ fn test_manifest_serialize_json() {
         channel: "stable".to_string(),
         published_at: "2025-09-01T00:00:00Z".to_string(),
         assets: vec![],
+        flags: Default::default(),
     };
 
     let json = manifest.to_json().unwrap();
@@ -86,6 +109,7 @@ fn test_persistent_cache_return_values_new_manifest() {
         channel: "stable".to_string(),
         published_at: "2025-09-01T12:00:00Z".to_string(),
         assets: vec![],
+        flags: Default::default(),
     };
 
     // This test documents the expected return pattern
@@ -1,5 +1,6 @@
+use ccstatus::config::HostPolicy;
 use ccstatus::updater::url_resolver::{
-    extract_host_from_url, resolve_manifest_url, try_urls_in_sequence, UrlResolverError,
+    extract_host_from_url, filter_allowed_urls, resolve_manifest_url, try_urls_in_sequence,
 };
 
 #[test]
@@ -251,3 +252,24 @@ fn test_error_context_in_sequential_trying() {
     assert!(error_msg.contains("https://third.com"));
     assert!(error_msg.contains("Connection failed"));
 }
+
+#[test]
+fn test_filter_allowed_urls() {
+    let urls = resolve_manifest_url(true);
+
+    // Empty allowlist keeps every URL in order
+    assert_eq!(
+        filter_allowed_urls(urls.clone(), &HostPolicy::default()),
+        urls
+    );
+
+    let policy = HostPolicy::new(vec!["raw.githubusercontent.com".to_string()]);
+    let filtered = filter_allowed_urls(urls, &policy);
+    assert!(!filtered.is_empty());
+    assert!(filtered
+        .iter()
+        .all(|url| url.starts_with("https://raw.githubusercontent.com/")));
+
+    let policy = HostPolicy::new(vec!["*.corp.example".to_string()]);
+    assert!(filter_allowed_urls(resolve_manifest_url(false), &policy).is_empty());
+}